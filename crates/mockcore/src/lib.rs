@@ -10,7 +10,7 @@ use {
     hash_types::{BlockHash, TxMerkleNode},
     hashes::Hash,
     locktime::LockTime,
-    secp256k1::{self, rand},
+    secp256k1,
     util::address::Address,
     util::amount::SignedAmount,
     util::bip32::{ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey},
@@ -268,6 +268,16 @@ impl Handle {
     self.state().broadcast_tx(template)
   }
 
+  /// Appends `block` to the chain instead of mining one.
+  pub fn push_block(&self, block: Block) {
+    self.state().push_block(block);
+  }
+
+  /// Makes `tx` available to `getrawtransaction` without adding it to a block.
+  pub fn insert_transaction(&self, tx: Transaction) {
+    self.state().transactions.insert(compute_txid(&tx), tx);
+  }
+
   pub fn height(&self) -> u64 {
     u64::try_from(self.state().blocks.len()).unwrap() - 1
   }
@@ -847,8 +847,6 @@ impl Api for Server {
     unlock: bool,
     outputs: Vec<JsonOutPoint>,
   ) -> Result<bool, jsonrpc_core::Error> {
-    assert!(!unlock);
-
     let mut state = self.state();
 
     if state.fail_lock_unspent {
@@ -860,7 +858,11 @@ impl Api for Server {
         vout: output.vout,
         txid: output.txid,
       };
-      assert!(state.locked.insert(output));
+      if unlock {
+        state.locked.remove(&output);
+      } else {
+        assert!(state.locked.insert(output));
+      }
     }
 
     Ok(true)
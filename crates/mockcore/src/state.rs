@@ -185,6 +185,40 @@ impl State {
     block
   }
 
+  /// Appends a block recorded elsewhere as is. Its inputs don't have to spend
+  /// known outputs.
+  pub(crate) fn push_block(&mut self, block: Block) {
+    assert_eq!(block.header.prev_blockhash, *self.hashes.last().unwrap());
+
+    for tx in block.txdata.iter() {
+      let txid = compute_txid(tx);
+
+      self.transactions.insert(txid, tx.clone());
+      self
+        .txid_to_block_height
+        .insert(txid, self.hashes.len().try_into().unwrap());
+
+      for input in tx.input.iter() {
+        self.utxos.remove(&input.previous_output);
+      }
+
+      for (vout, txout) in tx.output.iter().enumerate() {
+        if !txout.script_pubkey.is_op_return() {
+          self.utxos.insert(
+            OutPoint {
+              txid,
+              vout: vout.try_into().unwrap(),
+            },
+            Amount::from_sat(txout.value),
+          );
+        }
+      }
+    }
+
+    self.hashes.push(block.block_hash());
+    self.blocks.insert(block.block_hash(), block);
+  }
+
   pub(crate) fn pop_block(&mut self) -> BlockHash {
     let blockhash = self.hashes.pop().unwrap();
     self.blocks.remove(&blockhash);
@@ -289,8 +323,13 @@ impl State {
           script_pubkey: if let Some(recipient) = &template.recipient {
             recipient.script_pubkey()
           } else if template.p2tr {
+            // derive keys from the number of transactions seen so far, so that
+            // repeated runs produce the same transactions
             let secp = Secp256k1::new();
-            let keypair = KeyPair::new(&secp, &mut rand::thread_rng());
+            let seed = bitcoin::hashes::sha256::Hash::hash(
+              format!("{}:{i}", self.transactions.len() + self.mempool.len()).as_bytes(),
+            );
+            let keypair = KeyPair::from_seckey_slice(&secp, &seed.into_inner()).unwrap();
             let internal_key = XOnlyPublicKey::from_keypair(&keypair);
             Script::new_v1_p2tr(&secp, internal_key.0, None)
           } else {
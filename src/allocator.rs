@@ -0,0 +1,70 @@
+use {
+  super::*,
+  std::{
+    alloc::{self, GlobalAlloc, Layout},
+    sync::atomic::{AtomicU64, Ordering},
+  },
+};
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator, counting allocations while `count` runs, so
+/// `ord bench` can report how much the code it measures allocates. Installed
+/// as the global allocator of the `ord` binary with the `bench` feature,
+/// elsewhere nothing is counted.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    record(layout.size());
+    alloc::System.alloc(layout)
+  }
+
+  unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+    record(layout.size());
+    alloc::System.alloc_zeroed(layout)
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    record(new_size);
+    alloc::System.realloc(ptr, layout, new_size)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    alloc::System.dealloc(ptr, layout)
+  }
+}
+
+fn record(size: usize) {
+  if COUNTING.load(Ordering::Relaxed) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(u64::try_from(size).unwrap_or(u64::MAX), Ordering::Relaxed);
+  }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Allocations {
+  pub count: u64,
+  pub bytes: u64,
+}
+
+/// Runs `f`, counting the allocations made while it runs, on any thread.
+pub(crate) fn count<T>(f: impl FnOnce() -> T) -> (T, Allocations) {
+  ALLOCATIONS.store(0, Ordering::Relaxed);
+  ALLOCATED_BYTES.store(0, Ordering::Relaxed);
+  COUNTING.store(true, Ordering::Relaxed);
+
+  let result = f();
+
+  COUNTING.store(false, Ordering::Relaxed);
+
+  (
+    result,
+    Allocations {
+      count: ALLOCATIONS.load(Ordering::Relaxed),
+      bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+    },
+  )
+}
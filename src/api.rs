@@ -50,3 +50,22 @@ pub struct Inscription {
   pub timestamp: i64,
   pub value: Option<u64>,
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct ChildInscriptions {
+  pub ids: Vec<InscriptionId>,
+  pub more: bool,
+  pub page: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct ParentInscriptions {
+  pub ids: Vec<InscriptionId>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct SatInscriptions {
+  pub ids: Vec<InscriptionId>,
+  pub more: bool,
+  pub page: usize,
+}
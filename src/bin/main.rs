@@ -1,3 +1,9 @@
+// counting costs every allocation an atomic load, so it's only built for
+// `ord bench`
+#[cfg(feature = "bench")]
+#[global_allocator]
+static ALLOCATOR: ord::CountingAllocator = ord::CountingAllocator;
+
 fn main() {
   ord::main();
 }
@@ -0,0 +1,167 @@
+use {
+  super::*,
+  crate::relics::{Enshrining, MintTerms, Relic, BONESTONES_START_BLOCK},
+};
+
+/// Protocol parameters for test chains, loaded from the TOML file passed to
+/// `--bones-config`. Everything that is left out keeps its built-in value.
+#[derive(Deserialize, Default, PartialEq, Debug, Clone)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct BonesConfig {
+  pub(crate) base_token: BaseTokenTerms,
+  /// sealing fee in whole base tokens for tickers of one letter, two letters
+  /// and so on, the last one applying to all longer tickers
+  pub(crate) sealing_fees: Option<Vec<u128>>,
+  pub(crate) chains: BTreeMap<Chain, ChainParameters>,
+}
+
+#[derive(Deserialize, Default, PartialEq, Debug, Clone, Copy)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct BaseTokenTerms {
+  /// amount minted per burned bonestone
+  pub(crate) amount: Option<u128>,
+  /// number of bonestones that can be burned
+  pub(crate) cap: Option<u128>,
+}
+
+#[derive(Deserialize, Default, PartialEq, Debug, Clone, Copy)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct ChainParameters {
+  pub(crate) first_relic_height: Option<u32>,
+  pub(crate) first_relic_syndicate_height: Option<u32>,
+  /// height from which bonestone delegates can be burned to mint the base token
+  pub(crate) bonestones_start_height: Option<u32>,
+}
+
+impl BonesConfig {
+  pub(crate) fn load(path: &Path, chain: Chain) -> Result<Self> {
+    let config: Self = toml::from_str(
+      &fs::read_to_string(path)
+        .with_context(|| format!("failed to read bones config `{}`", path.display()))?,
+    )
+    .with_context(|| format!("invalid bones config `{}`", path.display()))?;
+
+    ensure!(
+      chain != Chain::Mainnet,
+      "bones config `{}` cannot be used on mainnet",
+      path.display()
+    );
+
+    ensure!(
+      config
+        .sealing_fees
+        .as_ref()
+        .map_or(true, |fees| !fees.is_empty()),
+      "`sealing-fees` in bones config `{}` must not be empty",
+      path.display()
+    );
+
+    Ok(config)
+  }
+
+  pub(crate) fn base_token_terms(&self) -> MintTerms {
+    MintTerms {
+      // mint amount per burned bonestone = ~21M total supply
+      amount: Some(self.base_token.amount.unwrap_or(572_000_000)),
+      // total amount of bonestone delegate inscriptions
+      cap: Some(self.base_token.cap.unwrap_or(3_670_709)),
+      price: None,
+      seed: None,
+      swap_height: None,
+      fee: None,
+    }
+  }
+
+  pub(crate) fn sealing_fee(&self, relic: Relic) -> u128 {
+    match &self.sealing_fees {
+      Some(fees) => {
+        let length = usize::try_from(relic.length()).unwrap();
+        fees[length.clamp(1, fees.len()) - 1] * 10u128.pow(Enshrining::DIVISIBILITY.into())
+      }
+      None => relic.sealing_fee(),
+    }
+  }
+
+  pub(crate) fn chain(&self, chain: Chain) -> ChainParameters {
+    self.chains.get(&chain).copied().unwrap_or_default()
+  }
+
+  pub(crate) fn bonestones_start_height(&self, chain: Chain) -> u32 {
+    self
+      .chain(chain)
+      .bonestones_start_height
+      .unwrap_or(BONESTONES_START_BLOCK)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parameters_are_loaded() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("bones.toml");
+
+    fs::write(
+      &path,
+      "
+sealing-fees = [100, 10, 1]
+
+[base-token]
+amount = 1000
+
+[chains.regtest]
+first-relic-height = 110
+bonestones-start-height = 5
+",
+    )
+    .unwrap();
+
+    let config = BonesConfig::load(&path, Chain::Regtest).unwrap();
+
+    assert_eq!(config.base_token_terms().amount, Some(1000));
+    assert_eq!(config.base_token_terms().cap, Some(3_670_709));
+
+    let unit = 10u128.pow(Enshrining::DIVISIBILITY.into());
+    assert_eq!(config.sealing_fee("A".parse().unwrap()), 100 * unit);
+    assert_eq!(config.sealing_fee("AB".parse().unwrap()), 10 * unit);
+    assert_eq!(config.sealing_fee("ABCDEF".parse().unwrap()), unit);
+
+    assert_eq!(config.chain(Chain::Regtest).first_relic_height, Some(110));
+    assert_eq!(
+      config.chain(Chain::Regtest).first_relic_syndicate_height,
+      None
+    );
+    assert_eq!(config.bonestones_start_height(Chain::Regtest), 5);
+    assert_eq!(config.chain(Chain::Signet), ChainParameters::default());
+  }
+
+  #[test]
+  fn defaults_match_built_in_parameters() {
+    let config = BonesConfig::default();
+    let relic = "ABCD".parse::<Relic>().unwrap();
+    assert_eq!(config.sealing_fee(relic), relic.sealing_fee());
+  }
+
+  #[test]
+  fn mainnet_is_rejected() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("bones.toml");
+
+    fs::write(&path, "").unwrap();
+
+    assert!(BonesConfig::load(&path, Chain::Mainnet).is_err());
+    assert!(BonesConfig::load(&path, Chain::Testnet).is_ok());
+  }
+
+  #[test]
+  fn unknown_keys_are_rejected() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("bones.toml");
+
+    fs::write(&path, "sealing-fee = 1").unwrap();
+
+    assert!(BonesConfig::load(&path, Chain::Regtest).is_err());
+  }
+}
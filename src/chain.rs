@@ -1,7 +1,9 @@
 use bitcoin::util::address;
 use {super::*, clap::ValueEnum};
 
-#[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(
+  Default, ValueEnum, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
 #[serde(rename_all = "kebab-case")]
 pub(crate) enum Chain {
   #[default]
@@ -82,6 +84,22 @@ impl Chain {
     Address::from_script(script, self.network())
   }
 
+  /// Parses `s` as an address of this chain, rejecting addresses encoded for
+  /// other chains, and returns it in canonical encoding.
+  pub(crate) fn parse_address(self, s: &str) -> Result<Address> {
+    let address = Address::from_str(s)?;
+
+    let canonical = self.address_from_script(&address.script_pubkey())?;
+
+    // bech32 addresses may be upper case
+    ensure!(
+      canonical.to_string().eq_ignore_ascii_case(s),
+      "address {s} is not valid on {self}"
+    );
+
+    Ok(canonical)
+  }
+
   pub(crate) fn join_with_data_dir(self, data_dir: &Path) -> PathBuf {
     match self {
       Self::Mainnet => data_dir.to_owned(),
@@ -106,3 +124,22 @@ impl Display for Chain {
     )
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn addresses_are_parsed_for_their_chain() {
+    let address = Chain::Mainnet
+      .address_from_script(&Script::new_p2pkh(&bitcoin::PubkeyHash::all_zeros()))
+      .unwrap();
+
+    assert_eq!(
+      Chain::Mainnet.parse_address(&address.to_string()).unwrap(),
+      address
+    );
+    assert!(Chain::Regtest.parse_address(&address.to_string()).is_err());
+    assert!(Chain::Mainnet.parse_address("foo").is_err());
+  }
+}
@@ -16,10 +16,13 @@ pub enum Charm {
   Mythic = 11,
   Burned = 12,
   Palindrome = 13,
+  Sealed = 14,
+  Enshrined = 15,
+  Bonestone = 16,
 }
 
 impl Charm {
-  pub const ALL: [Self; 14] = [
+  pub const ALL: [Self; 17] = [
     Self::Coin,
     Self::Uncommon,
     Self::Rare,
@@ -34,29 +37,34 @@ impl Charm {
     Self::Lost,
     Self::Vindicated,
     Self::Burned,
+    Self::Bonestone,
+    Self::Sealed,
+    Self::Enshrined,
   ];
 
-  pub fn flag(self) -> u16 {
-    1 << self as u16
+  pub fn flag(self) -> u32 {
+    1 << self as u32
   }
 
-  pub fn set(self, charms: &mut u16) {
+  pub fn set(self, charms: &mut u32) {
     *charms |= self.flag();
   }
 
-  pub fn is_set(self, charms: u16) -> bool {
+  pub fn is_set(self, charms: u32) -> bool {
     charms & self.flag() != 0
   }
 
-  pub fn unset(self, charms: u16) -> u16 {
+  pub fn unset(self, charms: u32) -> u32 {
     charms & !self.flag()
   }
 
   pub fn icon(self) -> &'static str {
     match self {
+      Self::Bonestone => "🦴",
       Self::Burned => "🔥",
       Self::Coin => "🪙",
       Self::Cursed => "👹",
+      Self::Enshrined => "⛩️",
       Self::Epic => "🪻",
       Self::Legendary => "🌝",
       Self::Lost => "🤔",
@@ -65,13 +73,14 @@ impl Charm {
       Self::Palindrome => "🦋",
       Self::Rare => "🧿",
       Self::Reinscription => "♻️",
+      Self::Sealed => "🔏",
       Self::Unbound => "🔓",
       Self::Uncommon => "🌱",
       Self::Vindicated => "\u{2764}\u{fe0f}\u{200d}\u{1f525}",
     }
   }
 
-  pub fn charms(charms: u16) -> Vec<Charm> {
+  pub fn charms(charms: u32) -> Vec<Charm> {
     Self::ALL
       .into_iter()
       .filter(|charm| charm.is_set(charms))
@@ -85,9 +94,11 @@ impl Display for Charm {
       f,
       "{}",
       match self {
+        Self::Bonestone => "bonestone",
         Self::Burned => "burned",
         Self::Coin => "coin",
         Self::Cursed => "cursed",
+        Self::Enshrined => "enshrined",
         Self::Epic => "epic",
         Self::Legendary => "legendary",
         Self::Lost => "lost",
@@ -96,6 +107,7 @@ impl Display for Charm {
         Self::Palindrome => "palindrome",
         Self::Rare => "rare",
         Self::Reinscription => "reinscription",
+        Self::Sealed => "sealed",
         Self::Unbound => "unbound",
         Self::Uncommon => "uncommon",
         Self::Vindicated => "vindicated",
@@ -109,16 +121,20 @@ impl FromStr for Charm {
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
     Ok(match s {
+      "bonestone" => Self::Bonestone,
       "burned" => Self::Burned,
       "coin" => Self::Coin,
       "cursed" => Self::Cursed,
+      "enshrined" => Self::Enshrined,
       "epic" => Self::Epic,
       "legendary" => Self::Legendary,
       "lost" => Self::Lost,
       "mythic" => Self::Mythic,
       "nineball" => Self::Nineball,
+      "palindrome" => Self::Palindrome,
       "rare" => Self::Rare,
       "reinscription" => Self::Reinscription,
+      "sealed" => Self::Sealed,
       "unbound" => Self::Unbound,
       "uncommon" => Self::Uncommon,
       "vindicated" => Self::Vindicated,
@@ -135,6 +151,14 @@ mod tests {
   fn flag() {
     assert_eq!(Charm::Coin.flag(), 0b1);
     assert_eq!(Charm::Cursed.flag(), 0b10);
+    assert_eq!(Charm::Bonestone.flag(), 1 << 16);
+  }
+
+  #[test]
+  fn round_trip() {
+    for charm in Charm::ALL {
+      assert_eq!(charm.to_string().parse::<Charm>().unwrap(), charm);
+    }
   }
 
   #[test]
@@ -1,14 +1,100 @@
-use super::*;
+use {super::*, crate::relics::SpacedRelic};
 
 #[derive(Deserialize, Default, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub(crate) struct Config {
+  #[serde(default)]
   pub(crate) hidden: HashSet<InscriptionId>,
+  #[serde(default)]
+  pub(crate) featured: BTreeSet<SpacedRelic>,
+  #[serde(default)]
+  pub(crate) verified: BTreeSet<SpacedRelic>,
+  /// content-security-policy of `/preview`, by content type
+  #[serde(default)]
+  pub(crate) preview_csp: BTreeMap<String, String>,
+  /// content types that are never previewed
+  #[serde(default)]
+  pub(crate) disable_preview: BTreeSet<String>,
+  /// external origins the model and pdf previews may load scripts from
+  #[serde(default)]
+  pub(crate) preview_origins: Vec<String>,
+  /// named chain, data dir and RPC endpoint combinations, see `--profile`
+  #[serde(default)]
+  pub(crate) profiles: BTreeMap<String, Profile>,
+  /// site title, logo and announcement shown on every page
+  #[serde(default)]
+  pub(crate) branding: Branding,
+}
+
+/// Lets public instances set themselves apart without changing templates.
+/// Featured bones on the home page come from the `featured` list.
+#[derive(Deserialize, Default, PartialEq, Debug, Clone)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct Branding {
+  /// replaces "Bones" in the navigation bar and is appended to page titles
+  pub(crate) title: Option<String>,
+  /// path of an image shown before the title, like `/content/<INSCRIPTION_ID>`
+  pub(crate) logo: Option<String>,
+  /// text shown above the content of every page
+  pub(crate) announcement: Option<String>,
+}
+
+/// Options selected together with `--profile`. Options passed on the command
+/// line take precedence.
+#[derive(Deserialize, Default, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct Profile {
+  pub(crate) chain: Option<Chain>,
+  pub(crate) data_dir: Option<PathBuf>,
+  pub(crate) dogecoin_data_dir: Option<PathBuf>,
+  pub(crate) cookie_file: Option<PathBuf>,
+  pub(crate) rpc_url: Option<String>,
 }
 
 impl Config {
   pub(crate) fn is_hidden(&self, inscription_id: InscriptionId) -> bool {
     self.hidden.contains(&inscription_id)
   }
+
+  /// Content types are matched without parameters, either exactly or by a
+  /// `type/*` pattern.
+  fn matches(pattern: &str, content_type: &str) -> bool {
+    let essence = content_type
+      .split(';')
+      .next()
+      .unwrap_or_default()
+      .trim()
+      .to_ascii_lowercase();
+
+    let pattern = pattern.trim().to_ascii_lowercase();
+
+    match pattern.strip_suffix("/*") {
+      Some(top_level) => essence.split('/').next() == Some(top_level),
+      None => essence == pattern,
+    }
+  }
+
+  pub(crate) fn is_preview_disabled(&self, content_type: Option<&str>) -> bool {
+    content_type.map_or(false, |content_type| {
+      self
+        .disable_preview
+        .iter()
+        .any(|pattern| Self::matches(pattern, content_type))
+    })
+  }
+
+  /// The configured preview policy for `content_type`, preferring an exact
+  /// match over a `type/*` one.
+  pub(crate) fn preview_csp(&self, content_type: Option<&str>) -> Option<&str> {
+    let content_type = content_type?;
+
+    self
+      .preview_csp
+      .iter()
+      .filter(|(pattern, _)| Self::matches(pattern, content_type))
+      .min_by_key(|(pattern, _)| pattern.ends_with("/*"))
+      .map(|(_, policy)| policy.as_str())
+  }
 }
 
 #[cfg(test)]
@@ -27,9 +113,87 @@ mod tests {
 
     let config = Config {
       hidden: iter::once(a).collect(),
+      ..default()
     };
 
     assert!(config.is_hidden(a));
     assert!(!config.is_hidden(b));
   }
+
+  #[test]
+  fn curated_relics_are_loaded() {
+    let config: Config = serde_yaml::from_str("featured:\n- FOO•BAR\nverified:\n- BAZ").unwrap();
+
+    assert_eq!(
+      config.featured,
+      iter::once("FOO•BAR".parse::<SpacedRelic>().unwrap()).collect()
+    );
+    assert_eq!(
+      config.verified,
+      iter::once("BAZ".parse::<SpacedRelic>().unwrap()).collect()
+    );
+    assert!(config.hidden.is_empty());
+  }
+
+  #[test]
+  fn preview_policies_are_loaded() {
+    let config: Config = serde_yaml::from_str(
+      "
+preview-csp:
+  image/*: default-src 'none'
+  image/png: default-src 'self'
+disable-preview:
+- text/html
+- image/svg+xml
+preview-origins:
+- https://example.com
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+      config.preview_csp(Some("image/png")),
+      Some("default-src 'self'")
+    );
+    assert_eq!(
+      config.preview_csp(Some("image/jpeg")),
+      Some("default-src 'none'")
+    );
+    assert_eq!(config.preview_csp(Some("text/plain")), None);
+    assert_eq!(config.preview_csp(None), None);
+
+    assert!(config.is_preview_disabled(Some("text/html;charset=utf-8")));
+    assert!(config.is_preview_disabled(Some("IMAGE/SVG+XML")));
+    assert!(!config.is_preview_disabled(Some("image/png")));
+    assert!(!config.is_preview_disabled(None));
+
+    assert_eq!(
+      config.preview_origins,
+      vec!["https://example.com".to_string()]
+    );
+  }
+
+  #[test]
+  fn branding_is_loaded() {
+    let config: Config = serde_yaml::from_str(
+      "
+branding:
+  title: Much Bones
+  logo: /static/favicon.png
+  announcement: Maintenance tonight
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+      config.branding,
+      Branding {
+        title: Some("Much Bones".into()),
+        logo: Some("/static/favicon.png".into()),
+        announcement: Some("Maintenance tonight".into()),
+      }
+    );
+
+    assert!(serde_yaml::from_str::<Config>("branding:\n  colour: red").is_err());
+  }
 }
@@ -2,7 +2,7 @@ use {
   self::{
     entry::{
       BlockHashValue, Entry, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
-      OutPointMapValue, OutPointValue, SatPointValue, SatRange, TxidValue,
+      OutPointMapValue, OutPointValue, SatPointValue, SatRange, ScriptHashValue, TxidValue,
     },
     reorg::*,
     updater::Updater,
@@ -11,53 +11,88 @@ use {
   crate::{
     charm::Charm,
     index::{
-      chest_entry::ChestEntryValue,
-      event::Event,
+      address_label::AddressLabel,
+      audit::AuditEntry,
+      block_activity::BlockRelicActivity,
+      chest_entry::{ChestEntry, ChestEntryValue},
+      digest::{AddressDigest, ChestUnlock, DigestPeriod},
+      distribution::RelicDistribution,
+      event::{BlockHook, Event, EventFilter, EventHook, EventInfo, EventKind},
+      fixture::RelicFixture,
+      integrity::{IntegrityReport, IntegrityViolation},
+      migration::MIGRATIONS,
+      owner_change::OwnerChange,
+      portfolio::{RelicPortfolio, RelicValuation},
       relics_entry::{
-        RelicEntry, RelicEntryValue, RelicIdValue, RelicOwner, RelicOwnerValue, RelicState,
-        SpacedRelicValue,
+        ActivityMetric, RelicActivity, RelicActivityValue, RelicEntry, RelicEntryValue,
+        RelicEstimates, RelicFees, RelicFeesValue, RelicIdValue, RelicOwner, RelicOwnerValue,
+        RelicState, RelicSupply, RelicSupplyValue, SpacedRelicValue, SwapHop,
       },
+      sealing::{EnshriningCheck, EnshriningHistory, SealingHistory, SealingStats},
+      snapshot::{RelicSnapshot, SnapshotInscription},
+      subsidy_schedule_entry::{SubsidyScheduleEntry, SubsidyScheduleEntryValue},
       syndicate_entry::{SyndicateEntry, SyndicateEntryValue, SyndicateIdValue},
     },
     inscription::ParsedInscription,
     relics::{
-      Enshrining, MintTerms, Relic, RelicError, RelicId, SpacedRelic, SyndicateId, RELIC_ID,
-      RELIC_NAME,
+      Enshrining, EnshriningViolation, Relic, RelicError, RelicId, SpacedRelic, SwapDirection,
+      SyndicateId, RELIC_ID, RELIC_NAME,
     },
     sat::Sat,
     sat_point::SatPoint,
     templates::BlockHashAndConfirmations,
     wallet::Wallet,
   },
-  bitcoin::BlockHeader,
+  bitcoin::{hashes::sha256, BlockHeader},
   bitcoincore_rpc::{json::GetBlockHeaderResult, Auth, Client},
   chrono::SubsecRound,
   indicatif::{ProgressBar, ProgressStyle},
   log::log_enabled,
   redb::ReadableTableMetadata,
   redb::{
-    Database, DatabaseError, MultimapTable, MultimapTableDefinition, ReadableMultimapTable,
-    ReadableTable, StorageError, Table, TableDefinition, WriteTransaction,
+    Database, DatabaseError, MultimapTable, MultimapTableDefinition, MultimapTableHandle,
+    ReadableMultimapTable, ReadableTable, StorageError, Table, TableDefinition, TableHandle,
+    WriteTransaction,
   },
   std::collections::HashMap,
   std::io::Cursor,
-  std::sync::atomic::{self, AtomicBool},
+  std::sync::atomic::{self, AtomicBool, AtomicU64},
   url::Url,
 };
 
-mod chest_entry;
+pub(crate) mod address_label;
+pub(crate) mod audit;
+pub(crate) mod block_activity;
+mod checkpoint;
+pub(crate) mod chest_entry;
+pub(crate) mod digest;
+pub(crate) mod distribution;
 pub(crate) mod entry;
 pub(crate) mod event;
 mod fetcher;
+pub(crate) mod fixture;
+pub(crate) mod integrity;
 mod lot;
+mod migration;
+pub(crate) mod owner_change;
+pub(crate) mod portfolio;
 pub(crate) mod relics_entry;
 mod reorg;
 mod rtx;
+pub(crate) mod sealing;
+mod search;
+pub(crate) mod snapshot;
+pub(crate) mod subsidy_schedule_entry;
 pub(crate) mod syndicate_entry;
 pub(crate) mod testing;
 mod updater;
+pub(crate) mod webhook;
 
-const SCHEMA_VERSION: u64 = 6;
+const SCHEMA_VERSION: u64 = 41;
+
+/// Recorded in `RELIC_ID_TO_POOL_PRICE` when a relic that had a pool price no
+/// longer has a pool with reserves, so averages stop at the last price.
+pub(crate) const NO_POOL_PRICE: u128 = u128::MAX;
 
 macro_rules! define_table {
   ($name:ident, $key:ty, $value:ty) => {
@@ -73,28 +108,60 @@ macro_rules! define_multimap_table {
 }
 
 define_table! { HEIGHT_TO_BLOCK_HASH, u32, &BlockHashValue }
+define_table! { HEIGHT_TO_RELIC_CHECKPOINT, u32, &[u8; 32] }
 define_table! { INSCRIPTION_ID_TO_SATPOINT, &InscriptionIdValue, &SatPointValue }
 define_table! { INSCRIPTION_ID_TO_TXIDS, &InscriptionIdValue, &[u8] }
+define_table! { INSCRIPTION_ID_TO_PRUNED_INSCRIPTION, &InscriptionIdValue, &str }
 define_table! { INSCRIPTION_TXID_TO_TX, &[u8], &[u8] }
 define_table! { PARTIAL_TXID_TO_INSCRIPTION_TXIDS, &[u8], &[u8] }
 define_table! { OUTPOINT_TO_SAT_RANGES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_VALUE, &OutPointValue, u64}
-define_multimap_table! { ADDRESS_TO_OUTPOINT, &[u8], &OutPointValue}
+define_table! { OUTPOINT_TO_TXOUT, &OutPointValue, &[u8] }
+define_multimap_table! { SCRIPT_HASH_TO_OUTPOINT, &ScriptHashValue, &OutPointValue }
+define_multimap_table! { ADDRESS_TO_EVENTS, &[u8], (u32, u32) }
+define_multimap_table! { ADDRESS_TO_SEQUENCE_NUMBER, &[u8], u32 }
+define_table! { ADDRESS_TO_INSCRIPTION_COUNT, &[u8], u64 }
+define_table! { SEQUENCE_NUMBER_TO_ADDRESS, u32, &[u8] }
+define_table! { EVENT_KEY_TO_EVENT, (u32, u32), Event }
+define_table! { EVENT_OUTBOX, (u32, u32), () }
+define_table! { EVENT_KIND_TO_EVENT_KEY, (u8, u32, u32), () }
 define_table! { SATPOINT_TO_INSCRIPTION_ID, &SatPointValue, &InscriptionIdValue }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
 define_table! { TRANSACTION_ID_TO_TRANSACTION, &TxidValue, &[u8] }
 define_table! { WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP, u32, u128 }
 define_table! { RELIC_TO_SEQUENCE_NUMBER, u128, u32 }
+define_table! { PENDING_SEALING_SEQUENCE_NUMBERS, u32, () }
 define_table! { SEQUENCE_NUMBER_TO_SPACED_RELIC, u32, SpacedRelicValue }
 define_table! { SEQUENCE_NUMBER_TO_SYNDICATE_ID, u32, SyndicateIdValue }
 define_table! { SEQUENCE_NUMBER_TO_CHEST, u32, ChestEntryValue }
+define_table! { SEQUENCE_NUMBER_TO_OWNER_CHANGE, (u32, u32), OwnerChange }
 define_multimap_table! { SYNDICATE_TO_CHEST_SEQUENCE_NUMBER, SyndicateIdValue, u32 }
+define_table! { CHEST_UNLOCK_HEIGHT_TO_SEQUENCE_NUMBER, (u64, u32), () }
 define_table! { RELIC_ID_TO_RELIC_ENTRY, RelicIdValue, RelicEntryValue }
 define_table! { RELIC_TO_RELIC_ID, u128, RelicIdValue }
+define_multimap_table! { RELIC_PREFIX_TO_RELIC_ID, &str, RelicIdValue }
+define_table! { RELIC_NUMBER_TO_RELIC_ID, u64, RelicIdValue }
+define_table! { RELIC_ID_TO_THUMBNAIL, RelicIdValue, InscriptionIdValue }
+define_table! { RELIC_ID_TO_SUPPLY, (RelicIdValue, u32), RelicSupplyValue }
+define_table! { RELIC_ID_TO_POOL_PRICE, (RelicIdValue, u32), u128 }
+define_table! { RELIC_TO_FEES, (u128, u32), RelicFeesValue }
+define_table! { HEIGHT_TO_FEES, u32, RelicFeesValue }
+define_table! { HEIGHT_TO_RELIC_ACTIVITY, (u32, RelicIdValue), RelicActivityValue }
+define_table! { HEIGHT_TO_RELIC_ERROR_COUNTS, (u32, &str), u64 }
 define_table! { RELIC_OWNER_TO_CLAIMABLE, &RelicOwnerValue, u128 }
+define_table! { LIQUIDITY_PROVIDER_TO_SHARES, (RelicIdValue, &RelicOwnerValue), u128 }
+define_table! { RELIC_OWNER_TO_FEES_PAID, (&RelicOwnerValue, RelicIdValue), u128 }
+// balance of an address after every block in which it changed
+define_table! { RELIC_BALANCE_JOURNAL, (RelicIdValue, &str, u32), u128 }
+define_multimap_table! { ADDRESS_TO_JOURNALED_RELIC_ID, &str, RelicIdValue }
+define_table! { RELIC_ID_TO_SUBSIDY_SCHEDULE, RelicIdValue, SubsidyScheduleEntryValue }
+define_table! { SUBSIDY_RELEASE_HEIGHT_TO_RELIC_ID, (u64, RelicIdValue), () }
 define_table! { SYNDICATE_ID_TO_SYNDICATE_ENTRY, SyndicateIdValue, SyndicateEntryValue }
 define_multimap_table! { RELIC_ID_TO_EVENTS, RelicIdValue, Event }
+define_table! { RELIC_ID_TO_EVENT_KEY, (RelicIdValue, u32, u32), () }
+define_table! { RELIC_ID_TO_BURN_EVENT_KEY, (RelicIdValue, u32, u32), () }
+define_table! { INSCRIPTION_ID_TO_CHEST_EVENT_KEY, (&InscriptionIdValue, u32, u32), () }
 define_table! { OUTPOINT_TO_RELIC_BALANCES, &OutPointValue, &[u8] }
 define_table! { TRANSACTION_ID_TO_RELIC, &TxidValue, u128 }
 define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
@@ -108,26 +175,63 @@ define_multimap_table! { TRANSACTION_ID_TO_EVENTS, &TxidValue, Event }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
 define_table! { SEQUENCE_NUMBER_TO_BONESTONE_BLOCK_HEIGHT, u32, u32 }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
+define_table! { AUDIT_LOG_ID_TO_ENTRY, u64, &str }
+// schema version of tables with migrations, see `migration::MIGRATIONS`
+define_table! { TABLE_TO_SCHEMA_VERSION, &str, u64 }
+
+// operator data in `Index::operator_database`, tables are created on first write
+define_table! { ADDRESS_TO_LABEL, &str, &str }
+define_table! { NEXT_DISPATCHED_EVENT_KEY, (), (u32, u32) }
+define_table! { INSCRIPTION_ID_TO_CONTENT, &InscriptionIdValue, &[u8] }
 
-pub(crate) struct Index {
+pub struct Index {
   auth: Auth,
   client: Client,
   database: Database,
+  /// Operator data that isn't derived from the chain, like address labels, in
+  /// a database of its own next to the index. Restoring a reorg savepoint
+  /// doesn't revert it, and writing it doesn't wait for the updater.
+  operator_database: Database,
   path: PathBuf,
+  /// receives the events of the outbox, see `dispatch_events`
   event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
+  events_dispatched: AtomicU64,
+  /// time the dispatcher waited for room in the event channel
+  event_dispatch_wait_micros: AtomicU64,
+  event_hooks: Vec<EventHook>,
+  block_hooks: Vec<BlockHook>,
   first_inscription_height: u32,
   first_relic_height: u32,
   first_relic_syndicate_height: u32,
+  bones_config: BonesConfig,
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
   index_sats: bool,
+  index_threads: usize,
   index_transactions: bool,
+  index_txouts: bool,
   index_relics: bool,
+  prune_content: bool,
+  index_relic_checkpoints: bool,
+  index_relic_fees: bool,
+  index_relic_history: bool,
+  /// only store the transactions relevant to inscriptions and relics, see
+  /// `--index-raw-transactions`
+  index_raw_transactions: bool,
+  /// whether Dogecoin Core discards old blocks, detected when opening the index
+  node_pruned: bool,
+  trusted_relic_checkpoints: BTreeMap<u32, sha256::Hash>,
+  /// number of blocks between relic state checkpoints
+  relic_checkpoint_interval: u32,
   unrecoverably_reorged: AtomicBool,
+  /// fixed clock in milliseconds since the unix epoch, only set with `--deterministic`
+  clock: Option<AtomicU64>,
   rpc_url: String,
   nr_parallel_requests: usize,
   chain: Chain,
+  /// number of blocks indexed in each write transaction
+  commit_interval: usize,
 }
 
 #[derive(Debug, PartialEq)]
@@ -148,6 +252,16 @@ pub(crate) enum Statistic {
   IndexTransactions,
   IndexRelics = 17,
   Relics = 18,
+  IndexRelicCheckpoints = 19,
+  IndexRelicFees = 20,
+  IndexTxouts = 21,
+  Enshrinings = 22,
+  BlocksToEnshrine = 23,
+  PruneContent = 24,
+  /// set while an update has committed some, but not all, of its batches
+  Updating = 25,
+  IndexRelicHistory = 26,
+  IndexRawTransactions = 27,
 }
 
 impl Statistic {
@@ -175,17 +289,44 @@ pub(crate) struct Info {
   pub(crate) page_size: usize,
   pub(crate) sat_ranges: u64,
   pub(crate) stored_bytes: u64,
+  /// schema versions of the tables with migrations
+  pub(crate) table_schema_versions: BTreeMap<String, u64>,
   pub(crate) transactions: Vec<TransactionInfo>,
   pub(crate) tree_height: u32,
   pub(crate) utxos_indexed: u64,
 }
 
+/// Backlog of the event outbox and how much the event channel held it up.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct EventOutboxInfo {
+  /// committed events not yet moved into the event channel
+  pub(crate) pending: u64,
+  /// events in the channel that consumers haven't received yet
+  pub(crate) queued: u64,
+  /// events moved into the channel since the index was opened
+  pub(crate) dispatched: u64,
+  /// seconds the dispatcher waited for room in the channel since the index
+  /// was opened
+  pub(crate) blocked_seconds: f64,
+}
+
 #[derive(Serialize)]
 pub(crate) struct TransactionInfo {
   pub(crate) starting_block_count: u32,
   pub(crate) starting_timestamp: u128,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableInfo {
+  pub name: String,
+  pub multimap: bool,
+  pub entries: u64,
+  pub stored_bytes: u64,
+  pub metadata_bytes: u64,
+  pub fragmented_bytes: u64,
+  pub tree_height: u32,
+}
+
 trait BitcoinCoreRpcResultExt<T> {
   fn into_option(self) -> Result<Option<T>>;
 }
@@ -213,7 +354,7 @@ impl Index {
   pub(crate) fn open(options: &Options) -> Result<Self> {
     Index::open_with_event_sender(options, None)
   }
-  pub fn open_with_event_sender(
+  pub(crate) fn open_with_event_sender(
     options: &Options,
     event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
   ) -> Result<Self> {
@@ -254,9 +395,17 @@ impl Index {
       data_dir.join("index.redb")
     };
 
+    let bones_config = options.load_bones_config()?;
+
     let index_sats;
     let index_transactions;
     let index_relics;
+    let index_relic_checkpoints;
+    let index_relic_fees;
+    let index_relic_history;
+    let index_raw_transactions;
+    let index_txouts;
+    let prune_content;
 
     let database = match unsafe { Database::builder().open(&path) } {
       Ok(database) => {
@@ -297,6 +446,36 @@ impl Index {
             .unwrap()
             .value()
             != 0;
+          index_relic_checkpoints = statistics
+            .get(&Statistic::IndexRelicCheckpoints.key())?
+            .unwrap()
+            .value()
+            != 0;
+          index_relic_fees = statistics
+            .get(&Statistic::IndexRelicFees.key())?
+            .unwrap()
+            .value()
+            != 0;
+          index_relic_history = statistics
+            .get(&Statistic::IndexRelicHistory.key())?
+            .unwrap()
+            .value()
+            != 0;
+          index_raw_transactions = statistics
+            .get(&Statistic::IndexRawTransactions.key())?
+            .unwrap()
+            .value()
+            != 0;
+          index_txouts = statistics
+            .get(&Statistic::IndexTxouts.key())?
+            .unwrap()
+            .value()
+            != 0;
+          prune_content = statistics
+            .get(&Statistic::PruneContent.key())?
+            .unwrap()
+            .value()
+            != 0;
         }
 
         database
@@ -329,10 +508,16 @@ impl Index {
         tx.open_table(HEIGHT_TO_BLOCK_HASH)?;
         tx.open_table(INSCRIPTION_ID_TO_SATPOINT)?;
         tx.open_table(INSCRIPTION_ID_TO_TXIDS)?;
+        tx.open_table(INSCRIPTION_ID_TO_PRUNED_INSCRIPTION)?;
         tx.open_table(INSCRIPTION_TXID_TO_TX)?;
         tx.open_table(PARTIAL_TXID_TO_INSCRIPTION_TXIDS)?;
         tx.open_table(OUTPOINT_TO_VALUE)?;
-        tx.open_multimap_table(ADDRESS_TO_OUTPOINT)?;
+        tx.open_table(OUTPOINT_TO_TXOUT)?;
+        tx.open_multimap_table(SCRIPT_HASH_TO_OUTPOINT)?;
+        tx.open_multimap_table(ADDRESS_TO_EVENTS)?;
+        tx.open_multimap_table(ADDRESS_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(ADDRESS_TO_INSCRIPTION_COUNT)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_ADDRESS)?;
         tx.open_table(SATPOINT_TO_INSCRIPTION_ID)?;
         tx.open_table(SAT_TO_SATPOINT)?;
         tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
@@ -341,22 +526,56 @@ impl Index {
         tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
         tx.open_multimap_table(RELIC_ID_TO_EVENTS)?;
         tx.open_multimap_table(TRANSACTION_ID_TO_EVENTS)?;
+        tx.open_table(EVENT_KEY_TO_EVENT)?;
+        tx.open_table(EVENT_OUTBOX)?;
+        tx.open_table(EVENT_KIND_TO_EVENT_KEY)?;
+        tx.open_table(RELIC_ID_TO_EVENT_KEY)?;
+        tx.open_table(RELIC_ID_TO_BURN_EVENT_KEY)?;
+        tx.open_table(INSCRIPTION_ID_TO_CHEST_EVENT_KEY)?;
         tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
         tx.open_table(OUTPOINT_TO_RELIC_BALANCES)?;
         tx.open_table(RELIC_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(PENDING_SEALING_SEQUENCE_NUMBERS)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SPACED_RELIC)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SYNDICATE_ID)?;
         tx.open_table(SEQUENCE_NUMBER_TO_CHEST)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_OWNER_CHANGE)?;
         tx.open_multimap_table(SYNDICATE_TO_CHEST_SEQUENCE_NUMBER)?;
+        tx.open_table(CHEST_UNLOCK_HEIGHT_TO_SEQUENCE_NUMBER)?;
         tx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
         tx.open_table(RELIC_TO_RELIC_ID)?;
+        tx.open_multimap_table(RELIC_PREFIX_TO_RELIC_ID)?;
+        tx.open_table(RELIC_NUMBER_TO_RELIC_ID)?;
         tx.open_table(RELIC_OWNER_TO_CLAIMABLE)?;
+        tx.open_table(LIQUIDITY_PROVIDER_TO_SHARES)?;
+        tx.open_table(RELIC_OWNER_TO_FEES_PAID)?;
+        tx.open_table(RELIC_BALANCE_JOURNAL)?;
+        tx.open_multimap_table(ADDRESS_TO_JOURNALED_RELIC_ID)?;
+        tx.open_table(RELIC_ID_TO_SUBSIDY_SCHEDULE)?;
+        tx.open_table(SUBSIDY_RELEASE_HEIGHT_TO_RELIC_ID)?;
+        tx.open_table(RELIC_ID_TO_THUMBNAIL)?;
+        tx.open_table(RELIC_ID_TO_SUPPLY)?;
+        tx.open_table(RELIC_ID_TO_POOL_PRICE)?;
+        tx.open_table(RELIC_TO_FEES)?;
+        tx.open_table(HEIGHT_TO_FEES)?;
+        tx.open_table(HEIGHT_TO_RELIC_ACTIVITY)?;
+        tx.open_table(HEIGHT_TO_RELIC_ERROR_COUNTS)?;
+        tx.open_table(HEIGHT_TO_RELIC_CHECKPOINT)?;
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
         tx.open_table(SEQUENCE_NUMBER_TO_BONESTONE_BLOCK_HEIGHT)?;
         tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+        tx.open_table(AUDIT_LOG_ID_TO_ENTRY)?;
+
+        {
+          let mut table_to_schema_version = tx.open_table(TABLE_TO_SCHEMA_VERSION)?;
+
+          for (table, version) in migration::latest_versions(MIGRATIONS) {
+            table_to_schema_version.insert(table, version)?;
+          }
+        }
 
         {
           let mut outpoint_to_sat_ranges = tx.open_table(OUTPOINT_TO_SAT_RANGES)?;
@@ -372,6 +591,14 @@ impl Index {
             tx.open_table(RELIC_TO_RELIC_ID)?
               .insert(relic.store(), id.store())?;
 
+            search::insert_relic(
+              &mut tx.open_multimap_table(RELIC_PREFIX_TO_RELIC_ID)?,
+              &mut tx.open_table(RELIC_NUMBER_TO_RELIC_ID)?,
+              relic,
+              0,
+              id,
+            )?;
+
             statistics.insert(&Statistic::Relics.into(), 1)?;
 
             tx.open_table(RELIC_ID_TO_RELIC_ENTRY)?.insert(
@@ -383,15 +610,7 @@ impl Index {
                 spaced_relic: SpacedRelic { relic, spacers: 0 },
                 symbol: Some('🦴'),
                 owner_sequence_number: None,
-                mint_terms: Some(MintTerms {
-                  // mint amount per burned bonestone = ~21M total supply
-                  amount: Some(572_000_000),
-                  // total amount of bonestone delegate inscriptions
-                  cap: Some(3_670_709),
-                  price: None,
-                  seed: None,
-                  swap_height: None,
-                }),
+                mint_terms: Some(bones_config.base_token_terms()),
                 state: RelicState {
                   subsidy_locked: true,
                   ..default()
@@ -414,16 +633,46 @@ impl Index {
           index_sats = options.index_sats;
           index_transactions = options.index_transactions;
           index_relics = options.index_relics;
+          index_relic_checkpoints = options.index_relic_checkpoints;
+          index_relic_fees = options.index_relic_fees;
+          index_relic_history = options.index_relic_history;
+          index_raw_transactions = options.index_raw_transactions;
+          index_txouts = options.index_txouts;
+          prune_content = options.prune_content;
 
           statistics.insert(&Statistic::IndexSats.key(), &u64::from(index_sats))?;
 
           statistics.insert(&Statistic::IndexRelics.key(), &u64::from(index_relics))?;
 
+          statistics.insert(
+            &Statistic::IndexRelicCheckpoints.key(),
+            &u64::from(index_relic_checkpoints),
+          )?;
+
+          statistics.insert(
+            &Statistic::IndexRelicFees.key(),
+            &u64::from(index_relic_fees),
+          )?;
+
+          statistics.insert(
+            &Statistic::IndexRelicHistory.key(),
+            &u64::from(index_relic_history),
+          )?;
+
+          statistics.insert(
+            &Statistic::IndexRawTransactions.key(),
+            &u64::from(index_raw_transactions),
+          )?;
+
           statistics.insert(
             &Statistic::IndexTransactions.key(),
             &u64::from(index_transactions),
           )?;
 
+          statistics.insert(&Statistic::IndexTxouts.key(), &u64::from(index_txouts))?;
+
+          statistics.insert(&Statistic::PruneContent.key(), &u64::from(prune_content))?;
+
           statistics.insert(&Statistic::Schema.key(), &SCHEMA_VERSION)?;
         }
 
@@ -434,32 +683,163 @@ impl Index {
       Err(error) => return Err(error.into()),
     };
 
+    let operator_database = Database::create(path.with_extension("operator.redb"))?;
+
+    if index_relic_checkpoints && !index_relics {
+      bail!("relic checkpoints require an index with `--index-relics`");
+    }
+
+    if index_relic_fees && !index_relics {
+      bail!("relic fee accounting requires an index with `--index-relics`");
+    }
+
+    if index_relic_history && !index_relics {
+      bail!("relic balance history requires an index with `--index-relics`");
+    }
+
+    let trusted_relic_checkpoints = match &options.verify_checkpoints {
+      Some(path) => {
+        if !index_relic_checkpoints {
+          bail!("verifying checkpoints requires an index with `--index-relic-checkpoints`");
+        }
+        checkpoint::load_trusted(path)?
+      }
+      None => BTreeMap::new(),
+    };
+
+    let node_pruned = match client.get_blockchain_info() {
+      Ok(info) => info.pruned,
+      Err(err) => {
+        log::warn!("failed to check whether Dogecoin Core is pruned: {err}");
+        false
+      }
+    };
+
+    if node_pruned {
+      log::warn!(
+        "Dogecoin Core is pruned, old blocks and the inputs of old transactions will be unavailable"
+      );
+    }
+
     let genesis_block_coinbase_transaction =
       options.chain().genesis_block().coinbase().unwrap().clone();
 
-    Ok(Self {
+    let index = Self {
       genesis_block_coinbase_txid: genesis_block_coinbase_transaction.txid(),
       auth,
       client,
       database,
+      operator_database,
       path,
       event_sender,
+      events_dispatched: AtomicU64::new(0),
+      event_dispatch_wait_micros: AtomicU64::new(0),
+      event_hooks: Vec::new(),
+      block_hooks: Vec::new(),
       first_inscription_height: options.first_inscription_height(),
-      first_relic_height: options.first_relic_height(),
-      first_relic_syndicate_height: options.first_relic_syndicate_height(),
+      first_relic_height: options.first_relic_height(&bones_config),
+      first_relic_syndicate_height: options.first_relic_syndicate_height(&bones_config),
       genesis_block_coinbase_transaction,
       height_limit: options.height_limit,
       index_sats,
+      index_threads: options.index_threads(),
       index_transactions,
+      index_txouts,
       index_relics,
+      prune_content,
+      index_relic_checkpoints,
+      index_relic_fees,
+      index_relic_history,
+      index_raw_transactions,
+      node_pruned,
+      trusted_relic_checkpoints,
+      relic_checkpoint_interval: options.relic_checkpoint_interval(),
+      bones_config,
       unrecoverably_reorged: AtomicBool::new(false),
+      clock: options.deterministic.then(|| AtomicU64::new(0)),
       rpc_url,
       nr_parallel_requests,
-      chain: options.chain_argument,
-    })
+      chain: options.chain(),
+      commit_interval: options.commit_interval(),
+    };
+
+    index.check_clean_shutdown()?;
+
+    index.migrate(MIGRATIONS)?;
+
+    Ok(index)
+  }
+
+  /// An update that is killed after committing some of its batches leaves the
+  /// `Updating` statistic set. Committed blocks are intact, since every commit
+  /// is atomic, so only an index with the marker set is re-verified at the
+  /// block it stopped at. Inconsistent relic state is rolled back to the oldest
+  /// savepoint, and the index refuses to open if there is none, or if the
+  /// state is still inconsistent afterwards.
+  fn check_clean_shutdown(&self) -> Result {
+    let updating = self
+      .database
+      .begin_read()?
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::Updating.key())?
+      .map(|x| x.value())
+      .unwrap_or(0);
+
+    if updating == 0 {
+      return Ok(());
+    }
+
+    let Some(height) = self.height()? else {
+      return Ok(());
+    };
+
+    log::warn!("index was not shut down cleanly, re-verifying block {height}");
+
+    if self.block_hash(Some(height.n()))?
+      != self
+        .client
+        .get_block_hash(height.n().into())
+        .into_option()?
+    {
+      log::warn!("block {height} is no longer in the active chain and will be rolled back");
+    }
+
+    if !self.index_relics || self.verify_relic_integrity()?.ok() {
+      return Ok(());
+    }
+
+    let mut wtx = self.begin_write()?;
+
+    let Some(oldest) = wtx.list_persistent_savepoints()?.min() else {
+      bail!(
+        "relic state at block {height} is inconsistent and there is no savepoint to roll back to, run `ord index verify` for details or rebuild the index"
+      );
+    };
+
+    log::warn!(
+      "relic state at block {height} is inconsistent, rolling back to the oldest savepoint"
+    );
+
+    let savepoint = wtx.get_persistent_savepoint(oldest)?;
+    wtx.restore_savepoint(&savepoint)?;
+
+    wtx
+      .open_table(STATISTIC_TO_COUNT)?
+      .insert(&Statistic::Updating.key(), &0)?;
+
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
+
+    ensure!(
+      self.verify_relic_integrity()?.ok(),
+      "relic state at block {} is still inconsistent after rolling back, rebuild the index",
+      self.block_count()?,
+    );
+
+    Ok(())
   }
 
-  pub(crate) fn get_unspent_outputs(&self, _wallet: Wallet) -> Result<BTreeMap<OutPoint, Amount>> {
+  pub(crate) fn get_unspent_outputs(&self, _wallet: &Wallet) -> Result<BTreeMap<OutPoint, Amount>> {
     let mut utxos = BTreeMap::new();
     utxos.extend(
       self
@@ -504,7 +884,7 @@ impl Index {
 
   pub(crate) fn get_unspent_output_ranges(
     &self,
-    wallet: Wallet,
+    wallet: &Wallet,
   ) -> Result<Vec<(OutPoint, Vec<(u64, u64)>)>> {
     self
       .get_unspent_outputs(wallet)?
@@ -521,6 +901,67 @@ impl Index {
     self.index_sats
   }
 
+  /// Opens the index configured by command line style `args`, for example
+  /// `["ord", "--index-relics"]`, for embedders that register hooks and drive
+  /// indexing with `update`.
+  pub fn open_with_args<I, T>(args: I) -> Result<Self>
+  where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+  {
+    Self::open(&Options::try_parse_from(args)?)
+  }
+
+  /// Registers a callback that is invoked with every event, for embedders that
+  /// consume events in-process without an async runtime. Hooks run on the
+  /// indexing thread once the block's write transaction has committed, so
+  /// events of blocks that are lost when indexing is interrupted are never
+  /// seen. Events of blocks that are later rolled back by a reorg are.
+  pub fn on_event(&mut self, hook: EventHook) {
+    self.event_hooks.push(hook);
+  }
+
+  /// Registers a callback that is invoked with the height and header of every
+  /// block after its events, with the same caveats as `on_event`.
+  pub fn on_block(&mut self, hook: BlockHook) {
+    self.block_hooks.push(hook);
+  }
+
+  pub(crate) fn is_node_pruned(&self) -> bool {
+    self.node_pruned
+  }
+
+  /// Whether inscription bodies are kept, false for indices built with
+  /// `--prune-content`.
+  pub(crate) fn has_content(&self) -> bool {
+    !self.prune_content
+  }
+
+  pub(crate) fn is_deterministic(&self) -> bool {
+    self.clock.is_some()
+  }
+
+  /// Milliseconds since the unix epoch, read from the fixed clock in
+  /// deterministic mode.
+  pub(crate) fn now_millis(&self) -> u128 {
+    match &self.clock {
+      Some(clock) => clock.load(atomic::Ordering::Relaxed).into(),
+      None => SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0),
+    }
+  }
+
+  /// Sets the fixed clock, which only exists in deterministic mode.
+  pub(crate) fn set_clock(&self, millis: u64) {
+    self
+      .clock
+      .as_ref()
+      .expect("the clock can only be set on an index opened with `--deterministic`")
+      .store(millis, atomic::Ordering::Relaxed);
+  }
+
   pub(crate) fn info(&self) -> Result<Info> {
     let wtx = self.begin_write()?;
 
@@ -567,6 +1008,7 @@ impl Index {
         outputs_traversed,
         page_size: stats.page_size(),
         stored_bytes: stats.stored_bytes(),
+        table_schema_versions: self.table_schema_versions()?,
         transactions,
         tree_height: stats.tree_height(),
         utxos_indexed: wtx.open_table(OUTPOINT_TO_SAT_RANGES)?.len()?,
@@ -576,7 +1018,7 @@ impl Index {
     Ok(info)
   }
 
-  pub(crate) fn update(&self) -> Result {
+  pub fn update(&self) -> Result {
     let mut updater = Updater::new(self)?;
 
     loop {
@@ -612,6 +1054,76 @@ impl Index {
     Ok(rtx::Rtx(self.database.begin_read()?))
   }
 
+  /// Entry counts and storage of every table, largest first.
+  pub(crate) fn table_info(&self) -> Result<Vec<TableInfo>> {
+    let rtx = self.database.begin_read()?;
+
+    let mut tables = Vec::new();
+
+    for handle in rtx.list_tables()? {
+      let name = handle.name().to_string();
+      let table = rtx.open_untyped_table(handle)?;
+      let stats = table.stats()?;
+      tables.push(TableInfo {
+        name,
+        multimap: false,
+        entries: table.len()?,
+        stored_bytes: stats.stored_bytes(),
+        metadata_bytes: stats.metadata_bytes(),
+        fragmented_bytes: stats.fragmented_bytes(),
+        tree_height: stats.tree_height(),
+      });
+    }
+
+    for handle in rtx.list_multimap_tables()? {
+      let name = handle.name().to_string();
+      let table = rtx.open_untyped_multimap_table(handle)?;
+      let stats = table.stats()?;
+      tables.push(TableInfo {
+        name,
+        multimap: true,
+        entries: table.len()?,
+        stored_bytes: stats.stored_bytes(),
+        metadata_bytes: stats.metadata_bytes(),
+        fragmented_bytes: stats.fragmented_bytes(),
+        tree_height: stats.tree_height(),
+      });
+    }
+
+    tables.sort_by(|a, b| {
+      b.stored_bytes
+        .cmp(&a.stored_bytes)
+        .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(tables)
+  }
+
+  /// Deletes the reorg savepoints, which pin the pages they reference and
+  /// prevent compaction. New ones are created as the index approaches the
+  /// chain tip again, and until then reorgs can't be rolled back. Returns the
+  /// number of deleted savepoints.
+  pub(crate) fn delete_savepoints(&self) -> Result<usize> {
+    let wtx = self.begin_write()?;
+
+    let savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
+
+    for savepoint in &savepoints {
+      wtx.delete_persistent_savepoint(*savepoint)?;
+    }
+
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
+
+    Ok(savepoints.len())
+  }
+
+  /// Compacts the database file, returning whether any space was reclaimed.
+  /// Fails if savepoints remain, see `delete_savepoints`.
+  pub(crate) fn compact(&mut self) -> Result<bool> {
+    Ok(self.database.compact()?)
+  }
+
   fn begin_write(&self) -> Result<WriteTransaction> {
     if cfg!(test) {
       let mut tx = self.database.begin_write()?;
@@ -622,6 +1134,14 @@ impl Index {
     }
   }
 
+  fn begin_operator_write(&self) -> Result<WriteTransaction> {
+    let mut tx = self.operator_database.begin_write()?;
+    if cfg!(test) {
+      tx.set_durability(redb::Durability::None);
+    }
+    Ok(tx)
+  }
+
   fn increment_statistic(wtx: &WriteTransaction, statistic: Statistic, n: u64) -> Result {
     let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
     let value = statistic_to_count
@@ -633,6 +1153,28 @@ impl Index {
     Ok(())
   }
 
+  #[cfg(test)]
+  pub(crate) fn set_statistic(&self, statistic: Statistic, value: u64) {
+    let wtx = self.begin_write().unwrap();
+    wtx
+      .open_table(STATISTIC_TO_COUNT)
+      .unwrap()
+      .insert(&statistic.key(), &value)
+      .unwrap();
+    wtx.commit().unwrap();
+  }
+
+  #[cfg(test)]
+  pub(crate) fn remove_relic_balances(&self, outpoint: OutPoint) {
+    let wtx = self.begin_write().unwrap();
+    wtx
+      .open_table(OUTPOINT_TO_RELIC_BALANCES)
+      .unwrap()
+      .remove(&outpoint.store())
+      .unwrap();
+    wtx.commit().unwrap();
+  }
+
   #[cfg(test)]
   pub(crate) fn statistic(&self, statistic: Statistic) -> u64 {
     self
@@ -659,6 +1201,10 @@ impl Index {
     self.begin_read()?.block_hash(height)
   }
 
+  pub(crate) fn tip(&self) -> Result<Option<(u32, BlockHash)>> {
+    self.begin_read()?.tip()
+  }
+
   pub(crate) fn blocks(&self, take: usize) -> Result<Vec<(u32, BlockHash)>> {
     let mut blocks = Vec::new();
 
@@ -708,14 +1254,15 @@ impl Index {
     )
   }
 
-  pub(crate) fn get_account_outputs(&self, address: String) -> Result<Vec<OutPoint>> {
+  /// Outputs whose script has the SHA256 `script_hash`, see `entry::script_hash`.
+  pub(crate) fn get_account_outputs(&self, script_hash: ScriptHashValue) -> Result<Vec<OutPoint>> {
     let mut result: Vec<OutPoint> = Vec::new();
 
     self
       .database
       .begin_read()?
-      .open_multimap_table(ADDRESS_TO_OUTPOINT)?
-      .get(address.as_bytes())?
+      .open_multimap_table(SCRIPT_HASH_TO_OUTPOINT)?
+      .get(&script_hash)?
       .for_each(|res| {
         if let Ok(item) = res {
           result.push(OutPoint::load(*item.value()));
@@ -727,6 +1274,47 @@ impl Index {
     Ok(result)
   }
 
+  /// Inscriptions held by `address`, oldest first, starting at `start`, and
+  /// the number of inscriptions it holds.
+  pub(crate) fn get_inscriptions_by_address(
+    &self,
+    address: &str,
+    start: usize,
+    limit: Option<usize>,
+  ) -> Result<(Vec<InscriptionId>, u64)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let inscriptions = rtx
+      .open_multimap_table(ADDRESS_TO_SEQUENCE_NUMBER)?
+      .get(address.as_bytes())?
+      .skip(start)
+      .take(limit.unwrap_or(usize::MAX))
+      .map(|result| {
+        let sequence_number = result?.value();
+        Ok(
+          InscriptionEntry::load(
+            sequence_number_to_inscription_entry
+              .get(sequence_number)?
+              .unwrap()
+              .value(),
+          )
+          .id,
+        )
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let total = rtx
+      .open_table(ADDRESS_TO_INSCRIPTION_COUNT)?
+      .get(address.as_bytes())?
+      .map(|count| count.value())
+      .unwrap_or_default();
+
+    Ok((inscriptions, total))
+  }
+
   pub(crate) fn block_header(&self, hash: BlockHash) -> Result<Option<BlockHeader>> {
     self.client.get_block_header(&hash).into_option()
   }
@@ -744,14 +1332,10 @@ impl Index {
       return Ok(None);
     }
 
-    Ok(
-      self
-        .client
-        .get_block_hash(height.into())
-        .into_option()?
-        .map(|hash| self.client.get_block(&hash))
-        .transpose()?,
-    )
+    match self.client.get_block_hash(height.into()).into_option()? {
+      Some(hash) => self.get_block_from_node(&hash),
+      None => Ok(None),
+    }
   }
 
   pub(crate) fn get_block_by_hash(&self, hash: BlockHash) -> Result<Option<Block>> {
@@ -771,10 +1355,20 @@ impl Index {
       return Ok(None);
     }
 
-    self.client.get_block(&hash).into_option()
+    self.get_block_from_node(&hash)
   }
 
-  pub fn get_inscription_ids_by_sat(&self, sat: Sat) -> Result<Vec<InscriptionId>> {
+  /// Fetches a block from Dogecoin Core. Pruned nodes fail to return blocks
+  /// they discarded, which are treated as missing instead of as errors.
+  fn get_block_from_node(&self, hash: &BlockHash) -> Result<Option<Block>> {
+    match self.client.get_block(hash).into_option() {
+      Ok(block) => Ok(block),
+      Err(_) if self.node_pruned => Ok(None),
+      Err(err) => Err(err),
+    }
+  }
+
+  pub(crate) fn get_inscription_ids_by_sat(&self, sat: Sat) -> Result<Vec<InscriptionId>> {
     let rtx = self.database.begin_read()?;
 
     let sequence_number_to_inscription_entry =
@@ -798,68 +1392,206 @@ impl Index {
     Ok(ids)
   }
 
-  pub fn get_inscription_id_by_sat_indexed(
+  /// A page of the inscriptions on `sat`, oldest first, and whether there are more.
+  pub(crate) fn get_inscription_ids_by_sat_paginated(
     &self,
     sat: Sat,
-    inscription_index: isize,
-  ) -> Result<Option<InscriptionId>> {
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
     let rtx = self.database.begin_read()?;
 
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let sat_to_sequence_number = rtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+    let mut ids = rtx
+      .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
+      .get(&sat.n())?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let sequence_number = result?.value();
+        Ok(
+          InscriptionEntry::load(
+            sequence_number_to_inscription_entry
+              .get(sequence_number)?
+              .unwrap()
+              .value(),
+          )
+          .id,
+        )
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
 
-    if inscription_index < 0 {
-      sat_to_sequence_number
-        .get(&sat.n())?
-        .nth_back((inscription_index + 1).abs_diff(0))
-    } else {
-      sat_to_sequence_number
-        .get(&sat.n())?
-        .nth(inscription_index.abs_diff(0))
+    let more = ids.len() > page_size;
+
+    if more {
+      ids.pop();
     }
-    .map(|result| {
-      result
-        .and_then(|sequence_number| {
-          let sequence_number = sequence_number.value();
-          sequence_number_to_inscription_entry
-            .get(sequence_number)
-            .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-        })
-        .map_err(|err| anyhow!(err.to_string()))
-    })
-    .transpose()
+
+    Ok((ids, more))
   }
 
-  pub(crate) fn get_inscription_id_by_inscription_number(
+  /// A page of the children of inscription `inscription_id`, in the order
+  /// they were inscribed, and whether there are more. `None` if the
+  /// inscription does not exist.
+  pub(crate) fn get_children_by_inscription_id_paginated(
     &self,
-    inscription_number: u64,
-  ) -> Result<Option<InscriptionId>> {
+    inscription_id: InscriptionId,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<Option<(Vec<InscriptionId>, bool)>> {
     let rtx = self.database.begin_read()?;
 
     let Some(sequence_number) = rtx
-      .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
-      .get(inscription_number)?
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
       .map(|guard| guard.value())
     else {
       return Ok(None);
     };
 
-    let inscription_id = rtx
-      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
-      .get(&sequence_number)?
-      .map(|entry| InscriptionEntry::load(entry.value()).id);
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut children = rtx
+      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
+      .get(sequence_number)?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let sequence_number = result?.value();
+        Ok(
+          InscriptionEntry::load(
+            sequence_number_to_inscription_entry
+              .get(sequence_number)?
+              .unwrap()
+              .value(),
+          )
+          .id,
+        )
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let more = children.len() > page_size;
+
+    if more {
+      children.pop();
+    }
+
+    Ok(Some((children, more)))
+  }
+
+  /// The parents of inscription `inscription_id`, in the order they were
+  /// declared. Parents that did not exist when it was inscribed are left out.
+  /// `None` if the inscription does not exist.
+  pub(crate) fn get_parents_by_inscription_id(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<Vec<InscriptionId>>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let entry = InscriptionEntry::load(
+      sequence_number_to_inscription_entry
+        .get(sequence_number)?
+        .unwrap()
+        .value(),
+    );
+
+    entry
+      .parents
+      .into_iter()
+      .map(|sequence_number| {
+        Ok(
+          InscriptionEntry::load(
+            sequence_number_to_inscription_entry
+              .get(sequence_number)?
+              .unwrap()
+              .value(),
+          )
+          .id,
+        )
+      })
+      .collect::<Result<Vec<InscriptionId>>>()
+      .map(Some)
+  }
+
+  pub(crate) fn get_inscription_id_by_sat_indexed(
+    &self,
+    sat: Sat,
+    inscription_index: isize,
+  ) -> Result<Option<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let sat_to_sequence_number = rtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+
+    if inscription_index < 0 {
+      sat_to_sequence_number
+        .get(&sat.n())?
+        .nth_back((inscription_index + 1).abs_diff(0))
+    } else {
+      sat_to_sequence_number
+        .get(&sat.n())?
+        .nth(inscription_index.abs_diff(0))
+    }
+    .map(|result| {
+      result
+        .and_then(|sequence_number| {
+          let sequence_number = sequence_number.value();
+          sequence_number_to_inscription_entry
+            .get(sequence_number)
+            .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
+        })
+        .map_err(|err| anyhow!(err.to_string()))
+    })
+    .transpose()
+  }
+
+  pub(crate) fn get_inscription_id_by_inscription_number(
+    &self,
+    inscription_number: u64,
+  ) -> Result<Option<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
+      .get(inscription_number)?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    let inscription_id = rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .get(&sequence_number)?
+      .map(|entry| InscriptionEntry::load(entry.value()).id);
 
     Ok(inscription_id)
   }
 
-  pub fn events_for_relic(
+  /// A page of the events of `relic`, newest first unless `oldest_first` is
+  /// set, and the total number of events of `relic`.
+  pub(crate) fn events_for_relic(
     &self,
     relic: Relic,
     page_size: usize,
     page_index: usize,
-  ) -> Result<Option<Vec<Event>>> {
+    oldest_first: bool,
+  ) -> Result<Option<(Vec<Event>, u64)>> {
     let rtx = self.database.begin_read()?;
 
     let Some(id) = rtx
@@ -870,16 +1602,24 @@ impl Index {
       return Ok(None);
     };
 
-    let events = rtx
-      .open_multimap_table(RELIC_ID_TO_EVENTS)?
-      .get(id)?
-      .rev()
-      .skip(page_index * page_size)
-      .take(page_size.saturating_add(1))
+    let events = rtx.open_multimap_table(RELIC_ID_TO_EVENTS)?.get(id)?;
+
+    let total = events.len();
+
+    let events: Box<dyn Iterator<Item = Result<redb::AccessGuard<Event>, StorageError>>> =
+      if oldest_first {
+        Box::new(events)
+      } else {
+        Box::new(events.rev())
+      };
+
+    let events = events
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size)
       .map(|result| result.map(|entry| entry.value()).map_err(|err| err.into()))
       .collect::<Result<Vec<Event>>>()?;
 
-    Ok(Some(events))
+    Ok(Some((events, total)))
   }
 
   pub fn events_for_tx(&self, txid: Txid) -> Result<Vec<Event>> {
@@ -894,145 +1634,627 @@ impl Index {
     Ok(events)
   }
 
-  pub fn has_relic_index(&self) -> bool {
-    self.index_relics
-  }
+  /// Events matching `filter` in chain order, starting at the event with key
+  /// `cursor`. Returns at most `limit` events and the key of the next matching
+  /// event, if any.
+  pub(crate) fn filter_events(
+    &self,
+    filter: &EventFilter,
+    cursor: Option<(u32, u32)>,
+    limit: usize,
+  ) -> Result<(Vec<Event>, Option<(u32, u32)>)> {
+    let rtx = self.database.begin_read()?;
 
-  pub fn get_relic_by_id(&self, id: RelicId) -> Result<Option<Relic>> {
-    Ok(
-      self
-        .database
-        .begin_read()?
-        .open_table(RELIC_ID_TO_RELIC_ENTRY)?
-        .get(&id.store())?
-        .map(|entry| RelicEntry::load(entry.value()).spaced_relic.relic),
-    )
-  }
+    let start = cursor
+      .unwrap_or_default()
+      .max((filter.from, 0))
+      .min((filter.to, u32::MAX));
+    let end = (filter.to, u32::MAX);
 
-  pub fn get_relic_by_number(&self, number: usize) -> Result<Option<Relic>> {
-    match self
-      .database
-      .begin_read()?
-      .open_table(RELIC_ID_TO_RELIC_ENTRY)?
-      .iter()?
-      .nth(number)
-    {
-      Some(result) => {
-        let rune_result =
-          result.map(|(_id, entry)| RelicEntry::load(entry.value()).spaced_relic.relic);
-        Ok(rune_result.ok())
+    let mut events = Vec::new();
+
+    if let Some(txid) = filter.txid {
+      for event in self.events_for_tx(txid)? {
+        if (event.block_height, event.event_index) >= start && filter.matches(&event) {
+          events.push(event);
+        }
+      }
+      events.sort_by_key(|event| (event.block_height, event.event_index));
+      events.truncate(limit + 1);
+    } else if let Some(relic_id) = filter.relic_id {
+      let event_key_to_event = rtx.open_table(EVENT_KEY_TO_EVENT)?;
+      for result in rtx
+        .open_table(RELIC_ID_TO_EVENT_KEY)?
+        .range((relic_id.store(), start.0, start.1)..=(relic_id.store(), end.0, end.1))?
+      {
+        let (key, _) = result?;
+        let (_, height, index) = key.value();
+        let event = event_key_to_event.get((height, index))?.unwrap().value();
+        if filter.matches(&event) {
+          events.push(event);
+          if events.len() > limit {
+            break;
+          }
+        }
+      }
+    } else if let Some(kind) = filter.kind {
+      let event_key_to_event = rtx.open_table(EVENT_KEY_TO_EVENT)?;
+      for result in rtx
+        .open_table(EVENT_KIND_TO_EVENT_KEY)?
+        .range((kind as u8, start.0, start.1)..=(kind as u8, end.0, end.1))?
+      {
+        let (key, _) = result?;
+        let (_, height, index) = key.value();
+        events.push(event_key_to_event.get((height, index))?.unwrap().value());
+        if events.len() > limit {
+          break;
+        }
+      }
+    } else {
+      for result in rtx.open_table(EVENT_KEY_TO_EVENT)?.range(start..=end)? {
+        let (_, event) = result?;
+        events.push(event.value());
+        if events.len() > limit {
+          break;
+        }
       }
-      None => Ok(None),
     }
+
+    let next = if events.len() > limit {
+      events
+        .pop()
+        .map(|event| (event.block_height, event.event_index))
+    } else {
+      None
+    };
+
+    Ok((events, next))
   }
 
-  pub fn relic(
-    &self,
-    relic: Relic,
-  ) -> Result<Option<(RelicId, RelicEntry, Option<InscriptionId>)>> {
+  /// The swap events of the `limit` blocks with the most swaps, by height.
+  pub(crate) fn get_swap_heavy_blocks(&self, limit: usize) -> Result<BTreeMap<u32, Vec<Event>>> {
     let rtx = self.database.begin_read()?;
 
-    let Some(id) = rtx
-      .open_table(RELIC_TO_RELIC_ID)?
-      .get(relic.0)?
-      .map(|guard| guard.value())
-    else {
-      return Ok(None);
-    };
+    let kind = EventKind::RelicSwapped as u8;
 
-    let entry = RelicEntry::load(
-      rtx
-        .open_table(RELIC_ID_TO_RELIC_ENTRY)?
-        .get(id)?
-        .unwrap()
-        .value(),
-    );
+    let mut swaps = BTreeMap::<u32, u64>::new();
 
-    let owner = if let Some(owner_sequence_number) = entry.owner_sequence_number {
-      Some(
-        InscriptionEntry::load(
-          rtx
-            .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
-            .get(owner_sequence_number)?
-            .unwrap()
-            .value(),
-        )
-        .id,
-      )
-    } else {
-      None
-    };
+    for result in rtx
+      .open_table(EVENT_KIND_TO_EVENT_KEY)?
+      .range((kind, 0, 0)..=(kind, u32::MAX, u32::MAX))?
+    {
+      let (key, _) = result?;
+      *swaps.entry(key.value().1).or_default() += 1;
+    }
 
-    Ok(Some((RelicId::load(id), entry, owner)))
+    let mut heights = swaps.into_iter().collect::<Vec<(u32, u64)>>();
+    heights.sort_by(|(a_height, a), (b_height, b)| b.cmp(a).then_with(|| a_height.cmp(b_height)));
+    heights.truncate(limit);
+
+    let event_key_to_event = rtx.open_table(EVENT_KEY_TO_EVENT)?;
+
+    let mut blocks = BTreeMap::new();
+
+    for (height, _) in heights {
+      let mut events = Vec::new();
+
+      for result in event_key_to_event.range((height, 0)..=(height, u32::MAX))? {
+        let event = result?.1.value();
+        if matches!(event.info, EventInfo::RelicSwapped { .. }) {
+          events.push(event);
+        }
+      }
+
+      blocks.insert(height, events);
+    }
+
+    Ok(blocks)
   }
 
-  pub fn relics(&self) -> Result<Vec<(RelicId, RelicEntry)>> {
-    let mut entries = Vec::new();
+  /// Relic activity of the block at `height`, from the events it emitted.
+  pub(crate) fn get_block_relic_activity(&self, height: u32) -> Result<BlockRelicActivity> {
+    let mut activity = BlockRelicActivity::new(height);
 
     for result in self
       .database
       .begin_read()?
-      .open_table(RELIC_ID_TO_RELIC_ENTRY)?
-      .iter()?
+      .open_table(EVENT_KEY_TO_EVENT)?
+      .range((height, 0)..=(height, u32::MAX))?
     {
-      let (id, entry) = result?;
-      entries.push((RelicId::load(id.value()), RelicEntry::load(entry.value())));
+      activity.add(&result?.1.value());
     }
 
-    Ok(entries)
+    Ok(activity)
   }
 
-  pub fn relics_paginated(
-    &self,
-    page_size: usize,
-    page_index: usize,
-  ) -> Result<(Vec<(RelicId, RelicEntry, Option<InscriptionId>)>, bool)> {
-    let rtx = self.database.begin_read()?;
-
-    let relic_id_to_relic_entry = rtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
-    let relic_to_sequence_number = rtx.open_table(RELIC_TO_SEQUENCE_NUMBER)?;
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+  /// Moves up to `EVENT_DISPATCH_BATCH_SIZE` events from the outbox into the
+  /// event channel, waiting for room if the channel is full. Returns the
+  /// number of events taken from the outbox, or `None` if there is no channel
+  /// or it was closed.
+  ///
+  /// The updater writes the keys of new events to the outbox in the same
+  /// transaction as the events themselves, so indexing never waits for
+  /// consumers, and committed events that weren't dispatched yet survive a
+  /// crash. Once events are in the channel, the key after them is stored in
+  /// the operator database, and the updater removes the keys before it from
+  /// the outbox. Events dispatched right before a crash may be dispatched
+  /// again.
+  ///
+  /// Restoring a reorg savepoint brings back the outbox keys of events
+  /// dispatched after the savepoint, which are skipped, since the operator
+  /// database isn't restored. The events of the blocks indexed again after a
+  /// reorg are dispatched, see `rewind_event_dispatch`.
+  pub(crate) fn dispatch_pending_events(&self) -> Result<Option<usize>> {
+    const EVENT_DISPATCH_BATCH_SIZE: usize = 1000;
+
+    let Some(sender) = &self.event_sender else {
+      return Ok(None);
+    };
 
-    let mut entries = Vec::new();
+    let next = self.next_dispatched_event_key()?;
 
-    for result in relic_id_to_relic_entry
-      .iter()?
-      .rev()
-      .skip(page_index.saturating_mul(page_size))
-      .take(page_size.saturating_add(1))
-    {
-      let (id_bytes, entry_bytes) = result?;
-      let relic_id = RelicId::load(id_bytes.value());
-      let relic_entry = RelicEntry::load(entry_bytes.value());
+    let events = {
+      let rtx = self.database.begin_read()?;
+      let event_key_to_event = rtx.open_table(EVENT_KEY_TO_EVENT)?;
 
-      let inscription_id_opt = if let Some(seq_number) =
-        relic_to_sequence_number.get(relic_entry.spaced_relic.relic.n())?
-      {
-        if let Some(inscription_entry_guard) =
-          sequence_number_to_inscription_entry.get(seq_number.value())?
-        {
-          let inscription_entry = InscriptionEntry::load(inscription_entry_guard.value());
-          Some(inscription_entry.id)
-        } else {
-          None
-        }
-      } else {
-        None
-      };
+      rtx
+        .open_table(EVENT_OUTBOX)?
+        .range(next..)?
+        .take(EVENT_DISPATCH_BATCH_SIZE)
+        .map(|result| {
+          let key = result?.0.value();
+          Ok((key, event_key_to_event.get(key)?.map(|event| event.value())))
+        })
+        .collect::<Result<Vec<((u32, u32), Option<Event>)>>>()?
+    };
 
-      entries.push((relic_id, relic_entry, inscription_id_opt));
+    if events.is_empty() {
+      return Ok(Some(0));
     }
 
-    let more = entries.len() > page_size;
-    if more {
-      entries.pop();
-    }
+    let mut dispatched = 0;
+    let mut last = next;
+    let mut sent = 0u64;
+
+    for (key, event) in events {
+      // keys of events that no longer exist are skipped
+      let Some(event) = event else {
+        dispatched += 1;
+        last = key;
+        continue;
+      };
+
+      let event = match sender.try_send(event) {
+        Ok(()) => None,
+        Err(tokio::sync::mpsc::error::TrySendError::Full(event)) => Some(event),
+        Err(tokio::sync::mpsc::error::TrySendError::Closed(_)) => return Ok(None),
+      };
+
+      if let Some(event) = event {
+        let start = Instant::now();
+        if sender.blocking_send(event).is_err() {
+          return Ok(None);
+        }
+        self.event_dispatch_wait_micros.fetch_add(
+          start.elapsed().as_micros().try_into().unwrap_or(u64::MAX),
+          atomic::Ordering::Relaxed,
+        );
+      }
+
+      dispatched += 1;
+      last = key;
+      sent += 1;
+    }
+
+    // the dispatcher doesn't write to the index, so it never waits for the
+    // updater to commit
+    let wtx = self.begin_operator_write()?;
+
+    wtx
+      .open_table(NEXT_DISPATCHED_EVENT_KEY)?
+      .insert((), (last.0, last.1 + 1))?;
+
+    wtx.commit()?;
+
+    self
+      .events_dispatched
+      .fetch_add(sent, atomic::Ordering::Relaxed);
+
+    Ok(Some(dispatched))
+  }
+
+  /// Key of the first event the dispatcher didn't move into the event channel
+  /// yet. Outbox keys before it belong to dispatched events.
+  pub(crate) fn next_dispatched_event_key(&self) -> Result<(u32, u32)> {
+    let rtx = self.operator_database.begin_read()?;
+
+    let table = match rtx.open_table(NEXT_DISPATCHED_EVENT_KEY) {
+      Ok(table) => table,
+      Err(redb::TableError::TableDoesNotExist(_)) => return Ok((0, 0)),
+      Err(err) => return Err(err.into()),
+    };
+
+    Ok(table.get(())?.map(|key| key.value()).unwrap_or_default())
+  }
+
+  /// Dispatches the events at `block_count` and above again, which a reorg
+  /// replaced with the events of the blocks of the new chain. Events of the
+  /// orphaned blocks that were already dispatched are not retracted.
+  pub(crate) fn rewind_event_dispatch(&self, block_count: u32) -> Result {
+    if self.next_dispatched_event_key()? <= (block_count, 0) {
+      return Ok(());
+    }
+
+    let wtx = self.begin_operator_write()?;
+
+    wtx
+      .open_table(NEXT_DISPATCHED_EVENT_KEY)?
+      .insert((), (block_count, 0))?;
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn event_outbox_info(&self) -> Result<EventOutboxInfo> {
+    let next = self.next_dispatched_event_key()?;
+
+    Ok(EventOutboxInfo {
+      pending: self
+        .database
+        .begin_read()?
+        .open_table(EVENT_OUTBOX)?
+        .range(next..)?
+        .count()
+        .try_into()
+        .unwrap(),
+      queued: self.event_sender.as_ref().map_or(0, |sender| {
+        (sender.max_capacity() - sender.capacity())
+          .try_into()
+          .unwrap()
+      }),
+      dispatched: self.events_dispatched.load(atomic::Ordering::Relaxed),
+      blocked_seconds: Duration::from_micros(
+        self
+          .event_dispatch_wait_micros
+          .load(atomic::Ordering::Relaxed),
+      )
+      .as_secs_f64(),
+    })
+  }
+
+  pub fn events_for_address(&self, address: &Address, from_height: u32) -> Result<Vec<Event>> {
+    let rtx = self.database.begin_read()?;
+
+    let event_key_to_event = rtx.open_table(EVENT_KEY_TO_EVENT)?;
+
+    let mut events = Vec::new();
+
+    for result in rtx
+      .open_multimap_table(ADDRESS_TO_EVENTS)?
+      .get(address.to_string().as_bytes())?
+    {
+      let (height, index) = result?.value();
+
+      if height < from_height {
+        continue;
+      }
+
+      if let Some(event) = event_key_to_event.get((height, index))? {
+        events.push(event.value());
+      }
+    }
+
+    Ok(events)
+  }
+
+  pub(crate) fn address_digest(
+    &self,
+    address: &Address,
+    period: DigestPeriod,
+  ) -> Result<AddressDigest> {
+    let to_height = self.block_count()?.saturating_sub(1);
+    let from_height = (to_height + 1).saturating_sub(period.blocks());
+
+    let events = self.events_for_address(address, from_height)?;
+
+    let rtx = self.database.begin_read()?;
+
+    let relic_id_to_relic_entry = rtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
+
+    let mut received = BTreeMap::new();
+    let mut spent = BTreeMap::new();
+
+    for event in &events {
+      let (totals, relic_id, amount) = match event.info {
+        EventInfo::RelicReceived {
+          relic_id, amount, ..
+        } => (&mut received, relic_id, amount),
+        EventInfo::RelicSpent {
+          relic_id, amount, ..
+        } => (&mut spent, relic_id, amount),
+        _ => continue,
+      };
+
+      let Some(entry) = relic_id_to_relic_entry.get(relic_id.store())? else {
+        continue;
+      };
+
+      *totals
+        .entry(RelicEntry::load(entry.value()).spaced_relic)
+        .or_default() += amount;
+    }
+
+    let claimable = rtx
+      .open_table(RELIC_OWNER_TO_CLAIMABLE)?
+      .get(&RelicOwner(address.script_pubkey().script_hash()).store())?
+      .map(|amount| amount.value())
+      .unwrap_or_default();
+
+    let sequence_number_to_chest = rtx.open_table(SEQUENCE_NUMBER_TO_CHEST)?;
+    let syndicate_id_to_syndicate_entry = rtx.open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)?;
+    let satpoint_to_sequence_number = rtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+    let satpoint_to_inscription_id = rtx.open_table(SATPOINT_TO_INSCRIPTION_ID)?;
+
+    let mut chest_unlocks = Vec::new();
+
+    for outpoint in self.get_account_outputs(entry::script_hash(&address.script_pubkey()))? {
+      for result in Self::inscriptions_on_output(
+        &satpoint_to_sequence_number,
+        &satpoint_to_inscription_id,
+        outpoint,
+      )? {
+        let (_, inscription_id, sequence_numbers) = result?;
+
+        for sequence_number in sequence_numbers {
+          let Some(chest) = sequence_number_to_chest.get(sequence_number)? else {
+            continue;
+          };
+          let chest = ChestEntry::load(chest.value());
+
+          let Some(syndicate) = syndicate_id_to_syndicate_entry.get(chest.syndicate.store())?
+          else {
+            continue;
+          };
+          let unlock_height = chest.unlock_height(SyndicateEntry::load(syndicate.value()).lock);
+
+          if unlock_height > u64::from(to_height)
+            && unlock_height <= u64::from(to_height) + u64::from(period.blocks())
+          {
+            chest_unlocks.push(ChestUnlock {
+              inscription_id,
+              syndicate: chest.syndicate,
+              amount: chest.amount,
+              unlock_height,
+            });
+          }
+        }
+      }
+    }
+
+    chest_unlocks.sort_by_key(|unlock| unlock.unlock_height);
+
+    Ok(AddressDigest {
+      address: address.to_string(),
+      period,
+      from_height,
+      to_height,
+      events: events.len(),
+      received,
+      spent,
+      claimable,
+      chest_unlocks,
+    })
+  }
+
+  pub(crate) fn path(&self) -> &Path {
+    &self.path
+  }
+
+  pub(crate) fn has_relic_index(&self) -> bool {
+    self.index_relics
+  }
+
+  pub(crate) fn has_relic_fee_index(&self) -> bool {
+    self.index_relic_fees
+  }
+
+  pub(crate) fn has_relic_history_index(&self) -> bool {
+    self.index_relic_history
+  }
+
+  /// Whether transactions relevant to inscriptions and relics, or all of
+  /// them, are stored in the index.
+  pub(crate) fn has_raw_transaction_index(&self) -> bool {
+    self.index_transactions || self.index_raw_transactions
+  }
+
+  pub(crate) fn get_relic_by_id(&self, id: RelicId) -> Result<Option<Relic>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(RELIC_ID_TO_RELIC_ENTRY)?
+        .get(&id.store())?
+        .map(|entry| RelicEntry::load(entry.value()).spaced_relic.relic),
+    )
+  }
+
+  pub(crate) fn get_relic_entry_by_id(&self, id: RelicId) -> Result<Option<RelicEntry>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(RELIC_ID_TO_RELIC_ENTRY)?
+        .get(&id.store())?
+        .map(|entry| RelicEntry::load(entry.value())),
+    )
+  }
+
+  /// Human readable amounts of a relic event, keyed by the name of the raw field.
+  pub(crate) fn formatted_event_amounts(&self, event: &Event) -> Result<BTreeMap<String, String>> {
+    let mut formatted = BTreeMap::new();
+
+    for (field, relic_id, amount) in event.amounts() {
+      if let Some(entry) = self.get_relic_entry_by_id(relic_id)? {
+        formatted.insert(field.into(), entry.pile(amount).to_string());
+      }
+    }
+
+    Ok(formatted)
+  }
+
+  /// Relics whose name starts with `prefix`, for search suggestions.
+  pub(crate) fn search_relics(
+    &self,
+    prefix: &str,
+    limit: usize,
+  ) -> Result<Vec<(RelicId, RelicEntry)>> {
+    let rtx = self.database.begin_read()?;
+
+    search::relics_by_prefix(
+      &rtx.open_multimap_table(RELIC_PREFIX_TO_RELIC_ID)?,
+      &rtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?,
+      prefix,
+      limit,
+    )
+  }
+
+  pub(crate) fn get_relic_id_by_number(&self, number: u64) -> Result<Option<RelicId>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(RELIC_NUMBER_TO_RELIC_ID)?
+        .get(number)?
+        .map(|id| RelicId::load(id.value())),
+    )
+  }
+
+  pub(crate) fn get_relic_by_number(&self, number: usize) -> Result<Option<Relic>> {
+    match self
+      .database
+      .begin_read()?
+      .open_table(RELIC_ID_TO_RELIC_ENTRY)?
+      .iter()?
+      .nth(number)
+    {
+      Some(result) => {
+        let rune_result =
+          result.map(|(_id, entry)| RelicEntry::load(entry.value()).spaced_relic.relic);
+        Ok(rune_result.ok())
+      }
+      None => Ok(None),
+    }
+  }
+
+  pub(crate) fn relic(
+    &self,
+    relic: Relic,
+  ) -> Result<Option<(RelicId, RelicEntry, Option<InscriptionId>)>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(id) = rtx
+      .open_table(RELIC_TO_RELIC_ID)?
+      .get(relic.0)?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    let entry = RelicEntry::load(
+      rtx
+        .open_table(RELIC_ID_TO_RELIC_ENTRY)?
+        .get(id)?
+        .unwrap()
+        .value(),
+    );
+
+    let owner = if let Some(owner_sequence_number) = entry.owner_sequence_number {
+      Some(
+        InscriptionEntry::load(
+          rtx
+            .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+            .get(owner_sequence_number)?
+            .unwrap()
+            .value(),
+        )
+        .id,
+      )
+    } else {
+      None
+    };
+
+    Ok(Some((RelicId::load(id), entry, owner)))
+  }
+
+  pub(crate) fn relics(&self) -> Result<Vec<(RelicId, RelicEntry)>> {
+    let mut entries = Vec::new();
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_table(RELIC_ID_TO_RELIC_ENTRY)?
+      .iter()?
+    {
+      let (id, entry) = result?;
+      entries.push((RelicId::load(id.value()), RelicEntry::load(entry.value())));
+    }
+
+    Ok(entries)
+  }
+
+  pub(crate) fn relics_paginated(
+    &self,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<(RelicId, RelicEntry, Option<InscriptionId>)>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let relic_id_to_relic_entry = rtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
+    let relic_to_sequence_number = rtx.open_table(RELIC_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut entries = Vec::new();
+
+    for result in relic_id_to_relic_entry
+      .iter()?
+      .rev()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+    {
+      let (id_bytes, entry_bytes) = result?;
+      let relic_id = RelicId::load(id_bytes.value());
+      let relic_entry = RelicEntry::load(entry_bytes.value());
+
+      let inscription_id_opt = if let Some(seq_number) =
+        relic_to_sequence_number.get(relic_entry.spaced_relic.relic.n())?
+      {
+        if let Some(inscription_entry_guard) =
+          sequence_number_to_inscription_entry.get(seq_number.value())?
+        {
+          let inscription_entry = InscriptionEntry::load(inscription_entry_guard.value());
+          Some(inscription_entry.id)
+        } else {
+          None
+        }
+      } else {
+        None
+      };
+
+      entries.push((relic_id, relic_entry, inscription_id_opt));
+    }
+
+    let more = entries.len() > page_size;
+    if more {
+      entries.pop();
+    }
 
     Ok((entries, more))
   }
 
-  pub fn sealing(&self, relic: Relic) -> Result<(Option<api::Inscription>, Option<Txid>)> {
+  pub(crate) fn sealing(&self, relic: Relic) -> Result<(Option<api::Inscription>, Option<Txid>)> {
     let rtx = self.database.begin_read()?;
 
     let relic_to_sequence_number = rtx.open_table(RELIC_TO_SEQUENCE_NUMBER)?;
@@ -1070,7 +2292,7 @@ impl Index {
     Ok((None, None))
   }
 
-  pub fn sealings_paginated(
+  pub(crate) fn sealings_paginated(
     &self,
     page_size: usize,
     page_index: usize,
@@ -1136,13 +2358,199 @@ impl Index {
     Ok((results, more))
   }
 
-  pub fn syndicate(
+  /// Sealed tickers that have not been enshrined yet, oldest sealing first.
+  pub(crate) fn get_pending_sealings(
     &self,
-    id: SyndicateId,
-  ) -> Result<Option<(SyndicateId, SyndicateEntry, Option<InscriptionId>)>> {
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<SealingHistory>, bool)> {
     let rtx = self.database.begin_read()?;
 
-    let Some(entry) = rtx
+    let mut sealings = Vec::new();
+
+    for result in rtx
+      .open_table(PENDING_SEALING_SEQUENCE_NUMBERS)?
+      .iter()?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+    {
+      let (sequence_number, _) = result?;
+      sealings.extend(Self::sealing_history(&rtx, sequence_number.value())?);
+    }
+
+    let more = sealings.len() > page_size;
+    sealings.truncate(page_size);
+
+    Ok((sealings, more))
+  }
+
+  pub(crate) fn get_sealing_history(&self, relic: Relic) -> Result<Option<SealingHistory>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(RELIC_TO_SEQUENCE_NUMBER)?
+      .get(relic.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    Self::sealing_history(&rtx, sequence_number)
+  }
+
+  /// Checks sealing `spaced_relic` and enshrining it with `enshrining`
+  /// against the rules the updater enforces. The sealing fee is only checked
+  /// if the `inputs` of the sealing transaction are given.
+  pub(crate) fn check_enshrining(
+    &self,
+    spaced_relic: SpacedRelic,
+    enshrining: &Enshrining,
+    inputs: Option<&[OutPoint]>,
+  ) -> Result<EnshriningCheck> {
+    let rtx = self.database.begin_read()?;
+
+    let mut violations = Vec::new();
+
+    if spaced_relic == SpacedRelic::from_str(RELIC_NAME)? {
+      violations.push(EnshriningViolation::ReservedName);
+    } else if rtx
+      .open_table(RELIC_TO_SEQUENCE_NUMBER)?
+      .get(spaced_relic.relic.n())?
+      .is_some()
+    {
+      violations.push(EnshriningViolation::AlreadySealed);
+    }
+
+    if rtx
+      .open_table(RELIC_TO_RELIC_ID)?
+      .get(spaced_relic.relic.n())?
+      .is_some()
+    {
+      violations.push(EnshriningViolation::AlreadyEnshrined);
+    }
+
+    violations.extend(enshrining.violations());
+
+    let sealing_fee = self.bones_config.sealing_fee(spaced_relic.relic);
+
+    let balance = match inputs {
+      Some(inputs) => {
+        let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RELIC_BALANCES)?;
+
+        let mut balance = 0u128;
+
+        for outpoint in inputs {
+          let Some(balances) = outpoint_to_balances.get(&outpoint.store())? else {
+            continue;
+          };
+
+          let buffer = balances.value();
+          let mut i = 0;
+          while i < buffer.len() {
+            let ((id, amount), length) = Index::decode_relic_balance(&buffer[i..])?;
+            i += length;
+
+            if id == RELIC_ID {
+              balance = balance.saturating_add(amount);
+            }
+          }
+        }
+
+        if balance < sealing_fee {
+          violations.push(EnshriningViolation::InsufficientSealingFee {
+            fee: sealing_fee,
+            balance,
+          });
+        }
+
+        Some(balance)
+      }
+      None => None,
+    };
+
+    Ok(EnshriningCheck {
+      spaced_relic,
+      sealing_fee,
+      balance,
+      violations,
+    })
+  }
+
+  pub(crate) fn get_sealing_stats(&self) -> Result<SealingStats> {
+    let rtx = self.database.begin_read()?;
+
+    let statistic_to_count = rtx.open_table(STATISTIC_TO_COUNT)?;
+    let statistic = |statistic: Statistic| -> Result<u64> {
+      Ok(
+        statistic_to_count
+          .get(&statistic.key())?
+          .map(|x| x.value())
+          .unwrap_or(0),
+      )
+    };
+
+    Ok(SealingStats::new(
+      rtx.open_table(PENDING_SEALING_SEQUENCE_NUMBERS)?.len()?,
+      statistic(Statistic::Enshrinings)?,
+      statistic(Statistic::BlocksToEnshrine)?,
+    ))
+  }
+
+  fn sealing_history(
+    rtx: &redb::ReadTransaction,
+    sequence_number: u32,
+  ) -> Result<Option<SealingHistory>> {
+    let Some(spaced_relic) = rtx
+      .open_table(SEQUENCE_NUMBER_TO_SPACED_RELIC)?
+      .get(sequence_number)?
+      .map(|guard| SpacedRelic::load(guard.value()))
+    else {
+      return Ok(None);
+    };
+
+    let Some(inscription) = rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .get(sequence_number)?
+      .map(|guard| InscriptionEntry::load(guard.value()))
+    else {
+      return Ok(None);
+    };
+
+    let enshrining = match rtx
+      .open_table(RELIC_TO_RELIC_ID)?
+      .get(spaced_relic.relic.store())?
+      .map(|guard| RelicId::load(guard.value()))
+    {
+      Some(id) => rtx
+        .open_table(RELIC_ID_TO_RELIC_ENTRY)?
+        .get(id.store())?
+        .map(|guard| RelicEntry::load(guard.value()))
+        .map(|entry| EnshriningHistory {
+          id,
+          txid: entry.enshrining,
+          block: entry.block,
+          timestamp: entry.timestamp,
+          blocks_to_enshrine: entry.block.saturating_sub(inscription.height.into()),
+        }),
+      None => None,
+    };
+
+    Ok(Some(SealingHistory {
+      spaced_relic,
+      inscription: inscription.id,
+      sealed_block: inscription.height,
+      sealed_timestamp: inscription.timestamp,
+      enshrining,
+    }))
+  }
+
+  pub(crate) fn syndicate(
+    &self,
+    id: SyndicateId,
+  ) -> Result<Option<(SyndicateId, SyndicateEntry, Option<InscriptionId>)>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(entry) = rtx
       .open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)?
       .get(id.store())?
       .map(|guard| guard.value())
@@ -1166,185 +2574,1791 @@ impl Index {
     Ok(Some((id, entry, owner)))
   }
 
-  pub fn syndicates(&self) -> Result<Vec<(SyndicateId, SyndicateEntry)>> {
+  pub(crate) fn syndicates(&self) -> Result<Vec<(SyndicateId, SyndicateEntry)>> {
+    let mut entries = Vec::new();
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)?
+      .iter()?
+    {
+      let (id, entry) = result?;
+      entries.push((
+        SyndicateId::load(id.value()),
+        SyndicateEntry::load(entry.value()),
+      ));
+    }
+
+    Ok(entries)
+  }
+
+  pub(crate) fn syndicates_paginated(
+    &self,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<(SyndicateId, SyndicateEntry)>, bool)> {
+    let mut entries = Vec::new();
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)?
+      .iter()?
+      .rev()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+    {
+      let (id, entry) = result?;
+      entries.push((
+        SyndicateId::load(id.value()),
+        SyndicateEntry::load(entry.value()),
+      ));
+    }
+
+    let more = entries.len() > page_size;
+
+    Ok((entries, more))
+  }
+
+  pub(crate) fn get_chest(&self, inscription_id: InscriptionId) -> Result<Option<ChestEntry>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    Ok(
+      rtx
+        .open_table(SEQUENCE_NUMBER_TO_CHEST)?
+        .get(sequence_number)?
+        .map(|entry| ChestEntry::load(entry.value())),
+    )
+  }
+
+  pub(crate) fn get_subsidy_schedule(&self, id: RelicId) -> Result<Option<SubsidyScheduleEntry>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(RELIC_ID_TO_SUBSIDY_SCHEDULE)?
+        .get(id.store())?
+        .map(|entry| SubsidyScheduleEntry::load(entry.value())),
+    )
+  }
+
+  /// Owners of the sealing inscription of relic `id`, oldest first, or `None`
+  /// if the relic has no owner.
+  pub(crate) fn get_relic_owner_history(&self, id: RelicId) -> Result<Option<Vec<OwnerChange>>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(RELIC_ID_TO_RELIC_ENTRY)?
+      .get(id.store())?
+      .and_then(|entry| RelicEntry::load(entry.value()).owner_sequence_number)
+    else {
+      return Ok(None);
+    };
+
+    let mut changes = Vec::new();
+    for result in rtx
+      .open_table(SEQUENCE_NUMBER_TO_OWNER_CHANGE)?
+      .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+    {
+      changes.push(result?.1.value());
+    }
+
+    Ok(Some(changes))
+  }
+
+  /// `RelicBurned` events of relic `id`, newest first, and whether there are
+  /// more.
+  pub(crate) fn get_relic_burns(
+    &self,
+    id: RelicId,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<Event>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let event_key_to_event = rtx.open_table(EVENT_KEY_TO_EVENT)?;
+
+    let mut burns = Vec::new();
+    for result in rtx
+      .open_table(RELIC_ID_TO_BURN_EVENT_KEY)?
+      .range((id.store(), 0, 0)..=(id.store(), u32::MAX, u32::MAX))?
+      .rev()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+    {
+      let (key, _) = result?;
+      let (_, height, index) = key.value();
+      burns.push(event_key_to_event.get((height, index))?.unwrap().value());
+    }
+
+    let more = burns.len() > page_size;
+    if more {
+      burns.pop();
+    }
+
+    Ok((burns, more))
+  }
+
+  /// `ChestTransferred` events of the chest on `inscription_id`, oldest first.
+  /// These remain available after the chest has been released.
+  pub(crate) fn get_chest_transfers(&self, inscription_id: InscriptionId) -> Result<Vec<Event>> {
+    self.get_chest_events(inscription_id, EventKind::ChestTransferred)
+  }
+
+  /// Events of `kind` of the chest on `inscription_id`, oldest first. Only
+  /// transfers, withdrawals and relocks name their chest.
+  pub(crate) fn get_chest_events(
+    &self,
+    inscription_id: InscriptionId,
+    kind: EventKind,
+  ) -> Result<Vec<Event>> {
+    let rtx = self.database.begin_read()?;
+
+    let event_key_to_event = rtx.open_table(EVENT_KEY_TO_EVENT)?;
+
+    let inscription_id = inscription_id.store();
+
+    let mut chest_events = Vec::new();
+    for result in rtx
+      .open_table(INSCRIPTION_ID_TO_CHEST_EVENT_KEY)?
+      .range((&inscription_id, 0, 0)..=(&inscription_id, u32::MAX, u32::MAX))?
+    {
+      let (key, _) = result?;
+      let (_, height, index) = key.value();
+      let event = event_key_to_event.get((height, index))?.unwrap().value();
+      if event.info.kind() == kind {
+        chest_events.push(event);
+      }
+    }
+
+    Ok(chest_events)
+  }
+
+  /// Chests of `syndicate` in creation order, along with their inscription ids.
+  pub(crate) fn get_syndicate_chests_paginated(
+    &self,
+    syndicate: SyndicateId,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<(InscriptionId, ChestEntry)>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_chest = rtx.open_table(SEQUENCE_NUMBER_TO_CHEST)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut entries = Vec::new();
+
+    for result in rtx
+      .open_multimap_table(SYNDICATE_TO_CHEST_SEQUENCE_NUMBER)?
+      .get(syndicate.store())?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+    {
+      let sequence_number = result?.value();
+
+      let chest = ChestEntry::load(
+        sequence_number_to_chest
+          .get(sequence_number)?
+          .unwrap()
+          .value(),
+      );
+
+      let inscription_id = InscriptionEntry::load(
+        sequence_number_to_inscription_entry
+          .get(sequence_number)?
+          .unwrap()
+          .value(),
+      )
+      .id;
+
+      entries.push((inscription_id, chest));
+    }
+
+    let more = entries.len() > page_size;
+
+    Ok((entries, more))
+  }
+
+  /// Chests unlocking at heights `from..=to`, of `syndicate` or of all
+  /// syndicates, by unlock height, along with their inscription ids and
+  /// syndicates. Also returns the amounts unlocking per treasure in the whole
+  /// range, and whether there are more chests than `page_size`.
+  pub(crate) fn get_chest_unlocks_paginated(
+    &self,
+    syndicate: Option<SyndicateId>,
+    from: u64,
+    to: u64,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(
+    Vec<(InscriptionId, ChestEntry, SyndicateEntry)>,
+    BTreeMap<SpacedRelic, u128>,
+    bool,
+  )> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_chest = rtx.open_table(SEQUENCE_NUMBER_TO_CHEST)?;
+    let syndicate_id_to_syndicate_entry = rtx.open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)?;
+
+    let load_syndicate = |id: SyndicateId| -> Result<SyndicateEntry> {
+      Ok(SyndicateEntry::load(
+        syndicate_id_to_syndicate_entry
+          .get(id.store())?
+          .unwrap()
+          .value(),
+      ))
+    };
+
+    // chests of a single syndicate are fewer than the chests unlocking in most
+    // ranges, so they are filtered instead of looked up by unlock height
+    let unlocks = match syndicate {
+      Some(syndicate) => {
+        let lock = load_syndicate(syndicate)?.lock;
+
+        let mut unlocks = Vec::new();
+
+        for result in rtx
+          .open_multimap_table(SYNDICATE_TO_CHEST_SEQUENCE_NUMBER)?
+          .get(syndicate.store())?
+        {
+          let sequence_number = result?.value();
+
+          let chest = ChestEntry::load(
+            sequence_number_to_chest
+              .get(sequence_number)?
+              .unwrap()
+              .value(),
+          );
+
+          let unlock_height = chest.unlock_height(lock);
+
+          if (from..=to).contains(&unlock_height) {
+            unlocks.push((unlock_height, sequence_number));
+          }
+        }
+
+        unlocks.sort();
+        unlocks
+      }
+      None => rtx
+        .open_table(CHEST_UNLOCK_HEIGHT_TO_SEQUENCE_NUMBER)?
+        .range((from, 0)..=(to, u32::MAX))?
+        .map(|result| result.map(|(key, _)| key.value()))
+        .collect::<Result<Vec<(u64, u32)>, StorageError>>()?,
+    };
+
+    let relic_id_to_relic_entry = rtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut syndicates = HashMap::new();
+    let mut totals = BTreeMap::new();
     let mut entries = Vec::new();
 
-    for result in self
-      .database
-      .begin_read()?
-      .open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)?
-      .iter()?
-    {
+    let start = page_index.saturating_mul(page_size);
+
+    for (i, (_, sequence_number)) in unlocks.into_iter().enumerate() {
+      let chest = ChestEntry::load(
+        sequence_number_to_chest
+          .get(sequence_number)?
+          .unwrap()
+          .value(),
+      );
+
+      let syndicate = match syndicates.get(&chest.syndicate) {
+        Some(syndicate) => *syndicate,
+        None => {
+          let syndicate = load_syndicate(chest.syndicate)?;
+          syndicates.insert(chest.syndicate, syndicate);
+          syndicate
+        }
+      };
+
+      let treasure = RelicEntry::load(
+        relic_id_to_relic_entry
+          .get(syndicate.treasure.store())?
+          .unwrap()
+          .value(),
+      )
+      .spaced_relic;
+
+      *totals.entry(treasure).or_default() += chest.amount;
+
+      if i >= start && i - start <= page_size {
+        let inscription_id = InscriptionEntry::load(
+          sequence_number_to_inscription_entry
+            .get(sequence_number)?
+            .unwrap()
+            .value(),
+        )
+        .id;
+
+        entries.push((inscription_id, chest, syndicate));
+      }
+    }
+
+    let more = entries.len() > page_size;
+
+    Ok((entries, totals, more))
+  }
+
+  pub(crate) fn get_relic_balances_for_outpoint(
+    &self,
+    outpoint: OutPoint,
+  ) -> Result<BTreeMap<SpacedRelic, Pile>> {
+    let rtx = self.database.begin_read()?;
+
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RELIC_BALANCES)?;
+
+    let id_to_relic_entries = rtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
+
+    let Some(balances) = outpoint_to_balances.get(&outpoint.store())? else {
+      return Ok(BTreeMap::new());
+    };
+
+    let balances_buffer = balances.value();
+
+    let mut balances = BTreeMap::new();
+    let mut i = 0;
+    while i < balances_buffer.len() {
+      let ((id, amount), length) = Index::decode_relic_balance(&balances_buffer[i..]).unwrap();
+      i += length;
+
+      let entry = RelicEntry::load(id_to_relic_entries.get(id.store())?.unwrap().value());
+
+      balances.insert(
+        entry.spaced_relic,
+        Pile {
+          amount,
+          divisibility: Enshrining::DIVISIBILITY,
+          symbol: entry.symbol,
+        },
+      );
+    }
+
+    Ok(balances)
+  }
+
+  pub(crate) fn get_relic_balance_map(
+    &self,
+  ) -> Result<BTreeMap<SpacedRelic, BTreeMap<OutPoint, Pile>>> {
+    let outpoint_balances = self.get_relic_balances()?;
+
+    let rtx = self.database.begin_read()?;
+
+    let relic_id_to_relic_entry = rtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
+
+    let mut relic_balances_by_id: BTreeMap<RelicId, BTreeMap<OutPoint, u128>> = BTreeMap::new();
+
+    for (outpoint, balances) in outpoint_balances {
+      for (relic_id, amount) in balances {
+        *relic_balances_by_id
+          .entry(relic_id)
+          .or_default()
+          .entry(outpoint)
+          .or_default() += amount;
+      }
+    }
+
+    let mut relic_balances = BTreeMap::new();
+
+    for (relic_id, balances) in relic_balances_by_id {
+      let RelicEntry {
+        spaced_relic,
+        symbol,
+        ..
+      } = RelicEntry::load(
+        relic_id_to_relic_entry
+          .get(&relic_id.store())?
+          .unwrap()
+          .value(),
+      );
+
+      relic_balances.insert(
+        spaced_relic,
+        balances
+          .into_iter()
+          .map(|(outpoint, amount)| {
+            (
+              outpoint,
+              Pile {
+                amount,
+                divisibility: Enshrining::DIVISIBILITY,
+                symbol,
+              },
+            )
+          })
+          .collect(),
+      );
+    }
+
+    Ok(relic_balances)
+  }
+
+  pub(crate) fn get_relic_balances(&self) -> Result<Vec<(OutPoint, Vec<(RelicId, u128)>)>> {
+    let mut result = Vec::new();
+
+    for entry in self
+      .database
+      .begin_read()?
+      .open_table(OUTPOINT_TO_RELIC_BALANCES)?
+      .iter()?
+    {
+      let (outpoint, balances_buffer) = entry?;
+      let outpoint = OutPoint::load(*outpoint.value());
+      let balances_buffer = balances_buffer.value();
+
+      let mut balances = Vec::new();
+      let mut i = 0;
+      while i < balances_buffer.len() {
+        let ((id, balance), length) = Index::decode_relic_balance(&balances_buffer[i..]).unwrap();
+        i += length;
+        balances.push((id, balance));
+      }
+
+      result.push((outpoint, balances));
+    }
+
+    Ok(result)
+  }
+
+  /// Distribution of relic `id` among the scripts of the unspent outputs
+  /// holding it.
+  pub(crate) fn get_relic_distribution(&self, id: RelicId) -> Result<RelicDistribution> {
+    let mut holders = HashMap::<Script, u128>::new();
+
+    for (outpoint, balances) in self.get_relic_balances()? {
+      let balance = balances
+        .into_iter()
+        .filter(|(relic_id, _)| *relic_id == id)
+        .map(|(_, balance)| balance)
+        .sum::<u128>();
+
+      if balance == 0 {
+        continue;
+      }
+
+      let txout = self
+        .get_txout(outpoint)?
+        .ok_or_else(|| anyhow!("output {outpoint} holding {id} not found"))?;
+
+      *holders.entry(txout.script_pubkey).or_default() += balance;
+    }
+
+    Ok(RelicDistribution::new(holders.into_values().collect()))
+  }
+
+  /// Relic balances of the unspent outputs of `address`, valued with the
+  /// current pools, see `RelicValuation`.
+  pub(crate) fn get_relic_portfolio(&self, address: &Address) -> Result<RelicPortfolio> {
+    let height = u64::from(self.block_count()?);
+
+    let mut balances = BTreeMap::<RelicId, u128>::new();
+
+    {
+      let rtx = self.database.begin_read()?;
+      let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RELIC_BALANCES)?;
+
+      for outpoint in self.get_account_outputs(entry::script_hash(&address.script_pubkey()))? {
+        let Some(buffer) = outpoint_to_balances.get(&outpoint.store())? else {
+          continue;
+        };
+
+        let buffer = buffer.value();
+
+        let mut i = 0;
+        while i < buffer.len() {
+          let ((id, amount), length) = Index::decode_relic_balance(&buffer[i..])?;
+          i += length;
+
+          let balance = balances.entry(id).or_default();
+          *balance = balance.saturating_add(amount);
+        }
+      }
+    }
+
+    let mut relics = Vec::new();
+
+    for (id, amount) in balances {
+      let entry = self
+        .get_relic_entry_by_id(id)?
+        .ok_or_else(|| anyhow!("relic {id} held by {address} not found"))?;
+
+      relics.push(RelicValuation::new(id, &entry, amount, height));
+    }
+
+    Ok(RelicPortfolio::new(relics))
+  }
+
+  pub(crate) fn get_relic_checkpoint(&self, height: u32) -> Result<Option<sha256::Hash>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(HEIGHT_TO_RELIC_CHECKPOINT)?
+        .get(&height)?
+        .map(|checkpoint| sha256::Hash::from_inner(*checkpoint.value())),
+    )
+  }
+
+  /// Appends `entry` to the audit log and returns its id.
+  ///
+  /// The audit log lives in the index database, so a reorg that restores a
+  /// savepoint also drops the entries written after it.
+  pub(crate) fn insert_audit_entry(&self, entry: &AuditEntry) -> Result<u64> {
+    let wtx = self.database.begin_write()?;
+
+    let id = {
+      let mut audit_log = wtx.open_table(AUDIT_LOG_ID_TO_ENTRY)?;
+
+      let id = audit_log
+        .last()?
+        .map(|(id, _)| id.value() + 1)
+        .unwrap_or_default();
+
+      audit_log.insert(id, serde_json::to_string(entry)?.as_str())?;
+
+      id
+    };
+
+    wtx.commit()?;
+
+    Ok(id)
+  }
+
+  /// Audit log entries, newest first.
+  pub(crate) fn get_audit_log(
+    &self,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<(u64, AuditEntry)>, bool)> {
+    let mut entries = Vec::new();
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_table(AUDIT_LOG_ID_TO_ENTRY)?
+      .iter()?
+      .rev()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+    {
+      let (id, entry) = result?;
+      entries.push((id.value(), serde_json::from_str(entry.value())?));
+    }
+
+    let more = entries.len() > page_size;
+    entries.truncate(page_size);
+
+    Ok((entries, more))
+  }
+
+  /// Labels `address`, replacing its previous label, or removes its label if
+  /// `label` is `None`. Returns whether the address had a label before.
+  ///
+  /// Labels live in the operator database, so they survive reorgs.
+  pub(crate) fn set_address_label(
+    &self,
+    address: &Address,
+    label: Option<&AddressLabel>,
+  ) -> Result<bool> {
+    let wtx = self.begin_operator_write()?;
+
+    let existed = {
+      let mut labels = wtx.open_table(ADDRESS_TO_LABEL)?;
+      let address = address.to_string();
+
+      match label {
+        Some(label) => labels
+          .insert(address.as_str(), serde_json::to_string(label)?.as_str())?
+          .is_some(),
+        None => labels.remove(address.as_str())?.is_some(),
+      }
+    };
+
+    wtx.commit()?;
+
+    Ok(existed)
+  }
+
+  pub(crate) fn get_address_label(&self, address: &Address) -> Result<Option<AddressLabel>> {
+    let rtx = self.operator_database.begin_read()?;
+
+    let labels = match rtx.open_table(ADDRESS_TO_LABEL) {
+      Ok(labels) => labels,
+      Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+      Err(err) => return Err(err.into()),
+    };
+
+    labels
+      .get(address.to_string().as_str())?
+      .map(|label| serde_json::from_str(label.value()).map_err(Into::into))
+      .transpose()
+  }
+
+  /// Content of inscription `id` cached by the server, see `Delegates`.
+  pub(crate) fn get_cached_content(&self, id: InscriptionId) -> Result<Option<Vec<u8>>> {
+    let rtx = self.operator_database.begin_read()?;
+
+    let table = match rtx.open_table(INSCRIPTION_ID_TO_CONTENT) {
+      Ok(table) => table,
+      Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+      Err(err) => return Err(err.into()),
+    };
+
+    Ok(
+      table
+        .get(&id.store())?
+        .map(|content| content.value().to_vec()),
+    )
+  }
+
+  pub(crate) fn cache_content(&self, id: InscriptionId, content: &[u8]) -> Result {
+    let wtx = self.begin_operator_write()?;
+
+    wtx
+      .open_table(INSCRIPTION_ID_TO_CONTENT)?
+      .insert(&id.store(), content)?;
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  /// Labeled addresses, ordered by address.
+  pub(crate) fn get_address_labels(&self) -> Result<Vec<(String, AddressLabel)>> {
+    let rtx = self.operator_database.begin_read()?;
+
+    let table = match rtx.open_table(ADDRESS_TO_LABEL) {
+      Ok(table) => table,
+      Err(redb::TableError::TableDoesNotExist(_)) => return Ok(Vec::new()),
+      Err(err) => return Err(err.into()),
+    };
+
+    let mut labels = Vec::new();
+
+    for result in table.iter()? {
+      let (address, label) = result?;
+      labels.push((
+        address.value().to_string(),
+        serde_json::from_str(label.value())?,
+      ));
+    }
+
+    Ok(labels)
+  }
+
+  /// Cumulative swap fees paid by `address` in every pool it swapped in,
+  /// denominated in the base token.
+  pub(crate) fn get_relic_fees_paid(&self, address: &Address) -> Result<Vec<(SpacedRelic, u128)>> {
+    let rtx = self.database.begin_read()?;
+
+    let relic_id_to_relic_entry = rtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
+
+    let owner = RelicOwner(address.script_pubkey().script_hash()).store();
+
+    let mut fees = Vec::new();
+
+    for result in rtx
+      .open_table(RELIC_OWNER_TO_FEES_PAID)?
+      .range((&owner, (0, 0))..=(&owner, (u64::MAX, u32::MAX)))?
+    {
+      let (key, fee) = result?;
+
+      let (_owner, id) = key.value();
+
+      let entry = RelicEntry::load(relic_id_to_relic_entry.get(id)?.unwrap().value());
+
+      fees.push((entry.spaced_relic, fee.value()));
+    }
+
+    Ok(fees)
+  }
+
+  /// Relic balances of `address` after block `height`, requires
+  /// `--index-relic-history`.
+  pub(crate) fn get_relic_balances_at(
+    &self,
+    address: &Address,
+    height: u32,
+  ) -> Result<BTreeMap<SpacedRelic, Pile>> {
+    let rtx = self.database.begin_read()?;
+
+    let relic_id_to_relic_entry = rtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
+    let journal = rtx.open_table(RELIC_BALANCE_JOURNAL)?;
+
+    let address = address.to_string();
+
+    let mut balances = BTreeMap::new();
+
+    for result in rtx
+      .open_multimap_table(ADDRESS_TO_JOURNALED_RELIC_ID)?
+      .get(address.as_str())?
+    {
+      let id = result?.value();
+
+      let balance = journal
+        .range((id, address.as_str(), 0)..=(id, address.as_str(), height))?
+        .next_back()
+        .transpose()?
+        .map(|(_, balance)| balance.value())
+        .unwrap_or_default();
+
+      if balance == 0 {
+        continue;
+      }
+
+      let entry = RelicEntry::load(relic_id_to_relic_entry.get(id)?.unwrap().value());
+
+      balances.insert(
+        entry.spaced_relic,
+        Pile {
+          amount: balance,
+          divisibility: Enshrining::DIVISIBILITY,
+          symbol: entry.symbol,
+        },
+      );
+    }
+
+    Ok(balances)
+  }
+
+  /// Addresses holding relic `id` after block `height` and their balances,
+  /// largest first, requires `--index-relic-history`.
+  pub(crate) fn get_relic_holders_at(
+    &self,
+    id: RelicId,
+    height: u32,
+  ) -> Result<Vec<(String, u128)>> {
+    let journal = self
+      .database
+      .begin_read()?
+      .open_table(RELIC_BALANCE_JOURNAL)?;
+
+    let mut holders = Vec::new();
+
+    // journal entries are ordered by address, then height, so jump from
+    // address to address and read the last entry of each up to `height`,
+    // instead of reading every entry of the relic
+    let mut next = journal.range((id.store(), "", 0)..)?.next().transpose()?;
+
+    while let Some((key, _)) = next {
+      let (relic_id, address, _) = key.value();
+
+      if relic_id != id.store() {
+        break;
+      }
+
+      let balance = journal
+        .range((id.store(), address, 0)..=(id.store(), address, height))?
+        .next_back()
+        .transpose()?
+        .map(|(_, balance)| balance.value())
+        .unwrap_or_default();
+
+      if balance > 0 {
+        holders.push((address.to_string(), balance));
+      }
+
+      next = journal
+        .range((
+          Bound::Excluded((id.store(), address, u32::MAX)),
+          Bound::Unbounded,
+        ))?
+        .next()
+        .transpose()?;
+    }
+
+    holders.sort_by(|(a, a_amount), (b, b_amount)| b_amount.cmp(a_amount).then_with(|| a.cmp(b)));
+
+    Ok(holders)
+  }
+
+  /// The first inscription on the enshrining outputs of relic `id`, recorded
+  /// when the relic was enshrined.
+  pub(crate) fn get_relic_thumbnail(&self, id: RelicId) -> Result<Option<InscriptionId>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(RELIC_ID_TO_THUMBNAIL)?
+        .get(id.store())?
+        .map(|thumbnail| InscriptionId::load(thumbnail.value())),
+    )
+  }
+
+  /// Supply of relic `id` at the end of every block between `from` and `to`
+  /// that changed it, up to `limit` blocks, along with the supply before
+  /// `from`, if any. The supply in blocks without a record is that of the
+  /// previous record.
+  pub(crate) fn get_relic_supply_history(
+    &self,
+    id: RelicId,
+    from: u32,
+    to: u32,
+    limit: usize,
+  ) -> Result<(Option<RelicSupply>, Vec<(u32, RelicSupply)>)> {
+    let rtx = self.database.begin_read()?;
+    let table = rtx.open_table(RELIC_ID_TO_SUPPLY)?;
+
+    let previous = table
+      .range((id.store(), 0)..(id.store(), from))?
+      .next_back()
+      .transpose()?
+      .map(|(_, supply)| RelicSupply::load(supply.value()));
+
+    let history = table
+      .range((id.store(), from)..=(id.store(), to))?
+      .take(limit)
+      .map(|result| {
+        result
+          .map(|(key, supply)| (key.value().1, RelicSupply::load(supply.value())))
+          .map_err(|err| err.into())
+      })
+      .collect::<Result<Vec<(u32, RelicSupply)>>>()?;
+
+    Ok((previous, history))
+  }
+
+  /// Time-weighted average pool price of relic `id` over the blocks from
+  /// `from` to `to`, together with the number of blocks it was averaged over.
+  /// The price in blocks without a record is that of the previous record, and
+  /// blocks before the pool was created or after it was drained are left out.
+  /// `None` if the relic had no pool in the whole range.
+  pub(crate) fn get_relic_twap(
+    &self,
+    id: RelicId,
+    from: u32,
+    to: u32,
+  ) -> Result<Option<(u128, u32)>> {
+    let rtx = self.database.begin_read()?;
+    let table = rtx.open_table(RELIC_ID_TO_POOL_PRICE)?;
+
+    let mut previous = table
+      .range((id.store(), 0)..(id.store(), from))?
+      .next_back()
+      .transpose()?
+      .map(|(_, price)| (from, price.value()));
+
+    let mut sum = 0u128;
+    let mut blocks = 0u32;
+
+    let mut accumulate = |(height, price): (u32, u128), until: u32| {
+      if price == NO_POOL_PRICE {
+        return;
+      }
+      sum = sum.saturating_add(price.saturating_mul((until - height).into()));
+      blocks += until - height;
+    };
+
+    for result in table.range((id.store(), from)..=(id.store(), to))? {
+      let (key, price) = result?;
+      let height = key.value().1;
+      if let Some(previous) = previous {
+        accumulate(previous, height);
+      }
+      previous = Some((height, price.value()));
+    }
+
+    if let Some(previous) = previous {
+      accumulate(previous, to.saturating_add(1));
+    }
+
+    Ok((blocks > 0).then(|| (sum / u128::from(blocks), blocks)))
+  }
+
+  /// The `limit` relics with the highest `metric` summed over the blocks from
+  /// `from` to `to`, highest first. Relics without activity are left out.
+  pub(crate) fn get_relic_leaderboard(
+    &self,
+    metric: ActivityMetric,
+    from: u32,
+    to: u32,
+    limit: usize,
+  ) -> Result<Vec<(RelicId, RelicActivity)>> {
+    let rtx = self.database.begin_read()?;
+    let table = rtx.open_table(HEIGHT_TO_RELIC_ACTIVITY)?;
+
+    let mut totals = HashMap::<RelicId, RelicActivity>::new();
+
+    for result in table.range((from, (0, 0))..=(to, (u64::MAX, u32::MAX)))? {
+      let (key, activity) = result?;
+      let total = totals.entry(RelicId::load(key.value().1)).or_default();
+      *total = total.add(RelicActivity::load(activity.value()));
+    }
+
+    let mut leaderboard = totals
+      .into_iter()
+      .filter(|(_, activity)| activity.metric(metric) > 0)
+      .collect::<Vec<(RelicId, RelicActivity)>>();
+
+    leaderboard.sort_unstable_by(|(a_id, a), (b_id, b)| {
+      b.metric(metric)
+        .cmp(&a.metric(metric))
+        .then_with(|| a_id.cmp(b_id))
+    });
+    leaderboard.truncate(limit);
+
+    Ok(leaderboard)
+  }
+
+  /// Events that `tx` would emit if it was mined in the next block. Nothing
+  /// is written to the index.
+  pub(crate) fn simulate_relic_transaction(&self, tx: &Transaction) -> Result<Vec<Event>> {
+    updater::simulate_relic_transaction(self, tx)
+  }
+
+  /// Estimated mint-out of relic `id`, extrapolated from its mints in the last
+  /// `RelicEntry::ESTIMATE_BLOCKS` blocks.
+  pub(crate) fn get_relic_estimates(
+    &self,
+    id: RelicId,
+    entry: &RelicEntry,
+  ) -> Result<Option<RelicEstimates>> {
+    let Some(height) = self.height()? else {
+      return Ok(None);
+    };
+    let height = u64::from(height.n());
+
+    // mints before the relic was enshrined are all zero
+    let since = height
+      .saturating_sub(RelicEntry::ESTIMATE_BLOCKS)
+      .max(entry.block.saturating_sub(1));
+
+    let previous = self
+      .database
+      .begin_read()?
+      .open_table(RELIC_ID_TO_SUPPLY)?
+      .range((id.store(), 0)..=(id.store(), u32::try_from(since)?))?
+      .next_back()
+      .transpose()?
+      .map(|(_, supply)| RelicSupply::load(supply.value()).mints)
+      .unwrap_or_default();
+
+    let recent_mints = entry.state.mints.saturating_sub(previous);
+
+    Ok(entry.estimates(height, recent_mints, height.saturating_sub(since)))
+  }
+
+  /// Base and quote amounts swapped with the pool of relic `id` in blocks from
+  /// height `since` on.
+  pub(crate) fn get_relic_swap_volume(&self, id: RelicId, since: u32) -> Result<(u128, u128)> {
+    let rtx = self.database.begin_read()?;
+
+    let mut base_volume = 0u128;
+    let mut quote_volume = 0u128;
+
+    for result in rtx
+      .open_multimap_table(RELIC_ID_TO_EVENTS)?
+      .get(id.store())?
+      .rev()
+    {
+      let event = result?.value();
+
+      if event.block_height < since {
+        break;
+      }
+
+      if let EventInfo::RelicSwapped {
+        base_amount,
+        quote_amount,
+        ..
+      } = event.info
+      {
+        base_volume = base_volume.saturating_add(base_amount);
+        quote_volume = quote_volume.saturating_add(quote_amount);
+      }
+    }
+
+    Ok((base_volume, quote_volume))
+  }
+
+  /// Pool swaps that sell `amount` of relic `from` for relic `to` in the next
+  /// block, in the order the updater executes a `Swap`: with the pool of the
+  /// other relic if either is the base token, otherwise by selling `from` for
+  /// base tokens and buying `to` with them.
+  pub(crate) fn get_swap_route(
+    &self,
+    from: RelicId,
+    to: RelicId,
+    amount: u128,
+  ) -> Result<Result<Vec<SwapHop>, RelicError>> {
+    let height = u64::from(self.block_count()?);
+
+    let legs = match (from, to) {
+      (RELIC_ID, _) => vec![(to, SwapDirection::BaseToQuote)],
+      (_, RELIC_ID) => vec![(from, SwapDirection::QuoteToBase)],
+      _ => vec![
+        (from, SwapDirection::QuoteToBase),
+        (to, SwapDirection::BaseToQuote),
+      ],
+    };
+
+    let mut hops = Vec::new();
+    let mut input = amount;
+
+    for (id, direction) in legs {
+      let Some(entry) = self.get_relic_entry_by_id(id)? else {
+        return Ok(Err(RelicError::RelicNotFound(id)));
+      };
+
+      match entry.swap_hop(id, direction, input, height) {
+        Ok(hop) => {
+          input = hop.output_amount;
+          hops.push(hop);
+        }
+        Err(err) => return Ok(Err(err)),
+      }
+    }
+
+    Ok(Ok(hops))
+  }
+
+  /// Cumulative fees paid for `relic`, or for all relics if `None`, up to the
+  /// end of block `height`.
+  pub(crate) fn get_relic_fees(&self, relic: Option<Relic>, height: u32) -> Result<RelicFees> {
+    let rtx = self.database.begin_read()?;
+
+    let fees = match relic {
+      Some(relic) => rtx
+        .open_table(RELIC_TO_FEES)?
+        .range((relic.n(), 0)..=(relic.n(), height))?
+        .next_back()
+        .transpose()?
+        .map(|(_, fees)| RelicFees::load(fees.value())),
+      None => rtx
+        .open_table(HEIGHT_TO_FEES)?
+        .range(0..=height)?
+        .next_back()
+        .transpose()?
+        .map(|(_, fees)| RelicFees::load(fees.value())),
+    };
+
+    Ok(fees.unwrap_or_default())
+  }
+
+  /// Cumulative fees paid for `relic`, or for all relics if `None`, before
+  /// `from`, and at every block from `from` to `to` that paid fees.
+  pub(crate) fn get_relic_fees_history(
+    &self,
+    relic: Option<Relic>,
+    from: u32,
+    to: u32,
+    limit: usize,
+  ) -> Result<(Option<RelicFees>, Vec<(u32, RelicFees)>)> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(relic) = relic else {
+      let table = rtx.open_table(HEIGHT_TO_FEES)?;
+
+      let previous = table
+        .range(0..from)?
+        .next_back()
+        .transpose()?
+        .map(|(_, fees)| RelicFees::load(fees.value()));
+
+      let history = table
+        .range(from..=to)?
+        .take(limit)
+        .map(|result| {
+          result
+            .map(|(height, fees)| (height.value(), RelicFees::load(fees.value())))
+            .map_err(|err| err.into())
+        })
+        .collect::<Result<Vec<(u32, RelicFees)>>>()?;
+
+      return Ok((previous, history));
+    };
+
+    let table = rtx.open_table(RELIC_TO_FEES)?;
+
+    let previous = table
+      .range((relic.n(), 0)..(relic.n(), from))?
+      .next_back()
+      .transpose()?
+      .map(|(_, fees)| RelicFees::load(fees.value()));
+
+    let history = table
+      .range((relic.n(), from)..=(relic.n(), to))?
+      .take(limit)
+      .map(|result| {
+        result
+          .map(|(key, fees)| (key.value().1, RelicFees::load(fees.value())))
+          .map_err(|err| err.into())
+      })
+      .collect::<Result<Vec<(u32, RelicFees)>>>()?;
+
+    Ok((previous, history))
+  }
+
+  /// Counts of cenotaphs and failed relic operations by kind, for at most
+  /// `limit` blocks from `from` to `to` that had any.
+  pub(crate) fn get_relic_error_counts(
+    &self,
+    from: u32,
+    to: u32,
+    limit: usize,
+  ) -> Result<Vec<(u32, BTreeMap<String, u64>)>> {
+    let rtx = self.database.begin_read()?;
+    let table = rtx.open_table(HEIGHT_TO_RELIC_ERROR_COUNTS)?;
+
+    let mut blocks: Vec<(u32, BTreeMap<String, u64>)> = Vec::new();
+
+    for result in table.range((from, "")..)? {
+      let (key, count) = result?;
+      let (height, kind) = key.value();
+
+      if height > to {
+        break;
+      }
+
+      match blocks.last_mut() {
+        Some((last, counts)) if *last == height => {
+          counts.insert(kind.to_string(), count.value());
+        }
+        _ => {
+          if blocks.len() == limit {
+            break;
+          }
+          blocks.push((height, [(kind.to_string(), count.value())].into()));
+        }
+      }
+    }
+
+    Ok(blocks)
+  }
+
+  pub(crate) fn get_relic_claimable(&self) -> Result<Vec<(RelicOwner, u128)>> {
+    let mut result = Vec::new();
+
+    for entry in self
+      .database
+      .begin_read()?
+      .open_table(RELIC_OWNER_TO_CLAIMABLE)?
+      .iter()?
+    {
+      let (owner, amount) = entry?;
+      result.push((RelicOwner::load(*owner.value()), amount.value()));
+    }
+
+    Ok(result)
+  }
+
+  /// Records the blocks indexed so far as a fixture, see `RelicFixture`.
+  pub(crate) fn relic_fixture(&self) -> Result<RelicFixture> {
+    let rtx = self.database.begin_read()?;
+
+    let height_to_block_hash = rtx.open_table(HEIGHT_TO_BLOCK_HASH)?;
+
+    let mut headers = Vec::new();
+    for result in height_to_block_hash.range(1..)? {
+      let (_, hash) = result?;
+      let header = self
+        .block_header(BlockHash::load(*hash.value()))?
+        .context("block header not found")?;
+      headers.push(hex::encode(consensus::encode::serialize(&header)));
+    }
+
+    // jump from block to block, instead of reading every event
+    let event_key_to_event = rtx.open_table(EVENT_KEY_TO_EVENT)?;
+    let mut heights = Vec::new();
+    let mut next = 1;
+    while let Some(result) = event_key_to_event.range((next, 0)..)?.next() {
+      let (key, _) = result?;
+      let (height, _) = key.value();
+      heights.push(height);
+      next = height + 1;
+    }
+
+    let mut blocks = BTreeMap::new();
+    let mut recorded = HashSet::new();
+    let mut inputs = BTreeSet::new();
+
+    for height in heights {
+      let hash = height_to_block_hash
+        .get(height)?
+        .map(|hash| BlockHash::load(*hash.value()))
+        .context("event height is not indexed")?;
+
+      let block = self
+        .get_block_from_node(&hash)?
+        .with_context(|| format!("block {height} not found"))?;
+
+      for tx in &block.txdata {
+        recorded.insert(tx.txid());
+        inputs.extend(
+          tx.input
+            .iter()
+            .filter(|input| !input.previous_output.is_null())
+            .map(|input| input.previous_output.txid),
+        );
+      }
+
+      blocks.insert(
+        height,
+        block
+          .txdata
+          .iter()
+          .map(|tx| hex::encode(consensus::encode::serialize(tx)))
+          .collect(),
+      );
+    }
+
+    let mut spent = Vec::new();
+    for txid in inputs {
+      if recorded.contains(&txid) {
+        continue;
+      }
+
+      let tx = self
+        .get_transaction(txid)?
+        .with_context(|| format!("spent transaction {txid} not found"))?;
+
+      spent.push(hex::encode(consensus::encode::serialize(&tx)));
+    }
+
+    Ok(RelicFixture {
+      chain: self.chain,
+      headers,
+      blocks,
+      spent,
+    })
+  }
+
+  pub(crate) fn relic_snapshot(&self) -> Result<RelicSnapshot> {
+    let rtx = self.database.begin_read()?;
+
+    let Some((height, block_hash)) = rtx
+      .open_table(HEIGHT_TO_BLOCK_HASH)?
+      .last()?
+      .map(|(height, hash)| (height.value(), BlockHash::load(*hash.value())))
+    else {
+      bail!("cannot export relic state from an empty index");
+    };
+
+    let mut relics = Vec::new();
+    for result in rtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?.iter()? {
+      let (id, entry) = result?;
+      relics.push((RelicId::load(id.value()), RelicEntry::load(entry.value())));
+    }
+
+    let mut thumbnails = Vec::new();
+    for result in rtx.open_table(RELIC_ID_TO_THUMBNAIL)?.iter()? {
+      let (id, thumbnail) = result?;
+      thumbnails.push((
+        RelicId::load(id.value()),
+        InscriptionId::load(thumbnail.value()),
+      ));
+    }
+
+    let mut sealings = Vec::new();
+    for result in rtx.open_table(SEQUENCE_NUMBER_TO_SPACED_RELIC)?.iter()? {
+      let (sequence_number, spaced_relic) = result?;
+      sealings.push((
+        sequence_number.value(),
+        SpacedRelic::load(spaced_relic.value()),
+      ));
+    }
+
+    let mut balances = Vec::new();
+    for result in rtx.open_table(OUTPOINT_TO_RELIC_BALANCES)?.iter()? {
+      let (outpoint, buffer) = result?;
+      let buffer = buffer.value();
+      let mut outpoint_balances = Vec::new();
+      let mut i = 0;
+      while i < buffer.len() {
+        let ((id, balance), length) = Index::decode_relic_balance(&buffer[i..])?;
+        i += length;
+        outpoint_balances.push((id, balance));
+      }
+      balances.push((OutPoint::load(*outpoint.value()), outpoint_balances));
+    }
+
+    let mut claimable = Vec::new();
+    for result in rtx.open_table(RELIC_OWNER_TO_CLAIMABLE)?.iter()? {
+      let (owner, amount) = result?;
+      claimable.push((RelicOwner::load(*owner.value()), amount.value()));
+    }
+
+    let mut liquidity_shares = Vec::new();
+    for result in rtx.open_table(LIQUIDITY_PROVIDER_TO_SHARES)?.iter()? {
+      let (key, shares) = result?;
+      let (id, provider) = key.value();
+      liquidity_shares.push((
+        RelicId::load(id),
+        RelicOwner::load(*provider),
+        shares.value(),
+      ));
+    }
+
+    let mut syndicates = Vec::new();
+    for result in rtx.open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)?.iter()? {
       let (id, entry) = result?;
-      entries.push((
+      syndicates.push((
         SyndicateId::load(id.value()),
         SyndicateEntry::load(entry.value()),
       ));
     }
 
-    Ok(entries)
+    let mut chests = Vec::new();
+    for result in rtx.open_table(SEQUENCE_NUMBER_TO_CHEST)?.iter()? {
+      let (sequence_number, entry) = result?;
+      chests.push((sequence_number.value(), ChestEntry::load(entry.value())));
+    }
+
+    let mut subsidy_schedules = Vec::new();
+    for result in rtx.open_table(RELIC_ID_TO_SUBSIDY_SCHEDULE)?.iter()? {
+      let (id, entry) = result?;
+      subsidy_schedules.push((
+        RelicId::load(id.value()),
+        SubsidyScheduleEntry::load(entry.value()),
+      ));
+    }
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+
+    // the latest inscriptions by sequence number and by inscription number
+    let latest = sequence_number_to_inscription_entry
+      .last()?
+      .map(|(sequence_number, _)| sequence_number.value())
+      .into_iter()
+      .chain(
+        rtx
+          .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
+          .last()?
+          .map(|(_, sequence_number)| sequence_number.value()),
+      )
+      .collect::<Vec<u32>>();
+
+    // all inscriptions the relic state refers to by sequence number
+    let sequence_numbers = relics
+      .iter()
+      .filter_map(|(_, entry)| entry.owner_sequence_number)
+      .chain(sealings.iter().map(|(sequence_number, _)| *sequence_number))
+      .chain(syndicates.iter().map(|(_, entry)| entry.sequence_number))
+      .chain(chests.iter().map(|(sequence_number, _)| *sequence_number))
+      .chain(latest)
+      .collect::<BTreeSet<u32>>();
+
+    let mut inscriptions = Vec::new();
+    for sequence_number in sequence_numbers {
+      let Some(entry) = sequence_number_to_inscription_entry.get(sequence_number)? else {
+        bail!("relic state refers to unknown inscription sequence number {sequence_number}");
+      };
+      let entry = InscriptionEntry::load(entry.value());
+      let satpoint = sequence_number_to_satpoint
+        .get(sequence_number)?
+        .map(|satpoint| SatPoint::load(*satpoint.value()))
+        .unwrap_or_default();
+      inscriptions.push(SnapshotInscription {
+        sequence_number,
+        id: entry.id,
+        number: entry.inscription_number,
+        charms: entry.charms,
+        fee: entry.fee,
+        height: entry.height,
+        timestamp: entry.timestamp,
+        satpoint,
+      });
+    }
+
+    Ok(RelicSnapshot {
+      height,
+      block_hash,
+      relics,
+      thumbnails,
+      sealings,
+      inscriptions,
+      balances,
+      claimable,
+      liquidity_shares,
+      syndicates,
+      chests,
+      subsidy_schedules,
+    })
   }
 
-  pub fn syndicates_paginated(
-    &self,
-    page_size: usize,
-    page_index: usize,
-  ) -> Result<(Vec<(SyndicateId, SyndicateEntry)>, bool)> {
-    let mut entries = Vec::new();
+  /// Cross-checks the relic tables against each other. Every relic held by
+  /// outputs, chests, claimable balances or locked in a pool must add up to
+  /// the relic supply ever created minus what was burned.
+  pub(crate) fn verify_relic_integrity(&self) -> Result<IntegrityReport> {
+    let rtx = self.database.begin_read()?;
 
-    for result in self
-      .database
-      .begin_read()?
-      .open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)?
-      .iter()?
-      .rev()
-      .skip(page_index.saturating_mul(page_size))
-      .take(page_size.saturating_add(1))
-    {
+    let height = rtx
+      .open_table(HEIGHT_TO_BLOCK_HASH)?
+      .last()?
+      .map(|(height, _)| height.value());
+
+    let mut violations = Vec::new();
+    let mut held: BTreeMap<RelicId, u128> = BTreeMap::new();
+
+    let mut outputs = 0;
+    for result in rtx.open_table(OUTPOINT_TO_RELIC_BALANCES)?.iter()? {
+      let (_, buffer) = result?;
+      let buffer = buffer.value();
+      let mut i = 0;
+      while i < buffer.len() {
+        let ((id, balance), length) = Index::decode_relic_balance(&buffer[i..])?;
+        i += length;
+        *held.entry(id).or_default() += balance;
+      }
+      outputs += 1;
+    }
+
+    let mut syndicates = BTreeMap::new();
+    for result in rtx.open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)?.iter()? {
       let (id, entry) = result?;
-      entries.push((
+      syndicates.insert(
         SyndicateId::load(id.value()),
         SyndicateEntry::load(entry.value()),
-      ));
+      );
     }
 
-    let more = entries.len() > page_size;
+    let mut chests = 0;
+    for result in rtx.open_table(SEQUENCE_NUMBER_TO_CHEST)?.iter()? {
+      let (sequence_number, entry) = result?;
+      let chest = ChestEntry::load(entry.value());
+      match syndicates.get(&chest.syndicate) {
+        Some(syndicate) => *held.entry(syndicate.treasure).or_default() += chest.amount,
+        None => violations.push(IntegrityViolation::ChestSyndicate {
+          sequence_number: sequence_number.value(),
+          syndicate: chest.syndicate,
+        }),
+      }
+      chests += 1;
+    }
 
-    Ok((entries, more))
-  }
+    // claimable balances are swap fees and royalties, always paid in the base token
+    for result in rtx.open_table(RELIC_OWNER_TO_CLAIMABLE)?.iter()? {
+      let (_, amount) = result?;
+      *held.entry(RELIC_ID).or_default() += amount.value();
+    }
 
-  pub fn get_relic_balances_for_outpoint(
-    &self,
-    outpoint: OutPoint,
-  ) -> Result<BTreeMap<SpacedRelic, Pile>> {
-    let rtx = self.database.begin_read()?;
+    let mut provider_shares: BTreeMap<RelicId, u128> = BTreeMap::new();
+    for result in rtx.open_table(LIQUIDITY_PROVIDER_TO_SHARES)?.iter()? {
+      let (key, shares) = result?;
+      let (id, _) = key.value();
+      *provider_shares.entry(RelicId::load(id)).or_default() += shares.value();
+    }
 
-    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RELIC_BALANCES)?;
+    let mut relics = Vec::new();
+    for result in rtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?.iter()? {
+      let (id, entry) = result?;
+      let id = RelicId::load(id.value());
+      let entry = RelicEntry::load(entry.value());
+      // mint payments and pool reserves are held in the base token
+      *held.entry(RELIC_ID).or_default() += entry.locked_base_supply();
+      if let Some(pool) = entry.pool {
+        if pool.base_supply == 0 || pool.quote_supply == 0 {
+          violations.push(IntegrityViolation::PoolReserves {
+            relic: id,
+            base_supply: pool.base_supply,
+            quote_supply: pool.quote_supply,
+          });
+        }
+        let shares = provider_shares.get(&id).copied().unwrap_or_default();
+        if shares > pool.shares {
+          violations.push(IntegrityViolation::PoolShares {
+            relic: id,
+            provider_shares: shares,
+            pool_shares: pool.shares,
+          });
+        }
+      }
+      relics.push((id, entry));
+    }
 
-    let id_to_relic_entries = rtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
+    for (id, entry) in &relics {
+      let balance = held.remove(id).unwrap_or_default();
+      let circulating_supply = entry.circulating_supply();
+      if balance != circulating_supply {
+        violations.push(IntegrityViolation::Supply {
+          relic: *id,
+          held: balance,
+          burned: entry.state.burned,
+          circulating_supply,
+        });
+      }
+    }
 
-    let Some(balances) = outpoint_to_balances.get(&outpoint.store())? else {
-      return Ok(BTreeMap::new());
-    };
+    for (id, balance) in held {
+      violations.push(IntegrityViolation::UnknownRelic {
+        relic: id,
+        held: balance,
+      });
+    }
 
-    let balances_buffer = balances.value();
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+
+    let mut sealings = 0;
+    for result in rtx.open_table(SEQUENCE_NUMBER_TO_SPACED_RELIC)?.iter()? {
+      let (sequence_number, spaced_relic) = result?;
+      let sequence_number = sequence_number.value();
+      if sequence_number_to_inscription_entry
+        .get(sequence_number)?
+        .is_none()
+        || sequence_number_to_satpoint.get(sequence_number)?.is_none()
+      {
+        violations.push(IntegrityViolation::SealingInscription {
+          sequence_number,
+          spaced_relic: SpacedRelic::load(spaced_relic.value()),
+        });
+      }
+      sealings += 1;
+    }
 
-    let mut balances = BTreeMap::new();
-    let mut i = 0;
-    while i < balances_buffer.len() {
-      let ((id, amount), length) = Index::decode_relic_balance(&balances_buffer[i..]).unwrap();
-      i += length;
+    Ok(IntegrityReport {
+      height,
+      relics: relics.len(),
+      outputs,
+      syndicates: syndicates.len(),
+      chests,
+      sealings,
+      violations,
+    })
+  }
 
-      let entry = RelicEntry::load(id_to_relic_entries.get(id.store())?.unwrap().value());
+  pub(crate) fn import_relic_snapshot(&self, snapshot: RelicSnapshot) -> Result {
+    if !self.index_relics {
+      bail!("cannot import relic state into an index without `--index-relics`");
+    }
 
-      balances.insert(
-        entry.spaced_relic,
-        Pile {
-          amount,
-          divisibility: Enshrining::DIVISIBILITY,
-          symbol: entry.symbol,
-        },
+    if self.index_sats {
+      bail!("cannot import relic state into an index with `--index-sats`");
+    }
+
+    if self.block_count()? != 0 {
+      bail!("cannot import relic state into an index that already contains blocks");
+    }
+
+    let block_hash = self.client.get_block_hash(snapshot.height.into())?;
+    if block_hash != snapshot.block_hash {
+      bail!(
+        "snapshot block hash {} does not match block {} on chain: {block_hash}",
+        snapshot.block_hash,
+        snapshot.height,
       );
     }
 
-    Ok(balances)
-  }
+    let wtx = self.begin_write()?;
 
-  pub fn get_relic_balance_map(&self) -> Result<BTreeMap<SpacedRelic, BTreeMap<OutPoint, Pile>>> {
-    let outpoint_balances = self.get_relic_balances()?;
+    wtx
+      .open_table(HEIGHT_TO_BLOCK_HASH)?
+      .insert(&snapshot.height, &snapshot.block_hash.store())?;
 
-    let rtx = self.database.begin_read()?;
+    {
+      let mut relic_id_to_relic_entry = wtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
+      let mut relic_to_relic_id = wtx.open_table(RELIC_TO_RELIC_ID)?;
+      let mut transaction_id_to_relic = wtx.open_table(TRANSACTION_ID_TO_RELIC)?;
+      let mut relic_prefix_to_relic_id = wtx.open_multimap_table(RELIC_PREFIX_TO_RELIC_ID)?;
+      let mut relic_number_to_relic_id = wtx.open_table(RELIC_NUMBER_TO_RELIC_ID)?;
+
+      for (id, entry) in &snapshot.relics {
+        let relic = entry.spaced_relic.relic;
+        relic_id_to_relic_entry.insert(id.store(), entry.store())?;
+        relic_to_relic_id.insert(relic.store(), id.store())?;
+        transaction_id_to_relic.insert(&entry.enshrining.store(), relic.store())?;
+        search::insert_relic(
+          &mut relic_prefix_to_relic_id,
+          &mut relic_number_to_relic_id,
+          relic,
+          entry.number,
+          *id,
+        )?;
+      }
 
-    let relic_id_to_relic_entry = rtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
+      wtx.open_table(STATISTIC_TO_COUNT)?.insert(
+        &Statistic::Relics.key(),
+        &u64::try_from(snapshot.relics.len())?,
+      )?;
+    }
 
-    let mut relic_balances_by_id: BTreeMap<RelicId, BTreeMap<OutPoint, u128>> = BTreeMap::new();
+    {
+      let mut relic_id_to_thumbnail = wtx.open_table(RELIC_ID_TO_THUMBNAIL)?;
 
-    for (outpoint, balances) in outpoint_balances {
-      for (relic_id, amount) in balances {
-        *relic_balances_by_id
-          .entry(relic_id)
-          .or_default()
-          .entry(outpoint)
-          .or_default() += amount;
+      for (id, thumbnail) in &snapshot.thumbnails {
+        relic_id_to_thumbnail.insert(id.store(), thumbnail.store())?;
       }
     }
 
-    let mut relic_balances = BTreeMap::new();
+    {
+      let mut relic_to_sequence_number = wtx.open_table(RELIC_TO_SEQUENCE_NUMBER)?;
+      let mut sequence_number_to_spaced_relic = wtx.open_table(SEQUENCE_NUMBER_TO_SPACED_RELIC)?;
+
+      let mut pending_sealing_sequence_numbers =
+        wtx.open_table(PENDING_SEALING_SEQUENCE_NUMBERS)?;
+
+      let enshrined = snapshot
+        .relics
+        .iter()
+        .map(|(_, entry)| entry.spaced_relic.relic)
+        .collect::<HashSet<Relic>>();
+
+      for (sequence_number, spaced_relic) in &snapshot.sealings {
+        relic_to_sequence_number.insert(spaced_relic.relic.store(), sequence_number)?;
+        sequence_number_to_spaced_relic.insert(sequence_number, spaced_relic.store())?;
+        if !enshrined.contains(&spaced_relic.relic) {
+          pending_sealing_sequence_numbers.insert(sequence_number, ())?;
+        }
+      }
 
-    for (relic_id, balances) in relic_balances_by_id {
-      let RelicEntry {
-        spaced_relic,
-        symbol,
-        ..
-      } = RelicEntry::load(
-        relic_id_to_relic_entry
-          .get(&relic_id.store())?
-          .unwrap()
-          .value(),
-      );
+      let sealed_heights = snapshot
+        .inscriptions
+        .iter()
+        .map(|inscription| (inscription.sequence_number, inscription.height))
+        .collect::<HashMap<u32, u32>>();
+
+      let mut enshrinings = 0;
+      let mut blocks_to_enshrine = 0;
+      for (_, entry) in &snapshot.relics {
+        let Some(sealed) = entry
+          .owner_sequence_number
+          .and_then(|sequence_number| sealed_heights.get(&sequence_number))
+        else {
+          continue;
+        };
+        enshrinings += 1;
+        blocks_to_enshrine += entry.block.saturating_sub((*sealed).into());
+      }
 
-      relic_balances.insert(
-        spaced_relic,
-        balances
-          .into_iter()
-          .map(|(outpoint, amount)| {
-            (
-              outpoint,
-              Pile {
-                amount,
-                divisibility: Enshrining::DIVISIBILITY,
-                symbol,
-              },
-            )
-          })
-          .collect(),
-      );
+      Self::increment_statistic(&wtx, Statistic::Enshrinings, enshrinings)?;
+      Self::increment_statistic(&wtx, Statistic::BlocksToEnshrine, blocks_to_enshrine)?;
     }
 
-    Ok(relic_balances)
-  }
+    {
+      let mut sequence_number_to_inscription_entry =
+        wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+      let mut inscription_id_to_sequence_number =
+        wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+      let mut inscription_number_to_sequence_number =
+        wtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+      let mut sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+      let mut satpoint_to_sequence_number = wtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+
+      for inscription in &snapshot.inscriptions {
+        sequence_number_to_inscription_entry.insert(
+          inscription.sequence_number,
+          &InscriptionEntry {
+            charms: inscription.charms,
+            fee: inscription.fee,
+            height: inscription.height,
+            id: inscription.id,
+            inscription_number: inscription.number,
+            parents: Vec::new(),
+            sat: None,
+            sequence_number: inscription.sequence_number,
+            timestamp: inscription.timestamp,
+          }
+          .store(),
+        )?;
+        inscription_id_to_sequence_number
+          .insert(&inscription.id.store(), inscription.sequence_number)?;
+        inscription_number_to_sequence_number
+          .insert(inscription.number, inscription.sequence_number)?;
+        sequence_number_to_satpoint
+          .insert(inscription.sequence_number, &inscription.satpoint.store())?;
+        satpoint_to_sequence_number
+          .insert(&inscription.satpoint.store(), inscription.sequence_number)?;
+      }
+    }
 
-  pub fn get_relic_balances(&self) -> Result<Vec<(OutPoint, Vec<(RelicId, u128)>)>> {
-    let mut result = Vec::new();
+    {
+      let mut outpoint_to_relic_balances = wtx.open_table(OUTPOINT_TO_RELIC_BALANCES)?;
+      let mut buffer = Vec::new();
+
+      for (outpoint, balances) in &snapshot.balances {
+        buffer.clear();
+        for (id, balance) in balances {
+          Index::encode_relic_balance(*id, *balance, &mut buffer);
+        }
+        outpoint_to_relic_balances.insert(&outpoint.store(), buffer.as_slice())?;
+      }
+    }
 
-    for entry in self
-      .database
-      .begin_read()?
-      .open_table(OUTPOINT_TO_RELIC_BALANCES)?
-      .iter()?
     {
-      let (outpoint, balances_buffer) = entry?;
-      let outpoint = OutPoint::load(*outpoint.value());
-      let balances_buffer = balances_buffer.value();
+      let mut relic_owner_to_claimable = wtx.open_table(RELIC_OWNER_TO_CLAIMABLE)?;
 
-      let mut balances = Vec::new();
-      let mut i = 0;
-      while i < balances_buffer.len() {
-        let ((id, balance), length) = Index::decode_relic_balance(&balances_buffer[i..]).unwrap();
-        i += length;
-        balances.push((id, balance));
+      for (owner, amount) in &snapshot.claimable {
+        relic_owner_to_claimable.insert(&owner.store(), amount)?;
       }
+    }
 
-      result.push((outpoint, balances));
+    {
+      let mut liquidity_provider_to_shares = wtx.open_table(LIQUIDITY_PROVIDER_TO_SHARES)?;
+
+      for (id, provider, shares) in &snapshot.liquidity_shares {
+        liquidity_provider_to_shares.insert((id.store(), &provider.store()), shares)?;
+      }
     }
 
-    Ok(result)
-  }
+    {
+      let mut syndicate_id_to_syndicate_entry = wtx.open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)?;
+      let mut sequence_number_to_syndicate_id = wtx.open_table(SEQUENCE_NUMBER_TO_SYNDICATE_ID)?;
 
-  pub fn get_relic_claimable(&self) -> Result<Vec<(RelicOwner, u128)>> {
-    let mut result = Vec::new();
+      for (id, entry) in &snapshot.syndicates {
+        syndicate_id_to_syndicate_entry.insert(id.store(), entry.store())?;
+        sequence_number_to_syndicate_id.insert(entry.sequence_number, id.store())?;
+      }
+    }
 
-    for entry in self
-      .database
-      .begin_read()?
-      .open_table(RELIC_OWNER_TO_CLAIMABLE)?
-      .iter()?
     {
-      let (owner, amount) = entry?;
-      result.push((RelicOwner::load(*owner.value()), amount.value()));
+      let mut sequence_number_to_chest = wtx.open_table(SEQUENCE_NUMBER_TO_CHEST)?;
+      let mut syndicate_to_chest_sequence_number =
+        wtx.open_multimap_table(SYNDICATE_TO_CHEST_SEQUENCE_NUMBER)?;
+      let mut chest_unlock_height_to_sequence_number =
+        wtx.open_table(CHEST_UNLOCK_HEIGHT_TO_SEQUENCE_NUMBER)?;
+
+      let locks = snapshot
+        .syndicates
+        .iter()
+        .map(|(id, entry)| (*id, entry.lock))
+        .collect::<HashMap<SyndicateId, Option<u64>>>();
+
+      for (sequence_number, entry) in &snapshot.chests {
+        sequence_number_to_chest.insert(sequence_number, entry.store())?;
+        syndicate_to_chest_sequence_number.insert(entry.syndicate.store(), sequence_number)?;
+
+        let lock = locks.get(&entry.syndicate).copied().flatten();
+
+        chest_unlock_height_to_sequence_number
+          .insert((entry.unlock_height(lock), *sequence_number), ())?;
+      }
     }
 
-    Ok(result)
+    {
+      let mut relic_id_to_subsidy_schedule = wtx.open_table(RELIC_ID_TO_SUBSIDY_SCHEDULE)?;
+      let mut subsidy_release_height_to_relic_id =
+        wtx.open_table(SUBSIDY_RELEASE_HEIGHT_TO_RELIC_ID)?;
+
+      for (id, entry) in &snapshot.subsidy_schedules {
+        relic_id_to_subsidy_schedule.insert(id.store(), entry.store())?;
+        subsidy_release_height_to_relic_id.insert(
+          (
+            entry.next_release_height(snapshot.height.into()),
+            id.store(),
+          ),
+          (),
+        )?;
+      }
+    }
+
+    wtx.commit()?;
+
+    Ok(())
   }
 
   pub(crate) fn inscription_relic_info(
@@ -1476,7 +4490,11 @@ impl Index {
         }
       }
 
-      None => None,
+      None => reader
+        .open_table(INSCRIPTION_ID_TO_PRUNED_INSCRIPTION)?
+        .get(&entry.id.store())?
+        .map(|inscription| serde_json::from_str(inscription.value()))
+        .transpose()?,
     }) else {
       return Ok(None);
     };
@@ -1493,13 +4511,9 @@ impl Index {
       if satpoint.outpoint == unbound_outpoint() || satpoint.outpoint == OutPoint::null() {
         None
       } else {
-        if let Some(transaction) = self.get_transaction(satpoint.outpoint.txid)? {
-          transaction
-            .output
-            .into_iter()
-            .nth(satpoint.outpoint.vout.try_into().unwrap())
-        } else {
-          return Ok(None);
+        match self.get_txout(satpoint.outpoint)? {
+          Some(output) => Some(output),
+          None => return Ok(None),
         }
       }
     } else {
@@ -1746,7 +4760,13 @@ impl Index {
         }
       }
 
-      None => return Ok(None),
+      None => Ok(
+        reader
+          .open_table(INSCRIPTION_ID_TO_PRUNED_INSCRIPTION)?
+          .get(&inscription_id.store())?
+          .map(|inscription| serde_json::from_str(inscription.value()))
+          .transpose()?,
+      ),
     }
   }
 
@@ -1809,7 +4829,7 @@ impl Index {
       return Ok(Some(self.genesis_block_coinbase_transaction.clone()));
     }
 
-    if self.index_transactions {
+    if self.has_raw_transaction_index() {
       if let Some(transaction) = self
         .database
         .begin_read()?
@@ -1827,10 +4847,53 @@ impl Index {
     }
   }
 
+  /// The consensus encoding of transaction `txid`, if it is stored in the
+  /// index, without asking Dogecoin Core.
+  pub(crate) fn get_stored_transaction(&self, txid: Txid) -> Result<Option<Vec<u8>>> {
+    if !self.has_raw_transaction_index() {
+      return Ok(None);
+    }
+
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(TRANSACTION_ID_TO_TRANSACTION)?
+        .get(&txid.store())?
+        .map(|transaction| transaction.value().to_vec()),
+    )
+  }
+
+  /// Returns the output at `outpoint`, from the index when it was built with
+  /// `--index-txouts` and the output is unspent, otherwise from the
+  /// transaction that created it.
+  pub(crate) fn get_txout(&self, outpoint: OutPoint) -> Result<Option<TxOut>> {
+    if self.index_txouts {
+      if let Some(txout) = self
+        .database
+        .begin_read()?
+        .open_table(OUTPOINT_TO_TXOUT)?
+        .get(&outpoint.store())?
+      {
+        return Ok(Some(consensus::encode::deserialize(txout.value())?));
+      }
+    }
+
+    Ok(
+      self
+        .get_transaction(outpoint.txid)?
+        .and_then(|tx| tx.output.into_iter().nth(outpoint.vout.try_into().unwrap())),
+    )
+  }
+
   pub(crate) fn get_network(&self) -> Result<Network> {
     Ok(self.chain.network())
   }
 
+  pub(crate) fn chain(&self) -> Chain {
+    self.chain
+  }
+
   pub(crate) fn get_transaction_blockhash(
     &self,
     txid: Txid,
@@ -1980,7 +5043,7 @@ impl Index {
       .collect()
   }
 
-  pub fn get_home_inscriptions(&self) -> Result<Vec<InscriptionId>> {
+  pub(crate) fn get_home_inscriptions(&self) -> Result<Vec<InscriptionId>> {
     Ok(
       self
         .database
@@ -1993,7 +5056,7 @@ impl Index {
     )
   }
 
-  pub fn get_inscriptions_paginated(
+  pub(crate) fn get_inscriptions_paginated(
     &self,
     page_size: u32,
     page_index: u32,
@@ -2029,7 +5092,7 @@ impl Index {
     Ok((inscriptions, more))
   }
 
-  pub fn get_feed_inscriptions(&self, n: usize) -> Result<Vec<(u32, InscriptionId)>> {
+  pub(crate) fn get_feed_inscriptions(&self, n: usize) -> Result<Vec<(u32, InscriptionId)>> {
     Ok(
       self
         .database
@@ -2045,7 +5108,7 @@ impl Index {
     )
   }
 
-  pub fn get_inscription_entry(
+  pub(crate) fn get_inscription_entry(
     &self,
     inscription_id: InscriptionId,
   ) -> Result<Option<InscriptionEntry>> {
@@ -2067,13 +5130,36 @@ impl Index {
     Ok(entry)
   }
 
-  pub fn encode_relic_balance(id: RelicId, balance: u128, buffer: &mut Vec<u8>) {
+  /// Up to `limit` inscription entries in sequence number order, starting
+  /// with sequence number `start`.
+  pub(crate) fn get_inscription_entries(
+    &self,
+    start: u32,
+    limit: usize,
+  ) -> Result<Vec<InscriptionEntry>> {
+    let mut entries = Vec::new();
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .range(start..)?
+      .take(limit)
+    {
+      let (_sequence_number, entry) = result?;
+      entries.push(InscriptionEntry::load(entry.value()));
+    }
+
+    Ok(entries)
+  }
+
+  pub(crate) fn encode_relic_balance(id: RelicId, balance: u128, buffer: &mut Vec<u8>) {
     relics::varint::encode_to_vec(id.block.into(), buffer);
     relics::varint::encode_to_vec(id.tx.into(), buffer);
     relics::varint::encode_to_vec(balance, buffer);
   }
 
-  pub fn decode_relic_balance(buffer: &[u8]) -> Result<((RelicId, u128), usize)> {
+  pub(crate) fn decode_relic_balance(buffer: &[u8]) -> Result<((RelicId, u128), usize)> {
     let mut len = 0;
     let (block, block_len) = relics::varint::decode(&buffer[len..])?;
     len += block_len;
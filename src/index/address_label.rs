@@ -0,0 +1,27 @@
+use super::*;
+
+/// Operator-assigned description of an address, e.g. an exchange hot wallet
+/// or a burn address, stored as JSON like audit entries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressLabel {
+  pub label: String,
+  /// free-form tags, e.g. `exchange`, `team` or `burn`
+  #[serde(default)]
+  pub tags: BTreeSet<String>,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn tags_are_optional() {
+    assert_eq!(
+      serde_json::from_str::<AddressLabel>(r#"{"label":"Burn"}"#).unwrap(),
+      AddressLabel {
+        label: "Burn".into(),
+        tags: BTreeSet::new(),
+      }
+    );
+  }
+}
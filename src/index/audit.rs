@@ -0,0 +1,38 @@
+use super::*;
+
+/// A state-changing request to the admin API, stored as JSON like webhooks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+  /// unix seconds at which the request was received
+  pub timestamp: u64,
+  /// the `x-admin-actor` header of the request, since all admins share a token
+  pub actor: Option<String>,
+  /// method and path, e.g. `POST /admin/cache/purge`
+  pub action: String,
+  pub query: Option<String>,
+  pub body: Option<String>,
+  /// HTTP status of the response
+  pub status: u16,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn audit_entries_round_trip_through_json() {
+    let entry = AuditEntry {
+      timestamp: 1,
+      actor: Some("alice".into()),
+      action: "POST /admin/cache/purge".into(),
+      query: Some("scope=all".into()),
+      body: None,
+      status: 200,
+    };
+
+    assert_eq!(
+      serde_json::from_str::<AuditEntry>(&serde_json::to_string(&entry).unwrap()).unwrap(),
+      entry
+    );
+  }
+}
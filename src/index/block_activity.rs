@@ -0,0 +1,136 @@
+use super::*;
+
+/// Relic activity of a single block, tallied from the events it emitted.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BlockRelicActivity {
+  pub height: u32,
+  pub events: u64,
+  #[serde(rename = "enshrined_bones")]
+  pub enshrined: Vec<RelicId>,
+  pub sealings: u64,
+  pub mints: u64,
+  pub swaps: u64,
+  /// base tokens traded by swaps
+  pub volume: u128,
+  pub burns: u64,
+  /// amounts burned, by relic
+  pub burned: BTreeMap<RelicId, u128>,
+  pub syndicates: SyndicateActivity,
+  /// failed operations and cenotaphs
+  pub errors: u64,
+}
+
+/// Syndicate operations of a single block.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyndicateActivity {
+  pub summoned: Vec<SyndicateId>,
+  pub encased: u64,
+  pub released: u64,
+  pub withdrawn: u64,
+  pub relocked: u64,
+}
+
+impl BlockRelicActivity {
+  pub fn new(height: u32) -> Self {
+    Self {
+      height,
+      ..default()
+    }
+  }
+
+  pub fn add(&mut self, event: &Event) {
+    self.events += 1;
+
+    match event.info {
+      EventInfo::RelicEnshrined { relic_id } => self.enshrined.push(relic_id),
+      EventInfo::RelicSealed { .. } => self.sealings += 1,
+      EventInfo::RelicMinted { .. } => self.mints += 1,
+      EventInfo::RelicSwapped { base_amount, .. } => {
+        self.swaps += 1;
+        self.volume = self.volume.saturating_add(base_amount);
+      }
+      EventInfo::RelicBurned { relic_id, amount } => {
+        self.burns += 1;
+        let burned = self.burned.entry(relic_id).or_default();
+        *burned = burned.saturating_add(amount);
+      }
+      EventInfo::SyndicateSummoned { syndicate_id, .. } => {
+        self.syndicates.summoned.push(syndicate_id)
+      }
+      EventInfo::ChestEncased { .. } => self.syndicates.encased += 1,
+      EventInfo::ChestReleased { .. } => self.syndicates.released += 1,
+      EventInfo::ChestWithdrawn { .. } => self.syndicates.withdrawn += 1,
+      EventInfo::ChestRelocked { .. } => self.syndicates.relocked += 1,
+      EventInfo::RelicError { .. } => self.errors += 1,
+      _ => {}
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn events_are_tallied() {
+    let relic_id = RelicId { block: 2, tx: 1 };
+    let syndicate_id = SyndicateId { block: 3, tx: 1 };
+
+    let mut activity = BlockRelicActivity::new(5);
+
+    for info in [
+      EventInfo::RelicEnshrined { relic_id },
+      EventInfo::RelicMinted {
+        relic_id,
+        amount: 10,
+      },
+      EventInfo::RelicMinted {
+        relic_id,
+        amount: 10,
+      },
+      EventInfo::RelicBurned {
+        relic_id,
+        amount: 3,
+      },
+      EventInfo::RelicBurned {
+        relic_id,
+        amount: 4,
+      },
+      EventInfo::SyndicateSummoned {
+        syndicate_id,
+        relic_id,
+      },
+      EventInfo::ChestEncased { syndicate_id },
+      EventInfo::RelicTransferred {
+        relic_id,
+        amount: 1,
+        output: 0,
+      },
+    ] {
+      activity.add(&Event {
+        block_height: 5,
+        event_index: 0,
+        txid: Txid::all_zeros(),
+        info,
+      });
+    }
+
+    assert_eq!(
+      activity,
+      BlockRelicActivity {
+        height: 5,
+        events: 8,
+        enshrined: vec![relic_id],
+        mints: 2,
+        burns: 2,
+        burned: [(relic_id, 7)].into(),
+        syndicates: SyndicateActivity {
+          summoned: vec![syndicate_id],
+          encased: 1,
+          ..default()
+        },
+        ..default()
+      }
+    );
+  }
+}
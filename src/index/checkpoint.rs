@@ -0,0 +1,123 @@
+use {
+  super::*,
+  bitcoin::hashes::{sha256, HashEngine},
+  redb::{Key, Value},
+};
+
+/// Builds the hash of the complete relic state after a block.
+///
+/// Tables are hashed in key order using the index storage encoding, so
+/// indexes built with the same schema version agree on the hash of every
+/// block, as long as they hash the same tables in the same order.
+#[derive(Default)]
+pub(crate) struct RelicStateHasher(sha256::HashEngine);
+
+impl RelicStateHasher {
+  pub(crate) fn table<K: Key + 'static, V: Value + 'static>(
+    mut self,
+    table: &impl ReadableTable<K, V>,
+  ) -> Result<Self> {
+    // prefix each table with its length, so records can't shift between tables
+    self.0.input(&table.len()?.to_le_bytes());
+
+    for result in table.iter()? {
+      let (key, value) = result?;
+
+      let key = key.value();
+      let key = K::as_bytes(&key);
+      let value = value.value();
+      let value = V::as_bytes(&value);
+
+      for bytes in [key.as_ref(), value.as_ref()] {
+        self
+          .0
+          .input(&u64::try_from(bytes.len()).unwrap().to_le_bytes());
+        self.0.input(bytes);
+      }
+    }
+
+    Ok(self)
+  }
+
+  pub(crate) fn finish(self) -> sha256::Hash {
+    sha256::Hash::from_engine(self.0)
+  }
+}
+
+/// Loads trusted checkpoints from a file with one `<HEIGHT> <HASH>` pair per
+/// line. Empty lines and lines starting with `#` are ignored.
+pub(crate) fn load_trusted(path: &Path) -> Result<BTreeMap<u32, sha256::Hash>> {
+  parse_trusted(
+    &fs::read_to_string(path)
+      .with_context(|| format!("failed to read checkpoints from `{}`", path.display()))?,
+  )
+}
+
+fn parse_trusted(checkpoints: &str) -> Result<BTreeMap<u32, sha256::Hash>> {
+  let mut trusted = BTreeMap::new();
+
+  for (i, line) in checkpoints.lines().enumerate() {
+    let line = line.trim();
+
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+
+    let Some((height, hash)) = line.split_once(char::is_whitespace) else {
+      bail!("invalid checkpoint on line {}: {line}", i + 1);
+    };
+
+    let height = height
+      .parse::<u32>()
+      .with_context(|| format!("invalid checkpoint height on line {}", i + 1))?;
+    let hash = hash
+      .trim()
+      .parse::<sha256::Hash>()
+      .with_context(|| format!("invalid checkpoint hash on line {}", i + 1))?;
+
+    if trusted.insert(height, hash).is_some() {
+      bail!("duplicate checkpoint for height {height}");
+    }
+  }
+
+  Ok(trusted)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn trusted_checkpoints_are_parsed() {
+    let hash = sha256::Hash::hash(b"foo");
+
+    assert_eq!(
+      parse_trusted(&format!("# comment\n\n1 {hash}\n2\t{hash}\n")).unwrap(),
+      [(1, hash), (2, hash)].into_iter().collect()
+    );
+  }
+
+  #[test]
+  fn invalid_checkpoints_are_rejected() {
+    let hash = sha256::Hash::hash(b"foo");
+
+    assert_eq!(
+      parse_trusted("1").unwrap_err().to_string(),
+      "invalid checkpoint on line 1: 1"
+    );
+    assert_eq!(
+      parse_trusted(&format!("a {hash}")).unwrap_err().to_string(),
+      "invalid checkpoint height on line 1"
+    );
+    assert_eq!(
+      parse_trusted("1 foo").unwrap_err().to_string(),
+      "invalid checkpoint hash on line 1"
+    );
+    assert_eq!(
+      parse_trusted(&format!("1 {hash}\n1 {hash}"))
+        .unwrap_err()
+        .to_string(),
+      "duplicate checkpoint for height 1"
+    );
+  }
+}
@@ -6,6 +6,21 @@ pub struct ChestEntry {
   pub syndicate: SyndicateId,
   pub created_block: u64,
   pub amount: u128,
+  /// total amount partially withdrawn from the Chest
+  pub withdrawn: u128,
+  /// unlock height the Chest was voluntarily relocked until
+  pub locked_until: Option<u64>,
+}
+
+impl ChestEntry {
+  /// first block in which the Chest can be released or withdrawn from,
+  /// given the lock of its Syndicate
+  pub fn unlock_height(&self, lock: Option<u64>) -> u64 {
+    let unlock_height = self.created_block + lock.unwrap_or_default();
+    self.locked_until.map_or(unlock_height, |locked_until| {
+      locked_until.max(unlock_height)
+    })
+  }
 }
 
 pub type ChestEntryValue = (
@@ -13,17 +28,23 @@ pub type ChestEntryValue = (
   SyndicateIdValue, // syndicate
   u64,              // created_block
   u128,             // amount
+  u128,             // withdrawn
+  Option<u64>,      // locked_until
 );
 
 impl Entry for ChestEntry {
   type Value = ChestEntryValue;
 
-  fn load((sequence_number, syndicate, created_block, amount): Self::Value) -> Self {
+  fn load(
+    (sequence_number, syndicate, created_block, amount, withdrawn, locked_until): Self::Value,
+  ) -> Self {
     Self {
       sequence_number,
       syndicate: SyndicateId::load(syndicate),
       created_block,
       amount,
+      withdrawn,
+      locked_until,
     }
   }
 
@@ -33,6 +54,8 @@ impl Entry for ChestEntry {
       self.syndicate.store(),
       self.created_block,
       self.amount,
+      self.withdrawn,
+      self.locked_until,
     )
   }
 }
@@ -0,0 +1,43 @@
+use {super::*, clap::ValueEnum};
+
+#[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestPeriod {
+  #[default]
+  Daily,
+  Weekly,
+}
+
+impl DigestPeriod {
+  /// number of blocks covered by one period, assuming one minute block times
+  pub fn blocks(self) -> u32 {
+    match self {
+      Self::Daily => 24 * 60,
+      Self::Weekly => 7 * 24 * 60,
+    }
+  }
+}
+
+/// Summary of an address's relic activity over the last period, suitable as a
+/// webhook payload or as input for notification emails.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressDigest {
+  pub address: String,
+  pub period: DigestPeriod,
+  pub from_height: u32,
+  pub to_height: u32,
+  pub events: usize,
+  pub received: BTreeMap<SpacedRelic, u128>,
+  pub spent: BTreeMap<SpacedRelic, u128>,
+  pub claimable: u128,
+  pub chest_unlocks: Vec<ChestUnlock>,
+}
+
+/// A chest held by the address that unlocks within the next period.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChestUnlock {
+  pub inscription_id: InscriptionId,
+  pub syndicate: SyndicateId,
+  pub amount: u128,
+  pub unlock_height: u64,
+}
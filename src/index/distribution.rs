@@ -0,0 +1,133 @@
+use super::*;
+
+/// How concentrated the balances of a relic are among the scripts holding it.
+///
+/// Only balances in unspent outputs are counted, so relics locked in pools or
+/// chests are left out.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelicDistribution {
+  pub holders: u64,
+  /// total amount held by all holders
+  pub held: u128,
+  /// share of `held` owned by the ten largest holders
+  pub top_10_share: f64,
+  /// 0 if every holder owns the same amount, approaching 1 if a single holder
+  /// owns everything
+  pub gini: f64,
+  pub buckets: Vec<HolderBucket>,
+}
+
+/// Holders owning at least `min_share` of the held supply, and less than the
+/// `min_share` of the previous bucket.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct HolderBucket {
+  pub min_share: f64,
+  pub holders: u64,
+  pub amount: u128,
+}
+
+impl RelicDistribution {
+  const BUCKETS: [f64; 5] = [0.01, 0.001, 0.0001, 0.00001, 0.0];
+
+  /// `balances` holds the total balance of every holder.
+  pub fn new(mut balances: Vec<u128>) -> Self {
+    balances.retain(|balance| *balance > 0);
+    balances.sort_unstable();
+
+    let held = balances.iter().sum::<u128>();
+    let holders = u64::try_from(balances.len()).unwrap();
+
+    let share = |amount: u128| {
+      if held == 0 {
+        0.0
+      } else {
+        amount as f64 / held as f64
+      }
+    };
+
+    let top_10_share = share(balances.iter().rev().take(10).sum());
+
+    // with balances sorted in ascending order
+    // G = 2 * Σ i * x_i / (n * Σ x_i) - (n + 1) / n
+    let gini = if balances.is_empty() || held == 0 {
+      0.0
+    } else {
+      let n = balances.len() as f64;
+      let weighted = balances
+        .iter()
+        .enumerate()
+        .map(|(i, balance)| (i + 1) as f64 * *balance as f64)
+        .sum::<f64>();
+      (2.0 * weighted / (n * held as f64) - (n + 1.0) / n).max(0.0)
+    };
+
+    let mut buckets = Self::BUCKETS
+      .iter()
+      .map(|min_share| HolderBucket {
+        min_share: *min_share,
+        holders: 0,
+        amount: 0,
+      })
+      .collect::<Vec<HolderBucket>>();
+
+    for balance in balances {
+      let bucket = buckets
+        .iter_mut()
+        .find(|bucket| share(balance) >= bucket.min_share)
+        .unwrap();
+      bucket.holders += 1;
+      bucket.amount += balance;
+    }
+
+    Self {
+      holders,
+      held,
+      top_10_share,
+      gini,
+      buckets,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn equal_balances_are_not_concentrated() {
+    let distribution = RelicDistribution::new(vec![5; 20]);
+    assert_eq!(distribution.holders, 20);
+    assert_eq!(distribution.held, 100);
+    assert_eq!(distribution.top_10_share, 0.5);
+    assert_eq!(distribution.gini, 0.0);
+    assert_eq!(distribution.buckets[0].holders, 20);
+  }
+
+  #[test]
+  fn single_whale_is_concentrated() {
+    let mut balances = vec![1; 99];
+    balances.push(1_000_000);
+
+    let distribution = RelicDistribution::new(balances);
+    assert_eq!(distribution.holders, 100);
+    assert!(distribution.top_10_share > 0.99);
+    assert!(distribution.gini > 0.98);
+    assert_eq!(distribution.buckets[0].holders, 1);
+    assert_eq!(distribution.buckets[0].amount, 1_000_000);
+    assert_eq!(distribution.buckets[2].holders, 0);
+    assert_eq!(distribution.buckets[3].holders, 0);
+    assert_eq!(distribution.buckets[4].holders, 99);
+  }
+
+  #[test]
+  fn empty_balances() {
+    let distribution = RelicDistribution::new(vec![0]);
+    assert_eq!(distribution.holders, 0);
+    assert_eq!(distribution.top_10_share, 0.0);
+    assert_eq!(distribution.gini, 0.0);
+    assert!(distribution
+      .buckets
+      .iter()
+      .all(|bucket| bucket.holders == 0));
+  }
+}
@@ -41,7 +41,7 @@ impl Entry for Txid {
 
 #[derive(Clone)]
 pub(crate) struct InscriptionEntry {
-  pub(crate) charms: u16,
+  pub(crate) charms: u32,
   pub(crate) fee: u64,
   pub(crate) height: u32,
   pub(crate) id: InscriptionId,
@@ -53,7 +53,7 @@ pub(crate) struct InscriptionEntry {
 }
 
 pub(crate) type InscriptionEntryValue = (
-  u16,                // charms
+  u32,                // charms
   u64,                // fee
   u32,                // height
   InscriptionIdValue, // inscription id
@@ -122,10 +122,10 @@ impl Entry for InscriptionId {
 
 pub(crate) struct OutPointMap {
   pub(crate) value: u64,
-  pub(crate) address: [u8; 34],
+  pub(crate) script_hash: ScriptHashValue,
 }
 
-pub(crate) type OutPointMapValue = (u64, [u8; 34]);
+pub(crate) type OutPointMapValue = (u64, ScriptHashValue);
 
 impl Entry for OutPointMap {
   type Value = OutPointMapValue;
@@ -133,15 +133,23 @@ impl Entry for OutPointMap {
   fn load(value: Self::Value) -> Self {
     Self {
       value: value.0,
-      address: value.1,
+      script_hash: value.1,
     }
   }
 
   fn store(self) -> Self::Value {
-    (self.value, self.address)
+    (self.value, self.script_hash)
   }
 }
 
+/// SHA256 of an output script, which Electrum clients show byte-reversed as
+/// the script hash, used to look up outputs independent of address encoding.
+pub(crate) type ScriptHashValue = [u8; 32];
+
+pub(crate) fn script_hash(script: &Script) -> ScriptHashValue {
+  sha256::Hash::hash(script.as_bytes()).into_inner()
+}
+
 pub type OutPointValue = [u8; 36];
 
 impl Entry for OutPoint {
@@ -4,7 +4,7 @@ use {super::*, bincode::Options, redb::TypeName, std::cmp::Ordering};
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum EventInfo {
   InscriptionCreated {
-    charms: u16,
+    charms: u32,
     inscription_id: InscriptionId,
     location: Option<SatPoint>,
     parent_inscription_ids: Vec<InscriptionId>,
@@ -72,6 +72,12 @@ pub enum EventInfo {
     fee: u128,
     is_sell_order: bool,
     is_exact_input: bool,
+    /// base token reserve of the pool after the swap
+    base_reserve: u128,
+    /// relic reserve of the pool after the swap
+    quote_reserve: u128,
+    /// price of one relic in base token units after the swap
+    price: u128,
   },
   #[serde(rename = "BoneClaimed")]
   RelicClaimed {
@@ -96,6 +102,58 @@ pub enum EventInfo {
     operation: RelicOperation,
     error: RelicError,
   },
+  LiquidityAdded {
+    #[serde(rename = "bone_id")]
+    relic_id: RelicId,
+    base_amount: u128,
+    quote_amount: u128,
+    shares: u128,
+  },
+  LiquidityRemoved {
+    #[serde(rename = "bone_id")]
+    relic_id: RelicId,
+    base_amount: u128,
+    quote_amount: u128,
+    shares: u128,
+  },
+  /// A chest inscription moved. Chests follow their inscription, so this is a
+  /// change of ownership.
+  ChestTransferred {
+    syndicate_id: SyndicateId,
+    inscription_id: InscriptionId,
+    new_location: SatPoint,
+    old_location: SatPoint,
+  },
+  /// Part of the amount of an unlocked chest was withdrawn, the chest remains.
+  ChestWithdrawn {
+    syndicate_id: SyndicateId,
+    inscription_id: InscriptionId,
+    amount: u128,
+  },
+  /// A chest was locked until a later block.
+  ChestRelocked {
+    syndicate_id: SyndicateId,
+    inscription_id: InscriptionId,
+    unlock_height: u64,
+  },
+  /// The owner of a relic configured a release schedule for its subsidy.
+  RelicSubsidyScheduled {
+    relic_id: RelicId,
+    amount: u128,
+    epoch: u64,
+  },
+  /// An epoch of a subsidy release schedule ended and part of the subsidy
+  /// became available for syndicate rewards.
+  RelicSubsidyReleased {
+    relic_id: RelicId,
+    amount: u128,
+  },
+  /// The owner of a syndicate with a royalty claimed their claimable balance,
+  /// which includes the royalties paid for its chests, see the preceding
+  /// `RelicClaimed` event for the amount.
+  SyndicateRoyaltiesClaimed {
+    syndicate_id: SyndicateId,
+  },
 }
 
 #[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
@@ -108,6 +166,115 @@ pub enum RelicOperation {
   Encase,
   Release,
   Claim,
+  LiquidityAdd,
+  LiquidityRemove,
+  Withdraw,
+  Relock,
+  ScheduleSubsidy,
+}
+
+/// The kind of an `EventInfo`, without its fields.
+///
+/// Kinds are stored as `u8` in the event kind index, so new kinds must only be
+/// appended.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
+pub enum EventKind {
+  InscriptionCreated,
+  InscriptionTransferred,
+  RelicSealed,
+  #[serde(alias = "BoneBurned")]
+  RelicBurned,
+  #[serde(alias = "BoneDeployed")]
+  RelicEnshrined,
+  #[serde(alias = "BoneMinted")]
+  RelicMinted,
+  #[serde(alias = "BoneSpent")]
+  RelicSpent,
+  #[serde(alias = "BoneReceived")]
+  RelicReceived,
+  #[serde(alias = "BoneTransferred")]
+  RelicTransferred,
+  #[serde(alias = "BoneSwapped")]
+  RelicSwapped,
+  RelicClaimed,
+  RelicSubsidyLocked,
+  SyndicateSummoned,
+  ChestEncased,
+  ChestReleased,
+  #[serde(alias = "BoneError")]
+  RelicError,
+  LiquidityAdded,
+  LiquidityRemoved,
+  ChestTransferred,
+  ChestWithdrawn,
+  ChestRelocked,
+  RelicSubsidyScheduled,
+  RelicSubsidyReleased,
+  SyndicateRoyaltiesClaimed,
+}
+
+impl EventInfo {
+  pub fn kind(&self) -> EventKind {
+    match self {
+      Self::InscriptionCreated { .. } => EventKind::InscriptionCreated,
+      Self::InscriptionTransferred { .. } => EventKind::InscriptionTransferred,
+      Self::RelicSealed { .. } => EventKind::RelicSealed,
+      Self::RelicBurned { .. } => EventKind::RelicBurned,
+      Self::RelicEnshrined { .. } => EventKind::RelicEnshrined,
+      Self::RelicMinted { .. } => EventKind::RelicMinted,
+      Self::RelicSpent { .. } => EventKind::RelicSpent,
+      Self::RelicReceived { .. } => EventKind::RelicReceived,
+      Self::RelicTransferred { .. } => EventKind::RelicTransferred,
+      Self::RelicSwapped { .. } => EventKind::RelicSwapped,
+      Self::RelicClaimed { .. } => EventKind::RelicClaimed,
+      Self::RelicSubsidyLocked { .. } => EventKind::RelicSubsidyLocked,
+      Self::SyndicateSummoned { .. } => EventKind::SyndicateSummoned,
+      Self::ChestEncased { .. } => EventKind::ChestEncased,
+      Self::ChestReleased { .. } => EventKind::ChestReleased,
+      Self::RelicError { .. } => EventKind::RelicError,
+      Self::LiquidityAdded { .. } => EventKind::LiquidityAdded,
+      Self::LiquidityRemoved { .. } => EventKind::LiquidityRemoved,
+      Self::ChestTransferred { .. } => EventKind::ChestTransferred,
+      Self::ChestWithdrawn { .. } => EventKind::ChestWithdrawn,
+      Self::ChestRelocked { .. } => EventKind::ChestRelocked,
+      Self::RelicSubsidyScheduled { .. } => EventKind::RelicSubsidyScheduled,
+      Self::RelicSubsidyReleased { .. } => EventKind::RelicSubsidyReleased,
+      Self::SyndicateRoyaltiesClaimed { .. } => EventKind::SyndicateRoyaltiesClaimed,
+    }
+  }
+}
+
+/// Criteria for `Index::filter_events`. Heights are inclusive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventFilter {
+  pub from: u32,
+  pub to: u32,
+  pub kind: Option<EventKind>,
+  pub relic_id: Option<RelicId>,
+  pub txid: Option<Txid>,
+}
+
+impl Default for EventFilter {
+  fn default() -> Self {
+    Self {
+      from: 0,
+      to: u32::MAX,
+      kind: None,
+      relic_id: None,
+      txid: None,
+    }
+  }
+}
+
+impl EventFilter {
+  pub fn matches(&self, event: &Event) -> bool {
+    (self.from..=self.to).contains(&event.block_height)
+      && self.kind.map_or(true, |kind| event.info.kind() == kind)
+      && self
+        .relic_id
+        .map_or(true, |relic_id| event.relic_id() == Some(relic_id))
+      && self.txid.map_or(true, |txid| event.txid == txid)
+  }
 }
 
 impl Display for Event {
@@ -134,6 +301,24 @@ pub struct EventWithRelicInscriptionInfo {
   pub inscription: Option<RelicShibescriptionJson>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub ticker: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub formatted: Option<BTreeMap<String, String>>,
+}
+
+/// An event with optional human readable amounts, serialized like a plain event
+/// if no formatted amounts are attached.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct FormattedEvent {
+  #[serde(flatten)]
+  pub event: Event,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub formatted: Option<BTreeMap<String, String>>,
+}
+
+impl Display for FormattedEvent {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.event)
+  }
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -156,9 +341,80 @@ impl Event {
         | EventInfo::RelicReceived { .. }
         | EventInfo::RelicTransferred { .. }
         | EventInfo::RelicSwapped { .. }
+        | EventInfo::LiquidityAdded { .. }
+        | EventInfo::LiquidityRemoved { .. }
     )
   }
 
+  pub fn address(&self) -> Option<&Address> {
+    match &self.info {
+      EventInfo::RelicSpent { address, .. } | EventInfo::RelicReceived { address, .. } => {
+        Some(address)
+      }
+      _ => None,
+    }
+  }
+
+  /// The syndicate whose chests this event concerns, if any.
+  pub fn syndicate_id(&self) -> Option<SyndicateId> {
+    match self.info {
+      EventInfo::SyndicateSummoned { syndicate_id, .. }
+      | EventInfo::ChestEncased { syndicate_id }
+      | EventInfo::ChestReleased { syndicate_id, .. }
+      | EventInfo::ChestTransferred { syndicate_id, .. }
+      | EventInfo::ChestWithdrawn { syndicate_id, .. }
+      | EventInfo::ChestRelocked { syndicate_id, .. }
+      | EventInfo::SyndicateRoyaltiesClaimed { syndicate_id } => Some(syndicate_id),
+      _ => None,
+    }
+  }
+
+  /// Raw amounts of this event by field name, with the relic they are denominated in.
+  /// Swap prices and fees are always paid in the base token.
+  pub fn amounts(&self) -> Vec<(&'static str, RelicId, u128)> {
+    match self.info {
+      EventInfo::RelicBurned { relic_id, amount }
+      | EventInfo::RelicMinted { relic_id, amount }
+      | EventInfo::RelicSpent {
+        relic_id, amount, ..
+      }
+      | EventInfo::RelicReceived {
+        relic_id, amount, ..
+      }
+      | EventInfo::RelicTransferred {
+        relic_id, amount, ..
+      } => vec![("amount", relic_id, amount)],
+      EventInfo::RelicSwapped {
+        relic_id,
+        base_amount,
+        quote_amount,
+        fee,
+        ..
+      } => vec![
+        ("base_amount", RELIC_ID, base_amount),
+        ("quote_amount", relic_id, quote_amount),
+        ("fee", RELIC_ID, fee),
+      ],
+      EventInfo::RelicClaimed { amount } => vec![("amount", RELIC_ID, amount)],
+      EventInfo::LiquidityAdded {
+        relic_id,
+        base_amount,
+        quote_amount,
+        ..
+      }
+      | EventInfo::LiquidityRemoved {
+        relic_id,
+        base_amount,
+        quote_amount,
+        ..
+      } => vec![
+        ("base_amount", RELIC_ID, base_amount),
+        ("quote_amount", relic_id, quote_amount),
+      ],
+      _ => Vec::new(),
+    }
+  }
+
   pub fn relic_id(&self) -> Option<RelicId> {
     match self.info {
       EventInfo::RelicEnshrined { relic_id, .. } => Some(relic_id),
@@ -170,7 +426,11 @@ impl Event {
       EventInfo::RelicSwapped { relic_id, .. } => Some(relic_id),
       EventInfo::RelicClaimed { .. } => Some(RELIC_ID),
       EventInfo::RelicSubsidyLocked { relic_id, .. } => Some(relic_id),
+      EventInfo::RelicSubsidyScheduled { relic_id, .. } => Some(relic_id),
+      EventInfo::RelicSubsidyReleased { relic_id, .. } => Some(relic_id),
       EventInfo::SyndicateSummoned { relic_id, .. } => Some(relic_id),
+      EventInfo::LiquidityAdded { relic_id, .. } => Some(relic_id),
+      EventInfo::LiquidityRemoved { relic_id, .. } => Some(relic_id),
       _ => None,
     }
   }
@@ -228,12 +488,29 @@ impl redb::Key for Event {
   }
 }
 
+/// Called synchronously with every committed event, see `Index::on_event`.
+pub type EventHook = Box<dyn Fn(&Event) + Send + Sync>;
+
+/// Called synchronously with the height and header of every committed block, see
+/// `Index::on_block`.
+pub type BlockHook = Box<dyn Fn(u32, &BlockHeader) + Send + Sync>;
+
 pub struct EventEmitter<'a, 'tx> {
   pub block_height: u32,
   pub event_index: u32,
-  pub event_sender: Option<&'a tokio::sync::mpsc::Sender<Event>>,
+  /// collects emitted events, which are passed to hooks once committed
+  pub emitted: Option<&'a mut Vec<Event>>,
+  /// keys of events waiting to be dispatched to the event channel
+  pub outbox: Option<&'a mut Table<'tx, (u32, u32), ()>>,
+  pub address_to_events: &'a mut MultimapTable<'tx, &'static [u8], (u32, u32)>,
   pub relic_id_to_events: &'a mut MultimapTable<'tx, RelicIdValue, Event>,
   pub transaction_id_to_events: &'a mut MultimapTable<'tx, &'static TxidValue, Event>,
+  pub event_key_to_event: &'a mut Table<'tx, (u32, u32), Event>,
+  pub event_kind_to_event_key: &'a mut Table<'tx, (u8, u32, u32), ()>,
+  pub relic_id_to_event_key: &'a mut Table<'tx, (RelicIdValue, u32, u32), ()>,
+  pub relic_id_to_burn_event_key: &'a mut Table<'tx, (RelicIdValue, u32, u32), ()>,
+  pub inscription_id_to_chest_event_key:
+    &'a mut Table<'tx, (&'static InscriptionIdValue, u32, u32), ()>,
 }
 
 impl<'a, 'tx> EventEmitter<'a, 'tx> {
@@ -245,19 +522,55 @@ impl<'a, 'tx> EventEmitter<'a, 'tx> {
       info,
     };
     self.event_index += 1;
-    if let Some(sender) = self.event_sender {
-      sender.blocking_send(event.clone())?;
+    if let Some(emitted) = self.emitted.as_mut() {
+      emitted.push(event.clone());
     }
     // store all events with the TX
     self
       .transaction_id_to_events
       .insert(&txid.store(), &event)?;
+    // index all events by height, kind and relic for filtering
+    let (height, index) = (event.block_height, event.event_index);
+    self.event_key_to_event.insert((height, index), &event)?;
+    if let Some(outbox) = self.outbox.as_mut() {
+      outbox.insert((height, index), ())?;
+    }
+    self
+      .event_kind_to_event_key
+      .insert((event.info.kind() as u8, height, index), ())?;
+    if let Some(relic_id) = event.relic_id() {
+      self
+        .relic_id_to_event_key
+        .insert((relic_id.store(), height, index), ())?;
+    }
+    // index burns separately, they are rare among the events of a relic
+    if let EventInfo::RelicBurned { relic_id, .. } = event.info {
+      self
+        .relic_id_to_burn_event_key
+        .insert((relic_id.store(), height, index), ())?;
+    }
+    // index the events that name their chest, so a chest's history doesn't
+    // need a scan of all events of its kind
+    if let EventInfo::ChestTransferred { inscription_id, .. }
+    | EventInfo::ChestWithdrawn { inscription_id, .. }
+    | EventInfo::ChestRelocked { inscription_id, .. } = event.info
+    {
+      self
+        .inscription_id_to_chest_event_key
+        .insert((&inscription_id.store(), height, index), ())?;
+    }
     // store some of the events with the relic
     if event.is_relic_history() {
       if let Some(relic_id) = event.relic_id() {
         self.relic_id_to_events.insert(relic_id.store(), &event)?;
       }
     }
+    // index spent and received relics by address
+    if let Some(address) = event.address() {
+      self
+        .address_to_events
+        .insert(address.to_string().as_bytes(), (height, index))?;
+    }
 
     Ok(())
   }
@@ -0,0 +1,64 @@
+use super::*;
+
+/// Blocks recorded from a chain, to replay through the relic updater in
+/// regression tests.
+///
+/// The headers of all blocks are recorded, so replayed blocks keep their
+/// hashes, but only the blocks that emitted events keep their transactions.
+/// The others are replayed with just a coinbase. Transactions spent by the
+/// recorded blocks that are not part of them are recorded as well, so the
+/// values of their outputs can be looked up.
+///
+/// Blocks are replayed from the genesis block on, without any prior state, so
+/// fixtures are only recorded from regtest.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelicFixture {
+  pub chain: Chain,
+  /// hex encoded headers of the blocks from height 1 on
+  pub headers: Vec<String>,
+  /// hex encoded transactions of the blocks that emitted events, by height
+  pub blocks: BTreeMap<u32, Vec<String>>,
+  /// hex encoded transactions spent by the recorded blocks
+  pub spent: Vec<String>,
+}
+
+impl RelicFixture {
+  /// Blocks to replay, from height 1 on.
+  pub(crate) fn blocks(&self) -> Result<Vec<Block>> {
+    let mut blocks = Vec::new();
+
+    for (height, header) in (1..).zip(&self.headers) {
+      let header = consensus::encode::deserialize(&hex::decode(header)?)?;
+
+      let txdata = match self.blocks.get(&height) {
+        Some(transactions) => transactions
+          .iter()
+          .map(|tx| Ok(consensus::encode::deserialize(&hex::decode(tx)?)?))
+          .collect::<Result<Vec<Transaction>>>()?,
+        None => vec![Transaction {
+          version: 2,
+          lock_time: bitcoin::PackedLockTime::ZERO,
+          input: vec![TxIn {
+            previous_output: OutPoint::null(),
+            script_sig: script::Builder::new().push_int(height.into()).into_script(),
+            sequence: Sequence::MAX,
+            witness: Witness::new(),
+          }],
+          output: Vec::new(),
+        }],
+      };
+
+      blocks.push(Block { header, txdata });
+    }
+
+    Ok(blocks)
+  }
+
+  pub(crate) fn spent(&self) -> Result<Vec<Transaction>> {
+    self
+      .spent
+      .iter()
+      .map(|tx| Ok(consensus::encode::deserialize(&hex::decode(tx)?)?))
+      .collect()
+  }
+}
@@ -0,0 +1,57 @@
+use super::*;
+
+/// Result of cross-checking the relic tables of an index against each other.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+  pub height: Option<u32>,
+  pub relics: usize,
+  pub outputs: usize,
+  pub syndicates: usize,
+  pub chests: usize,
+  pub sealings: usize,
+  pub violations: Vec<IntegrityViolation>,
+}
+
+impl IntegrityReport {
+  pub fn ok(&self) -> bool {
+    self.violations.is_empty()
+  }
+}
+
+/// A single broken invariant, tagged with the name of the check that failed.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum IntegrityViolation {
+  /// the relics held by outputs, chests, claimable balances and pools don't add
+  /// up to the circulating supply of the relic
+  Supply {
+    relic: RelicId,
+    held: u128,
+    burned: u128,
+    circulating_supply: u128,
+  },
+  /// outputs hold a relic that was never enshrined
+  UnknownRelic { relic: RelicId, held: u128 },
+  /// a chest references a syndicate that doesn't exist
+  ChestSyndicate {
+    sequence_number: u32,
+    syndicate: SyndicateId,
+  },
+  /// a sealing references an inscription that is not indexed
+  SealingInscription {
+    sequence_number: u32,
+    spaced_relic: SpacedRelic,
+  },
+  /// one side of a pool is empty, so it can't price swaps anymore
+  PoolReserves {
+    relic: RelicId,
+    base_supply: u128,
+    quote_supply: u128,
+  },
+  /// liquidity providers own more shares than the pool has outstanding
+  PoolShares {
+    relic: RelicId,
+    provider_shares: u128,
+    pool_shares: u128,
+  },
+}
@@ -0,0 +1,516 @@
+use super::*;
+
+/// Migrations of the index schema, in the order they are applied.
+///
+/// Changes that can't be made to an existing index, like changing the
+/// encoding of keys, still bump `SCHEMA_VERSION` and require a rebuild.
+/// Additive changes, like a new secondary index or an event field with a
+/// default, append a migration here instead, which brings the table up to
+/// date when an existing index is opened. Tables without migrations are at
+/// version 0.
+pub(crate) const MIGRATIONS: &[Migration] = &[
+  Migration {
+    table: "CHEST_UNLOCK_HEIGHT_TO_SEQUENCE_NUMBER",
+    version: 1,
+    description: "index chests by unlock height",
+    run: index_chest_unlock_heights,
+  },
+  Migration {
+    table: "ADDRESS_TO_LABEL",
+    version: 1,
+    description: "move address labels into the operator database",
+    run: move_address_labels,
+  },
+];
+
+/// In-place change of the schema of a single table.
+pub(crate) struct Migration {
+  /// name of the table, as in `define_table!`
+  pub(crate) table: &'static str,
+  /// version of the table after the migration, one more than before it
+  pub(crate) version: u64,
+  pub(crate) description: &'static str,
+  /// Applies the migration. Runs in the write transaction that records the
+  /// new version, so it is either applied completely or not at all.
+  pub(crate) run: fn(&Index, &WriteTransaction, &MigrationProgress) -> Result,
+}
+
+/// Progress bar of a running migration, hidden like the one of the updater.
+pub(crate) struct MigrationProgress(Option<ProgressBar>);
+
+impl MigrationProgress {
+  fn new(migration: &Migration) -> Self {
+    if cfg!(test) || log_enabled!(log::Level::Info) || integration_test() {
+      return Self(None);
+    }
+
+    let progress_bar = ProgressBar::new(0);
+    progress_bar.set_style(
+      ProgressStyle::with_template(&format!(
+        "[migrating {} to version {}] {{wide_bar}} {{pos}}/{{len}}",
+        migration.table, migration.version
+      ))
+      .unwrap(),
+    );
+
+    Self(Some(progress_bar))
+  }
+
+  /// Sets the number of steps, usually the number of entries migrated.
+  pub(crate) fn set_length(&self, length: u64) {
+    if let Some(progress_bar) = &self.0 {
+      progress_bar.set_length(length);
+    }
+  }
+
+  pub(crate) fn inc(&self, steps: u64) {
+    if let Some(progress_bar) = &self.0 {
+      progress_bar.inc(steps);
+    }
+  }
+
+  fn finish(self) {
+    if let Some(progress_bar) = self.0 {
+      progress_bar.finish_and_clear();
+    }
+  }
+}
+
+/// Adds the chests encased before chests were indexed by unlock height.
+fn index_chest_unlock_heights(
+  _: &Index,
+  wtx: &WriteTransaction,
+  progress: &MigrationProgress,
+) -> Result {
+  let sequence_number_to_chest = wtx.open_table(SEQUENCE_NUMBER_TO_CHEST)?;
+  let syndicate_id_to_syndicate_entry = wtx.open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)?;
+  let mut chest_unlock_height_to_sequence_number =
+    wtx.open_table(CHEST_UNLOCK_HEIGHT_TO_SEQUENCE_NUMBER)?;
+
+  progress.set_length(sequence_number_to_chest.len()?);
+
+  for result in sequence_number_to_chest.iter()? {
+    let (sequence_number, chest) = result?;
+    let chest = ChestEntry::load(chest.value());
+
+    let lock = syndicate_id_to_syndicate_entry
+      .get(chest.syndicate.store())?
+      .and_then(|syndicate| SyndicateEntry::load(syndicate.value()).lock);
+
+    chest_unlock_height_to_sequence_number
+      .insert((chest.unlock_height(lock), sequence_number.value()), ())?;
+
+    progress.inc(1);
+  }
+
+  Ok(())
+}
+
+/// Moves the address labels of the index database into the operator database,
+/// where reorgs don't revert them.
+fn move_address_labels(
+  index: &Index,
+  wtx: &WriteTransaction,
+  progress: &MigrationProgress,
+) -> Result {
+  // a reorg restores the labels of savepoints taken before they were moved,
+  // which are older than the ones in the operator database
+  let moved = match index
+    .operator_database
+    .begin_read()?
+    .open_table(ADDRESS_TO_LABEL)
+  {
+    Ok(_) => true,
+    Err(redb::TableError::TableDoesNotExist(_)) => false,
+    Err(err) => return Err(err.into()),
+  };
+
+  if !moved {
+    let labels = wtx.open_table(ADDRESS_TO_LABEL)?;
+
+    progress.set_length(labels.len()?);
+
+    let operator_wtx = index.begin_operator_write()?;
+
+    {
+      let mut operator_labels = operator_wtx.open_table(ADDRESS_TO_LABEL)?;
+
+      for result in labels.iter()? {
+        let (address, label) = result?;
+        operator_labels.insert(address.value(), label.value())?;
+        progress.inc(1);
+      }
+    }
+
+    operator_wtx.commit()?;
+  }
+
+  wtx.delete_table(ADDRESS_TO_LABEL)?;
+
+  Ok(())
+}
+
+/// Latest version of every table with migrations, which new indexes start at.
+pub(crate) fn latest_versions(migrations: &[Migration]) -> BTreeMap<&'static str, u64> {
+  let mut versions = BTreeMap::new();
+
+  for migration in migrations {
+    let version = versions.entry(migration.table).or_default();
+    *version = migration.version.max(*version);
+  }
+
+  versions
+}
+
+impl Index {
+  /// Applies the migrations newer than the version of their table, each in a
+  /// write transaction of its own, so an interrupted migration is resumed at
+  /// the migration it stopped at. A savepoint restored by a reorg also
+  /// restores table versions, so the index is migrated again afterwards.
+  pub(crate) fn migrate(&self, migrations: &[Migration]) -> Result {
+    let latest = latest_versions(migrations);
+
+    let mut versions = BTreeMap::new();
+
+    {
+      let rtx = self.database.begin_read()?;
+
+      // indexes created before migrations were introduced have no versions
+      match rtx.open_table(TABLE_TO_SCHEMA_VERSION) {
+        Ok(table) => {
+          for result in table.iter()? {
+            let (table, version) = result?;
+            versions.insert(table.value().to_string(), version.value());
+          }
+        }
+        Err(redb::TableError::TableDoesNotExist(_)) => {}
+        Err(err) => return Err(err.into()),
+      }
+    }
+
+    for (table, version) in &versions {
+      let known = latest.get(table.as_str()).copied().unwrap_or_default();
+      if *version > known {
+        bail!(
+          "index at `{}` appears to have been migrated by a newer, incompatible version of ord, consider updating ord: table {table} schema {version}, ord schema {known}",
+          self.path.display()
+        );
+      }
+    }
+
+    for migration in migrations {
+      let version = versions.get(migration.table).copied().unwrap_or_default();
+
+      if migration.version <= version {
+        continue;
+      }
+
+      ensure!(
+        migration.version == version + 1,
+        "migration of table {} to version {} requires version {}, but the table is at version {version}",
+        migration.table,
+        migration.version,
+        migration.version - 1,
+      );
+
+      log::info!(
+        "migrating table {} to version {}: {}",
+        migration.table,
+        migration.version,
+        migration.description
+      );
+
+      let start = Instant::now();
+
+      let progress = MigrationProgress::new(migration);
+
+      let wtx = self.begin_write()?;
+
+      (migration.run)(self, &wtx, &progress)?;
+
+      wtx
+        .open_table(TABLE_TO_SCHEMA_VERSION)?
+        .insert(migration.table, migration.version)?;
+
+      Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+      wtx.commit()?;
+
+      progress.finish();
+
+      log::info!(
+        "migrated table {} to version {} in {:.1}s",
+        migration.table,
+        migration.version,
+        start.elapsed().as_secs_f64()
+      );
+
+      versions.insert(migration.table.to_string(), migration.version);
+    }
+
+    Ok(())
+  }
+
+  /// Schema versions of the tables of the index, see `Migration`.
+  pub(crate) fn table_schema_versions(&self) -> Result<BTreeMap<String, u64>> {
+    let rtx = self.database.begin_read()?;
+
+    let table = match rtx.open_table(TABLE_TO_SCHEMA_VERSION) {
+      Ok(table) => table,
+      Err(redb::TableError::TableDoesNotExist(_)) => return Ok(BTreeMap::new()),
+      Err(err) => return Err(err.into()),
+    };
+
+    table
+      .iter()?
+      .map(|result| {
+        let (table, version) = result?;
+        Ok((table.value().to_string(), version.value()))
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::{index::testing::Context, relics::Summoning},
+  };
+
+  define_table! { MIGRATED, u32, u32 }
+
+  fn insert_one(_: &Index, wtx: &WriteTransaction, progress: &MigrationProgress) -> Result {
+    progress.set_length(1);
+
+    let mut table = wtx.open_table(MIGRATED)?;
+    let count = table.len()?;
+    table.insert(u32::try_from(count).unwrap(), 1)?;
+
+    progress.inc(1);
+
+    Ok(())
+  }
+
+  fn migrated(index: &Index) -> u64 {
+    match index.database.begin_read().unwrap().open_table(MIGRATED) {
+      Ok(table) => table.len().unwrap(),
+      Err(redb::TableError::TableDoesNotExist(_)) => 0,
+      Err(err) => panic!("{err}"),
+    }
+  }
+
+  const FIRST: Migration = Migration {
+    table: "MIGRATED",
+    version: 1,
+    description: "first",
+    run: insert_one,
+  };
+
+  const SECOND: Migration = Migration {
+    table: "MIGRATED",
+    version: 2,
+    description: "second",
+    run: insert_one,
+  };
+
+  #[test]
+  fn migrations_are_applied_once() {
+    let context = Context::builder().build();
+
+    context.index.migrate(&[FIRST]).unwrap();
+    assert_eq!(migrated(&context.index), 1);
+
+    context.index.migrate(&[FIRST, SECOND]).unwrap();
+    assert_eq!(migrated(&context.index), 2);
+
+    context.index.migrate(&[FIRST, SECOND]).unwrap();
+    assert_eq!(migrated(&context.index), 2);
+
+    assert_eq!(
+      context.index.table_schema_versions().unwrap(),
+      [("MIGRATED".into(), 2)].into()
+    );
+  }
+
+  #[test]
+  fn failed_migrations_are_rolled_back() {
+    fn fail(index: &Index, wtx: &WriteTransaction, progress: &MigrationProgress) -> Result {
+      insert_one(index, wtx, progress)?;
+      bail!("failed");
+    }
+
+    let context = Context::builder().build();
+
+    context
+      .index
+      .migrate(&[
+        FIRST,
+        Migration {
+          run: fail,
+          ..SECOND
+        },
+      ])
+      .unwrap_err();
+
+    assert_eq!(migrated(&context.index), 1);
+    assert_eq!(
+      context.index.table_schema_versions().unwrap(),
+      [("MIGRATED".into(), 1)].into()
+    );
+
+    context.index.migrate(&[FIRST, SECOND]).unwrap();
+    assert_eq!(migrated(&context.index), 2);
+  }
+
+  #[test]
+  fn tables_migrated_by_newer_versions_are_rejected() {
+    let context = Context::builder().build();
+
+    context.index.migrate(&[FIRST, SECOND]).unwrap();
+
+    assert!(context
+      .index
+      .migrate(&[FIRST])
+      .unwrap_err()
+      .to_string()
+      .contains("table MIGRATED schema 2, ord schema 1"));
+  }
+
+  #[test]
+  fn migrations_must_not_skip_versions() {
+    let context = Context::builder().build();
+
+    context.index.migrate(&[SECOND]).unwrap_err();
+    assert_eq!(migrated(&context.index), 0);
+  }
+
+  #[test]
+  fn new_indexes_start_at_the_latest_versions() {
+    let context = Context::builder().build();
+
+    assert_eq!(
+      context.index.table_schema_versions().unwrap(),
+      latest_versions(MIGRATIONS)
+        .into_iter()
+        .map(|(table, version)| (table.to_string(), version))
+        .collect()
+    );
+  }
+
+  #[test]
+  fn chests_encased_before_the_unlock_height_index_are_added_to_it() {
+    let context = Context::builder().build();
+
+    let syndicate = SyndicateId { block: 10, tx: 1 };
+
+    let wtx = context.index.begin_write().unwrap();
+
+    wtx
+      .open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)
+      .unwrap()
+      .insert(
+        syndicate.store(),
+        SyndicateEntry::new(
+          Summoning {
+            lock: Some(100),
+            ..default()
+          },
+          0,
+          Txid::all_zeros(),
+        )
+        .store(),
+      )
+      .unwrap();
+
+    {
+      let mut sequence_number_to_chest = wtx.open_table(SEQUENCE_NUMBER_TO_CHEST).unwrap();
+
+      for (sequence_number, locked_until) in [(1, None), (2, Some(500))] {
+        sequence_number_to_chest
+          .insert(
+            sequence_number,
+            ChestEntry {
+              sequence_number,
+              syndicate,
+              created_block: 20,
+              locked_until,
+              ..default()
+            }
+            .store(),
+          )
+          .unwrap();
+      }
+    }
+
+    assert_eq!(
+      MIGRATIONS[0].table,
+      CHEST_UNLOCK_HEIGHT_TO_SEQUENCE_NUMBER.name()
+    );
+
+    (MIGRATIONS[0].run)(&context.index, &wtx, &MigrationProgress(None)).unwrap();
+
+    assert_eq!(
+      wtx
+        .open_table(CHEST_UNLOCK_HEIGHT_TO_SEQUENCE_NUMBER)
+        .unwrap()
+        .iter()
+        .unwrap()
+        .map(|result| result.unwrap().0.value())
+        .collect::<Vec<(u64, u32)>>(),
+      [(120, 1), (500, 2)]
+    );
+  }
+
+  #[test]
+  fn address_labels_are_moved_into_the_operator_database() {
+    let context = Context::builder().build();
+
+    let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+    let label = r#"{"label":"Burn","tags":[]}"#;
+
+    let wtx = context.index.begin_write().unwrap();
+
+    wtx
+      .open_table(ADDRESS_TO_LABEL)
+      .unwrap()
+      .insert(address, label)
+      .unwrap();
+
+    assert_eq!(MIGRATIONS[1].table, ADDRESS_TO_LABEL.name());
+
+    (MIGRATIONS[1].run)(&context.index, &wtx, &MigrationProgress(None)).unwrap();
+
+    wtx.commit().unwrap();
+
+    assert_eq!(
+      context
+        .index
+        .get_address_label(&address.parse().unwrap())
+        .unwrap()
+        .unwrap()
+        .label,
+      "Burn"
+    );
+
+    assert!(matches!(
+      context
+        .index
+        .database
+        .begin_read()
+        .unwrap()
+        .open_table(ADDRESS_TO_LABEL),
+      Err(redb::TableError::TableDoesNotExist(_))
+    ));
+  }
+
+  #[test]
+  fn migrations_increment_versions_of_their_table() {
+    let mut versions = BTreeMap::<&str, u64>::new();
+
+    for migration in MIGRATIONS {
+      let version = versions.entry(migration.table).or_default();
+      assert_eq!(migration.version, *version + 1, "{}", migration.table);
+      *version = migration.version;
+    }
+  }
+}
@@ -0,0 +1,68 @@
+use {super::*, bincode::Options, redb::TypeName};
+
+/// A move of a sealing inscription, which controls its relic, to a new owner.
+/// The first change of every sealing is the sealing itself.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OwnerChange {
+  pub height: u32,
+  pub txid: Txid,
+  pub location: SatPoint,
+  /// `None` if the inscription was burned, lost to fees or sent to a
+  /// script without an address
+  pub address: Option<String>,
+}
+
+impl OwnerChange {
+  /// Appends `change` to the owner history of the sealing inscription with
+  /// `sequence_number`.
+  pub(super) fn record(
+    table: &mut Table<(u32, u32), OwnerChange>,
+    sequence_number: u32,
+    change: &OwnerChange,
+  ) -> Result {
+    let next = table
+      .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+      .next_back()
+      .transpose()?
+      .map(|(key, _)| key.value().1 + 1)
+      .unwrap_or_default();
+
+    table.insert((sequence_number, next), change)?;
+
+    Ok(())
+  }
+}
+
+impl redb::Value for OwnerChange {
+  type SelfType<'a>
+    = Self
+  where
+    Self: 'a;
+  type AsBytes<'a>
+    = Vec<u8>
+  where
+    Self: 'a;
+
+  fn fixed_width() -> Option<usize> {
+    None
+  }
+
+  fn from_bytes<'a>(data: &'a [u8]) -> Self::SelfType<'a>
+  where
+    Self: 'a,
+  {
+    bincode::DefaultOptions::new().deserialize(data).unwrap()
+  }
+
+  fn as_bytes<'a, 'b: 'a>(value: &'a Self::SelfType<'b>) -> Self::AsBytes<'a>
+  where
+    Self: 'a,
+    Self: 'b,
+  {
+    bincode::DefaultOptions::new().serialize(value).unwrap()
+  }
+
+  fn type_name() -> TypeName {
+    TypeName::new("OwnerChange")
+  }
+}
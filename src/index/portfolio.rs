@@ -0,0 +1,168 @@
+use super::*;
+
+/// Relic balances of an address, valued in the base token.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelicPortfolio {
+  #[serde(rename = "bones")]
+  pub relics: Vec<RelicValuation>,
+  /// sum of the values of the relics with a price
+  pub value: u128,
+  /// sum of the liquidation values of the relics that can be sold
+  pub liquidation_value: u128,
+}
+
+impl RelicPortfolio {
+  pub fn new(relics: Vec<RelicValuation>) -> Self {
+    Self {
+      value: relics
+        .iter()
+        .filter_map(|relic| relic.value)
+        .fold(0, u128::saturating_add),
+      liquidation_value: relics
+        .iter()
+        .filter_map(|relic| relic.liquidation_value)
+        .fold(0, u128::saturating_add),
+      relics,
+    }
+  }
+}
+
+/// Value of a relic balance in the base token.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelicValuation {
+  pub id: RelicId,
+  #[serde(rename = "spaced_bone")]
+  pub spaced_relic: SpacedRelic,
+  pub amount: u128,
+  /// `amount` at the current pool price, `None` if the relic has no pool yet
+  pub value: Option<u128>,
+  /// base token received for selling all of `amount` to the pool now, after
+  /// the pool fee and the price moving, `None` if the relic can't be sold
+  pub liquidation_value: Option<u128>,
+  /// `value` lost by liquidating, in basis points
+  pub slippage_bps: Option<u128>,
+}
+
+impl RelicValuation {
+  /// Values `amount` of relic `id` with the pool of its `entry` at `height`,
+  /// quoting the sale like `Index::get_swap_route`.
+  pub fn new(id: RelicId, entry: &RelicEntry, amount: u128, height: u64) -> Self {
+    if id == RELIC_ID {
+      return Self {
+        id,
+        spaced_relic: entry.spaced_relic,
+        amount,
+        value: Some(amount),
+        liquidation_value: Some(amount),
+        slippage_bps: Some(0),
+      };
+    }
+
+    let value = entry.pool.map(|pool| {
+      amount
+        .checked_mul(pool.base_supply)
+        .map(|value| value / pool.quote_supply.max(1))
+        // too large for exact arithmetic, so the value is approximate
+        .unwrap_or_else(|| {
+          (amount as f64 * pool.base_supply as f64 / pool.quote_supply.max(1) as f64) as u128
+        })
+    });
+
+    let hop = entry
+      .swap_hop(id, SwapDirection::QuoteToBase, amount, height)
+      .ok();
+
+    Self {
+      id,
+      spaced_relic: entry.spaced_relic,
+      amount,
+      value,
+      liquidation_value: hop.map(|hop| hop.output_amount),
+      slippage_bps: hop.map(|hop| hop.slippage_bps),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::relics::Pool};
+
+  fn entry(pool: Option<Pool>) -> RelicEntry {
+    RelicEntry {
+      spaced_relic: SpacedRelic::from_str("FOO•BAR").unwrap(),
+      pool,
+      ..default()
+    }
+  }
+
+  const ID: RelicId = RelicId { block: 10, tx: 1 };
+
+  #[test]
+  fn relics_are_valued_at_the_pool_price() {
+    let valuation = RelicValuation::new(
+      ID,
+      &entry(Some(Pool {
+        base_supply: 1_000_000,
+        quote_supply: 500_000,
+        fee_bps: 100,
+        shares: 0,
+      })),
+      1000,
+      0,
+    );
+
+    assert_eq!(valuation.value, Some(2000));
+
+    // the fee and the price moving lower the liquidation value
+    let liquidation_value = valuation.liquidation_value.unwrap();
+    assert!(liquidation_value < 2000);
+    assert!(liquidation_value > 1950);
+    assert!(valuation.slippage_bps.unwrap() >= 100);
+  }
+
+  #[test]
+  fn selling_into_shallow_pools_slips() {
+    let valuation = RelicValuation::new(
+      ID,
+      &entry(Some(Pool {
+        base_supply: 1000,
+        quote_supply: 1000,
+        fee_bps: 0,
+        shares: 0,
+      })),
+      1000,
+      0,
+    );
+
+    assert_eq!(valuation.value, Some(1000));
+    assert_eq!(valuation.liquidation_value, Some(500));
+    assert_eq!(valuation.slippage_bps, Some(5000));
+  }
+
+  #[test]
+  fn relics_without_pool_have_no_value() {
+    let valuation = RelicValuation::new(ID, &entry(None), 1000, 0);
+    assert_eq!(valuation.value, None);
+    assert_eq!(valuation.liquidation_value, None);
+    assert_eq!(valuation.slippage_bps, None);
+  }
+
+  #[test]
+  fn base_token_is_valued_at_its_amount() {
+    let valuation = RelicValuation::new(RELIC_ID, &entry(None), 1000, 0);
+    assert_eq!(valuation.value, Some(1000));
+    assert_eq!(valuation.liquidation_value, Some(1000));
+    assert_eq!(valuation.slippage_bps, Some(0));
+  }
+
+  #[test]
+  fn totals_skip_relics_without_value() {
+    let portfolio = RelicPortfolio::new(vec![
+      RelicValuation::new(RELIC_ID, &entry(None), 1000, 0),
+      RelicValuation::new(ID, &entry(None), 1000, 0),
+    ]);
+
+    assert_eq!(portfolio.value, 1000);
+    assert_eq!(portfolio.liquidation_value, 1000);
+  }
+}
@@ -1,7 +1,8 @@
 use {
   super::*,
   crate::relics::{
-    BalanceDiff, MintTerms, Pool, PoolSwap, Relic, RelicError, RelicId, SpacedRelic,
+    BalanceDiff, MintTerms, Pool, PoolError, PoolSwap, Relic, RelicError, RelicId, SpacedRelic,
+    SwapDirection,
   },
   bitcoin::ScriptHash,
 };
@@ -111,6 +112,172 @@ impl Entry for RelicState {
   }
 }
 
+/// Supply figures of a relic at the end of a block.
+#[derive(Debug, Default, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct RelicSupply {
+  pub circulating: u128,
+  pub burned: u128,
+  pub mints: u128,
+}
+
+pub type RelicSupplyValue = (u128, u128, u128);
+
+impl Entry for RelicSupply {
+  type Value = RelicSupplyValue;
+
+  fn load((circulating, burned, mints): Self::Value) -> Self {
+    Self {
+      circulating,
+      burned,
+      mints,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (self.circulating, self.burned, self.mints)
+  }
+}
+
+/// Base tokens paid in fees, cumulative up to the end of a block.
+#[derive(Debug, Default, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct RelicFees {
+  /// burned when sealing the ticker
+  pub sealing: u128,
+  /// paid for mints and locked in the pool
+  pub mints: u128,
+  /// collected by the pool on swaps
+  pub swaps: u128,
+}
+
+pub type RelicFeesValue = (u128, u128, u128);
+
+impl Entry for RelicFees {
+  type Value = RelicFeesValue;
+
+  fn load((sealing, mints, swaps): Self::Value) -> Self {
+    Self {
+      sealing,
+      mints,
+      swaps,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (self.sealing, self.mints, self.swaps)
+  }
+}
+
+impl RelicFees {
+  pub fn total(&self) -> u128 {
+    self
+      .sealing
+      .saturating_add(self.mints)
+      .saturating_add(self.swaps)
+  }
+
+  pub fn add(self, other: Self) -> Self {
+    Self {
+      sealing: self.sealing.checked_add(other.sealing).unwrap(),
+      mints: self.mints.checked_add(other.mints).unwrap(),
+      swaps: self.swaps.checked_add(other.swaps).unwrap(),
+    }
+  }
+
+  /// Fees paid after `earlier`, given both are cumulative.
+  pub fn since(self, earlier: Self) -> Self {
+    Self {
+      sealing: self.sealing.saturating_sub(earlier.sealing),
+      mints: self.mints.saturating_sub(earlier.mints),
+      swaps: self.swaps.saturating_sub(earlier.swaps),
+    }
+  }
+}
+
+/// Mints and swaps of a relic within a single block.
+#[derive(Debug, Default, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct RelicActivity {
+  pub mints: u64,
+  pub swaps: u64,
+  /// base tokens traded by swaps
+  pub volume: u128,
+  /// base tokens paid for mints and in swap fees
+  pub fees: u128,
+}
+
+pub type RelicActivityValue = (u64, u64, u128, u128);
+
+impl Entry for RelicActivity {
+  type Value = RelicActivityValue;
+
+  fn load((mints, swaps, volume, fees): Self::Value) -> Self {
+    Self {
+      mints,
+      swaps,
+      volume,
+      fees,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (self.mints, self.swaps, self.volume, self.fees)
+  }
+}
+
+impl RelicActivity {
+  pub fn add(self, other: Self) -> Self {
+    Self {
+      mints: self.mints.saturating_add(other.mints),
+      swaps: self.swaps.saturating_add(other.swaps),
+      volume: self.volume.saturating_add(other.volume),
+      fees: self.fees.saturating_add(other.fees),
+    }
+  }
+
+  pub fn metric(&self, metric: ActivityMetric) -> u128 {
+    match metric {
+      ActivityMetric::Volume => self.volume,
+      ActivityMetric::Fees => self.fees,
+      ActivityMetric::Mints => self.mints.into(),
+    }
+  }
+}
+
+/// What relics are ranked by on the leaderboard.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivityMetric {
+  Volume,
+  Fees,
+  Mints,
+}
+
+/// Projected end of the minting of a relic, extrapolated from the mints of
+/// recent blocks.
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct RelicEstimates {
+  /// average number of mints per block in recent blocks
+  pub mints_per_block: f64,
+  /// block in which the last mint is expected
+  pub mint_out_height: u64,
+  /// first block in which the pool is expected to be swappable, if minting
+  /// out creates one
+  pub pool_launch_height: Option<u64>,
+}
+
+/// One pool swap of a route, see `Index::get_swap_route`.
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct SwapHop {
+  /// the relic whose pool is swapped with
+  pub pool: RelicId,
+  pub direction: SwapDirection,
+  pub input_amount: u128,
+  pub output_amount: u128,
+  pub fee: u128,
+  /// output lost to the price moving and the fee, relative to swapping at the
+  /// current pool price, in basis points
+  pub slippage_bps: u128,
+}
+
 #[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
 pub struct RelicEntry {
   pub block: u64,
@@ -127,11 +294,87 @@ pub struct RelicEntry {
 }
 
 impl RelicEntry {
-  pub fn mintable(&self, base_balance: u128) -> Result<(u128, u128), RelicError> {
+  /// blocks of mint history the estimates are extrapolated from, about a day
+  pub const ESTIMATE_BLOCKS: u64 = 1440;
+
+  pub fn supply(&self) -> RelicSupply {
+    RelicSupply {
+      circulating: self.circulating_supply(),
+      burned: self.state.burned,
+      mints: self.state.mints,
+    }
+  }
+
+  pub fn pile(&self, amount: u128) -> Pile {
+    Pile {
+      amount,
+      divisibility: Enshrining::DIVISIBILITY,
+      symbol: self.symbol,
+    }
+  }
+
+  /// Amounts of this relic's supply, terms and pool, keyed by field name and
+  /// rendered with divisibility and symbol. Prices and pool base supply are
+  /// denominated in the base token.
+  pub fn formatted_amounts(&self) -> BTreeMap<String, String> {
+    let base = |amount| Pile {
+      amount,
+      divisibility: Enshrining::DIVISIBILITY,
+      symbol: Some('\u{1F9B4}'),
+    };
+
+    let mut formatted = BTreeMap::new();
+
+    formatted.insert("burned".into(), self.pile(self.state.burned).to_string());
+    formatted.insert(
+      "circulating_supply".into(),
+      self.pile(self.circulating_supply()).to_string(),
+    );
+    formatted.insert(
+      "max_supply".into(),
+      self.pile(self.max_supply()).to_string(),
+    );
+
+    if let Some(terms) = self.mint_terms {
+      if let Some(amount) = terms.amount {
+        formatted.insert("mint_amount".into(), self.pile(amount).to_string());
+      }
+      if let Some(price) = terms.price {
+        formatted.insert("mint_price".into(), base(price).to_string());
+      }
+    }
+
+    if let Some(pool) = self.pool {
+      formatted.insert(
+        "pool_base_supply".into(),
+        base(pool.base_supply).to_string(),
+      );
+      formatted.insert(
+        "pool_quote_supply".into(),
+        self.pile(pool.quote_supply).to_string(),
+      );
+    }
+
+    formatted
+  }
+
+  /// checks every rule the indexer enforces on a mint at the given height,
+  /// the balance check is skipped if no base token balance is given
+  /// returns the amount of minted tokens and the price in base tokens
+  pub fn mintable_at(
+    &self,
+    height: u64,
+    base_balance: Option<u128>,
+  ) -> Result<(u128, u128), RelicError> {
     let Some(terms) = self.mint_terms else {
       return Err(RelicError::Unmintable);
     };
 
+    // the relic does not exist before it was enshrined
+    if height < self.block {
+      return Err(RelicError::Unmintable);
+    }
+
     let cap = terms.cap.unwrap_or_default();
 
     if self.state.mints >= cap {
@@ -140,19 +383,18 @@ impl RelicEntry {
 
     let price = terms.price.unwrap_or_default();
 
-    if base_balance < price {
-      return Err(RelicError::MintInsufficientBalance(price));
+    if let Some(base_balance) = base_balance {
+      if base_balance < price {
+        return Err(RelicError::MintInsufficientBalance(price));
+      }
     }
 
     Ok((terms.amount.unwrap_or_default(), price))
   }
 
-  pub fn swap(
-    &self,
-    swap: PoolSwap,
-    balance: Option<u128>,
-    height: u64,
-  ) -> Result<BalanceDiff, RelicError> {
+  /// checks every rule the indexer enforces on a swap at the given height
+  /// returns the liquidity pool to swap with
+  pub fn swappable_at(&self, height: u64) -> Result<Pool, RelicError> {
     // fail the swap if pool does not exist (yet)
     let Some(pool) = self.pool else {
       return Err(RelicError::SwapNotAvailable);
@@ -165,6 +407,17 @@ impl RelicEntry {
       }
     }
 
+    Ok(pool)
+  }
+
+  pub fn swap(
+    &self,
+    swap: PoolSwap,
+    balance: Option<u128>,
+    height: u64,
+  ) -> Result<BalanceDiff, RelicError> {
+    let pool = self.swappable_at(height)?;
+
     match pool.calculate(swap) {
       Ok(diff) => {
         if let Some(balance) = balance {
@@ -178,6 +431,60 @@ impl RelicEntry {
     }
   }
 
+  /// exact-input swap of `input` with the pool of this relic, without a
+  /// slippage limit
+  pub fn swap_hop(
+    &self,
+    id: RelicId,
+    direction: SwapDirection,
+    input: u128,
+    height: u64,
+  ) -> Result<SwapHop, RelicError> {
+    let pool = self.swappable_at(height)?;
+
+    let diff = self.swap(
+      PoolSwap::Input {
+        direction,
+        input,
+        min_output: None,
+      },
+      None,
+      height,
+    )?;
+
+    let (from, to) = match direction {
+      SwapDirection::BaseToQuote => (pool.base_supply, pool.quote_supply),
+      SwapDirection::QuoteToBase => (pool.quote_supply, pool.base_supply),
+    };
+
+    let overflow = RelicError::SwapFailed(PoolError::Overflow);
+
+    let spot_output = input
+      .checked_mul(to)
+      .ok_or(overflow)?
+      .checked_div(from)
+      .unwrap_or_default();
+
+    let slippage_bps = if spot_output == 0 {
+      0
+    } else {
+      spot_output
+        .saturating_sub(diff.output)
+        .checked_mul(10_000)
+        .ok_or(overflow)?
+        / spot_output
+    };
+
+    Ok(SwapHop {
+      pool: id,
+      direction,
+      input_amount: diff.input,
+      output_amount: diff.output,
+      fee: diff.fee,
+      slippage_bps,
+    })
+  }
+
   /// max supply of this token: maximum amount of tokens that can be minted plus
   /// the additional amount that is created for the pool after minting is complete
   /// and the total subsidy
@@ -208,6 +515,38 @@ impl RelicEntry {
       - self.state.burned
   }
 
+  /// Estimates when minting ends, given that `recent_mints` mints were made
+  /// in the `blocks` blocks up to and including `height`. Returns `None` if the
+  /// relic is not minting or nobody minted recently.
+  pub fn estimates(&self, height: u64, recent_mints: u128, blocks: u64) -> Option<RelicEstimates> {
+    let terms = self.mint_terms?;
+    let cap = terms.cap.unwrap_or_default();
+
+    if self.pool.is_some() || self.state.mints >= cap || recent_mints == 0 || blocks == 0 {
+      return None;
+    }
+
+    // blocks needed for the remaining mints at the recent rate, rounded up
+    let remaining = cap - self.state.mints;
+    let needed = remaining
+      .checked_mul(blocks.into())?
+      .checked_add(recent_mints - 1)?
+      / recent_mints;
+
+    let mint_out_height = height.checked_add(u64::try_from(needed).ok()?)?;
+
+    // the pool is only created if both of its supplies are non-zero
+    let pool_launch_height = (terms.price.unwrap_or_default() > 0
+      && terms.seed.unwrap_or_default() > 0)
+      .then_some(mint_out_height.max(terms.swap_height.unwrap_or_default()));
+
+    Some(RelicEstimates {
+      mints_per_block: recent_mints as f64 / blocks as f64,
+      mint_out_height,
+      pool_launch_height,
+    })
+  }
+
   pub fn locked_base_supply(&self) -> u128 {
     self.pool.map(|pool| pool.base_supply).unwrap_or(
       self.state.mints
@@ -225,18 +564,20 @@ type MintTermsValue = (
   Option<u128>, // price
   Option<u128>, // seed
   Option<u64>,  // swap_height
+  Option<u16>,  // fee
 );
 
 impl Entry for MintTerms {
   type Value = MintTermsValue;
 
-  fn load((amount, cap, price, seed, swap_height): Self::Value) -> Self {
+  fn load((amount, cap, price, seed, swap_height, fee): Self::Value) -> Self {
     Self {
       amount,
       cap,
       price,
       seed,
       swap_height,
+      fee,
     }
   }
 
@@ -247,25 +588,32 @@ impl Entry for MintTerms {
       self.price,
       self.seed,
       self.swap_height,
+      self.fee,
     )
   }
 }
 
-pub type PoolValue = (u128, u128, u8);
+pub type PoolValue = (u128, u128, u16, u128);
 
 impl Entry for Pool {
   type Value = PoolValue;
 
-  fn load((base_supply, quote_supply, fee_percentage): Self::Value) -> Self {
+  fn load((base_supply, quote_supply, fee_bps, shares): Self::Value) -> Self {
     Self {
       base_supply,
       quote_supply,
-      fee_percentage,
+      fee_bps,
+      shares,
     }
   }
 
   fn store(self) -> Self::Value {
-    (self.base_supply, self.quote_supply, self.fee_percentage)
+    (
+      self.base_supply,
+      self.quote_supply,
+      self.fee_bps,
+      self.shares,
+    )
   }
 }
 
@@ -406,6 +754,7 @@ mod tests {
         price: Some(8),
         seed: Some(22),
         swap_height: Some(400_000),
+        fee: Some(30),
       }),
       state: RelicState {
         burned: 33,
@@ -417,7 +766,8 @@ mod tests {
       pool: Some(Pool {
         base_supply: 321,
         quote_supply: 123,
-        fee_percentage: 13,
+        fee_bps: 1300,
+        shares: 321,
       }),
       timestamp: 10,
       turbo: true,
@@ -433,9 +783,9 @@ mod tests {
       (7, 8),
       Some('a'),
       Some(123),
-      Some((Some(4), Some(1), Some(8), Some(22), Some(400_000))),
+      Some((Some(4), Some(1), Some(8), Some(22), Some(400_000), Some(30))),
       (33, 44, 55, 66, true),
-      Some((321, 123, 13)),
+      Some((321, 123, 1300, 321)),
       10,
       true,
     );
@@ -453,8 +803,132 @@ mod tests {
   #[test]
   fn mintable_default() {
     assert_eq!(
-      RelicEntry::default().mintable(0),
+      RelicEntry::default().mintable_at(0, Some(0)),
       Err(RelicError::Unmintable)
     );
   }
+
+  #[test]
+  fn mintable_at() {
+    let entry = RelicEntry {
+      block: 10,
+      mint_terms: Some(MintTerms {
+        amount: Some(1000),
+        cap: Some(1),
+        price: Some(5),
+        ..default()
+      }),
+      ..default()
+    };
+
+    assert_eq!(entry.mintable_at(9, None), Err(RelicError::Unmintable));
+    assert_eq!(entry.mintable_at(10, None), Ok((1000, 5)));
+    assert_eq!(
+      entry.mintable_at(10, Some(4)),
+      Err(RelicError::MintInsufficientBalance(5))
+    );
+    assert_eq!(entry.mintable_at(10, Some(5)), Ok((1000, 5)));
+
+    let minted_out = RelicEntry {
+      state: RelicState {
+        mints: 1,
+        ..default()
+      },
+      ..entry
+    };
+
+    assert_eq!(
+      minted_out.mintable_at(10, None),
+      Err(RelicError::MintCap(1))
+    );
+  }
+
+  #[test]
+  fn estimates() {
+    let entry = RelicEntry {
+      block: 10,
+      mint_terms: Some(MintTerms {
+        amount: Some(1000),
+        cap: Some(100),
+        price: Some(5),
+        seed: Some(1000),
+        swap_height: Some(200),
+        fee: None,
+      }),
+      state: RelicState {
+        mints: 40,
+        ..default()
+      },
+      ..default()
+    };
+
+    assert_eq!(entry.estimates(50, 0, 40), None);
+
+    // 60 remaining mints at 0.75 mints per block take 80 blocks
+    assert_eq!(
+      entry.estimates(50, 30, 40),
+      Some(RelicEstimates {
+        mints_per_block: 0.75,
+        mint_out_height: 130,
+        pool_launch_height: Some(200),
+      })
+    );
+
+    // partial blocks are rounded up
+    assert_eq!(entry.estimates(50, 7, 1).unwrap().mint_out_height, 59);
+
+    let unseeded = RelicEntry {
+      mint_terms: Some(MintTerms {
+        seed: None,
+        ..entry.mint_terms.unwrap()
+      }),
+      ..entry
+    };
+
+    assert_eq!(
+      unseeded.estimates(50, 30, 40).unwrap().pool_launch_height,
+      None
+    );
+
+    let minted_out = RelicEntry {
+      state: RelicState {
+        mints: 100,
+        ..default()
+      },
+      ..entry
+    };
+
+    assert_eq!(minted_out.estimates(50, 30, 40), None);
+  }
+
+  #[test]
+  fn swappable_at() {
+    let pool = Pool {
+      base_supply: 100,
+      quote_supply: 100,
+      fee_bps: 100,
+      shares: 100,
+    };
+
+    assert_eq!(
+      RelicEntry::default().swappable_at(0),
+      Err(RelicError::SwapNotAvailable)
+    );
+
+    let entry = RelicEntry {
+      mint_terms: Some(MintTerms {
+        swap_height: Some(20),
+        fee: None,
+        ..default()
+      }),
+      pool: Some(pool),
+      ..default()
+    };
+
+    assert_eq!(
+      entry.swappable_at(19),
+      Err(RelicError::SwapHeightNotReached(20))
+    );
+    assert_eq!(entry.swappable_at(20), Ok(pool));
+  }
 }
@@ -71,6 +71,11 @@ impl Reorg {
       index.block_count()?
     );
 
+    // the savepoint may predate migrations
+    index.migrate(MIGRATIONS)?;
+
+    index.rewind_event_dispatch(index.block_count()?)?;
+
     Ok(())
   }
 
@@ -104,3 +109,103 @@ impl Reorg {
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::index::{address_label::AddressLabel, testing::Context},
+  };
+
+  /// Takes a savepoint of the current index, which `Reorg::handle_reorg` rolls
+  /// back to.
+  fn savepoint(context: &Context) {
+    Reorg::update_savepoints(&context.index, context.index.block_count().unwrap()).unwrap();
+  }
+
+  #[test]
+  fn labels_survive_reorgs() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    savepoint(&context);
+
+    let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+      .parse::<Address>()
+      .unwrap();
+
+    let label = AddressLabel {
+      label: "Burn".into(),
+      tags: ["burn".into()].into(),
+    };
+
+    context
+      .index
+      .set_address_label(&address, Some(&label))
+      .unwrap();
+
+    context.mine_blocks(1);
+
+    Reorg::handle_reorg(&context.index, 2, 1).unwrap();
+
+    assert_eq!(context.index.block_count().unwrap(), 2);
+    assert_eq!(
+      context.index.get_address_label(&address).unwrap(),
+      Some(label)
+    );
+  }
+
+  #[test]
+  fn events_dispatched_before_a_reorg_are_not_dispatched_again() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .arg("--index-relics")
+      .event_sender(event_sender)
+      .build();
+
+    context.mint_base_token(1, 1);
+
+    savepoint(&context);
+
+    let dispatched = context.index.dispatch_pending_events().unwrap().unwrap();
+    assert!(dispatched > 0);
+
+    let block_count = context.index.block_count().unwrap();
+
+    context.mine_blocks(1);
+
+    Reorg::handle_reorg(&context.index, block_count + 1, 1).unwrap();
+
+    // the savepoint brought back the outbox keys of the dispatched events
+    assert!(
+      context
+        .index
+        .database
+        .begin_read()
+        .unwrap()
+        .open_table(EVENT_OUTBOX)
+        .unwrap()
+        .len()
+        .unwrap()
+        >= u64::try_from(dispatched).unwrap()
+    );
+
+    assert_eq!(context.index.event_outbox_info().unwrap().pending, 0);
+    assert_eq!(context.index.dispatch_pending_events().unwrap(), Some(0));
+
+    // the events of blocks indexed after the reorg are dispatched
+    context.mint_base_token(1, 1);
+
+    let redispatched = context.index.dispatch_pending_events().unwrap().unwrap();
+    assert!(redispatched > 0);
+
+    let mut received = Vec::new();
+    while let Ok(event) = event_receiver.try_recv() {
+      received.push((event.block_height, event.event_index));
+    }
+
+    assert_eq!(received.len(), dispatched + redispatched);
+    assert!(received.windows(2).all(|keys| keys[0] < keys[1]));
+  }
+}
@@ -32,6 +32,25 @@ impl Rtx {
     )
   }
 
+  /// Height and hash of the latest indexed block, read together so they
+  /// always refer to the same block.
+  pub(crate) fn tip(&self) -> Result<Option<(u32, BlockHash)>> {
+    Ok(
+      self
+        .0
+        .open_table(HEIGHT_TO_BLOCK_HASH)?
+        .range(0..)?
+        .next_back()
+        .transpose()?
+        .map(|(height, hash)| {
+          (
+            height.value(),
+            BlockHash::from(sha256d::Hash::from_slice(hash.value()).expect("Invalid block hash")),
+          )
+        }),
+    )
+  }
+
   pub(crate) fn block_hash(&self, height: Option<u32>) -> Result<Option<BlockHash>> {
     let height_to_block_header = self.0.open_table(HEIGHT_TO_BLOCK_HASH)?;
 
@@ -0,0 +1,75 @@
+use super::*;
+
+/// When a ticker was sealed, and when it was enshrined if it already was.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SealingHistory {
+  pub spaced_relic: SpacedRelic,
+  /// the sealing inscription
+  pub inscription: InscriptionId,
+  pub sealed_block: u32,
+  pub sealed_timestamp: u32,
+  pub enshrining: Option<EnshriningHistory>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnshriningHistory {
+  pub id: RelicId,
+  pub txid: Txid,
+  pub block: u64,
+  pub timestamp: u64,
+  /// blocks between the sealing and the enshrining
+  pub blocks_to_enshrine: u64,
+}
+
+/// How many sealed tickers are still waiting to be enshrined, and how long the
+/// enshrined ones waited.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SealingStats {
+  pub pending: u64,
+  pub enshrined: u64,
+  pub average_blocks_to_enshrine: Option<f64>,
+}
+
+/// Whether sealing a ticker and enshrining it with given terms would succeed.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct EnshriningCheck {
+  #[serde(rename = "spaced_bone")]
+  pub spaced_relic: SpacedRelic,
+  /// base tokens the sealing costs
+  pub sealing_fee: u128,
+  /// base tokens held by the given inputs, if any were given
+  pub balance: Option<u128>,
+  pub violations: Vec<EnshriningViolation>,
+}
+
+impl SealingStats {
+  pub(crate) fn new(pending: u64, enshrined: u64, blocks_to_enshrine: u64) -> Self {
+    Self {
+      pending,
+      enshrined,
+      average_blocks_to_enshrine: (enshrined > 0)
+        .then(|| blocks_to_enshrine as f64 / enshrined as f64),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn average_blocks_to_enshrine() {
+    assert_eq!(
+      SealingStats::new(3, 0, 0),
+      SealingStats {
+        pending: 3,
+        enshrined: 0,
+        average_blocks_to_enshrine: None,
+      }
+    );
+    assert_eq!(
+      SealingStats::new(0, 4, 10).average_blocks_to_enshrine,
+      Some(2.5)
+    );
+  }
+}
@@ -0,0 +1,127 @@
+use super::*;
+
+/// Relic names are indexed under each of their prefixes up to this many
+/// letters. Longer queries are looked up by their first letters and filtered.
+pub(crate) const MAX_PREFIX_LENGTH: usize = 8;
+
+/// Bounds the work done for short prefixes, which match many relics.
+const MAX_CANDIDATES: usize = 1000;
+
+/// Adds a newly enshrined relic to the search index.
+pub(crate) fn insert_relic(
+  prefix_to_relic_id: &mut MultimapTable<&'static str, RelicIdValue>,
+  number_to_relic_id: &mut Table<u64, RelicIdValue>,
+  relic: Relic,
+  number: u64,
+  id: RelicId,
+) -> Result {
+  let name = relic.to_string();
+
+  for length in 1..=name.len().min(MAX_PREFIX_LENGTH) {
+    prefix_to_relic_id.insert(&name[..length], id.store())?;
+  }
+
+  number_to_relic_id.insert(number, id.store())?;
+
+  Ok(())
+}
+
+/// Relics whose name starts with `prefix`, shortest names first and then in
+/// the order they were enshrined.
+pub(crate) fn relics_by_prefix(
+  prefix_to_relic_id: &impl ReadableMultimapTable<&'static str, RelicIdValue>,
+  id_to_entry: &impl ReadableTable<RelicIdValue, RelicEntryValue>,
+  prefix: &str,
+  limit: usize,
+) -> Result<Vec<(RelicId, RelicEntry)>> {
+  let mut relics = Vec::new();
+
+  for result in prefix_to_relic_id
+    .get(&prefix[..prefix.len().min(MAX_PREFIX_LENGTH)])?
+    .take(MAX_CANDIDATES)
+  {
+    let id = RelicId::load(result?.value());
+
+    let Some(entry) = id_to_entry.get(&id.store())? else {
+      continue;
+    };
+
+    let entry = RelicEntry::load(entry.value());
+
+    if entry.spaced_relic.relic.to_string().starts_with(prefix) {
+      relics.push((id, entry));
+    }
+  }
+
+  relics.sort_by_key(|(_, entry)| (entry.spaced_relic.relic.length(), entry.number));
+  relics.truncate(limit);
+
+  Ok(relics)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn relics_are_found_by_prefix() {
+    let tempdir = TempDir::new().unwrap();
+    let database = Database::create(tempdir.path().join("index.redb")).unwrap();
+
+    let relics = [("FOOBAR", 0), ("FOO", 1), ("BAR", 2), ("FOOBARBAZQUX", 3)];
+
+    let wtx = database.begin_write().unwrap();
+    {
+      let mut prefix_to_relic_id = wtx.open_multimap_table(RELIC_PREFIX_TO_RELIC_ID).unwrap();
+      let mut number_to_relic_id = wtx.open_table(RELIC_NUMBER_TO_RELIC_ID).unwrap();
+      let mut id_to_entry = wtx.open_table(RELIC_ID_TO_RELIC_ENTRY).unwrap();
+
+      for (name, number) in relics {
+        let relic = name.parse::<Relic>().unwrap();
+        let id = RelicId {
+          block: 1,
+          tx: number,
+        };
+        insert_relic(
+          &mut prefix_to_relic_id,
+          &mut number_to_relic_id,
+          relic,
+          number.into(),
+          id,
+        )
+        .unwrap();
+        id_to_entry
+          .insert(
+            id.store(),
+            RelicEntry {
+              number: number.into(),
+              spaced_relic: SpacedRelic { relic, spacers: 0 },
+              ..default()
+            }
+            .store(),
+          )
+          .unwrap();
+      }
+    }
+    wtx.commit().unwrap();
+
+    let rtx = database.begin_read().unwrap();
+    let prefix_to_relic_id = rtx.open_multimap_table(RELIC_PREFIX_TO_RELIC_ID).unwrap();
+    let id_to_entry = rtx.open_table(RELIC_ID_TO_RELIC_ENTRY).unwrap();
+
+    let names = |prefix, limit| {
+      relics_by_prefix(&prefix_to_relic_id, &id_to_entry, prefix, limit)
+        .unwrap()
+        .into_iter()
+        .map(|(_, entry)| entry.spaced_relic.relic.to_string())
+        .collect::<Vec<String>>()
+    };
+
+    assert_eq!(names("FOO", 10), ["FOO", "FOOBAR", "FOOBARBAZQUX"]);
+    assert_eq!(names("FOO", 1), ["FOO"]);
+    assert_eq!(names("FOOBARBAZ", 10), ["FOOBARBAZQUX"]);
+    assert_eq!(names("FOOBARBAR", 10), Vec::<String>::new());
+    assert_eq!(names("B", 10), ["BAR"]);
+    assert_eq!(names("X", 10), Vec::<String>::new());
+  }
+}
@@ -0,0 +1,42 @@
+use {
+  super::*,
+  crate::index::{
+    chest_entry::ChestEntry, relics_entry::RelicOwner, subsidy_schedule_entry::SubsidyScheduleEntry,
+  },
+};
+
+/// Complete relic state of an index at a given height.
+///
+/// All collections are ordered by their table keys, so two indexes at the
+/// same height produce byte-identical snapshots.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelicSnapshot {
+  pub height: u32,
+  pub block_hash: BlockHash,
+  pub relics: Vec<(RelicId, RelicEntry)>,
+  pub thumbnails: Vec<(RelicId, InscriptionId)>,
+  pub sealings: Vec<(u32, SpacedRelic)>,
+  pub inscriptions: Vec<SnapshotInscription>,
+  pub balances: Vec<(OutPoint, Vec<(RelicId, u128)>)>,
+  pub claimable: Vec<(RelicOwner, u128)>,
+  pub liquidity_shares: Vec<(RelicId, RelicOwner, u128)>,
+  pub syndicates: Vec<(SyndicateId, SyndicateEntry)>,
+  pub chests: Vec<(u32, ChestEntry)>,
+  #[serde(default)]
+  pub subsidy_schedules: Vec<(RelicId, SubsidyScheduleEntry)>,
+}
+
+/// An inscription referenced by the relic state, e.g. a sealing, relic owner,
+/// syndicate or chest. The most recent inscription is always included, so an
+/// index seeded from the snapshot continues with the same numbering.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotInscription {
+  pub sequence_number: u32,
+  pub id: InscriptionId,
+  pub number: u64,
+  pub charms: u32,
+  pub fee: u64,
+  pub height: u32,
+  pub timestamp: u32,
+  pub satpoint: SatPoint,
+}
@@ -0,0 +1,76 @@
+use super::*;
+
+/// Release schedule of a Relic subsidy, configured by the Relic owner.
+#[derive(Debug, Default, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct SubsidyScheduleEntry {
+  /// block in which the schedule was configured
+  pub start: u64,
+  /// length of an epoch in blocks
+  pub epoch: u64,
+  /// amount of the subsidy released at the end of every epoch
+  pub amount: u128,
+  /// total amount of the subsidy released so far, including any amount paid out before the
+  /// schedule was configured
+  pub released: u128,
+}
+
+impl SubsidyScheduleEntry {
+  /// whether an epoch of the schedule ends in the given block
+  pub fn releases_at(&self, height: u64) -> bool {
+    height > self.start && (height - self.start) % self.epoch == 0
+  }
+
+  /// first block after the given one in which an epoch ends
+  pub fn next_release_height(&self, height: u64) -> u64 {
+    self.start + self.epoch * (height.saturating_sub(self.start) / self.epoch + 1)
+  }
+}
+
+pub type SubsidyScheduleEntryValue = (
+  u64,  // start
+  u64,  // epoch
+  u128, // amount
+  u128, // released
+);
+
+impl Entry for SubsidyScheduleEntry {
+  type Value = SubsidyScheduleEntryValue;
+
+  fn load((start, epoch, amount, released): Self::Value) -> Self {
+    Self {
+      start,
+      epoch,
+      amount,
+      released,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (self.start, self.epoch, self.amount, self.released)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn releases_at_the_end_of_every_epoch() {
+    let entry = SubsidyScheduleEntry {
+      start: 100,
+      epoch: 10,
+      amount: 5,
+      released: 0,
+    };
+
+    assert!(!entry.releases_at(100));
+    assert!(!entry.releases_at(105));
+    assert!(entry.releases_at(110));
+    assert!(entry.releases_at(120));
+
+    assert_eq!(entry.next_release_height(90), 110);
+    assert_eq!(entry.next_release_height(100), 110);
+    assert_eq!(entry.next_release_height(109), 110);
+    assert_eq!(entry.next_release_height(110), 120);
+  }
+}
@@ -21,6 +21,10 @@ pub struct SyndicateEntry {
   pub royalty: u128,
   /// if this is set, only owner of the Syndicate inscription can chest
   pub gated: bool,
+  /// if this is set, only holders of an allowlist inscription can chest
+  pub allowlist: bool,
+  /// if this is set, only holders of at least this amount of the given Relic can chest
+  pub holding: Option<(RelicId, u128)>,
   /// how many blocks the relics should be locked in the chest, no withdrawal possible before
   pub lock: Option<u64>,
   /// rewards that are paid by holding Chests, denominated in Relics per Chest per block
@@ -36,6 +40,8 @@ impl SyndicateEntry {
     let Summoning {
       treasure,
       gated,
+      allowlist,
+      holding,
       cap,
       lock,
       height,
@@ -50,6 +56,8 @@ impl SyndicateEntry {
       sequence_number,
       treasure: treasure.unwrap_or(RELIC_ID),
       gated,
+      allowlist,
+      holding,
       cap,
       lock,
       height,
@@ -84,18 +92,18 @@ impl SyndicateEntry {
 }
 
 pub type SyndicateEntryValue = (
-  (u128, u128),               // summoning
-  u32,                        // sequence_number
-  RelicIdValue,               // treasure
-  Option<u32>,                // cap
-  Option<u64>,                // lock
-  (Option<u64>, Option<u64>), // height
-  u128,                       // quota
-  u128,                       // royalty
-  Option<u128>,               // subsidy
-  bool,                       // gated
-  bool,                       // turbo
-  u32,                        // chests
+  (u128, u128),                               // summoning
+  u32,                                        // sequence_number
+  RelicIdValue,                               // treasure
+  Option<u32>,                                // cap
+  Option<u64>,                                // lock
+  (Option<u64>, Option<u64>),                 // height
+  u128,                                       // quota
+  u128,                                       // royalty
+  Option<u128>,                               // subsidy
+  (bool, bool, Option<(RelicIdValue, u128)>), // gated, allowlist, holding
+  bool,                                       // turbo
+  u32,                                        // chests
 );
 
 impl Entry for SyndicateEntry {
@@ -112,7 +120,7 @@ impl Entry for SyndicateEntry {
       quota,
       royalty,
       reward,
-      gated,
+      (gated, allowlist, holding),
       turbo,
       chests,
     ): Self::Value,
@@ -133,6 +141,8 @@ impl Entry for SyndicateEntry {
       royalty,
       reward,
       gated,
+      allowlist,
+      holding: holding.map(|(id, amount)| (RelicId::load(id), amount)),
       turbo,
       chests,
     }
@@ -169,7 +179,11 @@ impl Entry for SyndicateEntry {
       self.quota,
       self.royalty,
       self.reward,
-      self.gated,
+      (
+        self.gated,
+        self.allowlist,
+        self.holding.map(|(id, amount)| (id.store(), amount)),
+      ),
       self.turbo,
       self.chests,
     )
@@ -116,6 +116,11 @@ impl Context {
     blocks
   }
 
+  /// Sets the clock used for index bookkeeping. Requires `--deterministic`.
+  pub(crate) fn set_time(&self, millis: u64) {
+    self.index.set_clock(millis);
+  }
+
   #[cfg(test)]
   pub(crate) fn mine_blocks_with_subsidy(&self, n: u64, subsidy: u64) -> Vec<Block> {
     let blocks = self.core.mine_blocks_with_subsidy(n, subsidy);
@@ -123,6 +128,28 @@ impl Context {
     blocks
   }
 
+  /// Replays the blocks of `fixture` on top of the genesis block and indexes
+  /// them.
+  #[cfg(test)]
+  pub(crate) fn replay_fixture(&self, fixture: &RelicFixture) {
+    assert_eq!(fixture.chain, self.options.chain());
+    assert_eq!(
+      self.core.height(),
+      0,
+      "fixtures are replayed on an empty chain"
+    );
+
+    for tx in fixture.spent().unwrap() {
+      self.core.insert_transaction(tx);
+    }
+
+    for block in fixture.blocks().unwrap() {
+      self.core.push_block(block);
+    }
+
+    self.index.update().unwrap();
+  }
+
   pub(crate) fn base_token_entry() -> RelicEntry {
     RelicEntry {
       block: 1,
@@ -142,6 +169,7 @@ impl Context {
         price: None,
         seed: None,
         swap_height: None,
+        fee: None,
       }),
       state: RelicState {
         subsidy_locked: true,
@@ -309,13 +337,31 @@ impl Context {
   pub(crate) fn enshrine(&self, relic: SpacedRelic, enshrining: Enshrining) -> (Txid, RelicId) {
     let block_count = usize::try_from(self.index.block_count().unwrap()).unwrap();
 
+    let txid = self.seal(relic, Some(enshrining));
+
+    (
+      txid,
+      RelicId {
+        block: u64::try_from(block_count + usize::from(Keepsake::COMMIT_CONFIRMATIONS) + 1)
+          .unwrap(),
+        tx: 1,
+      },
+    )
+  }
+
+  /// Seals `relic` to an inscription revealed on output 0 of the returned
+  /// transaction, enshrining it in the same transaction if `enshrining` is set.
+  #[cfg(test)]
+  pub(crate) fn seal(&self, relic: SpacedRelic, enshrining: Option<Enshrining>) -> Txid {
+    let block_count = usize::try_from(self.index.block_count().unwrap()).unwrap();
+
     // TODO: remove, this is only here to not change all the block number in test fixtures
     self.mine_blocks(1);
     self.mine_blocks(Keepsake::COMMIT_CONFIRMATIONS.into());
 
     let keepsake = Keepsake {
       sealing: true,
-      enshrining: Some(enshrining),
+      enshrining,
       // put any Relics into output number 2 to separate it from the Inscription
       pointer: Some(1),
       ..default()
@@ -343,14 +389,7 @@ impl Context {
 
     self.mine_blocks(1);
 
-    (
-      txid,
-      RelicId {
-        block: u64::try_from(block_count + usize::from(Keepsake::COMMIT_CONFIRMATIONS) + 1)
-          .unwrap(),
-        tx: 1,
-      },
-    )
+    txid
   }
 
   #[cfg(test)]
@@ -470,6 +509,8 @@ impl Context {
     receiver: &mut tokio::sync::mpsc::Receiver<Event>,
     expected: Vec<Event>,
   ) {
+    while self.index.dispatch_pending_events().unwrap() > Some(0) {}
+
     let actual: Vec<Event> = expected
       .iter()
       .map(|_| receiver.try_recv())
@@ -501,11 +542,11 @@ impl Context {
       debug_assert_eq!(events, self.index.events_for_tx(txid).unwrap());
     }
     for (relic_id, events) in relic_events {
-      let mut actual = self.index.events_for_relic(relic_id, 100, 0).unwrap();
-      if let Some(events) = actual.as_mut() {
-        // the API returns items from new to old, hence reverse the order here
-        events.reverse();
-      }
+      let actual = self
+        .index
+        .events_for_relic(relic_id, 100, 0, true)
+        .unwrap()
+        .map(|(events, _total)| events);
       debug_assert_eq!(Some(events), actual);
     }
   }
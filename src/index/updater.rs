@@ -1,8 +1,10 @@
 use {
   self::inscription_updater::InscriptionUpdater,
-  super::{fetcher::Fetcher, *},
+  super::{checkpoint::RelicStateHasher, fetcher::Fetcher, *},
   crate::{
     index::{event::EventEmitter, updater::relics_updater::RelicUpdater},
+    inscription::ParsedInscription,
+    relics::{Keepsake, RelicArtifact},
     sat::Sat,
     sat_point::SatPoint,
   },
@@ -18,6 +20,49 @@ mod relics_updater;
 pub(crate) struct BlockData {
   pub(crate) header: BlockHeader,
   pub(crate) txdata: Vec<(Transaction, Txid)>,
+  /// keepsakes deciphered by the fetch threads, one per transaction, if the
+  /// block is at or above the first relic height
+  pub(crate) keepsakes: Option<Vec<Option<RelicArtifact>>>,
+  /// inscriptions parsed by the fetch threads from the envelope of each
+  /// transaction on its own, if the block is at or above the first inscription
+  /// height. Transactions without an envelope are left out.
+  pub(crate) inscriptions: Option<HashMap<Txid, ParsedInscription>>,
+}
+
+impl BlockData {
+  /// Hashes transactions and, with `decipher_keepsakes` and
+  /// `parse_inscriptions`, decodes their keepsakes and inscription envelopes.
+  /// Runs on the fetch threads, off the serial indexing path.
+  fn parse(block: Block, decipher_keepsakes: bool, parse_inscriptions: bool) -> Self {
+    let mut data = Self::from(block);
+
+    if parse_inscriptions {
+      data.inscriptions = Some(
+        data
+          .txdata
+          .iter()
+          .filter_map(
+            |(tx, txid)| match Inscription::from_transactions(vec![tx.clone()]) {
+              ParsedInscription::None => None,
+              parsed => Some((*txid, parsed)),
+            },
+          )
+          .collect(),
+      );
+    }
+
+    if decipher_keepsakes {
+      data.keepsakes = Some(
+        data
+          .txdata
+          .iter()
+          .map(|(tx, _)| Keepsake::decipher(tx))
+          .collect(),
+      );
+    }
+
+    data
+  }
 }
 
 impl From<Block> for BlockData {
@@ -32,10 +77,14 @@ impl From<Block> for BlockData {
           (transaction, txid)
         })
         .collect(),
+      keepsakes: None,
+      inscriptions: None,
     }
   }
 }
 
+type BlockJob = (u32, mpsc::SyncSender<Result<Option<BlockData>>>);
+
 #[derive(Clone)]
 pub(crate) struct Updater<'index> {
   range_cache: HashMap<OutPointValue, Vec<u8>>,
@@ -45,6 +94,10 @@ pub(crate) struct Updater<'index> {
   outputs_cached: u64,
   outputs_inserted_since_flush: u64,
   outputs_traversed: u64,
+  /// heights and headers of indexed but not yet committed blocks
+  uncommitted_blocks: Vec<(u32, BlockHeader)>,
+  /// events of indexed but not yet committed blocks
+  uncommitted_events: Vec<Event>,
 }
 
 impl<'index> Updater<'_> {
@@ -57,6 +110,8 @@ impl<'index> Updater<'_> {
       outputs_cached: 0,
       outputs_inserted_since_flush: 0,
       outputs_traversed: 0,
+      uncommitted_blocks: Vec::new(),
+      uncommitted_events: Vec::new(),
     })
   }
 
@@ -66,13 +121,7 @@ impl<'index> Updater<'_> {
 
     wtx
       .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
-      .insert(
-        &self.height,
-        &SystemTime::now()
-          .duration_since(SystemTime::UNIX_EPOCH)
-          .map(|duration| duration.as_millis())
-          .unwrap_or(0),
-      )?;
+      .insert(&self.height, &self.index.now_millis())?;
 
     let mut progress_bar = if cfg!(test)
       || log_enabled!(log::Level::Info)
@@ -94,6 +143,8 @@ impl<'index> Updater<'_> {
     let (mut outpoint_sender, mut value_receiver) = Self::spawn_fetcher(self.index)?;
 
     let mut uncommitted = 0;
+    // whether a batch of this update was committed with the `Updating` marker
+    let mut unclean = false;
     let mut value_cache = HashMap::new();
     while let Ok(block) = rx.recv() {
       self.index_block(
@@ -119,8 +170,9 @@ impl<'index> Updater<'_> {
 
       uncommitted += 1;
 
-      if uncommitted == 1000 {
-        self.commit(wtx, value_cache)?;
+      if uncommitted == self.index.commit_interval {
+        self.commit(wtx, value_cache, true)?;
+        unclean = true;
         value_cache = HashMap::new();
         uncommitted = 0;
         wtx = self.index.begin_write()?;
@@ -133,18 +185,13 @@ impl<'index> Updater<'_> {
           .unwrap_or(0);
         if height != self.height {
           // another update has run between committing and beginning the new
-          // write transaction
+          // write transaction, and clears the marker when it finishes
+          unclean = false;
           break;
         }
         wtx
           .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
-          .insert(
-            &self.height,
-            &SystemTime::now()
-              .duration_since(SystemTime::UNIX_EPOCH)
-              .map(|duration| duration.as_millis())
-              .unwrap_or(0),
-          )?;
+          .insert(&self.height, &self.index.now_millis())?;
       }
 
       if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
@@ -152,8 +199,8 @@ impl<'index> Updater<'_> {
       }
     }
 
-    if uncommitted > 0 {
-      self.commit(wtx, value_cache)?;
+    if uncommitted > 0 || unclean {
+      self.commit(wtx, value_cache, false)?;
     }
 
     if let Some(progress_bar) = &mut progress_bar {
@@ -163,6 +210,9 @@ impl<'index> Updater<'_> {
     Ok(())
   }
 
+  /// Fetches blocks starting at `height` on `index_threads` worker threads,
+  /// which also pre-parse them, and delivers them in height order. At most
+  /// two blocks per thread are in flight ahead of the indexer.
   fn fetch_blocks_from(
     index: &Index,
     mut height: u32,
@@ -172,30 +222,86 @@ impl<'index> Updater<'_> {
 
     let height_limit = index.height_limit;
 
-    let client =
-      Client::new(&index.rpc_url, index.auth.clone()).context("failed to connect to RPC URL")?;
-
     let first_inscription_height = index.first_inscription_height;
 
-    thread::spawn(move || loop {
-      if let Some(height_limit) = height_limit {
-        if height >= height_limit {
+    let first_keepsake_height = index.index_relics.then_some(index.first_relic_height);
+
+    let threads = index.index_threads;
+
+    let (job_sender, job_receiver) = mpsc::sync_channel::<BlockJob>(threads);
+
+    let job_receiver = Arc::new(Mutex::new(job_receiver));
+
+    for _ in 0..threads {
+      let client =
+        Client::new(&index.rpc_url, index.auth.clone()).context("failed to connect to RPC URL")?;
+
+      let job_receiver = job_receiver.clone();
+
+      thread::spawn(move || loop {
+        let job = job_receiver.lock().unwrap().recv();
+
+        let Ok((height, result_sender)) = job else {
           break;
-        }
-      }
+        };
 
-      match Self::get_block_with_retries(&client, height, index_sats, first_inscription_height) {
-        Ok(Some(block)) => {
-          if let Err(err) = tx.send(block.into()) {
-            log::info!("Block receiver disconnected: {err}");
-            break;
+        let result =
+          Self::get_block_with_retries(&client, height, index_sats, first_inscription_height).map(
+            |block| {
+              block.map(|block| {
+                BlockData::parse(
+                  block,
+                  first_keepsake_height.map_or(false, |first| height >= first),
+                  height >= first_inscription_height,
+                )
+              })
+            },
+          );
+
+        // the receiver is gone if delivery already stopped at a lower height
+        result_sender.send(result).ok();
+      });
+    }
+
+    thread::spawn(move || {
+      let mut in_flight = VecDeque::new();
+      let mut next = height;
+
+      loop {
+        while in_flight.len() < threads * 2
+          && height_limit.map_or(true, |height_limit| next < height_limit)
+        {
+          let (result_sender, result_receiver) = mpsc::sync_channel(1);
+
+          if job_sender.send((next, result_sender)).is_err() {
+            return;
           }
-          height += 1;
+
+          in_flight.push_back(result_receiver);
+          next += 1;
         }
-        Ok(None) => break,
-        Err(err) => {
-          log::error!("failed to fetch block {height}: {err}");
+
+        let Some(result_receiver) = in_flight.pop_front() else {
           break;
+        };
+
+        match result_receiver.recv() {
+          Ok(Ok(Some(block))) => {
+            if let Err(err) = tx.send(block) {
+              log::info!("Block receiver disconnected: {err}");
+              break;
+            }
+            height += 1;
+          }
+          Ok(Ok(None)) => break,
+          Ok(Err(err)) => {
+            log::error!("failed to fetch block {height}: {err}");
+            break;
+          }
+          Err(err) => {
+            log::error!("block fetch thread for block {height} exited: {err}");
+            break;
+          }
         }
       }
     });
@@ -324,9 +430,11 @@ impl<'index> Updater<'_> {
     outpoint_sender: &mut Sender<OutPoint>,
     value_receiver: &mut Receiver<u64>,
     wtx: &mut WriteTransaction,
-    block: BlockData,
+    mut block: BlockData,
     value_cache: &mut HashMap<OutPoint, OutPointMapValue>,
   ) -> Result<()> {
+    let _span = telemetry::Span::enter("index_block").with_height(self.height);
+
     Reorg::detect_reorg(&block, self.height, self.index)?;
 
     let start = Instant::now();
@@ -347,7 +455,10 @@ impl<'index> Updater<'_> {
     };
 
     let mut outpoint_to_value = wtx.open_table(OUTPOINT_TO_VALUE)?;
-    let mut address_to_outpoint = wtx.open_multimap_table(ADDRESS_TO_OUTPOINT)?;
+    let mut script_hash_to_outpoint = wtx.open_multimap_table(SCRIPT_HASH_TO_OUTPOINT)?;
+    let mut address_to_sequence_number = wtx.open_multimap_table(ADDRESS_TO_SEQUENCE_NUMBER)?;
+    let mut address_to_inscription_count = wtx.open_table(ADDRESS_TO_INSCRIPTION_COUNT)?;
+    let mut sequence_number_to_address = wtx.open_table(SEQUENCE_NUMBER_TO_ADDRESS)?;
 
     let index_inscriptions = self.height >= index.first_inscription_height;
 
@@ -389,14 +500,46 @@ impl<'index> Updater<'_> {
       wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
     let mut transaction_id_to_events = wtx.open_multimap_table(TRANSACTION_ID_TO_EVENTS)?;
     let mut relic_id_to_events = wtx.open_multimap_table(RELIC_ID_TO_EVENTS)?;
+    let mut address_to_events = wtx.open_multimap_table(ADDRESS_TO_EVENTS)?;
+    let mut event_key_to_event = wtx.open_table(EVENT_KEY_TO_EVENT)?;
+    let mut event_kind_to_event_key = wtx.open_table(EVENT_KIND_TO_EVENT_KEY)?;
+    let mut relic_id_to_event_key = wtx.open_table(RELIC_ID_TO_EVENT_KEY)?;
+    let mut relic_id_to_burn_event_key = wtx.open_table(RELIC_ID_TO_BURN_EVENT_KEY)?;
+    let mut inscription_id_to_chest_event_key =
+      wtx.open_table(INSCRIPTION_ID_TO_CHEST_EVENT_KEY)?;
     let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
 
+    let mut event_outbox = wtx.open_table(EVENT_OUTBOX)?;
+
+    // the dispatcher only records how far it got, so that it never waits for
+    // the updater, and the keys of dispatched events are removed here
+    for key in event_outbox
+      .range(..self.index.next_dispatched_event_key()?)?
+      .map(|result| result.map(|(key, _)| key.value()))
+      .collect::<Result<Vec<(u32, u32)>, StorageError>>()?
+    {
+      event_outbox.remove(key)?;
+    }
+
+    let mut events = Vec::new();
+
     let mut emitter = EventEmitter {
       block_height: self.height,
       event_index: 0,
-      event_sender: self.index.event_sender.as_ref(),
+      emitted: (!self.index.event_hooks.is_empty()).then_some(&mut events),
+      outbox: self
+        .index
+        .event_sender
+        .is_some()
+        .then_some(&mut event_outbox),
+      address_to_events: &mut address_to_events,
       relic_id_to_events: &mut relic_id_to_events,
       transaction_id_to_events: &mut transaction_id_to_events,
+      event_key_to_event: &mut event_key_to_event,
+      event_kind_to_event_key: &mut event_kind_to_event_key,
+      relic_id_to_event_key: &mut relic_id_to_event_key,
+      relic_id_to_burn_event_key: &mut relic_id_to_burn_event_key,
+      inscription_id_to_chest_event_key: &mut inscription_id_to_chest_event_key,
     };
 
     let mut height_to_block_hash = wtx.open_table(HEIGHT_TO_BLOCK_HASH)?;
@@ -404,6 +547,8 @@ impl<'index> Updater<'_> {
     let mut inscription_id_to_satpoint = wtx.open_table(INSCRIPTION_ID_TO_SATPOINT)?;
     let mut inscription_id_to_txids = wtx.open_table(INSCRIPTION_ID_TO_TXIDS)?;
     let mut inscription_txid_to_tx = wtx.open_table(INSCRIPTION_TXID_TO_TX)?;
+    let mut inscription_id_to_pruned_inscription =
+      wtx.open_table(INSCRIPTION_ID_TO_PRUNED_INSCRIPTION)?;
     let mut partial_txid_to_inscription_txids =
       wtx.open_table(PARTIAL_TXID_TO_INSCRIPTION_TXIDS)?;
     let mut satpoint_to_inscription_id = wtx.open_table(SATPOINT_TO_INSCRIPTION_ID)?;
@@ -421,6 +566,7 @@ impl<'index> Updater<'_> {
     let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
 
     let mut sequence_number_to_spaced_relic = wtx.open_table(SEQUENCE_NUMBER_TO_SPACED_RELIC)?;
+    let mut sequence_number_to_owner_change = wtx.open_table(SEQUENCE_NUMBER_TO_OWNER_CHANGE)?;
     let mut sequence_number_to_bonestone_block_height =
       wtx.open_table(SEQUENCE_NUMBER_TO_BONESTONE_BLOCK_HEIGHT)?;
 
@@ -430,15 +576,19 @@ impl<'index> Updater<'_> {
       .unwrap_or(0);
 
     {
+      let sequence_number_to_chest = wtx.open_table(SEQUENCE_NUMBER_TO_CHEST)?;
       let home_inscription_count = home_inscriptions.len()?;
       let mut inscription_updater = InscriptionUpdater::new(
         self.height,
         &mut inscription_id_to_satpoint,
         &mut inscription_id_to_txids,
         &mut inscription_txid_to_tx,
+        &mut inscription_id_to_pruned_inscription,
         &mut partial_txid_to_inscription_txids,
         value_receiver,
         self.index.index_transactions,
+        self.index.index_raw_transactions,
+        self.index.prune_content,
         Vec::new(),
         &mut sequence_number_to_inscription_entry,
         &mut inscription_number_to_sequence_number,
@@ -452,15 +602,22 @@ impl<'index> Updater<'_> {
         &mut sequence_number_to_satpoint,
         // not really clean to have this as a dependency but ok for now
         &mut sequence_number_to_spaced_relic,
+        &sequence_number_to_chest,
+        &mut sequence_number_to_owner_change,
         &mut transaction_id_to_transaction,
         lost_sats,
         &mut outpoint_to_value,
-        &mut address_to_outpoint,
+        &mut script_hash_to_outpoint,
+        &mut address_to_sequence_number,
+        &mut address_to_inscription_count,
+        &mut sequence_number_to_address,
         &mut satpoint_to_inscription_id,
         block.header.time,
         value_cache,
         index.chain,
+        index.bones_config.bonestones_start_height(index.chain),
         &mut emitter,
+        block.inscriptions.take(),
       )?;
 
       if index_inscriptions {
@@ -572,13 +729,34 @@ impl<'index> Updater<'_> {
       let mut relic_id_to_relic_entry = wtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
       let mut syndicate_id_to_syndicate_entry = wtx.open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)?;
       let mut relic_to_relic_id = wtx.open_table(RELIC_TO_RELIC_ID)?;
+      let mut relic_prefix_to_relic_id = wtx.open_multimap_table(RELIC_PREFIX_TO_RELIC_ID)?;
+      let mut relic_number_to_relic_id = wtx.open_table(RELIC_NUMBER_TO_RELIC_ID)?;
+      let mut relic_id_to_thumbnail = wtx.open_table(RELIC_ID_TO_THUMBNAIL)?;
+      let mut relic_id_to_supply = wtx.open_table(RELIC_ID_TO_SUPPLY)?;
+      let mut relic_id_to_pool_price = wtx.open_table(RELIC_ID_TO_POOL_PRICE)?;
+      let mut relic_to_fees = wtx.open_table(RELIC_TO_FEES)?;
+      let mut height_to_fees = wtx.open_table(HEIGHT_TO_FEES)?;
+      let mut height_to_relic_activity = wtx.open_table(HEIGHT_TO_RELIC_ACTIVITY)?;
+      let mut height_to_relic_error_counts = wtx.open_table(HEIGHT_TO_RELIC_ERROR_COUNTS)?;
       let mut relic_owner_to_claimable = wtx.open_table(RELIC_OWNER_TO_CLAIMABLE)?;
+      let mut relic_balance_journal = wtx.open_table(RELIC_BALANCE_JOURNAL)?;
+      let mut address_to_journaled_relic_id =
+        wtx.open_multimap_table(ADDRESS_TO_JOURNALED_RELIC_ID)?;
+      let mut liquidity_provider_to_shares = wtx.open_table(LIQUIDITY_PROVIDER_TO_SHARES)?;
+      let mut relic_owner_to_fees_paid = wtx.open_table(RELIC_OWNER_TO_FEES_PAID)?;
+      let mut relic_id_to_subsidy_schedule = wtx.open_table(RELIC_ID_TO_SUBSIDY_SCHEDULE)?;
+      let mut subsidy_release_height_to_relic_id =
+        wtx.open_table(SUBSIDY_RELEASE_HEIGHT_TO_RELIC_ID)?;
       let mut transaction_id_to_relic = wtx.open_table(TRANSACTION_ID_TO_RELIC)?;
       let mut sequence_number_to_syndicate_id = wtx.open_table(SEQUENCE_NUMBER_TO_SYNDICATE_ID)?;
       let mut sequence_number_to_chest = wtx.open_table(SEQUENCE_NUMBER_TO_CHEST)?;
       let mut syndicate_to_chest_sequence_number =
         wtx.open_multimap_table(SYNDICATE_TO_CHEST_SEQUENCE_NUMBER)?;
+      let mut chest_unlock_height_to_sequence_number =
+        wtx.open_table(CHEST_UNLOCK_HEIGHT_TO_SEQUENCE_NUMBER)?;
       let mut relic_to_sequence_number = wtx.open_table(RELIC_TO_SEQUENCE_NUMBER)?;
+      let mut pending_sealing_sequence_numbers =
+        wtx.open_table(PENDING_SEALING_SEQUENCE_NUMBERS)?;
 
       let relics = statistic_to_count
         .get(&Statistic::Relics.into())?
@@ -587,44 +765,141 @@ impl<'index> Updater<'_> {
 
       let mut relic_updater = RelicUpdater {
         block_time: block.header.time,
+        activity: HashMap::new(),
         burned: HashMap::new(),
+        balance_changes: self.index.index_relic_history.then(HashMap::new),
+        // with `--index-transactions` the inscription updater stores all of them
+        raw_transactions: (self.index.index_raw_transactions && !self.index.index_transactions)
+          .then_some(&mut transaction_id_to_transaction),
         claimable: HashMap::new(),
+        errors: HashMap::new(),
+        fees: HashMap::new(),
         unsafe_txids: HashSet::new(),
+        updated: HashSet::new(),
         index: &self.index,
         height: self.height,
         first_relic_syndicate_height: self.index.first_relic_syndicate_height,
         id_to_entry: &mut relic_id_to_relic_entry,
         id_to_syndicate: &mut syndicate_id_to_syndicate_entry,
         inscription_id_to_sequence_number: &inscription_id_to_sequence_number,
+        liquidity_provider_to_shares: &mut liquidity_provider_to_shares,
         outpoint_to_balances: &mut outpoint_to_relic_balances,
         relic_owner_to_claimable: &mut relic_owner_to_claimable,
+        relic_balance_journal: &mut relic_balance_journal,
+        address_to_journaled_relic_id: &mut address_to_journaled_relic_id,
+        relic_owner_to_fees_paid: &mut relic_owner_to_fees_paid,
         relic_to_id: &mut relic_to_relic_id,
+        relic_prefix_to_id: &mut relic_prefix_to_relic_id,
+        relic_number_to_id: &mut relic_number_to_relic_id,
+        relic_id_to_thumbnail: &mut relic_id_to_thumbnail,
+        relic_id_to_supply: &mut relic_id_to_supply,
+        relic_id_to_pool_price: &mut relic_id_to_pool_price,
+        relic_id_to_subsidy_schedule: &mut relic_id_to_subsidy_schedule,
+        subsidy_release_height_to_relic_id: &mut subsidy_release_height_to_relic_id,
+        relic_to_fees: &mut relic_to_fees,
+        height_to_fees: &mut height_to_fees,
+        height_to_relic_activity: &mut height_to_relic_activity,
+        height_to_error_counts: &mut height_to_relic_error_counts,
         relics,
         statistic_to_count: &mut statistic_to_count,
         transaction_id_to_relic: &mut transaction_id_to_relic,
         satpoint_to_sequence_number: &satpoint_to_sequence_number,
-        sequence_number_to_inscription_entry: &sequence_number_to_inscription_entry,
+        sequence_number_to_inscription_entry: &mut sequence_number_to_inscription_entry,
         sequence_number_to_satpoint: &sequence_number_to_satpoint,
         sequence_number_to_spaced_relic: &mut sequence_number_to_spaced_relic,
+        sequence_number_to_owner_change: &mut sequence_number_to_owner_change,
         sequence_number_to_syndicate: &mut sequence_number_to_syndicate_id,
         sequence_number_to_chest: &mut sequence_number_to_chest,
         syndicate_to_chest_sequence_number: &mut syndicate_to_chest_sequence_number,
+        chest_unlock_height_to_sequence_number: &mut chest_unlock_height_to_sequence_number,
         relic_to_sequence_number: &mut relic_to_sequence_number,
+        pending_sealings: &mut pending_sealing_sequence_numbers,
         event_emitter: &mut emitter,
         inscription_id_to_txids: &mut inscription_id_to_txids,
         inscription_txid_to_tx: &mut inscription_txid_to_tx,
+        inscription_id_to_pruned_inscription: &mut inscription_id_to_pruned_inscription,
         sequence_number_to_bonestone_block_height: &mut sequence_number_to_bonestone_block_height,
       };
 
-      for (i, (tx, txid)) in block.txdata.iter().enumerate() {
-        relic_updater.index_relics(u32::try_from(i).unwrap(), tx, *txid)?;
+      let keepsakes = block.keepsakes.take().unwrap_or_else(|| {
+        block
+          .txdata
+          .iter()
+          .map(|(tx, _)| Keepsake::decipher(tx))
+          .collect()
+      });
+
+      for (i, ((tx, txid), artifact)) in block.txdata.iter().zip(keepsakes).enumerate() {
+        relic_updater.index_relics(u32::try_from(i).unwrap(), tx, *txid, artifact)?;
       }
 
       relic_updater.update()?;
+
+      // hashing the whole relic state is expensive, so only hash at checkpoint
+      // heights, including those that are verified against trusted checkpoints
+      if self.index.index_relic_checkpoints
+        && (self.height % self.index.relic_checkpoint_interval == 0
+          || self
+            .index
+            .trusted_relic_checkpoints
+            .contains_key(&self.height))
+      {
+        let checkpoint = RelicStateHasher::default()
+          .table(&relic_id_to_relic_entry)?
+          .table(&sequence_number_to_spaced_relic)?
+          .table(&outpoint_to_relic_balances)?
+          .table(&relic_owner_to_claimable)?
+          .table(&liquidity_provider_to_shares)?
+          .table(&syndicate_id_to_syndicate_entry)?
+          .table(&sequence_number_to_chest)?
+          .table(&relic_id_to_subsidy_schedule)?
+          .finish();
+
+        if let Some(trusted) = self.index.trusted_relic_checkpoints.get(&self.height) {
+          if *trusted != checkpoint {
+            bail!(
+              "relic state checkpoint mismatch at height {}: computed {checkpoint}, trusted {trusted}",
+              self.height
+            );
+          }
+        }
+
+        wtx
+          .open_table(HEIGHT_TO_RELIC_CHECKPOINT)?
+          .insert(&self.height, &checkpoint.into_inner())?;
+      }
+    }
+
+    if self.index.index_txouts {
+      let mut outpoint_to_txout = wtx.open_table(OUTPOINT_TO_TXOUT)?;
+
+      // only unspent outputs are kept, so the table doesn't grow with the chain
+      for (tx, txid) in &block.txdata {
+        if !tx.is_coin_base() {
+          for input in &tx.input {
+            outpoint_to_txout.remove(&input.previous_output.store())?;
+          }
+        }
+
+        for (vout, output) in tx.output.iter().enumerate() {
+          let outpoint = OutPoint {
+            txid: *txid,
+            vout: vout.try_into().unwrap(),
+          };
+
+          outpoint_to_txout.insert(
+            &outpoint.store(),
+            consensus::encode::serialize(output).as_slice(),
+          )?;
+        }
+      }
     }
 
     height_to_block_hash.insert(&self.height, &block.header.block_hash().store())?;
 
+    self.uncommitted_blocks.push((self.height, block.header));
+    self.uncommitted_events.append(&mut events);
+
     self.height += 1;
     self.outputs_traversed += outputs_in_block;
 
@@ -702,11 +977,16 @@ impl<'index> Updater<'_> {
     Ok(())
   }
 
+  /// Commit `wtx`, with `updating` set if more batches of the same update
+  /// follow.
   fn commit(
     &mut self,
     wtx: WriteTransaction,
     value_cache: HashMap<OutPoint, OutPointMapValue>,
+    updating: bool,
   ) -> Result {
+    let _span = telemetry::Span::enter("commit").with_height(self.height);
+
     log::info!(
       "Committing at block height {}, {} outputs traversed, {} in map, {} cached",
       self.height,
@@ -725,7 +1005,14 @@ impl<'index> Updater<'_> {
 
       let mut outpoint_to_sat_ranges = wtx.open_table(OUTPOINT_TO_SAT_RANGES)?;
 
-      for (outpoint, sat_range) in self.range_cache.drain() {
+      let mut range_cache = self.range_cache.drain().collect::<Vec<_>>();
+
+      // insertion order determines the database layout
+      if self.index.is_deterministic() {
+        range_cache.sort_unstable_by_key(|(outpoint, _)| *outpoint);
+      }
+
+      for (outpoint, sat_range) in range_cache {
         outpoint_to_sat_ranges.insert(&outpoint, sat_range.as_slice())?;
       }
 
@@ -734,12 +1021,18 @@ impl<'index> Updater<'_> {
 
     {
       let mut outpoint_to_value = wtx.open_table(OUTPOINT_TO_VALUE)?;
-      let mut address_to_outpoint = wtx.open_multimap_table(ADDRESS_TO_OUTPOINT)?;
+      let mut script_hash_to_outpoint = wtx.open_multimap_table(SCRIPT_HASH_TO_OUTPOINT)?;
+
+      let mut value_cache = value_cache.into_iter().collect::<Vec<_>>();
+
+      if self.index.is_deterministic() {
+        value_cache.sort_unstable_by_key(|(outpoint, _)| outpoint.store());
+      }
 
       for (outpoint, map) in value_cache {
         outpoint_to_value.insert(&outpoint.store(), map.0)?;
-        if map.1 != [0u8; 34] {
-          address_to_outpoint.insert(map.1.as_slice(), &outpoint.store())?;
+        if map.1 != [0u8; 32] {
+          script_hash_to_outpoint.insert(&map.1, &outpoint.store())?;
         }
       }
     }
@@ -750,11 +1043,196 @@ impl<'index> Updater<'_> {
     self.sat_ranges_since_flush = 0;
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
 
+    wtx
+      .open_table(STATISTIC_TO_COUNT)?
+      .insert(&Statistic::Updating.key(), &u64::from(updating))?;
+
     wtx.commit()?;
     if !cfg!(test) {
       Reorg::update_savepoints(self.index, self.height)?;
     }
 
+    // hooks only see events once they are committed, so events of blocks
+    // that are lost when indexing is interrupted are never passed to them,
+    // subscribers receive committed events from the outbox
+    let mut events = self.uncommitted_events.drain(..).peekable();
+
+    for (height, header) in self.uncommitted_blocks.drain(..) {
+      while let Some(event) = events.next_if(|event| event.block_height == height) {
+        for hook in &self.index.event_hooks {
+          hook(&event);
+        }
+      }
+
+      for hook in &self.index.block_hooks {
+        hook(height, &header);
+      }
+    }
+
     Ok(())
   }
 }
+
+/// Applies the relic operations of `tx` to the current relic state as if it
+/// was the first transaction of the next block, and returns the events it
+/// would emit. The write transaction is aborted, so nothing is committed.
+pub(super) fn simulate_relic_transaction(index: &Index, tx: &Transaction) -> Result<Vec<Event>> {
+  let height = index.block_count()?;
+  let txid = tx.txid();
+
+  let wtx = index.begin_write()?;
+
+  {
+    let mut transaction_id_to_events = wtx.open_multimap_table(TRANSACTION_ID_TO_EVENTS)?;
+    let mut relic_id_to_events = wtx.open_multimap_table(RELIC_ID_TO_EVENTS)?;
+    let mut address_to_events = wtx.open_multimap_table(ADDRESS_TO_EVENTS)?;
+    let mut event_key_to_event = wtx.open_table(EVENT_KEY_TO_EVENT)?;
+    let mut event_kind_to_event_key = wtx.open_table(EVENT_KIND_TO_EVENT_KEY)?;
+    let mut relic_id_to_event_key = wtx.open_table(RELIC_ID_TO_EVENT_KEY)?;
+    let mut relic_id_to_burn_event_key = wtx.open_table(RELIC_ID_TO_BURN_EVENT_KEY)?;
+    let mut inscription_id_to_chest_event_key =
+      wtx.open_table(INSCRIPTION_ID_TO_CHEST_EVENT_KEY)?;
+
+    let mut emitter = EventEmitter {
+      block_height: height,
+      event_index: 0,
+      emitted: None,
+      outbox: None,
+      address_to_events: &mut address_to_events,
+      relic_id_to_events: &mut relic_id_to_events,
+      transaction_id_to_events: &mut transaction_id_to_events,
+      event_key_to_event: &mut event_key_to_event,
+      event_kind_to_event_key: &mut event_kind_to_event_key,
+      relic_id_to_event_key: &mut relic_id_to_event_key,
+      relic_id_to_burn_event_key: &mut relic_id_to_burn_event_key,
+      inscription_id_to_chest_event_key: &mut inscription_id_to_chest_event_key,
+    };
+
+    let inscription_id_to_sequence_number = wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+    let inscription_id_to_txids = wtx.open_table(INSCRIPTION_ID_TO_TXIDS)?;
+    let inscription_txid_to_tx = wtx.open_table(INSCRIPTION_TXID_TO_TX)?;
+    let inscription_id_to_pruned_inscription =
+      wtx.open_table(INSCRIPTION_ID_TO_PRUNED_INSCRIPTION)?;
+    let satpoint_to_sequence_number = wtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+    let mut sequence_number_to_inscription_entry =
+      wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+    let mut sequence_number_to_spaced_relic = wtx.open_table(SEQUENCE_NUMBER_TO_SPACED_RELIC)?;
+    let mut sequence_number_to_owner_change = wtx.open_table(SEQUENCE_NUMBER_TO_OWNER_CHANGE)?;
+    let mut sequence_number_to_bonestone_block_height =
+      wtx.open_table(SEQUENCE_NUMBER_TO_BONESTONE_BLOCK_HEIGHT)?;
+    let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
+    let mut outpoint_to_relic_balances = wtx.open_table(OUTPOINT_TO_RELIC_BALANCES)?;
+    let mut relic_id_to_relic_entry = wtx.open_table(RELIC_ID_TO_RELIC_ENTRY)?;
+    let mut syndicate_id_to_syndicate_entry = wtx.open_table(SYNDICATE_ID_TO_SYNDICATE_ENTRY)?;
+    let mut relic_to_relic_id = wtx.open_table(RELIC_TO_RELIC_ID)?;
+    let mut relic_prefix_to_relic_id = wtx.open_multimap_table(RELIC_PREFIX_TO_RELIC_ID)?;
+    let mut relic_number_to_relic_id = wtx.open_table(RELIC_NUMBER_TO_RELIC_ID)?;
+    let mut relic_id_to_thumbnail = wtx.open_table(RELIC_ID_TO_THUMBNAIL)?;
+    let mut relic_id_to_supply = wtx.open_table(RELIC_ID_TO_SUPPLY)?;
+    let mut relic_id_to_pool_price = wtx.open_table(RELIC_ID_TO_POOL_PRICE)?;
+    let mut relic_to_fees = wtx.open_table(RELIC_TO_FEES)?;
+    let mut height_to_fees = wtx.open_table(HEIGHT_TO_FEES)?;
+    let mut height_to_relic_activity = wtx.open_table(HEIGHT_TO_RELIC_ACTIVITY)?;
+    let mut height_to_relic_error_counts = wtx.open_table(HEIGHT_TO_RELIC_ERROR_COUNTS)?;
+    let mut relic_owner_to_claimable = wtx.open_table(RELIC_OWNER_TO_CLAIMABLE)?;
+    let mut relic_balance_journal = wtx.open_table(RELIC_BALANCE_JOURNAL)?;
+    let mut address_to_journaled_relic_id =
+      wtx.open_multimap_table(ADDRESS_TO_JOURNALED_RELIC_ID)?;
+    let mut liquidity_provider_to_shares = wtx.open_table(LIQUIDITY_PROVIDER_TO_SHARES)?;
+    let mut relic_owner_to_fees_paid = wtx.open_table(RELIC_OWNER_TO_FEES_PAID)?;
+    let mut relic_id_to_subsidy_schedule = wtx.open_table(RELIC_ID_TO_SUBSIDY_SCHEDULE)?;
+    let mut subsidy_release_height_to_relic_id =
+      wtx.open_table(SUBSIDY_RELEASE_HEIGHT_TO_RELIC_ID)?;
+    let mut transaction_id_to_relic = wtx.open_table(TRANSACTION_ID_TO_RELIC)?;
+    let mut sequence_number_to_syndicate_id = wtx.open_table(SEQUENCE_NUMBER_TO_SYNDICATE_ID)?;
+    let mut sequence_number_to_chest = wtx.open_table(SEQUENCE_NUMBER_TO_CHEST)?;
+    let mut syndicate_to_chest_sequence_number =
+      wtx.open_multimap_table(SYNDICATE_TO_CHEST_SEQUENCE_NUMBER)?;
+    let mut chest_unlock_height_to_sequence_number =
+      wtx.open_table(CHEST_UNLOCK_HEIGHT_TO_SEQUENCE_NUMBER)?;
+    let mut relic_to_sequence_number = wtx.open_table(RELIC_TO_SEQUENCE_NUMBER)?;
+    let mut pending_sealing_sequence_numbers = wtx.open_table(PENDING_SEALING_SEQUENCE_NUMBERS)?;
+
+    let relics = statistic_to_count
+      .get(&Statistic::Relics.into())?
+      .map(|x| x.value())
+      .unwrap_or(0);
+
+    let mut relic_updater = RelicUpdater {
+      block_time: u32::try_from(Utc::now().timestamp()).unwrap_or(u32::MAX),
+      activity: HashMap::new(),
+      burned: HashMap::new(),
+      balance_changes: None,
+      raw_transactions: None,
+      claimable: HashMap::new(),
+      errors: HashMap::new(),
+      fees: HashMap::new(),
+      unsafe_txids: HashSet::new(),
+      updated: HashSet::new(),
+      index,
+      height,
+      first_relic_syndicate_height: index.first_relic_syndicate_height,
+      id_to_entry: &mut relic_id_to_relic_entry,
+      id_to_syndicate: &mut syndicate_id_to_syndicate_entry,
+      inscription_id_to_sequence_number: &inscription_id_to_sequence_number,
+      liquidity_provider_to_shares: &mut liquidity_provider_to_shares,
+      outpoint_to_balances: &mut outpoint_to_relic_balances,
+      relic_owner_to_claimable: &mut relic_owner_to_claimable,
+      relic_balance_journal: &mut relic_balance_journal,
+      address_to_journaled_relic_id: &mut address_to_journaled_relic_id,
+      relic_owner_to_fees_paid: &mut relic_owner_to_fees_paid,
+      relic_to_id: &mut relic_to_relic_id,
+      relic_prefix_to_id: &mut relic_prefix_to_relic_id,
+      relic_number_to_id: &mut relic_number_to_relic_id,
+      relic_id_to_thumbnail: &mut relic_id_to_thumbnail,
+      relic_id_to_supply: &mut relic_id_to_supply,
+      relic_id_to_pool_price: &mut relic_id_to_pool_price,
+      relic_id_to_subsidy_schedule: &mut relic_id_to_subsidy_schedule,
+      subsidy_release_height_to_relic_id: &mut subsidy_release_height_to_relic_id,
+      relic_to_fees: &mut relic_to_fees,
+      height_to_fees: &mut height_to_fees,
+      height_to_relic_activity: &mut height_to_relic_activity,
+      height_to_error_counts: &mut height_to_relic_error_counts,
+      relics,
+      statistic_to_count: &mut statistic_to_count,
+      transaction_id_to_relic: &mut transaction_id_to_relic,
+      satpoint_to_sequence_number: &satpoint_to_sequence_number,
+      sequence_number_to_inscription_entry: &mut sequence_number_to_inscription_entry,
+      sequence_number_to_satpoint: &sequence_number_to_satpoint,
+      sequence_number_to_spaced_relic: &mut sequence_number_to_spaced_relic,
+      sequence_number_to_owner_change: &mut sequence_number_to_owner_change,
+      sequence_number_to_syndicate: &mut sequence_number_to_syndicate_id,
+      sequence_number_to_chest: &mut sequence_number_to_chest,
+      syndicate_to_chest_sequence_number: &mut syndicate_to_chest_sequence_number,
+      chest_unlock_height_to_sequence_number: &mut chest_unlock_height_to_sequence_number,
+      relic_to_sequence_number: &mut relic_to_sequence_number,
+      pending_sealings: &mut pending_sealing_sequence_numbers,
+      event_emitter: &mut emitter,
+      inscription_id_to_txids: &inscription_id_to_txids,
+      inscription_txid_to_tx: &inscription_txid_to_tx,
+      inscription_id_to_pruned_inscription: &inscription_id_to_pruned_inscription,
+      sequence_number_to_bonestone_block_height: &mut sequence_number_to_bonestone_block_height,
+    };
+
+    relic_updater.index_relics(1, tx, txid, Keepsake::decipher(tx))?;
+  }
+
+  // events of earlier confirmations of the same transaction are left out
+  let mut events = wtx
+    .open_multimap_table(TRANSACTION_ID_TO_EVENTS)?
+    .get(&txid.store())?
+    .map(|event| event.map(|event| event.value()))
+    .filter(|event| {
+      event
+        .as_ref()
+        .map_or(true, |event| event.block_height == height)
+    })
+    .collect::<Result<Vec<Event>, StorageError>>()?;
+
+  events.sort_by_key(|event| event.event_index);
+
+  wtx.abort()?;
+
+  Ok(events)
+}
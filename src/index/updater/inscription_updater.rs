@@ -3,8 +3,10 @@ use {
   super::*,
   crate::{
     charm::Charm,
+    index::chest_entry::{ChestEntry, ChestEntryValue},
+    index::entry::{script_hash, ScriptHashValue},
     inscription::ParsedInscription,
-    relics::{BONESTONES_END_BLOCK, BONESTONES_INSCRIPTION_ID, BONESTONES_START_BLOCK},
+    relics::{BONESTONES_END_BLOCK, BONESTONES_INSCRIPTION_ID},
     sat::Sat,
     sat_point::SatPoint,
   },
@@ -36,9 +38,13 @@ pub(super) struct InscriptionUpdater<'a, 'tx, 'emitter> {
   id_to_satpoint: &'a mut Table<'tx, &'static InscriptionIdValue, &'static SatPointValue>,
   id_to_txids: &'a mut Table<'tx, &'static InscriptionIdValue, &'static [u8]>,
   txid_to_tx: &'a mut Table<'tx, &'static [u8], &'static [u8]>,
+  id_to_pruned_inscription: &'a mut Table<'tx, &'static InscriptionIdValue, &'static str>,
   partial_txid_to_txids: &'a mut Table<'tx, &'static [u8], &'static [u8]>,
   value_receiver: &'a mut Receiver<u64>,
   index_transactions: bool,
+  /// store transactions relevant to inscriptions, see `--index-raw-transactions`
+  index_raw_transactions: bool,
+  prune_content: bool,
   transaction_buffer: Vec<u8>,
   sequence_number_to_inscription_entry: &'a mut Table<'tx, u32, InscriptionEntryValue>,
   inscription_number_to_sequence_number: &'a mut Table<'tx, u64, u32>,
@@ -51,18 +57,29 @@ pub(super) struct InscriptionUpdater<'a, 'tx, 'emitter> {
   sequence_number_to_children: &'a mut MultimapTable<'tx, u32, u32>,
   sequence_number_to_satpoint: &'a mut Table<'tx, u32, &'static SatPointValue>,
   sequence_number_to_spaced_relic: &'a mut Table<'tx, u32, SpacedRelicValue>,
+  sequence_number_to_chest: &'a Table<'tx, u32, ChestEntryValue>,
+  sequence_number_to_owner_change: &'a mut Table<'tx, (u32, u32), OwnerChange>,
   transaction_id_to_transaction: &'a mut Table<'tx, &'static TxidValue, &'static [u8]>,
   lost_sats: u64,
   next_number: u64,
   pub(crate) next_sequence_number: u32,
   outpoint_to_value: &'a mut Table<'tx, &'static OutPointValue, u64>,
-  address_to_outpoint: &'a mut MultimapTable<'tx, &'static [u8], &'static OutPointValue>,
+  script_hash_to_outpoint:
+    &'a mut MultimapTable<'tx, &'static ScriptHashValue, &'static OutPointValue>,
+  address_to_sequence_number: &'a mut MultimapTable<'tx, &'static [u8], u32>,
+  address_to_inscription_count: &'a mut Table<'tx, &'static [u8], u64>,
+  sequence_number_to_address: &'a mut Table<'tx, u32, &'static [u8]>,
   reward: u64,
   satpoint_to_id: &'a mut Table<'tx, &'static SatPointValue, &'static InscriptionIdValue>,
   timestamp: u32,
   value_cache: &'a mut HashMap<OutPoint, OutPointMapValue>,
   chain: Chain,
+  /// see `BonesConfig::bonestones_start_height`
+  bonestones_start_height: u32,
   event_emitter: &'a mut EventEmitter<'emitter, 'tx>,
+  /// inscriptions of single transactions, parsed ahead of time by the fetch
+  /// threads, see `BlockData::inscriptions`
+  parsed_inscriptions: Option<HashMap<Txid, ParsedInscription>>,
 }
 
 impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
@@ -71,9 +88,12 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
     id_to_satpoint: &'a mut Table<'tx, &'static InscriptionIdValue, &'static SatPointValue>,
     id_to_txids: &'a mut Table<'tx, &'static InscriptionIdValue, &'static [u8]>,
     txid_to_tx: &'a mut Table<'tx, &'static [u8], &'static [u8]>,
+    id_to_pruned_inscription: &'a mut Table<'tx, &'static InscriptionIdValue, &'static str>,
     partial_txid_to_txids: &'a mut Table<'tx, &'static [u8], &'static [u8]>,
     value_receiver: &'a mut Receiver<u64>,
     index_transactions: bool,
+    index_raw_transactions: bool,
+    prune_content: bool,
     transaction_buffer: Vec<u8>,
     sequence_number_to_inscription_entry: &'a mut Table<'tx, u32, InscriptionEntryValue>,
     inscription_number_to_sequence_number: &'a mut Table<'tx, u64, u32>,
@@ -86,15 +106,26 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
     sequence_number_to_children: &'a mut MultimapTable<'tx, u32, u32>,
     sequence_number_to_satpoint: &'a mut Table<'tx, u32, &'static SatPointValue>,
     sequence_number_to_spaced_relic: &'a mut Table<'tx, u32, SpacedRelicValue>,
+    sequence_number_to_chest: &'a Table<'tx, u32, ChestEntryValue>,
+    sequence_number_to_owner_change: &'a mut Table<'tx, (u32, u32), OwnerChange>,
     transaction_id_to_transaction: &'a mut Table<'tx, &'static TxidValue, &'static [u8]>,
     lost_sats: u64,
     outpoint_to_value: &'a mut Table<'tx, &'static OutPointValue, u64>,
-    address_to_outpoint: &'a mut MultimapTable<'tx, &'static [u8], &'static OutPointValue>,
+    script_hash_to_outpoint: &'a mut MultimapTable<
+      'tx,
+      &'static ScriptHashValue,
+      &'static OutPointValue,
+    >,
+    address_to_sequence_number: &'a mut MultimapTable<'tx, &'static [u8], u32>,
+    address_to_inscription_count: &'a mut Table<'tx, &'static [u8], u64>,
+    sequence_number_to_address: &'a mut Table<'tx, u32, &'static [u8]>,
     satpoint_to_id: &'a mut Table<'tx, &'static SatPointValue, &'static InscriptionIdValue>,
     timestamp: u32,
     value_cache: &'a mut HashMap<OutPoint, OutPointMapValue>,
     chain: Chain,
+    bonestones_start_height: u32,
     event_emitter: &'a mut EventEmitter<'emitter, 'tx>,
+    parsed_inscriptions: Option<HashMap<Txid, ParsedInscription>>,
   ) -> Result<Self> {
     let mut next_number = inscription_number_to_sequence_number
       .iter()?
@@ -120,9 +151,12 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
       id_to_satpoint,
       id_to_txids,
       txid_to_tx,
+      id_to_pruned_inscription,
       partial_txid_to_txids,
       value_receiver,
       index_transactions,
+      index_raw_transactions,
+      prune_content,
       transaction_buffer,
       sequence_number_to_inscription_entry,
       inscription_number_to_sequence_number,
@@ -135,21 +169,40 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
       sequence_number_to_children,
       sequence_number_to_satpoint,
       sequence_number_to_spaced_relic,
+      sequence_number_to_chest,
+      sequence_number_to_owner_change,
       transaction_id_to_transaction,
       lost_sats,
       next_number,
       next_sequence_number,
       outpoint_to_value,
-      address_to_outpoint,
+      script_hash_to_outpoint,
+      address_to_sequence_number,
+      address_to_inscription_count,
+      sequence_number_to_address,
       reward: Height(height).subsidy(),
       satpoint_to_id,
       timestamp,
       value_cache,
       chain,
+      bonestones_start_height,
       event_emitter,
+      parsed_inscriptions,
     })
   }
 
+  fn store_transaction(&mut self, tx: &Transaction, txid: Txid) -> Result {
+    tx.consensus_encode(&mut self.transaction_buffer)
+      .expect("in-memory writers don't error");
+    self
+      .transaction_id_to_transaction
+      .insert(&txid.store(), self.transaction_buffer.as_slice())?;
+
+    self.transaction_buffer.clear();
+
+    Ok(())
+  }
+
   pub(super) fn index_transaction_inscriptions(
     &mut self,
     tx: &Transaction,
@@ -159,15 +212,12 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
     let mut inscriptions = Vec::new();
 
     if self.index_transactions {
-      tx.consensus_encode(&mut self.transaction_buffer)
-        .expect("in-memory writers don't error");
-      self
-        .transaction_id_to_transaction
-        .insert(&txid.store(), self.transaction_buffer.as_slice())?;
-
-      self.transaction_buffer.clear();
+      self.store_transaction(tx, txid)?;
     }
 
+    // whether the transaction is part of an inscription, complete or not
+    let mut inscribes = false;
+
     let mut input_value = 0;
     for tx_in in &tx.input {
       if tx_in.previous_output.is_null() {
@@ -210,15 +260,11 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
             .get(&tx_in.previous_output.txid.store())?
           {
             let tx: Transaction = consensus::encode::deserialize(transaction.value())?;
-            let output = tx.output[tx_in.previous_output.vout as usize].clone();
-            if let Some(address_from_script) =
-              self.chain.address_from_script(&output.script_pubkey).ok()
-            {
-              self.address_to_outpoint.remove(
-                address_from_script.to_string().as_bytes(),
-                &tx_in.previous_output.store(),
-              )?;
-            }
+            let output = &tx.output[tx_in.previous_output.vout as usize];
+            self.script_hash_to_outpoint.remove(
+              &script_hash(&output.script_pubkey),
+              &tx_in.previous_output.store(),
+            )?;
           }
           map.value()
         } else {
@@ -238,7 +284,7 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
       let previous_txid_bytes: [u8; 32] = previous_txid.into_inner();
       let mut txids_vec = vec![];
 
-      let txs = match self
+      let parsed = match self
         .partial_txid_to_txids
         .get(&previous_txid_bytes.as_slice())?
       {
@@ -256,19 +302,25 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
             txs.push(tx);
           }
           txs.push(tx.clone());
-          txs
-        }
-        None => {
-          vec![tx.clone()]
+          Inscription::from_transactions(txs)
         }
+        // continues no partial inscription, so it was parsed ahead of time
+        None => match &mut self.parsed_inscriptions {
+          Some(parsed_inscriptions) => parsed_inscriptions
+            .remove(&txid)
+            .unwrap_or(ParsedInscription::None),
+          None => Inscription::from_transactions(vec![tx.clone()]),
+        },
       };
 
-      match Inscription::from_transactions(txs) {
+      match parsed {
         ParsedInscription::None => {
           // todo: clean up db
         }
 
         ParsedInscription::Partial => {
+          inscribes = true;
+
           let mut txid_vec = txid.into_inner().to_vec();
           txids_vec.append(&mut txid_vec);
 
@@ -287,15 +339,23 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
         }
 
         ParsedInscription::Complete(_inscription) => {
+          inscribes = true;
+
           self
             .partial_txid_to_txids
             .remove(&previous_txid_bytes.as_slice())?;
 
-          let mut tx_buf = vec![];
-          tx.consensus_encode(&mut tx_buf)?;
-          self
-            .txid_to_tx
-            .insert(&txid.into_inner().as_slice(), tx_buf.as_slice())?;
+          if self.prune_content {
+            for partial_txid in txids_vec.chunks(32) {
+              self.txid_to_tx.remove(partial_txid)?;
+            }
+          } else {
+            let mut tx_buf = vec![];
+            tx.consensus_encode(&mut tx_buf)?;
+            self
+              .txid_to_tx
+              .insert(&txid.into_inner().as_slice(), tx_buf.as_slice())?;
+          }
 
           let mut txid_vec = txid.into_inner().to_vec();
           txids_vec.append(&mut txid_vec);
@@ -304,9 +364,21 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
           unsafe {
             std::ptr::copy_nonoverlapping(txids_vec.as_ptr(), inscription_id.as_mut_ptr(), 32)
           }
-          self
-            .id_to_txids
-            .insert(&inscription_id, txids_vec.as_slice())?;
+
+          if self.prune_content {
+            // keep everything but the body, which is what makes inscriptions large
+            let pruned = serde_json::to_string(&Inscription {
+              body: None,
+              .._inscription.clone()
+            })?;
+            self
+              .id_to_pruned_inscription
+              .insert(&inscription_id, pruned.as_str())?;
+          } else {
+            self
+              .id_to_txids
+              .insert(&inscription_id, txids_vec.as_slice())?;
+          }
 
           let og_inscription_id = InscriptionId {
             txid: Txid::from_slice(&txids_vec[0..32]).unwrap(),
@@ -343,6 +415,13 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
       inscriptions.append(&mut self.flotsam);
     }
 
+    if self.index_raw_transactions
+      && !self.index_transactions
+      && (inscribes || !inscriptions.is_empty())
+    {
+      self.store_transaction(tx, txid)?;
+    }
+
     inscriptions.sort_by_key(|flotsam| flotsam.offset);
     let mut inscriptions = inscriptions.into_iter().peekable();
     let mut inscription_id_to_script = HashMap::new();
@@ -351,6 +430,12 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
     for (vout, tx_out) in tx.output.iter().enumerate() {
       let end = output_value + tx_out.value;
 
+      let address = self
+        .chain
+        .address_from_script(&tx_out.script_pubkey)
+        .ok()
+        .map(|address| address.to_string());
+
       while let Some(flotsam) = inscriptions.peek() {
         if flotsam.offset >= end {
           break;
@@ -373,32 +458,26 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
           new_satpoint,
           is_op_return,
           txid,
+          address.as_deref(),
         )?;
       }
 
       output_value = end;
 
-      let address_from_script = self
-        .chain
-        .address_from_script(&tx_out.clone().script_pubkey);
-
-      let address = if address_from_script.is_err() {
-        [0u8; 34]
-      } else {
-        address_from_script
-          .unwrap()
-          .to_string()
-          .as_bytes()
-          .try_into()
-          .unwrap_or([0u8; 34])
-      };
-
       self.value_cache.insert(
         OutPoint {
           vout: vout.try_into().unwrap(),
           txid,
         },
-        (tx_out.clone().value, address),
+        (
+          tx_out.value,
+          // unspendable outputs are left out of the script hash index
+          if tx_out.script_pubkey.is_op_return() {
+            [0u8; 32]
+          } else {
+            script_hash(&tx_out.script_pubkey)
+          },
+        ),
       );
     }
 
@@ -417,6 +496,7 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
           new_satpoint,
           *op_return,
           txid,
+          None,
         )?;
       }
 
@@ -438,6 +518,7 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
     new_satpoint: SatPoint,
     op_return: bool,
     txid: Txid,
+    address: Option<&str>,
   ) -> Result {
     let inscription_id = flotsam.inscription_id;
     let mut seq_number = 0;
@@ -497,6 +578,33 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
               sequence_number,
             },
           )?;
+          // the sealing inscription controls its relic
+          OwnerChange::record(
+            self.sequence_number_to_owner_change,
+            sequence_number,
+            &OwnerChange {
+              height: self.height,
+              txid,
+              location: new_satpoint,
+              address: address.map(str::to_string),
+            },
+          )?;
+        }
+
+        if let Some(chest) = self
+          .sequence_number_to_chest
+          .get(sequence_number)?
+          .map(|entry| ChestEntry::load(entry.value()))
+        {
+          self.event_emitter.emit(
+            txid,
+            EventInfo::ChestTransferred {
+              syndicate_id: chest.syndicate,
+              inscription_id,
+              new_location: new_satpoint,
+              old_location: old_satpoint,
+            },
+          )?;
         }
 
         self
@@ -515,12 +623,15 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
           .inscription_number_to_sequence_number
           .insert(&self.next_number, seq_number)?;
 
-        if self.height >= BONESTONES_START_BLOCK && self.height < BONESTONES_END_BLOCK {
+        let mut charms = 0;
+
+        if self.height >= self.bonestones_start_height && self.height < BONESTONES_END_BLOCK {
           if let Some(delegate_id) = inscription_new.delegate() {
             if delegate_id == InscriptionId::from_str(BONESTONES_INSCRIPTION_ID)? {
               self
                 .sequence_number_to_bonestone_block_height
                 .insert(seq_number, self.height)?;
+              Charm::Bonestone.set(&mut charms);
             };
           };
         }
@@ -550,8 +661,6 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
           }
         }
 
-        let mut charms = 0;
-
         if op_return {
           Charm::Burned.set(&mut charms);
         }
@@ -594,6 +703,20 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
           .inscription_id_to_sequence_number
           .insert(&inscription_id.store(), seq_number)?;
 
+        // bonestones are revealed in bulk and already reported as relic mints
+        if !Charm::Bonestone.is_set(charms) {
+          self.event_emitter.emit(
+            txid,
+            EventInfo::InscriptionCreated {
+              charms,
+              inscription_id,
+              location: Some(new_satpoint),
+              parent_inscription_ids: inscription_new.parents(),
+              sequence_number: seq_number,
+            },
+          )?;
+        }
+
         self.next_number += 1;
       }
     }
@@ -613,6 +736,62 @@ impl<'a, 'tx, 'emitter> InscriptionUpdater<'a, 'tx, 'emitter> {
       .sequence_number_to_satpoint
       .insert(seq_number, &new_satpoint)?;
 
+    self.update_inscription_address(seq_number, address)?;
+
+    Ok(())
+  }
+
+  /// Moves the inscription with `sequence_number` from the address holding it
+  /// to `address`, if any.
+  fn update_inscription_address(&mut self, sequence_number: u32, address: Option<&str>) -> Result {
+    let old_address = self
+      .sequence_number_to_address
+      .remove(sequence_number)?
+      .map(|address| address.value().to_vec());
+
+    if let Some(old_address) = old_address {
+      self
+        .address_to_sequence_number
+        .remove(old_address.as_slice(), sequence_number)?;
+
+      let count = self
+        .address_to_inscription_count
+        .get(old_address.as_slice())?
+        .map(|count| count.value())
+        .unwrap_or_default();
+
+      if count <= 1 {
+        self
+          .address_to_inscription_count
+          .remove(old_address.as_slice())?;
+      } else {
+        self
+          .address_to_inscription_count
+          .insert(old_address.as_slice(), count - 1)?;
+      }
+    }
+
+    if let Some(address) = address {
+      let address = address.as_bytes();
+
+      self
+        .sequence_number_to_address
+        .insert(sequence_number, address)?;
+      self
+        .address_to_sequence_number
+        .insert(address, sequence_number)?;
+
+      let count = self
+        .address_to_inscription_count
+        .get(address)?
+        .map(|count| count.value())
+        .unwrap_or_default();
+
+      self
+        .address_to_inscription_count
+        .insert(address, count + 1)?;
+    }
+
     Ok(())
   }
 }
@@ -70,6 +70,12 @@ impl<'a, 'tx, 'emitter> RelicsBalance {
     })
   }
 
+  /// Whether the transaction holds no relics, neither from its inputs nor
+  /// added so far.
+  pub fn is_empty(&self) -> bool {
+    self.total.is_empty()
+  }
+
   fn lookup(entries: &HashMap<RelicId, Lot>, id: RelicId) -> u128 {
     entries.get(&id).map(|lot| lot.n()).unwrap_or_default()
   }
@@ -219,6 +225,8 @@ impl<'a, 'tx, 'emitter> RelicsBalance {
   }
 
   /// Assign allocated balances to outpoints, update burned balances, track unsafe outpoints.
+  /// If `balance_changes` is given, the amounts received and spent by each
+  /// address are added to it.
   pub fn finalize(
     mut self,
     tx: &Transaction,
@@ -226,6 +234,7 @@ impl<'a, 'tx, 'emitter> RelicsBalance {
     outpoint_to_balances: &'a mut Table<'tx, &'static OutPointValue, &'static [u8]>,
     unsafe_txids: &'a mut HashSet<Txid>,
     burned: &'a mut HashMap<RelicId, Lot>,
+    balance_changes: Option<&'a mut HashMap<(Address, RelicId), (Lot, Lot)>>,
     event_emitter: &'a mut EventEmitter<'emitter, 'tx>,
     index: &Index,
   ) -> Result {
@@ -277,7 +286,20 @@ impl<'a, 'tx, 'emitter> RelicsBalance {
       outpoint_to_balances.insert(&outpoint.store(), buffer.as_slice())?;
     }
 
-    for ((address, relic_id), spent) in self.incoming {
+    if let Some(balance_changes) = balance_changes {
+      for (key, received) in &self.outgoing {
+        balance_changes.entry(key.clone()).or_default().0 += *received;
+      }
+      for (key, spent) in &self.incoming {
+        balance_changes.entry(key.clone()).or_default().1 += *spent;
+      }
+    }
+
+    // emit events in a fixed order, independent of hash map iteration order
+    let mut incoming = self.incoming.into_iter().collect::<Vec<_>>();
+    incoming.sort_by_cached_key(|((address, relic_id), _)| (address.to_string(), *relic_id));
+
+    for ((address, relic_id), spent) in incoming {
       let info = if let Some(received) = self.outgoing.remove(&(address.clone(), relic_id)) {
         if received > spent {
           // spent less than received => net received
@@ -304,7 +326,10 @@ impl<'a, 'tx, 'emitter> RelicsBalance {
       };
       event_emitter.emit(txid, info)?
     }
-    for ((address, id), received) in self.outgoing {
+    let mut outgoing = self.outgoing.into_iter().collect::<Vec<_>>();
+    outgoing.sort_by_cached_key(|((address, id), _)| (address.to_string(), *id));
+
+    for ((address, id), received) in outgoing {
       event_emitter.emit(
         txid,
         // spent none, received all
@@ -317,7 +342,10 @@ impl<'a, 'tx, 'emitter> RelicsBalance {
     }
 
     // increment entries with burned relics
-    for (id, amount) in self.burned {
+    let mut burned_relics = self.burned.into_iter().collect::<Vec<(RelicId, Lot)>>();
+    burned_relics.sort_unstable_by_key(|(id, _)| *id);
+
+    for (id, amount) in burned_relics {
       *burned.entry(id).or_default() += amount;
 
       event_emitter.emit(
@@ -6,52 +6,102 @@ use {
       chest_entry::ChestEntry,
       event::{EventEmitter, EventInfo, RelicOperation},
       lot::Lot,
-      relics_entry::{RelicEntry, RelicOwner, RelicState},
+      relics_entry::{
+        RelicActivity, RelicActivityValue, RelicEntry, RelicFees, RelicFeesValue, RelicOwner,
+        RelicState, RelicSupplyValue,
+      },
+      search,
+      subsidy_schedule_entry::SubsidyScheduleEntry,
       syndicate_entry::SyndicateEntry,
       updater::relics_balance::RelicsBalance,
     },
     relics::{
-      BalanceDiff, Enshrining, Keepsake, Pool, PoolSwap, RelicArtifact, RelicError, SpacedRelic,
-      Summoning, Swap, SwapDirection, RELIC_ID,
+      BalanceDiff, Enshrining, LiquidityDiff, LiquidityRemove, Pool, PoolError, PoolSwap, Relic,
+      RelicArtifact, RelicCenotaph, RelicError, SpacedRelic, SubsidySchedule, Summoning, Swap,
+      SwapDirection, RELIC_ID,
     },
   },
 };
 
 pub(super) struct RelicUpdater<'a, 'tx, 'index, 'emitter> {
+  /// mints and swaps in this block, by relic
+  pub(super) activity: HashMap<RelicId, RelicActivity>,
   pub(super) block_time: u32,
   pub(super) burned: HashMap<RelicId, Lot>,
+  /// amounts received and spent by each address in this block, only tracked
+  /// with `--index-relic-history`
+  pub(super) balance_changes: Option<HashMap<(Address, RelicId), (Lot, Lot)>>,
   pub(super) claimable: HashMap<RelicOwner, u128>,
+  /// cenotaphs and failed operations in this block, by kind
+  pub(super) errors: HashMap<&'static str, u64>,
+  /// fees paid in this block, by relic
+  pub(super) fees: HashMap<Relic, RelicFees>,
+  /// where transactions carrying or moving relics are stored, only with
+  /// `--index-raw-transactions`
+  pub(super) raw_transactions: Option<&'a mut Table<'tx, &'static TxidValue, &'static [u8]>>,
   pub(super) unsafe_txids: HashSet<Txid>,
+  /// relics whose entry changed in this block
+  pub(super) updated: HashSet<RelicId>,
   pub(super) index: &'index Index,
   pub(super) height: u32,
   pub(super) first_relic_syndicate_height: u32,
   pub(super) id_to_entry: &'a mut Table<'tx, RelicIdValue, RelicEntryValue>,
   pub(super) id_to_syndicate: &'a mut Table<'tx, SyndicateIdValue, SyndicateEntryValue>,
   pub(super) inscription_id_to_sequence_number: &'a Table<'tx, &'static InscriptionIdValue, u32>,
+  pub(super) liquidity_provider_to_shares:
+    &'a mut Table<'tx, (RelicIdValue, &'static RelicOwnerValue), u128>,
   pub(super) outpoint_to_balances: &'a mut Table<'tx, &'static OutPointValue, &'static [u8]>,
   pub(super) relic_owner_to_claimable: &'a mut Table<'tx, &'static RelicOwnerValue, u128>,
+  pub(super) relic_balance_journal: &'a mut Table<'tx, (RelicIdValue, &'static str, u32), u128>,
+  pub(super) address_to_journaled_relic_id: &'a mut MultimapTable<'tx, &'static str, RelicIdValue>,
+  pub(super) relic_owner_to_fees_paid:
+    &'a mut Table<'tx, (&'static RelicOwnerValue, RelicIdValue), u128>,
   pub(super) relic_to_id: &'a mut Table<'tx, u128, RelicIdValue>,
+  pub(super) relic_prefix_to_id: &'a mut MultimapTable<'tx, &'static str, RelicIdValue>,
+  pub(super) relic_number_to_id: &'a mut Table<'tx, u64, RelicIdValue>,
+  pub(super) relic_id_to_thumbnail: &'a mut Table<'tx, RelicIdValue, InscriptionIdValue>,
+  pub(super) relic_id_to_supply: &'a mut Table<'tx, (RelicIdValue, u32), RelicSupplyValue>,
+  pub(super) relic_id_to_pool_price: &'a mut Table<'tx, (RelicIdValue, u32), u128>,
+  pub(super) relic_id_to_subsidy_schedule:
+    &'a mut Table<'tx, RelicIdValue, SubsidyScheduleEntryValue>,
+  pub(super) subsidy_release_height_to_relic_id: &'a mut Table<'tx, (u64, RelicIdValue), ()>,
+  pub(super) relic_to_fees: &'a mut Table<'tx, (u128, u32), RelicFeesValue>,
+  pub(super) height_to_fees: &'a mut Table<'tx, u32, RelicFeesValue>,
+  pub(super) height_to_relic_activity: &'a mut Table<'tx, (u32, RelicIdValue), RelicActivityValue>,
+  pub(super) height_to_error_counts: &'a mut Table<'tx, (u32, &'static str), u64>,
   pub(super) relics: u64,
   pub(super) statistic_to_count: &'a mut Table<'tx, u64, u64>,
   pub(super) transaction_id_to_relic: &'a mut Table<'tx, &'static TxidValue, u128>,
   pub(super) satpoint_to_sequence_number: &'a MultimapTable<'tx, &'static SatPointValue, u32>,
-  pub(super) sequence_number_to_inscription_entry: &'a Table<'tx, u32, InscriptionEntryValue>,
+  pub(super) sequence_number_to_inscription_entry: &'a mut Table<'tx, u32, InscriptionEntryValue>,
   pub(super) sequence_number_to_satpoint: &'a Table<'tx, u32, &'static SatPointValue>,
   pub(super) sequence_number_to_spaced_relic: &'a mut Table<'tx, u32, SpacedRelicValue>,
+  pub(super) sequence_number_to_owner_change: &'a mut Table<'tx, (u32, u32), OwnerChange>,
   pub(super) sequence_number_to_syndicate: &'a mut Table<'tx, u32, SyndicateIdValue>,
   pub(super) sequence_number_to_chest: &'a mut Table<'tx, u32, ChestEntryValue>,
   pub(super) syndicate_to_chest_sequence_number: &'a mut MultimapTable<'tx, SyndicateIdValue, u32>,
+  pub(super) chest_unlock_height_to_sequence_number: &'a mut Table<'tx, (u64, u32), ()>,
   pub(super) relic_to_sequence_number: &'a mut Table<'tx, u128, u32>,
+  /// sealings whose ticker has not been enshrined yet
+  pub(super) pending_sealings: &'a mut Table<'tx, u32, ()>,
   pub(super) event_emitter: &'a mut EventEmitter<'emitter, 'tx>,
   pub(super) inscription_id_to_txids: &'a Table<'tx, &'static InscriptionIdValue, &'static [u8]>,
   pub(super) inscription_txid_to_tx: &'a Table<'tx, &'static [u8], &'static [u8]>,
+  pub(super) inscription_id_to_pruned_inscription:
+    &'a Table<'tx, &'static InscriptionIdValue, &'static str>,
   pub(super) sequence_number_to_bonestone_block_height: &'a mut Table<'tx, u32, u32>,
 }
 
 impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
-  pub(super) fn index_relics(&mut self, tx_index: u32, tx: &Transaction, txid: Txid) -> Result<()> {
-    let artifact = Keepsake::decipher(tx);
-
+  /// Applies `tx` to the relic state. `artifact` is the transaction's
+  /// deciphered keepsake, decoded ahead of time by the block fetcher.
+  pub(super) fn index_relics(
+    &mut self,
+    tx_index: u32,
+    tx: &Transaction,
+    txid: Txid,
+    artifact: Option<RelicArtifact>,
+  ) -> Result<()> {
     let mut balances = RelicsBalance::new(
       tx,
       &self.unsafe_txids,
@@ -59,11 +109,29 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
       self.index,
     )?;
 
+    // scheduled subsidies are released at the start of the block, with the coinbase transaction
+    if tx_index == 0 {
+      self.release_subsidies(txid)?;
+    }
+
     if let Some(amount) = self.mint_base_token(txid, tx)? {
       balances.add_safe(RELIC_ID, amount);
     }
 
+    if let Some(raw_transactions) = &mut self.raw_transactions {
+      if artifact.is_some() || !balances.is_empty() {
+        let mut buffer = Vec::new();
+        tx.consensus_encode(&mut buffer)
+          .expect("in-memory writers don't error");
+        raw_transactions.insert(&txid.store(), buffer.as_slice())?;
+      }
+    }
+
     if let Some(RelicArtifact::Keepsake(keepsake)) = &artifact {
+      let span = telemetry::Span::enter("index_keepsake")
+        .with_height(self.height)
+        .with_txid(txid);
+
       if keepsake.sealing {
         match self.seal(tx, txid, balances.get(RELIC_ID))? {
           Ok(sealing_fee) => {
@@ -73,29 +141,20 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
           }
           Err(error) => {
             eprintln!("Sealing error: {error}");
-            self.event_emitter.emit(
-              txid,
-              EventInfo::RelicError {
-                operation: RelicOperation::Seal,
-                error,
-              },
-            )?;
+            self.emit_error(txid, RelicOperation::Seal, error)?;
           }
         }
       }
 
       let enshrined_relic = if let Some(enshrining) = keepsake.enshrining {
         match self.enshrine_relic(tx, txid, tx_index, enshrining)? {
-          Ok(id) => Some(id),
+          Ok(id) => {
+            span.set_relic(id);
+            Some(id)
+          }
           Err(error) => {
             eprintln!("Enshrine error: {error}");
-            self.event_emitter.emit(
-              txid,
-              EventInfo::RelicError {
-                operation: RelicOperation::Enshrine,
-                error,
-              },
-            )?;
+            self.emit_error(txid, RelicOperation::Enshrine, error)?;
             None
           }
         }
@@ -106,13 +165,17 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
       if let Some(swap) = &keepsake.swap {
         let input = swap.input.unwrap_or(RELIC_ID);
         let output = swap.output.unwrap_or(RELIC_ID);
+        span.set_relic(if input == RELIC_ID { output } else { input });
         // note: use safe balance here for Sandwich protection:
         // this will prevent swapping the same Relics twice within a block
         match self.swap(txid, swap, input, output, balances.get_safe(input))? {
           Ok((input_amount, output_amount, fees)) => {
             balances.remove_safe(input, Lot(input_amount));
             balances.add(output, Lot(output_amount));
-            for (owner, fee) in fees {
+            if self.index.index_relic_fees {
+              self.record_fees_paid(tx, &fees)?;
+            }
+            for (_relic_id, owner, fee) in fees {
               if let Some(owner) = owner {
                 // add fees to the claimable amount of the owner
                 *self.claimable.entry(owner).or_default() += fee;
@@ -124,13 +187,45 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
           }
           Err(error) => {
             eprintln!("Swap error: {error}");
-            self.event_emitter.emit(
-              txid,
-              EventInfo::RelicError {
-                operation: RelicOperation::Swap,
-                error,
-              },
-            )?;
+            self.emit_error(txid, RelicOperation::Swap, error)?;
+          }
+        }
+      }
+
+      if let Some(liquidity_add) = &keepsake.liquidity_add {
+        let relic_id = liquidity_add.relic;
+        span.set_relic(relic_id);
+        // deposit at most the given amounts, defaulting to the full balances
+        let base_amount = liquidity_add
+          .base_amount
+          .unwrap_or(u128::MAX)
+          .min(balances.get(RELIC_ID));
+        let quote_amount = liquidity_add
+          .quote_amount
+          .unwrap_or(u128::MAX)
+          .min(balances.get(relic_id));
+        match self.add_liquidity(txid, tx, relic_id, base_amount, quote_amount)? {
+          Ok(diff) => {
+            balances.remove(RELIC_ID, Lot(diff.base));
+            balances.remove(relic_id, Lot(diff.quote));
+          }
+          Err(error) => {
+            eprintln!("Liquidity add error: {error}");
+            self.emit_error(txid, RelicOperation::LiquidityAdd, error)?;
+          }
+        }
+      }
+
+      if let Some(liquidity_remove) = &keepsake.liquidity_remove {
+        span.set_relic(liquidity_remove.relic);
+        match self.remove_liquidity(txid, tx, liquidity_remove)? {
+          Ok(diff) => {
+            balances.add(RELIC_ID, Lot(diff.base));
+            balances.add(liquidity_remove.relic, Lot(diff.quote));
+          }
+          Err(error) => {
+            eprintln!("Liquidity remove error: {error}");
+            self.emit_error(txid, RelicOperation::LiquidityRemove, error)?;
           }
         }
       }
@@ -143,6 +238,7 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
           Some(id)
         };
         if let Some(id) = id {
+          span.set_relic(id);
           match self.mint(txid, id, balances.get(RELIC_ID))? {
             Ok((amount, price)) => {
               balances.remove(RELIC_ID, price);
@@ -150,13 +246,7 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
             }
             Err(error) => {
               eprintln!("Mint error: {error}");
-              self.event_emitter.emit(
-                txid,
-                EventInfo::RelicError {
-                  operation: RelicOperation::Mint,
-                  error,
-                },
-              )?;
+              self.emit_error(txid, RelicOperation::Mint, error)?;
             }
           }
         }
@@ -170,19 +260,20 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
             }
             Err(error) => {
               eprintln!("Syndicate summon error: {error}");
-              self.event_emitter.emit(
-                txid,
-                EventInfo::RelicError {
-                  operation: RelicOperation::Summon,
-                  error,
-                },
-              )?;
+              self.emit_error(txid, RelicOperation::Summon, error)?;
             }
           }
         }
 
+        if let Some(schedule) = &keepsake.subsidy_schedule {
+          if let Err(error) = self.schedule_subsidy(txid, tx, schedule)? {
+            eprintln!("Subsidy schedule error: {error}");
+            self.emit_error(txid, RelicOperation::ScheduleSubsidy, error)?;
+          }
+        }
+
         if let Some(syndicate_id) = &keepsake.encasing {
-          match self.encase_chest(txid, *syndicate_id, &balances)? {
+          match self.encase_chest(tx, txid, *syndicate_id, &balances)? {
             Ok((id, quota, owner, royalty)) => {
               // lock Chest quota
               balances.remove(id, Lot(quota));
@@ -194,17 +285,31 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
             }
             Err(error) => {
               eprintln!("Chest encase error: {error}");
-              self.event_emitter.emit(
-                txid,
-                EventInfo::RelicError {
-                  operation: RelicOperation::Encase,
-                  error,
-                },
-              )?;
+              self.emit_error(txid, RelicOperation::Encase, error)?;
+            }
+          }
+        }
+
+        if let Some(amount) = keepsake.withdraw {
+          match self.withdraw_chest(txid, tx, amount)? {
+            Ok((relic_id, amount)) => {
+              // payout part of the Relics from the Chest
+              balances.add(relic_id, Lot(amount));
+            }
+            Err(error) => {
+              eprintln!("Chest withdrawal error: {error}");
+              self.emit_error(txid, RelicOperation::Withdraw, error)?;
             }
           }
         }
 
+        if let Some(unlock_height) = keepsake.relock {
+          if let Err(error) = self.relock_chest(txid, tx, unlock_height)? {
+            eprintln!("Chest relock error: {error}");
+            self.emit_error(txid, RelicOperation::Relock, error)?;
+          }
+        }
+
         if keepsake.release {
           match self.release_chest(txid, tx)? {
             Ok((relic_id, amount)) => {
@@ -213,13 +318,7 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
             }
             Err(error) => {
               eprintln!("Chest release error: {error}");
-              self.event_emitter.emit(
-                txid,
-                EventInfo::RelicError {
-                  operation: RelicOperation::Release,
-                  error,
-                },
-              )?;
+              self.emit_error(txid, RelicOperation::Release, error)?;
             }
           }
         }
@@ -235,13 +334,7 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
           balances.allocate(claim, RELIC_ID, amount);
         } else {
           eprintln!("Claim error: no balance to claim");
-          self.event_emitter.emit(
-            txid,
-            EventInfo::RelicError {
-              operation: RelicOperation::Claim,
-              error: RelicError::NoClaimableBalance,
-            },
-          )?;
+          self.emit_error(txid, RelicOperation::Claim, RelicError::NoClaimableBalance)?;
         }
       }
 
@@ -265,7 +358,10 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
         .map(|pointer| pointer as usize)
         .or_else(first_non_op_return_output),
       // invalid protocol message: explicitly burn all Relics
-      Some(RelicArtifact::Cenotaph(_)) => None,
+      Some(RelicArtifact::Cenotaph(_)) => {
+        *self.errors.entry(RelicCenotaph::KIND).or_default() += 1;
+        None
+      }
     };
 
     if let Some(vout) = default_output {
@@ -281,6 +377,7 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
       self.outpoint_to_balances,
       &mut self.unsafe_txids,
       &mut self.burned,
+      self.balance_changes.as_mut(),
       self.event_emitter,
       self.index,
     )
@@ -299,8 +396,9 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
       let mut relic = self
         .load_relic_entry(syndicate.treasure)?
         .expect("Syndicate index inconsistent");
-      // subsidies have been used up
-      if relic.state.subsidy_remaining == 0 {
+      // subsidies have been used up or not released yet
+      let mut available = self.available_subsidy(syndicate.treasure, &relic)?;
+      if available == 0 {
         continue;
       }
       // update all Chests belonging to this Syndicate
@@ -316,14 +414,15 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
           .value();
         let mut chest = ChestEntry::load(chest_value);
         // limit payout to the available subsidy on the Relic
-        let payout = reward.min(relic.state.subsidy_remaining);
+        let payout = reward.min(available);
         chest.amount += payout;
         // subtract collected rewards from subsidy supply on the Relic
         relic.state.subsidy_remaining -= payout;
+        available -= payout;
         self
           .sequence_number_to_chest
           .insert(chest_sequence_number, chest.store())?;
-        if relic.state.subsidy_remaining == 0 {
+        if available == 0 {
           break;
         }
       }
@@ -331,17 +430,26 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
       self
         .id_to_entry
         .insert(syndicate.treasure.store(), relic.store())?;
+      self.updated.insert(syndicate.treasure);
     }
 
     // update burned counters
-    for (relic_id, burned) in self.burned {
+    let mut burned = self.burned.into_iter().collect::<Vec<(RelicId, Lot)>>();
+    burned.sort_unstable_by_key(|(relic_id, _)| *relic_id);
+    for (relic_id, burned) in burned {
       let mut entry = RelicEntry::load(self.id_to_entry.get(&relic_id.store())?.unwrap().value());
       entry.state.burned = entry.state.burned.checked_add(burned.n()).unwrap();
       self.id_to_entry.insert(&relic_id.store(), entry.store())?;
+      self.updated.insert(relic_id);
     }
 
     // update amounts of claimable balance
-    for (owner, amount) in self.claimable {
+    let mut claimable = self
+      .claimable
+      .into_iter()
+      .collect::<Vec<(RelicOwner, u128)>>();
+    claimable.sort_unstable();
+    for (owner, amount) in claimable {
       let current = self
         .relic_owner_to_claimable
         .get(&owner.store())?
@@ -352,9 +460,132 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
         .insert(&owner.store(), current.checked_add(amount).unwrap())?;
     }
 
+    // record the supply and pool price of every relic that changed in this block
+    let mut updated = self.updated.into_iter().collect::<Vec<RelicId>>();
+    updated.sort_unstable();
+    for relic_id in updated {
+      let entry = RelicEntry::load(self.id_to_entry.get(&relic_id.store())?.unwrap().value());
+      self
+        .relic_id_to_supply
+        .insert((relic_id.store(), self.height), entry.supply().store())?;
+      match entry
+        .pool
+        .filter(|pool| pool.base_supply > 0 && pool.quote_supply > 0)
+      {
+        Some(pool) => {
+          self
+            .relic_id_to_pool_price
+            .insert((relic_id.store(), self.height), pool.price())?;
+        }
+        None => {
+          // a relic whose pool was removed or drained has no price from here on
+          let priced = self
+            .relic_id_to_pool_price
+            .range((relic_id.store(), 0)..=(relic_id.store(), self.height))?
+            .next_back()
+            .transpose()?
+            .map_or(false, |(_, price)| price.value() != NO_POOL_PRICE);
+          if priced {
+            self
+              .relic_id_to_pool_price
+              .insert((relic_id.store(), self.height), NO_POOL_PRICE)?;
+          }
+        }
+      }
+    }
+
+    // add the fees paid in this block to the cumulative totals
+    let mut fees = self.fees.into_iter().collect::<Vec<(Relic, RelicFees)>>();
+    fees.sort_unstable_by_key(|(relic, _)| *relic);
+    let mut block_fees = RelicFees::default();
+    for (relic, paid) in fees {
+      block_fees = block_fees.add(paid);
+      let total = self
+        .relic_to_fees
+        .range((relic.n(), 0)..=(relic.n(), self.height))?
+        .next_back()
+        .transpose()?
+        .map(|(_, total)| RelicFees::load(total.value()))
+        .unwrap_or_default();
+      self
+        .relic_to_fees
+        .insert((relic.n(), self.height), total.add(paid).store())?;
+    }
+    if block_fees != RelicFees::default() {
+      let total = self
+        .height_to_fees
+        .range(0..=self.height)?
+        .next_back()
+        .transpose()?
+        .map(|(_, total)| RelicFees::load(total.value()))
+        .unwrap_or_default();
+      self
+        .height_to_fees
+        .insert(self.height, total.add(block_fees).store())?;
+    }
+
+    // record the mints and swaps of this block for leaderboards
+    let mut activity = self
+      .activity
+      .into_iter()
+      .collect::<Vec<(RelicId, RelicActivity)>>();
+    activity.sort_unstable_by_key(|(relic_id, _)| *relic_id);
+    for (relic_id, activity) in activity {
+      self
+        .height_to_relic_activity
+        .insert((self.height, relic_id.store()), activity.store())?;
+    }
+
+    for (kind, count) in self.errors {
+      self
+        .height_to_error_counts
+        .insert((self.height, kind), count)?;
+    }
+
+    // record the balance of every address whose balance changed in this block
+    let mut balance_changes = self
+      .balance_changes
+      .unwrap_or_default()
+      .into_iter()
+      .map(|((address, relic_id), change)| ((address.to_string(), relic_id), change))
+      .collect::<Vec<((String, RelicId), (Lot, Lot))>>();
+    balance_changes.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+    for ((address, relic_id), (received, spent)) in balance_changes {
+      let previous = self
+        .relic_balance_journal
+        .range(
+          (relic_id.store(), address.as_str(), 0)
+            ..=(relic_id.store(), address.as_str(), self.height),
+        )?
+        .next_back()
+        .transpose()?
+        .map(|(_, balance)| balance.value())
+        .unwrap_or_default();
+      let balance = previous
+        .saturating_add(received.n())
+        .saturating_sub(spent.n());
+      if balance == previous {
+        continue;
+      }
+      self
+        .relic_balance_journal
+        .insert((relic_id.store(), address.as_str(), self.height), balance)?;
+      self
+        .address_to_journaled_relic_id
+        .insert(address.as_str(), relic_id.store())?;
+    }
+
     Ok(())
   }
 
+  /// Emits the event for a failed operation and counts it for the block.
+  fn emit_error(&mut self, txid: Txid, operation: RelicOperation, error: RelicError) -> Result {
+    *self.errors.entry(error.kind()).or_default() += 1;
+    self
+      .event_emitter
+      .emit(txid, EventInfo::RelicError { operation, error })
+  }
+
   fn create_relic_entry(
     &mut self,
     txid: Txid,
@@ -380,6 +611,14 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     let number = self.relics;
     self.relics += 1;
 
+    search::insert_relic(
+      self.relic_prefix_to_id,
+      self.relic_number_to_id,
+      spaced_relic.relic,
+      number,
+      id,
+    )?;
+
     self
       .statistic_to_count
       .insert(&Statistic::Relics.into(), self.relics)?;
@@ -405,6 +644,7 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     };
 
     self.id_to_entry.insert(id.store(), entry.store())?;
+    self.updated.insert(id);
 
     self
       .event_emitter
@@ -494,7 +734,7 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
       // Ticker already sealed to an inscription
       return Ok(Err(RelicError::SealingAlreadyExists(spaced_relic)));
     }
-    let sealing_fee = spaced_relic.relic.sealing_fee();
+    let sealing_fee = self.index.bones_config.sealing_fee(spaced_relic.relic);
     if base_balance < sealing_fee {
       // insufficient RELIC to cover sealing fee
       return Ok(Err(RelicError::SealingInsufficientBalance(sealing_fee)));
@@ -505,6 +745,39 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     self
       .sequence_number_to_spaced_relic
       .insert(sequence_number, &spaced_relic.store())?;
+    self.pending_sealings.insert(sequence_number, ())?;
+    self.set_charm(sequence_number, Charm::Sealed)?;
+    // the owner history starts where the sealing inscription is after this block
+    let location = SatPoint::load(
+      *self
+        .sequence_number_to_satpoint
+        .get(sequence_number)?
+        .unwrap()
+        .value(),
+    );
+    let address = tx
+      .output
+      .get(usize::try_from(location.outpoint.vout).unwrap())
+      .filter(|_| location.outpoint.txid == txid)
+      .and_then(|output| {
+        self
+          .index
+          .chain
+          .address_from_script(&output.script_pubkey)
+          .ok()
+      })
+      .map(|address| address.to_string());
+    OwnerChange::record(
+      self.sequence_number_to_owner_change,
+      sequence_number,
+      &OwnerChange {
+        height: self.height,
+        txid,
+        location,
+        address,
+      },
+    )?;
+    self.fees.entry(spaced_relic.relic).or_default().sealing += sealing_fee;
     self.event_emitter.emit(
       txid,
       EventInfo::RelicSealed {
@@ -525,9 +798,10 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     // Find all inscriptions on the outputs
     let inscriptions = self.tx_inscriptions(txid, tx)?;
 
-    if inscriptions.is_empty() {
+    // the first inscription on the outputs is used as the relic thumbnail
+    let Some(thumbnail) = inscriptions.first().map(|entry| entry.id) else {
       return Ok(Err(RelicError::InscriptionMissing));
-    }
+    };
 
     // Iterate through all inscriptions to find a sealed relic
     let mut spaced_relic = None;
@@ -559,9 +833,55 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
       tx: tx_index,
     };
     self.create_relic_entry(txid, enshrining, id, spaced_relic, sequence_number)?;
+    self
+      .relic_id_to_thumbnail
+      .insert(id.store(), thumbnail.store())?;
+
+    self.pending_sealings.remove(sequence_number)?;
+    self.set_charm(sequence_number, Charm::Enshrined)?;
+    let sealed = self
+      .sequence_number_to_inscription_entry
+      .get(sequence_number)?
+      .map(|entry| InscriptionEntry::load(entry.value()).height);
+    if let Some(sealed) = sealed {
+      self.increment_statistic(Statistic::Enshrinings, 1)?;
+      self.increment_statistic(Statistic::BlocksToEnshrine, (self.height - sealed).into())?;
+    }
+
     Ok(Ok(id))
   }
 
+  fn set_charm(&mut self, sequence_number: u32, charm: Charm) -> Result {
+    let Some(entry) = self
+      .sequence_number_to_inscription_entry
+      .get(sequence_number)?
+      .map(|entry| InscriptionEntry::load(entry.value()))
+    else {
+      return Ok(());
+    };
+
+    let mut charms = entry.charms;
+    charm.set(&mut charms);
+
+    self.sequence_number_to_inscription_entry.insert(
+      sequence_number,
+      &InscriptionEntry { charms, ..entry }.store(),
+    )?;
+
+    Ok(())
+  }
+
+  fn increment_statistic(&mut self, statistic: Statistic, n: u64) -> Result {
+    let value = self
+      .statistic_to_count
+      .get(&statistic.into())?
+      .map(|x| x.value())
+      .unwrap_or(0)
+      + n;
+    self.statistic_to_count.insert(&statistic.into(), value)?;
+    Ok(())
+  }
+
   fn summon_syndicate(
     &mut self,
     txid: Txid,
@@ -613,6 +933,7 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
         self
           .id_to_entry
           .insert(&syndicate.treasure.store(), relic_entry.store())?;
+        self.updated.insert(syndicate.treasure);
       }
     }
     let syndicate_id = SyndicateId {
@@ -643,8 +964,139 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     Ok(Ok(syndicate_id))
   }
 
+  fn schedule_subsidy(
+    &mut self,
+    txid: Txid,
+    tx: &Transaction,
+    schedule: &SubsidySchedule,
+  ) -> Result<Result<(), RelicError>> {
+    let Some(relic_entry) = self.load_relic_entry(schedule.relic)? else {
+      return Ok(Err(RelicError::RelicNotFound(schedule.relic)));
+    };
+    // the Relic owner inscription must be spent in this transaction
+    let Some(owner_sequence_number) = relic_entry.owner_sequence_number else {
+      return Ok(Err(RelicError::RelicOwnerOnly));
+    };
+    if !self
+      .tx_inscriptions(txid, tx)?
+      .iter()
+      .any(|entry| entry.sequence_number == owner_sequence_number)
+    {
+      return Ok(Err(RelicError::RelicOwnerOnly));
+    }
+    // a locked subsidy has been promised to Syndicates and cannot be scheduled anymore
+    if relic_entry.state.subsidy_locked {
+      return Ok(Err(RelicError::RelicSubsidyLocked));
+    }
+    let height = u64::from(self.height);
+    // subsidy paid out before the schedule was configured counts as released
+    let released = match self
+      .relic_id_to_subsidy_schedule
+      .get(schedule.relic.store())?
+      .map(|entry| SubsidyScheduleEntry::load(entry.value()))
+    {
+      Some(previous) => {
+        // the new schedule replaces the pending release of the previous one
+        self
+          .subsidy_release_height_to_relic_id
+          .remove((previous.next_release_height(height), schedule.relic.store()))?;
+        previous.released
+      }
+      None => relic_entry.state.subsidy - relic_entry.state.subsidy_remaining,
+    };
+    let entry = SubsidyScheduleEntry {
+      start: height,
+      epoch: schedule.epoch,
+      amount: schedule.amount,
+      released,
+    };
+    self
+      .relic_id_to_subsidy_schedule
+      .insert(schedule.relic.store(), entry.store())?;
+    self.subsidy_release_height_to_relic_id.insert(
+      (entry.next_release_height(height), schedule.relic.store()),
+      (),
+    )?;
+    self.event_emitter.emit(
+      txid,
+      EventInfo::RelicSubsidyScheduled {
+        relic_id: schedule.relic,
+        amount: schedule.amount,
+        epoch: schedule.epoch,
+      },
+    )?;
+    Ok(Ok(()))
+  }
+
+  /// Releases the next part of every scheduled subsidy whose epoch ends in this block.
+  fn release_subsidies(&mut self, txid: Txid) -> Result {
+    let height = u64::from(self.height);
+    // schedules are keyed by their next release, so only those releasing now are read
+    let mut relic_ids = Vec::new();
+    for result in self
+      .subsidy_release_height_to_relic_id
+      .range((height, (0, 0))..=(height, (u64::MAX, u32::MAX)))?
+    {
+      let (key, _) = result?;
+      relic_ids.push(RelicId::load(key.value().1));
+    }
+    for relic_id in relic_ids {
+      self
+        .subsidy_release_height_to_relic_id
+        .remove((height, relic_id.store()))?;
+      let mut entry = SubsidyScheduleEntry::load(
+        self
+          .relic_id_to_subsidy_schedule
+          .get(relic_id.store())?
+          .expect("subsidy release index inconsistent")
+          .value(),
+      );
+      debug_assert!(entry.releases_at(height));
+      let relic = self
+        .load_relic_entry(relic_id)?
+        .expect("subsidy schedule index inconsistent");
+      let amount = entry
+        .amount
+        .min(relic.state.subsidy.saturating_sub(entry.released));
+      entry.released += amount;
+      if entry.released >= relic.state.subsidy {
+        // the whole subsidy has been released, the schedule is complete
+        self.relic_id_to_subsidy_schedule.remove(relic_id.store())?;
+      } else {
+        self
+          .relic_id_to_subsidy_schedule
+          .insert(relic_id.store(), entry.store())?;
+        self
+          .subsidy_release_height_to_relic_id
+          .insert((entry.next_release_height(height), relic_id.store()), ())?;
+      }
+      if amount > 0 {
+        self
+          .event_emitter
+          .emit(txid, EventInfo::RelicSubsidyReleased { relic_id, amount })?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Subsidy of the Relic that can be paid out as Syndicate rewards right now.
+  fn available_subsidy(&self, relic_id: RelicId, relic: &RelicEntry) -> Result<u128> {
+    Ok(
+      match self.relic_id_to_subsidy_schedule.get(relic_id.store())? {
+        Some(entry) => {
+          let paid = relic.state.subsidy - relic.state.subsidy_remaining;
+          SubsidyScheduleEntry::load(entry.value())
+            .released
+            .saturating_sub(paid)
+        }
+        None => relic.state.subsidy_remaining,
+      },
+    )
+  }
+
   fn encase_chest(
     &mut self,
+    tx: &Transaction,
     txid: Txid,
     syndicate_id: SyndicateId,
     balances: &RelicsBalance,
@@ -683,6 +1135,26 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
         return Ok(Err(RelicError::SyndicateIsGated));
       }
     }
+    if syndicate.allowlist {
+      // verify that an allowlist inscription, a child of the Syndicate inscription that is not a
+      // Chest, was spent by this transaction
+      let mut allowlisted = false;
+      for entry in self.tx_inscriptions(txid, tx)? {
+        if entry.sequence_number != sequence_number
+          && entry.parents.contains(&syndicate.sequence_number)
+          && self
+            .sequence_number_to_chest
+            .get(entry.sequence_number)?
+            .is_none()
+        {
+          allowlisted = true;
+          break;
+        }
+      }
+      if !allowlisted {
+        return Ok(Err(RelicError::SyndicateNotAllowlisted));
+      }
+    }
     // check balance for quota and royalty
     let mut required: HashMap<RelicId, u128> = HashMap::new();
     // note: treasure can also be RELIC
@@ -690,6 +1162,13 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     if syndicate.royalty > 0 {
       *required.entry(RELIC_ID).or_default() += syndicate.royalty;
     }
+    if let Some((id, amount)) = syndicate.holding {
+      // the holding must remain after the quota and royalty are taken
+      let taken = required.get(&id).copied().unwrap_or_default();
+      if balances.get(id) < amount.saturating_add(taken) {
+        return Ok(Err(RelicError::SyndicateHoldingInsufficient(id, amount)));
+      }
+    }
     for (id, amount) in required {
       if balances.get(id) < amount {
         return Ok(Err(RelicError::ChestInsufficientBalance(id, amount)));
@@ -701,6 +1180,8 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
       syndicate: syndicate_id,
       created_block: self.height.into(),
       amount: quota,
+      withdrawn: 0,
+      locked_until: None,
     };
     self
       .sequence_number_to_chest
@@ -709,6 +1190,9 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     self
       .syndicate_to_chest_sequence_number
       .insert(syndicate_id.store(), sequence_number)?;
+    self
+      .chest_unlock_height_to_sequence_number
+      .insert((chest.unlock_height(syndicate.lock), sequence_number), ())?;
     // update syndicate
     syndicate.chests += 1;
     self
@@ -726,12 +1210,12 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     )))
   }
 
-  fn release_chest(
-    &mut self,
+  /// the Chest on the first Inscription on the outputs, along with its Syndicate
+  fn tx_chest(
+    &self,
     txid: Txid,
     tx: &Transaction,
-  ) -> Result<Result<(RelicId, u128), RelicError>> {
-    // find the first Inscription on the outputs
+  ) -> Result<Result<(InscriptionId, ChestEntry, SyndicateEntry), RelicError>> {
     let inscriptions = self.tx_inscriptions(txid, tx)?;
     let Some(entry) = inscriptions.first() else {
       return Ok(Err(RelicError::InscriptionMissing));
@@ -743,7 +1227,7 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     else {
       return Ok(Err(RelicError::ChestNotFound));
     };
-    let Some(mut syndicate) = self
+    let Some(syndicate) = self
       .id_to_syndicate
       .get(chest.syndicate.store())?
       .map(|v| SyndicateEntry::load(v.value()))
@@ -753,7 +1237,89 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
         chest.syndicate
       );
     };
-    let unlock_height = chest.created_block + syndicate.lock.unwrap_or_default();
+    Ok(Ok((entry.id, chest, syndicate)))
+  }
+
+  fn withdraw_chest(
+    &mut self,
+    txid: Txid,
+    tx: &Transaction,
+    amount: u128,
+  ) -> Result<Result<(RelicId, u128), RelicError>> {
+    let (inscription_id, mut chest, syndicate) = match self.tx_chest(txid, tx)? {
+      Ok(chest) => chest,
+      Err(cause) => return Ok(Err(cause)),
+    };
+    let unlock_height = chest.unlock_height(syndicate.lock);
+    if unlock_height > self.height.into() {
+      return Ok(Err(RelicError::ChestLocked(unlock_height)));
+    }
+    // withdrawing everything is a release, which also destroys the Chest
+    if amount >= chest.amount {
+      return Ok(Err(RelicError::ChestWithdrawalExceedsAmount(chest.amount)));
+    }
+    chest.amount -= amount;
+    chest.withdrawn += amount;
+    self
+      .sequence_number_to_chest
+      .insert(chest.sequence_number, chest.store())?;
+    self.event_emitter.emit(
+      txid,
+      EventInfo::ChestWithdrawn {
+        syndicate_id: chest.syndicate,
+        inscription_id,
+        amount,
+      },
+    )?;
+    Ok(Ok((syndicate.treasure, amount)))
+  }
+
+  fn relock_chest(
+    &mut self,
+    txid: Txid,
+    tx: &Transaction,
+    unlock_height: u64,
+  ) -> Result<Result<(), RelicError>> {
+    let (inscription_id, mut chest, syndicate) = match self.tx_chest(txid, tx)? {
+      Ok(chest) => chest,
+      Err(cause) => return Ok(Err(cause)),
+    };
+    // a Chest can only be locked for longer, and not into the past
+    let earliest = chest.unlock_height(syndicate.lock).max(self.height.into());
+    if unlock_height <= earliest {
+      return Ok(Err(RelicError::ChestRelockHeight(earliest)));
+    }
+    self
+      .chest_unlock_height_to_sequence_number
+      .remove((chest.unlock_height(syndicate.lock), chest.sequence_number))?;
+    chest.locked_until = Some(unlock_height);
+    self
+      .sequence_number_to_chest
+      .insert(chest.sequence_number, chest.store())?;
+    self
+      .chest_unlock_height_to_sequence_number
+      .insert((unlock_height, chest.sequence_number), ())?;
+    self.event_emitter.emit(
+      txid,
+      EventInfo::ChestRelocked {
+        syndicate_id: chest.syndicate,
+        inscription_id,
+        unlock_height,
+      },
+    )?;
+    Ok(Ok(()))
+  }
+
+  fn release_chest(
+    &mut self,
+    txid: Txid,
+    tx: &Transaction,
+  ) -> Result<Result<(RelicId, u128), RelicError>> {
+    let (_, chest, mut syndicate) = match self.tx_chest(txid, tx)? {
+      Ok(chest) => chest,
+      Err(cause) => return Ok(Err(cause)),
+    };
+    let unlock_height = chest.unlock_height(syndicate.lock);
     if unlock_height > self.height.into() {
       return Ok(Err(RelicError::ChestLocked(unlock_height)));
     }
@@ -769,6 +1335,9 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     self
       .syndicate_to_chest_sequence_number
       .remove(chest.syndicate.store(), chest.sequence_number)?;
+    self
+      .chest_unlock_height_to_sequence_number
+      .remove((unlock_height, chest.sequence_number))?;
     self.event_emitter.emit(
       txid,
       EventInfo::ChestReleased {
@@ -786,7 +1355,7 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     input: RelicId,
     output: RelicId,
     input_balance: u128,
-  ) -> Result<Result<(u128, u128, Vec<(Option<RelicOwner>, u128)>), RelicError>> {
+  ) -> Result<Result<(u128, u128, Vec<(RelicId, Option<RelicOwner>, u128)>), RelicError>> {
     assert_ne!(
       input, output,
       "the parser produced an invalid Swap with input Relic == output Relic"
@@ -931,18 +1500,132 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     if satpoint.outpoint == unbound_outpoint() || satpoint.outpoint == OutPoint::null() {
       return Ok(None);
     }
+    self.get_output_owner(satpoint.outpoint).map(Some)
+  }
+
+  fn get_output_owner(&self, outpoint: OutPoint) -> Result<RelicOwner> {
     let Some(tx_info) = self
       .index
       .client
-      .get_raw_transaction_info(&satpoint.outpoint.txid)
+      .get_raw_transaction_info(&outpoint.txid)
       .into_option()?
     else {
-      panic!("can't get input transaction: {}", satpoint.outpoint.txid);
+      panic!("can't get input transaction: {}", outpoint.txid);
     };
-    let script = tx_info.vout[satpoint.outpoint.vout as usize]
+    let script = tx_info.vout[outpoint.vout as usize]
       .script_pub_key
       .script()?;
-    Ok(Some(RelicOwner(script.script_hash())))
+    Ok(RelicOwner(script.script_hash()))
+  }
+
+  /// pool shares belong to the script of the output spent by the first input
+  fn get_liquidity_provider(&self, tx: &Transaction) -> Result<Option<RelicOwner>> {
+    match tx.input.first() {
+      Some(input) if !input.previous_output.is_null() => {
+        self.get_output_owner(input.previous_output).map(Some)
+      }
+      _ => Ok(None),
+    }
+  }
+
+  fn get_liquidity_shares(&self, relic_id: RelicId, provider: RelicOwner) -> Result<u128> {
+    Ok(
+      self
+        .liquidity_provider_to_shares
+        .get((relic_id.store(), &provider.store()))?
+        .map(|shares| shares.value())
+        .unwrap_or_default(),
+    )
+  }
+
+  fn add_liquidity(
+    &mut self,
+    txid: Txid,
+    tx: &Transaction,
+    relic_id: RelicId,
+    base_amount: u128,
+    quote_amount: u128,
+  ) -> Result<Result<LiquidityDiff, RelicError>> {
+    let Some(mut entry) = self.load_relic_entry(relic_id)? else {
+      return Ok(Err(RelicError::RelicNotFound(relic_id)));
+    };
+    let Some(pool) = entry.pool.as_mut() else {
+      return Ok(Err(RelicError::SwapNotAvailable));
+    };
+    let diff = match pool.calculate_deposit(base_amount, quote_amount) {
+      Ok(diff) => diff,
+      Err(cause) => return Ok(Err(RelicError::LiquidityFailed(cause))),
+    };
+    let Some(provider) = self.get_liquidity_provider(tx)? else {
+      // only a coinbase has no input to own the shares, but it has no balance to deposit either
+      return Ok(Err(RelicError::LiquidityFailed(PoolError::DepositTooSmall)));
+    };
+    pool.apply_deposit(diff);
+    self.id_to_entry.insert(&relic_id.store(), entry.store())?;
+    self.updated.insert(relic_id);
+    let shares = self.get_liquidity_shares(relic_id, provider)? + diff.shares;
+    self
+      .liquidity_provider_to_shares
+      .insert((relic_id.store(), &provider.store()), shares)?;
+    self.event_emitter.emit(
+      txid,
+      EventInfo::LiquidityAdded {
+        relic_id,
+        base_amount: diff.base,
+        quote_amount: diff.quote,
+        shares: diff.shares,
+      },
+    )?;
+    Ok(Ok(diff))
+  }
+
+  fn remove_liquidity(
+    &mut self,
+    txid: Txid,
+    tx: &Transaction,
+    liquidity_remove: &LiquidityRemove,
+  ) -> Result<Result<LiquidityDiff, RelicError>> {
+    let relic_id = liquidity_remove.relic;
+    let Some(mut entry) = self.load_relic_entry(relic_id)? else {
+      return Ok(Err(RelicError::RelicNotFound(relic_id)));
+    };
+    let Some(pool) = entry.pool.as_mut() else {
+      return Ok(Err(RelicError::SwapNotAvailable));
+    };
+    let Some(provider) = self.get_liquidity_provider(tx)? else {
+      return Ok(Err(RelicError::PoolShareInsufficient(0)));
+    };
+    let owned = self.get_liquidity_shares(relic_id, provider)?;
+    let shares = liquidity_remove.shares.unwrap_or(owned);
+    if shares == 0 || shares > owned {
+      return Ok(Err(RelicError::PoolShareInsufficient(owned)));
+    }
+    let diff = match pool.calculate_withdrawal(shares) {
+      Ok(diff) => diff,
+      Err(cause) => return Ok(Err(RelicError::LiquidityFailed(cause))),
+    };
+    pool.apply_withdrawal(diff);
+    self.id_to_entry.insert(&relic_id.store(), entry.store())?;
+    self.updated.insert(relic_id);
+    if shares == owned {
+      self
+        .liquidity_provider_to_shares
+        .remove((relic_id.store(), &provider.store()))?;
+    } else {
+      self
+        .liquidity_provider_to_shares
+        .insert((relic_id.store(), &provider.store()), owned - shares)?;
+    }
+    self.event_emitter.emit(
+      txid,
+      EventInfo::LiquidityRemoved {
+        relic_id,
+        base_amount: diff.base,
+        quote_amount: diff.quote,
+        shares,
+      },
+    )?;
+    Ok(Ok(diff))
   }
 
   fn swap_apply(
@@ -952,9 +1635,12 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     relic_id: RelicId,
     entry: &mut RelicEntry,
     diff: BalanceDiff,
-  ) -> Result<(Option<RelicOwner>, u128)> {
+  ) -> Result<(RelicId, Option<RelicOwner>, u128)> {
     entry.pool.as_mut().unwrap().apply(diff);
+    let pool = entry.pool.unwrap();
     self.id_to_entry.insert(&relic_id.store(), entry.store())?;
+    self.updated.insert(relic_id);
+    self.fees.entry(entry.spaced_relic.relic).or_default().swaps += diff.fee;
     let owner = if diff.fee > 0 {
       if let Some(sequence_number) = entry.owner_sequence_number {
         self.get_inscription_owner(sequence_number)?
@@ -968,6 +1654,10 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
       SwapDirection::BaseToQuote => (diff.input, diff.output, diff.fee, false),
       SwapDirection::QuoteToBase => (diff.output, diff.input, diff.fee, true),
     };
+    let activity = self.activity.entry(relic_id).or_default();
+    activity.swaps += 1;
+    activity.volume = activity.volume.saturating_add(base_amount);
+    activity.fees += fee;
     self.event_emitter.emit(
       txid,
       EventInfo::RelicSwapped {
@@ -977,9 +1667,39 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
         fee,
         is_sell_order,
         is_exact_input: swap.is_exact_input,
+        base_reserve: pool.base_supply,
+        quote_reserve: pool.quote_supply,
+        price: pool.price(),
       },
     )?;
-    Ok((owner, diff.fee))
+    Ok((relic_id, owner, diff.fee))
+  }
+
+  /// add swap fees to the totals of the swapper, the owner of the first input
+  fn record_fees_paid(
+    &mut self,
+    tx: &Transaction,
+    fees: &[(RelicId, Option<RelicOwner>, u128)],
+  ) -> Result {
+    if fees.iter().all(|(_, _, fee)| *fee == 0) {
+      return Ok(());
+    }
+    let Some(payer) = self.get_liquidity_provider(tx)? else {
+      return Ok(());
+    };
+    for (relic_id, _owner, fee) in fees {
+      if *fee == 0 {
+        continue;
+      }
+      let key = (&payer.store(), relic_id.store());
+      let total = self
+        .relic_owner_to_fees_paid
+        .get(key)?
+        .map(|total| total.value())
+        .unwrap_or_default();
+      self.relic_owner_to_fees_paid.insert(key, total + fee)?;
+    }
+    Ok(())
   }
 
   /// mint base token for every burned bonestone inscription in the tx
@@ -1010,6 +1730,7 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     let amount = terms.amount.unwrap() * burned_bonestones;
 
     self.id_to_entry.insert(&RELIC_ID.store(), bone.store())?;
+    self.updated.insert(RELIC_ID);
 
     self.event_emitter.emit(
       txid,
@@ -1036,7 +1757,7 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
       return Ok(Err(RelicError::RelicNotFound(id)));
     };
 
-    let (amount, price) = match relic_entry.mintable(base_balance) {
+    let (amount, price) = match relic_entry.mintable_at(self.height.into(), Some(base_balance)) {
       Ok(result) => result,
       Err(cause) => {
         return Ok(Err(cause));
@@ -1060,13 +1781,27 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
         relic_entry.pool = Some(Pool {
           base_supply,
           quote_supply,
-          // for now the fee is always 1%
-          fee_percentage: 1,
+          fee_bps: relic_entry
+            .mint_terms
+            .unwrap()
+            .fee
+            .unwrap_or(Pool::DEFAULT_FEE_BPS),
+          // the initial liquidity is locked forever, so the pool can never be drained
+          shares: base_supply,
         })
       }
     }
 
     self.id_to_entry.insert(&id.store(), relic_entry.store())?;
+    self.updated.insert(id);
+    self
+      .fees
+      .entry(relic_entry.spaced_relic.relic)
+      .or_default()
+      .mints += price;
+    let activity = self.activity.entry(id).or_default();
+    activity.mints += 1;
+    activity.fees += price;
 
     self.event_emitter.emit(
       txid,
@@ -1094,9 +1829,32 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
     self
       .event_emitter
       .emit(txid, EventInfo::RelicClaimed { amount: amount.n() })?;
+    // claims carry no syndicate, so tell watchers of the claimer's syndicates
+    for syndicate_id in self.royalty_syndicates(owner)? {
+      self
+        .event_emitter
+        .emit(txid, EventInfo::SyndicateRoyaltiesClaimed { syndicate_id })?;
+    }
     Ok(Some(amount))
   }
 
+  /// Syndicates with a royalty whose inscription is owned by `owner`, and
+  /// whose royalties are paid to it.
+  fn royalty_syndicates(&self, owner: RelicOwner) -> Result<Vec<SyndicateId>> {
+    let mut syndicates = Vec::new();
+    for result in self.id_to_syndicate.iter()? {
+      let entry = result?;
+      let syndicate = SyndicateEntry::load(entry.1.value());
+      if syndicate.royalty == 0 {
+        continue;
+      }
+      if self.get_inscription_owner(syndicate.sequence_number)? == Some(owner) {
+        syndicates.push(SyndicateId::load(entry.0.value()));
+      }
+    }
+    Ok(syndicates)
+  }
+
   pub(crate) fn get_inscription_by_id(
     &self,
     inscription_id: InscriptionId,
@@ -1142,7 +1900,13 @@ impl<'a, 'tx, 'index, 'emitter> RelicUpdater<'a, 'tx, 'index, 'emitter> {
         }
       }
 
-      None => Ok(None),
+      None => Ok(
+        self
+          .inscription_id_to_pruned_inscription
+          .get(&inscription_id.store())?
+          .map(|inscription| serde_json::from_str(inscription.value()))
+          .transpose()?,
+      ),
     }
   }
 }
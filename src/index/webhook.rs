@@ -0,0 +1,143 @@
+use super::*;
+
+/// The entity a webhook is notified about.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Watch {
+  Address(Address),
+  #[serde(rename = "bone")]
+  Relic(SpacedRelic),
+  #[serde(rename = "syndicate_id")]
+  Syndicate(SyndicateId),
+}
+
+/// A callback registered by an operator, stored as JSON so entries written by
+/// older versions stay readable when fields are added.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Webhook {
+  pub url: String,
+  #[serde(flatten)]
+  pub watch: Watch,
+}
+
+impl Webhook {
+  /// Whether `event` touches the watched entity. `relic` is the relic the
+  /// event is denominated in, if any.
+  pub fn matches(&self, event: &Event, relic: Option<Relic>) -> bool {
+    match &self.watch {
+      Watch::Address(address) => event.address() == Some(address),
+      Watch::Relic(spaced_relic) => relic == Some(spaced_relic.relic),
+      Watch::Syndicate(id) => event.syndicate_id() == Some(*id),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn webhooks_round_trip_through_json() {
+    let webhook = Webhook {
+      url: "https://example.com/hook".into(),
+      watch: Watch::Relic("FOO•BAR".parse().unwrap()),
+    };
+
+    let json = serde_json::to_string(&webhook).unwrap();
+
+    assert_eq!(
+      json,
+      r#"{"url":"https://example.com/hook","bone":"FOO•BAR"}"#
+    );
+    assert_eq!(serde_json::from_str::<Webhook>(&json).unwrap(), webhook);
+  }
+
+  #[test]
+  fn webhooks_match_events_of_their_address_or_relic() {
+    let address = Address::from_script(
+      &Script::new_p2pkh(&bitcoin::PubkeyHash::all_zeros()),
+      Network::Regtest,
+    )
+    .unwrap();
+
+    let event = |info| Event {
+      block_height: 1,
+      event_index: 0,
+      txid: Txid::all_zeros(),
+      info,
+    };
+
+    let received = event(EventInfo::RelicReceived {
+      relic_id: RelicId { block: 1, tx: 0 },
+      amount: 1,
+      address: address.clone(),
+    });
+
+    let minted = event(EventInfo::RelicMinted {
+      relic_id: RelicId { block: 1, tx: 0 },
+      amount: 1,
+    });
+
+    let address_webhook = Webhook {
+      url: "https://example.com/hook".into(),
+      watch: Watch::Address(address),
+    };
+
+    assert!(address_webhook.matches(&received, None));
+    assert!(!address_webhook.matches(&minted, None));
+
+    let relic_webhook = Webhook {
+      url: "https://example.com/hook".into(),
+      watch: Watch::Relic("FOO•BAR".parse().unwrap()),
+    };
+
+    assert!(relic_webhook.matches(&minted, Some("FOOBAR".parse().unwrap())));
+    assert!(relic_webhook.matches(&received, Some("FOOBAR".parse().unwrap())));
+    assert!(!relic_webhook.matches(&minted, Some("FOO".parse().unwrap())));
+    assert!(!relic_webhook.matches(&minted, None));
+  }
+
+  #[test]
+  fn syndicate_webhooks_only_match_events_of_their_chests() {
+    let syndicate_id = SyndicateId { block: 2, tx: 1 };
+
+    let webhook = Webhook {
+      url: "https://example.com/hook".into(),
+      watch: Watch::Syndicate(syndicate_id),
+    };
+
+    assert_eq!(
+      serde_json::to_string(&webhook).unwrap(),
+      r#"{"url":"https://example.com/hook","syndicate_id":"2:1"}"#
+    );
+
+    let event = |info| Event {
+      block_height: 1,
+      event_index: 0,
+      txid: Txid::all_zeros(),
+      info,
+    };
+
+    assert!(webhook.matches(&event(EventInfo::ChestEncased { syndicate_id }), None));
+    assert!(webhook.matches(
+      &event(EventInfo::ChestReleased {
+        syndicate_id,
+        amount: 1,
+      }),
+      None
+    ));
+    assert!(!webhook.matches(
+      &event(EventInfo::ChestEncased {
+        syndicate_id: SyndicateId { block: 2, tx: 2 },
+      }),
+      None
+    ));
+    assert!(!webhook.matches(
+      &event(EventInfo::RelicMinted {
+        relic_id: syndicate_id,
+        amount: 1,
+      }),
+      Some("FOO".parse().unwrap())
+    ));
+  }
+}
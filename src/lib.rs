@@ -13,24 +13,11 @@
 use crate::sat_point::SatPoint;
 use {
   self::{
-    arguments::Arguments,
-    blocktime::Blocktime,
-    config::Config,
-    decimal::Decimal,
-    deserialize_from_str::DeserializeFromStr,
-    epoch::Epoch,
-    height::Height,
-    index::{Index, List},
-    inscription::Inscription,
-    inscription_id::InscriptionId,
-    media::Media,
-    options::Options,
-    outgoing::Outgoing,
-    representation::Representation,
-    sat::Sat,
-    subcommand::Subcommand,
-    tag::Tag,
-    tally::Tally,
+    arguments::Arguments, blocktime::Blocktime, bones_config::BonesConfig, config::Config,
+    decimal::Decimal, deserialize_from_str::DeserializeFromStr, epoch::Epoch, height::Height,
+    index::List, inscription::Inscription, inscription_id::InscriptionId, media::Media,
+    options::Options, outgoing::Outgoing, representation::Representation, sat::Sat,
+    subcommand::Subcommand, tag::Tag, tally::Tally,
   },
   anyhow::{anyhow, bail, ensure, Context, Error},
   bip39::Mnemonic,
@@ -62,7 +49,7 @@ use {
     fs::{self, File},
     io,
     net::{TcpListener, ToSocketAddrs},
-    ops::{Add, AddAssign, Sub},
+    ops::{Add, AddAssign, Bound, Sub},
     path::{Path, PathBuf},
     process::{self, Command},
     str::FromStr,
@@ -79,10 +66,15 @@ use {
 };
 
 pub use self::{
+  allocator::CountingAllocator,
   fee_rate::FeeRate,
+  index::{
+    event::{Event, EventInfo},
+    Index,
+  },
   object::Object,
   rarity::Rarity,
-  relics::Pile,
+  relics::{Enshrining, Keepsake, MintTerms, Pile, RelicId, SpacedRelic, Summoning, Swap},
   subcommand::wallet::transaction_builder::{Target, TransactionBuilder},
 };
 
@@ -103,8 +95,10 @@ macro_rules! tprintln {
     };
 }
 
+mod allocator;
 mod arguments;
 mod blocktime;
+mod bones_config;
 mod chain;
 mod config;
 mod decimal;
@@ -133,6 +127,7 @@ mod sat_point;
 pub mod subcommand;
 mod tag;
 mod tally;
+mod telemetry;
 mod templates;
 mod wallet;
 
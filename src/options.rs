@@ -10,12 +10,17 @@ pub(crate) struct Options {
   #[arg(long, help = "Load Dogecoin Core data dir from <DOGECOIN_DATA_DIR>.")]
   pub(crate) dogecoin_data_dir: Option<PathBuf>,
   #[arg(
-    long = "chain",
-    value_enum,
-    default_value = "mainnet",
-    help = "Use <CHAIN>."
+    long,
+    help = "Load bones protocol parameters for test chains from TOML file <BONES_CONFIG>."
+  )]
+  pub(crate) bones_config: Option<PathBuf>,
+  #[arg(long = "chain", value_enum, help = "Use <CHAIN>. [default: mainnet]")]
+  pub(crate) chain_argument: Option<Chain>,
+  #[arg(
+    long,
+    help = "Commit the index to disk every <COMMIT_INTERVAL> blocks. Lower values lose less progress when ord is killed, higher values index faster. [default: 1000]"
   )]
-  pub(crate) chain_argument: Chain,
+  pub(crate) commit_interval: Option<usize>,
   #[arg(long, help = "Load configuration from <CONFIG>.")]
   pub(crate) config: Option<PathBuf>,
   #[arg(long, help = "Load configuration from <CONFIG_DIR>.")]
@@ -48,16 +53,66 @@ pub(crate) struct Options {
   pub(crate) first_relic_syndicate_height: Option<u32>,
   #[arg(long, help = "Limit index to <HEIGHT_LIMIT> blocks.")]
   pub(crate) height_limit: Option<u32>,
+  #[arg(
+    long,
+    help = "Use a fixed clock and a fixed database write order, so indexing the same blocks always produces the same index. Intended for tests."
+  )]
+  pub(crate) deterministic: bool,
   #[arg(long, help = "Use index at <INDEX>.")]
   pub(crate) index: Option<PathBuf>,
   #[arg(long, help = "Track location of relics.")]
   pub(crate) index_relics: bool,
+  #[arg(
+    long,
+    help = "Store a hash of the relic state every `--relic-checkpoint-interval` blocks. Requires `--index-relics`."
+  )]
+  pub(crate) index_relic_checkpoints: bool,
+  #[arg(
+    long,
+    help = "Track the swap fees paid by every address in every pool. Requires `--index-relics`."
+  )]
+  pub(crate) index_relic_fees: bool,
+  #[arg(
+    long,
+    help = "Keep the bone balances of every address at every height, to look up balances and holders at past heights. Requires `--index-relics`."
+  )]
+  pub(crate) index_relic_history: bool,
   #[arg(long, help = "Track location of all satoshis.")]
   pub(crate) index_sats: bool,
+  #[arg(
+    long,
+    help = "Fetch and pre-parse blocks on <INDEX_THREADS> threads while indexing. Defaults to the number of CPUs."
+  )]
+  pub(crate) index_threads: Option<usize>,
+  #[arg(
+    long,
+    help = "Store the transactions that inscribe or move inscriptions, or that carry or move bones, in index, so `/tx/<TXID>/raw` serves them without dogecoin RPC calls."
+  )]
+  pub(crate) index_raw_transactions: bool,
   #[arg(long, help = "Store transactions in index.")]
   pub(crate) index_transactions: bool,
+  #[arg(
+    long,
+    help = "Store the script and value of every unspent output in index, so looking up UTXOs doesn't require dogecoin RPC calls."
+  )]
+  pub(crate) index_txouts: bool,
+  #[arg(
+    long,
+    help = "Discard inscription content after indexing its metadata, to save space. `/content` returns 404."
+  )]
+  pub(crate) prune_content: bool,
+  #[arg(
+    long,
+    help = "Use chain, data dir and Dogecoin Core RPC settings of <PROFILE> from the config file."
+  )]
+  pub(crate) profile: Option<String>,
   #[arg(long, short, help = "Use regtest. Equivalent to `--chain regtest`.")]
   pub(crate) regtest: bool,
+  #[arg(
+    long,
+    help = "Store relic state checkpoints every <RELIC_CHECKPOINT_INTERVAL> blocks and at every height in `--verify-checkpoints`. Hashing the relic state is expensive, so lower values index slower. [default: 1000]"
+  )]
+  pub(crate) relic_checkpoint_interval: Option<u32>,
   #[arg(long, help = "Connect to Dogecoin Core RPC at <RPC_URL>.")]
   pub(crate) rpc_url: Option<String>,
   #[arg(long, help = "Number of parallel requests to dogecoin node.")]
@@ -66,6 +121,11 @@ pub(crate) struct Options {
   pub(crate) signet: bool,
   #[arg(long, short, help = "Use testnet. Equivalent to `--chain testnet`.")]
   pub(crate) testnet: bool,
+  #[arg(
+    long,
+    help = "Abort indexing if a relic state checkpoint differs from the trusted checkpoints in <VERIFY_CHECKPOINTS>."
+  )]
+  pub(crate) verify_checkpoints: Option<PathBuf>,
   #[arg(long, default_value = "ord", help = "Use wallet named <WALLET>.")]
   pub(crate) wallet: String,
 }
@@ -79,10 +139,35 @@ impl Options {
     } else if self.testnet {
       Chain::Testnet
     } else {
-      self.chain_argument
+      self.chain_argument.unwrap_or_default()
     }
   }
 
+  /// Fill in the options set by the profile selected with `--profile` that
+  /// weren't passed on the command line.
+  pub(crate) fn with_profile(mut self) -> Result<Self> {
+    let Some(name) = &self.profile else {
+      return Ok(self);
+    };
+
+    let mut config = self.load_config()?;
+
+    let Some(profile) = config.profiles.remove(name) else {
+      bail!("profile `{name}` not found in config");
+    };
+
+    if !(self.signet || self.regtest || self.testnet) {
+      self.chain_argument = self.chain_argument.or(profile.chain);
+    }
+
+    self.data_dir = self.data_dir.or(profile.data_dir);
+    self.dogecoin_data_dir = self.dogecoin_data_dir.or(profile.dogecoin_data_dir);
+    self.cookie_file = self.cookie_file.or(profile.cookie_file);
+    self.rpc_url = self.rpc_url.or(profile.rpc_url);
+
+    Ok(self)
+  }
+
   pub(crate) fn csp_origin(&self) -> Option<String> {
     self.csp_origin.clone()
   }
@@ -99,27 +184,33 @@ impl Options {
     }
   }
 
-  pub(crate) fn first_relic_height(&self) -> u32 {
+  pub(crate) fn first_relic_height(&self, bones_config: &BonesConfig) -> u32 {
+    let first_relic_height = self
+      .first_relic_height
+      .or(bones_config.chain(self.chain()).first_relic_height);
+
     if self.chain() == Chain::Regtest {
-      self.first_relic_height.unwrap_or(0)
+      first_relic_height.unwrap_or(0)
     } else if integration_test() {
       0
     } else {
-      self
-        .first_relic_height
-        .unwrap_or_else(|| self.chain().first_relic_height())
+      first_relic_height.unwrap_or_else(|| self.chain().first_relic_height())
     }
   }
 
-  pub(crate) fn first_relic_syndicate_height(&self) -> u32 {
+  pub(crate) fn first_relic_syndicate_height(&self, bones_config: &BonesConfig) -> u32 {
+    let first_relic_syndicate_height = self.first_relic_syndicate_height.or(
+      bones_config
+        .chain(self.chain())
+        .first_relic_syndicate_height,
+    );
+
     if self.chain() == Chain::Regtest {
-      self.first_relic_syndicate_height.unwrap_or(0)
+      first_relic_syndicate_height.unwrap_or(0)
     } else if integration_test() {
       0
     } else {
-      self
-        .first_relic_syndicate_height
-        .unwrap_or_else(|| self.chain().first_relic_syndicate_height())
+      first_relic_syndicate_height.unwrap_or_else(|| self.chain().first_relic_syndicate_height())
     }
   }
 
@@ -137,6 +228,25 @@ impl Options {
     self.nr_parallel_requests.clone().unwrap_or(12)
   }
 
+  pub(crate) fn index_threads(&self) -> usize {
+    self
+      .index_threads
+      .unwrap_or_else(|| {
+        thread::available_parallelism()
+          .map(|threads| threads.get())
+          .unwrap_or(1)
+      })
+      .max(1)
+  }
+
+  pub(crate) fn commit_interval(&self) -> usize {
+    self.commit_interval.unwrap_or(1000).max(1)
+  }
+
+  pub(crate) fn relic_checkpoint_interval(&self) -> u32 {
+    self.relic_checkpoint_interval.unwrap_or(1000).max(1)
+  }
+
   pub(crate) fn cookie_file(&self) -> Result<PathBuf> {
     if let Some(cookie_file) = &self.cookie_file {
       return Ok(cookie_file.clone());
@@ -182,6 +292,13 @@ impl Options {
     }
   }
 
+  pub(crate) fn load_bones_config(&self) -> Result<BonesConfig> {
+    match &self.bones_config {
+      Some(path) => BonesConfig::load(path, self.chain()),
+      None => Ok(Default::default()),
+    }
+  }
+
   fn format_dogecoin_core_version(version: usize) -> String {
     format!(
       "{}.{}.{}.{}",
@@ -617,6 +734,7 @@ mod tests {
         .unwrap(),
       Config {
         hidden: iter::once(id).collect(),
+        ..default()
       }
     );
   }
@@ -648,7 +766,72 @@ mod tests {
       .unwrap(),
       Config {
         hidden: iter::once(id).collect(),
+        ..default()
       }
     );
   }
+
+  #[test]
+  fn profile_fills_in_options() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("ord.yaml");
+    fs::write(
+      &path,
+      "profiles:\n  test:\n    chain: testnet\n    data-dir: /var/lib/ord-test\n    rpc-url: 127.0.0.1:44555\n",
+    )
+    .unwrap();
+
+    let options = Arguments::try_parse_from([
+      "ord",
+      "--config",
+      path.to_str().unwrap(),
+      "--profile",
+      "test",
+      "index",
+    ])
+    .unwrap()
+    .options
+    .with_profile()
+    .unwrap();
+
+    assert_eq!(options.chain(), Chain::Testnet);
+    assert_eq!(options.data_dir, Some("/var/lib/ord-test".into()));
+    assert_eq!(options.rpc_url(), "127.0.0.1:44555");
+
+    let options = Arguments::try_parse_from([
+      "ord",
+      "--config",
+      path.to_str().unwrap(),
+      "--profile",
+      "test",
+      "--regtest",
+      "--rpc-url",
+      "127.0.0.1:1234",
+      "index",
+    ])
+    .unwrap()
+    .options
+    .with_profile()
+    .unwrap();
+
+    assert_eq!(options.chain(), Chain::Regtest);
+    assert_eq!(options.rpc_url(), "127.0.0.1:1234");
+
+    assert_eq!(
+      Arguments::try_parse_from([
+        "ord",
+        "--config",
+        path.to_str().unwrap(),
+        "--profile",
+        "main",
+        "index",
+      ])
+      .unwrap()
+      .options
+      .with_profile()
+      .unwrap_err()
+      .to_string(),
+      "profile `main` not found in config"
+    );
+  }
 }
@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, crate::config::Branding};
 
 #[derive(Clone)]
 pub(crate) struct PageConfig {
@@ -6,4 +6,56 @@ pub(crate) struct PageConfig {
   pub(crate) domain: Option<String>,
   pub(crate) index_sats: bool,
   pub(crate) csp_origin: Option<String>,
+  pub(crate) plain: bool,
+  pub(crate) branding: Branding,
+}
+
+impl PageConfig {
+  /// Returns `path`, carrying plain mode over to the linked page.
+  pub(crate) fn link(&self, path: &str) -> String {
+    if self.plain {
+      Self::plain_link(path)
+    } else {
+      path.into()
+    }
+  }
+
+  /// Rewrites the root-relative links in rendered `html` so that following
+  /// them stays in plain mode.
+  pub(crate) fn plain_links(html: &str) -> String {
+    lazy_static! {
+      static ref HREF: Regex = Regex::new(r#"href=(['"]?)(/[^'"\s>]*)"#).unwrap();
+    }
+
+    HREF
+      .replace_all(html, |captures: &regex::Captures| {
+        format!("href={}{}", &captures[1], Self::plain_link(&captures[2]))
+      })
+      .into_owned()
+  }
+
+  fn plain_link(path: &str) -> String {
+    if path.contains('?') {
+      format!("{path}&plain=true")
+    } else {
+      format!("{path}?plain=true")
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn plain_links() {
+    assert_eq!(
+      PageConfig::plain_links(
+        "<a href=/relic/FOO>FOO</a> <a href='/relics?page=1'>next</a> \
+         <a href=https://example.com/>example</a>"
+      ),
+      "<a href=/relic/FOO?plain=true>FOO</a> <a href='/relics?page=1&plain=true'>next</a> \
+       <a href=https://example.com/>example</a>",
+    );
+  }
 }
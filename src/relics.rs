@@ -11,10 +11,12 @@ use {
 };
 
 pub use {
-  artifact::RelicArtifact, cenotaph::RelicCenotaph, enshrining::Enshrining, enshrining::MintTerms,
-  flaw::RelicFlaw, keepsake::Keepsake, pile::Pile, pool::*, relic::Relic, relic_error::RelicError,
-  relic_id::RelicId as SyndicateId, relic_id::RelicId, spaced_relic::SpacedRelic,
-  summoning::Summoning, swap::Swap, transfer::Transfer,
+  artifact::RelicArtifact, cenotaph::RelicCenotaph, enshrining::Enshrining,
+  enshrining::EnshriningViolation, enshrining::MintTerms, flaw::RelicFlaw, keepsake::Keepsake,
+  liquidity::LiquidityAdd, liquidity::LiquidityRemove, pile::Pile, pool::*, relic::Relic,
+  relic_error::RelicError, relic_id::RelicId as SyndicateId, relic_id::RelicId,
+  spaced_relic::SpacedRelic, subsidy_schedule::SubsidySchedule, summoning::Summoning, swap::Swap,
+  transfer::Transfer,
 };
 
 pub const RELIC_ID: RelicId = RelicId { block: 1, tx: 0 };
@@ -38,12 +40,14 @@ pub mod cenotaph;
 pub mod enshrining;
 pub mod flaw;
 pub mod keepsake;
+pub mod liquidity;
 pub mod pile;
 pub mod pool;
 pub mod relic;
 pub mod relic_error;
 pub mod relic_id;
 pub mod spaced_relic;
+pub mod subsidy_schedule;
 pub mod summoning;
 pub mod swap;
 pub mod transfer;
@@ -4,3 +4,8 @@ use super::*;
 pub struct RelicCenotaph {
   pub flaw: Option<RelicFlaw>,
 }
+
+impl RelicCenotaph {
+  /// kind under which cenotaphs are counted alongside failed operations
+  pub const KIND: &'static str = "Cenotaph";
+}
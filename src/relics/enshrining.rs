@@ -29,6 +29,36 @@ pub struct MintTerms {
   pub seed: Option<u128>,
   /// minimum block height for swaps
   pub swap_height: Option<u64>,
+  /// swap fee of the liquidity pool in basis points, between `Pool::MIN_FEE_BPS` and
+  /// `Pool::MAX_FEE_BPS`
+  /// note: defaults to `Pool::DEFAULT_FEE_BPS` if omitted
+  pub fee: Option<u16>,
+}
+
+/// A rule that sealing a ticker and enshrining it would break.
+#[derive(Serialize, Debug, PartialEq, Copy, Clone, Eq)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum EnshriningViolation {
+  /// the ticker of the base token can't be sealed
+  ReservedName,
+  /// the ticker is sealed to another inscription
+  AlreadySealed,
+  AlreadyEnshrined,
+  /// mint terms were given, but the cap is zero
+  MintCapZero,
+  /// the pool fee is outside of `Pool::MIN_FEE_BPS..=Pool::MAX_FEE_BPS`
+  FeeOutOfRange {
+    fee: u16,
+  },
+  /// subsidy, seed and minted supply together overflow
+  SupplyOverflow,
+  /// cap times price overflows
+  MintValueOverflow,
+  /// the inputs don't hold enough base tokens to pay the sealing fee
+  InsufficientSealingFee {
+    fee: u128,
+    balance: u128,
+  },
 }
 
 impl Enshrining {
@@ -36,6 +66,33 @@ impl Enshrining {
   pub const DIVISIBILITY: u8 = 8;
   pub const MAX_SPACERS: u32 = 0b00000111_11111111_11111111_11111111;
 
+  /// Violations of these terms that make a keepsake carrying them a cenotaph.
+  pub fn violations(&self) -> Vec<EnshriningViolation> {
+    let mut violations = Vec::new();
+
+    if let Some(terms) = self.mint_terms {
+      if terms.cap.unwrap_or_default() == 0 {
+        violations.push(EnshriningViolation::MintCapZero);
+      }
+
+      if let Some(fee) = terms.fee {
+        if !(Pool::MIN_FEE_BPS..=Pool::MAX_FEE_BPS).contains(&fee) {
+          violations.push(EnshriningViolation::FeeOutOfRange { fee });
+        }
+      }
+    }
+
+    if self.max_supply().is_none() {
+      violations.push(EnshriningViolation::SupplyOverflow);
+    }
+
+    if self.total_mint_value().is_none() {
+      violations.push(EnshriningViolation::MintValueOverflow);
+    }
+
+    violations
+  }
+
   pub fn max_supply(&self) -> Option<u128> {
     let subsidy = self.subsidy.unwrap_or_default();
     let amount = self
@@ -67,3 +124,43 @@ impl Enshrining {
     cap.checked_mul(price)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn violations() {
+    assert_eq!(Enshrining::default().violations(), Vec::new());
+
+    assert_eq!(
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(u128::MAX),
+          cap: Some(2),
+          price: Some(u128::MAX),
+          fee: Some(Pool::MAX_FEE_BPS + 1),
+          ..default()
+        }),
+        ..default()
+      }
+      .violations(),
+      vec![
+        EnshriningViolation::FeeOutOfRange {
+          fee: Pool::MAX_FEE_BPS + 1
+        },
+        EnshriningViolation::SupplyOverflow,
+        EnshriningViolation::MintValueOverflow,
+      ]
+    );
+
+    assert_eq!(
+      Enshrining {
+        mint_terms: Some(MintTerms::default()),
+        ..default()
+      }
+      .violations(),
+      vec![EnshriningViolation::MintCapZero]
+    );
+  }
+}
@@ -6,7 +6,9 @@ pub enum RelicFlaw {
   EnshriningAndSummoning,
   InvalidEnshrining,
   InvalidBaseTokenMint,
+  InvalidLiquidity,
   InvalidScript,
+  InvalidSubsidySchedule,
   InvalidSwap,
   Opcode,
   TrailingIntegers,
@@ -29,7 +31,15 @@ impl Display for RelicFlaw {
         f,
         "invalid mint: to mint the base token eligible inscriptions must be burned"
       ),
+      Self::InvalidLiquidity => write!(
+        f,
+        "invalid liquidity operation: a Relic other than the base token is required"
+      ),
       Self::InvalidScript => write!(f, "invalid script in OP_RETURN"),
+      Self::InvalidSubsidySchedule => write!(
+        f,
+        "invalid subsidy schedule: a Relic, an amount and an epoch are required"
+      ),
       Self::InvalidSwap => write!(f, "invalid swap: input and output cannot be the same Relic"),
       Self::Opcode => write!(f, "non-pushdata opcode in OP_RETURN"),
       Self::TrailingIntegers => write!(f, "trailing integers in body"),
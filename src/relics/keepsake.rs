@@ -7,6 +7,7 @@ mod tag;
 
 /// Relic protocol message
 #[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(default)]
 pub struct Keepsake {
   /// allocation of Relics to outputs
   pub transfers: Vec<Transfer>,
@@ -30,6 +31,16 @@ pub struct Keepsake {
   pub encasing: Option<SyndicateId>,
   /// release a Chest
   pub release: bool,
+  /// withdraw part of the Relics of an unlocked Chest, keeping the Chest
+  pub withdraw: Option<u128>,
+  /// lock a Chest until the given block, which must be after its current unlock height
+  pub relock: Option<u64>,
+  /// deposit base and quote tokens into a liquidity pool in exchange for pool shares
+  pub liquidity_add: Option<LiquidityAdd>,
+  /// redeem pool shares for base and quote tokens of a liquidity pool
+  pub liquidity_remove: Option<LiquidityRemove>,
+  /// release the subsidy of a Relic on a schedule
+  pub subsidy_schedule: Option<SubsidySchedule>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -41,6 +52,9 @@ impl Keepsake {
   /// Runes use 13, Relics use 14
   pub const MAGIC_NUMBER: opcodes::All = opcodes::all::OP_PUSHNUM_14;
   pub const COMMIT_CONFIRMATIONS: u16 = 6;
+  /// Largest OP_RETURN script Dogecoin Core relays by default: 80 bytes of
+  /// data, the OP_RETURN and up to two bytes of push opcodes.
+  pub const MAX_STANDARD_SCRIPT_SIZE: usize = 83;
 
   pub fn decipher(transaction: &Transaction) -> Option<RelicArtifact> {
     let payload = match Keepsake::payload(transaction) {
@@ -98,6 +112,7 @@ impl Keepsake {
         price: Tag::Price.take(&mut fields, |[price]| Some(price)),
         seed: get_non_zero(Tag::Seed, &mut fields),
         swap_height: Tag::SwapHeight.take(&mut fields, |[height]| u64::try_from(height).ok()),
+        fee: Tag::Fee.take(&mut fields, |[fee]| u16::try_from(fee).ok()),
       }),
       turbo: Flag::Turbo.take(&mut flags),
     });
@@ -128,6 +143,13 @@ impl Keepsake {
       quota: Tag::Quota.take(&mut fields, |[quota]| Some(quota)),
       royalty: Tag::Royalty.take(&mut fields, |[royalty]| Some(royalty)),
       gated: Flag::Gated.take(&mut flags),
+      allowlist: Flag::Allowlist.take(&mut flags),
+      holding: Tag::Holding.take(&mut fields, |[block, tx, amount]| {
+        Some((
+          RelicId::new(block.try_into().ok()?, tx.try_into().ok()?)?,
+          amount,
+        ))
+      }),
       lock: Tag::Lock.take(&mut fields, |[lock]| u64::try_from(lock).ok()),
       reward: Tag::Reward.take(&mut fields, |[reward]| Some(reward)),
       lock_subsidy: Flag::LockSubsidy.take(&mut flags),
@@ -135,6 +157,63 @@ impl Keepsake {
     });
 
     let encasing = get_relic_id(Tag::Syndicate, &mut fields);
+    let withdraw = get_non_zero(Tag::Withdraw, &mut fields);
+    let relock = Tag::Relock.take(&mut fields, |[height]| u64::try_from(height).ok());
+
+    // the Relic of a liquidity operation is mandatory and the base token has no pool
+    let mut invalid_liquidity = false;
+
+    let liquidity_add = Flag::LiquidityAdd.take(&mut flags).then(|| {
+      let relic = get_relic_id(Tag::LiquidityAddRelic, &mut fields);
+      let base_amount = get_non_zero(Tag::LiquidityBaseAmount, &mut fields);
+      let quote_amount = get_non_zero(Tag::LiquidityQuoteAmount, &mut fields);
+      match relic {
+        Some(relic) if relic != RELIC_ID => Some(LiquidityAdd {
+          relic,
+          base_amount,
+          quote_amount,
+        }),
+        _ => {
+          invalid_liquidity = true;
+          None
+        }
+      }
+    });
+
+    let liquidity_remove = Flag::LiquidityRemove.take(&mut flags).then(|| {
+      let relic = get_relic_id(Tag::LiquidityRemoveRelic, &mut fields);
+      let shares = get_non_zero(Tag::LiquidityShares, &mut fields);
+      match relic {
+        Some(relic) if relic != RELIC_ID => Some(LiquidityRemove { relic, shares }),
+        _ => {
+          invalid_liquidity = true;
+          None
+        }
+      }
+    });
+
+    // the Relic, amount and epoch of a subsidy schedule are mandatory
+    let mut invalid_subsidy_schedule = false;
+
+    let subsidy_schedule = Flag::SubsidySchedule.take(&mut flags).then(|| {
+      let relic = get_relic_id(Tag::SubsidyRelic, &mut fields);
+      let amount = get_non_zero(Tag::SubsidyAmount, &mut fields);
+      let epoch = Tag::SubsidyEpoch.take(&mut fields, |[epoch]| {
+        u64::try_from(epoch).ok().filter(|epoch| *epoch > 0)
+      });
+      match (relic, amount, epoch) {
+        (Some(relic), Some(amount), Some(epoch)) => Some(SubsidySchedule {
+          relic,
+          amount,
+          epoch,
+        }),
+        _ => {
+          invalid_subsidy_schedule = true;
+          None
+        }
+      }
+    });
+
     let pointer = get_output_option(Tag::Pointer, &mut fields);
     let claim = get_output_option(Tag::Claim, &mut fields);
 
@@ -143,19 +222,9 @@ impl Keepsake {
       flaw.get_or_insert(RelicFlaw::EnshriningAndSummoning);
     }
 
-    // check for overflows or if mint terms are given, but the cap is zero
-    if enshrining
-      .map(|enshrining| {
-        let invalid_mint_cap = enshrining
-          .mint_terms
-          .map(|terms| terms.cap.unwrap_or_default() == 0)
-          .unwrap_or_default();
-        invalid_mint_cap
-          || enshrining.max_supply().is_none()
-          || enshrining.total_mint_value().is_none()
-      })
-      .unwrap_or_default()
-    {
+    // check for overflows, if mint terms are given, but the cap is zero,
+    // or if the pool fee is outside of the allowed tiers
+    if enshrining.map_or(false, |enshrining| !enshrining.violations().is_empty()) {
       flaw.get_or_insert(RelicFlaw::InvalidEnshrining);
     }
 
@@ -173,6 +242,14 @@ impl Keepsake {
       flaw.get_or_insert(RelicFlaw::InvalidSwap);
     }
 
+    if invalid_liquidity {
+      flaw.get_or_insert(RelicFlaw::InvalidLiquidity);
+    }
+
+    if invalid_subsidy_schedule {
+      flaw.get_or_insert(RelicFlaw::InvalidSubsidySchedule);
+    }
+
     if flags != 0 {
       flaw.get_or_insert(RelicFlaw::UnrecognizedFlag);
     }
@@ -196,6 +273,11 @@ impl Keepsake {
       summoning,
       encasing,
       release,
+      withdraw,
+      relock,
+      liquidity_add: liquidity_add.flatten(),
+      liquidity_remove: liquidity_remove.flatten(),
+      subsidy_schedule: subsidy_schedule.flatten(),
     }))
   }
 
@@ -228,6 +310,7 @@ impl Keepsake {
         Tag::Price.encode_option(terms.price, &mut payload);
         Tag::Seed.encode_option(terms.seed, &mut payload);
         Tag::SwapHeight.encode_option(terms.swap_height, &mut payload);
+        Tag::Fee.encode_option(terms.fee, &mut payload);
       }
     }
 
@@ -259,6 +342,10 @@ impl Keepsake {
         Flag::Gated.set(&mut flags);
       }
 
+      if summoning.allowlist {
+        Flag::Allowlist.set(&mut flags);
+      }
+
       if summoning.lock_subsidy {
         Flag::LockSubsidy.set(&mut flags);
       }
@@ -277,12 +364,41 @@ impl Keepsake {
       Tag::Quota.encode_option(summoning.quota, &mut payload);
       Tag::Royalty.encode_option(summoning.royalty, &mut payload);
       Tag::Reward.encode_option(summoning.reward, &mut payload);
+      if let Some((RelicId { block, tx }, amount)) = summoning.holding {
+        Tag::Holding.encode([block.into(), tx.into(), amount], &mut payload);
+      }
     }
 
     if let Some(SyndicateId { block, tx }) = self.encasing {
       Tag::Syndicate.encode([block.into(), tx.into()], &mut payload);
     }
 
+    Tag::Withdraw.encode_option(self.withdraw, &mut payload);
+    Tag::Relock.encode_option(self.relock, &mut payload);
+
+    if let Some(liquidity_add) = self.liquidity_add {
+      Flag::LiquidityAdd.set(&mut flags);
+      let RelicId { block, tx } = liquidity_add.relic;
+      Tag::LiquidityAddRelic.encode([block.into(), tx.into()], &mut payload);
+      Tag::LiquidityBaseAmount.encode_option(liquidity_add.base_amount, &mut payload);
+      Tag::LiquidityQuoteAmount.encode_option(liquidity_add.quote_amount, &mut payload);
+    }
+
+    if let Some(liquidity_remove) = self.liquidity_remove {
+      Flag::LiquidityRemove.set(&mut flags);
+      let RelicId { block, tx } = liquidity_remove.relic;
+      Tag::LiquidityRemoveRelic.encode([block.into(), tx.into()], &mut payload);
+      Tag::LiquidityShares.encode_option(liquidity_remove.shares, &mut payload);
+    }
+
+    if let Some(subsidy_schedule) = self.subsidy_schedule {
+      Flag::SubsidySchedule.set(&mut flags);
+      let RelicId { block, tx } = subsidy_schedule.relic;
+      Tag::SubsidyRelic.encode([block.into(), tx.into()], &mut payload);
+      Tag::SubsidyAmount.encode([subsidy_schedule.amount], &mut payload);
+      Tag::SubsidyEpoch.encode([subsidy_schedule.epoch.into()], &mut payload);
+    }
+
     if flags != 0 {
       Tag::Flags.encode([flags], &mut payload);
     }
@@ -1246,6 +1362,7 @@ mod tests {
             price: Some(321),
             seed: Some(300),
             swap_height: Some(400_000),
+            fee: None,
           }),
           turbo: true,
         }),
@@ -1260,8 +1377,13 @@ mod tests {
         summoning: None,
         encasing: None,
         release: true,
+        withdraw: None,
+        relock: None,
         pointer: Some(0),
         claim: Some(0),
+        liquidity_add: None,
+        liquidity_remove: None,
+        subsidy_schedule: None,
       }),
     );
   }
@@ -1423,6 +1545,50 @@ mod tests {
     );
   }
 
+  #[test]
+  fn keepsakes_recognize_pool_fee_tiers() {
+    assert_eq!(
+      decipher(&[
+        Tag::Flags.into(),
+        Flag::Enshrining.mask() | Flag::MintTerms.mask(),
+        Tag::Cap.into(),
+        1,
+        Tag::Fee.into(),
+        30,
+      ]),
+      RelicArtifact::Keepsake(Keepsake {
+        enshrining: Some(Enshrining {
+          mint_terms: Some(MintTerms {
+            cap: Some(1),
+            fee: Some(30),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      }),
+    );
+  }
+
+  #[test]
+  fn pool_fee_outside_of_tiers_is_cenotaph() {
+    for fee in [Pool::MIN_FEE_BPS - 1, Pool::MAX_FEE_BPS + 1] {
+      assert_eq!(
+        decipher(&[
+          Tag::Flags.into(),
+          Flag::Enshrining.mask() | Flag::MintTerms.mask(),
+          Tag::Cap.into(),
+          1,
+          Tag::Fee.into(),
+          fee.into(),
+        ]),
+        RelicArtifact::Cenotaph(RelicCenotaph {
+          flaw: Some(RelicFlaw::InvalidEnshrining),
+        }),
+      );
+    }
+  }
+
   #[test]
   fn keepsake_with_enshrining_and_summoning_is_cenotaph() {
     assert_eq!(
@@ -1451,6 +1617,121 @@ mod tests {
     );
   }
 
+  #[test]
+  fn keepsakes_recognize_liquidity_operations() {
+    assert_eq!(
+      decipher(&[
+        Tag::Flags.into(),
+        Flag::LiquidityAdd.mask(),
+        Tag::LiquidityAddRelic.into(),
+        1,
+        Tag::LiquidityAddRelic.into(),
+        100,
+        Tag::LiquidityBaseAmount.into(),
+        5,
+      ]),
+      RelicArtifact::Keepsake(Keepsake {
+        liquidity_add: Some(LiquidityAdd {
+          relic: relic_id(100),
+          base_amount: Some(5),
+          quote_amount: None,
+        }),
+        ..default()
+      }),
+    );
+
+    assert_eq!(
+      decipher(&[
+        Tag::Flags.into(),
+        Flag::LiquidityRemove.mask(),
+        Tag::LiquidityRemoveRelic.into(),
+        1,
+        Tag::LiquidityRemoveRelic.into(),
+        100,
+        Tag::LiquidityShares.into(),
+        7,
+      ]),
+      RelicArtifact::Keepsake(Keepsake {
+        liquidity_remove: Some(LiquidityRemove {
+          relic: relic_id(100),
+          shares: Some(7),
+        }),
+        ..default()
+      }),
+    );
+  }
+
+  #[test]
+  fn liquidity_operation_without_relic_produces_cenotaph() {
+    assert_eq!(
+      decipher(&[Tag::Flags.into(), Flag::LiquidityAdd.mask()]),
+      RelicArtifact::Cenotaph(RelicCenotaph {
+        flaw: Some(RelicFlaw::InvalidLiquidity),
+      }),
+    );
+
+    assert_eq!(
+      decipher(&[
+        Tag::Flags.into(),
+        Flag::LiquidityRemove.mask(),
+        Tag::LiquidityRemoveRelic.into(),
+        1,
+        Tag::LiquidityRemoveRelic.into(),
+        0,
+      ]),
+      RelicArtifact::Cenotaph(RelicCenotaph {
+        flaw: Some(RelicFlaw::InvalidLiquidity),
+      }),
+    );
+  }
+
+  #[test]
+  fn keepsakes_recognize_subsidy_schedules() {
+    assert_eq!(
+      decipher(&[
+        Tag::Flags.into(),
+        Flag::SubsidySchedule.mask(),
+        Tag::SubsidyRelic.into(),
+        1,
+        Tag::SubsidyRelic.into(),
+        100,
+        Tag::SubsidyAmount.into(),
+        5,
+        Tag::SubsidyEpoch.into(),
+        10,
+      ]),
+      RelicArtifact::Keepsake(Keepsake {
+        subsidy_schedule: Some(SubsidySchedule {
+          relic: relic_id(100),
+          amount: 5,
+          epoch: 10,
+        }),
+        ..default()
+      }),
+    );
+  }
+
+  #[test]
+  fn incomplete_subsidy_schedule_produces_cenotaph() {
+    assert_eq!(
+      decipher(&[
+        Tag::Flags.into(),
+        Flag::SubsidySchedule.mask(),
+        Tag::SubsidyRelic.into(),
+        1,
+        Tag::SubsidyRelic.into(),
+        100,
+        Tag::SubsidyAmount.into(),
+        5,
+        Tag::SubsidyEpoch.into(),
+        0,
+      ]),
+      RelicArtifact::Cenotaph(RelicCenotaph {
+        flaw: Some(RelicFlaw::InvalidSubsidySchedule),
+      }),
+    );
+  }
+
   #[test]
   fn payload_pushes_are_concatenated() {
     assert_eq!(
@@ -1610,6 +1891,7 @@ mod tests {
           price: Some(123),
           seed: Some(200),
           swap_height: Some(400_000),
+          fee: None,
         }),
         turbo: true,
       }),
@@ -1817,6 +2099,8 @@ mod tests {
       Some(Summoning {
         treasure: Some(relic_id(1)),
         gated: true,
+        allowlist: false,
+        holding: None,
         cap: Some(12312),
         lock: Some(10000),
         height: (Some(450_000), Some(550_000)),
@@ -1844,6 +2128,8 @@ mod tests {
       Some(Summoning {
         treasure: Some(relic_id(1)),
         gated: true,
+        allowlist: false,
+        holding: None,
         cap: Some(1_000_000),
         lock: Some(100_000),
         height: (Some(450_000), Some(550_000)),
@@ -1935,6 +2221,7 @@ mod tests {
             price: Some(123),
             seed: Some(200),
             swap_height: Some(400_000),
+            fee: None,
           }),
           turbo: true,
         }),
@@ -1949,8 +2236,13 @@ mod tests {
         summoning: None,
         encasing: None,
         release: false,
+        withdraw: None,
+        relock: None,
         pointer: Some(0),
         claim: Some(0),
+        liquidity_add: None,
+        liquidity_remove: None,
+        subsidy_schedule: None,
       },
       &[
         Tag::Symbol.into(),
@@ -2027,6 +2319,8 @@ mod tests {
         summoning: Some(Summoning {
           treasure: Some(relic_id(20)),
           gated: true,
+          allowlist: true,
+          holding: Some((relic_id(28), 29)),
           cap: Some(21),
           lock: Some(22),
           height: (Some(23), Some(24)),
@@ -2038,8 +2332,13 @@ mod tests {
         }),
         encasing: Some(relic_id(30)),
         release: true,
+        withdraw: Some(31),
+        relock: Some(32),
         pointer: Some(0),
         claim: Some(0),
+        liquidity_add: None,
+        liquidity_remove: None,
+        subsidy_schedule: None,
       },
       &[
         Tag::Treasure.into(),
@@ -2060,13 +2359,24 @@ mod tests {
         26,
         Tag::Reward.into(),
         27,
+        Tag::Holding.into(),
+        1,
+        Tag::Holding.into(),
+        28,
+        Tag::Holding.into(),
+        29,
         Tag::Syndicate.into(),
         1,
         Tag::Syndicate.into(),
         30,
+        Tag::Withdraw.into(),
+        31,
+        Tag::Relock.into(),
+        32,
         Tag::Flags.into(),
         Flag::Summoning.mask()
           | Flag::Gated.mask()
+          | Flag::Allowlist.mask()
           | Flag::LockSubsidy.mask()
           | Flag::Release.mask()
           | Flag::Turbo.mask(),
@@ -10,6 +10,10 @@ pub(super) enum Flag {
   LockSubsidy = 8,
   Release = 9,
   Turbo = 10,
+  LiquidityAdd = 11,
+  LiquidityRemove = 12,
+  Allowlist = 13,
+  SubsidySchedule = 14,
   #[allow(unused)]
   Cenotaph = 127,
 }
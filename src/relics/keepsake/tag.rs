@@ -13,6 +13,7 @@ pub(super) enum Tag {
   Price = 16,
   Subsidy = 18,
   SwapHeight = 22,
+  Fee = 24,
   // Mint
   Mint = 20,
   // Swap
@@ -29,8 +30,21 @@ pub(super) enum Tag {
   Quota = 50,
   Royalty = 52,
   Reward = 54,
+  Holding = 56,
   // Chest
   Syndicate = 60,
+  Withdraw = 62,
+  Relock = 64,
+  // Liquidity
+  LiquidityAddRelic = 70,
+  LiquidityBaseAmount = 72,
+  LiquidityQuoteAmount = 74,
+  LiquidityRemoveRelic = 76,
+  LiquidityShares = 78,
+  // Subsidy
+  SubsidyRelic = 80,
+  SubsidyAmount = 82,
+  SubsidyEpoch = 84,
 
   #[allow(unused)]
   Cenotaph = 126,
@@ -0,0 +1,19 @@
+use super::*;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Copy, Clone, Eq)]
+pub struct LiquidityAdd {
+  /// Relic whose liquidity pool receives the deposit
+  pub relic: RelicId,
+  /// max amount of base tokens to deposit, defaults to the full base token balance
+  pub base_amount: Option<u128>,
+  /// max amount of quote tokens to deposit, defaults to the full Relic balance
+  pub quote_amount: Option<u128>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Copy, Clone, Eq)]
+pub struct LiquidityRemove {
+  /// Relic whose liquidity pool to withdraw from
+  pub relic: RelicId,
+  /// amount of pool shares to redeem, defaults to all shares of the owner
+  pub shares: Option<u128>,
+}
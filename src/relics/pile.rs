@@ -1,6 +1,6 @@
-use super::*;
+use {super::*, serde::ser::SerializeStruct};
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+#[derive(Debug, Deserialize, PartialEq, Clone, Copy)]
 pub struct Pile {
   pub amount: u128,
   pub divisibility: u8,
@@ -32,6 +32,22 @@ impl Display for Pile {
   }
 }
 
+/// Serializes the raw fields along with a `formatted` field holding the
+/// amount as displayed, so clients don't have to apply the divisibility.
+impl Serialize for Pile {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut pile = serializer.serialize_struct("Pile", 4)?;
+    pile.serialize_field("amount", &self.amount)?;
+    pile.serialize_field("divisibility", &self.divisibility)?;
+    pile.serialize_field("symbol", &self.symbol)?;
+    pile.serialize_field("formatted", &self.to_string())?;
+    pile.end()
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -147,4 +163,21 @@ mod tests {
       "0\u{A0}$"
     );
   }
+
+  #[test]
+  fn serialize() {
+    let pile = Pile {
+      amount: 1_500_000_000,
+      divisibility: 8,
+      symbol: Some('$'),
+    };
+
+    let json = serde_json::to_string(&pile).unwrap();
+
+    assert_eq!(
+      json,
+      "{\"amount\":1500000000,\"divisibility\":8,\"symbol\":\"$\",\"formatted\":\"15\u{A0}$\"}"
+    );
+    assert_eq!(serde_json::from_str::<Pile>(&json).unwrap(), pile);
+  }
 }
@@ -40,11 +40,19 @@ pub struct BalanceDiff {
   pub fee: u128,
 }
 
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct LiquidityDiff {
+  pub base: u128,
+  pub quote: u128,
+  pub shares: u128,
+}
+
 #[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
 pub enum PoolError {
   Underflow,
   Overflow,
   Slippage,
+  DepositTooSmall,
 }
 
 impl Display for PoolError {
@@ -53,6 +61,7 @@ impl Display for PoolError {
       PoolError::Underflow => write!(f, "pool supply underflow"),
       PoolError::Overflow => write!(f, "pool supply overflow"),
       PoolError::Slippage => write!(f, "slippage over acceptable limit set by the user"),
+      PoolError::DepositTooSmall => write!(f, "deposit too small to receive any pool shares"),
     }
   }
 }
@@ -61,10 +70,20 @@ impl Display for PoolError {
 pub struct Pool {
   pub base_supply: u128,
   pub quote_supply: u128,
-  pub fee_percentage: u8,
+  /// swap fee in basis points
+  pub fee_bps: u16,
+  /// outstanding liquidity shares, the shares created together with the pool are never redeemable
+  pub shares: u128,
 }
 
 impl Pool {
+  /// fee of pools whose mint terms don't declare one, 1%
+  pub const DEFAULT_FEE_BPS: u16 = 100;
+  /// lowest fee tier a pool can declare, 0.1%
+  pub const MIN_FEE_BPS: u16 = 10;
+  /// highest fee tier a pool can declare, 5%
+  pub const MAX_FEE_BPS: u16 = 500;
+
   fn calc_output(from: u128, to: u128, input: u128) -> Result<u128, PoolError> {
     let output = to.checked_mul(input).ok_or(PoolError::Overflow)?
       / from.checked_add(input).ok_or(PoolError::Overflow)?;
@@ -90,9 +109,9 @@ impl Pool {
   fn calc_fee(&self, base_amount: u128) -> Result<u128, PoolError> {
     Ok(
       base_amount
-        .checked_mul(self.fee_percentage as u128)
+        .checked_mul(self.fee_bps.into())
         .ok_or(PoolError::Overflow)?
-        .div_ceil(100),
+        .div_ceil(10_000),
     )
   }
 
@@ -176,6 +195,80 @@ impl Pool {
     }
   }
 
+  fn mul_div(a: u128, b: u128, c: u128) -> Result<u128, PoolError> {
+    Ok(a.checked_mul(b).ok_or(PoolError::Overflow)? / c)
+  }
+
+  fn mul_div_ceil(a: u128, b: u128, c: u128) -> Result<u128, PoolError> {
+    Ok(a.checked_mul(b).ok_or(PoolError::Overflow)?.div_ceil(c))
+  }
+
+  /// deposit at most the given amounts at the current ratio of the pool,
+  /// rounding in favor of the pool
+  pub fn calculate_deposit(
+    &self,
+    max_base: u128,
+    max_quote: u128,
+  ) -> Result<LiquidityDiff, PoolError> {
+    let shares = Self::mul_div(max_base, self.shares, self.base_supply)?.min(Self::mul_div(
+      max_quote,
+      self.shares,
+      self.quote_supply,
+    )?);
+    if shares == 0 {
+      return Err(PoolError::DepositTooSmall);
+    }
+    let base = Self::mul_div_ceil(shares, self.base_supply, self.shares)?;
+    let quote = Self::mul_div_ceil(shares, self.quote_supply, self.shares)?;
+    self
+      .base_supply
+      .checked_add(base)
+      .ok_or(PoolError::Overflow)?;
+    self
+      .quote_supply
+      .checked_add(quote)
+      .ok_or(PoolError::Overflow)?;
+    self.shares.checked_add(shares).ok_or(PoolError::Overflow)?;
+    Ok(LiquidityDiff {
+      base,
+      quote,
+      shares,
+    })
+  }
+
+  /// redeem shares for their portion of both supplies, rounding in favor of the pool
+  pub fn calculate_withdrawal(&self, shares: u128) -> Result<LiquidityDiff, PoolError> {
+    if shares >= self.shares {
+      return Err(PoolError::Underflow);
+    }
+    Ok(LiquidityDiff {
+      base: Self::mul_div(shares, self.base_supply, self.shares)?,
+      quote: Self::mul_div(shares, self.quote_supply, self.shares)?,
+      shares,
+    })
+  }
+
+  pub fn apply_deposit(&mut self, diff: LiquidityDiff) {
+    self.base_supply += diff.base;
+    self.quote_supply += diff.quote;
+    self.shares += diff.shares;
+  }
+
+  pub fn apply_withdrawal(&mut self, diff: LiquidityDiff) {
+    self.base_supply -= diff.base;
+    self.quote_supply -= diff.quote;
+    self.shares -= diff.shares;
+  }
+
+  /// price of one whole quote token in base token units, rounded down
+  pub fn price(&self) -> u128 {
+    self
+      .base_supply
+      .checked_mul(10u128.pow(Enshrining::DIVISIBILITY.into()))
+      .and_then(|base| base.checked_div(self.quote_supply))
+      .unwrap_or(u128::MAX)
+  }
+
   pub fn lossy_price(&self) -> u128 {
     ((self.base_supply as f64 / self.quote_supply as f64)
       * 10f64.powf(Enshrining::DIVISIBILITY as f64)) as u128
@@ -196,10 +289,23 @@ mod tests {
   use super::*;
 
   fn new(base_supply: u128, quote_supply: u128, fee_percentage: u8) -> Pool {
+    with_fee_bps(base_supply, quote_supply, u16::from(fee_percentage) * 100)
+  }
+
+  fn with_fee_bps(base_supply: u128, quote_supply: u128, fee_bps: u16) -> Pool {
     Pool {
       base_supply,
       quote_supply,
-      fee_percentage,
+      fee_bps,
+      // swaps never change the number of shares
+      shares: 1_000_000,
+    }
+  }
+
+  fn with_shares(base_supply: u128, quote_supply: u128, shares: u128) -> Pool {
+    Pool {
+      shares,
+      ..new(base_supply, quote_supply, 1)
     }
   }
 
@@ -599,6 +705,23 @@ mod tests {
     }
   }
 
+  #[test]
+  fn fee_tiers() {
+    let cases = vec![(10, 4), (30, 11), (250, 92), (500, 183)];
+    for (fee_bps, fee) in cases {
+      let pool = with_fee_bps(444_555, 123_123, fee_bps);
+      let diff = pool
+        .execute_log(PoolSwap::Output {
+          direction: BaseToQuote,
+          output: 1000,
+          max_input: None,
+        })
+        .unwrap();
+      assert_eq!(diff.fee, fee);
+      assert_eq!(diff.input - diff.fee, 3641);
+    }
+  }
+
   #[test]
   fn slippage_too_low_output_swap_zero_fee() {
     let pool = new(100_000, 100_000, 0);
@@ -705,6 +828,7 @@ mod tests {
     fn case(base: u128, quote: u128, expected: u128) {
       let pool = new(base, quote, 1);
       assert_eq!(pool.lossy_price(), expected);
+      assert_eq!(pool.price(), expected);
     }
 
     // 1 to 1: price is 1.00000000
@@ -721,4 +845,72 @@ mod tests {
     // 1 to 1000: price is 0.00100000
     case(100, 100_000, 100000);
   }
+
+  #[test]
+  fn deposit_at_pool_ratio() {
+    let mut pool = with_shares(100_000, 50_000, 100_000);
+    let diff = pool.calculate_deposit(10_000, 10_000).unwrap();
+    assert_eq!(
+      diff,
+      LiquidityDiff {
+        base: 10_000,
+        quote: 5_000,
+        shares: 10_000,
+      }
+    );
+    pool.apply_deposit(diff);
+    assert_eq!(pool, with_shares(110_000, 55_000, 110_000));
+  }
+
+  #[test]
+  fn deposit_rounds_in_favor_of_pool() {
+    let pool = with_shares(3, 7, 3);
+    assert_eq!(
+      pool.calculate_deposit(1, 3),
+      Ok(LiquidityDiff {
+        base: 1,
+        quote: 3,
+        shares: 1,
+      })
+    );
+    assert_eq!(
+      pool.calculate_deposit(1, 2),
+      Err(PoolError::DepositTooSmall)
+    );
+  }
+
+  #[test]
+  fn withdrawal() {
+    let mut pool = with_shares(110_000, 55_000, 110_000);
+    let diff = pool.calculate_withdrawal(10_000).unwrap();
+    assert_eq!(
+      diff,
+      LiquidityDiff {
+        base: 10_000,
+        quote: 5_000,
+        shares: 10_000,
+      }
+    );
+    pool.apply_withdrawal(diff);
+    assert_eq!(pool, with_shares(100_000, 50_000, 100_000));
+  }
+
+  #[test]
+  fn pool_cannot_be_fully_withdrawn() {
+    let pool = with_shares(100_000, 50_000, 100_000);
+    assert_eq!(
+      pool.calculate_withdrawal(100_000),
+      Err(PoolError::Underflow)
+    );
+  }
+
+  #[test]
+  fn deposit_and_withdrawal_do_not_create_tokens() {
+    let mut pool = with_shares(123_457, 98_765, 111_111);
+    let deposit = pool.calculate_deposit(1_001, 1_001).unwrap();
+    pool.apply_deposit(deposit);
+    let withdrawal = pool.calculate_withdrawal(deposit.shares).unwrap();
+    assert!(withdrawal.base <= deposit.base);
+    assert!(withdrawal.quote <= deposit.quote);
+  }
 }
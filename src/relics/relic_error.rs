@@ -13,6 +13,8 @@ pub enum RelicError {
   SwapHeightNotReached(u64),
   SwapFailed(PoolError),
   SwapInsufficientBalance(u128),
+  LiquidityFailed(PoolError),
+  PoolShareInsufficient(u128),
   InscriptionMissing,
   InscriptionMetadataMissing,
   InvalidMetadata,
@@ -20,6 +22,8 @@ pub enum RelicError {
   SyndicateEnd(u64),
   SyndicateCap(u32),
   SyndicateIsGated,
+  SyndicateNotAllowlisted,
+  SyndicateHoldingInsufficient(RelicId, u128),
   SyndicateNotFound(SyndicateId),
   #[serde(rename = "BoneAlreadyEnshrined")]
   RelicAlreadyEnshrined,
@@ -32,9 +36,52 @@ pub enum RelicError {
   ChestInsufficientBalance(RelicId, u128),
   ChestNotFound,
   ChestLocked(u64),
+  ChestWithdrawalExceedsAmount(u128),
+  ChestRelockHeight(u64),
   NoClaimableBalance,
 }
 
+impl RelicError {
+  /// Name of the variant, as it is serialized.
+  pub fn kind(self) -> &'static str {
+    match self {
+      RelicError::SealingAlreadyExists(_) => "SealingAlreadyExists",
+      RelicError::SealingInsufficientBalance(_) => "SealingInsufficientBalance",
+      RelicError::SealingBaseToken => "SealingBaseToken",
+      RelicError::SealingNotFound => "SealingNotFound",
+      RelicError::Unmintable => "Unmintable",
+      RelicError::MintCap(_) => "MintCap",
+      RelicError::MintInsufficientBalance(_) => "MintInsufficientBalance",
+      RelicError::SwapNotAvailable => "SwapNotAvailable",
+      RelicError::SwapHeightNotReached(_) => "SwapHeightNotReached",
+      RelicError::SwapFailed(_) => "SwapFailed",
+      RelicError::SwapInsufficientBalance(_) => "SwapInsufficientBalance",
+      RelicError::LiquidityFailed(_) => "LiquidityFailed",
+      RelicError::PoolShareInsufficient(_) => "PoolShareInsufficient",
+      RelicError::InscriptionMissing => "InscriptionMissing",
+      RelicError::InscriptionMetadataMissing => "InscriptionMetadataMissing",
+      RelicError::InvalidMetadata => "InvalidMetadata",
+      RelicError::SyndicateStart(_) => "SyndicateStart",
+      RelicError::SyndicateEnd(_) => "SyndicateEnd",
+      RelicError::SyndicateCap(_) => "SyndicateCap",
+      RelicError::SyndicateIsGated => "SyndicateIsGated",
+      RelicError::SyndicateNotAllowlisted => "SyndicateNotAllowlisted",
+      RelicError::SyndicateHoldingInsufficient(..) => "SyndicateHoldingInsufficient",
+      RelicError::SyndicateNotFound(_) => "SyndicateNotFound",
+      RelicError::RelicAlreadyEnshrined => "BoneAlreadyEnshrined",
+      RelicError::RelicNotFound(_) => "BoneNotFound",
+      RelicError::RelicOwnerOnly => "BoneOwnerOnly",
+      RelicError::RelicSubsidyLocked => "BoneSubsidyLocked",
+      RelicError::ChestInsufficientBalance(..) => "ChestInsufficientBalance",
+      RelicError::ChestNotFound => "ChestNotFound",
+      RelicError::ChestLocked(_) => "ChestLocked",
+      RelicError::ChestWithdrawalExceedsAmount(_) => "ChestWithdrawalExceedsAmount",
+      RelicError::ChestRelockHeight(_) => "ChestRelockHeight",
+      RelicError::NoClaimableBalance => "NoClaimableBalance",
+    }
+  }
+}
+
 impl Display for RelicError {
   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
     match self {
@@ -54,6 +101,10 @@ impl Display for RelicError {
       RelicError::SwapInsufficientBalance(required) => {
         write!(f, "insufficient balance for swap {required}")
       }
+      RelicError::LiquidityFailed(cause) => write!(f, "liquidity operation failed: {cause}"),
+      RelicError::PoolShareInsufficient(shares) => {
+        write!(f, "insufficient pool shares: only {shares} owned")
+      }
       RelicError::SyndicateStart(start) => write!(f, "Syndicate opens on block {start}"),
       RelicError::SyndicateEnd(end) => write!(f, "Syndicate closed on block {end}"),
       RelicError::SyndicateCap(cap) => write!(f, "Syndicate limited to {cap} Chests"),
@@ -85,9 +136,24 @@ impl Display for RelicError {
       RelicError::ChestLocked(unlock_height) => {
         write!(f, "Chest is locked until block {unlock_height}")
       }
+      RelicError::ChestWithdrawalExceedsAmount(amount) => {
+        write!(f, "Chest holds {amount}, release it to withdraw everything")
+      }
+      RelicError::ChestRelockHeight(height) => {
+        write!(f, "Chest can only be relocked until a block after {height}")
+      }
       RelicError::SyndicateIsGated => {
         write!(f, "unable to encase Chest: Syndicate is gated to the owner")
       }
+      RelicError::SyndicateNotAllowlisted => {
+        write!(f, "unable to encase Chest: no allowlist Inscription spent")
+      }
+      RelicError::SyndicateHoldingInsufficient(id, amount) => {
+        write!(
+          f,
+          "unable to encase Chest: holding of {amount} {id} required"
+        )
+      }
       RelicError::NoClaimableBalance => {
         write!(f, "unable to claim: No claimable balance for given output")
       }
@@ -97,12 +163,19 @@ impl Display for RelicError {
 
 #[cfg(test)]
 mod tests {
-  use crate::index::event::{Event, EventInfo, RelicOperation};
-  use crate::index::relics_entry::{RelicEntry, RelicState};
-  use crate::relics::enshrining::{Enshrining, MintTerms};
+  use crate::charm::Charm;
+  use crate::index::event::{Event, EventFilter, EventInfo, EventKind, RelicOperation};
+  use crate::index::fixture::RelicFixture;
+  use crate::index::relics_entry::{ActivityMetric, RelicEntry, RelicFees, RelicState};
+  use crate::index::sealing::{EnshriningCheck, SealingStats};
+  use crate::index::subsidy_schedule_entry::SubsidyScheduleEntry;
+  use crate::index::webhook::{Watch, Webhook};
+  use crate::relics::enshrining::{Enshrining, EnshriningViolation, MintTerms};
   use crate::relics::keepsake::Keepsake;
+  use crate::relics::liquidity::{LiquidityAdd, LiquidityRemove};
   use crate::relics::pool::Pool;
   use crate::relics::relic::Relic;
+  use crate::relics::subsidy_schedule::SubsidySchedule;
   use crate::relics::summoning::Summoning;
   use crate::relics::swap::Swap;
   use crate::relics::transfer::Transfer;
@@ -130,248 +203,3228 @@ mod tests {
     let context = Context::builder().arg("--index-relics").build();
 
     context.mine_blocks(1);
-    context.relic_tx(&[], 1, Keepsake::default());
-    context.mine_blocks(1);
-    context.assert_relics([(RELIC_ID, Context::base_token_entry())], []);
+    context.relic_tx(&[], 1, Keepsake::default());
+    context.mine_blocks(1);
+    context.assert_relics([(RELIC_ID, Context::base_token_entry())], []);
+  }
+
+  #[test]
+  fn enshrining_with_no_transfers_creates_relic() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    let (_, mut entry_base) = context.mint_base_token(1, 1);
+
+    let (txid, id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(1000),
+          cap: Some(1),
+          price: Some(1),
+          seed: Some(1000),
+          swap_height: None,
+          fee: None,
+        }),
+        ..default()
+      },
+    );
+
+    // sealing fee is burned
+    entry_base.state.burned += 100000000;
+
+    context.assert_relics(
+      [
+        (RELIC_ID, entry_base),
+        (
+          id,
+          RelicEntry {
+            block: id.block,
+            enshrining: txid,
+            number: 1,
+            spaced_relic: SpacedRelic {
+              relic: Relic(RELIC),
+              spacers: 0,
+            },
+            timestamp: id.block,
+            owner_sequence_number: Some(0),
+            mint_terms: Some(MintTerms {
+              amount: Some(1000),
+              cap: Some(1),
+              price: Some(1),
+              seed: Some(1000),
+              swap_height: None,
+              fee: None,
+            }),
+            ..default()
+          },
+        ),
+      ],
+      // all base tokens were spent on the sealing fee
+      [],
+    );
+  }
+
+  #[test]
+  fn enshrining_records_thumbnail() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let (txid, id) = context.enshrine(SpacedRelic::new(Relic(RELIC), 0), Enshrining::default());
+
+    assert_eq!(
+      context.index.get_relic_thumbnail(id).unwrap(),
+      Some(InscriptionId { txid, index: 0 })
+    );
+    assert_eq!(context.index.get_relic_thumbnail(RELIC_ID).unwrap(), None);
+  }
+
+  #[test]
+  fn enshrinings_can_be_checked_before_sealing() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let spaced_relic = SpacedRelic::new(Relic(RELIC), 0);
+    let inputs = context.relic_outpoints(vec![(RELIC_ID, 1)]);
+
+    assert_eq!(
+      context
+        .index
+        .check_enshrining(spaced_relic, &Enshrining::default(), Some(&inputs))
+        .unwrap(),
+      EnshriningCheck {
+        spaced_relic,
+        sealing_fee: 100000000,
+        balance: Some(100000000),
+        violations: Vec::new(),
+      }
+    );
+
+    assert_eq!(
+      context
+        .index
+        .check_enshrining(spaced_relic, &Enshrining::default(), Some(&[]))
+        .unwrap()
+        .violations,
+      vec![EnshriningViolation::InsufficientSealingFee {
+        fee: 100000000,
+        balance: 0,
+      }]
+    );
+
+    context.enshrine(spaced_relic, Enshrining::default());
+
+    assert_eq!(
+      context
+        .index
+        .check_enshrining(spaced_relic, &Enshrining::default(), None)
+        .unwrap()
+        .violations,
+      vec![
+        EnshriningViolation::AlreadySealed,
+        EnshriningViolation::AlreadyEnshrined,
+      ]
+    );
+
+    assert_eq!(
+      context
+        .index
+        .check_enshrining(
+          SpacedRelic::from_str(RELIC_NAME).unwrap(),
+          &Enshrining {
+            mint_terms: Some(MintTerms::default()),
+            ..default()
+          },
+          None,
+        )
+        .unwrap()
+        .violations,
+      vec![
+        EnshriningViolation::ReservedName,
+        EnshriningViolation::AlreadyEnshrined,
+        EnshriningViolation::MintCapZero,
+      ]
+    );
+  }
+
+  #[test]
+  fn outputs_are_indexed_by_script_hash() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    let (txid, _) = context.mint_base_token(1, 1);
+
+    // test transactions pay to a P2WPKH script, which has no legacy address
+    let outputs = context
+      .index
+      .get_account_outputs(crate::index::entry::script_hash(&Script::new_v0_p2wpkh(
+        &bitcoin::WPubkeyHash::all_zeros(),
+      )))
+      .unwrap();
+
+    // output 0 is the OP_RETURN of the keepsake
+    assert!(!outputs.contains(&OutPoint { txid, vout: 0 }));
+    assert!(outputs.contains(&OutPoint { txid, vout: 1 }));
+  }
+
+  #[test]
+  fn relic_events_are_paginated_in_either_order() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(3, 1);
+
+    let relic = context.index.get_relic_by_id(RELIC_ID).unwrap().unwrap();
+
+    let (mut newest_first, total) = context
+      .index
+      .events_for_relic(relic, 1000, 0, false)
+      .unwrap()
+      .unwrap();
+    assert!(total >= 3);
+    assert_eq!(u64::try_from(newest_first.len()).unwrap(), total);
+
+    let (oldest_first, _) = context
+      .index
+      .events_for_relic(relic, 1000, 0, true)
+      .unwrap()
+      .unwrap();
+    newest_first.reverse();
+    assert_eq!(oldest_first, newest_first);
+
+    for page_index in 0..3 {
+      assert_eq!(
+        context
+          .index
+          .events_for_relic(relic, 1, page_index, true)
+          .unwrap()
+          .unwrap(),
+        (vec![oldest_first[page_index].clone()], total)
+      );
+    }
+  }
+
+  #[test]
+  fn events_can_be_filtered() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let (txid, id) = context.enshrine(SpacedRelic::new(Relic(RELIC), 0), Enshrining::default());
+
+    let (events, next) = context
+      .index
+      .filter_events(
+        &EventFilter {
+          kind: Some(EventKind::RelicEnshrined),
+          ..default()
+        },
+        None,
+        10,
+      )
+      .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].txid, txid);
+    assert_eq!(events[0].info, EventInfo::RelicEnshrined { relic_id: id });
+    assert_eq!(next, None);
+
+    let (events, _) = context
+      .index
+      .filter_events(
+        &EventFilter {
+          relic_id: Some(id),
+          ..default()
+        },
+        None,
+        10,
+      )
+      .unwrap();
+    assert!(!events.is_empty());
+    assert!(events.iter().all(|event| event.relic_id() == Some(id)));
+
+    let (events, _) = context
+      .index
+      .filter_events(
+        &EventFilter {
+          txid: Some(txid),
+          ..default()
+        },
+        None,
+        10,
+      )
+      .unwrap();
+    assert_eq!(events, context.index.events_for_tx(txid).unwrap());
+
+    let (all, next) = context
+      .index
+      .filter_events(&EventFilter::default(), None, 1000)
+      .unwrap();
+    assert!(all.len() > 1);
+    assert_eq!(next, None);
+
+    let (first, next) = context
+      .index
+      .filter_events(&EventFilter::default(), None, 1)
+      .unwrap();
+    assert_eq!(first, all[..1].to_vec());
+    assert_eq!(next, Some((all[1].block_height, all[1].event_index)));
+
+    let (second, _) = context
+      .index
+      .filter_events(&EventFilter::default(), next, 1)
+      .unwrap();
+    assert_eq!(second, all[1..2].to_vec());
+  }
+
+  #[test]
+  fn checkpoints_are_stored_for_every_block() {
+    let context = Context::builder()
+      .args([
+        "--index-relics",
+        "--index-relic-checkpoints",
+        "--relic-checkpoint-interval",
+        "1",
+      ])
+      .build();
+
+    let genesis = context.index.get_relic_checkpoint(0).unwrap().unwrap();
+
+    context.mint_base_token(1, 1);
+
+    let height = context.index.block_count().unwrap() - 1;
+
+    assert_ne!(
+      context.index.get_relic_checkpoint(height).unwrap().unwrap(),
+      genesis
+    );
+    assert_eq!(
+      context.index.get_relic_checkpoint(0).unwrap(),
+      Some(genesis)
+    );
+    assert_eq!(
+      context.index.get_relic_checkpoint(height + 1).unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn checkpoints_are_stored_every_interval() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("checkpoints.txt");
+
+    let context = Context::builder()
+      .args([
+        "--index-relics",
+        "--index-relic-checkpoints",
+        "--relic-checkpoint-interval",
+        "3",
+      ])
+      .build();
+
+    context.mine_blocks(4);
+
+    let checkpoint = context.index.get_relic_checkpoint(3).unwrap().unwrap();
+
+    assert!(context.index.get_relic_checkpoint(0).unwrap().is_some());
+    assert_eq!(context.index.get_relic_checkpoint(1).unwrap(), None);
+    assert_eq!(context.index.get_relic_checkpoint(2).unwrap(), None);
+    assert_eq!(context.index.get_relic_checkpoint(4).unwrap(), None);
+
+    // trusted checkpoints are always computed, so they can be verified, and
+    // empty blocks don't change the relic state
+    fs::write(&path, format!("2 {checkpoint}")).unwrap();
+
+    let context = Context::builder()
+      .args([
+        "--index-relics",
+        "--index-relic-checkpoints",
+        "--relic-checkpoint-interval",
+        "1000",
+        "--verify-checkpoints",
+      ])
+      .arg(&path)
+      .build();
+
+    context.mine_blocks(4);
+
+    assert_eq!(
+      context.index.get_relic_checkpoint(2).unwrap(),
+      Some(checkpoint)
+    );
+    assert_eq!(context.index.get_relic_checkpoint(1).unwrap(), None);
+    assert_eq!(context.index.get_relic_checkpoint(3).unwrap(), None);
+  }
+
+  #[test]
+  #[should_panic(expected = "relic state checkpoint mismatch at height 0")]
+  fn diverging_checkpoint_aborts_indexing() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("checkpoints.txt");
+    fs::write(
+      &path,
+      format!("0 {}", bitcoin::hashes::sha256::Hash::all_zeros()),
+    )
+    .unwrap();
+
+    Context::builder()
+      .args([
+        "--index-relics",
+        "--index-relic-checkpoints",
+        "--verify-checkpoints",
+      ])
+      .arg(&path)
+      .build();
+  }
+
+  #[test]
+  fn deterministic_indexes_are_reproducible() {
+    let run = || {
+      let context = Context::builder()
+        .args(["--index-relics", "--deterministic"])
+        .build();
+
+      context.set_time(1_000);
+
+      context.mint_base_token(2, 3);
+
+      context.enshrine(SpacedRelic::new(Relic(RELIC), 0), Enshrining::default());
+
+      context.set_time(2_000);
+
+      context.relic_tx(
+        &context.relic_outpoints(vec![(RELIC_ID, 1)]),
+        3,
+        Keepsake::default(),
+      );
+
+      context.mine_blocks(1);
+
+      let (events, _) = context.index.filter_events(&default(), None, 1000).unwrap();
+
+      let timestamps = context
+        .index
+        .info()
+        .unwrap()
+        .transactions
+        .into_iter()
+        .map(|transaction| transaction.starting_timestamp)
+        .collect::<Vec<u128>>();
+
+      (context.index.relic_snapshot().unwrap(), events, timestamps)
+    };
+
+    let (snapshot, events, timestamps) = run();
+
+    assert_eq!(timestamps.first(), Some(&0));
+    assert!(timestamps.contains(&2_000));
+    assert_eq!(run(), (snapshot, events, timestamps));
+  }
+
+  #[test]
+  fn index_threads_do_not_change_the_index() {
+    let run = |threads: &str| {
+      let context = Context::builder()
+        .args([
+          "--index-relics",
+          "--deterministic",
+          "--index-threads",
+          threads,
+        ])
+        .build();
+
+      context.mint_base_token(2, 3);
+
+      context.enshrine(SpacedRelic::new(Relic(RELIC), 0), Enshrining::default());
+
+      context.relic_tx(
+        &context.relic_outpoints(vec![(RELIC_ID, 1)]),
+        3,
+        Keepsake::default(),
+      );
+
+      context.mine_blocks(5);
+
+      let (events, _) = context.index.filter_events(&default(), None, 1000).unwrap();
+
+      (
+        context.index.block_count().unwrap(),
+        context.index.relic_snapshot().unwrap(),
+        events,
+      )
+    };
+
+    assert_eq!(run("1"), run("4"));
+  }
+
+  #[test]
+  fn supply_is_recorded_for_blocks_that_change_it() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    let (_, first) = context.mint_base_token(1, 1);
+    let first_height = context.index.block_count().unwrap() - 1;
+
+    context.mine_blocks(2);
+
+    let (_, second) = context.mint_base_token(2, 1);
+    let second_height = context.index.block_count().unwrap() - 1;
+
+    let (previous, history) = context
+      .index
+      .get_relic_supply_history(RELIC_ID, 0, u32::MAX, 100)
+      .unwrap();
+
+    assert_eq!(previous, None);
+    assert_eq!(
+      history,
+      [
+        (first_height, first.supply()),
+        (
+          second_height,
+          RelicEntry {
+            state: RelicState {
+              mints: first.state.mints + second.state.mints,
+              ..second.state
+            },
+            ..second
+          }
+          .supply()
+        ),
+      ]
+    );
+
+    let (previous, history) = context
+      .index
+      .get_relic_supply_history(RELIC_ID, first_height + 1, u32::MAX, 100)
+      .unwrap();
+
+    assert_eq!(previous, Some(first.supply()));
+    assert_eq!(history.len(), 1);
+  }
+
+  #[test]
+  fn sealing_and_mint_fees_are_accounted() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(2, 1);
+
+    let (_, id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(1000),
+          cap: Some(1),
+          price: Some(5000),
+          seed: Some(1000),
+          swap_height: None,
+          fee: None,
+        }),
+        ..default()
+      },
+    );
+    let sealing_height = context.index.block_count().unwrap() - 1;
+
+    context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 5000)]),
+      1,
+      Keepsake {
+        mint: Some(id),
+        ..default()
+      },
+    );
+    context.mine_blocks(1);
+    let mint_height = context.index.block_count().unwrap() - 1;
+
+    let sealing = RelicFees {
+      sealing: Relic(RELIC).sealing_fee(),
+      ..default()
+    };
+    let total = RelicFees {
+      mints: 5000,
+      ..sealing
+    };
+
+    let relic = Some(Relic(RELIC));
+
+    assert_eq!(
+      context.index.get_relic_fees(relic, sealing_height).unwrap(),
+      sealing
+    );
+    assert_eq!(
+      context.index.get_relic_fees(relic, u32::MAX).unwrap(),
+      total
+    );
+    assert_eq!(context.index.get_relic_fees(None, u32::MAX).unwrap(), total);
+    assert_eq!(
+      context
+        .index
+        .get_relic_fees(None, sealing_height - 1)
+        .unwrap(),
+      RelicFees::default()
+    );
+
+    let (previous, history) = context
+      .index
+      .get_relic_fees_history(relic, mint_height, u32::MAX, 100)
+      .unwrap();
+
+    assert_eq!(previous, Some(sealing));
+    assert_eq!(history, [(mint_height, total)]);
+  }
+
+  #[test]
+  fn txouts_are_stored_in_the_index() {
+    let context = Context::builder().arg("--index-txouts").build();
+
+    let block = context.mine_blocks(1).remove(0);
+
+    let coinbase = &block.txdata[0];
+
+    assert_eq!(
+      context
+        .index
+        .get_txout(OutPoint {
+          txid: coinbase.txid(),
+          vout: 0,
+        })
+        .unwrap(),
+      Some(coinbase.output[0].clone()),
+    );
+  }
+
+  #[test]
+  fn spent_txouts_are_removed_from_the_index() {
+    let context = Context::builder().arg("--index-txouts").build();
+
+    let txouts = || {
+      context
+        .index
+        .table_info()
+        .unwrap()
+        .into_iter()
+        .find(|table| table.name == "OUTPOINT_TO_TXOUT")
+        .unwrap()
+        .entries
+    };
+
+    context.mine_blocks(1);
+
+    let before = txouts();
+
+    context.core.broadcast_tx(mockcore::TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..default()
+    });
+
+    let block = context.mine_blocks(1).remove(0);
+
+    let created = block
+      .txdata
+      .iter()
+      .map(|tx| tx.output.len() as u64)
+      .sum::<u64>();
+
+    assert_eq!(txouts(), before + created - 1);
+  }
+
+  #[test]
+  fn snapshot_contains_relic_state() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let (txid, id) = context.enshrine(SpacedRelic::new(Relic(RELIC), 0), Enshrining::default());
+
+    let snapshot = context.index.relic_snapshot().unwrap();
+
+    assert_eq!(snapshot.height, context.index.block_count().unwrap() - 1);
+    assert_eq!(
+      snapshot
+        .relics
+        .iter()
+        .map(|(id, _)| *id)
+        .collect::<Vec<_>>(),
+      [RELIC_ID, id]
+    );
+    assert_eq!(
+      snapshot.thumbnails,
+      [(id, InscriptionId { txid, index: 0 })]
+    );
+    assert_eq!(snapshot.sealings, [(0, SpacedRelic::new(Relic(RELIC), 0))]);
+    assert_eq!(snapshot.inscriptions.len(), 1);
+    assert_eq!(
+      snapshot.inscriptions[0].id,
+      InscriptionId { txid, index: 0 }
+    );
+    assert!(snapshot.syndicates.is_empty());
+    assert!(snapshot.chests.is_empty());
+  }
+
+  #[test]
+  fn snapshot_cannot_be_imported_into_populated_index() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let snapshot = context.index.relic_snapshot().unwrap();
+
+    assert_eq!(
+      context
+        .index
+        .import_relic_snapshot(snapshot)
+        .unwrap_err()
+        .to_string(),
+      "cannot import relic state into an index that already contains blocks"
+    );
+  }
+
+  #[test]
+  fn imported_snapshot_keeps_indexing_new_blocks() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    context.enshrine(SpacedRelic::new(Relic(RELIC), 0), Enshrining::default());
+
+    let imported = Index::open(&Options {
+      index: Some(context.tempdir.path().join("imported.redb")),
+      ..context.options.clone()
+    })
+    .unwrap();
+
+    imported
+      .import_relic_snapshot(context.index.relic_snapshot().unwrap())
+      .unwrap();
+
+    imported.update().unwrap();
+
+    assert_eq!(
+      imported.relic_snapshot().unwrap(),
+      context.index.relic_snapshot().unwrap()
+    );
+
+    // mint on top of the imported state
+    context.mint_base_token(2, 1);
+
+    imported.update().unwrap();
+
+    let expected = context.index.relic_snapshot().unwrap();
+    let actual = imported.relic_snapshot().unwrap();
+
+    assert_eq!(actual.height, expected.height);
+    assert_eq!(actual.relics, expected.relics);
+    assert_eq!(actual.balances, expected.balances);
+    assert_eq!(actual.claimable, expected.claimable);
+  }
+
+  #[test]
+  fn relic_state_passes_integrity_check() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(2, 1);
+
+    let (_, id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(1000),
+          cap: Some(1),
+          price: Some(5000),
+          seed: Some(1000),
+          swap_height: None,
+          fee: None,
+        }),
+        ..default()
+      },
+    );
+
+    context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 5000)]),
+      1,
+      Keepsake {
+        mint: Some(id),
+        ..default()
+      },
+    );
+    context.mine_blocks(1);
+
+    // swap fees are paid to the owner of the relic as claimable balance
+    context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 562)]),
+      1,
+      Keepsake {
+        swap: Some(Swap {
+          output: Some(id),
+          output_amount: Some(100),
+          input: None,
+          input_amount: Some(562),
+          is_exact_input: false,
+        }),
+        ..default()
+      },
+    );
+    context.mine_blocks(1);
+
+    let report = context.index.verify_relic_integrity().unwrap();
+
+    assert_eq!(report.violations, []);
+    assert_eq!(report.relics, 2);
+    assert_eq!(report.sealings, 1);
+    assert!(report.ok());
+  }
+
+  #[test]
+  fn burns_are_indexed_by_relic() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(2, 1);
+
+    context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(1000),
+          cap: Some(1),
+          price: Some(5000),
+          seed: Some(1000),
+          swap_height: None,
+          fee: None,
+        }),
+        ..default()
+      },
+    );
+
+    let (burns, more) = context.index.get_relic_burns(RELIC_ID, 10, 0).unwrap();
+
+    assert!(!more);
+    assert_eq!(
+      burns
+        .into_iter()
+        .map(|event| event.info)
+        .collect::<Vec<EventInfo>>(),
+      vec![EventInfo::RelicBurned {
+        relic_id: RELIC_ID,
+        // sealing fee is burned
+        amount: 100000000,
+      }]
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_relic_burns(RelicId { block: 1, tx: 9 }, 10, 0)
+        .unwrap(),
+      (Vec::new(), false)
+    );
+  }
+
+  #[test]
+  fn transfers_are_indexed_by_address() {
+    use crate::index::digest::DigestPeriod;
+
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let recipient = Address::from_script(
+      &Script::new_p2pkh(&bitcoin::PubkeyHash::all_zeros()),
+      Network::Regtest,
+    )
+    .unwrap();
+
+    context.core.broadcast_tx(mockcore::TransactionTemplate {
+      input_outpoints: &context.relic_outpoints(vec![(RELIC_ID, 572_000_000)]),
+      recipient: Some(recipient.clone()),
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let height = context.index.block_count().unwrap() - 1;
+
+    assert_eq!(
+      context
+        .index
+        .events_for_address(&recipient, 0)
+        .unwrap()
+        .into_iter()
+        .map(|event| (event.block_height, event.info))
+        .collect::<Vec<(u32, EventInfo)>>(),
+      vec![(
+        height,
+        EventInfo::RelicReceived {
+          relic_id: RELIC_ID,
+          amount: 572_000_000,
+          address: recipient.clone(),
+        }
+      )]
+    );
+
+    assert_eq!(
+      context
+        .index
+        .events_for_address(&recipient, height + 1)
+        .unwrap(),
+      Vec::new()
+    );
+
+    let digest = context
+      .index
+      .address_digest(&recipient, DigestPeriod::Daily)
+      .unwrap();
+
+    assert_eq!(digest.address, recipient.to_string());
+    assert_eq!(digest.from_height, 0);
+    assert_eq!(digest.to_height, height);
+    assert_eq!(digest.events, 1);
+    assert_eq!(
+      digest.received,
+      [(Context::base_token_entry().spaced_relic, 572_000_000)]
+        .into_iter()
+        .collect()
+    );
+    assert!(digest.spent.is_empty());
+    assert!(digest.chest_unlocks.is_empty());
+  }
+
+  #[test]
+  fn updates_are_committed_every_commit_interval() {
+    use crate::index::Statistic;
+
+    let context = Context::builder()
+      .args(["--index-relics", "--commit-interval", "1"])
+      .build();
+
+    let commits = context.index.statistic(Statistic::Commits);
+
+    context.mine_blocks(3);
+
+    // one commit per block, and a final one clearing the unclean marker
+    assert_eq!(context.index.statistic(Statistic::Commits), commits + 4);
+    assert_eq!(context.index.statistic(Statistic::Updating), 0);
+  }
+
+  #[test]
+  fn unclean_shutdown_is_detected_on_open() {
+    use crate::index::Statistic;
+
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let outpoint = context.relic_outpoints(vec![(RELIC_ID, 1)])[0];
+
+    let Context {
+      options,
+      core: _core,
+      tempdir: _tempdir,
+      index,
+    } = context;
+
+    // an index killed between batches is verified, and opens if it is intact
+    index.set_statistic(Statistic::Updating, 1);
+    drop(index);
+
+    let index = Index::open(&options).unwrap();
+
+    // inconsistent relic state without a savepoint to roll back to is refused
+    index.set_statistic(Statistic::Updating, 1);
+    index.remove_relic_balances(outpoint);
+    drop(index);
+
+    assert!(Index::open(&options)
+      .err()
+      .unwrap()
+      .to_string()
+      .contains("is inconsistent and there is no savepoint"));
+  }
+
+  #[test]
+  fn sealing_inscription_transfers_are_owner_changes() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let (txid, id) = context.enshrine(SpacedRelic::new(Relic(RELIC), 0), Enshrining::default());
+    let sealing_height = context.index.block_count().unwrap() - 1;
+
+    let transfer = context.relic_tx(&[OutPoint { txid, vout: 0 }], 1, Keepsake::default());
+    context.mine_blocks(1);
+
+    let history = context.index.get_relic_owner_history(id).unwrap().unwrap();
+
+    assert_eq!(
+      history
+        .iter()
+        .map(|change| (change.height, change.txid, change.location.outpoint))
+        .collect::<Vec<(u32, Txid, OutPoint)>>(),
+      [
+        (sealing_height, txid, OutPoint { txid, vout: 0 }),
+        (
+          sealing_height + 1,
+          transfer,
+          OutPoint {
+            txid: transfer,
+            vout: 0
+          }
+        ),
+      ]
+    );
+
+    // the base token has no owner
+    assert_eq!(
+      context.index.get_relic_owner_history(RELIC_ID).unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn base_token_is_mintable() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    let (txid, entry) = context.mint_base_token(1, 1);
+
+    context.assert_relics(
+      [(RELIC_ID, entry)],
+      [(OutPoint { txid, vout: 1 }, vec![(RELIC_ID, 572_000_000)])],
+    );
+
+    assert_eq!(entry.circulating_supply(), 572_000_000);
+  }
+
+  #[test]
+  fn quote_token_is_mintable() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    let (_, mut entry_base) = context.mint_base_token(2, 1);
+
+    let (txid_enshrine, id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(1000),
+          cap: Some(1),
+          price: Some(5000),
+          seed: Some(1000),
+          swap_height: None,
+          fee: None,
+        }),
+        ..default()
+      },
+    );
+
+    // sealing fee is burned
+    entry_base.state.burned += 100000000;
+
+    let txid_mint = context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 5000)]),
+      1,
+      Keepsake {
+        mint: Some(id),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    let mut entry_quote = RelicEntry {
+      block: id.block,
+      enshrining: txid_enshrine,
+      number: 1,
+      spaced_relic: SpacedRelic {
+        relic: Relic(RELIC),
+        spacers: 0,
+      },
+      symbol: None,
+      owner_sequence_number: Some(0),
+      mint_terms: Some(MintTerms {
+        amount: Some(1000),
+        cap: Some(1),
+        price: Some(5000),
+        seed: Some(1000),
+        swap_height: None,
+        fee: None,
+      }),
+      state: RelicState {
+        mints: 1,
+        ..default()
+      },
+      pool: Some(Pool {
+        base_supply: 5000,
+        quote_supply: 1000,
+        fee_bps: 100,
+        shares: 5000,
+      }),
+      timestamp: id.block,
+      turbo: false,
+    };
+
+    context.assert_relics(
+      [(RELIC_ID, entry_base), (id, entry_quote)],
+      [(
+        OutPoint {
+          txid: txid_mint,
+          vout: 0,
+        },
+        vec![(RELIC_ID, 99995000), (id, 1000)],
+      )],
+    );
+
+    let txid_swap = context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 560)]),
+      1,
+      Keepsake {
+        swap: Some(Swap {
+          output: Some(id),
+          output_amount: Some(100),
+          input: None,
+          // max input of base tokens
+          input_amount: Some(562),
+          is_exact_input: false,
+        }),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    // update expected balances after the swap
+    entry_quote.pool.as_mut().unwrap().base_supply += 556;
+    entry_quote.pool.as_mut().unwrap().quote_supply -= 100;
+
+    context.assert_relics(
+      [(RELIC_ID, entry_base), (id, entry_quote)],
+      [(
+        OutPoint {
+          txid: txid_swap,
+          vout: 0,
+        },
+        vec![(RELIC_ID, 99995000 - 556 - 6), (id, 1100)],
+      )],
+    );
+  }
+
+  #[test]
+  fn swap_volume_is_summed_from_height() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(2, 1);
+
+    let (_, id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(1000),
+          cap: Some(1),
+          price: Some(5000),
+          seed: Some(1000),
+          swap_height: None,
+          fee: None,
+        }),
+        ..default()
+      },
+    );
+
+    context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 5000)]),
+      1,
+      Keepsake {
+        mint: Some(id),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.get_relic_swap_volume(id, 0).unwrap(), (0, 0));
+
+    for _ in 0..2 {
+      context.relic_tx(
+        &context.relic_outpoints(vec![(RELIC_ID, 560)]),
+        1,
+        Keepsake {
+          swap: Some(Swap {
+            output: Some(id),
+            output_amount: Some(100),
+            input: None,
+            input_amount: Some(600),
+            is_exact_input: false,
+          }),
+          ..default()
+        },
+      );
+
+      context.mine_blocks(1);
+    }
+
+    let swaps = context
+      .index
+      .filter_events(
+        &EventFilter {
+          kind: Some(EventKind::RelicSwapped),
+          ..default()
+        },
+        None,
+        10,
+      )
+      .unwrap()
+      .0
+      .into_iter()
+      .map(|event| match event.info {
+        EventInfo::RelicSwapped {
+          base_amount,
+          quote_amount,
+          ..
+        } => (event.block_height, base_amount, quote_amount),
+        _ => unreachable!(),
+      })
+      .collect::<Vec<(u32, u128, u128)>>();
+
+    assert_eq!(swaps.len(), 2);
+    assert_eq!(
+      context.index.get_relic_swap_volume(id, 0).unwrap(),
+      (swaps[0].1 + swaps[1].1, swaps[0].2 + swaps[1].2)
+    );
+    assert_eq!(
+      context.index.get_relic_swap_volume(id, swaps[1].0).unwrap(),
+      (swaps[1].1, swaps[1].2)
+    );
+  }
+
+  #[test]
+  fn twap_weights_pool_prices_by_block() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(2, 1);
+
+    let (_, id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(1000),
+          cap: Some(1),
+          price: Some(5000),
+          seed: Some(1000),
+          swap_height: None,
+          fee: None,
+        }),
+        ..default()
+      },
+    );
+
+    let price = || {
+      context
+        .index
+        .get_relic_entry_by_id(id)
+        .unwrap()
+        .unwrap()
+        .pool
+        .unwrap()
+        .price()
+    };
+
+    // no pool yet
+    let before = context.index.block_count().unwrap() - 1;
+    assert_eq!(context.index.get_relic_twap(id, 0, before).unwrap(), None);
+
+    context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 5000)]),
+      1,
+      Keepsake {
+        mint: Some(id),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    let created = context.index.block_count().unwrap() - 1;
+    let initial = price();
+
+    context.mine_blocks(2);
+
+    context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 560)]),
+      1,
+      Keepsake {
+        swap: Some(Swap {
+          output: Some(id),
+          output_amount: Some(100),
+          input: None,
+          input_amount: Some(600),
+          is_exact_input: false,
+        }),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    let swapped = context.index.block_count().unwrap() - 1;
+    let after = price();
+    assert!(after > initial);
+
+    // three blocks at the initial price, two after the swap
+    assert_eq!(
+      context.index.get_relic_twap(id, 0, swapped + 1).unwrap(),
+      Some(((initial * 3 + after * 2) / 5, 5))
+    );
+
+    // the price before the window carries into it
+    assert_eq!(
+      context
+        .index
+        .get_relic_twap(id, created + 1, created + 2)
+        .unwrap(),
+      Some((initial, 2))
+    );
+    assert_eq!(
+      context.index.get_relic_twap(id, swapped, swapped).unwrap(),
+      Some((after, 1))
+    );
+  }
+
+  #[test]
+  fn leaderboards_rank_relics_by_activity_in_window() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(2, 1);
+
+    let (_, id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(1000),
+          cap: Some(1),
+          price: Some(5000),
+          seed: Some(1000),
+          swap_height: None,
+          fee: None,
+        }),
+        ..default()
+      },
+    );
+
+    context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 5000)]),
+      1,
+      Keepsake {
+        mint: Some(id),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    let minted = context.index.block_count().unwrap() - 1;
+
+    context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 560)]),
+      1,
+      Keepsake {
+        swap: Some(Swap {
+          output: Some(id),
+          output_amount: Some(100),
+          input: None,
+          input_amount: Some(600),
+          is_exact_input: false,
+        }),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    let swapped = context.index.block_count().unwrap() - 1;
+
+    let fees = context
+      .index
+      .get_relic_fees(Some(Relic(RELIC)), swapped)
+      .unwrap();
+
+    let leaderboard = |metric, from| {
+      context
+        .index
+        .get_relic_leaderboard(metric, from, swapped, 10)
+        .unwrap()
+    };
+
+    let activity = leaderboard(ActivityMetric::Mints, minted);
+    assert_eq!(activity.len(), 1);
+    assert_eq!(activity[0].0, id);
+    assert_eq!(activity[0].1.mints, 1);
+    assert_eq!(activity[0].1.swaps, 1);
+    assert!(activity[0].1.volume > 0);
+    assert_eq!(activity[0].1.fees, fees.mints + fees.swaps);
+
+    // only the swap falls into the window
+    assert_eq!(leaderboard(ActivityMetric::Mints, swapped), Vec::new());
+    assert_eq!(leaderboard(ActivityMetric::Volume, swapped).len(), 1);
+  }
+
+  #[test]
+  fn block_relic_activity_is_tallied_from_events() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(2, 1);
+
+    let (_, id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(1000),
+          cap: Some(2),
+          price: Some(5000),
+          seed: Some(1000),
+          swap_height: None,
+          fee: None,
+        }),
+        ..default()
+      },
+    );
+
+    let enshrined = u32::try_from(id.block).unwrap();
+
+    let activity = context.index.get_block_relic_activity(enshrined).unwrap();
+    assert_eq!(activity.enshrined, vec![id]);
+    assert_eq!(activity.mints, 0);
+
+    context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 5000)]),
+      1,
+      Keepsake {
+        mint: Some(id),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    let activity = context
+      .index
+      .get_block_relic_activity(enshrined + 1)
+      .unwrap();
+    assert_eq!(activity.height, enshrined + 1);
+    assert_eq!(activity.enshrined, Vec::new());
+    assert_eq!(activity.mints, 1);
+    assert_eq!(activity.swaps, 0);
+  }
+
+  /// Also records the `swap` fixture in `src/index/fixtures` when
+  /// `UPDATE_SNAPSHOTS` is set.
+  #[test]
+  fn recorded_fixtures_replay_to_the_same_state() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(2, 1);
+
+    let (_, id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(1000),
+          cap: Some(1),
+          price: Some(5000),
+          seed: Some(1000),
+          swap_height: None,
+          fee: None,
+        }),
+        ..default()
+      },
+    );
+
+    context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 5000)]),
+      1,
+      Keepsake {
+        mint: Some(id),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(3);
+
+    context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 560)]),
+      1,
+      Keepsake {
+        swap: Some(Swap {
+          output: Some(id),
+          output_amount: Some(100),
+          input: None,
+          input_amount: Some(600),
+          is_exact_input: false,
+        }),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    let fixture = serde_json::from_str::<RelicFixture>(
+      &serde_json::to_string(&context.index.relic_fixture().unwrap()).unwrap(),
+    )
+    .unwrap();
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+      let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/index/fixtures/swap");
+      fs::write(
+        path.with_extension("fixture.json"),
+        serde_json::to_vec_pretty(&fixture).unwrap(),
+      )
+      .unwrap();
+      fs::write(
+        path.with_extension("snapshot.json"),
+        serde_json::to_vec_pretty(&context.index.relic_snapshot().unwrap()).unwrap(),
+      )
+      .unwrap();
+    }
+
+    // blocks without events are replayed without their transactions
+    assert!(!fixture.blocks.is_empty());
+    assert!(fixture.blocks.len() < fixture.headers.len());
+
+    let replay = Context::builder().arg("--index-relics").build();
+    replay.replay_fixture(&fixture);
+
+    assert_eq!(
+      replay.index.relic_snapshot().unwrap(),
+      context.index.relic_snapshot().unwrap()
+    );
+  }
+
+  /// Replays every fixture in `src/index/fixtures` and compares the resulting
+  /// state with the snapshot recorded next to it. With `UPDATE_SNAPSHOTS` set,
+  /// the snapshots are rewritten instead.
+  #[test]
+  fn fixtures_match_their_snapshots() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/index/fixtures");
+
+    let mut replayed = 0;
+
+    for entry in fs::read_dir(dir).unwrap() {
+      let path = entry.unwrap().path();
+
+      let Some(name) = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_suffix(".fixture.json"))
+      else {
+        continue;
+      };
+
+      let fixture =
+        serde_json::from_str::<RelicFixture>(&fs::read_to_string(&path).unwrap()).unwrap();
+
+      let context = Context::builder()
+        .chain(fixture.chain)
+        .arg("--index-relics")
+        .build();
+      context.replay_fixture(&fixture);
+      replayed += 1;
+
+      let snapshot = context.index.relic_snapshot().unwrap();
+      let snapshot_path = path.with_file_name(format!("{name}.snapshot.json"));
+
+      if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(
+          &snapshot_path,
+          serde_json::to_vec_pretty(&snapshot).unwrap(),
+        )
+        .unwrap();
+        continue;
+      }
+
+      assert_eq!(
+        snapshot,
+        serde_json::from_str(&fs::read_to_string(&snapshot_path).unwrap()).unwrap(),
+        "state after replaying fixture `{name}` differs from its snapshot",
+      );
+    }
+
+    assert!(replayed > 0, "no fixtures found in src/index/fixtures");
+  }
+
+  #[test]
+  fn swap_routes_match_executed_swaps() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(3, 1);
+
+    let mut ids = Vec::new();
+
+    for i in 0..2 {
+      let (_, id) = context.enshrine(
+        SpacedRelic::new(Relic(RELIC + i), 0),
+        Enshrining {
+          mint_terms: Some(MintTerms {
+            amount: Some(1000),
+            cap: Some(1),
+            price: Some(5000),
+            seed: Some(1000),
+            swap_height: None,
+            fee: None,
+          }),
+          ..default()
+        },
+      );
+
+      context.relic_tx(
+        &context.relic_outpoints(vec![(RELIC_ID, 5000)]),
+        1,
+        Keepsake {
+          mint: Some(id),
+          ..default()
+        },
+      );
+
+      context.mine_blocks(1);
+
+      ids.push(id);
+    }
+
+    assert_eq!(
+      context
+        .index
+        .get_swap_route(ids[0], RELIC_ID, 0)
+        .unwrap()
+        .unwrap()
+        .len(),
+      1
+    );
+    assert_eq!(
+      context
+        .index
+        .get_swap_route(RELIC_ID, ids[1], 100)
+        .unwrap()
+        .unwrap()[0]
+        .direction,
+      SwapDirection::BaseToQuote
+    );
+    assert_eq!(
+      context
+        .index
+        .get_swap_route(ids[0], RelicId { block: 1, tx: 1 }, 100)
+        .unwrap(),
+      Err(RelicError::RelicNotFound(RelicId { block: 1, tx: 1 }))
+    );
+
+    let route = context
+      .index
+      .get_swap_route(ids[0], ids[1], 100)
+      .unwrap()
+      .unwrap();
+
+    assert_eq!(route.len(), 2);
+    assert_eq!(route[0].pool, ids[0]);
+    assert_eq!(route[0].direction, SwapDirection::QuoteToBase);
+    assert_eq!(route[0].input_amount, 100);
+    assert_eq!(route[1].pool, ids[1]);
+    assert_eq!(route[1].direction, SwapDirection::BaseToQuote);
+    assert_eq!(route[1].input_amount, route[0].output_amount);
+    assert!(route.iter().all(|hop| hop.slippage_bps > 0));
+
+    context.relic_tx(
+      &context.relic_outpoints(vec![(ids[0], 1000)]),
+      1,
+      Keepsake {
+        swap: Some(Swap {
+          input: Some(ids[0]),
+          output: Some(ids[1]),
+          input_amount: Some(100),
+          output_amount: Some(route[1].output_amount),
+          is_exact_input: true,
+        }),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    let swaps = context
+      .index
+      .filter_events(
+        &EventFilter {
+          kind: Some(EventKind::RelicSwapped),
+          ..default()
+        },
+        None,
+        10,
+      )
+      .unwrap()
+      .0
+      .into_iter()
+      .map(|event| match event.info {
+        EventInfo::RelicSwapped {
+          relic_id,
+          base_amount,
+          quote_amount,
+          ..
+        } => (relic_id, base_amount, quote_amount),
+        _ => unreachable!(),
+      })
+      .collect::<Vec<(RelicId, u128, u128)>>();
+
+    assert_eq!(
+      swaps,
+      [
+        (ids[0], route[0].output_amount, route[0].input_amount),
+        (ids[1], route[1].input_amount, route[1].output_amount),
+      ]
+    );
+  }
+
+  #[test]
+  fn balances_and_holders_are_journaled_by_height() {
+    let context = Context::builder()
+      .args(["--index-relics", "--index-relic-history"])
+      .build();
+
+    // relic transactions pay to a P2WPKH script with an all-zero hash
+    let holder = context
+      .index
+      .chain()
+      .address_from_script(&Script::new_v0_p2wpkh(&bitcoin::WPubkeyHash::all_zeros()))
+      .unwrap();
+
+    let height = || context.index.block_count().unwrap() - 1;
+
+    let before = height();
+    context.mint_base_token(1, 1);
+    let first = height();
+    context.mint_base_token(1, 1);
+    let second = height();
+
+    let balances = |height| {
+      context
+        .index
+        .get_relic_balances_at(&holder, height)
+        .unwrap()
+        .into_values()
+        .map(|pile| pile.amount)
+        .collect::<Vec<u128>>()
+    };
+
+    assert_eq!(balances(before), Vec::new());
+    assert_eq!(balances(first), vec![100000000]);
+    assert_eq!(balances(second), vec![200000000]);
+
+    assert_eq!(
+      context
+        .index
+        .get_relic_holders_at(RELIC_ID, before)
+        .unwrap(),
+      Vec::new()
+    );
+    assert_eq!(
+      context.index.get_relic_holders_at(RELIC_ID, first).unwrap(),
+      vec![(holder.to_string(), 100000000)]
+    );
+  }
+
+  #[test]
+  fn only_relevant_transactions_are_stored() {
+    let context = Context::builder()
+      .args(["--index-relics", "--index-raw-transactions"])
+      .build();
+
+    let (minted, _) = context.mint_base_token(1, 1);
+
+    let transferred = context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 100000000)]),
+      1,
+      Keepsake::default(),
+    );
+    let blocks = context.mine_blocks(1);
+
+    let stored = |txid| context.index.get_stored_transaction(txid).unwrap();
+
+    assert_eq!(
+      stored(minted),
+      Some(consensus::encode::serialize(
+        &context.index.get_transaction(minted).unwrap().unwrap()
+      ))
+    );
+    assert!(stored(transferred).is_some());
+    assert_eq!(stored(blocks[0].txdata[0].txid()), None);
+  }
+
+  #[test]
+  fn swap_fees_paid_are_tracked() {
+    let context = Context::builder()
+      .args(["--index-relics", "--index-relic-fees"])
+      .build();
+
+    context.mint_base_token(2, 1);
+
+    let (_, id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(1000),
+          cap: Some(1),
+          price: Some(5000),
+          seed: Some(1000),
+          swap_height: None,
+          fee: None,
+        }),
+        ..default()
+      },
+    );
+
+    context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 5000)]),
+      1,
+      Keepsake {
+        mint: Some(id),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    // relic transactions pay to a P2WPKH script with an all-zero hash
+    let swapper = Address::from_script(
+      &Script::new_v0_p2wpkh(&bitcoin::WPubkeyHash::all_zeros()),
+      Network::Regtest,
+    )
+    .unwrap();
+
+    assert!(context
+      .index
+      .get_relic_fees_paid(&swapper)
+      .unwrap()
+      .is_empty());
+
+    for _ in 0..2 {
+      context.relic_tx(
+        &context.relic_outpoints(vec![(RELIC_ID, 560)]),
+        1,
+        Keepsake {
+          swap: Some(Swap {
+            output: Some(id),
+            output_amount: Some(100),
+            input: None,
+            input_amount: Some(600),
+            is_exact_input: false,
+          }),
+          ..default()
+        },
+      );
+
+      context.mine_blocks(1);
+    }
+
+    let fees = context.index.get_relic_fees_paid(&swapper).unwrap();
+
+    assert_eq!(fees.len(), 1);
+    assert_eq!(fees[0].0, SpacedRelic::new(Relic(RELIC), 0));
+    assert_eq!(
+      fees[0].1,
+      context
+        .index
+        .filter_events(
+          &EventFilter {
+            kind: Some(EventKind::RelicSwapped),
+            ..default()
+          },
+          None,
+          10,
+        )
+        .unwrap()
+        .0
+        .iter()
+        .map(|event| match event.info {
+          EventInfo::RelicSwapped { fee, .. } => fee,
+          _ => unreachable!(),
+        })
+        .sum::<u128>()
+    );
+    assert!(fees[0].1 > 0);
+  }
+
+  #[test]
+  fn liquidity_can_be_added_and_removed() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    let (_, mut entry_base) = context.mint_base_token(2, 1);
+
+    let (_, id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(1000),
+          cap: Some(1),
+          price: Some(5000),
+          seed: Some(1000),
+          swap_height: None,
+          fee: None,
+        }),
+        ..default()
+      },
+    );
+
+    // sealing fee is burned
+    entry_base.state.burned += 100000000;
+
+    context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 5000)]),
+      1,
+      Keepsake {
+        mint: Some(id),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    let mut entry_quote = context.index.get_relic_entry_by_id(id).unwrap().unwrap();
+
+    let txid_add = context.relic_tx(
+      &context.relic_outpoints(vec![(RELIC_ID, 1000), (id, 200)]),
+      1,
+      Keepsake {
+        liquidity_add: Some(LiquidityAdd {
+          relic: id,
+          base_amount: Some(1000),
+          quote_amount: None,
+        }),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    // the deposit is limited by the base amount and made at the pool ratio of 5:1
+    entry_quote.pool = Some(Pool {
+      base_supply: 6000,
+      quote_supply: 1200,
+      fee_bps: 100,
+      shares: 6000,
+    });
+
+    context.assert_relics(
+      [(RELIC_ID, entry_base), (id, entry_quote)],
+      [(
+        OutPoint {
+          txid: txid_add,
+          vout: 0,
+        },
+        vec![(RELIC_ID, 99994000), (id, 800)],
+      )],
+    );
+
+    let txid_remove = context.relic_tx(
+      &[OutPoint {
+        txid: txid_add,
+        vout: 0,
+      }],
+      1,
+      Keepsake {
+        liquidity_remove: Some(LiquidityRemove {
+          relic: id,
+          shares: None,
+        }),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    entry_quote.pool = Some(Pool {
+      base_supply: 5000,
+      quote_supply: 1000,
+      fee_bps: 100,
+      shares: 5000,
+    });
+
+    context.assert_relics(
+      [(RELIC_ID, entry_base), (id, entry_quote)],
+      [(
+        OutPoint {
+          txid: txid_remove,
+          vout: 0,
+        },
+        vec![(RELIC_ID, 99995000), (id, 1000)],
+      )],
+    );
+
+    assert!(context
+      .index
+      .relic_snapshot()
+      .unwrap()
+      .liquidity_shares
+      .is_empty());
+  }
+
+  #[test]
+  fn summoning_creates_syndicate() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    let (_txid_base, mut entry_base) = context.mint_base_token(1, 1);
+
+    // we need to enshrine a relic first
+    let (txid_relic_enshrine, relic_id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        mint_terms: Some(MintTerms {
+          amount: Some(1000),
+          cap: Some(1),
+          price: Some(1),
+          seed: Some(1000),
+          swap_height: None,
+          fee: None,
+        }),
+        ..default()
+      },
+    );
+
+    // sealing fee is burned
+    entry_base.state.burned += 100000000;
+
+    let relic_entry = RelicEntry {
+      block: relic_id.block,
+      enshrining: txid_relic_enshrine,
+      number: 1,
+      spaced_relic: SpacedRelic {
+        relic: Relic(RELIC),
+        spacers: 0,
+      },
+      timestamp: relic_id.block,
+      owner_sequence_number: Some(0),
+      mint_terms: Some(MintTerms {
+        amount: Some(1000),
+        cap: Some(1),
+        price: Some(1),
+        seed: Some(1000),
+        swap_height: None,
+        fee: None,
+      }),
+      ..default()
+    };
+
+    // summon a super basic syndicate
+    let (_, syndicate_id, mut syndicate_entry) = context.syndicate(Summoning {
+      treasure: Some(relic_id),
+      quota: Some(1),
+      ..default()
+    });
+    // sequence_number 0 will be the sealed Relic ticker
+    syndicate_entry.sequence_number = 1;
+
+    context.assert_relics(
+      [(RELIC_ID, entry_base), (relic_id, relic_entry)],
+      // all base tokens were spent on the sealing fee
+      [],
+    );
+
+    context.assert_syndicates([(syndicate_id, syndicate_entry)]);
+  }
+
+  #[test]
+  fn royalty_claims_are_delivered_to_syndicate_watchers() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let (_, syndicate_id, _) = context.syndicate(Summoning {
+      quota: Some(1),
+      royalty: Some(10),
+      ..default()
+    });
+
+    let block_count = usize::try_from(context.index.block_count().unwrap()).unwrap();
+
+    context.mine_blocks(1);
+
+    // pays the quota and the royalty to the owner of the syndicate inscription
+    context.core.broadcast_tx(mockcore::TransactionTemplate {
+      inputs: &[(
+        block_count,
+        0,
+        0,
+        inscription("text/plain;charset=utf-8", "chest").to_script(),
+      )],
+      input_outpoints: &context.relic_outpoints(vec![(RELIC_ID, 11)]),
+      op_return: Some(
+        Keepsake {
+          encasing: Some(syndicate_id),
+          pointer: Some(1),
+          ..default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let claim = context.relic_tx(
+      &[],
+      1,
+      Keepsake {
+        claim: Some(0),
+        ..default()
+      },
+    );
+
+    context.mine_blocks(1);
+
+    let events = context.index.events_for_tx(claim).unwrap();
+
+    assert!(events
+      .iter()
+      .any(|event| event.info == EventInfo::RelicClaimed { amount: 10 }));
+    assert!(events
+      .iter()
+      .any(|event| event.info == EventInfo::SyndicateRoyaltiesClaimed { syndicate_id }));
+
+    let webhook = Webhook {
+      url: "https://example.com/hook".into(),
+      watch: Watch::Syndicate(syndicate_id),
+    };
+
+    assert!(events.iter().any(|event| webhook.matches(event, None)));
+  }
+
+  #[test]
+  fn chests_follow_their_inscription() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let (_, syndicate_id, _) = context.syndicate(Summoning {
+      quota: Some(1),
+      ..default()
+    });
+
+    let block_count = usize::try_from(context.index.block_count().unwrap()).unwrap();
+
+    context.mine_blocks(1);
+
+    // the chest inscription is revealed in the encasing transaction and ends up on output 0
+    let encase_txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+      inputs: &[(
+        block_count,
+        0,
+        0,
+        inscription("text/plain;charset=utf-8", "chest").to_script(),
+      )],
+      input_outpoints: &context.relic_outpoints(vec![(RELIC_ID, 1)]),
+      op_return: Some(
+        Keepsake {
+          encasing: Some(syndicate_id),
+          pointer: Some(1),
+          ..default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let chest = InscriptionId {
+      txid: encase_txid,
+      index: 0,
+    };
+
+    assert!(context.index.get_chest(chest).unwrap().is_some());
+    assert!(context.index.get_chest_transfers(chest).unwrap().is_empty());
+
+    let transfer_txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+      input_outpoints: &[OutPoint {
+        txid: encase_txid,
+        vout: 0,
+      }],
+      outputs: 1,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let transfers = context.index.get_chest_transfers(chest).unwrap();
+
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers[0].txid, transfer_txid);
+    assert_eq!(
+      transfers[0].info,
+      EventInfo::ChestTransferred {
+        syndicate_id,
+        inscription_id: chest,
+        new_location: SatPoint {
+          outpoint: OutPoint {
+            txid: transfer_txid,
+            vout: 0,
+          },
+          offset: 0,
+        },
+        old_location: SatPoint {
+          outpoint: OutPoint {
+            txid: encase_txid,
+            vout: 0,
+          },
+          offset: 0,
+        },
+      }
+    );
+    assert!(context.index.get_chest(chest).unwrap().is_some());
+  }
+
+  #[test]
+  fn chests_can_be_partially_withdrawn_and_relocked() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let (_, syndicate_id, _) = context.syndicate(Summoning {
+      quota: Some(1000),
+      lock: Some(5),
+      ..default()
+    });
+
+    let block_count = usize::try_from(context.index.block_count().unwrap()).unwrap();
+
+    context.mine_blocks(1);
+
+    let encase_txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+      inputs: &[(
+        block_count,
+        0,
+        0,
+        inscription("text/plain;charset=utf-8", "chest").to_script(),
+      )],
+      input_outpoints: &context.relic_outpoints(vec![(RELIC_ID, 1000)]),
+      op_return: Some(
+        Keepsake {
+          encasing: Some(syndicate_id),
+          pointer: Some(1),
+          ..default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let chest = InscriptionId {
+      txid: encase_txid,
+      index: 0,
+    };
+
+    let created_block = context
+      .index
+      .get_chest(chest)
+      .unwrap()
+      .unwrap()
+      .created_block;
+
+    let mut location = OutPoint {
+      txid: encase_txid,
+      vout: 0,
+    };
+
+    let mut spend_chest = |keepsake: Keepsake| {
+      let txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+        input_outpoints: &[location],
+        op_return: Some(keepsake.encipher()),
+        outputs: 2,
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      location = OutPoint { txid, vout: 0 };
+
+      context
+        .index
+        .events_for_tx(txid)
+        .unwrap()
+        .into_iter()
+        .map(|event| event.info)
+        .collect::<Vec<EventInfo>>()
+    };
+
+    let withdraw = || Keepsake {
+      withdraw: Some(400),
+      pointer: Some(1),
+      ..default()
+    };
+
+    // the chest is still locked
+    assert!(spend_chest(withdraw()).contains(&EventInfo::RelicError {
+      operation: RelicOperation::Withdraw,
+      error: RelicError::ChestLocked(created_block + 5),
+    }));
+
+    context.mine_blocks(5);
+
+    assert!(
+      spend_chest(withdraw()).contains(&EventInfo::ChestWithdrawn {
+        syndicate_id,
+        inscription_id: chest,
+        amount: 400,
+      })
+    );
+
+    let entry = context.index.get_chest(chest).unwrap().unwrap();
+    assert_eq!(entry.amount, 600);
+    assert_eq!(entry.withdrawn, 400);
+
+    // withdrawing everything requires a release
+    assert!(spend_chest(Keepsake {
+      withdraw: Some(600),
+      pointer: Some(1),
+      ..default()
+    })
+    .contains(&EventInfo::RelicError {
+      operation: RelicOperation::Withdraw,
+      error: RelicError::ChestWithdrawalExceedsAmount(600),
+    }));
+
+    let height = u64::from(context.index.block_count().unwrap());
+
+    assert!(spend_chest(Keepsake {
+      relock: Some(height + 10),
+      ..default()
+    })
+    .contains(&EventInfo::ChestRelocked {
+      syndicate_id,
+      inscription_id: chest,
+      unlock_height: height + 10,
+    }));
+
+    // relocking can only extend the lock
+    assert!(spend_chest(Keepsake {
+      relock: Some(height + 5),
+      ..default()
+    })
+    .contains(&EventInfo::RelicError {
+      operation: RelicOperation::Relock,
+      error: RelicError::ChestRelockHeight(height + 10),
+    }));
+
+    assert!(spend_chest(Keepsake {
+      release: true,
+      pointer: Some(1),
+      ..default()
+    })
+    .contains(&EventInfo::RelicError {
+      operation: RelicOperation::Release,
+      error: RelicError::ChestLocked(height + 10),
+    }));
+
+    assert_eq!(
+      context
+        .index
+        .get_chest_events(chest, EventKind::ChestWithdrawn)
+        .unwrap()
+        .len(),
+      1
+    );
+    assert_eq!(
+      context
+        .index
+        .get_chest_events(chest, EventKind::ChestRelocked)
+        .unwrap()
+        .len(),
+      1
+    );
+  }
+
+  #[test]
+  fn syndicate_gates_reject_chests() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let (_, allowlisted, _) = context.syndicate(Summoning {
+      quota: Some(1),
+      allowlist: true,
+      ..default()
+    });
+
+    let missing = RelicId { block: 1, tx: 9 };
+
+    let (_, holding_missing, _) = context.syndicate(Summoning {
+      quota: Some(1),
+      holding: Some((missing, 1)),
+      ..default()
+    });
+
+    let (_, holding, _) = context.syndicate(Summoning {
+      quota: Some(1),
+      holding: Some((RELIC_ID, 1)),
+      ..default()
+    });
+
+    let encase = |syndicate_id| {
+      let block_count = usize::try_from(context.index.block_count().unwrap()).unwrap();
+
+      context.mine_blocks(1);
+
+      let txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+        inputs: &[(
+          block_count,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "chest").to_script(),
+        )],
+        input_outpoints: &context.relic_outpoints(vec![(RELIC_ID, 1)]),
+        op_return: Some(
+          Keepsake {
+            encasing: Some(syndicate_id),
+            pointer: Some(1),
+            ..default()
+          }
+          .encipher(),
+        ),
+        outputs: 2,
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      (
+        InscriptionId { txid, index: 0 },
+        context
+          .index
+          .events_for_tx(txid)
+          .unwrap()
+          .into_iter()
+          .map(|event| event.info)
+          .collect::<Vec<EventInfo>>(),
+      )
+    };
+
+    let (chest, events) = encase(allowlisted);
+    assert!(context.index.get_chest(chest).unwrap().is_none());
+    assert!(events.contains(&EventInfo::RelicError {
+      operation: RelicOperation::Encase,
+      error: RelicError::SyndicateNotAllowlisted,
+    }));
+
+    let (chest, events) = encase(holding_missing);
+    assert!(context.index.get_chest(chest).unwrap().is_none());
+    assert!(events.contains(&EventInfo::RelicError {
+      operation: RelicOperation::Encase,
+      error: RelicError::SyndicateHoldingInsufficient(missing, 1),
+    }));
+
+    let (chest, events) = encase(holding);
+    assert!(context.index.get_chest(chest).unwrap().is_some());
+    assert!(events.contains(&EventInfo::ChestEncased {
+      syndicate_id: holding
+    }));
+  }
+
+  #[test]
+  fn syndicate_gates_accept_chests() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let balance = context
+      .index
+      .get_relic_balances()
+      .unwrap()
+      .into_iter()
+      .flat_map(|(_, balances)| balances)
+      .filter(|(id, _)| *id == RELIC_ID)
+      .map(|(_, amount)| amount)
+      .sum::<u128>();
+
+    let (syndicate_txid, allowlisted, _) = context.syndicate(Summoning {
+      quota: Some(1),
+      allowlist: true,
+      ..default()
+    });
+
+    // the encased quota doesn't count towards the holding
+    let (_, above_threshold, _) = context.syndicate(Summoning {
+      quota: Some(1),
+      holding: Some((RELIC_ID, balance)),
+      ..default()
+    });
+
+    let (_, at_threshold, _) = context.syndicate(Summoning {
+      quota: Some(1),
+      holding: Some((RELIC_ID, balance - 1)),
+      ..default()
+    });
+
+    // an allowlist inscription is a child of the syndicate inscription
+    let block_count = usize::try_from(context.index.block_count().unwrap()).unwrap();
+
+    context.mine_blocks(1);
+
+    let allowlist_txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+      inputs: &[(
+        block_count,
+        0,
+        0,
+        script::Builder::new()
+          .push_slice(b"ord")
+          .push_int(1)
+          .push_slice(b"text/plain;charset=utf-8")
+          .push_int(0)
+          .push_slice(b"allowlist")
+          .push_slice(&Tag::Parent.bytes())
+          .push_slice(
+            &InscriptionId {
+              txid: syndicate_txid,
+              index: 0,
+            }
+            .value(),
+          )
+          .into_script(),
+      )],
+      outputs: 1,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let encase = |syndicate_id, allowlist: &[OutPoint]| {
+      let block_count = usize::try_from(context.index.block_count().unwrap()).unwrap();
+
+      context.mine_blocks(1);
+
+      let mut input_outpoints = context.relic_outpoints(vec![(RELIC_ID, 1)]);
+      input_outpoints.extend_from_slice(allowlist);
+
+      let txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+        inputs: &[(
+          block_count,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "chest").to_script(),
+        )],
+        input_outpoints: &input_outpoints,
+        op_return: Some(
+          Keepsake {
+            encasing: Some(syndicate_id),
+            pointer: Some(1),
+            ..default()
+          }
+          .encipher(),
+        ),
+        outputs: 2,
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      (
+        InscriptionId { txid, index: 0 },
+        context
+          .index
+          .events_for_tx(txid)
+          .unwrap()
+          .into_iter()
+          .map(|event| event.info)
+          .collect::<Vec<EventInfo>>(),
+      )
+    };
+
+    let (chest, events) = encase(above_threshold, &[]);
+    assert!(context.index.get_chest(chest).unwrap().is_none());
+    assert!(events.contains(&EventInfo::RelicError {
+      operation: RelicOperation::Encase,
+      error: RelicError::SyndicateHoldingInsufficient(RELIC_ID, balance),
+    }));
+
+    let (chest, events) = encase(at_threshold, &[]);
+    assert!(context.index.get_chest(chest).unwrap().is_some());
+    assert!(events.contains(&EventInfo::ChestEncased {
+      syndicate_id: at_threshold
+    }));
+
+    let (chest, events) = encase(
+      allowlisted,
+      &[OutPoint {
+        txid: allowlist_txid,
+        vout: 0,
+      }],
+    );
+    assert!(context.index.get_chest(chest).unwrap().is_some());
+    assert!(events.contains(&EventInfo::ChestEncased {
+      syndicate_id: allowlisted
+    }));
+  }
+
+  #[test]
+  fn owner_can_schedule_subsidy_releases() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let (enshrine_txid, relic_id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        subsidy: Some(1000),
+        ..default()
+      },
+    );
+
+    let schedule = || Keepsake {
+      subsidy_schedule: Some(SubsidySchedule {
+        relic: relic_id,
+        amount: 300,
+        epoch: 2,
+      }),
+      pointer: Some(1),
+      ..default()
+    };
+
+    let schedule_from = |outpoint: OutPoint| {
+      let txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+        input_outpoints: &[outpoint],
+        op_return: Some(schedule().encipher()),
+        outputs: 2,
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      context
+        .index
+        .events_for_tx(txid)
+        .unwrap()
+        .into_iter()
+        .map(|event| event.info)
+        .collect::<Vec<EventInfo>>()
+    };
+
+    // only the owner inscription can schedule the subsidy
+    assert!(schedule_from(OutPoint {
+      txid: enshrine_txid,
+      vout: 1,
+    })
+    .contains(&EventInfo::RelicError {
+      operation: RelicOperation::ScheduleSubsidy,
+      error: RelicError::RelicOwnerOnly,
+    }));
+    assert_eq!(context.index.get_subsidy_schedule(relic_id).unwrap(), None);
+
+    let start = context.index.block_count().unwrap().into();
+
+    assert!(schedule_from(OutPoint {
+      txid: enshrine_txid,
+      vout: 0,
+    })
+    .contains(&EventInfo::RelicSubsidyScheduled {
+      relic_id,
+      amount: 300,
+      epoch: 2,
+    }));
+
+    let entry = SubsidyScheduleEntry {
+      start,
+      epoch: 2,
+      amount: 300,
+      released: 0,
+    };
+    assert_eq!(
+      context.index.get_subsidy_schedule(relic_id).unwrap(),
+      Some(entry)
+    );
+
+    let released = || {
+      context
+        .index
+        .filter_events(
+          &EventFilter {
+            kind: Some(EventKind::RelicSubsidyReleased),
+            ..default()
+          },
+          None,
+          10,
+        )
+        .unwrap()
+        .0
+        .into_iter()
+        .map(|event| event.info)
+        .collect::<Vec<EventInfo>>()
+    };
+
+    context.mine_blocks(1);
+    assert_eq!(released(), Vec::new());
+
+    context.mine_blocks(1);
+    assert_eq!(
+      released(),
+      vec![EventInfo::RelicSubsidyReleased {
+        relic_id,
+        amount: 300,
+      }]
+    );
+    assert_eq!(
+      context.index.get_subsidy_schedule(relic_id).unwrap(),
+      Some(SubsidyScheduleEntry {
+        released: 300,
+        ..entry
+      })
+    );
+
+    // the last release is limited to the remaining subsidy and completes the schedule
+    context.mine_blocks(6);
+    assert_eq!(
+      released().last(),
+      Some(&EventInfo::RelicSubsidyReleased {
+        relic_id,
+        amount: 100,
+      })
+    );
+    assert_eq!(context.index.get_subsidy_schedule(relic_id).unwrap(), None);
+  }
+
+  #[test]
+  fn scheduled_subsidy_throttles_syndicate_rewards() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    let (enshrine_txid, relic_id) = context.enshrine(
+      SpacedRelic::new(Relic(RELIC), 0),
+      Enshrining {
+        subsidy: Some(1000),
+        ..default()
+      },
+    );
+
+    let start = context.index.block_count().unwrap();
+
+    let schedule_txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+      input_outpoints: &[OutPoint {
+        txid: enshrine_txid,
+        vout: 0,
+      }],
+      op_return: Some(
+        Keepsake {
+          subsidy_schedule: Some(SubsidySchedule {
+            relic: relic_id,
+            amount: 300,
+            epoch: 10,
+          }),
+          pointer: Some(1),
+          ..default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    // a rewarded syndicate inscription is a child of the relic owner inscription,
+    // which moved to the first output of the schedule transaction
+    let block_count = usize::try_from(context.index.block_count().unwrap()).unwrap();
+
+    context.mine_blocks(1);
+
+    let summon_txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+      inputs: &[(
+        block_count,
+        0,
+        0,
+        script::Builder::new()
+          .push_slice(b"ord")
+          .push_int(1)
+          .push_slice(b"text/plain;charset=utf-8")
+          .push_int(0)
+          .push_slice(b"rewards")
+          .push_slice(&Tag::Parent.bytes())
+          .push_slice(
+            &InscriptionId {
+              txid: enshrine_txid,
+              index: 0,
+            }
+            .value(),
+          )
+          .into_script(),
+      )],
+      input_outpoints: &[OutPoint {
+        txid: schedule_txid,
+        vout: 0,
+      }],
+      op_return: Some(
+        Keepsake {
+          summoning: Some(Summoning {
+            treasure: Some(relic_id),
+            quota: Some(0),
+            reward: Some(100),
+            ..default()
+          }),
+          pointer: Some(1),
+          ..default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let syndicate_id = SyndicateId {
+      block: u64::try_from(block_count + 1).unwrap(),
+      tx: 1,
+    };
+
+    assert!(context
+      .index
+      .events_for_tx(summon_txid)
+      .unwrap()
+      .into_iter()
+      .any(|event| event.info
+        == EventInfo::SyndicateSummoned {
+          syndicate_id,
+          relic_id,
+        }));
+
+    let block_count = usize::try_from(context.index.block_count().unwrap()).unwrap();
+
+    context.mine_blocks(1);
+
+    let chest_txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+      inputs: &[(
+        block_count,
+        0,
+        0,
+        inscription("text/plain;charset=utf-8", "chest").to_script(),
+      )],
+      op_return: Some(
+        Keepsake {
+          encasing: Some(syndicate_id),
+          pointer: Some(1),
+          ..default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let chest = InscriptionId {
+      txid: chest_txid,
+      index: 0,
+    };
+
+    let reward = || context.index.get_chest(chest).unwrap().unwrap().amount;
+
+    // nothing is paid out before the first release
+    context.mine_blocks((start + 10 - context.index.block_count().unwrap()).into());
+    assert_eq!(reward(), 0);
+
+    // the first release pays out 100 per block, until the released 300 are used up
+    context.mine_blocks(1);
+    assert_eq!(reward(), 100);
+
+    context.mine_blocks(5);
+    assert_eq!(reward(), 300);
+
+    // the second release resumes the rewards
+    context.mine_blocks(5);
+    assert_eq!(reward(), 400);
+  }
+
+  #[test]
+  fn inscriptions_are_indexed_by_address() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/plain;charset=utf-8", "foo").to_script(),
+      )],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let owner = Address::from_script(
+      &context
+        .index
+        .get_txout(OutPoint { txid, vout: 0 })
+        .unwrap()
+        .unwrap()
+        .script_pubkey,
+      Network::Regtest,
+    )
+    .unwrap()
+    .to_string();
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_address(&owner, 0, None)
+        .unwrap(),
+      (vec![inscription_id], 1)
+    );
+
+    let recipient = Address::from_script(
+      &Script::new_p2pkh(&bitcoin::PubkeyHash::all_zeros()),
+      Network::Regtest,
+    )
+    .unwrap();
+
+    context.core.broadcast_tx(mockcore::TransactionTemplate {
+      input_outpoints: &[OutPoint { txid, vout: 0 }],
+      recipient: Some(recipient.clone()),
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_address(&owner, 0, None)
+        .unwrap(),
+      (Vec::new(), 0)
+    );
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_address(&recipient.to_string(), 0, None)
+        .unwrap(),
+      (vec![inscription_id], 1)
+    );
   }
 
   #[test]
-  fn enshrining_with_no_transfers_creates_relic() {
+  fn cenotaphs_and_failed_operations_are_counted() {
     let context = Context::builder().arg("--index-relics").build();
 
-    let (_, mut entry_base) = context.mint_base_token(1, 1);
+    context.mine_blocks(2);
 
-    let (txid, id) = context.enshrine(
-      SpacedRelic::new(Relic(RELIC), 0),
-      Enshrining {
-        mint_terms: Some(MintTerms {
-          amount: Some(1000),
-          cap: Some(1),
-          price: Some(1),
-          seed: Some(1000),
-          swap_height: None,
-        }),
-        ..default()
-      },
-    );
+    context.core.broadcast_tx(mockcore::TransactionTemplate {
+      inputs: &[(1, 0, 0, Script::new())],
+      // a varint that never ends
+      op_return: Some(
+        script::Builder::new()
+          .push_opcode(opcodes::all::OP_RETURN)
+          .push_opcode(Keepsake::MAGIC_NUMBER)
+          .push_slice(&[128])
+          .into_script(),
+      ),
+      ..default()
+    });
 
-    // sealing fee is burned
-    entry_base.state.burned += 100000000;
+    context.core.broadcast_tx(mockcore::TransactionTemplate {
+      inputs: &[(2, 0, 0, Script::new())],
+      op_return: Some(
+        Keepsake {
+          claim: Some(0),
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
 
-    context.assert_relics(
-      [
-        (RELIC_ID, entry_base),
-        (
-          id,
-          RelicEntry {
-            block: id.block,
-            enshrining: txid,
-            number: 1,
-            spaced_relic: SpacedRelic {
-              relic: Relic(RELIC),
-              spacers: 0,
-            },
-            timestamp: id.block,
-            owner_sequence_number: Some(0),
-            mint_terms: Some(MintTerms {
-              amount: Some(1000),
-              cap: Some(1),
-              price: Some(1),
-              seed: Some(1000),
-              swap_height: None,
-            }),
-            ..default()
-          },
-        ),
-      ],
-      // all base tokens were spent on the sealing fee
-      [],
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .get_relic_error_counts(0, u32::MAX, 10)
+        .unwrap(),
+      vec![(
+        3,
+        [
+          ("Cenotaph".to_string(), 1),
+          ("NoClaimableBalance".to_string(), 1)
+        ]
+        .into()
+      )]
     );
+
+    assert!(context
+      .index
+      .get_relic_error_counts(4, u32::MAX, 10)
+      .unwrap()
+      .is_empty());
   }
 
   #[test]
-  fn base_token_is_mintable() {
+  fn sealed_tickers_are_pending_until_enshrined() {
     let context = Context::builder().arg("--index-relics").build();
 
-    let (txid, entry) = context.mint_base_token(1, 1);
+    context.mint_base_token(1, 1);
 
-    context.assert_relics(
-      [(RELIC_ID, entry)],
-      [(OutPoint { txid, vout: 1 }, vec![(RELIC_ID, 572_000_000)])],
+    let spaced_relic = SpacedRelic::new(Relic(RELIC), 0);
+    let sealing_txid = context.seal(spaced_relic, None);
+    let sealed_block = context.index.block_count().unwrap() - 1;
+
+    let history = context
+      .index
+      .get_sealing_history(spaced_relic.relic)
+      .unwrap()
+      .unwrap();
+    assert_eq!(
+      history.inscription,
+      InscriptionId {
+        txid: sealing_txid,
+        index: 0
+      }
     );
+    assert_eq!(history.sealed_block, sealed_block);
+    assert_eq!(history.enshrining, None);
 
-    assert_eq!(entry.circulating_supply(), 572_000_000);
+    assert_eq!(
+      context.index.get_pending_sealings(10, 0).unwrap(),
+      (vec![history], false)
+    );
+    assert_eq!(
+      context.index.get_sealing_stats().unwrap(),
+      SealingStats::new(1, 0, 0)
+    );
+
+    context.mine_blocks(2);
+
+    let enshrining_txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+      inputs: &[(sealed_block.try_into().unwrap(), 1, 0, Script::new())],
+      op_return: Some(
+        Keepsake {
+          enshrining: Some(Enshrining::default()),
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let enshrining = context
+      .index
+      .get_sealing_history(spaced_relic.relic)
+      .unwrap()
+      .unwrap()
+      .enshrining
+      .unwrap();
+    assert_eq!(enshrining.txid, enshrining_txid);
+    assert_eq!(enshrining.block, u64::from(sealed_block) + 3);
+    assert_eq!(enshrining.blocks_to_enshrine, 3);
+
+    assert_eq!(
+      context.index.get_pending_sealings(10, 0).unwrap(),
+      (Vec::new(), false)
+    );
+    assert_eq!(
+      context.index.get_sealing_stats().unwrap(),
+      SealingStats::new(0, 1, 3)
+    );
   }
 
   #[test]
-  fn quote_token_is_mintable() {
+  fn sealing_and_enshrining_set_charms() {
     let context = Context::builder().arg("--index-relics").build();
 
-    let (_, mut entry_base) = context.mint_base_token(2, 1);
+    context.mint_base_token(1, 1);
 
-    let (txid_enshrine, id) = context.enshrine(
-      SpacedRelic::new(Relic(RELIC), 0),
-      Enshrining {
-        mint_terms: Some(MintTerms {
-          amount: Some(1000),
-          cap: Some(1),
-          price: Some(5000),
-          seed: Some(1000),
-          swap_height: None,
-        }),
-        ..default()
-      },
-    );
+    let inscription_id = InscriptionId {
+      txid: context.seal(SpacedRelic::new(Relic(RELIC), 0), None),
+      index: 0,
+    };
+    let sealed_block = context.index.block_count().unwrap() - 1;
 
-    // sealing fee is burned
-    entry_base.state.burned += 100000000;
+    let charms = |inscription_id| {
+      Charm::charms(
+        context
+          .index
+          .get_inscription_entry(inscription_id)
+          .unwrap()
+          .unwrap()
+          .charms,
+      )
+    };
 
-    let txid_mint = context.relic_tx(
-      &context.relic_outpoints(vec![(RELIC_ID, 5000)]),
-      1,
-      Keepsake {
-        mint: Some(id),
-        ..default()
-      },
-    );
+    assert_eq!(charms(inscription_id), [Charm::Sealed]);
+
+    context.mine_blocks(2);
+
+    context.core.broadcast_tx(mockcore::TransactionTemplate {
+      inputs: &[(sealed_block.try_into().unwrap(), 1, 0, Script::new())],
+      op_return: Some(
+        Keepsake {
+          enshrining: Some(Enshrining::default()),
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
 
     context.mine_blocks(1);
 
-    let mut entry_quote = RelicEntry {
-      block: id.block,
-      enshrining: txid_enshrine,
-      number: 1,
-      spaced_relic: SpacedRelic {
-        relic: Relic(RELIC),
-        spacers: 0,
-      },
-      symbol: None,
-      owner_sequence_number: Some(0),
-      mint_terms: Some(MintTerms {
-        amount: Some(1000),
-        cap: Some(1),
-        price: Some(5000),
-        seed: Some(1000),
-        swap_height: None,
-      }),
-      state: RelicState {
-        mints: 1,
-        ..default()
-      },
-      pool: Some(Pool {
-        base_supply: 5000,
-        quote_supply: 1000,
-        fee_percentage: 1,
-      }),
-      timestamp: id.block,
-      turbo: false,
-    };
+    assert_eq!(charms(inscription_id), [Charm::Sealed, Charm::Enshrined]);
+  }
 
-    context.assert_relics(
-      [(RELIC_ID, entry_base), (id, entry_quote)],
-      [(
-        OutPoint {
-          txid: txid_mint,
-          vout: 0,
-        },
-        vec![(RELIC_ID, 99995000), (id, 1000)],
-      )],
-    );
+  #[test]
+  fn pruned_indices_keep_inscription_metadata() {
+    let context = Context::builder()
+      .args(["--index-relics", "--prune-content"])
+      .build();
 
-    let txid_swap = context.relic_tx(
-      &context.relic_outpoints(vec![(RELIC_ID, 560)]),
-      1,
-      Keepsake {
-        swap: Some(Swap {
-          output: Some(id),
-          output_amount: Some(100),
-          input: None,
-          // max input of base tokens
-          input_amount: Some(562),
-          is_exact_input: false,
-        }),
-        ..default()
-      },
-    );
+    assert!(!context.index.has_content());
 
-    context.mine_blocks(1);
+    context.mint_base_token(1, 1);
 
-    // update expected balances after the swap
-    entry_quote.pool.as_mut().unwrap().base_supply += 556;
-    entry_quote.pool.as_mut().unwrap().quote_supply -= 100;
+    let spaced_relic = SpacedRelic::new(Relic(RELIC), 0);
+    let (txid, id) = context.enshrine(spaced_relic, Enshrining::default());
 
-    context.assert_relics(
-      [(RELIC_ID, entry_base), (id, entry_quote)],
-      [(
-        OutPoint {
-          txid: txid_swap,
-          vout: 0,
-        },
-        vec![(RELIC_ID, 99995000 - 556 - 6), (id, 1100)],
-      )],
+    assert_eq!(
+      context.index.get_relic_by_id(id).unwrap(),
+      Some(spaced_relic.relic)
     );
+
+    let inscription = context
+      .index
+      .get_inscription_by_id(InscriptionId { txid, index: 0 })
+      .unwrap()
+      .unwrap();
+    assert_eq!(inscription.body, None);
+    assert!(inscription.metadata.is_some());
   }
 
   #[test]
-  fn summoning_creates_syndicate() {
+  fn transactions_can_be_simulated() {
     let context = Context::builder().arg("--index-relics").build();
 
-    let (_txid_base, mut entry_base) = context.mint_base_token(1, 1);
+    context.mint_base_token(2, 1);
 
-    // we need to enshrine a relic first
-    let (txid_relic_enshrine, relic_id) = context.enshrine(
+    let (_, id) = context.enshrine(
       SpacedRelic::new(Relic(RELIC), 0),
       Enshrining {
         mint_terms: Some(MintTerms {
           amount: Some(1000),
           cap: Some(1),
-          price: Some(1),
+          price: Some(5000),
           seed: Some(1000),
           swap_height: None,
+          fee: None,
         }),
         ..default()
       },
     );
 
-    // sealing fee is burned
-    entry_base.state.burned += 100000000;
+    let simulate = |keepsake: Keepsake| {
+      let tx = Transaction {
+        version: 2,
+        lock_time: bitcoin::PackedLockTime::ZERO,
+        input: context
+          .relic_outpoints(vec![(RELIC_ID, 5000)])
+          .into_iter()
+          .map(|previous_output| TxIn {
+            previous_output,
+            ..default()
+          })
+          .collect(),
+        output: vec![
+          TxOut {
+            value: 0,
+            script_pubkey: keepsake.encipher(),
+          },
+          TxOut {
+            value: 100,
+            script_pubkey: Script::new(),
+          },
+        ],
+      };
+      (
+        tx.txid(),
+        context.index.simulate_relic_transaction(&tx).unwrap(),
+      )
+    };
 
-    let relic_entry = RelicEntry {
-      block: relic_id.block,
-      enshrining: txid_relic_enshrine,
-      number: 1,
-      spaced_relic: SpacedRelic {
-        relic: Relic(RELIC),
-        spacers: 0,
-      },
-      timestamp: relic_id.block,
-      owner_sequence_number: Some(0),
-      mint_terms: Some(MintTerms {
-        amount: Some(1000),
-        cap: Some(1),
-        price: Some(1),
-        seed: Some(1000),
-        swap_height: None,
-      }),
+    let (txid, events) = simulate(Keepsake {
+      mint: Some(id),
       ..default()
-    };
+    });
 
-    // summon a super basic syndicate
-    let (_, syndicate_id, mut syndicate_entry) = context.syndicate(Summoning {
-      treasure: Some(relic_id),
-      quota: Some(1),
+    assert!(events.iter().all(|event| event.txid == txid));
+    assert!(events.iter().any(|event| event.info
+      == EventInfo::RelicMinted {
+        relic_id: id,
+        amount: 1000,
+      }));
+
+    // nothing was written to the index
+    assert!(context.index.events_for_tx(txid).unwrap().is_empty());
+    assert_eq!(
+      context
+        .index
+        .get_relic_entry_by_id(id)
+        .unwrap()
+        .unwrap()
+        .state
+        .mints,
+      0
+    );
+
+    let (_, events) = simulate(Keepsake {
+      claim: Some(1),
       ..default()
     });
-    // sequence_number 0 will be the sealed Relic ticker
-    syndicate_entry.sequence_number = 1;
 
-    context.assert_relics(
-      [(RELIC_ID, entry_base), (relic_id, relic_entry)],
-      // all base tokens were spent on the sealing fee
-      [],
+    assert!(events.iter().any(|event| event.info
+      == EventInfo::RelicError {
+        operation: RelicOperation::Claim,
+        error: RelicError::NoClaimableBalance,
+      }));
+  }
+
+  #[test]
+  fn committed_events_wait_in_the_outbox_until_dispatched() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1);
+    let context = Context::builder()
+      .arg("--index-relics")
+      .event_sender(event_sender)
+      .build();
+
+    context.mint_base_token(1, 1);
+
+    // indexing didn't wait for the channel, which only has room for one event
+    let pending = context.index.event_outbox_info().unwrap().pending;
+    assert!(pending > 1);
+
+    let receiver = std::thread::spawn(move || {
+      let mut received = Vec::new();
+      while let Some(event) = event_receiver.blocking_recv() {
+        received.push((event.block_height, event.event_index));
+      }
+      received
+    });
+
+    assert_eq!(
+      context.index.dispatch_pending_events().unwrap(),
+      Some(usize::try_from(pending).unwrap())
     );
 
-    context.assert_syndicates([(syndicate_id, syndicate_entry)]);
+    let info = context.index.event_outbox_info().unwrap();
+    assert_eq!(info.pending, 0);
+    assert_eq!(info.dispatched, pending);
+
+    // closes the channel
+    drop(context);
+
+    let received = receiver.join().unwrap();
+    assert_eq!(received.len(), usize::try_from(pending).unwrap());
+    assert!(received.windows(2).all(|keys| keys[0] < keys[1]));
   }
 
   #[test]
@@ -393,6 +3446,7 @@ mod tests {
           price: Some(5000),
           seed: Some(1000),
           swap_height: None,
+          fee: None,
         }),
         ..default()
       },
@@ -407,6 +3461,7 @@ mod tests {
           price: Some(5000),
           seed: Some(1000),
           swap_height: None,
+          fee: None,
         }),
         ..default()
       },
@@ -727,6 +3782,9 @@ mod tests {
             fee: 6,
             is_sell_order: true,
             is_exact_input: false,
+            base_reserve: 4432,
+            quote_reserve: 1129,
+            price: 392559787,
           },
         },
         Event {
@@ -740,6 +3798,9 @@ mod tests {
             fee: 6,
             is_sell_order: false,
             is_exact_input: false,
+            base_reserve: 5556,
+            quote_reserve: 900,
+            price: 617333333,
           },
         },
         Event {
@@ -791,6 +3852,47 @@ mod tests {
     );
   }
 
+  #[test]
+  fn index_hooks_are_called_during_updates() {
+    let mut context = Context::builder().arg("--index-relics").build();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let blocks = Arc::new(Mutex::new(Vec::new()));
+
+    {
+      let events = events.clone();
+      context.index.on_event(Box::new(move |event| {
+        events.lock().unwrap().push(event.clone())
+      }));
+    }
+
+    {
+      let blocks = blocks.clone();
+      context.index.on_block(Box::new(move |height, header| {
+        blocks.lock().unwrap().push((height, header.block_hash()))
+      }));
+    }
+
+    let (txid, _) = context.mint_base_token(1, 1);
+
+    assert!(events
+      .lock()
+      .unwrap()
+      .iter()
+      .any(|event: &Event| event.txid == txid));
+
+    let block_count = context.index.block_count().unwrap();
+    let blocks = blocks.lock().unwrap();
+    assert_eq!(blocks.len(), 3);
+    for (i, (height, hash)) in blocks.iter().enumerate() {
+      assert_eq!(*height, block_count - 3 + u32::try_from(i).unwrap());
+      assert_eq!(
+        context.index.block_hash(Some(*height)).unwrap(),
+        Some(*hash)
+      );
+    }
+  }
+
   // #[test]
   // fn enshrining_with_edict_creates_relic() {
   //   let context = Context::builder().arg("--index-relics").build();
@@ -63,13 +63,14 @@ impl Display for RelicId {
 impl FromStr for RelicId {
   type Err = Error;
 
+  /// Parses `BLOCK:TX`, ignoring surrounding whitespace and leading zeros.
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    let (height, index) = s.split_once(':').ok_or(Error::Separator)?;
+    let (height, index) = s.trim().split_once(':').ok_or(Error::Separator)?;
 
-    Ok(Self {
-      block: height.parse().map_err(Error::Block)?,
-      tx: index.parse().map_err(Error::Transaction)?,
-    })
+    let block = height.parse().map_err(Error::Block)?;
+    let tx = index.parse().map_err(Error::Transaction)?;
+
+    Self::new(block, tx).ok_or(Error::Bounds { block, tx })
   }
 }
 
@@ -78,14 +79,32 @@ pub enum Error {
   Separator,
   Block(ParseIntError),
   Transaction(ParseIntError),
+  Bounds { block: u64, tx: u32 },
+}
+
+impl Error {
+  /// Stable identifier of the error variant, for API clients.
+  pub fn kind(&self) -> &'static str {
+    match self {
+      Self::Separator => "separator",
+      Self::Block(_) => "block",
+      Self::Transaction(_) => "transaction",
+      Self::Bounds { .. } => "bounds",
+    }
+  }
 }
 
 impl Display for Error {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
     match self {
-      Self::Separator => write!(f, "missing separator"),
-      Self::Block(err) => write!(f, "invalid height: {err}"),
-      Self::Transaction(err) => write!(f, "invalid index: {err}"),
+      Self::Separator => write!(f, "missing `:` separator, expected `BLOCK:TX`"),
+      Self::Block(err) => write!(f, "invalid block height, expected `BLOCK:TX`: {err}"),
+      Self::Transaction(err) => {
+        write!(f, "invalid transaction index, expected `BLOCK:TX`: {err}")
+      }
+      Self::Bounds { block, tx } => {
+        write!(f, "invalid id {block}:{tx}, block 0 only has transaction 0")
+      }
     }
   }
 }
@@ -164,6 +183,40 @@ mod tests {
       "1:2".parse::<RelicId>().unwrap(),
       RelicId { block: 1, tx: 2 }
     );
+    assert_eq!(
+      "0:1".parse::<RelicId>(),
+      Err(Error::Bounds { block: 0, tx: 1 })
+    );
+    assert_eq!(
+      "0:0".parse::<RelicId>().unwrap(),
+      RelicId { block: 0, tx: 0 }
+    );
+  }
+
+  #[test]
+  fn from_str_accepts_padding() {
+    assert_eq!(
+      "0001:02".parse::<RelicId>().unwrap(),
+      RelicId { block: 1, tx: 2 }
+    );
+    assert_eq!(
+      " 1:2\n".parse::<RelicId>().unwrap(),
+      RelicId { block: 1, tx: 2 }
+    );
+    assert_eq!("0001:02".parse::<RelicId>().unwrap().to_string(), "1:2");
+  }
+
+  #[test]
+  fn error() {
+    assert_eq!(Error::Separator.kind(), "separator");
+    assert_eq!(
+      "1:a".parse::<RelicId>().unwrap_err().to_string(),
+      "invalid transaction index, expected `BLOCK:TX`: invalid digit found in string"
+    );
+    assert_eq!(
+      "0:1".parse::<RelicId>().unwrap_err().to_string(),
+      "invalid id 0:1, block 0 only has transaction 0"
+    );
   }
 
   #[test]
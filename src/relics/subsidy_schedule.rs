@@ -0,0 +1,13 @@
+use super::*;
+
+/// Releases the subsidy of a Relic for Syndicate rewards in equal parts at the end of every epoch,
+/// instead of all at once. Only the owner of the Relic can schedule its subsidy.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Copy, Clone, Eq)]
+pub struct SubsidySchedule {
+  /// Relic whose subsidy is released
+  pub relic: RelicId,
+  /// amount of the subsidy released per epoch
+  pub amount: u128,
+  /// length of an epoch in blocks
+  pub epoch: u64,
+}
@@ -17,6 +17,13 @@ pub struct Summoning {
   pub royalty: Option<u128>,
   /// if this is set, only owner of the Syndicate inscription can chest
   pub gated: bool,
+  /// if this is set, only holders of an allowlist inscription can chest
+  /// allowlist inscriptions are children of the Syndicate inscription that are not Chests,
+  /// and must be spent by the encasing transaction
+  pub allowlist: bool,
+  /// if this is set, only holders of at least this amount of the given Relic can chest
+  /// the amount must be spent by the encasing transaction, but is not taken
+  pub holding: Option<(RelicId, u128)>,
   /// how many blocks the relics should be locked in the chest, no withdrawal possible before
   pub lock: Option<u64>,
   /// rewards that are paid by having relics wrapped, measured in Relics per Chest per block
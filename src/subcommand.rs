@@ -1,6 +1,10 @@
 use super::*;
 
+pub mod bench;
+pub mod bones;
 pub mod epochs;
+pub mod events;
+pub mod export;
 pub mod find;
 mod index;
 pub mod info;
@@ -20,12 +24,23 @@ fn print_json(output: impl Serialize) -> Result {
 
 #[derive(Debug, Parser)]
 pub(crate) enum Subcommand {
+  #[command(subcommand, about = "Benchmark commands")]
+  Bench(bench::Bench),
+  #[command(subcommand, about = "Relic state commands")]
+  Bones(bones::Bones),
   #[command(about = "List the first satoshis of each reward epoch")]
   Epochs,
+  #[command(subcommand, about = "Event log commands")]
+  Events(events::Events),
+  #[command(subcommand, about = "Export index data")]
+  Export(export::Export),
   #[command(about = "Find a satoshi's current location")]
   Find(find::Find),
-  #[command(about = "Update the index")]
-  Index,
+  #[command(about = "Update, inspect, verify or compact the index")]
+  Index {
+    #[command(subcommand)]
+    subcommand: Option<index::IndexSubcommand>,
+  },
   #[command(about = "Display index statistics")]
   Info(info::Info),
   #[command(about = "List the satoshis in an output")]
@@ -47,18 +62,23 @@ pub(crate) enum Subcommand {
 impl Subcommand {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
     match self {
+      Self::Bench(bench) => bench.run(options),
+      Self::Bones(bones) => bones.run(options),
       Self::Epochs => epochs::run(),
+      Self::Events(events) => events.run(options),
+      Self::Export(export) => export.run(options),
       Self::Find(find) => find.run(options),
-      Self::Index => index::run(options),
+      Self::Index { subcommand } => index::run(subcommand, options),
       Self::Info(info) => info.run(options),
       Self::List(list) => list.run(options),
       Self::Parse(parse) => parse.run(),
       Self::Preview(preview) => preview.run(),
       Self::Server(server) => {
-        let index = Arc::new(Index::open(&options)?);
+        let (event_sender, event_receiver) = tokio::sync::mpsc::channel(1024);
+        let index = Arc::new(Index::open_with_event_sender(&options, Some(event_sender))?);
         let handle = axum_server::Handle::new();
         LISTENERS.lock().unwrap().push(handle.clone());
-        server.run(options, index, handle)
+        server.run(options, index, handle, event_receiver)
       }
       Self::Subsidy(subsidy) => subsidy.run(),
       Self::Traits(traits) => traits.run(),
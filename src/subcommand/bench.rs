@@ -0,0 +1,17 @@
+use super::*;
+
+pub mod swaps;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Bench {
+  #[command(about = "Replay indexed swaps through the pool engine")]
+  Swaps(swaps::Swaps),
+}
+
+impl Bench {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    match self {
+      Self::Swaps(swaps) => swaps.run(options),
+    }
+  }
+}
@@ -0,0 +1,287 @@
+use {
+  super::*,
+  crate::{
+    allocator::{self, Allocations},
+    relics::{BalanceDiff, Pool, PoolError, PoolSwap, RelicId, SwapDirection},
+  },
+  std::hint,
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Swaps {
+  #[arg(
+    long,
+    default_value_t = 10,
+    help = "Replay the swaps of the <BLOCKS> blocks with the most swaps."
+  )]
+  blocks: usize,
+  #[arg(
+    long,
+    default_value_t = 1000,
+    help = "Replay the swaps <ITERATIONS> times."
+  )]
+  iterations: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  /// heights of the replayed blocks
+  pub blocks: Vec<u32>,
+  /// swaps replayed per iteration
+  pub swaps: usize,
+  pub iterations: u32,
+  pub seconds: f64,
+  pub swaps_per_second: f64,
+  pub nanos_per_swap: f64,
+  /// allocations of all iterations together, only counted by the `ord`
+  /// binary built with the `bench` feature
+  pub allocations: Option<Allocations>,
+}
+
+/// A swap as it was indexed: the pool before it, the swap, its result and the
+/// pool after it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Case {
+  pool: Pool,
+  swap: PoolSwap,
+  diff: BalanceDiff,
+  after: Pool,
+}
+
+impl Case {
+  /// Reconstructs the swap of a `RelicSwapped` event with a pool charging
+  /// `fee_bps`. Slippage limits are left out, since they don't change the
+  /// result of swaps that succeeded. `None` if the event is inconsistent.
+  fn new(info: &EventInfo, fee_bps: u16) -> Option<Self> {
+    let EventInfo::RelicSwapped {
+      base_amount,
+      quote_amount,
+      fee,
+      is_sell_order,
+      is_exact_input,
+      base_reserve,
+      quote_reserve,
+      ..
+    } = *info
+    else {
+      return None;
+    };
+
+    let (direction, input, output, base_supply, quote_supply) = if is_sell_order {
+      (
+        SwapDirection::QuoteToBase,
+        quote_amount,
+        base_amount,
+        base_reserve.checked_add(base_amount)?.checked_add(fee)?,
+        quote_reserve.checked_sub(quote_amount)?,
+      )
+    } else {
+      (
+        SwapDirection::BaseToQuote,
+        base_amount,
+        quote_amount,
+        base_reserve.checked_sub(base_amount.checked_sub(fee)?)?,
+        quote_reserve.checked_add(quote_amount)?,
+      )
+    };
+
+    let swap = if is_exact_input {
+      PoolSwap::Input {
+        direction,
+        input,
+        min_output: None,
+      }
+    } else {
+      PoolSwap::Output {
+        direction,
+        output,
+        max_input: None,
+      }
+    };
+
+    // shares don't affect swaps
+    let pool = Pool {
+      base_supply,
+      quote_supply,
+      fee_bps,
+      shares: 0,
+    };
+
+    Some(Self {
+      pool,
+      swap,
+      diff: BalanceDiff {
+        direction,
+        input,
+        output,
+        fee,
+      },
+      after: Pool {
+        base_supply: base_reserve,
+        quote_supply: quote_reserve,
+        ..pool
+      },
+    })
+  }
+
+  fn replay(&self) -> Result<(BalanceDiff, Pool), PoolError> {
+    let mut pool = self.pool;
+    let diff = pool.calculate(self.swap)?;
+    pool.apply(diff);
+    Ok((diff, pool))
+  }
+}
+
+impl Swaps {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    ensure!(self.iterations > 0, "at least one iteration is required");
+
+    let index = Index::open(&options)?;
+
+    if !index.has_relic_index() {
+      bail!("benchmarking swaps requires index created with `--index-relics` flag");
+    }
+
+    index.update()?;
+
+    let blocks = index.get_swap_heavy_blocks(self.blocks)?;
+
+    let mut fees = HashMap::<RelicId, Option<u16>>::new();
+    let mut cases = Vec::new();
+
+    for event in blocks.values().flatten() {
+      let EventInfo::RelicSwapped { relic_id, .. } = event.info else {
+        continue;
+      };
+
+      // the fee of a pool is set by its mint terms when the pool is created
+      // and pools are never removed, so the current fee is the one every swap
+      // of the pool paid
+      let fee_bps = match fees.get(&relic_id) {
+        Some(fee_bps) => *fee_bps,
+        None => {
+          let fee_bps = index
+            .get_relic_entry_by_id(relic_id)?
+            .and_then(|entry| entry.pool)
+            .map(|pool| pool.fee_bps);
+          fees.insert(relic_id, fee_bps);
+          fee_bps
+        }
+      };
+
+      let position = format!("{}:{}", event.block_height, event.event_index);
+
+      let case = fee_bps
+        .and_then(|fee_bps| Case::new(&event.info, fee_bps))
+        .ok_or_else(|| anyhow!("swap event {position} is inconsistent with its pool"))?;
+
+      // a replay that differs means the pool engine changed its results
+      ensure!(
+        case.replay() == Ok((case.diff, case.after)),
+        "replaying swap event {position} differs from the indexed result"
+      );
+
+      cases.push(case);
+    }
+
+    ensure!(!cases.is_empty(), "no swaps indexed");
+
+    let start = Instant::now();
+
+    let ((), allocations) = allocator::count(|| {
+      for _ in 0..self.iterations {
+        for case in &cases {
+          let _ = hint::black_box(hint::black_box(case).replay());
+        }
+      }
+    });
+
+    let seconds = start.elapsed().as_secs_f64();
+
+    let replayed = cases.len() as f64 * f64::from(self.iterations);
+
+    print_json(Output {
+      blocks: blocks.into_keys().collect(),
+      swaps: cases.len(),
+      iterations: self.iterations,
+      seconds,
+      swaps_per_second: replayed / seconds,
+      nanos_per_swap: seconds * 1e9 / replayed,
+      allocations: cfg!(feature = "bench").then_some(allocations),
+    })?;
+
+    Ok(Box::new(Empty {}))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn swaps_are_reconstructed_from_events() {
+    let before = Pool {
+      base_supply: 1_000_000,
+      quote_supply: 5_000_000,
+      fee_bps: 100,
+      shares: 0,
+    };
+
+    for swap in [
+      PoolSwap::Input {
+        direction: SwapDirection::BaseToQuote,
+        input: 1000,
+        min_output: None,
+      },
+      PoolSwap::Input {
+        direction: SwapDirection::QuoteToBase,
+        input: 5000,
+        min_output: None,
+      },
+      PoolSwap::Output {
+        direction: SwapDirection::BaseToQuote,
+        output: 5000,
+        max_input: None,
+      },
+      PoolSwap::Output {
+        direction: SwapDirection::QuoteToBase,
+        output: 1000,
+        max_input: None,
+      },
+    ] {
+      let diff = before.calculate(swap).unwrap();
+      let mut after = before;
+      after.apply(diff);
+
+      let (base_amount, quote_amount, is_sell_order) = match diff.direction {
+        SwapDirection::BaseToQuote => (diff.input, diff.output, false),
+        SwapDirection::QuoteToBase => (diff.output, diff.input, true),
+      };
+
+      let info = EventInfo::RelicSwapped {
+        relic_id: RelicId { block: 1, tx: 0 },
+        base_amount,
+        quote_amount,
+        fee: diff.fee,
+        is_sell_order,
+        is_exact_input: matches!(swap, PoolSwap::Input { .. }),
+        base_reserve: after.base_supply,
+        quote_reserve: after.quote_supply,
+        price: after.price(),
+      };
+
+      let case = Case::new(&info, 100).unwrap();
+
+      assert_eq!(
+        case,
+        Case {
+          pool: before,
+          swap,
+          diff,
+          after,
+        }
+      );
+      assert_eq!(case.replay(), Ok((diff, after)));
+    }
+  }
+}
@@ -0,0 +1,91 @@
+use {
+  super::*,
+  crate::relics::{RelicId, SpacedRelic},
+  serde::de::DeserializeOwned,
+  url::Url,
+};
+
+pub mod balances;
+pub mod export;
+pub mod import;
+pub mod list;
+pub mod record;
+pub mod show;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Bones {
+  #[command(about = "List the relic balances of an address")]
+  Balances(balances::Balances),
+  #[command(about = "Export relic state snapshot")]
+  Export(export::Export),
+  #[command(about = "Seed a new index from a relic state snapshot")]
+  Import(import::Import),
+  #[command(about = "List all relics")]
+  List(list::List),
+  #[command(about = "Record indexed blocks as a regression test fixture")]
+  Record(record::Record),
+  #[command(about = "Show a relic")]
+  Show(show::Show),
+}
+
+impl Bones {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    match self {
+      Self::Balances(balances) => balances.run(options),
+      Self::Export(export) => export.run(options),
+      Self::Import(import) => import.run(options),
+      Self::List(list) => list.run(options),
+      Self::Record(record) => record.run(options),
+      Self::Show(show) => show.run(options),
+    }
+  }
+}
+
+/// Where the query subcommands read relic state from, and how they print it.
+#[derive(Debug, Parser)]
+pub(crate) struct Source {
+  #[arg(
+    long,
+    help = "Query the explorer running at <SERVER> instead of the local index."
+  )]
+  server: Option<Url>,
+  #[arg(long, help = "Print JSON instead of a human-readable table.")]
+  json: bool,
+}
+
+/// A local index or a remote explorer to query relic state from.
+enum Backend {
+  Index(Index),
+  Server(Url),
+}
+
+impl Source {
+  fn backend(&self, options: &Options) -> Result<Backend> {
+    if let Some(server) = &self.server {
+      return Ok(Backend::Server(server.clone()));
+    }
+
+    let index = Index::open(options)?;
+
+    if !index.has_relic_index() {
+      bail!("querying bones requires index created with `--index-relics` flag");
+    }
+
+    index.update()?;
+
+    Ok(Backend::Index(index))
+  }
+}
+
+impl Backend {
+  /// GET the JSON at `path`, relative to the server URL, so explorers mounted
+  /// under a path prefix work as well.
+  fn get<T: DeserializeOwned>(server: &Url, path: &str) -> Result<T> {
+    let url = format!("{}/{path}", server.as_str().trim_end_matches('/'));
+
+    reqwest::blocking::get(&url)
+      .and_then(|response| response.error_for_status())
+      .and_then(|response| response.json())
+      .with_context(|| format!("failed to query {url}"))
+  }
+}
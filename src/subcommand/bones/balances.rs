@@ -0,0 +1,89 @@
+use {
+  super::*,
+  crate::{
+    index::entry::script_hash,
+    templates::{AddressOutputJson, OutputCompactJson},
+  },
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Balances {
+  #[arg(help = "List the relic balances of <ADDRESS>.")]
+  address: String,
+  #[command(flatten)]
+  source: Source,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub address: String,
+  pub outputs: usize,
+  #[serde(rename = "bones")]
+  pub relics: BTreeMap<SpacedRelic, Pile>,
+}
+
+impl Balances {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let mut balances = Vec::new();
+
+    let outputs = match self.source.backend(&options)? {
+      Backend::Server(server) => {
+        let outpoints =
+          Backend::get::<Vec<AddressOutputJson>>(&server, &format!("address/{}", self.address))?
+            .into_iter()
+            .flat_map(|output| output.outpoint)
+            .collect::<Vec<OutPoint>>();
+
+        // keep the request line short for addresses with many outputs
+        for chunk in outpoints.chunks(100) {
+          let list = chunk
+            .iter()
+            .map(|outpoint| outpoint.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+
+          for output in Backend::get::<Vec<OutputCompactJson>>(&server, &format!("outputs/{list}"))?
+          {
+            balances.extend(output.relics);
+          }
+        }
+
+        outpoints.len()
+      }
+      Backend::Index(index) => {
+        let address = options.chain().parse_address(&self.address)?;
+
+        let outpoints = index.get_account_outputs(script_hash(&address.script_pubkey()))?;
+
+        for outpoint in &outpoints {
+          balances.extend(index.get_relic_balances_for_outpoint(*outpoint)?);
+        }
+
+        outpoints.len()
+      }
+    };
+
+    let mut relics = BTreeMap::<SpacedRelic, Pile>::new();
+
+    for (spaced_relic, pile) in balances {
+      relics
+        .entry(spaced_relic)
+        .and_modify(|total| total.amount += pile.amount)
+        .or_insert(pile);
+    }
+
+    if self.source.json {
+      print_json(Output {
+        address: self.address,
+        outputs,
+        relics,
+      })?;
+    } else {
+      for (spaced_relic, pile) in relics {
+        println!("{spaced_relic:<32} {pile}");
+      }
+    }
+
+    Ok(Box::new(Empty {}))
+  }
+}
@@ -0,0 +1,67 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Export {
+  #[arg(
+    long,
+    help = "Only export if the index is at <HEIGHT>. Use together with `--height-limit`."
+  )]
+  height: Option<u32>,
+  #[arg(long, help = "Write snapshot to <OUTPUT> instead of stdout.")]
+  output: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub height: u32,
+  pub block_hash: BlockHash,
+  pub relics: usize,
+  pub outputs: usize,
+  pub syndicates: usize,
+  pub chests: usize,
+}
+
+impl Export {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    if !index.has_relic_index() {
+      bail!("export requires index created with `--index-relics` flag");
+    }
+
+    index.update()?;
+
+    let snapshot = index.relic_snapshot()?;
+
+    if let Some(height) = self.height {
+      if snapshot.height != height {
+        bail!(
+          "index is at height {}, not {height}: rerun with `--height-limit {}`",
+          snapshot.height,
+          height + 1
+        );
+      }
+    }
+
+    let Some(path) = self.output else {
+      print_json(&snapshot)?;
+      return Ok(Box::new(Empty {}));
+    };
+
+    let file = File::create(&path)
+      .with_context(|| format!("failed to create snapshot file `{}`", path.display()))?;
+
+    serde_json::to_writer_pretty(io::BufWriter::new(file), &snapshot)?;
+
+    print_json(Output {
+      height: snapshot.height,
+      block_hash: snapshot.block_hash,
+      relics: snapshot.relics.len(),
+      outputs: snapshot.balances.len(),
+      syndicates: snapshot.syndicates.len(),
+      chests: snapshot.chests.len(),
+    })?;
+
+    Ok(Box::new(Empty {}))
+  }
+}
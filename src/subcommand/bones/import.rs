@@ -0,0 +1,55 @@
+use {super::*, crate::index::snapshot::RelicSnapshot};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Import {
+  #[arg(help = "Read relic state snapshot from <SNAPSHOT>.")]
+  snapshot: PathBuf,
+  #[arg(long, help = "Require the snapshot to be taken at <HEIGHT>.")]
+  height: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub snapshot_height: u32,
+  pub height: Option<u32>,
+  pub relics: usize,
+  pub outputs: usize,
+}
+
+impl Import {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let file = File::open(&self.snapshot)
+      .with_context(|| format!("failed to open snapshot `{}`", self.snapshot.display()))?;
+
+    let snapshot: RelicSnapshot = serde_json::from_reader(io::BufReader::new(file))
+      .with_context(|| format!("failed to parse snapshot `{}`", self.snapshot.display()))?;
+
+    if let Some(height) = self.height {
+      if snapshot.height != height {
+        bail!(
+          "snapshot was taken at height {}, not {height}",
+          snapshot.height
+        );
+      }
+    }
+
+    let index = Index::open(&options)?;
+
+    let snapshot_height = snapshot.height;
+    let relics = snapshot.relics.len();
+    let outputs = snapshot.balances.len();
+
+    index.import_relic_snapshot(snapshot)?;
+
+    index.update()?;
+
+    print_json(Output {
+      snapshot_height,
+      height: index.height()?.map(|height| height.n()),
+      relics,
+      outputs,
+    })?;
+
+    Ok(Box::new(Empty {}))
+  }
+}
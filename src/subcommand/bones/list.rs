@@ -0,0 +1,80 @@
+use {
+  super::*,
+  crate::templates::{relic::RelicEntryHtml, relics::RelicsHtml},
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct List {
+  #[command(flatten)]
+  source: Source,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub id: RelicId,
+  pub owner: Option<InscriptionId>,
+  #[serde(flatten)]
+  pub entry: RelicEntryHtml,
+}
+
+impl List {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let backend = self.source.backend(&options)?;
+
+    let mut relics = Vec::new();
+
+    for page in 0.. {
+      let (entries, more) = match &backend {
+        Backend::Server(server) => {
+          let page = Backend::get::<RelicsHtml>(server, &format!("bones/{page}?json=true"))?;
+          (page.entries, page.more)
+        }
+        Backend::Index(index) => {
+          let (entries, more) = index.relics_paginated(50, page)?;
+          (
+            entries
+              .into_iter()
+              .map(|(id, entry, owner)| (id, RelicEntryHtml::from(entry), owner))
+              .collect(),
+            more,
+          )
+        }
+      };
+
+      relics.extend(
+        entries
+          .into_iter()
+          .map(|(id, entry, owner)| Output { id, owner, entry }),
+      );
+
+      if !more {
+        break;
+      }
+    }
+
+    if self.source.json {
+      print_json(&relics)?;
+    } else {
+      println!(
+        "{:<8} {:<16} {:<32} {:>12} POOL",
+        "NUMBER", "ID", "BONE", "MINTS"
+      );
+      for relic in &relics {
+        println!(
+          "{:<8} {:<16} {:<32} {:>12} {}",
+          relic.entry.number,
+          relic.id,
+          relic.entry.spaced_relic,
+          relic.entry.state.mints,
+          if relic.entry.pool.is_some() {
+            "yes"
+          } else {
+            "no"
+          },
+        );
+      }
+    }
+
+    Ok(Box::new(Empty {}))
+  }
+}
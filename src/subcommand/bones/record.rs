@@ -0,0 +1,53 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Record {
+  #[arg(
+    long,
+    help = "Write the fixture to <OUTPUT>.fixture.json and the relic state snapshot to <OUTPUT>.snapshot.json."
+  )]
+  output: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub height: u32,
+  pub blocks: usize,
+  pub spent: usize,
+}
+
+impl Record {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    // fixtures are replayed from the genesis block, see `RelicFixture`
+    if options.chain() != Chain::Regtest {
+      bail!("fixtures can only be recorded from regtest");
+    }
+
+    let index = Index::open(&options)?;
+
+    if !index.has_relic_index() {
+      bail!("recording requires index created with `--index-relics` flag");
+    }
+
+    index.update()?;
+
+    let fixture = index.relic_fixture()?;
+    let snapshot = index.relic_snapshot()?;
+
+    for (extension, json) in [
+      ("fixture.json", serde_json::to_vec_pretty(&fixture)?),
+      ("snapshot.json", serde_json::to_vec_pretty(&snapshot)?),
+    ] {
+      let path = self.output.with_extension(extension);
+      fs::write(&path, json).with_context(|| format!("failed to write `{}`", path.display()))?;
+    }
+
+    print_json(Output {
+      height: snapshot.height,
+      blocks: fixture.blocks.len(),
+      spent: fixture.spent.len(),
+    })?;
+
+    Ok(Box::new(Empty {}))
+  }
+}
@@ -0,0 +1,85 @@
+use {
+  super::*,
+  crate::{
+    subcommand::server::query,
+    templates::relic::{RelicEntryHtml, RelicHtml},
+  },
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Show {
+  #[arg(help = "Show relic <BONE>, given by name, id or number.")]
+  bone: String,
+  #[command(flatten)]
+  source: Source,
+}
+
+impl Show {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let relic = match self.source.backend(&options)? {
+      Backend::Server(server) => {
+        Backend::get::<RelicHtml>(&server, &format!("bone/{}?json=true", self.bone))?
+      }
+      Backend::Index(index) => {
+        let relic = match self.bone.parse::<query::Relic>()? {
+          query::Relic::Spaced(spaced_relic) => Some(spaced_relic.relic),
+          query::Relic::Id(id) => index.get_relic_by_id(id)?,
+          query::Relic::Number(number) => index.get_relic_by_number(usize::try_from(number)?)?,
+        };
+
+        let Some((id, entry, owner)) = relic.map(|relic| index.relic(relic)).transpose()?.flatten()
+        else {
+          bail!("bone {} not found", self.bone);
+        };
+
+        // the next block is the earliest a mint or swap could be included in
+        let height = u64::from(index.block_count()?);
+
+        RelicHtml {
+          mintable: entry.mintable_at(height, None).is_ok(),
+          swappable: entry.swappable_at(height).is_ok(),
+          thumb: index.get_relic_thumbnail(id)?,
+          estimates: index.get_relic_estimates(id, &entry)?,
+          formatted: None,
+          entry: RelicEntryHtml::from(entry),
+          id,
+          owner,
+        }
+      }
+    };
+
+    if self.source.json {
+      print_json(&relic)?;
+    } else {
+      let entry = &relic.entry;
+      let terms = entry.mint_terms.unwrap_or_default();
+
+      println!("bone       {}", entry.spaced_relic);
+      println!("id         {}", relic.id);
+      println!("number     {}", entry.number);
+      println!("symbol     {}", entry.symbol.unwrap_or('¤'));
+      println!("block      {}", entry.block);
+      println!("enshrining {}", entry.enshrining);
+      match terms.cap {
+        Some(cap) => println!("mints      {} / {cap}", entry.state.mints),
+        None => println!("mints      {}", entry.state.mints),
+      }
+      println!("burned     {}", entry.pile(entry.state.burned));
+      match entry.pool {
+        Some(pool) => println!(
+          "pool       {} / {}",
+          entry.relic(pool.base_supply),
+          entry.pile(pool.quote_supply)
+        ),
+        None => println!("pool       none"),
+      }
+      println!("mintable   {}", relic.mintable);
+      println!("swappable  {}", relic.swappable);
+      if let Some(owner) = relic.owner {
+        println!("owner      {owner}");
+      }
+    }
+
+    Ok(Box::new(Empty {}))
+  }
+}
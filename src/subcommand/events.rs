@@ -0,0 +1,17 @@
+use super::*;
+
+pub mod replay;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Events {
+  #[command(about = "Stream stored events into an external message bus")]
+  Replay(replay::Replay),
+}
+
+impl Events {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    match self {
+      Self::Replay(replay) => replay.run(options),
+    }
+  }
+}
@@ -0,0 +1,418 @@
+use {
+  super::*,
+  crate::index::event::EventFilter,
+  clap::ValueEnum,
+  std::{
+    io::{BufRead, BufReader, BufWriter, Write},
+    net::TcpStream,
+  },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum SinkKind {
+  Stdout,
+  Nats,
+  Kafka,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Replay {
+  #[arg(
+    long,
+    value_enum,
+    help = "Write events as JSON lines to stdout, publish them to a NATS server, or produce them to Kafka through a Kafka REST proxy."
+  )]
+  sink: SinkKind,
+  #[arg(long, default_value_t = 0, help = "Replay events from block <FROM>.")]
+  from: u32,
+  #[arg(
+    long,
+    default_value_t = 1000,
+    help = "Read and deliver <BATCH_SIZE> events at a time."
+  )]
+  batch_size: usize,
+  #[arg(
+    long,
+    help = "Record the next undelivered event in <CHECKPOINT> after every acknowledged batch. Rerunning with the same file resumes the replay."
+  )]
+  checkpoint: Option<PathBuf>,
+  #[arg(
+    long,
+    default_value = "127.0.0.1:4222",
+    help = "Connect to the NATS server at <NATS_ADDRESS>."
+  )]
+  nats_address: String,
+  #[arg(
+    long,
+    default_value = "http://127.0.0.1:8082",
+    help = "Produce to the Kafka REST proxy at <KAFKA_REST_URL>."
+  )]
+  kafka_rest_url: String,
+  #[arg(
+    long,
+    default_value = "bones.events",
+    help = "Publish to NATS subject or Kafka topic <TOPIC>."
+  )]
+  topic: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub resumed_from: Option<String>,
+  pub delivered: usize,
+  pub last: Option<String>,
+}
+
+/// A message on the bus. `key` is the event's position in the event log, so
+/// consumers can discard events delivered again after a resumed replay.
+#[derive(Serialize)]
+struct Message<'a> {
+  key: String,
+  event: &'a Event,
+}
+
+impl<'a> Message<'a> {
+  fn new(event: &'a Event) -> Self {
+    Self {
+      key: format!("{}:{}", event.block_height, event.event_index),
+      event,
+    }
+  }
+}
+
+/// A message bus that events are delivered to in batches. `deliver` only
+/// returns once the bus has acknowledged the whole batch.
+trait Sink {
+  fn deliver(&mut self, messages: &[Message]) -> Result;
+}
+
+struct Stdout;
+
+impl Sink for Stdout {
+  fn deliver(&mut self, messages: &[Message]) -> Result {
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    for message in messages {
+      serde_json::to_writer(&mut stdout, message)?;
+      writeln!(stdout)?;
+    }
+    stdout.flush()?;
+    Ok(())
+  }
+}
+
+/// Publishes with the NATS text protocol. A batch is acknowledged by the
+/// `PONG` the server sends after processing every preceding `PUB`.
+struct Nats {
+  reader: BufReader<TcpStream>,
+  writer: BufWriter<TcpStream>,
+  subject: String,
+}
+
+impl Nats {
+  fn connect(address: &str, subject: String) -> Result<Self> {
+    let stream = TcpStream::connect(address)
+      .with_context(|| format!("failed to connect to NATS server at `{address}`"))?;
+
+    let mut nats = Self {
+      reader: BufReader::new(stream.try_clone()?),
+      writer: BufWriter::new(stream),
+      subject,
+    };
+
+    let info = nats.read_line()?;
+    ensure!(
+      info.starts_with("INFO "),
+      "unexpected greeting from NATS server: {info}"
+    );
+
+    write!(
+      nats.writer,
+      "CONNECT {{\"verbose\":false,\"pedantic\":false,\"name\":\"ord\"}}\r\n"
+    )?;
+
+    Ok(nats)
+  }
+
+  fn read_line(&mut self) -> Result<String> {
+    let mut line = String::new();
+    if self.reader.read_line(&mut line)? == 0 {
+      bail!("NATS server closed the connection");
+    }
+    Ok(line.trim_end().into())
+  }
+}
+
+impl Sink for Nats {
+  fn deliver(&mut self, messages: &[Message]) -> Result {
+    for message in messages {
+      let payload = serde_json::to_vec(message)?;
+      write!(self.writer, "PUB {} {}\r\n", self.subject, payload.len())?;
+      self.writer.write_all(&payload)?;
+      self.writer.write_all(b"\r\n")?;
+    }
+
+    self.writer.write_all(b"PING\r\n")?;
+    self.writer.flush()?;
+
+    loop {
+      match self.read_line()?.as_str() {
+        "PONG" => return Ok(()),
+        "PING" => {
+          self.writer.write_all(b"PONG\r\n")?;
+          self.writer.flush()?;
+        }
+        line if line.starts_with("-ERR") => bail!("NATS server error: {line}"),
+        _ => {}
+      }
+    }
+  }
+}
+
+/// Produces through the Kafka REST proxy v2 API, which acknowledges a batch
+/// once every record has been written to the topic.
+struct Kafka {
+  client: reqwest::blocking::Client,
+  url: String,
+}
+
+#[derive(Serialize)]
+struct KafkaRecords<'a> {
+  records: Vec<KafkaRecord<'a>>,
+}
+
+#[derive(Serialize)]
+struct KafkaRecord<'a> {
+  key: &'a str,
+  value: &'a Message<'a>,
+}
+
+impl Sink for Kafka {
+  fn deliver(&mut self, messages: &[Message]) -> Result {
+    let records = KafkaRecords {
+      records: messages
+        .iter()
+        .map(|message| KafkaRecord {
+          key: &message.key,
+          value: message,
+        })
+        .collect(),
+    };
+
+    self
+      .client
+      .post(&self.url)
+      .header(
+        reqwest::header::CONTENT_TYPE,
+        "application/vnd.kafka.json.v2+json",
+      )
+      .body(serde_json::to_vec(&records)?)
+      .send()?
+      .error_for_status()?;
+
+    Ok(())
+  }
+}
+
+impl Replay {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    if self.batch_size == 0 {
+      bail!("batch size must be greater than zero");
+    }
+
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    let resumed_from = match &self.checkpoint {
+      Some(path) => Self::load_checkpoint(path)?,
+      None => None,
+    };
+
+    let mut sink: Box<dyn Sink> = match self.sink {
+      SinkKind::Stdout => Box::new(Stdout),
+      SinkKind::Nats => Box::new(Nats::connect(&self.nats_address, self.topic.clone())?),
+      SinkKind::Kafka => Box::new(Kafka {
+        client: reqwest::blocking::Client::new(),
+        url: format!(
+          "{}/topics/{}",
+          self.kafka_rest_url.trim_end_matches('/'),
+          self.topic
+        ),
+      }),
+    };
+
+    let output = self.replay(&index, sink.as_mut(), resumed_from)?;
+
+    // stdout carries the events themselves
+    if self.sink != SinkKind::Stdout {
+      print_json(output)?;
+    }
+
+    Ok(Box::new(Empty {}))
+  }
+
+  fn replay(
+    &self,
+    index: &Index,
+    sink: &mut dyn Sink,
+    resumed_from: Option<(u32, u32)>,
+  ) -> Result<Output> {
+    let filter = EventFilter {
+      from: self.from,
+      ..default()
+    };
+
+    let mut cursor = resumed_from;
+    let mut delivered = 0;
+    let mut last = None;
+
+    loop {
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        break;
+      }
+
+      let (events, next) = index.filter_events(&filter, cursor, self.batch_size)?;
+
+      if events.is_empty() {
+        break;
+      }
+
+      let messages = events.iter().map(Message::new).collect::<Vec<Message>>();
+
+      sink.deliver(&messages)?;
+
+      delivered += messages.len();
+      last = messages.last().map(|message| message.key.clone());
+
+      if let Some(path) = &self.checkpoint {
+        let resume_at = next.unwrap_or_else(|| {
+          let event = events.last().unwrap();
+          (event.block_height, event.event_index + 1)
+        });
+        Self::save_checkpoint(path, resume_at)?;
+      }
+
+      match next {
+        Some(next) => cursor = Some(next),
+        None => break,
+      }
+    }
+
+    Ok(Output {
+      resumed_from: resumed_from.map(|(height, index)| format!("{height}:{index}")),
+      delivered,
+      last,
+    })
+  }
+
+  fn load_checkpoint(path: &Path) -> Result<Option<(u32, u32)>> {
+    let checkpoint = match fs::read_to_string(path) {
+      Ok(checkpoint) => checkpoint,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+      Err(err) => return Err(err.into()),
+    };
+
+    let invalid = || anyhow!("invalid checkpoint file `{}`", path.display());
+
+    let (height, index) = checkpoint.trim().split_once(':').ok_or_else(invalid)?;
+
+    Ok(Some((
+      height.parse().map_err(|_| invalid())?,
+      index.parse().map_err(|_| invalid())?,
+    )))
+  }
+
+  fn save_checkpoint(path: &Path, (height, index): (u32, u32)) -> Result {
+    // write to a temporary file first, so a crash never leaves a truncated file
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, format!("{height}:{index}\n"))?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::relics::RelicId, std::net::TcpListener};
+
+  fn event(block_height: u32, event_index: u32) -> Event {
+    Event {
+      block_height,
+      event_index,
+      txid: Txid::all_zeros(),
+      info: EventInfo::RelicMinted {
+        relic_id: RelicId { block: 1, tx: 0 },
+        amount: 1,
+      },
+    }
+  }
+
+  #[test]
+  fn checkpoints_round_trip() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("checkpoint");
+
+    assert_eq!(Replay::load_checkpoint(&path).unwrap(), None);
+
+    Replay::save_checkpoint(&path, (10, 3)).unwrap();
+    assert_eq!(Replay::load_checkpoint(&path).unwrap(), Some((10, 3)));
+
+    fs::write(&path, "foo").unwrap();
+    assert!(Replay::load_checkpoint(&path).is_err());
+  }
+
+  #[test]
+  fn nats_batches_are_acknowledged_by_pong() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let server = thread::spawn(move || {
+      let (stream, _) = listener.accept().unwrap();
+      let mut reader = BufReader::new(stream.try_clone().unwrap());
+      let mut writer = stream;
+
+      writer.write_all(b"INFO {}\r\n").unwrap();
+
+      let mut payloads = Vec::new();
+      loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let line = line.trim_end();
+
+        if let Some(args) = line.strip_prefix("PUB ") {
+          let (subject, len) = args.split_once(' ').unwrap();
+          assert_eq!(subject, "bones.events");
+          let mut payload = vec![0; len.parse::<usize>().unwrap() + 2];
+          io::Read::read_exact(&mut reader, &mut payload).unwrap();
+          payload.truncate(payload.len() - 2);
+          payloads.push(payload);
+        } else if line == "PING" {
+          writer.write_all(b"PONG\r\n").unwrap();
+          return payloads;
+        }
+      }
+    });
+
+    let mut nats = Nats::connect(&address, "bones.events".into()).unwrap();
+
+    let events = [event(1, 0), event(1, 1)];
+    nats
+      .deliver(&events.iter().map(Message::new).collect::<Vec<Message>>())
+      .unwrap();
+
+    let payloads = server
+      .join()
+      .unwrap()
+      .into_iter()
+      .map(|payload| serde_json::from_slice::<serde_json::Value>(&payload).unwrap())
+      .collect::<Vec<serde_json::Value>>();
+
+    assert_eq!(payloads.len(), 2);
+    assert_eq!(payloads[0]["key"], "1:0");
+    assert_eq!(payloads[1]["key"], "1:1");
+    assert_eq!(
+      serde_json::from_value::<Event>(payloads[1]["event"].clone()).unwrap(),
+      event(1, 1)
+    );
+  }
+}
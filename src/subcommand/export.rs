@@ -0,0 +1,17 @@
+use super::*;
+
+pub mod content;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Export {
+  #[command(about = "Export inscription contents for pinning services")]
+  Content(content::Content),
+}
+
+impl Export {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    match self {
+      Self::Content(content) => content.run(options),
+    }
+  }
+}
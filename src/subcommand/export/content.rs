@@ -0,0 +1,437 @@
+use {
+  super::*,
+  crate::index::entry::InscriptionEntry,
+  bitcoin::hashes::{sha256, Hash},
+  ciborium::Value,
+  clap::ValueEnum,
+  std::io::{BufWriter, Write},
+};
+
+const MANIFEST: &str = "manifest.jsonl";
+
+/// multicodec code for raw binary blocks
+const RAW: u8 = 0x55;
+/// multihash code for sha2-256
+const SHA2_256: u8 = 0x12;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+  Car,
+  Dir,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Content {
+  #[arg(
+    long,
+    value_enum,
+    default_value_t = Format::Car,
+    help = "Write contents as CARv1 files or as a directory tree."
+  )]
+  format: Format,
+  #[arg(
+    long,
+    default_value_t = 0,
+    help = "Only export inscriptions created at or after <SINCE_HEIGHT>."
+  )]
+  since_height: u32,
+  #[arg(
+    long,
+    default_value_t = 1000,
+    help = "Scan <CHUNK_SIZE> inscriptions at a time, and pack each chunk into one CAR file."
+  )]
+  chunk_size: usize,
+  #[arg(
+    long,
+    help = "Write export to directory <OUTPUT>. Rerunning with the same directory resumes the export."
+  )]
+  output: PathBuf,
+}
+
+/// One line of `manifest.jsonl`, written once the content is on disk.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+  pub sequence_number: u32,
+  pub inscription_id: InscriptionId,
+  pub number: u64,
+  pub height: u32,
+  pub content_type: Option<String>,
+  pub size: usize,
+  pub sha256: String,
+  pub cid: String,
+  /// file containing the content, relative to the output directory
+  pub path: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub resumed_after: Option<u32>,
+  pub exported: usize,
+  pub last_sequence_number: Option<u32>,
+}
+
+struct Item {
+  entry: InscriptionEntry,
+  content_type: Option<String>,
+  body: Vec<u8>,
+  sha256: sha256::Hash,
+}
+
+impl Item {
+  fn cid(&self) -> Vec<u8> {
+    let mut cid = vec![1, RAW, SHA2_256, 32];
+    cid.extend_from_slice(&self.sha256.into_inner());
+    cid
+  }
+
+  fn manifest_entry(&self, path: String) -> ManifestEntry {
+    ManifestEntry {
+      sequence_number: self.entry.sequence_number,
+      inscription_id: self.entry.id,
+      number: self.entry.inscription_number,
+      height: self.entry.height,
+      content_type: self.content_type.clone(),
+      size: self.body.len(),
+      sha256: self.sha256.to_string(),
+      cid: format_cid(&self.cid()),
+      path,
+    }
+  }
+}
+
+impl Content {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    if self.chunk_size == 0 {
+      bail!("chunk size must be greater than zero");
+    }
+
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    if self.format == Format::Dir {
+      fs::create_dir_all(self.output.join("content"))?;
+    } else {
+      fs::create_dir_all(&self.output)?;
+    }
+
+    let manifest_path = self.output.join(MANIFEST);
+
+    let resumed_after = Self::resume(&manifest_path)?;
+
+    let mut manifest = fs::OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(&manifest_path)
+      .with_context(|| format!("failed to open manifest `{}`", manifest_path.display()))?;
+
+    let mut next = resumed_after.map(|n| n + 1).unwrap_or_default();
+    let mut exported = 0;
+    let mut last_sequence_number = resumed_after;
+
+    loop {
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        break;
+      }
+
+      let entries = index.get_inscription_entries(next, self.chunk_size)?;
+
+      let Some(last) = entries.last() else {
+        break;
+      };
+
+      next = last.sequence_number + 1;
+
+      let mut items = Vec::new();
+
+      for entry in entries {
+        if entry.height < self.since_height {
+          continue;
+        }
+
+        let Some(inscription) = index.get_inscription_by_id(entry.id)? else {
+          continue;
+        };
+
+        let content_type = inscription.content_type().map(str::to_string);
+
+        let Some(body) = inscription.into_body() else {
+          continue;
+        };
+
+        items.push(Item {
+          sha256: sha256::Hash::hash(&body),
+          entry,
+          content_type,
+          body,
+        });
+      }
+
+      if items.is_empty() {
+        continue;
+      }
+
+      let lines = match self.format {
+        Format::Car => self.write_car(&items)?,
+        Format::Dir => self.write_dir(&items)?,
+      };
+
+      // the manifest is only extended once the contents are on disk, so a
+      // crashed export resumes after the last complete line
+      let mut buffer = Vec::new();
+      for line in lines {
+        serde_json::to_writer(&mut buffer, &line)?;
+        buffer.push(b'\n');
+      }
+      manifest.write_all(&buffer)?;
+      manifest.sync_data()?;
+
+      exported += items.len();
+      last_sequence_number = items.last().map(|item| item.entry.sequence_number);
+    }
+
+    print_json(Output {
+      resumed_after,
+      exported,
+      last_sequence_number,
+    })?;
+
+    Ok(Box::new(Empty {}))
+  }
+
+  /// Drops an incomplete trailing line from the manifest and returns the
+  /// sequence number of the last exported inscription.
+  fn resume(path: &Path) -> Result<Option<u32>> {
+    let mut manifest = match fs::read(path) {
+      Ok(manifest) => manifest,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+      Err(err) => return Err(err.into()),
+    };
+
+    let complete = manifest
+      .iter()
+      .rposition(|byte| *byte == b'\n')
+      .map(|i| i + 1)
+      .unwrap_or_default();
+
+    if complete < manifest.len() {
+      fs::OpenOptions::new()
+        .write(true)
+        .open(path)?
+        .set_len(u64::try_from(complete)?)?;
+      manifest.truncate(complete);
+    }
+
+    let Some(line) = manifest
+      .split(|byte| *byte == b'\n')
+      .filter(|line| !line.is_empty())
+      .last()
+    else {
+      return Ok(None);
+    };
+
+    Ok(Some(
+      serde_json::from_slice::<ManifestEntry>(line)
+        .context("invalid manifest entry")?
+        .sequence_number,
+    ))
+  }
+
+  fn write_car(&self, items: &[Item]) -> Result<Vec<ManifestEntry>> {
+    let name = format!("content-{:010}.car", items[0].entry.sequence_number);
+    let path = self.output.join(&name);
+    let partial = self.output.join(format!("{name}.partial"));
+
+    let mut cids = BTreeSet::new();
+    let mut blocks = Vec::new();
+    for item in items {
+      let cid = item.cid();
+      if cids.insert(cid.clone()) {
+        blocks.push((cid, &item.body));
+      }
+    }
+
+    let mut writer = BufWriter::new(File::create(&partial)?);
+
+    let header = car_header(blocks.iter().map(|(cid, _)| cid.as_slice()))?;
+    write_section(&mut writer, &[header.as_slice()])?;
+
+    for (cid, body) in blocks {
+      write_section(&mut writer, &[cid.as_slice(), body.as_slice()])?;
+    }
+
+    writer.into_inner()?.sync_all()?;
+
+    fs::rename(&partial, &path)?;
+
+    Ok(
+      items
+        .iter()
+        .map(|item| item.manifest_entry(name.clone()))
+        .collect(),
+    )
+  }
+
+  fn write_dir(&self, items: &[Item]) -> Result<Vec<ManifestEntry>> {
+    items
+      .iter()
+      .map(|item| {
+        let name = format!("content/{}", item.entry.id);
+        fs::write(self.output.join(&name), &item.body)?;
+        Ok(item.manifest_entry(name))
+      })
+      .collect()
+  }
+}
+
+/// CARv1 header, listing every block as a root so they are all pinned on import
+fn car_header<'a>(roots: impl Iterator<Item = &'a [u8]>) -> Result<Vec<u8>> {
+  let header = Value::Map(vec![
+    (
+      Value::Text("roots".into()),
+      Value::Array(
+        roots
+          .map(|cid| {
+            // DAG-CBOR links are CBOR tag 42 with a multibase identity prefix
+            let mut bytes = vec![0];
+            bytes.extend_from_slice(cid);
+            Value::Tag(42, Box::new(Value::Bytes(bytes)))
+          })
+          .collect(),
+      ),
+    ),
+    (Value::Text("version".into()), Value::Integer(1.into())),
+  ]);
+
+  let mut encoded = Vec::new();
+  ciborium::into_writer(&header, &mut encoded)?;
+  Ok(encoded)
+}
+
+/// Writes a varint length prefix followed by `parts`.
+fn write_section(writer: &mut impl Write, parts: &[&[u8]]) -> Result {
+  let mut prefix = Vec::new();
+  crate::relics::varint::encode_to_vec(
+    parts.iter().map(|part| part.len() as u128).sum(),
+    &mut prefix,
+  );
+  writer.write_all(&prefix)?;
+  for part in parts {
+    writer.write_all(part)?;
+  }
+  Ok(())
+}
+
+/// Multibase base32 string form of a binary CID, as used by IPFS tooling.
+fn format_cid(cid: &[u8]) -> String {
+  const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+  let mut encoded = String::from("b");
+  let mut buffer = 0u16;
+  let mut bits = 0;
+
+  for byte in cid {
+    buffer = (buffer << 8) | u16::from(*byte);
+    bits += 8;
+    while bits >= 5 {
+      bits -= 5;
+      encoded.push(ALPHABET[usize::from((buffer >> bits) & 0x1f)].into());
+    }
+  }
+
+  if bits > 0 {
+    encoded.push(ALPHABET[usize::from((buffer << (5 - bits)) & 0x1f)].into());
+  }
+
+  encoded
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn cids_are_formatted_as_base32() {
+    let item = Item {
+      entry: InscriptionEntry {
+        charms: 0,
+        fee: 0,
+        height: 0,
+        id: inscription_id(1),
+        inscription_number: 0,
+        parents: Vec::new(),
+        sat: None,
+        sequence_number: 0,
+        timestamp: 0,
+      },
+      content_type: None,
+      body: b"hello".to_vec(),
+      sha256: sha256::Hash::hash(b"hello"),
+    };
+
+    // matches `ipfs add --raw-leaves --cid-version 1` for the same bytes
+    assert_eq!(
+      format_cid(&item.cid()),
+      "bafkreibm6jg3ux5qumhcn2b3flc3tyu6dmlb4xa7u5bf44yegnrjhc4yeq"
+    );
+  }
+
+  #[test]
+  fn sections_are_length_prefixed() {
+    let mut section = Vec::new();
+    write_section(&mut section, &[[1; 100].as_slice(), [2; 100].as_slice()]).unwrap();
+    assert_eq!(&section[..2], &[0xc8, 0x01]);
+    assert_eq!(section.len(), 202);
+  }
+
+  #[test]
+  fn car_header_lists_roots() {
+    let cid = [1, RAW, SHA2_256, 32, 0, 0];
+
+    let header: Value =
+      ciborium::from_reader(car_header(iter::once(cid.as_slice())).unwrap().as_slice()).unwrap();
+
+    assert_eq!(
+      header,
+      Value::Map(vec![
+        (
+          Value::Text("roots".into()),
+          Value::Array(vec![Value::Tag(
+            42,
+            Box::new(Value::Bytes(vec![0, 1, RAW, SHA2_256, 32, 0, 0]))
+          )])
+        ),
+        (Value::Text("version".into()), Value::Integer(1.into())),
+      ])
+    );
+  }
+
+  #[test]
+  fn resume_drops_incomplete_lines() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join(MANIFEST);
+
+    assert_eq!(Content::resume(&path).unwrap(), None);
+
+    let entry = ManifestEntry {
+      sequence_number: 7,
+      inscription_id: inscription_id(1),
+      number: 7,
+      height: 1,
+      content_type: None,
+      size: 0,
+      sha256: String::new(),
+      cid: String::new(),
+      path: String::new(),
+    };
+
+    fs::write(
+      &path,
+      format!("{}\n{{\"sequence_n", serde_json::to_string(&entry).unwrap()),
+    )
+    .unwrap();
+
+    assert_eq!(Content::resume(&path).unwrap(), Some(7));
+    assert!(fs::read_to_string(&path).unwrap().ends_with("}\n"));
+  }
+}
@@ -1,10 +1,33 @@
 use super::*;
 
+mod compact;
+mod stats;
 mod update;
+mod verify;
 
-pub(crate) fn run(options: Options) -> SubcommandResult {
-  let index = Index::open(&options)?;
+#[derive(Debug, Parser)]
+pub(crate) enum IndexSubcommand {
+  #[command(about = "Update the index")]
+  Update,
+  #[command(about = "Report entry counts and sizes of the index tables")]
+  Stats,
+  #[command(about = "Compact the index to reclaim unused space")]
+  Compact,
+  #[command(about = "Check the relic state of the index for inconsistencies")]
+  Verify(verify::Verify),
+}
+
+pub(crate) fn run(subcommand: Option<IndexSubcommand>, options: Options) -> SubcommandResult {
+  match subcommand {
+    None => {
+      let index = Index::open(&options)?;
 
-  index.update()?;
-  Ok(Box::new(()))
+      index.update()?;
+      Ok(Box::new(()))
+    }
+    Some(IndexSubcommand::Update) => update::run(options),
+    Some(IndexSubcommand::Stats) => stats::run(options),
+    Some(IndexSubcommand::Compact) => compact::run(options),
+    Some(IndexSubcommand::Verify(verify)) => verify.run(options),
+  }
 }
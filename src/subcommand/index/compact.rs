@@ -0,0 +1,39 @@
+use super::*;
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub savepoints_deleted: usize,
+  pub size_before: u64,
+  pub size_after: u64,
+}
+
+pub(crate) fn run(options: Options) -> SubcommandResult {
+  let mut index = Index::open(&options)?;
+
+  let size_before = fs::metadata(index.path())?.len();
+
+  eprintln!("Deleting reorg savepoints…");
+  let savepoints_deleted = index.delete_savepoints()?;
+  eprintln!("Deleted {savepoints_deleted} savepoint(s)");
+
+  eprintln!(
+    "Compacting {} ({size_before} bytes)…",
+    index.path().display()
+  );
+  let start = Instant::now();
+  index.compact()?;
+
+  let size_after = fs::metadata(index.path())?.len();
+  eprintln!(
+    "Compacted to {size_after} bytes in {:.1}s",
+    start.elapsed().as_secs_f64()
+  );
+
+  print_json(Output {
+    savepoints_deleted,
+    size_before,
+    size_after,
+  })?;
+
+  Ok(Box::new(Empty {}))
+}
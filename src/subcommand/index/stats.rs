@@ -0,0 +1,26 @@
+use {super::*, crate::index::TableInfo};
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub index_path: PathBuf,
+  pub index_file_size: u64,
+  pub stored_bytes: u64,
+  pub fragmented_bytes: u64,
+  pub tables: Vec<TableInfo>,
+}
+
+pub(crate) fn run(options: Options) -> SubcommandResult {
+  let index = Index::open(&options)?;
+
+  let tables = index.table_info()?;
+
+  print_json(Output {
+    index_path: index.path().into(),
+    index_file_size: fs::metadata(index.path())?.len(),
+    stored_bytes: tables.iter().map(|table| table.stored_bytes).sum(),
+    fragmented_bytes: tables.iter().map(|table| table.fragmented_bytes).sum(),
+    tables,
+  })?;
+
+  Ok(Box::new(Empty {}))
+}
@@ -0,0 +1,34 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Verify {
+  #[arg(long, help = "Verify the index as is, without updating it first.")]
+  no_update: bool,
+}
+
+impl Verify {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    if !index.has_relic_index() {
+      bail!("verify requires index created with `--index-relics` flag");
+    }
+
+    if !self.no_update {
+      index.update()?;
+    }
+
+    let report = index.verify_relic_integrity()?;
+
+    print_json(&report)?;
+
+    if !report.ok() {
+      bail!(
+        "index failed verification with {} violation(s)",
+        report.violations.len()
+      );
+    }
+
+    Ok(Box::new(Empty {}))
+  }
+}
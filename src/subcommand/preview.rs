@@ -41,7 +41,7 @@ impl Preview {
     );
 
     let options = Options {
-      chain_argument: Chain::Regtest,
+      chain_argument: Some(Chain::Regtest),
       dogecoin_data_dir: Some(dogecoin_data_dir),
       data_dir: Some(tmpdir.path().into()),
       rpc_url: Some(format!("127.0.0.1:{rpc_port}")),
@@ -1,20 +1,58 @@
-use crate::index::event::{EventInfo, EventWithInscriptionInfo, EventWithRelicInscriptionInfo};
+use crate::index::event::{
+  Event, EventFilter, EventInfo, EventKind, EventWithInscriptionInfo,
+  EventWithRelicInscriptionInfo, FormattedEvent,
+};
 use crate::templates::{InscriptionCompactHtml, RelicShibescriptionJson};
 use {
   self::{
+    admin::{Admin, ACTOR_HEADER},
+    byte_range::ByteRange,
+    cache::{CacheScope, ResponseCache},
+    coin_selection::Candidate,
+    content_encoding::ContentEncoding,
+    curation::{Curation, CurationList},
+    delegates::Delegates,
     deserialize_from_str::DeserializeFromStr,
     error::{OptionExt, ServerError, ServerResult},
+    json::{to_string, AmountFormat, Json, AMOUNT_FORMAT_HEADER},
+    leaderboard::Leaderboards,
+    ownership::{Attestation, Ownership},
+    standby::Standby,
+    webhooks::Webhooks,
   },
   super::*,
   crate::{
     charm::Charm,
-    index::{entry::Entry, relics_entry::RelicOwner},
+    index::{
+      address_label::AddressLabel,
+      audit::AuditEntry,
+      block_activity::BlockRelicActivity,
+      chest_entry::ChestEntry,
+      digest::DigestPeriod,
+      distribution::RelicDistribution,
+      entry::{script_hash, Entry},
+      owner_change::OwnerChange,
+      portfolio::RelicPortfolio,
+      relics_entry::{ActivityMetric, RelicEntry, RelicFees, RelicOwner, SwapHop},
+      sealing::{EnshriningCheck, SealingHistory, SealingStats},
+      syndicate_entry::SyndicateEntry,
+      webhook::{Watch, Webhook},
+      EventOutboxInfo,
+    },
     page_config::PageConfig,
-    relics::{RelicId, SpacedRelic},
+    relics::{
+      Enshrining, Keepsake, MintTerms, RelicArtifact, RelicCenotaph, RelicError, RelicId,
+      SpacedRelic, SyndicateId,
+    },
     subcommand::server::accept_json::AcceptJson,
     templates::{
-      relic::RelicHtml, relic_events::RelicEventsHtml, relics::RelicsHtml, sealing::SealingHtml,
-      sealings::SealingsHtml, syndicate::SyndicateHtml, syndicates::SyndicatesHtml,
+      relic::{RelicEntryHtml, RelicHtml, RelicMetaJson},
+      relic_events::RelicEventsHtml,
+      relics::RelicsHtml,
+      sealing::SealingHtml,
+      sealings::SealingsHtml,
+      syndicate::SyndicateHtml,
+      syndicates::SyndicatesHtml,
       AddressOutputJson, BlockHtml, BlockJson, HomeHtml, InputHtml, InscriptionByAddressJson,
       InscriptionDecoded, InscriptionDecodedHtml, InscriptionHtml, InscriptionJson,
       InscriptionsHtml, OutputCompactJson, OutputHtml, OutputJson, PageContent, PageHtml,
@@ -25,14 +63,18 @@ use {
   },
   axum::{
     body,
-    extract::{Extension, Json, Path, Query},
+    extract::{Extension, Path, Query},
     headers::UserAgent,
     http::{header, HeaderMap, HeaderValue, StatusCode, Uri},
-    response::{IntoResponse, Redirect, Response},
-    routing::{get, post},
+    middleware::{self, Next},
+    response::{
+      sse::{self, KeepAlive, Sse},
+      IntoResponse, Redirect, Response,
+    },
+    routing::{delete, get, post},
     Router, TypedHeader,
   },
-  axum_server::Handle,
+  axum_server::{tls_rustls::RustlsConfig, Handle},
   http::HeaderName,
   linked_hash_map::LinkedHashMap,
   rayon::prelude::{IntoParallelRefIterator, ParallelIterator},
@@ -44,64 +86,69 @@ use {
     caches::DirCache,
     AcmeConfig,
   },
-  serde_json::{json, to_string},
+  serde_json::json,
   std::collections::HashMap,
-  std::{cmp::Ordering, str},
-  tokio_stream::StreamExt,
+  std::{cmp::Ordering, convert::Infallible, str},
+  tokio::sync::{broadcast, mpsc},
+  tokio_stream::{Stream, StreamExt},
   tower_http::{
-    compression::CompressionLayer,
-    cors::{Any, CorsLayer},
+    compression::{
+      predicate::{DefaultPredicate, Predicate},
+      CompressionLayer,
+    },
+    cors::{AllowOrigin, Any, CorsLayer},
     set_header::SetResponseHeaderLayer,
   },
+  url::Url,
 };
 
 mod accept_json;
+mod admin;
+mod byte_range;
+mod cache;
+mod coin_selection;
+mod content_encoding;
+mod curation;
+mod delegates;
+mod digests;
 mod error;
+#[cfg(feature = "graphql")]
+mod graphql;
+mod json;
+mod leaderboard;
+mod ownership;
 pub(crate) mod query;
+mod standby;
+mod streaming;
+mod webhooks;
 
-// Helper function to get transaction details
+// Helper function to get transaction details, the returned flag is set if the
+// spent output is unavailable because Dogecoin Core is pruned
 fn get_transaction_details(
   input: &TxIn,
   index: &Arc<Index>,
   page_config: &Arc<PageConfig>,
-) -> (String, String) {
+) -> (String, String, bool) {
   let txid = input.previous_output.txid;
   let result = if txid
     == Txid::from_str("0000000000000000000000000000000000000000000000000000000000000000").unwrap()
   {
-    (String::new(), String::new())
+    (String::new(), String::new(), false)
   } else {
     index
-      .get_transaction(txid)
-      .map(|transaction| {
-        transaction
-          .map(|t| {
-            let value = t
-              .output
-              .clone()
-              .into_iter()
-              .nth(input.previous_output.vout as usize)
-              .map(|output| output.value.to_string())
-              .unwrap_or_else(|| "0".to_string());
-
-            let script_pubkey = t
-              .output
-              .into_iter()
-              .nth(input.previous_output.vout as usize)
-              .map(|output| output.script_pubkey)
-              .unwrap_or_else(|| Script::new());
-
-            let address = page_config
-              .chain
-              .address_from_script(&script_pubkey)
-              .map(|address| address.to_string())
-              .unwrap_or(String::new());
-
-            (value, address)
-          })
-          .unwrap_or((String::new(), String::new()))
+      .get_txout(input.previous_output)
+      .ok()
+      .flatten()
+      .map(|output| {
+        let address = page_config
+          .chain
+          .address_from_script(&output.script_pubkey)
+          .map(|address| address.to_string())
+          .unwrap_or(String::new());
+
+        (output.value.to_string(), address, false)
       })
-      .unwrap_or((String::new(), String::new()))
+      .unwrap_or((String::new(), String::new(), index.is_node_pruned()))
   };
 
   result
@@ -113,6 +160,15 @@ pub(crate) struct InscriptionAddressJson {
   pub(crate) total_inscriptions: usize,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct InscriptionCreatedJson {
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) content_type: Option<String>,
+  pub(crate) charms: Vec<Charm>,
+  pub(crate) location: Option<SatPoint>,
+  pub(crate) block_height: u32,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct UtxoAddressJson {
   pub(crate) utxos: Vec<Utxo>,
@@ -126,6 +182,8 @@ struct UtxoBalanceQuery {
   limit: Option<usize>,
   show_all: Option<bool>,
   show_unsafe: Option<bool>,
+  /// include outputs holding bones, which are skipped by default
+  show_bones: Option<bool>,
   value_filter: Option<u64>,
 }
 
@@ -139,15 +197,762 @@ struct OutputsPostQuery {
   outputs: Vec<String>,
 }
 
+const INDEX_HEIGHT_HEADER: &str = "x-index-height";
+
+const INDEX_BLOCK_HASH_HEADER: &str = "x-index-block-hash";
+
+const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+/// Number of inscriptions looked up per request to add their details to
+/// events, after which events are returned without them.
+const MAX_EVENT_INSCRIPTION_LOOKUPS: usize = 1000;
+
+/// Blocks in a day at Dogecoin's one minute block interval, the window of the
+/// volume reported by `/pairs`.
+const PAIR_VOLUME_BLOCKS: u32 = 1440;
+
+/// Window of `/bone/:bone/twap` without a `window` parameter, a day of blocks.
+const DEFAULT_TWAP_WINDOW: u32 = 1440;
+
+/// Largest window accepted by `/bone/:bone/twap`, a week of blocks.
+const MAX_TWAP_WINDOW: u32 = 10080;
+
+/// Window of `/bones/leaderboard` if none is given, a day of blocks.
+const DEFAULT_LEADERBOARD_WINDOW: u32 = 1440;
+
+/// Largest window accepted by `/bones/leaderboard`, a week of blocks.
+const MAX_LEADERBOARD_WINDOW: u32 = 10080;
+
+/// Most inscriptions resolved by a single `/inscriptions/resolve` request.
+const MAX_RESOLVED_INSCRIPTIONS: usize = 1000;
+
+/// Most bones resolved by a single `/bones/resolve` request.
+const MAX_RESOLVED_RELICS: usize = 1000;
+
+/// More outputs than fit into a standard transaction.
+const MAX_ESTIMATED_OUTPUTS: usize = 10_000;
+
+/// Path prefix of the current JSON API, e.g. `/v1/bone/:bone`.
+const API_VERSION: &str = "v1";
+
+/// Date after which unversioned JSON paths may stop being served.
+const LEGACY_API_SUNSET: &str = "Thu, 01 Jul 2027 00:00:00 GMT";
+
+#[derive(Serialize)]
+struct ApiVersionsJson {
+  current: &'static str,
+  versions: Vec<&'static str>,
+  unversioned: UnversionedApiJson,
+}
+
+#[derive(Serialize)]
+struct UnversionedApiJson {
+  deprecated: bool,
+  sunset: &'static str,
+}
+
+/// Keepsake to estimate, for a transaction with `outputs` outputs besides its
+/// OP_RETURN output, at `fee_rate` sat/vB.
+#[derive(Deserialize)]
+struct EstimateKeepsakeRequest {
+  keepsake: Keepsake,
+  outputs: usize,
+  fee_rate: f64,
+}
+
+/// Proposed sealing and enshrining terms, see `Enshrining`.
+#[derive(Deserialize)]
+struct ValidateEnshriningRequest {
+  #[serde(rename = "bone")]
+  spaced_relic: SpacedRelic,
+  symbol: Option<char>,
+  subsidy: Option<u128>,
+  mint_terms: Option<MintTerms>,
+  #[serde(default)]
+  turbo: bool,
+  /// inputs of the sealing transaction, which pay the sealing fee
+  inputs: Option<Vec<OutPoint>>,
+}
+
+#[derive(Deserialize)]
+struct ProveOwnershipRequest {
+  bone: DeserializeFromStr<query::Relic>,
+  nonce: Option<String>,
+  signature: Option<String>,
+}
+
 #[derive(Deserialize)]
 struct JsonQuery {
   json: Option<bool>,
 }
 
+#[derive(Deserialize)]
+struct FormattedJsonQuery {
+  json: Option<bool>,
+  formatted: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct RelicBalancesQuery {
+  json: Option<bool>,
+  formatted: Option<bool>,
+  addresses: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct AddressedBalanceJson<T> {
+  amount: T,
+  /// address of the output holding the balance, if its script has one
+  address: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SupplyQuery {
+  from: Option<u32>,
+  to: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct HistoryQuery {
+  /// defaults to the current height
+  height: Option<u32>,
+  page: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct AddressRelicBalancesJson {
+  address: String,
+  height: u32,
+  #[serde(rename = "bones")]
+  relics: BTreeMap<SpacedRelic, Pile>,
+}
+
+#[derive(Serialize)]
+struct RelicHoldersJson {
+  id: RelicId,
+  #[serde(rename = "spaced_bone")]
+  spaced_relic: SpacedRelic,
+  height: u32,
+  holders: Vec<RelicHolderJson>,
+  more: bool,
+}
+
+#[derive(Serialize)]
+struct RelicHolderJson {
+  address: String,
+  amount: u128,
+}
+
+#[derive(Serialize)]
+struct SupplyPointJson {
+  height: u32,
+  circulating_supply: u128,
+  burned: u128,
+  mints: u128,
+  /// change since the previous point, or since before `from` for the first one
+  circulating_supply_delta: i128,
+  burned_delta: u128,
+  mints_delta: u128,
+}
+
+#[derive(Serialize)]
+struct RelicMintStatusJson {
+  id: RelicId,
+  #[serde(rename = "spaced_bone")]
+  spaced_relic: SpacedRelic,
+  /// whether a mint would succeed in the next block
+  mintable: bool,
+  mints: u128,
+  cap: u128,
+  remaining: u128,
+  /// amount minted by every mint, a transaction can mint once
+  amount: u128,
+  /// price of a mint in the base token, `None` for free mints
+  price: Option<u128>,
+  /// average number of mints per block over the last `window` blocks
+  mints_per_block: Option<f64>,
+  window: u64,
+  blocks_to_mint_out: Option<u64>,
+  mint_out_height: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct RelicSupplyJson {
+  id: RelicId,
+  #[serde(rename = "spaced_bone")]
+  spaced_relic: SpacedRelic,
+  from: u32,
+  to: u32,
+  /// one point per block that changed the supply
+  points: Vec<SupplyPointJson>,
+  /// whether there are more points after the last one, up to `to`
+  more: bool,
+}
+
+#[derive(Serialize)]
+struct RelicDistributionJson {
+  id: RelicId,
+  #[serde(rename = "spaced_bone")]
+  spaced_relic: SpacedRelic,
+  height: u32,
+  #[serde(flatten)]
+  distribution: RelicDistribution,
+}
+
+#[derive(Serialize)]
+struct RelicOwnerHistoryJson {
+  id: RelicId,
+  #[serde(rename = "spaced_bone")]
+  spaced_relic: SpacedRelic,
+  /// the sealing inscription, whose owner controls the bone
+  inscription: InscriptionId,
+  changes: Vec<OwnerChange>,
+}
+
+#[derive(Serialize)]
+struct RelicBurnJson {
+  block_height: u32,
+  txid: Txid,
+  amount: u128,
+}
+
+#[derive(Serialize)]
+struct RelicBurnsJson {
+  id: RelicId,
+  #[serde(rename = "spaced_bone")]
+  spaced_relic: SpacedRelic,
+  /// total burned, including burns without an event, like swap fees of
+  /// unsealed bones
+  burned: u128,
+  /// newest first
+  burns: Vec<RelicBurnJson>,
+  more: bool,
+  page_index: usize,
+}
+
+#[derive(Serialize)]
+struct BurnedRelicJson {
+  id: RelicId,
+  #[serde(rename = "spaced_bone")]
+  spaced_relic: SpacedRelic,
+  burned: u128,
+  max_supply: u128,
+  circulating_supply: u128,
+}
+
+#[derive(Deserialize)]
+struct TwapQuery {
+  window: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct ResolvedInscriptionJson {
+  id: InscriptionId,
+  /// the inscription whose content is served for `id`, if not `id` itself
+  delegate: Option<InscriptionId>,
+  /// effective content type served by `/content/<ID>`
+  content_type: Option<String>,
+  content_length: Option<usize>,
+  /// whether `/content/<ID>` serves a body
+  body: bool,
+}
+
+/// A bone identifier of a `/bones/resolve` request, and the bone it names or
+/// why it names none.
+#[derive(Serialize)]
+struct ResolvedRelicJson {
+  query: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  id: Option<RelicId>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  entry: Option<RelicEntryHtml>,
+  /// the inscription the bone is sealed to
+  #[serde(skip_serializing_if = "Option::is_none")]
+  owner: Option<InscriptionId>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LeaderboardQuery {
+  metric: ActivityMetric,
+  window: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct LeaderboardJson {
+  metric: ActivityMetric,
+  window: u32,
+  from: u32,
+  to: u32,
+  #[serde(rename = "bones")]
+  relics: Vec<LeaderboardEntryJson>,
+}
+
+#[derive(Serialize)]
+struct LeaderboardEntryJson {
+  rank: usize,
+  id: RelicId,
+  #[serde(rename = "spaced_bone")]
+  spaced_relic: SpacedRelic,
+  /// the ranked metric summed over the window
+  value: u128,
+  mints: u64,
+  swaps: u64,
+  /// base tokens traded by swaps
+  volume: u128,
+  /// base tokens paid for mints and in swap fees
+  fees: u128,
+}
+
+#[derive(Serialize)]
+struct RelicTwapJson {
+  id: RelicId,
+  #[serde(rename = "spaced_bone")]
+  spaced_relic: SpacedRelic,
+  window: u32,
+  from: u32,
+  to: u32,
+  /// blocks of the window in which the pool existed
+  blocks: u32,
+  /// time-weighted average price of one whole bone in base token units
+  twap: Option<u128>,
+  /// price at the end of block `to`
+  price: Option<u128>,
+}
+
+#[derive(Serialize)]
+struct RelicSubsidyJson {
+  id: RelicId,
+  #[serde(rename = "spaced_bone")]
+  spaced_relic: SpacedRelic,
+  subsidy: u128,
+  /// subsidy paid out to chests so far
+  paid: u128,
+  /// subsidy available for syndicate rewards so far, all of it without a schedule
+  released: u128,
+  locked: bool,
+  schedule: Option<SubsidyScheduleJson>,
+}
+
+#[derive(Serialize)]
+struct SubsidyScheduleJson {
+  amount: u128,
+  epoch: u64,
+  start: u64,
+  next_release_height: u64,
+  remaining_releases: u128,
+  final_release_height: u64,
+}
+
+#[derive(Serialize)]
+struct FeesPointJson {
+  height: u32,
+  /// fees paid in this block
+  fees: RelicFees,
+  /// fees paid up to the end of this block
+  cumulative: RelicFees,
+}
+
+#[derive(Serialize)]
+struct RelicFeesJson {
+  #[serde(rename = "spaced_bone", skip_serializing_if = "Option::is_none")]
+  spaced_relic: Option<SpacedRelic>,
+  from: u32,
+  to: u32,
+  /// fees paid from `from` to `to`
+  total: RelicFees,
+  /// fees paid up to the end of block `to`
+  cumulative: RelicFees,
+  /// one point per block that paid fees
+  blocks: Vec<FeesPointJson>,
+  /// whether there are more blocks after the last one, up to `to`
+  more: bool,
+}
+
+#[derive(Serialize)]
+struct ErrorCountsJson {
+  cenotaphs: u64,
+  /// failed operations by error
+  errors: BTreeMap<String, u64>,
+}
+
+impl ErrorCountsJson {
+  fn new(mut counts: BTreeMap<String, u64>) -> Self {
+    Self {
+      cenotaphs: counts.remove(RelicCenotaph::KIND).unwrap_or_default(),
+      errors: counts,
+    }
+  }
+}
+
+#[derive(Serialize)]
+struct ErrorStatsPointJson {
+  height: u32,
+  #[serde(flatten)]
+  counts: ErrorCountsJson,
+}
+
+#[derive(Serialize)]
+struct ErrorStatsJson {
+  from: u32,
+  to: u32,
+  /// counts summed over `blocks`
+  total: ErrorCountsJson,
+  /// one point per block with cenotaphs or failed operations
+  blocks: Vec<ErrorStatsPointJson>,
+  /// whether there are more blocks after the last one, up to `to`
+  more: bool,
+}
+
+#[derive(Deserialize)]
+struct RelicsQuery {
+  json: Option<bool>,
+  curated: Option<CurationList>,
+}
+
+#[derive(Deserialize)]
+struct DigestQuery {
+  period: Option<DigestPeriod>,
+}
+
+#[derive(Deserialize)]
+struct CachePurgeQuery {
+  scope: CacheScope,
+}
+
+#[derive(Serialize)]
+struct CurationJson {
+  list: CurationList,
+  #[serde(rename = "bones")]
+  relics: Vec<SpacedRelic>,
+}
+
+#[derive(Deserialize)]
+struct TokenListQuery {
+  curated: Option<CurationList>,
+}
+
+#[derive(Serialize)]
+struct TokenListJson {
+  name: String,
+  height: u32,
+  tags: BTreeMap<CurationList, TokenListTagJson>,
+  tokens: Vec<TokenJson>,
+}
+
+#[derive(Serialize)]
+struct TokenListTagJson {
+  name: String,
+  description: String,
+}
+
+#[derive(Serialize)]
+struct TokenJson {
+  id: RelicId,
+  name: SpacedRelic,
+  ticker: String,
+  symbol: Option<char>,
+  decimals: u8,
+  #[serde(rename = "logoURI", skip_serializing_if = "Option::is_none")]
+  logo_uri: Option<String>,
+  tags: Vec<CurationList>,
+}
+
+#[derive(Serialize)]
+struct PairsJson {
+  height: u32,
+  pairs: Vec<PairJson>,
+}
+
+#[derive(Serialize)]
+struct PairJson {
+  /// `BASE_TICKER`, e.g. `BONE_FOOBAR`
+  ticker_id: String,
+  base_currency: String,
+  target_currency: String,
+  pool_id: RelicId,
+  /// base tokens per whole target token, in the base token's smallest unit
+  last_price: u128,
+  /// amounts swapped in the last `PAIR_VOLUME_BLOCKS` blocks
+  base_volume: u128,
+  target_volume: u128,
+  /// both sides of the pool valued in base tokens
+  liquidity_in_base: u128,
+  base_supply: u128,
+  target_supply: u128,
+  fee_percentage: f64,
+  fee_bps: u16,
+}
+
+#[derive(Deserialize)]
+struct SwapRouteQuery {
+  from: DeserializeFromStr<query::Relic>,
+  to: DeserializeFromStr<query::Relic>,
+  /// exact input amount, in the smallest unit of `from`
+  amount: DeserializeFromStr<u128>,
+  /// acceptable slippage beyond the quote in basis points, defaults to none
+  slippage_bps: Option<u16>,
+}
+
+#[derive(Serialize)]
+struct SwapRouteJson {
+  height: u32,
+  input: RelicId,
+  output: RelicId,
+  input_amount: u128,
+  output_amount: u128,
+  /// `output_amount` less the acceptable slippage, to set as the `output_amount`
+  /// of an exact-input `Swap`
+  min_output_amount: u128,
+  hops: Vec<SwapHop>,
+}
+
+#[derive(Serialize)]
+struct RelicCheckpointJson {
+  height: u32,
+  hash: String,
+}
+
+#[derive(Serialize)]
+struct WebhookJson {
+  id: u64,
+  #[serde(flatten)]
+  webhook: Webhook,
+}
+
+#[derive(Serialize)]
+struct PortfolioJson {
+  address: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  label: Option<AddressLabel>,
+  height: u32,
+  #[serde(flatten)]
+  portfolio: RelicPortfolio,
+}
+
+#[derive(Serialize)]
+struct FeesPaidJson {
+  address: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  label: Option<AddressLabel>,
+  /// cumulative swap fees per pool, denominated in the base token
+  #[serde(rename = "bones")]
+  relics: BTreeMap<SpacedRelic, u128>,
+  total: u128,
+}
+
+#[derive(Deserialize)]
+struct SelectQuery {
+  #[serde(alias = "relic")]
+  bone: DeserializeFromStr<query::Relic>,
+  /// amount to cover, in the smallest unit of the bone
+  amount: DeserializeFromStr<u128>,
+}
+
+#[derive(Serialize)]
+struct SelectionJson {
+  #[serde(rename = "bone")]
+  relic: RelicId,
+  amount: u128,
+  /// combined balance of the selected outputs
+  selected: u128,
+  outputs: Vec<SelectedOutputJson>,
+}
+
+#[derive(Serialize)]
+struct SelectedOutputJson {
+  outpoint: OutPoint,
+  amount: u128,
+  value: u64,
+  #[serde(rename = "other_bones")]
+  other_relics: usize,
+  inscriptions: usize,
+}
+
+#[derive(Serialize)]
+struct AddressLabelJson {
+  address: String,
+  #[serde(flatten)]
+  label: AddressLabel,
+}
+
+#[derive(Serialize)]
+struct ChestJson {
+  inscription_id: InscriptionId,
+  syndicate: SyndicateId,
+  #[serde(rename = "bone")]
+  relic: SpacedRelic,
+  amount: u128,
+  /// total amount partially withdrawn from the chest
+  withdrawn: u128,
+  created_block: u64,
+  unlock_height: u64,
+  /// whether the chest can be released at the current height
+  unlocked: bool,
+  owner: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ChestTransferJson {
+  block_height: u32,
+  txid: Txid,
+  location: SatPoint,
+  address: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ChestHistoryJson {
+  inscription_id: InscriptionId,
+  /// whether the chest has not been released yet
+  active: bool,
+  transfers: Vec<ChestTransferJson>,
+}
+
+#[derive(Serialize)]
+struct ChestWithdrawalJson {
+  block_height: u32,
+  txid: Txid,
+  amount: u128,
+}
+
+#[derive(Serialize)]
+struct ChestWithdrawalsJson {
+  inscription_id: InscriptionId,
+  active: bool,
+  withdrawals: Vec<ChestWithdrawalJson>,
+}
+
+#[derive(Serialize)]
+struct ChestRelockJson {
+  block_height: u32,
+  txid: Txid,
+  unlock_height: u64,
+}
+
+#[derive(Serialize)]
+struct ChestRelocksJson {
+  inscription_id: InscriptionId,
+  active: bool,
+  relocks: Vec<ChestRelockJson>,
+}
+
+#[derive(Serialize)]
+struct SyndicateChestsJson {
+  syndicate: SyndicateId,
+  chests: Vec<ChestJson>,
+  more: bool,
+  page_index: usize,
+}
+
+#[derive(Deserialize)]
+struct ChestUnlocksQuery {
+  /// defaults to the next block
+  from: Option<u64>,
+  to: Option<u64>,
+  page: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct ChestUnlocksJson {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  syndicate: Option<SyndicateId>,
+  from: u64,
+  to: u64,
+  /// chests by unlock height
+  chests: Vec<ChestJson>,
+  /// amount unlocking per bone in the whole range, not only this page
+  #[serde(rename = "bones")]
+  relics: BTreeMap<SpacedRelic, u128>,
+  more: bool,
+  page_index: usize,
+}
+
+#[derive(Serialize)]
+struct RelicIdJson {
+  id: RelicId,
+  block: u64,
+  tx: u32,
+}
+
+#[derive(Serialize)]
+struct CachePurgeJson {
+  scope: CacheScope,
+  evicted: BTreeMap<CacheScope, usize>,
+}
+
+#[derive(Serialize)]
+struct StandbyJson {
+  /// lease holder id of this server
+  server: String,
+  /// whether this server is updating the index
+  writer: bool,
+}
+
 #[derive(Deserialize)]
 struct EventsQuery {
   json: Option<bool>,
   show_inscriptions: Option<bool>,
+  formatted: Option<bool>,
+  /// page size of `/events/:bone/:page`, at most 1000
+  limit: Option<usize>,
+  order: Option<EventOrder>,
+}
+
+#[derive(Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum EventOrder {
+  Asc,
+  #[default]
+  Desc,
+}
+
+#[derive(Deserialize)]
+struct EventFilterQuery {
+  from: Option<u32>,
+  to: Option<u32>,
+  kind: Option<EventKind>,
+  relic: Option<DeserializeFromStr<query::Relic>>,
+  txid: Option<Txid>,
+  limit: Option<usize>,
+  cursor: Option<DeserializeFromStr<EventCursor>>,
+  formatted: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct EventsJson {
+  events: Vec<FormattedEvent>,
+  next: Option<String>,
+  /// latest indexed block after the events were read
+  index_height: Option<u32>,
+  index_block_hash: Option<BlockHash>,
+}
+
+/// Position in the event log, formatted as `<HEIGHT>:<EVENT_INDEX>`.
+struct EventCursor {
+  height: u32,
+  event_index: u32,
+}
+
+impl FromStr for EventCursor {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (height, event_index) = s
+      .split_once(':')
+      .ok_or_else(|| anyhow!("invalid event cursor: {s}"))?;
+
+    Ok(Self {
+      height: height.parse()?,
+      event_index: event_index.parse()?,
+    })
+  }
+}
+
+impl Display for EventCursor {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}:{}", self.height, self.event_index)
+  }
 }
 
 enum BlockQuery {
@@ -169,6 +974,7 @@ impl FromStr for BlockQuery {
 
 enum SpawnConfig {
   Https(AxumAcceptor),
+  Tls(RustlsConfig),
   Http,
   Redirect(String),
 }
@@ -200,6 +1006,103 @@ struct Search {
   query: String,
 }
 
+#[derive(Deserialize)]
+struct SuggestQuery {
+  q: String,
+}
+
+#[derive(Serialize)]
+struct AuditLogEntryJson {
+  id: u64,
+  #[serde(flatten)]
+  entry: AuditEntry,
+}
+
+#[derive(Serialize)]
+struct AuditLogJson {
+  page: usize,
+  /// newest first
+  entries: Vec<AuditLogEntryJson>,
+  more: bool,
+}
+
+#[derive(Serialize)]
+struct SimulationJson {
+  txid: Txid,
+  /// the first operation that would fail, if any
+  error: Option<RelicError>,
+  events: Vec<EventWithRelicInscriptionInfo>,
+}
+
+/// Keepsake or cenotaph deciphered from a transaction, both `None` if the
+/// transaction has no relic OP_RETURN output.
+#[derive(Serialize)]
+struct KeepsakeDecodeJson {
+  /// `None` if a bare OP_RETURN script was decoded
+  txid: Option<Txid>,
+  keepsake: Option<Keepsake>,
+  cenotaph: Option<RelicCenotaph>,
+}
+
+impl KeepsakeDecodeJson {
+  fn new(txid: Option<Txid>, tx: &Transaction) -> Self {
+    let (keepsake, cenotaph) = match Keepsake::decipher(tx) {
+      Some(RelicArtifact::Keepsake(keepsake)) => (Some(keepsake), None),
+      Some(RelicArtifact::Cenotaph(cenotaph)) => (None, Some(cenotaph)),
+      None => (None, None),
+    };
+
+    Self {
+      txid,
+      keepsake,
+      cenotaph,
+    }
+  }
+}
+
+#[derive(Serialize)]
+struct KeepsakeEstimateJson {
+  /// hex of the OP_RETURN script
+  script: String,
+  script_size: usize,
+  /// whether Dogecoin Core relays the script by default
+  standard: bool,
+  /// bytes the OP_RETURN output adds to the transaction
+  output_size: usize,
+  /// fee the OP_RETURN output adds at the requested fee rate
+  fee: u64,
+  /// whether the indexer reads the keepsake back as requested, which it
+  /// doesn't if the keepsake refers to outputs the transaction lacks
+  round_trips: bool,
+  /// the keepsake as the indexer reads it back
+  keepsake: Option<Keepsake>,
+  cenotaph: Option<RelicCenotaph>,
+}
+
+#[derive(Serialize)]
+struct PendingSealingsJson {
+  #[serde(flatten)]
+  stats: SealingStats,
+  page: usize,
+  /// oldest sealing first
+  sealings: Vec<SealingHistory>,
+  more: bool,
+}
+
+#[derive(Serialize)]
+struct SearchSuggestionJson {
+  kind: &'static str,
+  label: String,
+  path: String,
+}
+
+#[derive(Serialize)]
+struct SearchSuggestionsJson {
+  query: String,
+  /// best matches first
+  suggestions: Vec<SearchSuggestionJson>,
+}
+
 #[derive(RustEmbed)]
 #[folder = "static"]
 struct StaticAssets;
@@ -245,6 +1148,19 @@ pub(crate) struct Server {
     help = "Listen on <HTTPS_PORT> for incoming HTTPS requests. [default: 443]."
   )]
   https_port: Option<u16>,
+  #[clap(
+    long,
+    requires = "tls_key",
+    conflicts_with_all = ["acme_domain", "acme_cache", "acme_contact"],
+    help = "Serve HTTPS with the PEM certificate chain in <TLS_CERT> instead of requesting a certificate with ACME."
+  )]
+  tls_cert: Option<PathBuf>,
+  #[clap(
+    long,
+    requires = "tls_cert",
+    help = "Load the PEM private key of `--tls-cert` from <TLS_KEY>."
+  )]
+  tls_key: Option<PathBuf>,
   #[clap(long, help = "Store ACME TLS certificates in <ACME_CACHE>.")]
   acme_cache: Option<PathBuf>,
   #[clap(long, help = "Provide ACME contact <ACME_CONTACT>.")]
@@ -255,38 +1171,189 @@ pub(crate) struct Server {
   https: bool,
   #[clap(long, help = "Redirect HTTP traffic to HTTPS.")]
   redirect_http_to_https: bool,
-}
-
-impl Server {
-  pub(crate) fn run(self, options: Options, index: Arc<Index>, handle: Handle) -> SubcommandResult {
-    Runtime::new()?.block_on(async {
-      let index_clone = index.clone();
-
-      let index_thread = thread::spawn(move || loop {
-        if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
-          break;
-        }
-        if let Err(error) = index_clone.update() {
-          log::warn!("{error}");
+  #[clap(long, help = "POST relic activity digests to <DIGEST_WEBHOOK>.")]
+  digest_webhook: Option<Url>,
+  #[clap(
+    long,
+    help = "Send relic activity digests for <DIGEST_ADDRESS>. May be given multiple times."
+  )]
+  digest_address: Vec<Address>,
+  #[clap(
+    long,
+    value_enum,
+    default_value_t,
+    help = "Send relic activity digests every <DIGEST_PERIOD>."
+  )]
+  digest_period: DigestPeriod,
+  #[clap(
+    long,
+    help = "Enable the /admin endpoints for requests bearing <ADMIN_TOKEN>."
+  )]
+  admin_token: Option<String>,
+  #[clap(
+    long,
+    default_value = "1000",
+    help = "Keep up to <RESPONSE_CACHE_SIZE> responses per cache scope in memory."
+  )]
+  response_cache_size: usize,
+  #[clap(
+    long,
+    help = "Serve the index without updating it, or following the chain, until promoted with `POST /admin/promote`."
+  )]
+  standby: bool,
+  #[clap(
+    long,
+    help = "Only allow cross-origin requests from <CORS_ORIGIN>. May be given multiple times. [default: any origin]"
+  )]
+  cors_origin: Vec<String>,
+  #[clap(long, help = "Allow cross-origin POST requests.")]
+  allow_post_cors: bool,
+  #[clap(long, help = "Don't send a Strict-Transport-Security header.")]
+  disable_hsts: bool,
+  #[clap(
+    long,
+    help = "Append <CSP_EXTRA> directives to the default Content-Security-Policy."
+  )]
+  csp_extra: Option<String>,
+  #[clap(
+    long,
+    value_enum,
+    default_value_t,
+    help = "Write amounts in JSON responses as <AMOUNT_FORMAT>, unless requests ask otherwise with an X-Amount-Format header."
+  )]
+  amount_format: AmountFormat,
+  #[clap(
+    long,
+    help = "Export traces of requests and index updates to the OpenTelemetry collector at <OTLP_ENDPOINT>, e.g. `http://localhost:4317`. Requires the `telemetry` feature."
+  )]
+  otlp_endpoint: Option<String>,
+}
+
+impl Server {
+  pub(crate) fn run(
+    self,
+    options: Options,
+    index: Arc<Index>,
+    handle: Handle,
+    mut event_receiver: mpsc::Receiver<Event>,
+  ) -> SubcommandResult {
+    Runtime::new()?.block_on(async {
+      if let Some(endpoint) = &self.otlp_endpoint {
+        telemetry::init(endpoint)?;
+      }
+
+      // the dispatcher waits for room when the channel is full, so events are
+      // always drained and fanned out to subscribers, if there are any
+      let (event_broadcast, _) = broadcast::channel::<Event>(1024);
+      let event_broadcast_clone = event_broadcast.clone();
+      tokio::spawn(async move {
+        while let Some(event) = event_receiver.recv().await {
+          event_broadcast_clone.send(event).ok();
+        }
+      });
+
+      let webhooks = Arc::new(Webhooks::load(index.path())?);
+
+      {
+        let index = index.clone();
+        let webhooks = webhooks.clone();
+        let events = event_broadcast.subscribe();
+        thread::spawn(move || webhooks.send(&index, events));
+      }
+
+      let standby = Arc::new(Standby::new(index.path()));
+
+      if !self.standby {
+        if let Err(lease) = standby.promote(Self::unix_seconds())? {
+          bail!(
+            "index is being updated by server {}, use `--standby` to serve it without updating",
+            lease.holder
+          );
+        }
+      }
+
+      {
+        let standby = standby.clone();
+        thread::spawn(move || loop {
+          if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+            break;
+          }
+          if let Err(error) = standby.heartbeat(Self::unix_seconds()) {
+            log::warn!("{error}");
+          }
+          thread::sleep(Duration::from_millis(5000));
+        });
+      }
+
+      // only the server updating the index moves events out of its outbox
+      {
+        let index = index.clone();
+        let standby = standby.clone();
+        thread::spawn(move || loop {
+          if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+            break;
+          }
+
+          let dispatched = if standby.is_writer() {
+            index.dispatch_pending_events()
+          } else {
+            Ok(Some(0))
+          };
+
+          match dispatched {
+            Ok(Some(0)) => thread::sleep(Duration::from_millis(100)),
+            Ok(Some(_)) => {}
+            Ok(None) => break,
+            Err(error) => {
+              log::warn!("{error}");
+              thread::sleep(Duration::from_millis(5000));
+            }
+          }
+        });
+      }
+
+      let index_clone = index.clone();
+      let standby_clone = standby.clone();
+
+      let index_thread = thread::spawn(move || loop {
+        if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+          break;
+        }
+        if standby_clone.is_writer() {
+          if let Err(error) = index_clone.update() {
+            log::warn!("{error}");
+          }
         }
         thread::sleep(Duration::from_millis(5000));
       });
       INDEXER.lock().unwrap().replace(index_thread);
 
+      if let Some(webhook) = self.digest_webhook.clone() {
+        let index = index.clone();
+        let addresses = self.digest_address.clone();
+        let period = self.digest_period;
+        thread::spawn(move || digests::send_digests(&index, &webhook, &addresses, period));
+      }
+
       let config = options.load_config()?;
       let acme_domains = self.acme_domains()?;
+      let cors = self.cors()?;
+      let content_security_policy = self.content_security_policy()?;
 
       let page_config = Arc::new(PageConfig {
         chain: options.chain(),
         domain: acme_domains.first().cloned(),
         index_sats: index.has_sat_index(),
         csp_origin: options.csp_origin(),
+        plain: false,
+        branding: config.branding.clone(),
       });
 
-      let router = Router::new()
+      let routes = Router::new()
         .route("/", get(Self::home))
         .route("/block-count", get(Self::block_count))
         .route("/block/:query", get(Self::block))
+        .route("/block/:query/bones", get(Self::block_relics))
         .route("/blocks/:query/:endquery", get(Self::blocks))
         .route("/bounties", get(Self::bounties))
         .route("/content/:inscription_id", get(Self::content))
@@ -295,6 +1362,18 @@ impl Server {
         .route("/feed.xml", get(Self::feed))
         .route("/input/:block/:transaction/:input", get(Self::input))
         .route("/inscription/:inscription_id", get(Self::inscription))
+        .route(
+          "/inscription/:inscription_id/children",
+          get(Self::inscription_children),
+        )
+        .route(
+          "/inscription/:inscription_id/children/:page",
+          get(Self::r_children_paginated),
+        )
+        .route(
+          "/inscription/:inscription_id/parents",
+          get(Self::inscription_parents),
+        )
         .route("/inscriptions", get(Self::inscriptions))
         .route("/inscriptions/:from", get(Self::inscriptions_from))
         .route("/shibescription/:inscription_id", get(Self::inscription))
@@ -314,7 +1393,31 @@ impl Server {
         .route("/outputs/:output_list", get(Self::outputs))
         .route("/outputs_full/:output_list", get(Self::outputs_full))
         .route("/address/:address", get(Self::outputs_by_address))
+        .route(
+          "/scripthash/:script_hash/outputs",
+          get(Self::outputs_by_script_hash),
+        )
+        .route("/address/:address/fees-paid", get(Self::address_fees_paid))
+        .route("/address/:address/portfolio", get(Self::address_portfolio))
+        .route("/address/:address/select", get(Self::address_select))
+        .route("/labels", get(Self::address_labels))
+        .route(
+          "/labels/address/:address",
+          get(Self::address_label)
+            .put(Self::set_address_label)
+            .delete(Self::remove_address_label),
+        )
         .route("/preview/:inscription_id", get(Self::preview))
+        .route("/r/blockhash/:height", get(Self::r_blockhash))
+        .route("/r/blockheight", get(Self::r_blockheight))
+        .route("/r/blocktime", get(Self::r_blocktime))
+        .route(
+          "/r/children/:inscription_id/:page",
+          get(Self::r_children_paginated),
+        )
+        .route("/r/metadata/:inscription_id", get(Self::r_metadata))
+        .route("/r/sat/:sat", get(Self::r_sat))
+        .route("/r/sat/:sat/:page", get(Self::r_sat_paginated))
         .route("/range/:start/:end", get(Self::range))
         .route("/rare.txt", get(Self::rare_txt))
         .route(
@@ -331,46 +1434,152 @@ impl Server {
           get(Self::inscriptions_by_address),
         )
         .route("/inscriptions/validate", get(Self::inscriptions_validate))
+        .route("/inscriptions/resolve", post(Self::inscriptions_resolve))
         .route("/sat/:sat", get(Self::sat))
+        .route("/sse/inscriptions", get(Self::inscriptions_sse))
         .route("/search", get(Self::search_by_query))
         .route("/search/*query", get(Self::search_by_path))
+        .route("/search/suggest", get(Self::search_suggest))
         .route("/static/*path", get(Self::static_asset))
+        .route("/stats/errors", get(Self::relic_error_stats))
         .route("/status", get(Self::status))
         .route("/tx/:txid", get(Self::transaction))
+        .route("/tx/:txid/raw", get(Self::transaction_raw))
         .route("/events/:block", get(Self::block_events))
-        .route("/events", post(Self::tx_events))
+        .route("/events", get(Self::events).post(Self::tx_events))
+        .route("/events/stream", post(Self::tx_events_stream))
+        .route("/events/outbox", get(Self::event_outbox))
+        .route("/simulate", post(Self::simulate))
+        .route("/decode/keepsake", post(Self::decode_keepsake))
+        .route("/estimate/keepsake", post(Self::estimate_keepsake))
+        .route("/validate/enshrining", post(Self::validate_enshrining))
+        .route("/decode/tx/:txid", get(Self::decode_tx))
         .route("/events/recent", get(Self::recent_relic_events))
         .route("/events/:bone/:page", get(Self::relic_events_paginated))
         .route("/bone/:bone", get(Self::relic))
+        .route("/bone/:bone/meta", get(Self::relic_meta))
+        .route("/bone/:bone/supply", get(Self::relic_supply))
+        .route("/bone/:bone/mint-status", get(Self::relic_mint_status))
+        .route("/bone/:bone/fees", get(Self::relic_fees))
+        .route("/bone/:bone/subsidy", get(Self::relic_subsidy))
+        .route("/bone/:bone/twap", get(Self::relic_twap))
+        .route("/bone/:bone/owner-history", get(Self::relic_owner_history))
+        .route("/bone/:bone/burns", get(Self::relic_burns))
+        .route("/bone/:bone/burns/:page", get(Self::relic_burns_paginated))
+        .route("/bone/:bone/distribution", get(Self::relic_distribution))
+        .route("/bone/:bone/holders", get(Self::relic_holders))
+        .route("/parse/relic-id/:raw", get(Self::parse_relic_id))
         .route("/bones", get(Self::relics))
         .route("/bones/:page", get(Self::relics_paginated))
         .route("/bones/balances", get(Self::relics_balances))
+        .route("/bones/resolve", post(Self::relics_resolve))
+        .route(
+          "/bones/balances/:address",
+          get(Self::address_relic_balances),
+        )
+        .route("/bones/burned", get(Self::relics_burned))
         .route("/bones/claimable", get(Self::relics_claimable))
+        .route("/bones/fees", get(Self::relics_fees))
+        .route("/bones/leaderboard", get(Self::relics_leaderboard))
+        .route("/bones/checkpoint/:height", get(Self::relic_checkpoint))
+        .route("/tokenlist.json", get(Self::token_list))
+        .route("/pairs", get(Self::pairs))
+        .route("/swap/route", get(Self::swap_route))
+        .route("/graphql", post(Self::graphql))
+        .route("/digest/:address", get(Self::address_digest))
         .route("/tick/:tick", get(Self::sealing_info))
         .route("/tickers/:page", get(Self::sealings_paginated))
+        .route("/tickers/pending", get(Self::pending_sealings))
+        .route(
+          "/tickers/pending/:page",
+          get(Self::pending_sealings_paginated),
+        )
+        .route("/tick/:tick/history", get(Self::sealing_history))
         .route("/syndicate/:syndicate", get(Self::syndicate))
+        .route("/syndicate/:syndicate/chests", get(Self::syndicate_chests))
+        .route(
+          "/syndicate/:syndicate/chests/:page",
+          get(Self::syndicate_chests_paginated),
+        )
+        .route(
+          "/syndicate/:syndicate/unlocks",
+          get(Self::syndicate_chest_unlocks),
+        )
+        .route("/chests/unlocking", get(Self::chest_unlocks))
+        .route("/chest/:inscription_id", get(Self::chest))
+        .route("/chest/:inscription_id/history", get(Self::chest_history))
+        .route(
+          "/chest/:inscription_id/withdrawals",
+          get(Self::chest_withdrawals),
+        )
+        .route("/chest/:inscription_id/relocks", get(Self::chest_relocks))
         .route("/syndicates", get(Self::syndicates))
         .route("/syndicates/:page", get(Self::syndicates_paginated))
         .route("/bonestones", get(Self::bonestones))
         .route("/bonestones/length", get(Self::bonestones_length))
         .route("/outputs", post(Self::outputs_post))
+        .route("/admin/cache/purge", post(Self::admin_cache_purge))
+        .route("/admin/promote", post(Self::admin_promote))
+        .route("/admin/audit-log", get(Self::admin_audit_log))
+        .route(
+          "/admin/audit-log/:page",
+          get(Self::admin_audit_log_paginated),
+        )
+        .route("/prove/ownership", post(Self::prove_ownership))
+        .route("/prove/ownership/:token", get(Self::ownership_attestation))
+        .route("/webhooks", get(Self::webhooks).post(Self::add_webhook))
+        .route("/webhooks/:id", delete(Self::remove_webhook))
+        .route(
+          "/admin/curation/:list/:bone",
+          post(Self::admin_curation_insert).delete(Self::admin_curation_remove),
+        )
+        .layer(middleware::from_fn(ResponseCache::middleware));
+
+      #[cfg(feature = "telemetry")]
+      let routes = routes.layer(middleware::from_fn(telemetry::middleware));
+
+      let router = Router::new()
+        .nest(&format!("/{API_VERSION}"), routes.clone())
+        .merge(routes)
+        .layer(middleware::from_fn(Self::api_version))
+        .layer(middleware::from_fn(Self::audit_log))
+        .route("/api/versions", get(Self::api_versions))
+        .layer(middleware::from_fn(Self::index_tip))
+        .layer(middleware::from_fn(AmountFormat::middleware))
         .layer(Extension(index))
+        .layer(Extension(self.amount_format))
+        .layer(Extension(Arc::new(ResponseCache::new(
+          self.response_cache_size,
+        ))))
+        .layer(Extension(Arc::new(Admin::new(self.admin_token.clone()))))
+        .layer(Extension(Arc::new(Curation::new(&config))))
+        .layer(Extension(Arc::new(Ownership::new())))
+        .layer(Extension(Arc::new(Leaderboards::new())))
+        .layer(Extension(Arc::new(Delegates::new())))
+        .layer(Extension(standby))
+        .layer(Extension(webhooks))
+        .layer(Extension(event_broadcast))
+        .layer(middleware::from_fn(Self::plain_mode))
         .layer(Extension(page_config))
         .layer(Extension(Arc::new(config)))
         .layer(SetResponseHeaderLayer::if_not_present(
           header::CONTENT_SECURITY_POLICY,
-          HeaderValue::from_static("default-src 'self'"),
+          content_security_policy,
         ))
         .layer(SetResponseHeaderLayer::overriding(
           header::STRICT_TRANSPORT_SECURITY,
-          HeaderValue::from_static("max-age=31536000; includeSubDomains; preload"),
+          (!self.disable_hsts)
+            .then(|| HeaderValue::from_static("max-age=31536000; includeSubDomains; preload")),
         ))
+        .layer(cors)
         .layer(
-          CorsLayer::new()
-            .allow_methods([http::Method::GET, http::Method::POST])
-            .allow_origin(Any),
-        )
-        .layer(CompressionLayer::new());
+          CompressionLayer::new().compress_when(DefaultPredicate::new().and(
+            // inscription content is compressed ahead of time and may be a byte range
+            |_: StatusCode, _: http::Version, headers: &HeaderMap, _: &http::Extensions| {
+              !headers.contains_key(header::ACCEPT_RANGES)
+            },
+          )),
+        );
       match (self.http_port(), self.https_port()) {
         (Some(http_port), None) => {
           self
@@ -383,7 +1592,7 @@ impl Server {
               router,
               handle,
               https_port,
-              SpawnConfig::Https(self.acceptor(&options)?),
+              self.https_spawn_config(&options).await?,
             )?
             .await??
         }
@@ -398,20 +1607,19 @@ impl Server {
             SpawnConfig::Http
           };
 
+          let https_spawn_config = self.https_spawn_config(&options).await?;
+
           let (http_result, https_result) = tokio::join!(
             self.spawn(router.clone(), handle.clone(), http_port, http_spawn_config)?,
-            self.spawn(
-              router,
-              handle,
-              https_port,
-              SpawnConfig::Https(self.acceptor(&options)?),
-            )?
+            self.spawn(router, handle, https_port, https_spawn_config)?
           );
           http_result.and(https_result)??;
         }
         (None, None) => unreachable!(),
       }
 
+      telemetry::shutdown();
+
       Ok(Box::new(Empty {}) as Box<dyn Output>)
     })
   }
@@ -432,7 +1640,7 @@ impl Server {
       eprintln!(
         "Listening on {}://{addr}",
         match config {
-          SpawnConfig::Https(_) => "https",
+          SpawnConfig::Https(_) | SpawnConfig::Tls(_) => "https",
           _ => "http",
         }
       );
@@ -447,6 +1655,12 @@ impl Server {
             .serve(router.into_make_service())
             .await
         }
+        SpawnConfig::Tls(config) => {
+          axum_server::bind_rustls(addr, config)
+            .handle(handle)
+            .serve(router.into_make_service())
+            .await
+        }
         SpawnConfig::Redirect(destination) => {
           axum_server::Server::bind(addr)
             .handle(handle)
@@ -486,8 +1700,58 @@ impl Server {
     }
   }
 
+  fn cors(&self) -> Result<CorsLayer> {
+    let origin: AllowOrigin = if self.cors_origin.is_empty() {
+      Any.into()
+    } else {
+      AllowOrigin::list(
+        self
+          .cors_origin
+          .iter()
+          .map(|origin| {
+            HeaderValue::from_str(origin)
+              .map_err(|err| anyhow!("invalid CORS origin `{origin}`: {err}"))
+          })
+          .collect::<Result<Vec<HeaderValue>>>()?,
+      )
+    };
+
+    let mut cors = CorsLayer::new()
+      .allow_methods([http::Method::GET])
+      .allow_origin(origin)
+      .allow_headers([HeaderName::from_static(AMOUNT_FORMAT_HEADER)])
+      .expose_headers([
+        HeaderName::from_static(INDEX_HEIGHT_HEADER),
+        HeaderName::from_static(INDEX_BLOCK_HASH_HEADER),
+        HeaderName::from_static(TOTAL_COUNT_HEADER),
+      ]);
+
+    if self.allow_post_cors {
+      // POST bodies are JSON, which browsers only send after a preflight for the content type
+      cors = cors
+        .allow_methods([http::Method::GET, http::Method::POST])
+        .allow_headers([
+          header::CONTENT_TYPE,
+          HeaderName::from_static(AMOUNT_FORMAT_HEADER),
+        ]);
+    }
+
+    Ok(cors)
+  }
+
+  fn content_security_policy(&self) -> Result<HeaderValue> {
+    match &self.csp_extra {
+      Some(extra) => HeaderValue::from_str(&format!("default-src 'self'; {extra}"))
+        .map_err(|err| anyhow!("invalid content-security-policy directives `{extra}`: {err}")),
+      None => Ok(HeaderValue::from_static("default-src 'self'")),
+    }
+  }
+
   fn http_port(&self) -> Option<u16> {
-    if self.http || self.http_port.is_some() || (self.https_port.is_none() && !self.https) {
+    if self.http
+      || self.http_port.is_some()
+      || (self.https_port.is_none() && !self.https && self.tls_cert.is_none())
+    {
       Some(self.http_port.unwrap_or(80))
     } else {
       None
@@ -495,13 +1759,33 @@ impl Server {
   }
 
   fn https_port(&self) -> Option<u16> {
-    if self.https || self.https_port.is_some() {
+    if self.https || self.https_port.is_some() || self.tls_cert.is_some() {
       Some(self.https_port.unwrap_or(443))
     } else {
       None
     }
   }
 
+  /// Serve a static certificate if one was given, otherwise request one with
+  /// ACME, which answers TLS-ALPN-01 challenges on the HTTPS port itself.
+  async fn https_spawn_config(&self, options: &Options) -> Result<SpawnConfig> {
+    if let (Some(cert), Some(key)) = (&self.tls_cert, &self.tls_key) {
+      return Ok(SpawnConfig::Tls(
+        RustlsConfig::from_pem_file(cert, key)
+          .await
+          .with_context(|| {
+            format!(
+              "failed to load TLS certificate `{}` and key `{}`",
+              cert.display(),
+              key.display()
+            )
+          })?,
+      ));
+    }
+
+    Ok(SpawnConfig::Https(self.acceptor(options)?))
+  }
+
   fn acceptor(&self, options: &Options) -> Result<AxumAcceptor> {
     let config = AcmeConfig::new(self.acme_domains()?)
       .contact(&self.acme_contact)
@@ -585,11 +1869,7 @@ impl Server {
       }
     } else {
       index
-        .get_transaction(outpoint.txid)?
-        .ok_or_not_found(|| format!("output {outpoint}"))?
-        .output
-        .into_iter()
-        .nth(outpoint.vout as usize)
+        .get_txout(outpoint)?
         .ok_or_not_found(|| format!("output {outpoint}"))?
     };
 
@@ -636,6 +1916,7 @@ impl Server {
     let show_all = query.show_all.unwrap_or(false);
     let value_filter = query.value_filter.unwrap_or(0);
     let show_unsafe = query.show_unsafe.unwrap_or(false);
+    let show_relics = query.show_bones.unwrap_or(false);
 
     let items_per_page = query.limit.unwrap_or(10);
     let page = page as usize;
@@ -646,14 +1927,18 @@ impl Server {
     };
     let mut element_counter = 0;
 
-    let outpoints: Vec<OutPoint> = index.get_account_outputs(address.clone())?;
+    let address = Self::parse_address(&index, &address)?;
+
+    let outpoints: Vec<OutPoint> =
+      index.get_account_outputs(script_hash(&address.script_pubkey()))?;
 
     let mut utxos = Vec::new();
     let mut total_shibes = 0u128;
     let mut inscription_shibes = 0u128;
 
     for outpoint in outpoints {
-      if !index.get_relic_balances_for_outpoint(outpoint)?.is_empty() {
+      let relics = index.get_relic_balances_for_outpoint(outpoint)?;
+      if !relics.is_empty() && !show_relics {
         continue;
       }
       if !show_all
@@ -662,21 +1947,16 @@ impl Server {
         continue;
       }
 
-      let txid = outpoint.txid;
-      let vout = outpoint.vout;
       let output = index
-        .get_transaction(txid)?
-        .ok_or_not_found(|| format!("{txid} current transaction"))?
-        .output
-        .into_iter()
-        .nth(vout.try_into().unwrap())
-        .ok_or_not_found(|| format!("{vout} current transaction output"))?;
+        .get_txout(outpoint)?
+        .ok_or_not_found(|| format!("output {outpoint}"))?;
 
       if value_filter > 0 && output.value <= value_filter {
         continue;
       }
 
-      if !index.get_inscriptions_on_output(outpoint)?.is_empty() {
+      let inscription_count = index.get_inscriptions_on_output(outpoint)?.len();
+      if inscription_count > 0 {
         inscription_shibes += output.value as u128;
         if !show_unsafe {
           continue;
@@ -687,18 +1967,22 @@ impl Server {
 
       total_shibes += output.value as u128;
 
-      let confirmations = if let Some(block_hash_info) = index.get_transaction_blockhash(txid)? {
-        block_hash_info.confirmations
-      } else {
-        None
-      };
+      let confirmations =
+        if let Some(block_hash_info) = index.get_transaction_blockhash(outpoint.txid)? {
+          block_hash_info.confirmations
+        } else {
+          None
+        };
 
       utxos.push(Utxo {
-        txid,
-        vout,
+        txid: outpoint.txid,
+        vout: outpoint.vout,
         script: output.script_pubkey,
         shibes: output.value,
         confirmations,
+        has_inscriptions: inscription_count > 0,
+        inscription_count,
+        relics,
       });
     }
     Ok(
@@ -712,6 +1996,24 @@ impl Server {
     )
   }
 
+  /// Describe `output` along with the inscriptions and bones it carries.
+  fn utxo(index: &Index, outpoint: OutPoint, output: TxOut) -> ServerResult<Utxo> {
+    let inscription_count = index.get_inscriptions_on_output(outpoint)?.len();
+
+    Ok(Utxo {
+      txid: outpoint.txid,
+      vout: outpoint.vout,
+      script: output.script_pubkey,
+      shibes: output.value,
+      confirmations: index
+        .get_transaction_blockhash(outpoint.txid)?
+        .and_then(|block_hash_info| block_hash_info.confirmations),
+      has_inscriptions: inscription_count > 0,
+      inscription_count,
+      relics: index.get_relic_balances_for_outpoint(outpoint)?,
+    })
+  }
+
   async fn inscriptions_by_address(
     Extension(index): Extension<Arc<Index>>,
     Path(params): Path<(String, u32)>,
@@ -740,108 +2042,82 @@ impl Server {
 
     let items_per_page = query.limit.unwrap_or(10);
     let page = page as usize;
-    let start_index = if page == 0 || page == 1 {
+    let start_index = if show_all || page == 0 || page == 1 {
       0
     } else {
       (page - 1) * items_per_page + 1
     };
-    let mut element_counter = 0;
 
-    let mut all_inscriptions_json = Vec::new();
-    let outpoints: Vec<OutPoint> = index.get_account_outputs(address)?;
+    let address = Self::parse_address(&index, &address)?.to_string();
 
-    for outpoint in outpoints {
-      let inscriptions = index.get_inscriptions_on_output(outpoint)?;
+    let (inscription_ids, total) = index.get_inscriptions_by_address(
+      &address,
+      start_index,
+      (!show_all).then_some(items_per_page),
+    )?;
 
-      if inscriptions.is_empty() {
-        continue;
-      }
+    let mut all_inscriptions_json = Vec::new();
 
-      element_counter += 1;
-      if !show_all
-        && (element_counter < start_index || element_counter > start_index + items_per_page - 1)
-      {
-        continue;
-      }
+    for inscription_id in inscription_ids {
+      let satpoint = index
+        .get_inscription_satpoint_by_id(inscription_id)?
+        .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
-      let txid = outpoint.txid;
-      let vout = outpoint.vout;
+      let outpoint = satpoint.outpoint;
 
       let output = index
-        .get_transaction(txid)?
-        .ok_or_not_found(|| format!("dunes {txid} current transaction"))?
-        .output
-        .into_iter()
-        .nth(vout.try_into().unwrap())
-        .ok_or_not_found(|| format!("dunes {vout} current transaction output"))?;
-      let shibes = output.value;
-      let script = output.script_pubkey;
+        .get_txout(outpoint)?
+        .ok_or_not_found(|| format!("output {outpoint}"))?;
 
-      if value_filter > 0 && shibes <= value_filter {
-        element_counter -= 1;
+      if value_filter > 0 && output.value <= value_filter {
         continue;
       }
 
-      for inscription_id in inscriptions {
-        let inscription = index
-          .get_inscription_by_id(inscription_id)?
-          .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
-
-        let entry = index
-          .get_inscription_entry(inscription_id)?
-          .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
-
-        let satpoint = index
-          .get_inscription_satpoint_by_id(inscription_id)?
-          .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
-
-        let content_type = inscription.content_type().map(|s| s.to_string());
-        let content_length = inscription.content_length();
-        let content = inscription.into_body();
+      let inscription = index
+        .get_inscription_by_id(inscription_id)?
+        .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
-        let str_content = match (content_type.clone(), content) {
-          (Some(ref ct), Some(c))
-            if ct.starts_with("application/json") || ct.starts_with("text") =>
-          {
-            Some(String::from_utf8_lossy(c.as_slice()).to_string())
-          }
-          (None, Some(c)) => Some(String::from_utf8_lossy(c.as_slice()).to_string()),
-          _ => None,
-        };
+      let entry = index
+        .get_inscription_entry(inscription_id)?
+        .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
-        let confirmations = if let Some(block_hash_info) = index.get_transaction_blockhash(txid)? {
-          block_hash_info.confirmations
-        } else {
-          None
-        };
+      let content_type = inscription.content_type().map(|s| s.to_string());
+      let content_length = inscription.content_length();
+      let content = inscription.into_body();
 
-        let inscription_json = InscriptionByAddressJson {
-          utxo: Utxo {
-            txid,
-            vout,
-            script: script.clone(),
-            shibes,
-            confirmations,
-          },
-          content: str_content,
-          content_length,
-          content_type,
-          genesis_height: entry.height,
-          inscription_id,
-          inscription_number: entry.inscription_number,
-          timestamp: entry.timestamp,
-          offset: satpoint.offset,
-        };
+      let str_content = match (content_type.clone(), content) {
+        (Some(ref ct), Some(c)) if ct.starts_with("application/json") || ct.starts_with("text") => {
+          Some(String::from_utf8_lossy(c.as_slice()).to_string())
+        }
+        (None, Some(c)) => Some(String::from_utf8_lossy(c.as_slice()).to_string()),
+        _ => None,
+      };
 
-        all_inscriptions_json.push(inscription_json);
-      }
+      all_inscriptions_json.push(InscriptionByAddressJson {
+        utxo: Self::utxo(&index, outpoint, output)?,
+        content: str_content,
+        content_length,
+        content_type,
+        genesis_height: entry.height,
+        inscription_id,
+        inscription_number: entry.inscription_number,
+        timestamp: entry.timestamp,
+        offset: satpoint.offset,
+      });
     }
+
     Ok(
-      Json(InscriptionAddressJson {
-        inscriptions: all_inscriptions_json,
-        total_inscriptions: element_counter,
-      })
-      .into_response(),
+      (
+        [(
+          HeaderName::from_static(TOTAL_COUNT_HEADER),
+          HeaderValue::from(total),
+        )],
+        Json(InscriptionAddressJson {
+          inscriptions: all_inscriptions_json,
+          total_inscriptions: total.try_into().unwrap(),
+        }),
+      )
+        .into_response(),
     )
   }
 
@@ -849,16 +2125,44 @@ impl Server {
     Extension(index): Extension<Arc<Index>>,
     Path(address): Path<String>,
   ) -> Result<String, ServerError> {
+    let address = Self::parse_address(&index, &address)?;
+
     let mut outputs = vec![];
-    let outpoints = index.get_account_outputs(address)?;
+    let outpoints = index.get_account_outputs(script_hash(&address.script_pubkey()))?;
 
-    outputs.push(AddressOutputJson::new(outpoints));
+    outputs.push(AddressOutputJson {
+      label: index.get_address_label(&address)?,
+      ..AddressOutputJson::new(outpoints)
+    });
 
     let outputs_json = to_string(&outputs).context("Failed to serialize outputs")?;
 
     Ok(outputs_json)
   }
 
+  async fn outputs_by_script_hash(
+    Extension(index): Extension<Arc<Index>>,
+    Path(script_hash): Path<String>,
+  ) -> ServerResult<Json<Vec<AddressOutputJson>>> {
+    // Electrum script hashes are the SHA256 of the script, byte-reversed
+    let mut script_hash: [u8; 32] = hex::decode(&script_hash)
+      .ok()
+      .and_then(|script_hash| script_hash.try_into().ok())
+      .ok_or_else(|| {
+        ServerError::BadRequest(format!(
+          "invalid script hash {script_hash}: expected 64 hex characters"
+        ))
+      })?;
+
+    script_hash.reverse();
+
+    task::block_in_place(|| {
+      Ok(Json(vec![AddressOutputJson::new(
+        index.get_account_outputs(script_hash)?,
+      )]))
+    })
+  }
+
   async fn outputs_full(
     Extension(server_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -891,11 +2195,7 @@ impl Server {
         }
       } else {
         index
-          .get_transaction(outpoint.txid)?
-          .ok_or_not_found(|| format!("output {outpoint}"))?
-          .output
-          .into_iter()
-          .nth(outpoint.vout as usize)
+          .get_txout(outpoint)?
           .ok_or_not_found(|| format!("output {outpoint}"))?
       };
 
@@ -1062,29 +2362,77 @@ impl Server {
   async fn home(
     Extension(page_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(curation): Extension<Arc<Curation>>,
   ) -> ServerResult<PageHtml<HomeHtml>> {
-    Ok(HomeHtml::new(index.blocks(100)?, index.get_home_inscriptions()?).page(page_config))
+    Ok(
+      HomeHtml::new(
+        index.blocks(100)?,
+        index.get_home_inscriptions()?,
+        curation.relics(CurationList::Featured),
+      )
+      .page(page_config),
+    )
   }
 
   async fn install_script() -> Redirect {
     Redirect::to("https://raw.githubusercontent.com/apezord/ord-dogecoin/master/install.sh")
   }
 
-  async fn block(
-    Extension(page_config): Extension<Arc<PageConfig>>,
+  async fn block_relics(
     Extension(index): Extension<Arc<Index>>,
     Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Block>>,
-  ) -> ServerResult<PageHtml<BlockHtml>> {
-    let (block, height) = match query {
-      query::Block::Height(height) => {
-        let block = index
-          .get_block_by_height(height)?
-          .ok_or_not_found(|| format!("block {height}"))?;
-
-        (block, height)
+  ) -> ServerResult<Json<BlockRelicActivity>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no bone index".to_string(),
+        ));
       }
-      query::Block::Hash(hash) => {
-        let info = index
+
+      let height = match query {
+        query::Block::Height(height) => height,
+        query::Block::Hash(hash) => {
+          let info = index
+            .block_header_info(hash)?
+            .ok_or_not_found(|| format!("block {hash}"))?;
+
+          let height = u32::try_from(info.height).unwrap();
+
+          // a stale block shares its height with the main chain block, whose
+          // activity is all that is indexed
+          if index.block_hash(Some(height))? != Some(hash) {
+            return Err(ServerError::NotFound(format!("block {hash}")));
+          }
+
+          height
+        }
+      };
+
+      let block_count = index.block_count()?;
+
+      if height >= block_count {
+        return Err(ServerError::NotFound(format!("block {height}")));
+      }
+
+      Ok(Json(index.get_block_relic_activity(height)?))
+    })
+  }
+
+  async fn block(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Block>>,
+  ) -> ServerResult<Response> {
+    let (block, height) = match query {
+      query::Block::Height(height) => {
+        let block = index
+          .get_block_by_height(height)?
+          .ok_or_not_found(|| format!("block {height}"))?;
+
+        (block, height)
+      }
+      query::Block::Hash(hash) => {
+        let info = index
           .block_header_info(hash)?
           .ok_or_not_found(|| format!("block {hash}"))?;
 
@@ -1121,7 +2469,7 @@ impl Server {
         tx.input
           .par_iter()
           .map(|input| get_transaction_details(input, &index, &page_config))
-          .map(move |(value, address)| (txid.clone(), value, address))
+          .map(move |(value, address, _pruned)| (txid.clone(), value, address))
           .collect::<Vec<_>>()
       })
       .collect();
@@ -1209,7 +2557,7 @@ impl Server {
       })
       .collect();
 
-    Ok(
+    streaming::html(
       BlockHtml::new(
         block,
         Height(height),
@@ -1224,6 +2572,7 @@ impl Server {
       )
       .page(page_config),
     )
+    .await
   }
 
   async fn blocks(
@@ -1231,7 +2580,7 @@ impl Server {
     Extension(index): Extension<Arc<Index>>,
     Path(path): Path<(u32, u32)>,
     Query(query): Query<BlocksQuery>,
-  ) -> Result<String, ServerError> {
+  ) -> ServerResult<Response> {
     let (height, endheight) = path;
     let mut blocks = vec![];
     for height in height..endheight {
@@ -1264,6 +2613,7 @@ impl Server {
 
       let mut input_values_per_tx: HashMap<_, _> = HashMap::new();
       let mut input_addresses_per_tx: HashMap<_, _> = HashMap::new();
+      let mut pruned = false;
 
       if !query.no_input_data.unwrap_or(true) {
         // Parallelize the processing using Rayon
@@ -1275,20 +2625,22 @@ impl Server {
             tx.input
               .par_iter()
               .map(|input| get_transaction_details(input, &index, &page_config))
-              .map(move |(value, address)| (txid.clone(), value, address))
+              .map(move |(value, address, pruned)| (txid.clone(), value, address, pruned))
               .collect::<Vec<_>>()
           })
           .collect();
 
+        pruned = results.iter().any(|(_, _, _, pruned)| *pruned);
+
         // Separate the results into the desired HashMaps
         input_values_per_tx = results
           .iter()
-          .map(|(txid, value, _)| (txid.clone(), value.clone()))
+          .map(|(txid, value, _, _)| (txid.clone(), value.clone()))
           .collect();
 
         input_addresses_per_tx = results
           .iter()
-          .map(|(txid, _, address)| (txid.clone(), address.clone()))
+          .map(|(txid, _, address, _)| (txid.clone(), address.clone()))
           .collect();
       }
 
@@ -1413,13 +2765,11 @@ impl Server {
         inscriptions_per_tx,
         output_addresses_per_tx,
         output_scripts_per_tx,
+        pruned,
       ));
     }
 
-    // This will convert the Vec<BlocksJson> into a JSON string
-    let blocks_json = to_string(&blocks).context("Failed to serialize blocks")?;
-
-    Ok(blocks_json)
+    streaming::json_array(blocks).await
   }
 
   async fn transaction(
@@ -1455,9 +2805,60 @@ impl Server {
     })
   }
 
+  /// Serves the hex of a transaction, from the index if it is stored there,
+  /// see `--index-raw-transactions`, otherwise from Dogecoin Core. A txid
+  /// always names the same transaction, so its hex is served with the txid as
+  /// `ETag` and revalidations of known transactions are answered with
+  /// `304 Not Modified`.
+  async fn transaction_raw(
+    Extension(index): Extension<Arc<Index>>,
+    Path(txid): Path<Txid>,
+    headers: HeaderMap,
+  ) -> ServerResult<Response> {
+    task::block_in_place(|| {
+      let raw = match index.get_stored_transaction(txid)? {
+        Some(raw) => raw,
+        None => consensus::encode::serialize(
+          &index
+            .get_transaction(txid)?
+            .ok_or_not_found(|| format!("transaction {txid}"))?,
+        ),
+      };
+
+      let etag = format!("\"{txid}\"");
+
+      let mut response_headers = HeaderMap::new();
+      response_headers.insert(
+        header::ETAG,
+        HeaderValue::from_str(&etag).map_err(|err| ServerError::Internal(err.into()))?,
+      );
+      response_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("max-age=31536000, immutable"),
+      );
+
+      if headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map_or(false, |value| {
+          value.split(',').any(|tag| tag.trim() == etag)
+        })
+      {
+        return Ok((StatusCode::NOT_MODIFIED, response_headers).into_response());
+      }
+
+      response_headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/plain; charset=utf-8"),
+      );
+
+      Ok((response_headers, hex::encode(raw)).into_response())
+    })
+  }
+
   async fn block_events(
     Extension(index): Extension<Arc<Index>>,
-    Query(query): Query<JsonQuery>,
+    Query(query): Query<EventsQuery>,
     Path(block_number): Path<u32>,
   ) -> ServerResult<Response> {
     task::block_in_place(|| {
@@ -1477,7 +2878,7 @@ impl Server {
         for txid in txids {
           if let Ok(events) = index.events_for_tx(txid) {
             for event in events {
-              response.push(event);
+              response.push(Self::formatted_event(&index, event, &query)?);
             }
           }
         }
@@ -1488,6 +2889,98 @@ impl Server {
     })
   }
 
+  async fn events(
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<EventFilterQuery>,
+  ) -> ServerResult<Json<EventsJson>> {
+    task::block_in_place(|| {
+      let relic_id = match query.relic {
+        Some(DeserializeFromStr(relic_query)) => Some(Self::relic_by_query(&index, relic_query)?.0),
+        None => None,
+      };
+
+      let filter = EventFilter {
+        from: query.from.unwrap_or(0),
+        to: query.to.unwrap_or(u32::MAX),
+        kind: query.kind,
+        relic_id,
+        txid: query.txid,
+      };
+
+      if filter.from > filter.to {
+        return Err(ServerError::BadRequest(format!(
+          "from height {} is greater than to height {}",
+          filter.from, filter.to
+        )));
+      }
+
+      let limit = query.limit.unwrap_or(100);
+
+      if limit == 0 || limit > 1000 {
+        return Err(ServerError::BadRequest(
+          "limit must be between 1 and 1000".into(),
+        ));
+      }
+
+      let cursor = query
+        .cursor
+        .map(|DeserializeFromStr(cursor)| (cursor.height, cursor.event_index));
+
+      let (events, next) = index.filter_events(&filter, cursor, limit)?;
+
+      let tip = index.tip()?;
+
+      let formatted = query.formatted.unwrap_or(false);
+
+      let events = events
+        .into_iter()
+        .map(|event| {
+          Ok(FormattedEvent {
+            formatted: if formatted {
+              Some(index.formatted_event_amounts(&event)?)
+            } else {
+              None
+            },
+            event,
+          })
+        })
+        .collect::<ServerResult<Vec<FormattedEvent>>>()?;
+
+      Ok(Json(EventsJson {
+        events,
+        next: next
+          .map(|(height, event_index)| EventCursor {
+            height,
+            event_index,
+          })
+          .map(|cursor| cursor.to_string()),
+        index_height: tip.map(|(height, _)| height),
+        index_block_hash: tip.map(|(_, hash)| hash),
+      }))
+    })
+  }
+
+  #[cfg(feature = "graphql")]
+  async fn graphql(
+    Extension(index): Extension<Arc<Index>>,
+    Json(request): Json<async_graphql::Request>,
+  ) -> Json<async_graphql::Response> {
+    Json(task::block_in_place(|| graphql::execute(index, request)))
+  }
+
+  #[cfg(not(feature = "graphql"))]
+  async fn graphql() -> ServerResult<Response> {
+    Err(ServerError::NotFound(
+      "this server was built without the `graphql` feature".into(),
+    ))
+  }
+
+  async fn event_outbox(
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult<Json<EventOutboxInfo>> {
+    task::block_in_place(|| Ok(Json(index.event_outbox_info()?)))
+  }
+
   async fn tx_events(
     Extension(index): Extension<Arc<Index>>,
     Extension(page_config): Extension<Arc<PageConfig>>,
@@ -1497,52 +2990,16 @@ impl Server {
     task::block_in_place(|| {
       Ok(if query.json.unwrap_or(false) {
         let mut response = Vec::new();
+        let mut lookups = 0;
         for txid in txids {
           if let Ok(events) = index.events_for_tx(txid) {
             for event in events {
-              if query.show_inscriptions.unwrap_or(false) {
-                let mut event_with_inscription = EventWithRelicInscriptionInfo {
-                  block_height: event.block_height,
-                  event_index: event.event_index,
-                  txid: event.txid,
-                  inscription: None,
-                  info: event.info.clone(),
-                  ticker: None,
-                };
-                match event.info {
-                  EventInfo::InscriptionTransferred {
-                    inscription_id,
-                    new_location,
-                    ..
-                  } => {
-                    let Some(inscription_info) =
-                      index.inscription_relic_info(query::Inscription::Id(inscription_id))?
-                    else {
-                      response.push(event_with_inscription);
-                      continue;
-                    };
-
-                    event_with_inscription.inscription = Some(RelicShibescriptionJson {
-                      is_bonestone: inscription_info.is_bonestone,
-                      relic_sealed: inscription_info.relic_sealed,
-                      relic_enshrined: inscription_info.relic_enshrined,
-                    });
-                    response.push(event_with_inscription);
-                  }
-                  _ => {
-                    response.push(event_with_inscription);
-                  }
-                }
-              } else {
-                response.push(EventWithRelicInscriptionInfo {
-                  block_height: event.block_height,
-                  event_index: event.event_index,
-                  txid: event.txid,
-                  inscription: None,
-                  info: event.info,
-                  ticker: None,
-                });
-              }
+              response.push(Self::event_with_inscription_info(
+                &index,
+                event,
+                &query,
+                &mut lookups,
+              )?);
             }
           }
         }
@@ -1553,6 +3010,268 @@ impl Server {
     })
   }
 
+  /// Like `tx_events`, but writes one event per line as it is read from the
+  /// index, instead of building the whole response in memory.
+  async fn tx_events_stream(
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<EventsQuery>,
+    Json(txids): Json<Vec<Txid>>,
+  ) -> Response {
+    let (sender, receiver) = mpsc::channel::<String>(64);
+    let format = AmountFormat::current();
+
+    tokio::task::spawn_blocking(move || {
+      let mut lookups = 0;
+      for txid in txids {
+        let Ok(events) = index.events_for_tx(txid) else {
+          continue;
+        };
+        for event in events {
+          let event = match Self::event_with_inscription_info(&index, event, &query, &mut lookups) {
+            Ok(event) => event,
+            Err(err) => {
+              log::warn!("event stream for {txid} failed: {err:?}");
+              return;
+            }
+          };
+          let line = String::from_utf8(json::to_vec(&event, format).unwrap()).unwrap() + "\n";
+          // stop reading once the client has gone away
+          if sender.blocking_send(line).is_err() {
+            return;
+          }
+        }
+      }
+    });
+
+    let lines = futures::stream::unfold(receiver, |mut receiver| async move {
+      receiver
+        .recv()
+        .await
+        .map(|line| (Ok::<String, Infallible>(line), receiver))
+    });
+
+    (
+      [(header::CONTENT_TYPE, "application/x-ndjson")],
+      body::StreamBody::new(lines),
+    )
+      .into_response()
+  }
+
+  /// Deciphers the keepsake of the raw transaction or OP_RETURN script in the
+  /// request body, with the parser used by the indexer.
+  async fn decode_keepsake(body: String) -> ServerResult<Json<KeepsakeDecodeJson>> {
+    let bytes = hex::decode(body.trim())
+      .map_err(|_| ServerError::BadRequest("invalid transaction or script hex".into()))?;
+
+    if let Ok(tx) = consensus::encode::deserialize::<Transaction>(&bytes) {
+      return Ok(Json(KeepsakeDecodeJson::new(Some(tx.txid()), &tx)));
+    }
+
+    // otherwise decode the body as the script of a single output
+    let tx = Transaction {
+      version: 2,
+      lock_time: bitcoin::PackedLockTime::ZERO,
+      input: Vec::new(),
+      output: vec![TxOut {
+        value: 0,
+        script_pubkey: Script::from(bytes),
+      }],
+    };
+
+    Ok(Json(KeepsakeDecodeJson::new(None, &tx)))
+  }
+
+  /// Enciphers the keepsake in the request body like wallets do, and returns
+  /// the size and fee of its OP_RETURN output.
+  async fn estimate_keepsake(
+    Json(request): Json<EstimateKeepsakeRequest>,
+  ) -> ServerResult<Json<KeepsakeEstimateJson>> {
+    let fee_rate = FeeRate::try_from(request.fee_rate)
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+    if request.outputs > MAX_ESTIMATED_OUTPUTS {
+      return Err(ServerError::BadRequest(format!(
+        "transactions can have at most {MAX_ESTIMATED_OUTPUTS} outputs besides the OP_RETURN output"
+      )));
+    }
+
+    let script_pubkey = request.keepsake.encipher();
+
+    let op_return = TxOut {
+      value: 0,
+      script_pubkey,
+    };
+
+    // Dogecoin has no witness data, so virtual and actual sizes are the same
+    let output_size = consensus::encode::serialize(&op_return).len();
+
+    let mut output = vec![TxOut::default(); request.outputs];
+    output.push(op_return.clone());
+
+    let tx = Transaction {
+      version: 2,
+      lock_time: bitcoin::PackedLockTime::ZERO,
+      input: Vec::new(),
+      output,
+    };
+
+    let KeepsakeDecodeJson {
+      keepsake, cenotaph, ..
+    } = KeepsakeDecodeJson::new(None, &tx);
+
+    Ok(Json(KeepsakeEstimateJson {
+      script: hex::encode(op_return.script_pubkey.as_bytes()),
+      script_size: op_return.script_pubkey.len(),
+      standard: op_return.script_pubkey.len() <= Keepsake::MAX_STANDARD_SCRIPT_SIZE,
+      output_size,
+      fee: fee_rate.fee(output_size).to_sat(),
+      round_trips: keepsake.as_ref() == Some(&request.keepsake),
+      keepsake,
+      cenotaph,
+    }))
+  }
+
+  async fn decode_tx(
+    Extension(index): Extension<Arc<Index>>,
+    Path(txid): Path<Txid>,
+  ) -> ServerResult<Json<KeepsakeDecodeJson>> {
+    task::block_in_place(|| {
+      let tx = index
+        .get_transaction(txid)?
+        .ok_or_not_found(|| format!("transaction {txid}"))?;
+
+      Ok(Json(KeepsakeDecodeJson::new(Some(txid), &tx)))
+    })
+  }
+
+  /// Runs the keepsake of the raw transaction in the request body against the
+  /// current relic state, without writing to the index.
+  async fn simulate(
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<EventsQuery>,
+    body: String,
+  ) -> ServerResult<Json<SimulationJson>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no bone index".to_string(),
+        ));
+      }
+
+      let tx = hex::decode(body.trim())
+        .ok()
+        .and_then(|bytes| consensus::encode::deserialize::<Transaction>(&bytes).ok())
+        .ok_or_else(|| ServerError::BadRequest("invalid raw transaction hex".into()))?;
+
+      match Keepsake::decipher(&tx) {
+        None => {
+          return Err(ServerError::BadRequest(
+            "transaction contains no keepsake".into(),
+          ))
+        }
+        // the inscriptions of the transaction are not simulated
+        Some(RelicArtifact::Keepsake(keepsake))
+          if keepsake.sealing
+            || keepsake.enshrining.is_some()
+            || keepsake.summoning.is_some()
+            || keepsake.encasing.is_some()
+            || keepsake.release
+            || keepsake.withdraw.is_some()
+            || keepsake.relock.is_some()
+            || keepsake.subsidy_schedule.is_some() =>
+        {
+          return Err(ServerError::BadRequest(
+            "sealings, enshrinings, summonings, chests and subsidy schedules cannot be simulated"
+              .into(),
+          ))
+        }
+        Some(_) => {}
+      }
+
+      let events = index.simulate_relic_transaction(&tx)?;
+
+      let error = events.iter().find_map(|event| match event.info {
+        EventInfo::RelicError { error, .. } => Some(error),
+        _ => None,
+      });
+
+      let mut lookups = 0;
+
+      Ok(Json(SimulationJson {
+        txid: tx.txid(),
+        error,
+        events: events
+          .into_iter()
+          .map(|event| Self::event_with_inscription_info(&index, event, &query, &mut lookups))
+          .collect::<ServerResult<Vec<EventWithRelicInscriptionInfo>>>()?,
+      }))
+    })
+  }
+
+  /// Returns every rule the updater would reject the proposed sealing and
+  /// enshrining for, so creators can check their terms before paying fees.
+  async fn validate_enshrining(
+    Extension(index): Extension<Arc<Index>>,
+    Json(request): Json<ValidateEnshriningRequest>,
+  ) -> ServerResult<Json<EnshriningCheck>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no bone index".to_string(),
+        ));
+      }
+
+      let enshrining = Enshrining {
+        symbol: request.symbol,
+        subsidy: request.subsidy,
+        mint_terms: request.mint_terms,
+        turbo: request.turbo,
+      };
+
+      Ok(Json(index.check_enshrining(
+        request.spaced_relic,
+        &enshrining,
+        request.inputs.as_deref(),
+      )?))
+    })
+  }
+
+  /// Adds formatted amounts and, while fewer than
+  /// `MAX_EVENT_INSCRIPTION_LOOKUPS` have been made for the request, details of
+  /// transferred inscriptions to `event`.
+  fn event_with_inscription_info(
+    index: &Index,
+    event: Event,
+    query: &EventsQuery,
+    lookups: &mut usize,
+  ) -> ServerResult<EventWithRelicInscriptionInfo> {
+    let inscription = match &event.info {
+      EventInfo::InscriptionTransferred { inscription_id, .. }
+        if query.show_inscriptions.unwrap_or(false) && *lookups < MAX_EVENT_INSCRIPTION_LOOKUPS =>
+      {
+        *lookups += 1;
+        index
+          .inscription_relic_info(query::Inscription::Id(*inscription_id))?
+          .map(|inscription_info| RelicShibescriptionJson {
+            is_bonestone: inscription_info.is_bonestone,
+            relic_sealed: inscription_info.relic_sealed,
+            relic_enshrined: inscription_info.relic_enshrined,
+          })
+      }
+      _ => None,
+    };
+
+    Ok(EventWithRelicInscriptionInfo {
+      block_height: event.block_height,
+      event_index: event.event_index,
+      txid: event.txid,
+      inscription,
+      formatted: Self::formatted_amounts(index, &event, query)?,
+      info: event.info,
+      ticker: None,
+    })
+  }
+
   async fn recent_relic_events(
     Extension(index): Extension<Arc<Index>>,
     Query(query): Query<EventsQuery>,
@@ -1576,6 +3295,7 @@ impl Server {
                         event_index: event.event_index,
                         txid: event.txid,
                         inscription: None,
+                        formatted: Self::formatted_amounts(&index, &event, &query)?,
                         info: event.info,
                         ticker: None,
                       };
@@ -1614,6 +3334,29 @@ impl Server {
     })
   }
 
+  fn formatted_amounts(
+    index: &Index,
+    event: &Event,
+    query: &EventsQuery,
+  ) -> ServerResult<Option<BTreeMap<String, String>>> {
+    Ok(if query.formatted.unwrap_or(false) {
+      Some(index.formatted_event_amounts(event)?)
+    } else {
+      None
+    })
+  }
+
+  fn formatted_event(
+    index: &Index,
+    event: Event,
+    query: &EventsQuery,
+  ) -> ServerResult<FormattedEvent> {
+    Ok(FormattedEvent {
+      formatted: Self::formatted_amounts(index, &event, query)?,
+      event,
+    })
+  }
+
   async fn relic_events_paginated(
     Extension(server_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1621,7 +3364,7 @@ impl Server {
       DeserializeFromStr<query::Relic>,
       DeserializeFromStr<usize>,
     )>,
-    Query(query): Query<JsonQuery>,
+    Query(query): Query<EventsQuery>,
   ) -> ServerResult<Response> {
     task::block_in_place(|| {
       if !index.has_relic_index() {
@@ -1644,14 +3387,34 @@ impl Server {
         .relic(relic)?
         .ok_or_not_found(|| format!("bone {relic}"))?;
 
-      let events = index
-        .events_for_relic(relic, 1_000, page_index)?
+      let page_size = query.limit.unwrap_or(1_000).clamp(1, 1_000);
+
+      let (events, total_events) = index
+        .events_for_relic(
+          relic,
+          page_size,
+          page_index,
+          query.order.unwrap_or_default() == EventOrder::Asc,
+        )?
         .ok_or_not_found(|| format!("bone {relic}"))?;
 
+      let events = events
+        .into_iter()
+        .map(|event| Self::formatted_event(&index, event, &query))
+        .collect::<ServerResult<Vec<FormattedEvent>>>()?;
+
+      let next_page = (u64::try_from(page_index.saturating_add(1).saturating_mul(page_size))
+        .unwrap_or(u64::MAX)
+        < total_events)
+        .then_some(page_index + 1);
+
       Ok(if query.json.unwrap_or(false) {
         Json(RelicEventsHtml {
           spaced_relic: entry.spaced_relic,
           events,
+          total_events,
+          next_page,
+          prev_page: page_index.checked_sub(1),
         })
         .into_response()
       } else {
@@ -1660,290 +3423,1786 @@ impl Server {
     })
   }
 
+  fn parse_address(index: &Index, address: &str) -> ServerResult<Address> {
+    index
+      .chain()
+      .parse_address(address)
+      .map_err(|err| ServerError::BadRequest(format!("invalid address: {err}")))
+  }
+
+  fn relic_by_query(
+    index: &Index,
+    relic_query: query::Relic,
+  ) -> ServerResult<(RelicId, RelicEntry, Option<InscriptionId>)> {
+    if !index.has_relic_index() {
+      return Err(ServerError::NotFound(
+        "this server has no bone index".to_string(),
+      ));
+    }
+
+    let relic = match relic_query {
+      query::Relic::Spaced(spaced_relic) => spaced_relic.relic,
+      query::Relic::Id(relic_id) => index
+        .get_relic_by_id(relic_id)?
+        .ok_or_not_found(|| format!("bone {relic_id}"))?,
+      query::Relic::Number(number) => index
+        .get_relic_by_number(usize::try_from(number).unwrap())?
+        .ok_or_not_found(|| format!("bone number {number}"))?,
+    };
+
+    index
+      .relic(relic)?
+      .ok_or_not_found(|| format!("bone {relic}"))
+  }
+
   async fn relic(
     Extension(server_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(curation): Extension<Arc<Curation>>,
     Path(DeserializeFromStr(relic_query)): Path<DeserializeFromStr<query::Relic>>,
-    Query(query): Query<JsonQuery>,
+    Query(query): Query<FormattedJsonQuery>,
   ) -> ServerResult<Response> {
     task::block_in_place(|| {
-      if !index.has_relic_index() {
-        return Err(ServerError::NotFound(
-          "this server has no bone index".to_string(),
-        ));
-      }
-
-      let relic = match relic_query {
-        query::Relic::Spaced(spaced_relic) => spaced_relic.relic,
-        query::Relic::Id(relic_id) => index
-          .get_relic_by_id(relic_id)?
-          .ok_or_not_found(|| format!("bone {relic_id}"))?,
-        query::Relic::Number(number) => index
-          .get_relic_by_number(usize::try_from(number).unwrap())?
-          .ok_or_not_found(|| format!("bone number {number}"))?,
-      };
+      let (id, entry, owner) = Self::relic_by_query(&index, relic_query)?;
 
-      let (id, entry, owner) = index
-        .relic(relic)?
-        .ok_or_not_found(|| format!("bone {relic}"))?;
-
-      if entry.enshrining != Txid::all_zeros() {
-        let enshrining_txid = entry.enshrining;
-
-        let transaction = index
-          .get_transaction(enshrining_txid)?
-          .ok_or_not_found(|| format!("transaction {enshrining_txid}"))?;
-
-        let mut thumb: Option<InscriptionId> = None;
-
-        for (vout, output) in transaction.output.iter().enumerate() {
-          let outpoint = OutPoint::new(enshrining_txid, vout as u32);
-
-          let inscriptions = index.get_inscriptions_on_output(outpoint)?;
+      let formatted = query
+        .formatted
+        .unwrap_or(false)
+        .then(|| entry.formatted_amounts());
 
-          if let Some(inscription_id) = inscriptions.first() {
-            thumb = Some(*inscription_id);
-            break;
-          }
-        }
+      // the next block is the earliest a mint or swap could be included in
+      let height = u64::from(index.block_count()?);
+      let mintable = entry.mintable_at(height, None).is_ok();
+      let swappable = entry.swappable_at(height).is_ok();
 
-        let mintable = entry.mintable(u128::MAX).is_ok();
+      let thumb = index.get_relic_thumbnail(id)?;
+      let estimates = index.get_relic_estimates(id, &entry)?;
 
-        return Ok(if query.json.unwrap_or(false) {
-          Json(RelicHtml {
-            entry: entry.into(),
-            id,
-            mintable,
-            owner,
-            thumb,
-          })
-          .into_response()
-        } else {
-          RelicHtml {
-            entry: entry.into(),
-            id,
-            mintable,
-            owner,
-            thumb,
-          }
-          .page(server_config)
-          .into_response()
-        });
-      }
+      let mut entry = RelicEntryHtml::from(entry);
+      curation.flag(&mut entry);
 
-      let mintable = entry.mintable(u128::MAX).is_ok();
+      let relic = RelicHtml {
+        entry,
+        id,
+        mintable,
+        swappable,
+        owner,
+        thumb,
+        estimates,
+        formatted,
+      };
 
       Ok(if query.json.unwrap_or(false) {
-        Json(RelicHtml {
-          entry: entry.into(),
-          id,
-          mintable,
-          owner,
-          thumb: None,
-        })
-        .into_response()
+        Json(relic).into_response()
       } else {
-        RelicHtml {
-          entry: entry.into(),
-          id,
-          mintable,
-          owner,
-          thumb: None,
-        }
-        .page(server_config)
-        .into_response()
+        relic.page(server_config).into_response()
       })
     })
   }
 
-  async fn relics(
-    Extension(server_config): Extension<Arc<PageConfig>>,
+  /// Resolves bone ids, spaced names and numbers, in any mix, to their
+  /// entries. Identifiers that are invalid or name no bone get an `error`
+  /// instead of failing the request.
+  async fn relics_resolve(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(curation): Extension<Arc<Curation>>,
+    Json(queries): Json<Vec<String>>,
+  ) -> ServerResult<Json<Vec<ResolvedRelicJson>>> {
+    if queries.len() > MAX_RESOLVED_RELICS {
+      return Err(ServerError::BadRequest(format!(
+        "at most {MAX_RESOLVED_RELICS} bones can be resolved at once"
+      )));
+    }
+
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no bone index".to_string(),
+        ));
+      }
+
+      queries
+        .into_iter()
+        .map(|query| {
+          let resolved = match query.parse::<query::Relic>() {
+            Ok(relic_query) => Self::relic_by_query(&index, relic_query),
+            Err(err) => Err(ServerError::BadRequest(format!(
+              "invalid bone `{query}`: {err}"
+            ))),
+          };
+
+          match resolved {
+            Ok((id, entry, owner)) => {
+              let mut entry = RelicEntryHtml::from(entry);
+              curation.flag(&mut entry);
+
+              Ok(ResolvedRelicJson {
+                query,
+                id: Some(id),
+                entry: Some(entry),
+                owner,
+                error: None,
+              })
+            }
+            Err(ServerError::NotFound(error) | ServerError::BadRequest(error)) => {
+              Ok(ResolvedRelicJson {
+                query,
+                id: None,
+                entry: None,
+                owner: None,
+                error: Some(error),
+              })
+            }
+            Err(err) => Err(err),
+          }
+        })
+        .collect::<ServerResult<Vec<ResolvedRelicJson>>>()
+        .map(Json)
+    })
+  }
+
+  async fn relic_meta(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(relic_query)): Path<DeserializeFromStr<query::Relic>>,
+  ) -> ServerResult<Json<RelicMetaJson>> {
+    task::block_in_place(|| {
+      let (id, entry, owner) = Self::relic_by_query(&index, relic_query)?;
+
+      let metadata = match owner {
+        Some(owner) => index
+          .get_inscription_by_id(owner)?
+          .and_then(|inscription| inscription.metadata()),
+        None => None,
+      };
+
+      Ok(Json(RelicMetaJson {
+        id,
+        spaced_relic: entry.spaced_relic,
+        symbol: entry.symbol,
+        spacers: entry.spaced_relic.spacers,
+        divisibility: Enshrining::DIVISIBILITY,
+        thumbnail: index.get_relic_thumbnail(id)?,
+        owner,
+        owner_sequence_number: entry.owner_sequence_number,
+        metadata,
+      }))
+    })
+  }
+
+  async fn relic_supply(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(relic_query)): Path<DeserializeFromStr<query::Relic>>,
+    Query(query): Query<SupplyQuery>,
+  ) -> ServerResult<Json<RelicSupplyJson>> {
+    task::block_in_place(|| {
+      let (id, entry, _) = Self::relic_by_query(&index, relic_query)?;
+
+      let from = query.from.unwrap_or(0);
+      let to = query.to.unwrap_or(u32::MAX);
+
+      if from > to {
+        return Err(ServerError::BadRequest(
+          "`from` must not be greater than `to`".into(),
+        ));
+      }
+
+      let (mut previous, mut history) = index.get_relic_supply_history(id, from, to, 1001)?;
+
+      let more = history.len() > 1000;
+      history.truncate(1000);
+
+      let points = history
+        .into_iter()
+        .map(|(height, supply)| {
+          let before = previous.unwrap_or_default();
+          previous = Some(supply);
+          SupplyPointJson {
+            height,
+            circulating_supply: supply.circulating,
+            burned: supply.burned,
+            mints: supply.mints,
+            circulating_supply_delta: i128::try_from(supply.circulating).unwrap_or(i128::MAX)
+              - i128::try_from(before.circulating).unwrap_or(i128::MAX),
+            burned_delta: supply.burned.saturating_sub(before.burned),
+            mints_delta: supply.mints.saturating_sub(before.mints),
+          }
+        })
+        .collect();
+
+      Ok(Json(RelicSupplyJson {
+        id,
+        spaced_relic: entry.spaced_relic,
+        from,
+        to,
+        points,
+        more,
+      }))
+    })
+  }
+
+  async fn relic_mint_status(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(relic_query)): Path<DeserializeFromStr<query::Relic>>,
+  ) -> ServerResult<Json<RelicMintStatusJson>> {
+    task::block_in_place(|| {
+      let (id, entry, _) = Self::relic_by_query(&index, relic_query)?;
+
+      let terms = entry
+        .mint_terms
+        .ok_or_not_found(|| format!("bone {} mint terms", entry.spaced_relic))?;
+
+      let height = index.height()?.ok_or_not_found(|| "genesis block")?.n();
+
+      let cap = terms.cap.unwrap_or_default();
+
+      let estimates = index.get_relic_estimates(id, &entry)?;
+
+      Ok(Json(RelicMintStatusJson {
+        id,
+        spaced_relic: entry.spaced_relic,
+        // the next block is the earliest a mint could be included in
+        mintable: entry.mintable_at(index.block_count()?.into(), None).is_ok(),
+        mints: entry.state.mints,
+        cap,
+        remaining: cap.saturating_sub(entry.state.mints),
+        amount: terms.amount.unwrap_or_default(),
+        price: terms.price.filter(|price| *price > 0),
+        mints_per_block: estimates.map(|estimates| estimates.mints_per_block),
+        window: RelicEntry::ESTIMATE_BLOCKS,
+        blocks_to_mint_out: estimates
+          .map(|estimates| estimates.mint_out_height.saturating_sub(height.into())),
+        mint_out_height: estimates.map(|estimates| estimates.mint_out_height),
+      }))
+    })
+  }
+
+  async fn relic_distribution(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(relic_query)): Path<DeserializeFromStr<query::Relic>>,
+  ) -> ServerResult<Json<RelicDistributionJson>> {
+    task::block_in_place(|| {
+      let (id, entry, _) = Self::relic_by_query(&index, relic_query)?;
+
+      let height = index.height()?.ok_or_not_found(|| "genesis block")?.n();
+
+      Ok(Json(RelicDistributionJson {
+        id,
+        spaced_relic: entry.spaced_relic,
+        height,
+        distribution: index.get_relic_distribution(id)?,
+      }))
+    })
+  }
+
+  /// The height of a balance history query, the current height by default.
+  fn history_height(index: &Index, height: Option<u32>) -> ServerResult<u32> {
+    if !index.has_relic_history_index() {
+      return Err(ServerError::NotFound(
+        "this server has no bone history index".to_string(),
+      ));
+    }
+
+    let current = index.height()?.ok_or_not_found(|| "genesis block")?.n();
+
+    match height {
+      Some(height) if height > current => Err(ServerError::BadRequest(format!(
+        "height {height} is above the index height {current}"
+      ))),
+      Some(height) => Ok(height),
+      None => Ok(current),
+    }
+  }
+
+  async fn address_relic_balances(
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<String>,
+    Query(query): Query<HistoryQuery>,
+  ) -> ServerResult<Json<AddressRelicBalancesJson>> {
+    task::block_in_place(|| {
+      let height = Self::history_height(&index, query.height)?;
+
+      let address = Self::parse_address(&index, &address)?;
+
+      Ok(Json(AddressRelicBalancesJson {
+        relics: index.get_relic_balances_at(&address, height)?,
+        address: address.to_string(),
+        height,
+      }))
+    })
+  }
+
+  async fn relic_holders(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(relic_query)): Path<DeserializeFromStr<query::Relic>>,
+    Query(query): Query<HistoryQuery>,
+  ) -> ServerResult<Json<RelicHoldersJson>> {
+    const PAGE_SIZE: usize = 1000;
+
+    task::block_in_place(|| {
+      let height = Self::history_height(&index, query.height)?;
+
+      let (id, entry, _) = Self::relic_by_query(&index, relic_query)?;
+
+      let holders = index.get_relic_holders_at(id, height)?;
+
+      let start = query.page.unwrap_or_default().saturating_mul(PAGE_SIZE);
+
+      Ok(Json(RelicHoldersJson {
+        id,
+        spaced_relic: entry.spaced_relic,
+        height,
+        more: holders.len() > start.saturating_add(PAGE_SIZE),
+        holders: holders
+          .into_iter()
+          .skip(start)
+          .take(PAGE_SIZE)
+          .map(|(address, amount)| RelicHolderJson { address, amount })
+          .collect(),
+      }))
+    })
+  }
+
+  async fn relic_fees(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(relic_query)): Path<DeserializeFromStr<query::Relic>>,
+    Query(query): Query<SupplyQuery>,
+  ) -> ServerResult<Json<RelicFeesJson>> {
+    task::block_in_place(|| {
+      let (_, entry, _) = Self::relic_by_query(&index, relic_query)?;
+      Ok(Json(Self::fees_report(
+        &index,
+        Some(entry.spaced_relic),
+        query,
+      )?))
+    })
+  }
+
+  async fn relic_twap(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(relic_query)): Path<DeserializeFromStr<query::Relic>>,
+    Query(query): Query<TwapQuery>,
+  ) -> ServerResult<Json<RelicTwapJson>> {
+    task::block_in_place(|| {
+      let (id, entry, _) = Self::relic_by_query(&index, relic_query)?;
+
+      let window = query.window.unwrap_or(DEFAULT_TWAP_WINDOW);
+
+      if window == 0 || window > MAX_TWAP_WINDOW {
+        return Err(ServerError::BadRequest(format!(
+          "`window` must be between 1 and {MAX_TWAP_WINDOW}"
+        )));
+      }
+
+      let to = index.height()?.ok_or_not_found(|| "genesis block")?.n();
+      let from = (to + 1).saturating_sub(window);
+
+      let twap = index.get_relic_twap(id, from, to)?;
+
+      Ok(Json(RelicTwapJson {
+        id,
+        spaced_relic: entry.spaced_relic,
+        window,
+        from,
+        to,
+        blocks: twap.map_or(0, |(_, blocks)| blocks),
+        twap: twap.map(|(twap, _)| twap),
+        price: entry.pool.map(|pool| pool.price()),
+      }))
+    })
+  }
+
+  async fn relic_subsidy(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(relic_query)): Path<DeserializeFromStr<query::Relic>>,
+  ) -> ServerResult<Json<RelicSubsidyJson>> {
+    task::block_in_place(|| {
+      let (id, entry, _) = Self::relic_by_query(&index, relic_query)?;
+
+      let height = index.height()?.ok_or_not_found(|| "genesis block")?.n();
+
+      let subsidy = entry.state.subsidy;
+      let paid = subsidy - entry.state.subsidy_remaining;
+      let schedule = index.get_subsidy_schedule(id)?;
+
+      Ok(Json(RelicSubsidyJson {
+        id,
+        spaced_relic: entry.spaced_relic,
+        subsidy,
+        paid,
+        released: schedule.map_or(subsidy, |schedule| schedule.released),
+        locked: entry.state.subsidy_locked,
+        schedule: schedule.map(|schedule| {
+          let remaining_releases = (subsidy - schedule.released).div_ceil(schedule.amount);
+          let next_release_height = schedule.next_release_height(height.into());
+          SubsidyScheduleJson {
+            amount: schedule.amount,
+            epoch: schedule.epoch,
+            start: schedule.start,
+            next_release_height,
+            remaining_releases,
+            final_release_height: next_release_height.saturating_add(
+              schedule.epoch.saturating_mul(
+                u64::try_from(remaining_releases.saturating_sub(1)).unwrap_or(u64::MAX),
+              ),
+            ),
+          }
+        }),
+      }))
+    })
+  }
+
+  async fn relic_owner_history(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(relic_query)): Path<DeserializeFromStr<query::Relic>>,
+  ) -> ServerResult<Json<RelicOwnerHistoryJson>> {
+    task::block_in_place(|| {
+      let (id, entry, owner) = Self::relic_by_query(&index, relic_query)?;
+
+      let inscription = owner.ok_or_not_found(|| format!("owner of bone {id}"))?;
+
+      let changes = index.get_relic_owner_history(id)?.unwrap_or_default();
+
+      Ok(Json(RelicOwnerHistoryJson {
+        id,
+        spaced_relic: entry.spaced_relic,
+        inscription,
+        changes,
+      }))
+    })
+  }
+
+  async fn relic_burns(
+    Extension(index): Extension<Arc<Index>>,
+    Path(relic_query): Path<DeserializeFromStr<query::Relic>>,
+  ) -> ServerResult<Json<RelicBurnsJson>> {
+    Self::relic_burns_paginated(Extension(index), Path((relic_query, 0))).await
+  }
+
+  async fn relic_burns_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(relic_query), page_index)): Path<(
+      DeserializeFromStr<query::Relic>,
+      usize,
+    )>,
+  ) -> ServerResult<Json<RelicBurnsJson>> {
+    task::block_in_place(|| {
+      let (id, entry, _) = Self::relic_by_query(&index, relic_query)?;
+
+      let (burns, more) = index.get_relic_burns(id, 100, page_index)?;
+
+      Ok(Json(RelicBurnsJson {
+        id,
+        spaced_relic: entry.spaced_relic,
+        burned: entry.state.burned,
+        burns: burns
+          .into_iter()
+          .filter_map(|event| match event.info {
+            EventInfo::RelicBurned { amount, .. } => Some(RelicBurnJson {
+              block_height: event.block_height,
+              txid: event.txid,
+              amount,
+            }),
+            _ => None,
+          })
+          .collect(),
+        more,
+        page_index,
+      }))
+    })
+  }
+
+  async fn relics_burned(
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult<Json<Vec<BurnedRelicJson>>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no bone index".to_string(),
+        ));
+      }
+
+      let mut relics = index
+        .relics()?
+        .into_iter()
+        .filter(|(_, entry)| entry.state.burned > 0)
+        .map(|(id, entry)| BurnedRelicJson {
+          id,
+          spaced_relic: entry.spaced_relic,
+          burned: entry.state.burned,
+          max_supply: entry.max_supply(),
+          circulating_supply: entry.circulating_supply(),
+        })
+        .collect::<Vec<BurnedRelicJson>>();
+
+      relics.sort_by(|a, b| b.burned.cmp(&a.burned).then(a.id.cmp(&b.id)));
+
+      Ok(Json(relics))
+    })
+  }
+
+  async fn relics_fees(
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<SupplyQuery>,
+  ) -> ServerResult<Json<RelicFeesJson>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no bone index".to_string(),
+        ));
+      }
+
+      Ok(Json(Self::fees_report(&index, None, query)?))
+    })
+  }
+
+  async fn relics_leaderboard(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(leaderboards): Extension<Arc<Leaderboards>>,
+    Query(query): Query<LeaderboardQuery>,
+  ) -> ServerResult<Json<LeaderboardJson>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no bone index".to_string(),
+        ));
+      }
+
+      let window = query.window.unwrap_or(DEFAULT_LEADERBOARD_WINDOW);
+
+      if window == 0 || window > MAX_LEADERBOARD_WINDOW {
+        return Err(ServerError::BadRequest(format!(
+          "`window` must be between 1 and {MAX_LEADERBOARD_WINDOW}"
+        )));
+      }
+
+      let (to, tip) = index.tip()?.ok_or_not_found(|| "genesis block")?;
+      let from = (to + 1).saturating_sub(window);
+
+      let leaderboard = leaderboards.get(&index, query.metric, window, to, tip)?;
+
+      let relics = leaderboard
+        .iter()
+        .enumerate()
+        .map(|(rank, (id, activity))| {
+          let entry = index
+            .get_relic_entry_by_id(*id)?
+            .ok_or_not_found(|| format!("bone {id}"))?;
+
+          Ok(LeaderboardEntryJson {
+            rank: rank + 1,
+            id: *id,
+            spaced_relic: entry.spaced_relic,
+            value: activity.metric(query.metric),
+            mints: activity.mints,
+            swaps: activity.swaps,
+            volume: activity.volume,
+            fees: activity.fees,
+          })
+        })
+        .collect::<ServerResult<Vec<LeaderboardEntryJson>>>()?;
+
+      Ok(Json(LeaderboardJson {
+        metric: query.metric,
+        window,
+        from,
+        to,
+        relics,
+      }))
+    })
+  }
+
+  fn fees_report(
+    index: &Index,
+    spaced_relic: Option<SpacedRelic>,
+    query: SupplyQuery,
+  ) -> ServerResult<RelicFeesJson> {
+    let relic = spaced_relic.map(|spaced_relic| spaced_relic.relic);
+
+    let from = query.from.unwrap_or(0);
+    let to = query.to.unwrap_or(u32::MAX);
+
+    if from > to {
+      return Err(ServerError::BadRequest(
+        "`from` must not be greater than `to`".into(),
+      ));
+    }
+
+    let (previous, mut history) = index.get_relic_fees_history(relic, from, to, 1001)?;
+
+    let more = history.len() > 1000;
+    history.truncate(1000);
+
+    let previous = previous.unwrap_or_default();
+    let cumulative = index.get_relic_fees(relic, to)?;
+
+    let mut before = previous;
+    let blocks = history
+      .into_iter()
+      .map(|(height, cumulative)| {
+        let fees = cumulative.since(before);
+        before = cumulative;
+        FeesPointJson {
+          height,
+          fees,
+          cumulative,
+        }
+      })
+      .collect();
+
+    Ok(RelicFeesJson {
+      spaced_relic,
+      from,
+      to,
+      total: cumulative.since(previous),
+      cumulative,
+      blocks,
+      more,
+    })
+  }
+
+  async fn relic_error_stats(
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<SupplyQuery>,
+  ) -> ServerResult<Json<ErrorStatsJson>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no bone index".to_string(),
+        ));
+      }
+
+      let from = query.from.unwrap_or(0);
+      let to = query.to.unwrap_or(u32::MAX);
+
+      if from > to {
+        return Err(ServerError::BadRequest(
+          "`from` must not be greater than `to`".into(),
+        ));
+      }
+
+      let mut history = index.get_relic_error_counts(from, to, 1001)?;
+
+      let more = history.len() > 1000;
+      history.truncate(1000);
+
+      let mut total = BTreeMap::<String, u64>::new();
+      for (_, counts) in &history {
+        for (kind, count) in counts {
+          *total.entry(kind.clone()).or_default() += count;
+        }
+      }
+
+      Ok(Json(ErrorStatsJson {
+        from,
+        to,
+        total: ErrorCountsJson::new(total),
+        blocks: history
+          .into_iter()
+          .map(|(height, counts)| ErrorStatsPointJson {
+            height,
+            counts: ErrorCountsJson::new(counts),
+          })
+          .collect(),
+        more,
+      }))
+    })
+  }
+
+  async fn pairs(Extension(index): Extension<Arc<Index>>) -> ServerResult<Json<PairsJson>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no bone index".to_string(),
+        ));
+      }
+
+      let height = index.block_count()?;
+      let since = height.saturating_sub(PAIR_VOLUME_BLOCKS);
+
+      let base = index
+        .get_relic_by_id(crate::relics::RELIC_ID)?
+        .ok_or_not_found(|| "base token")?;
+
+      let mut pairs = Vec::new();
+
+      for (id, entry) in index.relics()? {
+        let Ok(pool) = entry.swappable_at(height.into()) else {
+          continue;
+        };
+
+        let (base_volume, target_volume) = index.get_relic_swap_volume(id, since)?;
+
+        pairs.push(PairJson {
+          ticker_id: format!("{base}_{}", entry.spaced_relic.relic),
+          base_currency: base.to_string(),
+          target_currency: entry.spaced_relic.to_string(),
+          pool_id: id,
+          last_price: pool.lossy_price(),
+          base_volume,
+          target_volume,
+          liquidity_in_base: pool.base_supply.saturating_mul(2),
+          base_supply: pool.base_supply,
+          target_supply: pool.quote_supply,
+          fee_percentage: f64::from(pool.fee_bps) / 100.0,
+          fee_bps: pool.fee_bps,
+        });
+      }
+
+      Ok(Json(PairsJson { height, pairs }))
+    })
+  }
+
+  async fn swap_route(
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<SwapRouteQuery>,
+  ) -> ServerResult<Json<SwapRouteJson>> {
+    task::block_in_place(|| {
+      let (input, _, _) = Self::relic_by_query(&index, query.from.0)?;
+      let (output, _, _) = Self::relic_by_query(&index, query.to.0)?;
+
+      if input == output {
+        return Err(ServerError::BadRequest(
+          "cannot swap a bone for itself".into(),
+        ));
+      }
+
+      let slippage_bps = query.slippage_bps.unwrap_or_default();
+
+      if slippage_bps > 10_000 {
+        return Err(ServerError::BadRequest(
+          "slippage_bps must be at most 10000".into(),
+        ));
+      }
+
+      let height = index.block_count()?;
+
+      let hops = index
+        .get_swap_route(input, output, query.amount.0)?
+        .map_err(|err| ServerError::BadRequest(format!("no route: {err}")))?;
+
+      let output_amount = hops.last().map(|hop| hop.output_amount).unwrap_or_default();
+
+      // output_amount * slippage_bps / 10000, split so it can't overflow
+      let slippage_bps = u128::from(slippage_bps);
+      let slippage =
+        output_amount / 10_000 * slippage_bps + output_amount % 10_000 * slippage_bps / 10_000;
+      let min_output_amount = output_amount - slippage;
+
+      Ok(Json(SwapRouteJson {
+        height,
+        input,
+        output,
+        input_amount: query.amount.0,
+        output_amount,
+        min_output_amount,
+        hops,
+      }))
+    })
+  }
+
+  async fn token_list(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Extension(curation): Extension<Arc<Curation>>,
+    Query(query): Query<TokenListQuery>,
+  ) -> ServerResult<Json<TokenListJson>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no bone index".to_string(),
+        ));
+      }
+
+      // logo URIs are absolute if the public origin of this server is known
+      let origin = page_config
+        .csp_origin
+        .clone()
+        .or_else(|| {
+          page_config
+            .domain
+            .as_ref()
+            .map(|domain| format!("https://{domain}"))
+        })
+        .unwrap_or_default();
+
+      let mut tokens = Vec::new();
+
+      for (id, entry) in index.relics()? {
+        let spaced_relic = entry.spaced_relic;
+
+        let tags = CurationList::ALL
+          .into_iter()
+          .filter(|list| curation.contains(*list, spaced_relic.relic))
+          .collect::<Vec<CurationList>>();
+
+        if let Some(curated) = query.curated {
+          if !tags.contains(&curated) {
+            continue;
+          }
+        }
+
+        tokens.push(TokenJson {
+          id,
+          name: spaced_relic,
+          ticker: spaced_relic.relic.to_string(),
+          symbol: entry.symbol,
+          decimals: Enshrining::DIVISIBILITY,
+          logo_uri: index
+            .get_relic_thumbnail(id)?
+            .map(|thumbnail| format!("{origin}/content/{thumbnail}")),
+          tags,
+        });
+      }
+
+      Ok(Json(TokenListJson {
+        name: "Bones".into(),
+        height: index.block_count()?,
+        tags: CurationList::ALL
+          .into_iter()
+          .map(|list| {
+            (
+              list,
+              TokenListTagJson {
+                name: list.to_string(),
+                description: format!("Bones marked as {list} by this server"),
+              },
+            )
+          })
+          .collect(),
+        tokens,
+      }))
+    })
+  }
+
+  async fn relic_checkpoint(
+    Extension(index): Extension<Arc<Index>>,
+    Path(height): Path<u32>,
+  ) -> ServerResult<Json<RelicCheckpointJson>> {
+    task::block_in_place(|| {
+      let hash = index
+        .get_relic_checkpoint(height)?
+        .ok_or_not_found(|| format!("checkpoint for height {height}"))?;
+
+      Ok(Json(RelicCheckpointJson {
+        height,
+        hash: hash.to_string(),
+      }))
+    })
+  }
+
+  async fn parse_relic_id(Path(raw): Path<String>) -> Response {
+    match raw.parse::<RelicId>() {
+      Ok(id) => Json(RelicIdJson {
+        id,
+        block: id.block,
+        tx: id.tx,
+      })
+      .into_response(),
+      Err(err) => (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+          "error": err.kind(),
+          "message": err.to_string(),
+        })),
+      )
+        .into_response(),
+    }
+  }
+
+  async fn relics(
+    Extension(server_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Extension(curation): Extension<Arc<Curation>>,
+    Query(query): Query<RelicsQuery>,
+  ) -> ServerResult<Response> {
+    Self::relics_paginated(
+      Extension(server_config),
+      Extension(index),
+      Extension(curation),
+      Path(0),
+      Query(query),
+    )
+    .await
+  }
+
+  async fn relics_paginated(
+    Extension(server_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Extension(curation): Extension<Arc<Curation>>,
+    Path(page_index): Path<usize>,
+    Query(query): Query<RelicsQuery>,
+  ) -> ServerResult<Response> {
+    let relics = task::block_in_place(|| {
+      let (entries, more) = match query.curated {
+        Some(list) => {
+          let relics = curation.relics(list);
+
+          let mut entries = Vec::new();
+          for spaced_relic in relics.iter().skip(page_index * 50).take(50) {
+            if let Some(entry) = index.relic(spaced_relic.relic)? {
+              entries.push(entry);
+            }
+          }
+
+          (entries, relics.len() > (page_index + 1) * 50)
+        }
+        None => index.relics_paginated(50, page_index)?,
+      };
+
+      let prev = page_index.checked_sub(1);
+      let next = more.then_some(page_index + 1);
+
+      let entries = entries
+        .into_iter()
+        .map(|(id, entry, inscription_id)| {
+          let mut entry = RelicEntryHtml::from(entry);
+          curation.flag(&mut entry);
+          (id, entry, inscription_id)
+        })
+        .collect();
+
+      ServerResult::Ok(RelicsHtml {
+        entries,
+        more,
+        prev,
+        next,
+      })
+    })?;
+
+    if query.json.unwrap_or(false) {
+      streaming::json(relics).await
+    } else {
+      streaming::html(relics.page(server_config)).await
+    }
+  }
+
+  async fn sealing_info(
+    Extension(server_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(relic_query)): Path<DeserializeFromStr<query::Relic>>,
+    Query(query): Query<JsonQuery>,
+  ) -> ServerResult<Response> {
+    // Offload blocking DB operations
+    task::block_in_place(|| {
+      let relic = match relic_query {
+        query::Relic::Spaced(spaced_relic) => spaced_relic.relic,
+        query::Relic::Id(relic_id) => index
+          .get_relic_by_id(relic_id)?
+          .ok_or_not_found(|| format!("tick {relic_id}"))?,
+        query::Relic::Number(number) => index
+          .get_relic_by_number(usize::try_from(number).unwrap())?
+          .ok_or_not_found(|| format!("tick number {number}"))?,
+      };
+
+      let entry = index.sealing(relic)?;
+      let inscription = if let Some(inscription) = entry.0 {
+        inscription
+      } else {
+        return Err(ServerError::BadRequest(format!("tick {relic} not found")));
+      };
+      let enshrining_tx = entry.1;
+      // Decide on JSON or HTML
+      Ok(if query.json.unwrap_or(false) {
+        // Return raw JSON
+        Json(SealingHtml {
+          inscription,
+          enshrining_tx,
+        })
+        .into_response()
+      } else {
+        // Return HTML
+        SealingHtml {
+          inscription,
+          enshrining_tx,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn sealing_history(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(spaced_relic)): Path<DeserializeFromStr<SpacedRelic>>,
+  ) -> ServerResult<Json<SealingHistory>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no bone index".to_string(),
+        ));
+      }
+
+      Ok(Json(
+        index
+          .get_sealing_history(spaced_relic.relic)?
+          .ok_or_not_found(|| format!("tick {spaced_relic}"))?,
+      ))
+    })
+  }
+
+  async fn pending_sealings(
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult<Json<PendingSealingsJson>> {
+    Self::pending_sealings_page(index, 0).await
+  }
+
+  async fn pending_sealings_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Path(page): Path<usize>,
+  ) -> ServerResult<Json<PendingSealingsJson>> {
+    Self::pending_sealings_page(index, page).await
+  }
+
+  async fn pending_sealings_page(
+    index: Arc<Index>,
+    page: usize,
+  ) -> ServerResult<Json<PendingSealingsJson>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no bone index".to_string(),
+        ));
+      }
+
+      let (sealings, more) = index.get_pending_sealings(100, page)?;
+
+      Ok(Json(PendingSealingsJson {
+        stats: index.get_sealing_stats()?,
+        page,
+        sealings,
+        more,
+      }))
+    })
+  }
+
+  async fn sealings_paginated(
+    Extension(server_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(page_index): Path<usize>,
+    Query(query): Query<JsonQuery>,
+  ) -> ServerResult<Response> {
+    // Offload blocking DB operations
+    task::block_in_place(|| {
+      // page_size here is 50 — adjust as you wish
+      let (entries, more) = index.sealings_paginated(50, page_index)?;
+
+      let prev = page_index.checked_sub(1);
+      let next = more.then_some(page_index + 1);
+
+      // Decide on JSON or HTML
+      Ok(if query.json.unwrap_or(false) {
+        // Return raw JSON
+        Json(SealingsHtml {
+          entries,
+          more,
+          prev,
+          next,
+        })
+        .into_response()
+      } else {
+        // Return HTML
+        SealingsHtml {
+          entries,
+          more,
+          prev,
+          next,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn relics_balances(
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<RelicBalancesQuery>,
+  ) -> ServerResult<Response> {
+    task::block_in_place(|| {
+      if !query.json.unwrap_or(false) {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+      }
+
+      let balances = index.get_relic_balance_map()?;
+
+      // outputs are looked up once, even if they hold several relics
+      let addresses = if query.addresses.unwrap_or(false) {
+        let mut addresses = HashMap::new();
+
+        for outpoint in balances.values().flat_map(|balances| balances.keys()) {
+          if addresses.contains_key(outpoint) {
+            continue;
+          }
+
+          let address = index
+            .get_txout(*outpoint)?
+            .and_then(|output| {
+              index
+                .chain()
+                .address_from_script(&output.script_pubkey)
+                .ok()
+            })
+            .map(|address| address.to_string());
+
+          addresses.insert(*outpoint, address);
+        }
+
+        Some(addresses)
+      } else {
+        None
+      };
+
+      Ok(if query.formatted.unwrap_or(false) {
+        Self::relic_balances_json(balances, addresses.as_ref(), |pile| pile.to_string())
+      } else {
+        Self::relic_balances_json(balances, addresses.as_ref(), |pile| pile.amount)
+      })
+    })
+  }
+
+  fn relic_balances_json<T: Serialize>(
+    balances: BTreeMap<SpacedRelic, BTreeMap<OutPoint, Pile>>,
+    addresses: Option<&HashMap<OutPoint, Option<String>>>,
+    amount: impl Fn(Pile) -> T,
+  ) -> Response {
+    match addresses {
+      None => Json(
+        balances
+          .into_iter()
+          .map(|(relic, balances)| {
+            (
+              relic,
+              balances
+                .into_iter()
+                .map(|(outpoint, pile)| (outpoint, amount(pile)))
+                .collect(),
+            )
+          })
+          .collect::<BTreeMap<SpacedRelic, BTreeMap<OutPoint, T>>>(),
+      )
+      .into_response(),
+      Some(addresses) => Json(
+        balances
+          .into_iter()
+          .map(|(relic, balances)| {
+            (
+              relic,
+              balances
+                .into_iter()
+                .map(|(outpoint, pile)| {
+                  (
+                    outpoint,
+                    AddressedBalanceJson {
+                      amount: amount(pile),
+                      address: addresses.get(&outpoint).cloned().flatten(),
+                    },
+                  )
+                })
+                .collect(),
+            )
+          })
+          .collect::<BTreeMap<SpacedRelic, BTreeMap<OutPoint, AddressedBalanceJson<T>>>>(),
+      )
+      .into_response(),
+    }
+  }
+
+  async fn relics_claimable(
     Extension(index): Extension<Arc<Index>>,
     Query(query): Query<JsonQuery>,
   ) -> ServerResult<Response> {
-    Self::relics_paginated(
-      Extension(server_config),
-      Extension(index),
-      Path(0),
-      Query(query),
-    )
-    .await
+    task::block_in_place(|| {
+      Ok(if query.json.unwrap_or(false) {
+        Json(
+          index
+            .get_relic_claimable()?
+            .into_iter()
+            .collect::<BTreeMap<RelicOwner, u128>>(),
+        )
+        .into_response()
+      } else {
+        StatusCode::NOT_FOUND.into_response()
+      })
+    })
   }
 
-  async fn relics_paginated(
+  async fn address_digest(
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<String>,
+    Query(query): Query<DigestQuery>,
+  ) -> ServerResult<Response> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no relic index".to_string(),
+        ));
+      }
+
+      let address = Self::parse_address(&index, &address)?;
+
+      Ok(Json(index.address_digest(&address, query.period.unwrap_or_default())?).into_response())
+    })
+  }
+
+  async fn address_fees_paid(
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<String>,
+  ) -> ServerResult<Json<FeesPaidJson>> {
+    task::block_in_place(|| {
+      if !index.has_relic_fee_index() {
+        return Err(ServerError::NotFound(
+          "this server has no relic fee index".to_string(),
+        ));
+      }
+
+      let address = Self::parse_address(&index, &address)?;
+
+      let relics = index
+        .get_relic_fees_paid(&address)?
+        .into_iter()
+        .collect::<BTreeMap<SpacedRelic, u128>>();
+
+      Ok(Json(FeesPaidJson {
+        address: address.to_string(),
+        label: index.get_address_label(&address)?,
+        total: relics.values().sum(),
+        relics,
+      }))
+    })
+  }
+
+  /// Values the bones held by the address with the current pools, quoting
+  /// the sale of every balance like `/swap/route`.
+  async fn address_portfolio(
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<String>,
+  ) -> ServerResult<Json<PortfolioJson>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no bone index".to_string(),
+        ));
+      }
+
+      let address = Self::parse_address(&index, &address)?;
+
+      Ok(Json(PortfolioJson {
+        address: address.to_string(),
+        label: index.get_address_label(&address)?,
+        height: index.block_count()?,
+        portfolio: index.get_relic_portfolio(&address)?,
+      }))
+    })
+  }
+
+  /// Selects outputs of the address holding enough of a bone, preferring
+  /// outputs that carry nothing else, see `coin_selection::select`.
+  async fn address_select(
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<String>,
+    Query(query): Query<SelectQuery>,
+  ) -> ServerResult<Json<SelectionJson>> {
+    task::block_in_place(|| {
+      let (relic, entry, _) = Self::relic_by_query(&index, query.bone.0)?;
+
+      let address = Self::parse_address(&index, &address)?;
+
+      let mut candidates = Vec::new();
+
+      for outpoint in index.get_account_outputs(script_hash(&address.script_pubkey()))? {
+        let relics = index.get_relic_balances_for_outpoint(outpoint)?;
+
+        let Some(pile) = relics.get(&entry.spaced_relic) else {
+          continue;
+        };
+
+        candidates.push(Candidate {
+          outpoint,
+          amount: pile.amount,
+          other_relics: relics.len() - 1,
+          inscriptions: index.get_inscriptions_on_output(outpoint)?.len(),
+        });
+      }
+
+      let held = candidates
+        .iter()
+        .fold(0u128, |sum, candidate| sum.saturating_add(candidate.amount));
+
+      let Some(selected) = coin_selection::select(candidates, query.amount.0) else {
+        return Err(ServerError::BadRequest(format!(
+          "insufficient balance: {address} holds {held} of {}",
+          entry.spaced_relic
+        )));
+      };
+
+      let mut outputs = Vec::new();
+
+      for candidate in selected {
+        let output = index
+          .get_txout(candidate.outpoint)?
+          .ok_or_not_found(|| format!("output {}", candidate.outpoint))?;
+
+        outputs.push(SelectedOutputJson {
+          outpoint: candidate.outpoint,
+          amount: candidate.amount,
+          value: output.value,
+          other_relics: candidate.other_relics,
+          inscriptions: candidate.inscriptions,
+        });
+      }
+
+      Ok(Json(SelectionJson {
+        relic,
+        amount: query.amount.0,
+        selected: outputs
+          .iter()
+          .fold(0u128, |sum, output| sum.saturating_add(output.amount)),
+        outputs,
+      }))
+    })
+  }
+
+  async fn address_labels(
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult<Json<Vec<AddressLabelJson>>> {
+    task::block_in_place(|| {
+      Ok(Json(
+        index
+          .get_address_labels()?
+          .into_iter()
+          .map(|(address, label)| AddressLabelJson { address, label })
+          .collect(),
+      ))
+    })
+  }
+
+  async fn address_label(
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<String>,
+  ) -> ServerResult<Json<AddressLabelJson>> {
+    task::block_in_place(|| {
+      let address = Self::parse_address(&index, &address)?;
+
+      let label = index
+        .get_address_label(&address)?
+        .ok_or_not_found(|| format!("label of {address}"))?;
+
+      Ok(Json(AddressLabelJson {
+        address: address.to_string(),
+        label,
+      }))
+    })
+  }
+
+  async fn set_address_label(
+    Extension(admin): Extension<Arc<Admin>>,
+    Extension(index): Extension<Arc<Index>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+    Json(label): Json<AddressLabel>,
+  ) -> ServerResult<Json<AddressLabelJson>> {
+    admin.authorize(&headers)?;
+
+    if label.label.is_empty() {
+      return Err(ServerError::BadRequest("label must not be empty".into()));
+    }
+
+    task::block_in_place(|| {
+      // labels are keyed by the canonical encoding of the address
+      let address = Self::parse_address(&index, &address)?;
+
+      index.set_address_label(&address, Some(&label))?;
+
+      Ok(Json(AddressLabelJson {
+        address: address.to_string(),
+        label,
+      }))
+    })
+  }
+
+  async fn remove_address_label(
+    Extension(admin): Extension<Arc<Admin>>,
+    Extension(index): Extension<Arc<Index>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+  ) -> ServerResult<StatusCode> {
+    admin.authorize(&headers)?;
+
+    task::block_in_place(|| {
+      let address = Self::parse_address(&index, &address)?;
+
+      if !index.set_address_label(&address, None)? {
+        return Err(ServerError::NotFound(format!(
+          "label of {address} not found"
+        )));
+      }
+
+      Ok(StatusCode::NO_CONTENT)
+    })
+  }
+
+  async fn syndicate(
     Extension(server_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Path(page_index): Path<usize>,
+    Path(DeserializeFromStr(syndicate_query)): Path<DeserializeFromStr<query::Syndicate>>,
     Query(query): Query<JsonQuery>,
   ) -> ServerResult<Response> {
     task::block_in_place(|| {
-      let (entries, more) = index.relics_paginated(50, page_index)?;
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no relic index".to_string(),
+        ));
+      }
 
-      let prev = page_index.checked_sub(1);
-      let next = more.then_some(page_index + 1);
+      let syndicate_id = Self::syndicate_id_by_query(&index, syndicate_query)?;
 
-      let entries = entries
-        .into_iter()
-        .map(|(id, entry, inscription_id)| (id, entry.into(), inscription_id))
-        .collect();
+      let (id, entry, owner) = index
+        .syndicate(syndicate_id)?
+        .ok_or_not_found(|| format!("syndicate {syndicate_id}"))?;
+
+      let relic = index.get_relic_by_id(entry.treasure)?.unwrap();
+
+      let (_, treasure, _) = index
+        .relic(relic)?
+        .ok_or_not_found(|| format!("relic {relic}"))?;
+
+      let chestable = entry.chestable(index.block_count()?.into()).is_ok();
+      let response = SyndicateHtml {
+        entry: entry.into(),
+        id,
+        chestable,
+        owner,
+        treasure: treasure.into(),
+      };
 
       Ok(if query.json.unwrap_or(false) {
-        Json(RelicsHtml {
-          entries,
-          more,
-          prev,
-          next,
-        })
-        .into_response()
+        Json(response).into_response()
       } else {
-        RelicsHtml {
-          entries,
-          more,
-          prev,
-          next,
+        response.page(server_config).into_response()
+      })
+    })
+  }
+
+  fn syndicate_id_by_query(index: &Index, query: query::Syndicate) -> ServerResult<SyndicateId> {
+    Ok(match query {
+      query::Syndicate::Id(id) => id,
+      query::Syndicate::Inscription(id) => {
+        let (info, _txout, _inscription, _) = index
+          .inscription_info(query::Inscription::Id(id), true)?
+          .ok_or_not_found(|| format!("inscription {id}"))?;
+        info
+          .syndicate
+          .ok_or_not_found(|| format!("syndicate on inscription {id}"))?
+      }
+    })
+  }
+
+  fn chest_json(
+    index: &Index,
+    inscription_id: InscriptionId,
+    chest: ChestEntry,
+    syndicate: &SyndicateEntry,
+    height: u64,
+  ) -> ServerResult<ChestJson> {
+    let relic = index.get_relic_by_id(syndicate.treasure)?.unwrap();
+
+    let (_, treasure, _) = index
+      .relic(relic)?
+      .ok_or_not_found(|| format!("relic {relic}"))?;
+
+    let owner = index
+      .inscription_info(query::Inscription::Id(inscription_id), true)?
+      .and_then(|(info, _txout, _inscription, _)| info.address);
+
+    let unlock_height = chest.unlock_height(syndicate.lock);
+
+    Ok(ChestJson {
+      inscription_id,
+      syndicate: chest.syndicate,
+      relic: treasure.spaced_relic,
+      amount: chest.amount,
+      withdrawn: chest.withdrawn,
+      created_block: chest.created_block,
+      unlock_height,
+      unlocked: unlock_height <= height,
+      owner,
+    })
+  }
+
+  fn unix_seconds() -> u64 {
+    SystemTime::now()
+      .duration_since(SystemTime::UNIX_EPOCH)
+      .map(|duration| duration.as_secs())
+      .unwrap_or_default()
+  }
+
+  /// Without `nonce` and `signature`, issues a challenge to sign with the
+  /// address holding the bone's owner inscription. With them, checks the
+  /// signature and returns an attestation token.
+  async fn prove_ownership(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(ownership): Extension<Arc<Ownership>>,
+    Json(request): Json<ProveOwnershipRequest>,
+  ) -> ServerResult<Response> {
+    task::block_in_place(|| {
+      let (_, entry, owner) = Self::relic_by_query(&index, request.bone.0)?;
+
+      let inscription_id =
+        owner.ok_or_not_found(|| format!("owner inscription of bone {}", entry.spaced_relic))?;
+
+      let address = index
+        .inscription_info(query::Inscription::Id(inscription_id), true)?
+        .and_then(|(info, _txout, _inscription, _)| info.address)
+        .ok_or_not_found(|| format!("address of inscription {inscription_id}"))?;
+
+      let address = Address::from_str(&address).map_err(|err| anyhow!(err))?;
+
+      let now = Self::unix_seconds();
+
+      Ok(match (request.nonce, request.signature) {
+        (None, None) => {
+          Json(ownership.challenge(entry.spaced_relic, inscription_id, &address, now))
+            .into_response()
+        }
+        (Some(nonce), Some(signature)) => {
+          Json(ownership.attest(&nonce, &signature, &address, now)?).into_response()
+        }
+        _ => {
+          return Err(ServerError::BadRequest(
+            "`nonce` and `signature` must be given together".into(),
+          ))
         }
-        .page(server_config)
-        .into_response()
       })
     })
   }
 
-  async fn sealing_info(
-    Extension(server_config): Extension<Arc<PageConfig>>,
+  async fn ownership_attestation(
+    Extension(ownership): Extension<Arc<Ownership>>,
+    Path(token): Path<String>,
+  ) -> ServerResult<Json<Attestation>> {
+    Ok(Json(
+      ownership
+        .verify(&token, Self::unix_seconds())
+        .ok_or_else(|| ServerError::Unauthorized("invalid or expired token".into()))?,
+    ))
+  }
+
+  async fn chest(
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Json<ChestJson>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no relic index".to_string(),
+        ));
+      }
+
+      let chest = index
+        .get_chest(inscription_id)?
+        .ok_or_not_found(|| format!("chest {inscription_id}"))?;
+
+      let (_, syndicate, _) = index
+        .syndicate(chest.syndicate)?
+        .ok_or_not_found(|| format!("syndicate {}", chest.syndicate))?;
+
+      Ok(Json(Self::chest_json(
+        &index,
+        inscription_id,
+        chest,
+        &syndicate,
+        index.block_count()?.into(),
+      )?))
+    })
+  }
+
+  async fn chest_history(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Json<ChestHistoryJson>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no relic index".to_string(),
+        ));
+      }
+
+      let transfers = index
+        .get_chest_transfers(inscription_id)?
+        .into_iter()
+        .filter_map(|event| match event.info {
+          EventInfo::ChestTransferred { new_location, .. } => {
+            Some((event.block_height, event.txid, new_location))
+          }
+          _ => None,
+        })
+        .map(|(block_height, txid, location)| {
+          let address = index
+            .get_txout(location.outpoint)?
+            .and_then(|output| {
+              page_config
+                .chain
+                .address_from_script(&output.script_pubkey)
+                .ok()
+            })
+            .map(|address| address.to_string());
+
+          Ok(ChestTransferJson {
+            block_height,
+            txid,
+            location,
+            address,
+          })
+        })
+        .collect::<ServerResult<Vec<ChestTransferJson>>>()?;
+
+      let active = index.get_chest(inscription_id)?.is_some();
+
+      if !active && transfers.is_empty() {
+        return Err(ServerError::NotFound(format!("chest {inscription_id}")));
+      }
+
+      Ok(Json(ChestHistoryJson {
+        inscription_id,
+        active,
+        transfers,
+      }))
+    })
+  }
+
+  async fn chest_withdrawals(
     Extension(index): Extension<Arc<Index>>,
-    Path(DeserializeFromStr(relic_query)): Path<DeserializeFromStr<query::Relic>>,
-    Query(query): Query<JsonQuery>,
-  ) -> ServerResult<Response> {
-    // Offload blocking DB operations
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Json<ChestWithdrawalsJson>> {
     task::block_in_place(|| {
-      let relic = match relic_query {
-        query::Relic::Spaced(spaced_relic) => spaced_relic.relic,
-        query::Relic::Id(relic_id) => index
-          .get_relic_by_id(relic_id)?
-          .ok_or_not_found(|| format!("tick {relic_id}"))?,
-        query::Relic::Number(number) => index
-          .get_relic_by_number(usize::try_from(number).unwrap())?
-          .ok_or_not_found(|| format!("tick number {number}"))?,
-      };
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no relic index".to_string(),
+        ));
+      }
 
-      let entry = index.sealing(relic)?;
-      let inscription = if let Some(inscription) = entry.0 {
-        inscription
-      } else {
-        return Err(ServerError::BadRequest(format!("tick {relic} not found")));
-      };
-      let enshrining_tx = entry.1;
-      // Decide on JSON or HTML
-      Ok(if query.json.unwrap_or(false) {
-        // Return raw JSON
-        Json(SealingHtml {
-          inscription,
-          enshrining_tx,
+      let withdrawals = index
+        .get_chest_events(inscription_id, EventKind::ChestWithdrawn)?
+        .into_iter()
+        .filter_map(|event| match event.info {
+          EventInfo::ChestWithdrawn { amount, .. } => Some(ChestWithdrawalJson {
+            block_height: event.block_height,
+            txid: event.txid,
+            amount,
+          }),
+          _ => None,
         })
-        .into_response()
-      } else {
-        // Return HTML
-        SealingHtml {
-          inscription,
-          enshrining_tx,
-        }
-        .page(server_config)
-        .into_response()
-      })
+        .collect::<Vec<ChestWithdrawalJson>>();
+
+      let active = index.get_chest(inscription_id)?.is_some();
+
+      if !active && withdrawals.is_empty() {
+        return Err(ServerError::NotFound(format!("chest {inscription_id}")));
+      }
+
+      Ok(Json(ChestWithdrawalsJson {
+        inscription_id,
+        active,
+        withdrawals,
+      }))
     })
   }
 
-  async fn sealings_paginated(
-    Extension(server_config): Extension<Arc<PageConfig>>,
+  async fn chest_relocks(
     Extension(index): Extension<Arc<Index>>,
-    Path(page_index): Path<usize>,
-    Query(query): Query<JsonQuery>,
-  ) -> ServerResult<Response> {
-    // Offload blocking DB operations
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Json<ChestRelocksJson>> {
     task::block_in_place(|| {
-      // page_size here is 50 — adjust as you wish
-      let (entries, more) = index.sealings_paginated(50, page_index)?;
-
-      let prev = page_index.checked_sub(1);
-      let next = more.then_some(page_index + 1);
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no relic index".to_string(),
+        ));
+      }
 
-      // Decide on JSON or HTML
-      Ok(if query.json.unwrap_or(false) {
-        // Return raw JSON
-        Json(SealingsHtml {
-          entries,
-          more,
-          prev,
-          next,
+      let relocks = index
+        .get_chest_events(inscription_id, EventKind::ChestRelocked)?
+        .into_iter()
+        .filter_map(|event| match event.info {
+          EventInfo::ChestRelocked { unlock_height, .. } => Some(ChestRelockJson {
+            block_height: event.block_height,
+            txid: event.txid,
+            unlock_height,
+          }),
+          _ => None,
         })
-        .into_response()
-      } else {
-        // Return HTML
-        SealingsHtml {
-          entries,
-          more,
-          prev,
-          next,
-        }
-        .page(server_config)
-        .into_response()
-      })
+        .collect::<Vec<ChestRelockJson>>();
+
+      let active = index.get_chest(inscription_id)?.is_some();
+
+      if !active && relocks.is_empty() {
+        return Err(ServerError::NotFound(format!("chest {inscription_id}")));
+      }
+
+      Ok(Json(ChestRelocksJson {
+        inscription_id,
+        active,
+        relocks,
+      }))
     })
   }
 
-  async fn relics_balances(
+  async fn syndicate_chests(
     Extension(index): Extension<Arc<Index>>,
-    Query(query): Query<JsonQuery>,
-  ) -> ServerResult<Response> {
-    task::block_in_place(|| {
-      Ok(if query.json.unwrap_or(false) {
-        Json(
-          index
-            .get_relic_balance_map()?
-            .into_iter()
-            .map(|(relic, balances)| {
-              (
-                relic,
-                balances
-                  .into_iter()
-                  .map(|(outpoint, pile)| (outpoint, pile.amount))
-                  .collect(),
-              )
-            })
-            .collect::<BTreeMap<SpacedRelic, BTreeMap<OutPoint, u128>>>(),
-        )
-        .into_response()
-      } else {
-        StatusCode::NOT_FOUND.into_response()
-      })
-    })
+    Path(syndicate_query): Path<DeserializeFromStr<query::Syndicate>>,
+  ) -> ServerResult<Json<SyndicateChestsJson>> {
+    Self::syndicate_chests_paginated(
+      Extension(index),
+      Path((syndicate_query, DeserializeFromStr(0))),
+    )
+    .await
   }
 
-  async fn relics_claimable(
+  async fn syndicate_chests_paginated(
     Extension(index): Extension<Arc<Index>>,
-    Query(query): Query<JsonQuery>,
-  ) -> ServerResult<Response> {
+    Path((DeserializeFromStr(syndicate_query), DeserializeFromStr(page_index))): Path<(
+      DeserializeFromStr<query::Syndicate>,
+      DeserializeFromStr<usize>,
+    )>,
+  ) -> ServerResult<Json<SyndicateChestsJson>> {
     task::block_in_place(|| {
-      Ok(if query.json.unwrap_or(false) {
-        Json(
-          index
-            .get_relic_claimable()?
-            .into_iter()
-            .collect::<BTreeMap<RelicOwner, u128>>(),
-        )
-        .into_response()
-      } else {
-        StatusCode::NOT_FOUND.into_response()
-      })
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no relic index".to_string(),
+        ));
+      }
+
+      let syndicate_id = Self::syndicate_id_by_query(&index, syndicate_query)?;
+
+      let (_, syndicate, _) = index
+        .syndicate(syndicate_id)?
+        .ok_or_not_found(|| format!("syndicate {syndicate_id}"))?;
+
+      let (entries, more) = index.get_syndicate_chests_paginated(syndicate_id, 100, page_index)?;
+
+      let height = u64::from(index.block_count()?);
+
+      let chests = entries
+        .into_iter()
+        .take(100)
+        .map(|(inscription_id, chest)| {
+          Self::chest_json(&index, inscription_id, chest, &syndicate, height)
+        })
+        .collect::<ServerResult<Vec<ChestJson>>>()?;
+
+      Ok(Json(SyndicateChestsJson {
+        syndicate: syndicate_id,
+        chests,
+        more,
+        page_index,
+      }))
     })
   }
 
-  async fn syndicate(
-    Extension(server_config): Extension<Arc<PageConfig>>,
+  async fn syndicate_chest_unlocks(
     Extension(index): Extension<Arc<Index>>,
     Path(DeserializeFromStr(syndicate_query)): Path<DeserializeFromStr<query::Syndicate>>,
-    Query(query): Query<JsonQuery>,
-  ) -> ServerResult<Response> {
+    Query(query): Query<ChestUnlocksQuery>,
+  ) -> ServerResult<Json<ChestUnlocksJson>> {
     task::block_in_place(|| {
       if !index.has_relic_index() {
         return Err(ServerError::NotFound(
@@ -1951,42 +5210,71 @@ impl Server {
         ));
       }
 
-      let syndicate_id = match syndicate_query {
-        query::Syndicate::Id(id) => id,
-        query::Syndicate::Inscription(id) => {
-          let (info, _txout, _inscription, _) = index
-            .inscription_info(query::Inscription::Id(id), true)?
-            .ok_or_not_found(|| format!("inscription {id}"))?;
-          info
-            .syndicate
-            .ok_or_not_found(|| format!("syndicate on inscription {id}"))?
-        }
-      };
+      let syndicate_id = Self::syndicate_id_by_query(&index, syndicate_query)?;
 
-      let (id, entry, owner) = index
+      index
         .syndicate(syndicate_id)?
         .ok_or_not_found(|| format!("syndicate {syndicate_id}"))?;
 
-      let relic = index.get_relic_by_id(entry.treasure)?.unwrap();
+      Ok(Json(Self::chest_unlocks_json(
+        &index,
+        Some(syndicate_id),
+        query,
+      )?))
+    })
+  }
 
-      let (_, treasure, _) = index
-        .relic(relic)?
-        .ok_or_not_found(|| format!("relic {relic}"))?;
+  async fn chest_unlocks(
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<ChestUnlocksQuery>,
+  ) -> ServerResult<Json<ChestUnlocksJson>> {
+    task::block_in_place(|| {
+      if !index.has_relic_index() {
+        return Err(ServerError::NotFound(
+          "this server has no relic index".to_string(),
+        ));
+      }
 
-      let chestable = entry.chestable(index.block_count()?.into()).is_ok();
-      let response = SyndicateHtml {
-        entry: entry.into(),
-        id,
-        chestable,
-        owner,
-        treasure: treasure.into(),
-      };
+      Ok(Json(Self::chest_unlocks_json(&index, None, query)?))
+    })
+  }
 
-      Ok(if query.json.unwrap_or(false) {
-        Json(response).into_response()
-      } else {
-        response.page(server_config).into_response()
+  fn chest_unlocks_json(
+    index: &Index,
+    syndicate: Option<SyndicateId>,
+    query: ChestUnlocksQuery,
+  ) -> ServerResult<ChestUnlocksJson> {
+    let height = u64::from(index.block_count()?);
+
+    let from = query.from.unwrap_or(height);
+    let to = query.to.unwrap_or(u64::MAX);
+    let page_index = query.page.unwrap_or_default();
+
+    if from > to {
+      return Err(ServerError::BadRequest(
+        "`from` must not be greater than `to`".into(),
+      ));
+    }
+
+    let (entries, relics, more) =
+      index.get_chest_unlocks_paginated(syndicate, from, to, 100, page_index)?;
+
+    let chests = entries
+      .into_iter()
+      .take(100)
+      .map(|(inscription_id, chest, syndicate)| {
+        Self::chest_json(index, inscription_id, chest, &syndicate, height)
       })
+      .collect::<ServerResult<Vec<ChestJson>>>()?;
+
+    Ok(ChestUnlocksJson {
+      syndicate,
+      from,
+      to,
+      chests,
+      relics,
+      more,
+      page_index,
     })
   }
 
@@ -2010,7 +5298,7 @@ impl Server {
     Path(page_index): Path<usize>,
     Query(query): Query<JsonQuery>,
   ) -> ServerResult<Response> {
-    task::block_in_place(|| {
+    let syndicates = task::block_in_place(|| {
       let (entries, more) = index.syndicates_paginated(50, page_index)?;
 
       let prev = page_index.checked_sub(1);
@@ -2022,25 +5310,19 @@ impl Server {
         .map(|(id, entry)| (id, entry.into()))
         .collect();
 
-      Ok(if query.json.unwrap_or(false) {
-        Json(SyndicatesHtml {
-          entries,
-          more,
-          prev,
-          next,
-        })
-        .into_response()
-      } else {
-        SyndicatesHtml {
-          entries,
-          more,
-          prev,
-          next,
-        }
-        .page(server_config)
-        .into_response()
+      ServerResult::Ok(SyndicatesHtml {
+        entries,
+        more,
+        prev,
+        next,
       })
-    })
+    })?;
+
+    if query.json.unwrap_or(false) {
+      streaming::json(syndicates).await
+    } else {
+      streaming::html(syndicates.page(server_config)).await
+    }
   }
 
   async fn bonestones(
@@ -2058,9 +5340,114 @@ impl Server {
     Ok(Json(json!({"bonestones": result})).into_response())
   }
 
-  async fn bonestones_length(Extension(index): Extension<Arc<Index>>) -> ServerResult<Json<usize>> {
-    let bonestones = index.get_all_bonestones_inscription_ids()?;
-    Ok(Json(bonestones.len()))
+  async fn bonestones_length(Extension(index): Extension<Arc<Index>>) -> ServerResult<Json<usize>> {
+    let bonestones = index.get_all_bonestones_inscription_ids()?;
+    Ok(Json(bonestones.len()))
+  }
+
+  async fn r_blockheight(Extension(index): Extension<Arc<Index>>) -> ServerResult<Json<u32>> {
+    task::block_in_place(|| {
+      Ok(Json(
+        index.height()?.ok_or_not_found(|| "genesis block")?.n(),
+      ))
+    })
+  }
+
+  async fn r_blockhash(
+    Extension(index): Extension<Arc<Index>>,
+    Path(height): Path<u32>,
+  ) -> ServerResult<Json<String>> {
+    task::block_in_place(|| {
+      Ok(Json(
+        index
+          .block_hash(Some(height))?
+          .ok_or_not_found(|| format!("block {height}"))?
+          .to_string(),
+      ))
+    })
+  }
+
+  async fn r_blocktime(Extension(index): Extension<Arc<Index>>) -> ServerResult<Json<i64>> {
+    task::block_in_place(|| {
+      let height = index.height()?.ok_or_not_found(|| "genesis block")?;
+      Ok(Json(index.blocktime(height)?.unix_timestamp()))
+    })
+  }
+
+  async fn r_children_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Path((inscription_id, page)): Path<(InscriptionId, usize)>,
+  ) -> ServerResult<Json<api::ChildInscriptions>> {
+    task::block_in_place(|| {
+      let (ids, more) = index
+        .get_children_by_inscription_id_paginated(inscription_id, 100, page)?
+        .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+      Ok(Json(api::ChildInscriptions { ids, more, page }))
+    })
+  }
+
+  async fn inscription_children(
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Json<api::ChildInscriptions>> {
+    Self::r_children_paginated(Extension(index), Path((inscription_id, 0))).await
+  }
+
+  async fn inscription_parents(
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Json<api::ParentInscriptions>> {
+    task::block_in_place(|| {
+      let ids = index
+        .get_parents_by_inscription_id(inscription_id)?
+        .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+      Ok(Json(api::ParentInscriptions { ids }))
+    })
+  }
+
+  async fn r_metadata(
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Json<String>> {
+    task::block_in_place(|| {
+      let metadata = index
+        .get_inscription_by_id(inscription_id)?
+        .ok_or_not_found(|| format!("inscription {inscription_id}"))?
+        .metadata
+        .ok_or_not_found(|| format!("inscription {inscription_id} metadata"))?;
+
+      Ok(Json(hex::encode(metadata)))
+    })
+  }
+
+  async fn r_sat(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(sat)): Path<DeserializeFromStr<Sat>>,
+  ) -> ServerResult<Json<api::SatInscriptions>> {
+    Self::r_sat_page(&index, sat, 0)
+  }
+
+  async fn r_sat_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(sat), page)): Path<(DeserializeFromStr<Sat>, usize)>,
+  ) -> ServerResult<Json<api::SatInscriptions>> {
+    Self::r_sat_page(&index, sat, page)
+  }
+
+  fn r_sat_page(index: &Index, sat: Sat, page: usize) -> ServerResult<Json<api::SatInscriptions>> {
+    if !index.has_sat_index() {
+      return Err(ServerError::NotFound(
+        "this server has no sat index".to_string(),
+      ));
+    }
+
+    task::block_in_place(|| {
+      let (ids, more) = index.get_inscription_ids_by_sat_paginated(sat, 100, page)?;
+
+      Ok(Json(api::SatInscriptions { ids, more, page }))
+    })
   }
 
   async fn status(Extension(index): Extension<Arc<Index>>) -> (StatusCode, &'static str) {
@@ -2078,24 +5465,28 @@ impl Server {
   }
 
   async fn search_by_query(
+    Extension(page_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Query(search): Query<Search>,
   ) -> ServerResult<Redirect> {
-    Self::search(&index, &search.query).await
+    Self::search(&page_config, &index, &search.query).await
   }
 
   async fn search_by_path(
+    Extension(page_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(search): Path<Search>,
   ) -> ServerResult<Redirect> {
-    Self::search(&index, &search.query).await
+    Self::search(&page_config, &index, &search.query).await
   }
 
-  async fn search(index: &Index, query: &str) -> ServerResult<Redirect> {
-    Self::search_inner(index, query)
+  async fn search(page_config: &PageConfig, index: &Index, query: &str) -> ServerResult<Redirect> {
+    Ok(Redirect::to(
+      &page_config.link(&Self::search_inner(index, query)?),
+    ))
   }
 
-  fn search_inner(index: &Index, query: &str) -> ServerResult<Redirect> {
+  fn search_inner(index: &Index, query: &str) -> ServerResult<String> {
     lazy_static! {
       static ref HASH: Regex = Regex::new(r"^[[:xdigit:]]{64}$").unwrap();
       static ref OUTPOINT: Regex = Regex::new(r"^[[:xdigit:]]{64}:\d+$").unwrap();
@@ -2108,16 +5499,16 @@ impl Server {
 
     if HASH.is_match(query) {
       if index.block_header(query.parse().unwrap())?.is_some() {
-        Ok(Redirect::to(&format!("/block/{query}")))
+        Ok(format!("/block/{query}"))
       } else {
-        Ok(Redirect::to(&format!("/tx/{query}")))
+        Ok(format!("/tx/{query}"))
       }
     } else if OUTPOINT.is_match(query) {
-      Ok(Redirect::to(&format!("/output/{query}")))
+      Ok(format!("/output/{query}"))
     } else if INSCRIPTION_ID.is_match(query) {
-      Ok(Redirect::to(&format!("/shibescription/{query}")))
+      Ok(format!("/shibescription/{query}"))
     } else if RELIC.is_match(query) {
-      Ok(Redirect::to(&format!("/relic/{query}")))
+      Ok(format!("/relic/{query}"))
     } else if RELIC_ID.is_match(query) {
       let id = query
         .parse::<RelicId>()
@@ -2125,12 +5516,86 @@ impl Server {
 
       let relic = index.get_relic_by_id(id)?.ok_or_not_found(|| "relic ID")?;
 
-      Ok(Redirect::to(&format!("/relic/{relic}")))
+      Ok(format!("/relic/{relic}"))
     } else {
-      Ok(Redirect::to(&format!("/sat/{query}")))
+      Ok(format!("/sat/{query}"))
     }
   }
 
+  async fn search_suggest(
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<SuggestQuery>,
+  ) -> ServerResult<Json<SearchSuggestionsJson>> {
+    const LIMIT: usize = 10;
+
+    task::block_in_place(|| {
+      let q = query.q.trim();
+
+      let mut suggestions = Vec::new();
+
+      let relic_suggestion = |id: RelicId, entry: RelicEntry| SearchSuggestionJson {
+        kind: "bone",
+        label: entry.spaced_relic.to_string(),
+        path: format!("/bone/{id}"),
+      };
+
+      if let Ok(number) = q.parse::<u64>() {
+        if let Some(id) = index.get_inscription_id_by_inscription_number(number)? {
+          suggestions.push(SearchSuggestionJson {
+            kind: "inscription",
+            label: format!("Shibescription {number}"),
+            path: format!("/shibescription/{id}"),
+          });
+        }
+
+        if index.has_relic_index() {
+          if let Some(id) = index.get_relic_id_by_number(number)? {
+            if let Some(entry) = index.get_relic_entry_by_id(id)? {
+              suggestions.push(relic_suggestion(id, entry));
+            }
+          }
+        }
+
+        if index
+          .height()?
+          .map_or(false, |height| u64::from(height.n()) >= number)
+        {
+          suggestions.push(SearchSuggestionJson {
+            kind: "block",
+            label: format!("Block {number}"),
+            path: format!("/block/{number}"),
+          });
+        }
+      } else if index.has_relic_index() {
+        if let Ok(id) = q.parse::<RelicId>() {
+          if let Some(entry) = index.get_relic_entry_by_id(id)? {
+            suggestions.push(relic_suggestion(id, entry));
+          }
+        }
+
+        // tickers are matched regardless of case and spacers
+        let prefix = q
+          .chars()
+          .filter(|c| !matches!(c, '•' | '.'))
+          .collect::<String>()
+          .to_uppercase();
+
+        if !prefix.is_empty() && prefix.chars().all(|c| c.is_ascii_uppercase()) {
+          for (id, entry) in index.search_relics(&prefix, LIMIT)? {
+            suggestions.push(relic_suggestion(id, entry));
+          }
+        }
+      }
+
+      suggestions.truncate(LIMIT);
+
+      Ok(Json(SearchSuggestionsJson {
+        query: query.q,
+        suggestions,
+      }))
+    })
+  }
+
   async fn favicon(user_agent: Option<TypedHeader<UserAgent>>) -> ServerResult<Response> {
     if user_agent
       .map(|user_agent| {
@@ -2261,11 +5726,16 @@ impl Server {
     Extension(config): Extension<Arc<Config>>,
     Path(inscription_id): Path<InscriptionId>,
     Extension(page_config): Extension<Arc<PageConfig>>,
+    request_headers: HeaderMap,
   ) -> ServerResult<Response> {
     if config.is_hidden(inscription_id) {
       return Ok(PreviewUnknownHtml.into_response());
     }
 
+    if !index.has_content() {
+      return Err(ServerError::NotFound("content pruned".into()));
+    }
+
     let mut inscription = index
       .get_inscription_by_id(inscription_id)?
       .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
@@ -2276,11 +5746,68 @@ impl Server {
         .ok_or_not_found(|| format!("delegate {inscription_id}"))?
     }
 
-    Ok(
-      Self::content_response(inscription, &page_config)
-        .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
-        .into_response(),
-    )
+    let (headers, body) = Self::content_response(inscription, &page_config)
+      .ok_or_not_found(|| format!("inscription {inscription_id} content"))?;
+
+    Self::deliver_content(&request_headers, headers, body)
+  }
+
+  /// Serves the byte range of `body` requested in `request_headers`, or all
+  /// of it, compressed with the encoding preferred by the client if its
+  /// content type is compressible.
+  fn deliver_content(
+    request_headers: &HeaderMap,
+    mut headers: HeaderMap,
+    body: Vec<u8>,
+  ) -> ServerResult<Response> {
+    headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    headers.insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+
+    let len = body.len();
+
+    // ranges refer to the uncompressed content
+    if let Some(range) = request_headers
+      .get(header::RANGE)
+      .and_then(|range| range.to_str().ok())
+      .and_then(ByteRange::parse)
+    {
+      let Some(range) = range.resolve(len) else {
+        headers.insert(
+          header::CONTENT_RANGE,
+          HeaderValue::from_str(&format!("bytes */{len}"))
+            .map_err(|err| ServerError::Internal(err.into()))?,
+        );
+        return Ok((StatusCode::RANGE_NOT_SATISFIABLE, headers).into_response());
+      };
+
+      headers.insert(
+        header::CONTENT_RANGE,
+        HeaderValue::from_str(&format!("bytes {}-{}/{len}", range.start, range.end - 1))
+          .map_err(|err| ServerError::Internal(err.into()))?,
+      );
+
+      return Ok((StatusCode::PARTIAL_CONTENT, headers, body[range].to_vec()).into_response());
+    }
+
+    let encoding = ContentEncoding::negotiate(request_headers);
+
+    let compressible = ContentEncoding::compressible(
+      headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok()),
+    );
+
+    if encoding == ContentEncoding::Identity || !compressible {
+      return Ok((headers, body).into_response());
+    }
+
+    let body = encoding
+      .compress(&body)
+      .map_err(|err| ServerError::Internal(err.into()))?;
+
+    headers.insert(header::CONTENT_ENCODING, encoding.header_value());
+
+    Ok((headers, body).into_response())
   }
 
   fn content_response(
@@ -2326,26 +5853,43 @@ impl Server {
 
   pub(super) fn preview_content_security_policy(
     media: Media,
+    content_type: Option<&str>,
+    config: &Config,
     csp: &Option<String>,
   ) -> ServerResult<[(HeaderName, HeaderValue); 1]> {
-    let default = match media {
-      Media::Audio => "default-src 'self'",
-      Media::Image => "default-src 'self' 'unsafe-inline'",
-      Media::Model => "script-src-elem 'self' https://ajax.googleapis.com",
-      Media::Pdf => "script-src-elem 'self' https://cdn.jsdelivr.net",
-      Media::Text => "default-src 'self'",
-      Media::Unknown => "default-src 'self'",
-      Media::Video => "default-src 'self'",
-      _ => "",
+    let policy = match config.preview_csp(content_type) {
+      Some(policy) => policy.to_string(),
+      None => {
+        let default = match media {
+          Media::Audio => "default-src 'self'",
+          Media::Image => "default-src 'self' 'unsafe-inline'",
+          Media::Model => "script-src-elem 'self' https://ajax.googleapis.com",
+          Media::Pdf => "script-src-elem 'self' https://cdn.jsdelivr.net",
+          Media::Text => "default-src 'self'",
+          Media::Unknown => "default-src 'self'",
+          Media::Video => "default-src 'self'",
+          _ => "",
+        };
+
+        match media {
+          Media::Model | Media::Pdf => iter::once(default)
+            .chain(config.preview_origins.iter().map(String::as_str))
+            .collect::<Vec<&str>>()
+            .join(" "),
+          _ => default.to_string(),
+        }
+      }
     };
 
     let value = if let Some(csp_origin) = &csp {
-      default
+      policy
         .replace("'self'", csp_origin)
         .parse()
         .map_err(|err| anyhow!("invalid content-security-policy origin `{csp_origin}`: {err}"))?
     } else {
-      HeaderValue::from_static(default)
+      policy
+        .parse()
+        .map_err(|err| anyhow!("invalid content-security-policy `{policy}`: {err}"))?
     };
 
     Ok([(header::CONTENT_SECURITY_POLICY, value)])
@@ -2361,6 +5905,10 @@ impl Server {
       return Ok(PreviewUnknownHtml.into_response());
     }
 
+    if !index.has_content() {
+      return Err(ServerError::NotFound("content pruned".into()));
+    }
+
     let mut inscription = index
       .get_inscription_by_id(inscription_id)?
       .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
@@ -2371,17 +5919,44 @@ impl Server {
         .ok_or_not_found(|| format!("delegate {inscription_id}"))?
     }
 
+    if config.is_preview_disabled(inscription.content_type()) {
+      return Ok(
+        (
+          Self::preview_content_security_policy(
+            Media::Unknown,
+            None,
+            &config,
+            &page_config.csp_origin,
+          )?,
+          PreviewUnknownHtml,
+        )
+          .into_response(),
+      );
+    }
+
     let media = inscription.media();
-    let content_security_policy =
-      Self::preview_content_security_policy(media, &page_config.csp_origin)?;
+    let content_security_policy = Self::preview_content_security_policy(
+      media,
+      inscription.content_type(),
+      &config,
+      &page_config.csp_origin,
+    )?;
 
     match media {
       Media::Audio => Ok(PreviewAudioHtml { inscription_id }.into_response()),
-      Media::Iframe => Ok(
-        Self::content_response(inscription, &page_config)
-          .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
-          .into_response(),
-      ),
+      Media::Iframe => {
+        let configured = config.preview_csp(inscription.content_type()).is_some();
+
+        let (mut headers, body) = Self::content_response(inscription, &page_config)
+          .ok_or_not_found(|| format!("inscription {inscription_id} content"))?;
+
+        if configured {
+          headers.remove(header::CONTENT_SECURITY_POLICY);
+          headers.extend(content_security_policy);
+        }
+
+        Ok((headers, body).into_response())
+      }
       Media::Model => {
         Ok((content_security_policy, PreviewModelHtml { inscription_id }).into_response())
       }
@@ -2404,6 +5979,7 @@ impl Server {
   async fn inscription(
     Extension(page_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(delegates): Extension<Arc<Delegates>>,
     Path(inscription_id): Path<InscriptionId>,
     Query(query): Query<JsonQuery>,
   ) -> ServerResult<Response> {
@@ -2419,11 +5995,11 @@ impl Server {
     let entry = inscription_info.3;
 
     if let Some(delegate) = inscription.delegate() {
-      let delegate_inscription = index
-        .get_inscription_by_id(delegate)?
+      let delegate_content = delegates
+        .content(&index, delegate)?
         .ok_or_not_found(|| format!("delegate {inscription_id}"))?;
       inscription.body = Some(Vec::new());
-      inscription.content_type = delegate_inscription.content_type;
+      inscription.content_type = delegate_content.content_type;
     }
 
     let satpoint = index
@@ -2431,11 +6007,7 @@ impl Server {
       .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
     let output = index
-      .get_transaction(satpoint.outpoint.txid)?
-      .ok_or_not_found(|| format!("inscription {inscription_id} current transaction"))?
-      .output
-      .into_iter()
-      .nth(satpoint.outpoint.vout.try_into().unwrap())
+      .get_txout(satpoint.outpoint)?
       .ok_or_not_found(|| format!("inscription {inscription_id} current transaction output"))?;
 
     if !query.json.unwrap_or_default() {
@@ -2522,6 +6094,51 @@ impl Server {
     Self::inscriptions_inner(page_config, index, None).await
   }
 
+  async fn inscriptions_resolve(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(config): Extension<Arc<Config>>,
+    Extension(delegates): Extension<Arc<Delegates>>,
+    Json(ids): Json<Vec<InscriptionId>>,
+  ) -> ServerResult<Json<Vec<Option<ResolvedInscriptionJson>>>> {
+    if ids.len() > MAX_RESOLVED_INSCRIPTIONS {
+      return Err(ServerError::BadRequest(format!(
+        "at most {MAX_RESOLVED_INSCRIPTIONS} inscriptions can be resolved at once"
+      )));
+    }
+
+    task::block_in_place(|| {
+      let has_content = index.has_content();
+
+      ids
+        .into_iter()
+        .map(|id| {
+          let Some((content, delegate)) = delegates.resolve(&index, id)? else {
+            return Ok(None);
+          };
+
+          let delegate_id = content.delegate;
+
+          // inscriptions whose delegate doesn't exist have no content
+          let effective = match delegate_id {
+            Some(_) => delegate.unwrap_or_default(),
+            None => content,
+          };
+
+          Ok(Some(ResolvedInscriptionJson {
+            id,
+            delegate: delegate_id,
+            body: has_content && !config.is_hidden(id) && effective.content_length.is_some(),
+            content_type: effective
+              .content_type
+              .map(|content_type| String::from_utf8_lossy(&content_type).into_owned()),
+            content_length: effective.content_length,
+          }))
+        })
+        .collect::<ServerResult<Vec<Option<ResolvedInscriptionJson>>>>()
+        .map(Json)
+    })
+  }
+
   async fn inscriptions_validate(
     Extension(index): Extension<Arc<Index>>,
     Extension(server_config): Extension<Arc<PageConfig>>,
@@ -2543,11 +6160,7 @@ impl Server {
           .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
         let output = index
-          .get_transaction(satpoint.outpoint.txid)?
-          .ok_or_not_found(|| format!("inscription {inscription_id} current transaction"))?
-          .output
-          .into_iter()
-          .nth(satpoint.outpoint.vout.try_into().unwrap())
+          .get_txout(satpoint.outpoint)?
           .ok_or_not_found(|| format!("inscription {inscription_id} current transaction output"))?;
 
         let address = Address::from_str(address_str);
@@ -2598,11 +6211,7 @@ impl Server {
       let inscriptions = index.get_inscriptions_on_output(outpoint)?;
 
       let output = index
-        .get_transaction(outpoint.txid)?
-        .ok_or_not_found(|| format!("inscription {tx_id} current transaction"))?
-        .output
-        .into_iter()
-        .nth(outpoint.vout.try_into().unwrap())
+        .get_txout(outpoint)?
         .ok_or_not_found(|| format!("inscription {vout} current transaction output"))?;
 
       for inscription_id in inscriptions {
@@ -2632,21 +6241,8 @@ impl Server {
           _ => None,
         };
 
-        let confirmations =
-          if let Some(block_hash_info) = index.get_transaction_blockhash(outpoint.txid)? {
-            block_hash_info.confirmations
-          } else {
-            None
-          };
-
         let inscription_json = InscriptionByAddressJson {
-          utxo: Utxo {
-            txid: tx_id,
-            vout,
-            script: output.script_pubkey.clone(),
-            shibes: output.value,
-            confirmations,
-          },
+          utxo: Self::utxo(&index, outpoint, output.clone())?,
           content: str_content,
           content_length,
           content_type,
@@ -2780,6 +6376,404 @@ impl Server {
     Redirect::to(&destination)
   }
 
+  async fn inscriptions_sse(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(event_broadcast): Extension<broadcast::Sender<Event>>,
+  ) -> Sse<impl Stream<Item = Result<sse::Event, Infallible>>> {
+    let events = futures::stream::unfold(event_broadcast.subscribe(), |mut receiver| async move {
+      loop {
+        match receiver.recv().await {
+          Ok(event) => return Some((event, receiver)),
+          Err(broadcast::error::RecvError::Lagged(skipped)) => {
+            log::warn!("inscription event stream lagged behind by {skipped} events");
+          }
+          Err(broadcast::error::RecvError::Closed) => return None,
+        }
+      }
+    });
+
+    let stream = events.filter_map(move |event| {
+      let EventInfo::InscriptionCreated {
+        charms,
+        inscription_id,
+        location,
+        ..
+      } = event.info
+      else {
+        return None;
+      };
+
+      let content_type = task::block_in_place(|| index.get_inscription_by_id(inscription_id))
+        .ok()
+        .flatten()
+        .and_then(|inscription| inscription.content_type().map(str::to_string));
+
+      sse::Event::default()
+        .event("inscription")
+        .id(inscription_id.to_string())
+        .json_data(InscriptionCreatedJson {
+          inscription_id,
+          content_type,
+          charms: Charm::charms(charms),
+          location,
+          block_height: event.block_height,
+        })
+        .ok()
+        .map(Ok)
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+  }
+
+  async fn admin_cache_purge(
+    Extension(admin): Extension<Arc<Admin>>,
+    Extension(cache): Extension<Arc<ResponseCache>>,
+    headers: HeaderMap,
+    Query(query): Query<CachePurgeQuery>,
+  ) -> ServerResult<Response> {
+    admin.authorize(&headers)?;
+
+    Ok(
+      Json(CachePurgeJson {
+        scope: query.scope,
+        evicted: cache.purge(query.scope),
+      })
+      .into_response(),
+    )
+  }
+
+  async fn admin_promote(
+    Extension(admin): Extension<Arc<Admin>>,
+    Extension(standby): Extension<Arc<Standby>>,
+    headers: HeaderMap,
+  ) -> ServerResult<Json<StandbyJson>> {
+    admin.authorize(&headers)?;
+
+    if !standby.is_writer() {
+      if let Err(lease) = standby.promote(Self::unix_seconds())? {
+        return Err(ServerError::BadRequest(format!(
+          "index lease held by server {} in epoch {}, last renewed at {}",
+          lease.holder, lease.epoch, lease.renewed
+        )));
+      }
+    }
+
+    Ok(Json(StandbyJson {
+      server: standby.holder().into(),
+      writer: standby.is_writer(),
+    }))
+  }
+
+  async fn admin_audit_log(
+    Extension(admin): Extension<Arc<Admin>>,
+    Extension(index): Extension<Arc<Index>>,
+    headers: HeaderMap,
+  ) -> ServerResult<Json<AuditLogJson>> {
+    Self::audit_log_page(&admin, &index, &headers, 0)
+  }
+
+  async fn admin_audit_log_paginated(
+    Extension(admin): Extension<Arc<Admin>>,
+    Extension(index): Extension<Arc<Index>>,
+    headers: HeaderMap,
+    Path(page): Path<usize>,
+  ) -> ServerResult<Json<AuditLogJson>> {
+    Self::audit_log_page(&admin, &index, &headers, page)
+  }
+
+  fn audit_log_page(
+    admin: &Admin,
+    index: &Index,
+    headers: &HeaderMap,
+    page: usize,
+  ) -> ServerResult<Json<AuditLogJson>> {
+    admin.authorize(headers)?;
+
+    task::block_in_place(|| {
+      let (entries, more) = index.get_audit_log(100, page)?;
+
+      Ok(Json(AuditLogJson {
+        page,
+        entries: entries
+          .into_iter()
+          .map(|(id, entry)| AuditLogEntryJson { id, entry })
+          .collect(),
+        more,
+      }))
+    })
+  }
+
+  async fn admin_curation_insert(
+    Extension(admin): Extension<Arc<Admin>>,
+    Extension(curation): Extension<Arc<Curation>>,
+    Extension(cache): Extension<Arc<ResponseCache>>,
+    headers: HeaderMap,
+    Path((list, DeserializeFromStr(spaced_relic))): Path<(
+      CurationList,
+      DeserializeFromStr<SpacedRelic>,
+    )>,
+  ) -> ServerResult<Response> {
+    admin.authorize(&headers)?;
+
+    if curation.insert(list, spaced_relic) {
+      cache.purge(CacheScope::Relic);
+    }
+
+    Ok(
+      Json(CurationJson {
+        list,
+        relics: curation.relics(list),
+      })
+      .into_response(),
+    )
+  }
+
+  async fn admin_curation_remove(
+    Extension(admin): Extension<Arc<Admin>>,
+    Extension(curation): Extension<Arc<Curation>>,
+    Extension(cache): Extension<Arc<ResponseCache>>,
+    headers: HeaderMap,
+    Path((list, DeserializeFromStr(spaced_relic))): Path<(
+      CurationList,
+      DeserializeFromStr<SpacedRelic>,
+    )>,
+  ) -> ServerResult<Response> {
+    admin.authorize(&headers)?;
+
+    if !curation.remove(list, spaced_relic.relic) {
+      return Err(ServerError::NotFound(format!(
+        "bone {spaced_relic} is not {list}"
+      )));
+    }
+
+    cache.purge(CacheScope::Relic);
+
+    Ok(
+      Json(CurationJson {
+        list,
+        relics: curation.relics(list),
+      })
+      .into_response(),
+    )
+  }
+
+  async fn webhooks(
+    Extension(admin): Extension<Arc<Admin>>,
+    Extension(webhooks): Extension<Arc<Webhooks>>,
+    headers: HeaderMap,
+  ) -> ServerResult<Json<Vec<WebhookJson>>> {
+    admin.authorize(&headers)?;
+
+    Ok(Json(
+      webhooks
+        .list()
+        .into_iter()
+        .map(|(id, webhook)| WebhookJson { id, webhook })
+        .collect(),
+    ))
+  }
+
+  async fn add_webhook(
+    Extension(admin): Extension<Arc<Admin>>,
+    Extension(index): Extension<Arc<Index>>,
+    Extension(webhooks): Extension<Arc<Webhooks>>,
+    headers: HeaderMap,
+    Json(mut webhook): Json<Webhook>,
+  ) -> ServerResult<Json<WebhookJson>> {
+    admin.authorize(&headers)?;
+
+    // events carry addresses of this chain, which only compare equal to
+    // addresses in canonical encoding
+    if let Watch::Address(address) = &mut webhook.watch {
+      *address = Self::parse_address(&index, &address.to_string())?;
+    }
+
+    if let Watch::Syndicate(id) = webhook.watch {
+      task::block_in_place(|| index.syndicate(id))?
+        .ok_or_else(|| ServerError::NotFound(format!("syndicate {id} not found")))?;
+    }
+
+    let url = Url::parse(&webhook.url)
+      .map_err(|err| ServerError::BadRequest(format!("invalid webhook url: {err}")))?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+      return Err(ServerError::BadRequest(
+        "webhook url must use http or https".into(),
+      ));
+    }
+
+    task::block_in_place(|| {
+      Ok(Json(WebhookJson {
+        id: webhooks.insert(webhook.clone())?,
+        webhook,
+      }))
+    })
+  }
+
+  async fn remove_webhook(
+    Extension(admin): Extension<Arc<Admin>>,
+    Extension(webhooks): Extension<Arc<Webhooks>>,
+    headers: HeaderMap,
+    Path(id): Path<u64>,
+  ) -> ServerResult<StatusCode> {
+    admin.authorize(&headers)?;
+
+    task::block_in_place(|| {
+      if !webhooks.remove(id)? {
+        return Err(ServerError::NotFound(format!("webhook {id} not found")));
+      }
+
+      Ok(StatusCode::NO_CONTENT)
+    })
+  }
+
+  /// Records state-changing admin requests that passed authorization in the
+  /// audit log, once they were handled.
+  async fn audit_log(
+    Extension(admin): Extension<Arc<Admin>>,
+    Extension(index): Extension<Arc<Index>>,
+    request: http::Request<body::Body>,
+    next: Next<body::Body>,
+  ) -> Response {
+    let path = request.uri().path();
+    let path = path
+      .strip_prefix(&format!("/{API_VERSION}"))
+      .filter(|rest| rest.starts_with('/'))
+      .unwrap_or(path)
+      .to_string();
+
+    if !Admin::audited(request.method(), &path) || admin.authorize(request.headers()).is_err() {
+      return next.run(request).await;
+    }
+
+    let action = format!("{} {path}", request.method());
+    let query = request.uri().query().map(str::to_string);
+    let actor = request
+      .headers()
+      .get(ACTOR_HEADER)
+      .and_then(|value| value.to_str().ok())
+      .map(str::to_string);
+
+    let (parts, request_body) = request.into_parts();
+
+    let bytes = match hyper::body::to_bytes(request_body).await {
+      Ok(bytes) => bytes,
+      Err(error) => {
+        return ServerError::BadRequest(format!("failed to read request body: {error}"))
+          .into_response()
+      }
+    };
+
+    let body = (!bytes.is_empty()).then(|| String::from_utf8_lossy(&bytes).into_owned());
+
+    let response = next
+      .run(http::Request::from_parts(parts, body::Body::from(bytes)))
+      .await;
+
+    let entry = AuditEntry {
+      timestamp: Self::unix_seconds(),
+      actor,
+      action,
+      query,
+      body,
+      status: response.status().as_u16(),
+    };
+
+    if let Err(error) = task::block_in_place(|| index.insert_audit_entry(&entry)) {
+      log::error!(
+        "failed to record `{}` in the audit log: {error}",
+        entry.action
+      );
+    }
+
+    response
+  }
+
+  /// Marks JSON responses served from unversioned paths as deprecated in
+  /// favor of their versioned aliases.
+  async fn api_version<B>(request: http::Request<B>, next: Next<B>) -> Response {
+    let path = request.uri().path().to_string();
+
+    let mut response = next.run(request).await;
+
+    let versioned = path
+      .strip_prefix('/')
+      .and_then(|path| path.strip_prefix(API_VERSION))
+      .map(|rest| rest.is_empty() || rest.starts_with('/'))
+      .unwrap_or_default();
+
+    let json = response
+      .headers()
+      .get(header::CONTENT_TYPE)
+      .and_then(|content_type| content_type.to_str().ok())
+      .map(|content_type| content_type.starts_with("application/json"))
+      .unwrap_or_default();
+
+    if !versioned && json {
+      let headers = response.headers_mut();
+      headers.insert("deprecation", HeaderValue::from_static("true"));
+      headers.insert("sunset", HeaderValue::from_static(LEGACY_API_SUNSET));
+      if let Ok(link) = HeaderValue::from_str(&format!(
+        "</{API_VERSION}{path}>; rel=\"successor-version\""
+      )) {
+        headers.insert(header::LINK, link);
+      }
+    }
+
+    response
+  }
+
+  /// Adds the latest indexed block to every response, so clients of load
+  /// balanced servers can tell when a replica is behind. The headers are read
+  /// after the handler, so they are at least as new as the response.
+  async fn index_tip<B>(
+    Extension(index): Extension<Arc<Index>>,
+    request: http::Request<B>,
+    next: Next<B>,
+  ) -> Response {
+    let mut response = next.run(request).await;
+
+    if let Ok(Some((height, hash))) = task::block_in_place(|| index.tip()) {
+      let headers = response.headers_mut();
+      headers.insert(INDEX_HEIGHT_HEADER, HeaderValue::from(height));
+      if let Ok(hash) = HeaderValue::from_str(&hash.to_string()) {
+        headers.insert(INDEX_BLOCK_HASH_HEADER, hash);
+      }
+    }
+
+    response
+  }
+
+  async fn api_versions() -> Json<ApiVersionsJson> {
+    Json(ApiVersionsJson {
+      current: API_VERSION,
+      versions: vec![API_VERSION],
+      unversioned: UnversionedApiJson {
+        deprecated: true,
+        sunset: LEGACY_API_SUNSET,
+      },
+    })
+  }
+
+  async fn plain_mode<B>(mut request: http::Request<B>, next: Next<B>) -> Response {
+    let plain = request
+      .uri()
+      .query()
+      .map(|query| query.split('&').any(|pair| pair == "plain=true"))
+      .unwrap_or_default();
+
+    if plain {
+      if let Some(page_config) = request.extensions().get::<Arc<PageConfig>>().cloned() {
+        request.extensions_mut().insert(Arc::new(PageConfig {
+          plain: true,
+          ..(*page_config).clone()
+        }));
+      }
+    }
+
+    next.run(request).await
+  }
+
   async fn outputs_post(
     Extension(index): Extension<Arc<Index>>,
     Json(body): Json<OutputsPostQuery>,
@@ -2909,3 +6903,38 @@ fn format_balance(balance: u128, decimal_places: u8) -> String {
   // Combine integer and cleaned-up fractional part
   format!("{}.{}", integer_part, fractional_string)
 }
+
+/// Accepts a single HTTP request on `listener`, answers it with 200 OK and
+/// returns its body.
+#[cfg(test)]
+fn receive_request(listener: &TcpListener) -> Vec<u8> {
+  use std::io::{BufRead, BufReader, Read, Write};
+
+  let (stream, _) = listener.accept().unwrap();
+  let mut reader = BufReader::new(stream);
+
+  let mut content_length = 0;
+  loop {
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    let line = line.trim_end();
+    if line.is_empty() {
+      break;
+    }
+    if let Some((name, value)) = line.split_once(':') {
+      if name.eq_ignore_ascii_case("content-length") {
+        content_length = value.trim().parse().unwrap();
+      }
+    }
+  }
+
+  let mut body = vec![0; content_length];
+  reader.read_exact(&mut body).unwrap();
+
+  reader
+    .get_mut()
+    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+    .unwrap();
+
+  body
+}
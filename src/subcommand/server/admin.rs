@@ -0,0 +1,120 @@
+use super::*;
+
+/// Names the admin making a request in the audit log, since all admins share
+/// the same token.
+pub(super) const ACTOR_HEADER: &str = "x-admin-actor";
+
+/// Guards the `/admin` endpoints, which are disabled unless a token is configured.
+pub(crate) struct Admin {
+  token: Option<String>,
+}
+
+impl Admin {
+  pub(crate) fn new(token: Option<String>) -> Self {
+    Self { token }
+  }
+
+  pub(super) fn authorize(&self, headers: &HeaderMap) -> ServerResult<()> {
+    let Some(token) = &self.token else {
+      return Err(ServerError::NotFound(
+        "this server has no admin API".to_string(),
+      ));
+    };
+
+    let bearer = headers
+      .get(header::AUTHORIZATION)
+      .and_then(|value| value.to_str().ok())
+      .and_then(|value| value.strip_prefix("Bearer "));
+
+    let authorized = bearer.map_or(false, |bearer| {
+      Self::constant_time_eq(bearer.as_bytes(), token.as_bytes())
+    });
+
+    if !authorized {
+      return Err(ServerError::Unauthorized("invalid admin token".to_string()));
+    }
+
+    Ok(())
+  }
+
+  /// Compares `a` and `b` in time independent of the position of the first
+  /// differing byte, so the token can't be recovered from response times.
+  fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+  }
+
+  /// Whether a request to `path`, without the API version prefix, changes
+  /// state through the admin API and is recorded in the audit log.
+  pub(super) fn audited(method: &http::Method, path: &str) -> bool {
+    method != http::Method::GET
+      && (path.starts_with("/admin/")
+        || path == "/webhooks"
+        || path.starts_with("/webhooks/")
+        || path.starts_with("/labels/"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn headers(authorization: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+      header::AUTHORIZATION,
+      HeaderValue::from_str(authorization).unwrap(),
+    );
+    headers
+  }
+
+  #[test]
+  fn audited() {
+    assert!(Admin::audited(&http::Method::POST, "/admin/cache/purge"));
+    assert!(Admin::audited(&http::Method::DELETE, "/webhooks/1"));
+    assert!(Admin::audited(&http::Method::POST, "/webhooks"));
+    assert!(Admin::audited(
+      &http::Method::PUT,
+      "/labels/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+    ));
+    assert!(!Admin::audited(&http::Method::GET, "/labels"));
+    assert!(!Admin::audited(&http::Method::GET, "/webhooks"));
+    assert!(!Admin::audited(&http::Method::GET, "/admin/audit-log"));
+    assert!(!Admin::audited(&http::Method::POST, "/events"));
+  }
+
+  #[test]
+  fn constant_time_eq() {
+    assert!(Admin::constant_time_eq(b"", b""));
+    assert!(Admin::constant_time_eq(b"foo", b"foo"));
+    assert!(!Admin::constant_time_eq(b"foo", b"fob"));
+    assert!(!Admin::constant_time_eq(b"foo", b"fo"));
+    assert!(!Admin::constant_time_eq(b"foo", b"foobar"));
+  }
+
+  #[test]
+  fn authorize() {
+    assert!(matches!(
+      Admin::new(None).authorize(&headers("Bearer foo")),
+      Err(ServerError::NotFound(_))
+    ));
+
+    let admin = Admin::new(Some("foo".into()));
+
+    assert!(admin.authorize(&headers("Bearer foo")).is_ok());
+
+    assert!(matches!(
+      admin.authorize(&headers("Bearer bar")),
+      Err(ServerError::Unauthorized(_))
+    ));
+
+    assert!(matches!(
+      admin.authorize(&headers("Bearer fo")),
+      Err(ServerError::Unauthorized(_))
+    ));
+
+    assert!(matches!(
+      admin.authorize(&HeaderMap::new()),
+      Err(ServerError::Unauthorized(_))
+    ));
+  }
+}
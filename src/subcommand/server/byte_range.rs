@@ -0,0 +1,84 @@
+use super::*;
+
+/// A single range of a `Range: bytes=…` request header. Requests for multiple
+/// ranges are answered with the whole body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ByteRange {
+  /// `bytes=<first>-<last>`, both inclusive
+  Bounded(u64, u64),
+  /// `bytes=<first>-`
+  From(u64),
+  /// `bytes=-<length>`, the last `length` bytes
+  Suffix(u64),
+}
+
+impl ByteRange {
+  pub(crate) fn parse(value: &str) -> Option<Self> {
+    let spec = value.trim().strip_prefix("bytes=")?.trim();
+
+    if spec.contains(',') {
+      return None;
+    }
+
+    let (first, last) = spec.split_once('-')?;
+
+    match (first.trim(), last.trim()) {
+      ("", length) => Some(Self::Suffix(length.parse().ok()?)),
+      (first, "") => Some(Self::From(first.parse().ok()?)),
+      (first, last) => {
+        let first = first.parse().ok()?;
+        let last = last.parse().ok()?;
+        (first <= last).then_some(Self::Bounded(first, last))
+      }
+    }
+  }
+
+  /// The range of bytes of a body of `len` bytes, or `None` if the range is
+  /// not satisfiable.
+  pub(crate) fn resolve(self, len: usize) -> Option<std::ops::Range<usize>> {
+    let len = u64::try_from(len).ok()?;
+
+    let (start, end) = match self {
+      Self::Bounded(first, last) => (first, last.saturating_add(1).min(len)),
+      Self::From(first) => (first, len),
+      Self::Suffix(length) => (len.saturating_sub(length), len),
+    };
+
+    if start >= end {
+      return None;
+    }
+
+    Some(usize::try_from(start).ok()?..usize::try_from(end).ok()?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse() {
+    assert_eq!(
+      ByteRange::parse("bytes=0-99"),
+      Some(ByteRange::Bounded(0, 99))
+    );
+    assert_eq!(ByteRange::parse("bytes=100-"), Some(ByteRange::From(100)));
+    assert_eq!(ByteRange::parse("bytes=-100"), Some(ByteRange::Suffix(100)));
+    assert_eq!(ByteRange::parse("bytes=99-0"), None);
+    assert_eq!(ByteRange::parse("bytes=0-1, 4-5"), None);
+    assert_eq!(ByteRange::parse("items=0-1"), None);
+    assert_eq!(ByteRange::parse("bytes=-"), None);
+  }
+
+  #[test]
+  fn resolve() {
+    assert_eq!(ByteRange::Bounded(0, 99).resolve(50), Some(0..50));
+    assert_eq!(ByteRange::Bounded(10, 19).resolve(50), Some(10..20));
+    assert_eq!(ByteRange::From(40).resolve(50), Some(40..50));
+    assert_eq!(ByteRange::Suffix(10).resolve(50), Some(40..50));
+    assert_eq!(ByteRange::Suffix(100).resolve(50), Some(0..50));
+    assert_eq!(ByteRange::From(50).resolve(50), None);
+    assert_eq!(ByteRange::Suffix(0).resolve(50), None);
+    assert_eq!(ByteRange::Suffix(10).resolve(0), None);
+  }
+}
@@ -0,0 +1,303 @@
+use {
+  super::*,
+  axum::{body::Bytes, extract::OriginalUri},
+  std::sync::Mutex,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CacheScope {
+  Content,
+  Relic,
+  Block,
+  Address,
+  All,
+}
+
+impl CacheScope {
+  const CACHED: [CacheScope; 4] = [Self::Content, Self::Relic, Self::Block, Self::Address];
+
+  fn of(path: &str) -> Option<Self> {
+    if path.starts_with("/content/") {
+      Some(Self::Content)
+    } else if path.starts_with("/bone/") || path.starts_with("/bones") {
+      Some(Self::Relic)
+    } else if path.starts_with("/block/") || path.starts_with("/blocks/") {
+      Some(Self::Block)
+    } else if path.starts_with("/inscriptions/balance/") {
+      Some(Self::Address)
+    } else {
+      None
+    }
+  }
+}
+
+struct CachedResponse {
+  height: Option<u32>,
+  headers: HeaderMap,
+  body: Bytes,
+}
+
+/// In-process LRU caches of successful GET responses, one per scope.
+///
+/// Inscription content never changes, all other responses are only served
+/// from the cache while the index height is unchanged. Content is cached for
+/// every encoding, so compressed variants are only compressed once.
+pub(crate) struct ResponseCache {
+  capacity: usize,
+  caches: Mutex<BTreeMap<CacheScope, LinkedHashMap<String, CachedResponse>>>,
+}
+
+impl ResponseCache {
+  pub(crate) fn new(capacity: usize) -> Self {
+    Self {
+      capacity,
+      caches: Mutex::new(
+        CacheScope::CACHED
+          .into_iter()
+          .map(|scope| (scope, LinkedHashMap::new()))
+          .collect(),
+      ),
+    }
+  }
+
+  fn get(&self, scope: CacheScope, key: &str, height: Option<u32>) -> Option<Response> {
+    let mut caches = self.caches.lock().unwrap();
+    let cache = caches.get_mut(&scope)?;
+
+    let cached = cache.get_refresh(key)?;
+
+    if cached.height != height {
+      cache.remove(key);
+      return None;
+    }
+
+    let mut response = body::boxed(body::Full::from(cached.body.clone())).into_response();
+    *response.headers_mut() = cached.headers.clone();
+    Some(response)
+  }
+
+  fn insert(&self, scope: CacheScope, key: String, response: CachedResponse) {
+    if self.capacity == 0 {
+      return;
+    }
+
+    let mut caches = self.caches.lock().unwrap();
+    let cache = caches.entry(scope).or_default();
+
+    cache.insert(key, response);
+
+    while cache.len() > self.capacity {
+      cache.pop_front();
+    }
+  }
+
+  /// Drops all cached responses of `scope` and returns the number of evicted
+  /// entries per scope.
+  pub(crate) fn purge(&self, scope: CacheScope) -> BTreeMap<CacheScope, usize> {
+    let mut caches = self.caches.lock().unwrap();
+
+    caches
+      .iter_mut()
+      .filter(|(cached, _)| scope == CacheScope::All || **cached == scope)
+      .map(|(cached, cache)| {
+        let evicted = cache.len();
+        cache.clear();
+        (*cached, evicted)
+      })
+      .collect()
+  }
+
+  /// Cache key of `request`, or `None` if its response must not be cached.
+  /// Keys use the original URI, since versioned and unversioned API paths
+  /// share the same routes but not the same response headers.
+  fn key<B>(scope: CacheScope, request: &http::Request<B>) -> Option<String> {
+    let uri = request
+      .extensions()
+      .get::<OriginalUri>()
+      .map(|original| &original.0)
+      .unwrap_or(request.uri());
+
+    // content is cached once per negotiated encoding, byte ranges are not cached
+    match scope {
+      CacheScope::Content => {
+        if request.headers().contains_key(header::RANGE) {
+          return None;
+        }
+        Some(format!(
+          "{} {}",
+          uri,
+          ContentEncoding::negotiate(request.headers())
+        ))
+      }
+      _ => Some(uri.to_string()),
+    }
+  }
+
+  pub(crate) async fn middleware<B>(
+    Extension(cache): Extension<Arc<ResponseCache>>,
+    Extension(index): Extension<Arc<Index>>,
+    request: http::Request<B>,
+    next: Next<B>,
+  ) -> Response {
+    let scope = CacheScope::of(request.uri().path());
+
+    let Some(scope) = scope.filter(|_| request.method() == http::Method::GET) else {
+      return next.run(request).await;
+    };
+
+    let Some(mut key) = Self::key(scope, &request) else {
+      return next.run(request).await;
+    };
+
+    // amounts are written differently depending on the requested format
+    if AmountFormat::current() == AmountFormat::String {
+      key.push_str(" strings");
+    }
+
+    let height = match scope {
+      CacheScope::Content => None,
+      _ => match index.block_count() {
+        Ok(height) => Some(height),
+        Err(_) => return next.run(request).await,
+      },
+    };
+
+    if let Some(response) = cache.get(scope, &key, height) {
+      return response;
+    }
+
+    let response = next.run(request).await;
+
+    // streamed responses are too large to buffer
+    if response.status() != StatusCode::OK
+      || body::HttpBody::size_hint(response.body()).exact().is_none()
+    {
+      return response;
+    }
+
+    let (parts, body) = response.into_parts();
+
+    let body = match hyper::body::to_bytes(body).await {
+      Ok(body) => body,
+      Err(error) => {
+        return ServerError::Internal(anyhow!("failed to buffer response: {error}")).into_response()
+      }
+    };
+
+    cache.insert(
+      scope,
+      key,
+      CachedResponse {
+        height,
+        headers: parts.headers.clone(),
+        body: body.clone(),
+      },
+    );
+
+    Response::from_parts(parts, body::boxed(body::Full::from(body)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn cached(height: Option<u32>) -> CachedResponse {
+    CachedResponse {
+      height,
+      headers: HeaderMap::new(),
+      body: Bytes::from_static(b"foo"),
+    }
+  }
+
+  #[test]
+  fn scope() {
+    assert_eq!(CacheScope::of("/content/foo"), Some(CacheScope::Content));
+    assert_eq!(CacheScope::of("/bone/foo"), Some(CacheScope::Relic));
+    assert_eq!(CacheScope::of("/bones/1"), Some(CacheScope::Relic));
+    assert_eq!(CacheScope::of("/block/1"), Some(CacheScope::Block));
+    assert_eq!(
+      CacheScope::of("/inscriptions/balance/foo/0"),
+      Some(CacheScope::Address)
+    );
+    assert_eq!(CacheScope::of("/inscriptions/0"), None);
+    assert_eq!(CacheScope::of("/status"), None);
+  }
+
+  #[test]
+  fn key() {
+    let request = |uri: &str, original: Option<&str>| {
+      let mut request = http::Request::get(uri).body(()).unwrap();
+      if let Some(original) = original {
+        request
+          .extensions_mut()
+          .insert(OriginalUri(original.parse().unwrap()));
+      }
+      request
+    };
+
+    assert_eq!(
+      ResponseCache::key(CacheScope::Relic, &request("/bones", None)),
+      Some("/bones".into())
+    );
+    assert_eq!(
+      ResponseCache::key(CacheScope::Relic, &request("/bones", Some("/v1/bones"))),
+      Some("/v1/bones".into())
+    );
+    assert_ne!(
+      ResponseCache::key(CacheScope::Relic, &request("/bones", Some("/v1/bones"))),
+      ResponseCache::key(CacheScope::Relic, &request("/bones", Some("/bones")))
+    );
+
+    let mut range = request("/content/foo", None);
+    range
+      .headers_mut()
+      .insert(header::RANGE, HeaderValue::from_static("bytes=0-1"));
+    assert_eq!(ResponseCache::key(CacheScope::Content, &range), None);
+  }
+
+  #[test]
+  fn stale_entries_are_not_served() {
+    let cache = ResponseCache::new(10);
+    cache.insert(CacheScope::Relic, "/bones".into(), cached(Some(1)));
+    assert!(cache.get(CacheScope::Relic, "/bones", Some(2)).is_none());
+    assert!(cache.get(CacheScope::Relic, "/bones", Some(1)).is_none());
+  }
+
+  #[test]
+  fn least_recently_used_entries_are_evicted() {
+    let cache = ResponseCache::new(2);
+    cache.insert(CacheScope::Content, "a".into(), cached(None));
+    cache.insert(CacheScope::Content, "b".into(), cached(None));
+    assert!(cache.get(CacheScope::Content, "a", None).is_some());
+    cache.insert(CacheScope::Content, "c".into(), cached(None));
+    assert!(cache.get(CacheScope::Content, "a", None).is_some());
+    assert!(cache.get(CacheScope::Content, "b", None).is_none());
+  }
+
+  #[test]
+  fn purge() {
+    let cache = ResponseCache::new(10);
+    cache.insert(CacheScope::Content, "a".into(), cached(None));
+    cache.insert(CacheScope::Content, "b".into(), cached(None));
+    cache.insert(CacheScope::Block, "c".into(), cached(Some(1)));
+
+    assert_eq!(
+      cache.purge(CacheScope::Content),
+      [(CacheScope::Content, 2)].into_iter().collect()
+    );
+
+    assert_eq!(
+      cache.purge(CacheScope::All),
+      [
+        (CacheScope::Content, 0),
+        (CacheScope::Relic, 0),
+        (CacheScope::Block, 1),
+        (CacheScope::Address, 0)
+      ]
+      .into_iter()
+      .collect()
+    );
+  }
+}
@@ -0,0 +1,157 @@
+use {super::*, std::mem};
+
+/// An output holding the bone to select, and what else it carries.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Candidate {
+  pub(crate) outpoint: OutPoint,
+  /// amount of the bone to select
+  pub(crate) amount: u128,
+  /// other bones held by the output
+  pub(crate) other_relics: usize,
+  pub(crate) inscriptions: usize,
+}
+
+impl Candidate {
+  /// Outputs carrying inscriptions are avoided the most, since spending them
+  /// risks sending the inscriptions along, then outputs carrying other bones.
+  fn tier(&self) -> usize {
+    if self.inscriptions > 0 {
+      2
+    } else if self.other_relics > 0 {
+      1
+    } else {
+      0
+    }
+  }
+}
+
+/// Selects outputs of `candidates` whose combined balance covers `amount`,
+/// or returns `None` if all of them together don't.
+///
+/// Outputs of a tier are only selected if the outputs of the tiers below it
+/// can't cover the amount, and then only as few as needed. From the highest
+/// tier needed down, each tier covers what the tiers below it can't.
+pub(crate) fn select(candidates: Vec<Candidate>, amount: u128) -> Option<Vec<Candidate>> {
+  let mut tiers = [Vec::new(), Vec::new(), Vec::new()];
+
+  for candidate in candidates {
+    if candidate.amount > 0 {
+      tiers[candidate.tier()].push(candidate);
+    }
+  }
+
+  let totals = tiers.iter().map(|tier| sum(tier)).collect::<Vec<u128>>();
+
+  let mut covered = 0u128;
+  let top = totals.iter().position(|total| {
+    covered = covered.saturating_add(*total);
+    covered >= amount
+  })?;
+
+  let mut selected = Vec::new();
+  let mut remaining = amount;
+
+  for tier in (0..=top).rev() {
+    let below = totals[..tier]
+      .iter()
+      .fold(0u128, |sum, total| sum.saturating_add(*total));
+
+    for candidate in fewest(mem::take(&mut tiers[tier]), remaining.saturating_sub(below)) {
+      remaining = remaining.saturating_sub(candidate.amount);
+      selected.push(candidate);
+    }
+  }
+
+  Some(selected)
+}
+
+fn sum(candidates: &[Candidate]) -> u128 {
+  candidates
+    .iter()
+    .fold(0u128, |sum, candidate| sum.saturating_add(candidate.amount))
+}
+
+/// The fewest of `candidates` covering `amount`, if they can: the largest
+/// ones, until a single one covers the rest, which is then the smallest one
+/// that does, to keep the change small.
+fn fewest(mut candidates: Vec<Candidate>, mut amount: u128) -> Vec<Candidate> {
+  candidates.sort_by(|a, b| b.amount.cmp(&a.amount));
+
+  let mut selected = Vec::new();
+
+  while amount > 0 && !candidates.is_empty() {
+    // sorted descending, so the candidates covering the rest come first
+    match candidates
+      .iter()
+      .rposition(|candidate| candidate.amount >= amount)
+    {
+      Some(i) => {
+        selected.push(candidates.remove(i));
+        amount = 0;
+      }
+      None => {
+        let candidate = candidates.remove(0);
+        amount -= candidate.amount;
+        selected.push(candidate);
+      }
+    }
+  }
+
+  selected
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn candidate(vout: u32, amount: u128, other_relics: usize, inscriptions: usize) -> Candidate {
+    Candidate {
+      outpoint: OutPoint {
+        txid: Txid::all_zeros(),
+        vout,
+      },
+      amount,
+      other_relics,
+      inscriptions,
+    }
+  }
+
+  fn selected(candidates: Vec<Candidate>, amount: u128) -> Option<Vec<u32>> {
+    select(candidates, amount).map(|selected| {
+      selected
+        .into_iter()
+        .map(|candidate| candidate.outpoint.vout)
+        .collect()
+    })
+  }
+
+  #[test]
+  fn smallest_output_covering_the_amount_is_selected() {
+    let candidates = vec![
+      candidate(0, 10, 0, 0),
+      candidate(1, 5, 0, 0),
+      candidate(2, 3, 0, 0),
+    ];
+
+    assert_eq!(selected(candidates.clone(), 4), Some(vec![1]));
+    assert_eq!(selected(candidates.clone(), 14), Some(vec![0, 1]));
+    assert_eq!(selected(candidates.clone(), 18), Some(vec![0, 1, 2]));
+    assert_eq!(selected(candidates, 19), None);
+  }
+
+  #[test]
+  fn outputs_carrying_inscriptions_or_other_bones_are_avoided() {
+    let candidates = vec![
+      candidate(0, 100, 0, 1),
+      candidate(1, 100, 1, 0),
+      candidate(2, 2, 0, 0),
+      candidate(3, 0, 0, 0),
+    ];
+
+    assert_eq!(selected(candidates.clone(), 2), Some(vec![2]));
+    assert_eq!(selected(candidates.clone(), 50), Some(vec![1]));
+    assert_eq!(selected(candidates.clone(), 101), Some(vec![1, 2]));
+    assert_eq!(selected(candidates.clone(), 150), Some(vec![0, 1]));
+    assert_eq!(selected(candidates, 203), None);
+  }
+}
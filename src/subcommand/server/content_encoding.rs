@@ -0,0 +1,174 @@
+use {super::*, std::io::Write};
+
+/// Encoding of inscription content, negotiated from the `Accept-Encoding`
+/// header of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ContentEncoding {
+  Brotli,
+  Gzip,
+  Identity,
+}
+
+impl ContentEncoding {
+  /// The encoding with the highest quality accepted by the client, preferring
+  /// brotli over gzip at equal quality.
+  pub(crate) fn negotiate(headers: &HeaderMap) -> Self {
+    let mut brotli = 0.0;
+    let mut gzip = 0.0;
+
+    for value in headers.get_all(header::ACCEPT_ENCODING) {
+      let Ok(value) = value.to_str() else {
+        continue;
+      };
+
+      for coding in value.split(',') {
+        let mut params = coding.split(';');
+
+        let name = params.next().unwrap_or_default().trim();
+
+        let quality = params
+          .filter_map(|param| param.trim().strip_prefix("q="))
+          .find_map(|quality| quality.parse::<f32>().ok())
+          .unwrap_or(1.0);
+
+        if name.eq_ignore_ascii_case("br") {
+          brotli = quality;
+        } else if name.eq_ignore_ascii_case("gzip") || name.eq_ignore_ascii_case("x-gzip") {
+          gzip = quality;
+        }
+      }
+    }
+
+    if brotli > 0.0 && brotli >= gzip {
+      Self::Brotli
+    } else if gzip > 0.0 {
+      Self::Gzip
+    } else {
+      Self::Identity
+    }
+  }
+
+  /// Whether content of `content_type` is worth compressing. Media formats
+  /// are compressed already.
+  pub(crate) fn compressible(content_type: Option<&str>) -> bool {
+    let Some(content_type) = content_type else {
+      return false;
+    };
+
+    let essence = content_type
+      .split(';')
+      .next()
+      .unwrap_or_default()
+      .trim()
+      .to_ascii_lowercase();
+
+    essence.starts_with("text/")
+      || essence.ends_with("+json")
+      || essence.ends_with("+xml")
+      || matches!(
+        essence.as_str(),
+        "application/json"
+          | "application/javascript"
+          | "application/xml"
+          | "application/x-javascript"
+      )
+  }
+
+  pub(crate) fn compress(self, body: &[u8]) -> io::Result<Vec<u8>> {
+    match self {
+      Self::Brotli => {
+        let mut output = Vec::new();
+        {
+          let mut writer = brotli::CompressorWriter::new(&mut output, 4096, 9, 22);
+          writer.write_all(body)?;
+          writer.flush()?;
+        }
+        Ok(output)
+      }
+      Self::Gzip => {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(body)?;
+        encoder.finish()
+      }
+      Self::Identity => Ok(body.to_vec()),
+    }
+  }
+
+  pub(crate) fn header_value(self) -> HeaderValue {
+    HeaderValue::from_static(match self {
+      Self::Brotli => "br",
+      Self::Gzip => "gzip",
+      Self::Identity => "identity",
+    })
+  }
+}
+
+impl Display for ContentEncoding {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Brotli => write!(f, "br"),
+      Self::Gzip => write!(f, "gzip"),
+      Self::Identity => write!(f, "identity"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, std::io::Read};
+
+  fn negotiate(accept_encoding: &str) -> ContentEncoding {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+      header::ACCEPT_ENCODING,
+      HeaderValue::from_str(accept_encoding).unwrap(),
+    );
+    ContentEncoding::negotiate(&headers)
+  }
+
+  #[test]
+  fn negotiation() {
+    assert_eq!(
+      ContentEncoding::negotiate(&HeaderMap::new()),
+      ContentEncoding::Identity
+    );
+    assert_eq!(negotiate("gzip, deflate, br"), ContentEncoding::Brotli);
+    assert_eq!(negotiate("gzip"), ContentEncoding::Gzip);
+    assert_eq!(negotiate("br;q=0.5, gzip"), ContentEncoding::Gzip);
+    assert_eq!(negotiate("br;q=0, gzip;q=0"), ContentEncoding::Identity);
+    assert_eq!(negotiate("deflate"), ContentEncoding::Identity);
+  }
+
+  #[test]
+  fn compressible_content_types() {
+    assert!(ContentEncoding::compressible(Some(
+      "text/html;charset=utf-8"
+    )));
+    assert!(ContentEncoding::compressible(Some("application/json")));
+    assert!(ContentEncoding::compressible(Some("model/gltf+json")));
+    assert!(ContentEncoding::compressible(Some("image/svg+xml")));
+    assert!(!ContentEncoding::compressible(Some("video/mp4")));
+    assert!(!ContentEncoding::compressible(Some("image/png")));
+    assert!(!ContentEncoding::compressible(None));
+  }
+
+  #[test]
+  fn compressed_content_round_trips() {
+    let body = "hello world ".repeat(100).into_bytes();
+
+    let mut decompressed = Vec::new();
+    brotli::Decompressor::new(
+      ContentEncoding::Brotli.compress(&body).unwrap().as_slice(),
+      4096,
+    )
+    .read_to_end(&mut decompressed)
+    .unwrap();
+    assert_eq!(decompressed, body);
+
+    let mut decompressed = Vec::new();
+    flate2::read::GzDecoder::new(ContentEncoding::Gzip.compress(&body).unwrap().as_slice())
+      .read_to_end(&mut decompressed)
+      .unwrap();
+    assert_eq!(decompressed, body);
+  }
+}
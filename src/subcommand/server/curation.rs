@@ -0,0 +1,147 @@
+use {
+  super::*,
+  crate::{relics::Relic, templates::relic::RelicEntryHtml},
+  std::sync::RwLock,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CurationList {
+  Featured,
+  Verified,
+}
+
+impl CurationList {
+  pub(crate) const ALL: [CurationList; 2] = [Self::Featured, Self::Verified];
+}
+
+impl Display for CurationList {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Featured => write!(f, "featured"),
+      Self::Verified => write!(f, "verified"),
+    }
+  }
+}
+
+/// Curated relic lists, seeded from the `featured` and `verified` config keys.
+///
+/// Changes made through the admin API only live until the server restarts.
+pub(crate) struct Curation {
+  lists: RwLock<BTreeMap<CurationList, BTreeMap<Relic, SpacedRelic>>>,
+}
+
+impl Curation {
+  pub(crate) fn new(config: &Config) -> Self {
+    let list = |relics: &BTreeSet<SpacedRelic>| {
+      relics
+        .iter()
+        .map(|spaced_relic| (spaced_relic.relic, *spaced_relic))
+        .collect()
+    };
+
+    Self {
+      lists: RwLock::new(
+        [
+          (CurationList::Featured, list(&config.featured)),
+          (CurationList::Verified, list(&config.verified)),
+        ]
+        .into_iter()
+        .collect(),
+      ),
+    }
+  }
+
+  pub(crate) fn contains(&self, list: CurationList, relic: Relic) -> bool {
+    self
+      .lists
+      .read()
+      .unwrap()
+      .get(&list)
+      .map(|relics| relics.contains_key(&relic))
+      .unwrap_or_default()
+  }
+
+  pub(crate) fn relics(&self, list: CurationList) -> Vec<SpacedRelic> {
+    self
+      .lists
+      .read()
+      .unwrap()
+      .get(&list)
+      .map(|relics| relics.values().copied().collect())
+      .unwrap_or_default()
+  }
+
+  /// Returns false if `spaced_relic` was already on `list`.
+  pub(crate) fn insert(&self, list: CurationList, spaced_relic: SpacedRelic) -> bool {
+    self
+      .lists
+      .write()
+      .unwrap()
+      .entry(list)
+      .or_default()
+      .insert(spaced_relic.relic, spaced_relic)
+      .is_none()
+  }
+
+  /// Returns false if `relic` was not on `list`.
+  pub(crate) fn remove(&self, list: CurationList, relic: Relic) -> bool {
+    self
+      .lists
+      .write()
+      .unwrap()
+      .get_mut(&list)
+      .and_then(|relics| relics.remove(&relic))
+      .is_some()
+  }
+
+  pub(crate) fn flag(&self, entry: &mut RelicEntryHtml) {
+    entry.featured = self.contains(CurationList::Featured, entry.spaced_relic.relic);
+    entry.verified = self.contains(CurationList::Verified, entry.spaced_relic.relic);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn lists_are_seeded_from_config() {
+    let curation = Curation::new(&Config {
+      verified: iter::once("FOO•BAR".parse().unwrap()).collect(),
+      ..default()
+    });
+
+    let relic = "FOOBAR".parse::<Relic>().unwrap();
+
+    assert!(curation.contains(CurationList::Verified, relic));
+    assert!(!curation.contains(CurationList::Featured, relic));
+    assert_eq!(
+      curation.relics(CurationList::Verified),
+      vec!["FOO•BAR".parse::<SpacedRelic>().unwrap()]
+    );
+  }
+
+  #[test]
+  fn insert_and_remove() {
+    let curation = Curation::new(&Config::default());
+
+    let spaced_relic = "FOO•BAR".parse::<SpacedRelic>().unwrap();
+
+    assert!(curation.insert(CurationList::Featured, spaced_relic));
+    assert!(!curation.insert(CurationList::Featured, spaced_relic));
+    assert!(curation.contains(CurationList::Featured, spaced_relic.relic));
+
+    let mut entry = RelicEntryHtml {
+      spaced_relic,
+      ..default()
+    };
+    curation.flag(&mut entry);
+    assert!(entry.featured);
+    assert!(!entry.verified);
+
+    assert!(curation.remove(CurationList::Featured, spaced_relic.relic));
+    assert!(!curation.remove(CurationList::Featured, spaced_relic.relic));
+    assert!(!curation.contains(CurationList::Featured, spaced_relic.relic));
+  }
+}
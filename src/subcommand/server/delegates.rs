@@ -0,0 +1,146 @@
+use {super::*, bincode::Options, std::sync::Mutex};
+
+/// Inscriptions whose content is remembered by `Delegates` in memory.
+const CAPACITY: usize = 10_000;
+
+/// What `/content/<ID>` of an inscription serves.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Content {
+  /// the inscription whose content is served instead, if any
+  pub(crate) delegate: Option<InscriptionId>,
+  /// raw content type, which isn't necessarily UTF-8
+  pub(crate) content_type: Option<Vec<u8>>,
+  pub(crate) content_length: Option<usize>,
+}
+
+/// Cache of the content type and length of inscriptions, so resolving the
+/// delegate of an inscription doesn't parse the delegate every time. Recently
+/// used entries are kept in an LRU in memory, all of them in the
+/// `INSCRIPTION_ID_TO_CONTENT` table of the operator database, so they survive
+/// restarts.
+///
+/// Inscription content never changes, so entries never go stale. Inscriptions
+/// that don't exist are not cached, since a delegate may be inscribed after
+/// the inscriptions delegating to it.
+pub(crate) struct Delegates {
+  cache: Mutex<LinkedHashMap<InscriptionId, Content>>,
+}
+
+impl Delegates {
+  pub(crate) fn new() -> Self {
+    Self {
+      cache: Mutex::new(LinkedHashMap::new()),
+    }
+  }
+
+  /// The content of inscription `id` itself, without following its delegate.
+  pub(crate) fn content(&self, index: &Index, id: InscriptionId) -> Result<Option<Content>> {
+    if let Some(content) = self.cache.lock().unwrap().get_refresh(&id) {
+      return Ok(Some(content.clone()));
+    }
+
+    let content = match index.get_cached_content(id)? {
+      Some(content) => {
+        // the table outlives reorgs, which may have removed the inscription
+        if !index.inscription_exists(id)? {
+          return Ok(None);
+        }
+
+        bincode::DefaultOptions::new().deserialize(&content)?
+      }
+      None => {
+        let Some(inscription) = index.get_inscription_by_id(id)? else {
+          return Ok(None);
+        };
+
+        let content = Content {
+          delegate: inscription.delegate(),
+          content_length: inscription.content_length(),
+          content_type: inscription.content_type,
+        };
+
+        index.cache_content(id, &bincode::DefaultOptions::new().serialize(&content)?)?;
+
+        content
+      }
+    };
+
+    let mut cache = self.cache.lock().unwrap();
+
+    cache.insert(id, content.clone());
+
+    if cache.len() > CAPACITY {
+      cache.pop_front();
+    }
+
+    Ok(Some(content))
+  }
+
+  /// The content of inscription `id` and of its delegate, if it has one.
+  /// `None` if `id` doesn't exist, the delegate's content is `None` if the
+  /// delegate doesn't exist.
+  pub(crate) fn resolve(
+    &self,
+    index: &Index,
+    id: InscriptionId,
+  ) -> Result<Option<(Content, Option<Content>)>> {
+    let Some(content) = self.content(index, id)? else {
+      return Ok(None);
+    };
+
+    let delegate = match content.delegate {
+      Some(delegate) => self.content(index, delegate)?,
+      None => None,
+    };
+
+    Ok(Some((content, delegate)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  #[test]
+  fn content_is_cached_across_restarts() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    // content types are not necessarily UTF-8
+    let content_type = vec![0xff, b'/', b'x'];
+
+    let txid = context.core.broadcast_tx(mockcore::TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription::new(Some(content_type.clone()), Some(b"foo".to_vec())).to_script(),
+      )],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    let content = Content {
+      delegate: None,
+      content_type: Some(content_type),
+      content_length: Some(3),
+    };
+
+    assert_eq!(context.index.get_cached_content(id).unwrap(), None);
+    assert_eq!(
+      Delegates::new().content(&context.index, id).unwrap(),
+      Some(content.clone())
+    );
+
+    // a new cache starts without entries in memory and reads the table
+    assert!(context.index.get_cached_content(id).unwrap().is_some());
+    assert_eq!(
+      Delegates::new().content(&context.index, id).unwrap(),
+      Some(content)
+    );
+  }
+}
@@ -0,0 +1,119 @@
+use super::*;
+
+/// POSTs the digest of every address to `webhook` each time another `period`
+/// worth of blocks has been indexed. Digests that fail to be delivered are
+/// retried on every poll until they are, or until the digests of the next
+/// period are due and replace them.
+pub(super) fn send_digests(
+  index: &Index,
+  webhook: &Url,
+  addresses: &[Address],
+  period: DigestPeriod,
+) {
+  let client = reqwest::blocking::Client::new();
+
+  let mut next = index.block_count().unwrap_or_default() + period.blocks();
+
+  // addresses whose digest of the current period is yet to be delivered
+  let mut pending = Vec::new();
+
+  loop {
+    if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+      break;
+    }
+
+    if index.block_count().unwrap_or_default() >= next {
+      pending = addresses.to_vec();
+      next += period.blocks();
+    }
+
+    if !pending.is_empty() {
+      pending = send(&client, index, webhook, &pending, period);
+    }
+
+    thread::sleep(Duration::from_millis(5000));
+  }
+}
+
+/// Sends one digest per address and returns the addresses whose digest
+/// failed to be delivered, so they can be retried.
+fn send(
+  client: &reqwest::blocking::Client,
+  index: &Index,
+  webhook: &Url,
+  addresses: &[Address],
+  period: DigestPeriod,
+) -> Vec<Address> {
+  let mut failed = Vec::new();
+
+  for address in addresses {
+    let result = index.address_digest(address, period).and_then(|digest| {
+      client
+        .post(webhook.clone())
+        .json(&digest)
+        .send()?
+        .error_for_status()?;
+      Ok(())
+    });
+
+    if let Err(error) = result {
+      log::warn!("failed to send digest for {address}: {error}");
+      failed.push(address.clone());
+    }
+  }
+
+  failed
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::index::{digest::AddressDigest, testing::Context},
+  };
+
+  #[test]
+  fn digests_are_posted_to_webhook() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    context.mint_base_token(1, 1);
+
+    // relic transactions pay to a P2WPKH script with an all-zero hash
+    let address = Address::from_script(
+      &Script::new_v0_p2wpkh(&bitcoin::WPubkeyHash::all_zeros()),
+      Network::Regtest,
+    )
+    .unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let webhook = Url::parse(&format!("http://{}/digest", listener.local_addr().unwrap())).unwrap();
+    let receiver = thread::spawn(move || receive_request(&listener));
+
+    assert_eq!(
+      send(
+        &reqwest::blocking::Client::new(),
+        &context.index,
+        &webhook,
+        &[address.clone()],
+        DigestPeriod::Daily,
+      ),
+      Vec::new()
+    );
+
+    let digest = serde_json::from_slice::<AddressDigest>(&receiver.join().unwrap()).unwrap();
+
+    assert_eq!(
+      digest,
+      context
+        .index
+        .address_digest(&address, DigestPeriod::Daily)
+        .unwrap()
+    );
+    assert_eq!(
+      digest.received,
+      [(Context::base_token_entry().spaced_relic, 572_000_000)]
+        .into_iter()
+        .collect()
+    );
+  }
+}
@@ -1,9 +1,11 @@
 use super::*;
 
+#[derive(Debug)]
 pub(super) enum ServerError {
   Internal(Error),
   BadRequest(String),
   NotFound(String),
+  Unauthorized(String),
 }
 
 pub(super) type ServerResult<T> = Result<T, ServerError>;
@@ -23,6 +25,7 @@ impl IntoResponse for ServerError {
       }
       Self::NotFound(message) => (StatusCode::NOT_FOUND, message).into_response(),
       Self::BadRequest(message) => (StatusCode::BAD_REQUEST, message).into_response(),
+      Self::Unauthorized(message) => (StatusCode::UNAUTHORIZED, message).into_response(),
     }
   }
 }
@@ -0,0 +1,422 @@
+use {
+  super::*,
+  crate::{index::entry::InscriptionEntry, relics::Pool},
+  async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Json as GraphQLJson, Object, Request,
+    Result as GraphQLResult, Schema, SimpleObject,
+  },
+  once_cell::sync::Lazy,
+};
+
+type RelicSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+static SCHEMA: Lazy<RelicSchema> =
+  Lazy::new(|| Schema::build(QueryRoot, EmptyMutation, EmptySubscription).finish());
+
+/// Executes `request` against the index. Resolvers read from the index
+/// synchronously, so this must be called from a blocking context.
+pub(super) fn execute(index: Arc<Index>, request: Request) -> async_graphql::Response {
+  futures::executor::block_on(SCHEMA.execute(request.data(index)))
+}
+
+fn index<'a>(ctx: &Context<'a>) -> GraphQLResult<&'a Arc<Index>> {
+  ctx.data::<Arc<Index>>()
+}
+
+fn relic(index: &Index, query: query::Relic) -> Result<Option<RelicObject>> {
+  let relic = match query {
+    query::Relic::Spaced(spaced_relic) => Some(spaced_relic.relic),
+    query::Relic::Id(id) => index.get_relic_by_id(id)?,
+    query::Relic::Number(number) => index.get_relic_by_number(usize::try_from(number)?)?,
+  };
+
+  let Some(relic) = relic else {
+    return Ok(None);
+  };
+
+  Ok(
+    index
+      .relic(relic)?
+      .map(|(id, entry, owner)| RelicObject { id, entry, owner }),
+  )
+}
+
+fn inscription(index: &Index, id: InscriptionId) -> Result<Option<InscriptionObject>> {
+  Ok(
+    index
+      .get_inscription_entry(id)?
+      .map(|entry| InscriptionObject { entry }),
+  )
+}
+
+pub(super) struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+  /// Look up a bone by spaced ticker, id or number.
+  async fn bone(&self, ctx: &Context<'_>, query: String) -> GraphQLResult<Option<RelicObject>> {
+    Ok(relic(index(ctx)?, query.parse()?)?)
+  }
+
+  /// Bones in reverse enshrining order, 50 per page.
+  async fn bones(&self, ctx: &Context<'_>, page: Option<usize>) -> GraphQLResult<Vec<RelicObject>> {
+    let (entries, _more) = index(ctx)?.relics_paginated(50, page.unwrap_or_default())?;
+
+    Ok(
+      entries
+        .into_iter()
+        .map(|(id, entry, owner)| RelicObject { id, entry, owner })
+        .collect(),
+    )
+  }
+
+  async fn inscription(
+    &self,
+    ctx: &Context<'_>,
+    id: String,
+  ) -> GraphQLResult<Option<InscriptionObject>> {
+    Ok(inscription(index(ctx)?, id.parse()?)?)
+  }
+
+  async fn syndicate(
+    &self,
+    ctx: &Context<'_>,
+    id: String,
+  ) -> GraphQLResult<Option<SyndicateObject>> {
+    Ok(
+      index(ctx)?
+        .syndicate(id.parse::<SyndicateId>()?)?
+        .map(|(id, entry, owner)| SyndicateObject { id, entry, owner }),
+    )
+  }
+
+  /// Events in chain order, see `GET /events` for the meaning of the arguments.
+  #[allow(clippy::too_many_arguments)]
+  async fn events(
+    &self,
+    ctx: &Context<'_>,
+    from: Option<u32>,
+    to: Option<u32>,
+    kind: Option<String>,
+    bone: Option<String>,
+    txid: Option<String>,
+    limit: Option<usize>,
+  ) -> GraphQLResult<Vec<EventObject>> {
+    let index = index(ctx)?;
+
+    let relic_id = match bone {
+      Some(bone) => match relic(index, bone.parse()?)? {
+        Some(relic) => Some(relic.id),
+        None => return Ok(Vec::new()),
+      },
+      None => None,
+    };
+
+    let filter = EventFilter {
+      from: from.unwrap_or(0),
+      to: to.unwrap_or(u32::MAX),
+      kind: kind.map(|kind| parse_kind(&kind)).transpose()?,
+      relic_id,
+      txid: txid.map(|txid| txid.parse()).transpose()?,
+    };
+
+    events(index, &filter, limit)
+  }
+}
+
+fn parse_kind(kind: &str) -> Result<EventKind> {
+  Ok(serde_json::from_value(serde_json::Value::String(
+    kind.into(),
+  ))?)
+}
+
+fn events(
+  index: &Index,
+  filter: &EventFilter,
+  limit: Option<usize>,
+) -> GraphQLResult<Vec<EventObject>> {
+  let limit = limit.unwrap_or(100);
+
+  if limit == 0 || limit > 1000 {
+    return Err("limit must be between 1 and 1000".into());
+  }
+
+  let (events, _next) = index.filter_events(filter, None, limit)?;
+
+  Ok(events.into_iter().map(EventObject).collect())
+}
+
+pub(super) struct RelicObject {
+  id: RelicId,
+  entry: RelicEntry,
+  owner: Option<InscriptionId>,
+}
+
+#[Object(name = "Bone")]
+impl RelicObject {
+  async fn id(&self) -> String {
+    self.id.to_string()
+  }
+
+  async fn spaced_bone(&self) -> String {
+    self.entry.spaced_relic.to_string()
+  }
+
+  async fn number(&self) -> u64 {
+    self.entry.number
+  }
+
+  async fn symbol(&self) -> Option<String> {
+    self.entry.symbol.map(|symbol| symbol.to_string())
+  }
+
+  async fn divisibility(&self) -> u8 {
+    Enshrining::DIVISIBILITY
+  }
+
+  async fn enshrining(&self) -> String {
+    self.entry.enshrining.to_string()
+  }
+
+  async fn timestamp(&self) -> u64 {
+    self.entry.timestamp
+  }
+
+  async fn mints(&self) -> String {
+    self.entry.state.mints.to_string()
+  }
+
+  async fn burned(&self) -> String {
+    self.entry.state.burned.to_string()
+  }
+
+  async fn circulating_supply(&self) -> String {
+    self.entry.circulating_supply().to_string()
+  }
+
+  async fn turbo(&self) -> bool {
+    self.entry.turbo
+  }
+
+  async fn pool(&self) -> Option<PoolObject> {
+    self.entry.pool.map(PoolObject::from)
+  }
+
+  /// The inscription that sealed the ticker.
+  async fn owner(&self, ctx: &Context<'_>) -> GraphQLResult<Option<InscriptionObject>> {
+    match self.owner {
+      Some(owner) => Ok(inscription(index(ctx)?, owner)?),
+      None => Ok(None),
+    }
+  }
+
+  async fn thumbnail(&self, ctx: &Context<'_>) -> GraphQLResult<Option<InscriptionObject>> {
+    let index = index(ctx)?;
+
+    match index.get_relic_thumbnail(self.id)? {
+      Some(thumbnail) => Ok(inscription(index, thumbnail)?),
+      None => Ok(None),
+    }
+  }
+
+  async fn events(
+    &self,
+    ctx: &Context<'_>,
+    kind: Option<String>,
+    limit: Option<usize>,
+  ) -> GraphQLResult<Vec<EventObject>> {
+    events(
+      index(ctx)?,
+      &EventFilter {
+        kind: kind.map(|kind| parse_kind(&kind)).transpose()?,
+        relic_id: Some(self.id),
+        ..default()
+      },
+      limit,
+    )
+  }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "Pool")]
+pub(super) struct PoolObject {
+  base_supply: String,
+  quote_supply: String,
+  fee_bps: u16,
+  shares: String,
+}
+
+impl From<Pool> for PoolObject {
+  fn from(pool: Pool) -> Self {
+    Self {
+      base_supply: pool.base_supply.to_string(),
+      quote_supply: pool.quote_supply.to_string(),
+      fee_bps: pool.fee_bps,
+      shares: pool.shares.to_string(),
+    }
+  }
+}
+
+pub(super) struct InscriptionObject {
+  entry: InscriptionEntry,
+}
+
+#[Object(name = "Inscription")]
+impl InscriptionObject {
+  async fn id(&self) -> String {
+    self.entry.id.to_string()
+  }
+
+  async fn number(&self) -> u64 {
+    self.entry.inscription_number
+  }
+
+  async fn sequence_number(&self) -> u32 {
+    self.entry.sequence_number
+  }
+
+  async fn height(&self) -> u32 {
+    self.entry.height
+  }
+
+  async fn timestamp(&self) -> u32 {
+    self.entry.timestamp
+  }
+
+  async fn fee(&self) -> u64 {
+    self.entry.fee
+  }
+
+  async fn content_type(&self, ctx: &Context<'_>) -> GraphQLResult<Option<String>> {
+    Ok(
+      index(ctx)?
+        .get_inscription_by_id(self.entry.id)?
+        .and_then(|inscription| inscription.content_type().map(str::to_string)),
+    )
+  }
+
+  async fn satpoint(&self, ctx: &Context<'_>) -> GraphQLResult<Option<String>> {
+    Ok(
+      index(ctx)?
+        .get_inscription_satpoint_by_id(self.entry.id)?
+        .map(|satpoint| satpoint.to_string()),
+    )
+  }
+
+  /// The bone ticker sealed by this inscription, if any.
+  async fn bone_sealed(&self, ctx: &Context<'_>) -> GraphQLResult<Option<String>> {
+    Ok(
+      index(ctx)?
+        .inscription_relic_info(query::Inscription::Id(self.entry.id))?
+        .and_then(|info| info.relic_sealed)
+        .map(|spaced_relic| spaced_relic.to_string()),
+    )
+  }
+}
+
+pub(super) struct SyndicateObject {
+  id: SyndicateId,
+  entry: SyndicateEntry,
+  owner: Option<InscriptionId>,
+}
+
+#[Object(name = "Syndicate")]
+impl SyndicateObject {
+  async fn id(&self) -> String {
+    self.id.to_string()
+  }
+
+  async fn treasure(&self, ctx: &Context<'_>) -> GraphQLResult<Option<RelicObject>> {
+    Ok(relic(index(ctx)?, query::Relic::Id(self.entry.treasure))?)
+  }
+
+  async fn chests(&self) -> u32 {
+    self.entry.chests
+  }
+
+  async fn cap(&self) -> Option<u32> {
+    self.entry.cap
+  }
+
+  async fn quota(&self) -> String {
+    self.entry.quota.to_string()
+  }
+
+  async fn royalty(&self) -> String {
+    self.entry.royalty.to_string()
+  }
+
+  async fn gated(&self) -> bool {
+    self.entry.gated
+  }
+
+  async fn allowlist(&self) -> bool {
+    self.entry.allowlist
+  }
+
+  async fn holding_bone(&self) -> Option<String> {
+    self.entry.holding.map(|(id, _)| id.to_string())
+  }
+
+  async fn holding_amount(&self) -> Option<String> {
+    self.entry.holding.map(|(_, amount)| amount.to_string())
+  }
+
+  async fn lock(&self) -> Option<u64> {
+    self.entry.lock
+  }
+
+  async fn reward(&self) -> Option<String> {
+    self.entry.reward.map(|reward| reward.to_string())
+  }
+
+  async fn owner(&self, ctx: &Context<'_>) -> GraphQLResult<Option<InscriptionObject>> {
+    match self.owner {
+      Some(owner) => Ok(inscription(index(ctx)?, owner)?),
+      None => Ok(None),
+    }
+  }
+}
+
+pub(super) struct EventObject(Event);
+
+#[Object(name = "Event")]
+impl EventObject {
+  async fn block_height(&self) -> u32 {
+    self.0.block_height
+  }
+
+  async fn event_index(&self) -> u32 {
+    self.0.event_index
+  }
+
+  async fn txid(&self) -> String {
+    self.0.txid.to_string()
+  }
+
+  async fn kind(&self) -> String {
+    format!("{:?}", self.0.info.kind())
+  }
+
+  /// The event fields, serialized like the REST event endpoints do.
+  async fn info(&self) -> GraphQLJson<EventInfo> {
+    GraphQLJson(self.0.info.clone())
+  }
+
+  async fn bone(&self, ctx: &Context<'_>) -> GraphQLResult<Option<RelicObject>> {
+    match self.0.relic_id() {
+      Some(relic_id) => Ok(relic(index(ctx)?, query::Relic::Id(relic_id))?),
+      None => Ok(None),
+    }
+  }
+
+  async fn inscription(&self, ctx: &Context<'_>) -> GraphQLResult<Option<InscriptionObject>> {
+    match self.0.info {
+      EventInfo::InscriptionCreated { inscription_id, .. }
+      | EventInfo::InscriptionTransferred { inscription_id, .. } => {
+        Ok(inscription(index(ctx)?, inscription_id)?)
+      }
+      _ => Ok(None),
+    }
+  }
+}
@@ -0,0 +1,188 @@
+use {
+  super::*, axum::extract::FromRequest, clap::ValueEnum, serde_json::ser::Formatter, std::io::Write,
+};
+
+/// Header with which requests choose the `AmountFormat` of the response.
+pub(super) const AMOUNT_FORMAT_HEADER: &str = "x-amount-format";
+
+/// How `u128` values, which all relic amounts are, are written in JSON
+/// responses. JavaScript numbers lose precision beyond 2^53, so clients that
+/// parse responses with `JSON.parse` should ask for strings.
+#[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub(super) enum AmountFormat {
+  #[default]
+  Number,
+  String,
+}
+
+tokio::task_local! {
+  static AMOUNT_FORMAT: AmountFormat;
+}
+
+impl AmountFormat {
+  /// The format of the request being served, or the default outside of one.
+  /// Not available on blocking threads the request spawned, so handlers that
+  /// serialize there read it beforehand.
+  pub(super) fn current() -> Self {
+    AMOUNT_FORMAT.try_with(|format| *format).unwrap_or_default()
+  }
+
+  /// Serves requests with the format they ask for in the `X-Amount-Format`
+  /// header, or with the default format of the server.
+  pub(super) async fn middleware<B>(
+    Extension(default): Extension<AmountFormat>,
+    request: http::Request<B>,
+    next: Next<B>,
+  ) -> ServerResult<Response> {
+    let format = match request.headers().get(AMOUNT_FORMAT_HEADER) {
+      Some(value) => value
+        .to_str()
+        .ok()
+        .and_then(|value| AmountFormat::from_str(value, true).ok())
+        .ok_or_else(|| {
+          ServerError::BadRequest(format!(
+            "invalid {AMOUNT_FORMAT_HEADER} header, expected `number` or `string`"
+          ))
+        })?,
+      None => default,
+    };
+
+    Ok(AMOUNT_FORMAT.scope(format, next.run(request)).await)
+  }
+}
+
+/// Writes `u128` and `i128` values as strings. Map keys are strings already,
+/// so values written inside of them are left as they are.
+#[derive(Default)]
+struct StringAmounts {
+  in_string: bool,
+}
+
+impl Formatter for StringAmounts {
+  fn begin_string<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+    self.in_string = true;
+    writer.write_all(b"\"")
+  }
+
+  fn end_string<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+    self.in_string = false;
+    writer.write_all(b"\"")
+  }
+
+  fn write_u128<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: u128) -> io::Result<()> {
+    if self.in_string {
+      write!(writer, "{value}")
+    } else {
+      write!(writer, "\"{value}\"")
+    }
+  }
+
+  fn write_i128<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: i128) -> io::Result<()> {
+    if self.in_string {
+      write!(writer, "{value}")
+    } else {
+      write!(writer, "\"{value}\"")
+    }
+  }
+}
+
+/// Serializes `value` as JSON, writing amounts in `format`.
+pub(super) fn to_writer<W: io::Write, T: ?Sized + Serialize>(
+  writer: W,
+  value: &T,
+  format: AmountFormat,
+) -> serde_json::Result<()> {
+  match format {
+    AmountFormat::Number => serde_json::to_writer(writer, value),
+    AmountFormat::String => value.serialize(&mut serde_json::Serializer::with_formatter(
+      writer,
+      StringAmounts::default(),
+    )),
+  }
+}
+
+pub(super) fn to_vec<T: ?Sized + Serialize>(
+  value: &T,
+  format: AmountFormat,
+) -> serde_json::Result<Vec<u8>> {
+  let mut json = Vec::new();
+  to_writer(&mut json, value, format)?;
+  Ok(json)
+}
+
+/// Serializes `value` as JSON, writing amounts in the format of the request
+/// being served.
+pub(super) fn to_string<T: ?Sized + Serialize>(value: &T) -> serde_json::Result<String> {
+  Ok(String::from_utf8(to_vec(value, AmountFormat::current())?).expect("JSON is valid UTF-8"))
+}
+
+/// Like `axum::Json`, but responses write amounts in the format of the
+/// request being served.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct Json<T>(pub(super) T);
+
+impl<T: Serialize> IntoResponse for Json<T> {
+  fn into_response(self) -> Response {
+    match to_vec(&self.0, AmountFormat::current()) {
+      Ok(json) => (
+        [(
+          header::CONTENT_TYPE,
+          HeaderValue::from_static("application/json"),
+        )],
+        json,
+      )
+        .into_response(),
+      Err(err) => ServerError::Internal(err.into()).into_response(),
+    }
+  }
+}
+
+#[async_trait::async_trait]
+impl<T, S, B> FromRequest<S, B> for Json<T>
+where
+  axum::Json<T>: FromRequest<S, B>,
+  S: Send + Sync,
+  B: Send + 'static,
+{
+  type Rejection = <axum::Json<T> as FromRequest<S, B>>::Rejection;
+
+  async fn from_request(request: http::Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+    let axum::Json(value) = axum::Json::<T>::from_request(request, state).await?;
+    Ok(Self(value))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Debug, PartialEq, Serialize, Deserialize)]
+  struct Balance {
+    amount: u128,
+    value: u64,
+    by_amount: BTreeMap<u128, String>,
+  }
+
+  #[test]
+  fn amounts_round_trip_in_both_formats() {
+    let balance = Balance {
+      amount: u128::MAX,
+      value: 1,
+      by_amount: [(2u128.pow(53) + 1, "foo".into())].into(),
+    };
+
+    let numbers = String::from_utf8(to_vec(&balance, AmountFormat::Number).unwrap()).unwrap();
+    assert_eq!(
+      numbers,
+      r#"{"amount":340282366920938463463374607431768211455,"value":1,"by_amount":{"9007199254740993":"foo"}}"#
+    );
+
+    let strings = String::from_utf8(to_vec(&balance, AmountFormat::String).unwrap()).unwrap();
+    assert_eq!(
+      strings,
+      r#"{"amount":"340282366920938463463374607431768211455","value":1,"by_amount":{"9007199254740993":"foo"}}"#
+    );
+
+    assert_eq!(serde_json::from_str::<Balance>(&numbers).unwrap(), balance);
+  }
+}
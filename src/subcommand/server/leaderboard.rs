@@ -0,0 +1,108 @@
+use {
+  super::*,
+  crate::index::relics_entry::{ActivityMetric, RelicActivity},
+  std::sync::Mutex,
+};
+
+/// Relics listed on a leaderboard.
+const LEADERBOARD_SIZE: usize = 100;
+
+type Leaderboard = Arc<Vec<(RelicId, RelicActivity)>>;
+
+/// Leaderboards computed at the current chain tip. Leaderboards of a window
+/// only change when a block is indexed or reorged away, so each is computed
+/// once per tip and the cache is cleared when the tip changes. The tip is
+/// identified by its hash, since a reorg can replace it with a block at the
+/// same height.
+pub(crate) struct Leaderboards {
+  cache: Mutex<(
+    Option<BlockHash>,
+    HashMap<(ActivityMetric, u32), Leaderboard>,
+  )>,
+}
+
+impl Leaderboards {
+  pub(crate) fn new() -> Self {
+    Self {
+      cache: Mutex::new((None, HashMap::new())),
+    }
+  }
+
+  /// The leaderboard of `metric` over the `window` blocks up to and including
+  /// the tip `block_hash` at `height`.
+  pub(crate) fn get(
+    &self,
+    index: &Index,
+    metric: ActivityMetric,
+    window: u32,
+    height: u32,
+    block_hash: BlockHash,
+  ) -> Result<Leaderboard> {
+    {
+      let cache = self.cache.lock().unwrap();
+      if cache.0 == Some(block_hash) {
+        if let Some(leaderboard) = cache.1.get(&(metric, window)) {
+          return Ok(leaderboard.clone());
+        }
+      }
+    }
+
+    let from = (height + 1).saturating_sub(window);
+
+    let leaderboard =
+      Arc::new(index.get_relic_leaderboard(metric, from, height, LEADERBOARD_SIZE)?);
+
+    let mut cache = self.cache.lock().unwrap();
+
+    if cache.0 != Some(block_hash) {
+      *cache = (Some(block_hash), HashMap::new());
+    }
+
+    cache.1.insert((metric, window), leaderboard.clone());
+
+    Ok(leaderboard)
+  }
+
+  #[cfg(test)]
+  fn cached(&self) -> (Option<BlockHash>, usize) {
+    let cache = self.cache.lock().unwrap();
+    (cache.0, cache.1.len())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  #[test]
+  fn leaderboards_are_cached_per_tip() {
+    let context = Context::builder().arg("--index-relics").build();
+
+    let leaderboards = Leaderboards::new();
+
+    assert_eq!(
+      *leaderboards
+        .get(&context.index, ActivityMetric::Volume, 10, 5, blockhash(1))
+        .unwrap(),
+      Vec::new()
+    );
+    leaderboards
+      .get(&context.index, ActivityMetric::Fees, 10, 5, blockhash(1))
+      .unwrap();
+    leaderboards
+      .get(&context.index, ActivityMetric::Fees, 10, 5, blockhash(1))
+      .unwrap();
+    assert_eq!(leaderboards.cached(), (Some(blockhash(1)), 2));
+
+    leaderboards
+      .get(&context.index, ActivityMetric::Fees, 10, 6, blockhash(2))
+      .unwrap();
+    assert_eq!(leaderboards.cached(), (Some(blockhash(2)), 1));
+
+    // a reorg replaced the tip with another block at the same height
+    leaderboards
+      .get(&context.index, ActivityMetric::Fees, 10, 6, blockhash(3))
+      .unwrap();
+    assert_eq!(leaderboards.cached(), (Some(blockhash(3)), 1));
+  }
+}
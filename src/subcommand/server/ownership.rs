@@ -0,0 +1,309 @@
+use {
+  super::*,
+  bitcoin::{
+    consensus::Encodable,
+    hashes::{
+      hmac::{Hmac, HmacEngine},
+      sha256, sha256d, Hash, HashEngine,
+    },
+    secp256k1::{
+      ecdsa::{RecoverableSignature, RecoveryId},
+      rand::{self, RngCore},
+      Message, Secp256k1,
+    },
+    PublicKey, VarInt,
+  },
+  std::sync::Mutex,
+};
+
+/// How long a challenge can be answered.
+const CHALLENGE_SECONDS: u64 = 5 * 60;
+
+/// How long an attestation token stays valid.
+const ATTESTATION_SECONDS: u64 = 60 * 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Challenge {
+  #[serde(rename = "bone")]
+  pub(crate) spaced_relic: SpacedRelic,
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) address: Address,
+  pub(crate) nonce: String,
+  /// the text to sign with `signmessage`
+  pub(crate) message: String,
+  pub(crate) expires: u64,
+}
+
+/// Statement that `address` controlled the owner inscription of a bone at
+/// `issued`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Attestation {
+  #[serde(rename = "bone")]
+  pub(crate) spaced_relic: SpacedRelic,
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) address: Address,
+  pub(crate) issued: u64,
+  pub(crate) expires: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Proof {
+  pub(crate) token: String,
+  #[serde(flatten)]
+  pub(crate) attestation: Attestation,
+}
+
+/// Issues ownership challenges and signs attestations with a key generated at
+/// startup, so tokens and pending challenges don't survive a restart.
+pub(crate) struct Ownership {
+  key: [u8; 32],
+  challenges: Mutex<HashMap<String, Challenge>>,
+}
+
+impl Ownership {
+  pub(crate) fn new() -> Self {
+    let mut key = [0; 32];
+    rand::thread_rng().fill_bytes(&mut key);
+
+    Self {
+      key,
+      challenges: Mutex::new(HashMap::new()),
+    }
+  }
+
+  pub(crate) fn challenge(
+    &self,
+    spaced_relic: SpacedRelic,
+    inscription_id: InscriptionId,
+    address: &Address,
+    now: u64,
+  ) -> Challenge {
+    let mut nonce = [0; 16];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let nonce = hex::encode(nonce);
+
+    let challenge = Challenge {
+      spaced_relic,
+      inscription_id,
+      address: address.clone(),
+      message: format!("I control {spaced_relic} via {inscription_id}. Nonce: {nonce}"),
+      nonce: nonce.clone(),
+      expires: now + CHALLENGE_SECONDS,
+    };
+
+    let mut challenges = self.challenges.lock().unwrap();
+    challenges.retain(|_, challenge| challenge.expires > now);
+    challenges.insert(nonce, challenge.clone());
+
+    challenge
+  }
+
+  /// Consumes the challenge for `nonce` and checks that `signature` signs its
+  /// message with the key of `address`, the current owner address.
+  pub(super) fn attest(
+    &self,
+    nonce: &str,
+    signature: &str,
+    address: &Address,
+    now: u64,
+  ) -> ServerResult<Proof> {
+    let challenge = self
+      .challenges
+      .lock()
+      .unwrap()
+      .remove(nonce)
+      .filter(|challenge| challenge.expires > now)
+      .ok_or_else(|| ServerError::BadRequest("unknown or expired nonce".into()))?;
+
+    if challenge.address != *address {
+      return Err(ServerError::Unauthorized(
+        "owner inscription moved since the challenge was issued".into(),
+      ));
+    }
+
+    if !verify_message(address, &challenge.message, signature)
+      .map_err(|err| ServerError::BadRequest(format!("invalid signature: {err}")))?
+    {
+      return Err(ServerError::Unauthorized(format!(
+        "signature was not made by {address}"
+      )));
+    }
+
+    let attestation = Attestation {
+      spaced_relic: challenge.spaced_relic,
+      inscription_id: challenge.inscription_id,
+      address: challenge.address,
+      issued: now,
+      expires: now + ATTESTATION_SECONDS,
+    };
+
+    Ok(Proof {
+      token: self.sign(&attestation)?,
+      attestation,
+    })
+  }
+
+  /// Returns the attestation in `token` if it was issued by this server and
+  /// has not expired.
+  pub(crate) fn verify(&self, token: &str, now: u64) -> Option<Attestation> {
+    let (payload, mac) = token.split_once('.')?;
+
+    if self.mac(payload.as_bytes()).to_string() != mac {
+      return None;
+    }
+
+    let attestation = serde_json::from_slice::<Attestation>(
+      &base64::decode_config(payload, base64::URL_SAFE_NO_PAD).ok()?,
+    )
+    .ok()?;
+
+    (attestation.expires > now).then_some(attestation)
+  }
+
+  fn sign(&self, attestation: &Attestation) -> Result<String> {
+    let payload = base64::encode_config(serde_json::to_vec(attestation)?, base64::URL_SAFE_NO_PAD);
+    let mac = self.mac(payload.as_bytes());
+    Ok(format!("{payload}.{mac}"))
+  }
+
+  fn mac(&self, payload: &[u8]) -> Hmac<sha256::Hash> {
+    let mut engine = HmacEngine::<sha256::Hash>::new(&self.key);
+    engine.input(payload);
+    Hmac::from_engine(engine)
+  }
+}
+
+/// Hash signed by Dogecoin Core's `signmessage`.
+fn signed_message_hash(message: &str) -> sha256d::Hash {
+  let mut engine = sha256d::Hash::engine();
+  engine.input(b"\x19Dogecoin Signed Message:\n");
+  VarInt(message.len() as u64)
+    .consensus_encode(&mut engine)
+    .unwrap();
+  engine.input(message.as_bytes());
+  sha256d::Hash::from_engine(engine)
+}
+
+/// Checks a base64 compact signature, as produced by `signmessage`, against a
+/// P2PKH address.
+fn verify_message(address: &Address, message: &str, signature: &str) -> Result<bool> {
+  let signature = base64::decode(signature)?;
+
+  ensure!(signature.len() == 65, "expected 65 bytes");
+
+  let header = signature[0];
+
+  ensure!((27..=34).contains(&header), "invalid header byte {header}");
+
+  let signature = RecoverableSignature::from_compact(
+    &signature[1..],
+    RecoveryId::from_i32(i32::from((header - 27) & 3))?,
+  )?;
+
+  let public_key = PublicKey {
+    compressed: header >= 31,
+    inner: Secp256k1::verification_only().recover_ecdsa(
+      &Message::from_slice(&signed_message_hash(message).into_inner())?,
+      &signature,
+    )?,
+  };
+
+  Ok(Address::p2pkh(&public_key, address.network) == *address)
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, bitcoin::secp256k1::SecretKey};
+
+  fn sign_message(secret_key: &SecretKey, message: &str) -> String {
+    let (recovery_id, signature) = Secp256k1::new()
+      .sign_ecdsa_recoverable(
+        &Message::from_slice(&signed_message_hash(message).into_inner()).unwrap(),
+        secret_key,
+      )
+      .serialize_compact();
+
+    let mut bytes = vec![31 + u8::try_from(recovery_id.to_i32()).unwrap()];
+    bytes.extend_from_slice(&signature);
+    base64::encode(bytes)
+  }
+
+  fn address(secret_key: &SecretKey) -> Address {
+    Address::p2pkh(
+      &PublicKey::new(secret_key.public_key(&Secp256k1::new())),
+      Network::Bitcoin,
+    )
+  }
+
+  #[test]
+  fn challenge_and_attest() {
+    let ownership = Ownership::new();
+
+    let secret_key = SecretKey::from_slice(&[1; 32]).unwrap();
+    let address = address(&secret_key);
+
+    let challenge =
+      ownership.challenge("FOO•BAR".parse().unwrap(), inscription_id(1), &address, 100);
+
+    let signature = sign_message(&secret_key, &challenge.message);
+
+    let proof = ownership
+      .attest(&challenge.nonce, &signature, &address, 101)
+      .unwrap();
+
+    assert_eq!(proof.attestation.address, address);
+    assert_eq!(
+      ownership.verify(&proof.token, 102),
+      Some(proof.attestation.clone())
+    );
+    assert_eq!(
+      ownership.verify(&proof.token, proof.attestation.expires),
+      None
+    );
+    assert_eq!(ownership.verify(&format!("{}0", proof.token), 102), None);
+
+    assert!(matches!(
+      ownership.attest(&challenge.nonce, &signature, &address, 101),
+      Err(ServerError::BadRequest(_))
+    ));
+  }
+
+  #[test]
+  fn signatures_from_other_keys_are_rejected() {
+    let ownership = Ownership::new();
+
+    let secret_key = SecretKey::from_slice(&[1; 32]).unwrap();
+    let address = address(&secret_key);
+
+    let challenge =
+      ownership.challenge("FOO•BAR".parse().unwrap(), inscription_id(1), &address, 100);
+
+    let signature = sign_message(
+      &SecretKey::from_slice(&[2; 32]).unwrap(),
+      &challenge.message,
+    );
+
+    assert!(matches!(
+      ownership.attest(&challenge.nonce, &signature, &address, 101),
+      Err(ServerError::Unauthorized(_))
+    ));
+  }
+
+  #[test]
+  fn expired_challenges_are_rejected() {
+    let ownership = Ownership::new();
+
+    let secret_key = SecretKey::from_slice(&[1; 32]).unwrap();
+    let address = address(&secret_key);
+
+    let challenge =
+      ownership.challenge("FOO•BAR".parse().unwrap(), inscription_id(1), &address, 100);
+
+    let signature = sign_message(&secret_key, &challenge.message);
+
+    assert!(matches!(
+      ownership.attest(&challenge.nonce, &signature, &address, challenge.expires),
+      Err(ServerError::BadRequest(_))
+    ));
+  }
+}
@@ -46,7 +46,7 @@ impl Display for Inscription {
 }
 
 #[derive(Debug)]
-pub(super) enum Relic {
+pub(crate) enum Relic {
   Spaced(SpacedRelic),
   Id(RelicId),
   Number(u64),
@@ -0,0 +1,259 @@
+use {
+  super::*,
+  bitcoin::secp256k1::rand::{self, RngCore},
+};
+
+/// Seconds after its last renewal that a writer lease can be taken over.
+const LEASE_SECONDS: u64 = 30;
+
+const LEASE_PREFIX: &str = "writer.lease.";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Lease {
+  pub(crate) holder: String,
+  pub(crate) epoch: u64,
+  pub(crate) renewed: u64,
+}
+
+/// Decides which of the servers keeping their index in the same directory may
+/// update it.
+///
+/// Every server needs its own index file, since an index can only be opened
+/// by one process, so the lease fences the servers of a directory rather than
+/// a single file. Each takeover creates the lease file of the next epoch,
+/// `writer.lease.<EPOCH>`, which fails if another server created it first, so
+/// only one server wins an expired lease. The writer renews its epoch's file,
+/// and steps down once a later epoch exists.
+///
+/// A standby is cold: it serves its copy of the index as it was when copied,
+/// and only follows the chain once it has taken over the lease.
+pub(crate) struct Standby {
+  dir: PathBuf,
+  holder: String,
+  /// epoch of the lease this server holds, while it is the writer
+  epoch: Mutex<Option<u64>>,
+}
+
+impl Standby {
+  pub(crate) fn new(index_path: &Path) -> Self {
+    let mut nonce = [0; 8];
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    Self {
+      dir: index_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+        .into(),
+      holder: format!("{}-{}", process::id(), hex::encode(nonce)),
+      epoch: Mutex::new(None),
+    }
+  }
+
+  pub(crate) fn holder(&self) -> &str {
+    &self.holder
+  }
+
+  pub(crate) fn is_writer(&self) -> bool {
+    self.epoch.lock().unwrap().is_some()
+  }
+
+  /// Takes over the lease and becomes the writer, unless another server
+  /// renewed it less than `LEASE_SECONDS` ago.
+  pub(crate) fn promote(&self, now: u64) -> Result<Result<(), Lease>> {
+    let result = self.acquire(now)?;
+    *self.epoch.lock().unwrap() = result.as_ref().ok().copied();
+    Ok(result.map(|_| ()))
+  }
+
+  /// Renews the lease while this server is the writer, and steps down if
+  /// another server has taken it over in the meantime.
+  pub(crate) fn heartbeat(&self, now: u64) -> Result {
+    if !self.is_writer() {
+      return Ok(());
+    }
+
+    if let Err(lease) = self.acquire(now)? {
+      log::warn!(
+        "index lease in `{}` taken over by {} in epoch {}, stopping index updates",
+        self.dir.display(),
+        lease.holder,
+        lease.epoch,
+      );
+      *self.epoch.lock().unwrap() = None;
+    }
+
+    Ok(())
+  }
+
+  fn path(&self, epoch: u64) -> PathBuf {
+    self.dir.join(format!("{LEASE_PREFIX}{epoch}"))
+  }
+
+  /// The lease of the latest epoch, if any server ever held one.
+  fn latest(&self) -> Result<Option<Lease>> {
+    let mut latest = None;
+
+    for entry in fs::read_dir(&self.dir)? {
+      let epoch = entry?
+        .file_name()
+        .to_str()
+        .and_then(|name| name.strip_prefix(LEASE_PREFIX))
+        .and_then(|epoch| epoch.parse::<u64>().ok());
+
+      if epoch > latest {
+        latest = epoch;
+      }
+    }
+
+    let Some(epoch) = latest else {
+      return Ok(None);
+    };
+
+    let path = self.path(epoch);
+
+    Ok(Some(
+      serde_json::from_slice(&fs::read(&path)?)
+        .with_context(|| format!("invalid index lease `{}`", path.display()))?,
+    ))
+  }
+
+  /// Writes the lease to a temporary file, so it is complete once it appears
+  /// under its final name.
+  fn write_partial(&self, epoch: u64, now: u64) -> Result<PathBuf> {
+    let partial = self
+      .dir
+      .join(format!("{LEASE_PREFIX}partial.{}", self.holder));
+
+    fs::write(
+      &partial,
+      serde_json::to_vec(&Lease {
+        holder: self.holder.clone(),
+        epoch,
+        renewed: now,
+      })?,
+    )?;
+
+    Ok(partial)
+  }
+
+  /// Renews or takes over the lease, returning its epoch, or the live lease of
+  /// another server.
+  fn acquire(&self, now: u64) -> Result<Result<u64, Lease>> {
+    loop {
+      let epoch = match self.latest()? {
+        Some(lease) if lease.holder == self.holder => {
+          // only the holder writes to the file of its epoch
+          let partial = self.write_partial(lease.epoch, now)?;
+          fs::rename(partial, self.path(lease.epoch))?;
+          return Ok(Ok(lease.epoch));
+        }
+        Some(lease) if now < lease.renewed + LEASE_SECONDS => return Ok(Err(lease)),
+        Some(lease) => lease.epoch + 1,
+        None => 0,
+      };
+
+      // linking fails if the file exists, so of the servers taking over the
+      // same expired lease, only the first one gets the new epoch
+      let partial = self.write_partial(epoch, now)?;
+      let result = fs::hard_link(&partial, self.path(epoch));
+      fs::remove_file(&partial)?;
+
+      match result {
+        Ok(()) => {
+          for old in 0..epoch {
+            fs::remove_file(self.path(old)).ok();
+          }
+          return Ok(Ok(epoch));
+        }
+        // another server won this epoch, its lease is live
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+        Err(err) => return Err(err.into()),
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, std::sync::Barrier};
+
+  #[test]
+  fn only_one_server_holds_the_lease() {
+    let tempdir = TempDir::new().unwrap();
+    let index_path = tempdir.path().join("index.redb");
+
+    let leader = Standby::new(&index_path);
+    let standby = Standby::new(&index_path);
+
+    assert!(leader.promote(100).unwrap().is_ok());
+    assert!(leader.is_writer());
+
+    assert_eq!(
+      standby.promote(110).unwrap(),
+      Err(Lease {
+        holder: leader.holder().into(),
+        epoch: 0,
+        renewed: 100,
+      })
+    );
+    assert!(!standby.is_writer());
+
+    leader.heartbeat(120).unwrap();
+    assert!(standby.promote(120 + LEASE_SECONDS - 1).unwrap().is_err());
+
+    assert!(standby.promote(120 + LEASE_SECONDS).unwrap().is_ok());
+    assert!(standby.is_writer());
+
+    leader.heartbeat(121 + LEASE_SECONDS).unwrap();
+    assert!(!leader.is_writer());
+  }
+
+  #[test]
+  fn concurrent_promotions_elect_one_writer() {
+    let tempdir = TempDir::new().unwrap();
+    let index_path = tempdir.path().join("index.redb");
+
+    let leader = Standby::new(&index_path);
+    assert!(leader.promote(100).unwrap().is_ok());
+
+    let standbys = (0..8)
+      .map(|_| Arc::new(Standby::new(&index_path)))
+      .collect::<Vec<_>>();
+
+    let barrier = Arc::new(Barrier::new(standbys.len()));
+
+    let threads = standbys
+      .iter()
+      .map(|standby| {
+        let standby = standby.clone();
+        let barrier = barrier.clone();
+        thread::spawn(move || {
+          barrier.wait();
+          standby.promote(100 + LEASE_SECONDS).unwrap().is_ok()
+        })
+      })
+      .collect::<Vec<_>>();
+
+    let promoted = threads
+      .into_iter()
+      .map(|thread| thread.join().unwrap())
+      .filter(|promoted| *promoted)
+      .count();
+
+    assert_eq!(promoted, 1);
+    assert_eq!(
+      standbys
+        .iter()
+        .filter(|standby| standby.is_writer())
+        .count(),
+      1
+    );
+
+    leader.heartbeat(100 + LEASE_SECONDS).unwrap();
+    assert!(!leader.is_writer());
+
+    let writer = standbys.iter().find(|standby| standby.is_writer()).unwrap();
+    assert_eq!(writer.latest().unwrap().unwrap().epoch, 1);
+  }
+}
@@ -0,0 +1,181 @@
+use {
+  super::*,
+  std::{io::Write, mem},
+};
+
+/// Bodies are serialized in chunks of this size. A body that fits in a single
+/// chunk is sent with a content length, larger ones are streamed with chunked
+/// encoding, so the whole body is never held in memory.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Chunks serialized ahead of the client before serialization waits.
+const CHUNKS_IN_FLIGHT: usize = 4;
+
+/// Array elements serialized in parallel at a time.
+const BATCH_SIZE: usize = 64;
+
+enum Chunk {
+  More(Vec<u8>),
+  Last(Vec<u8>),
+}
+
+/// Sends every full chunk written to it down a channel.
+struct ChunkWriter {
+  buffer: Vec<u8>,
+  sender: mpsc::Sender<Result<Chunk, io::Error>>,
+}
+
+impl Write for ChunkWriter {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self.buffer.extend_from_slice(buf);
+
+    if self.buffer.len() >= CHUNK_SIZE {
+      let chunk = mem::replace(&mut self.buffer, Vec::with_capacity(CHUNK_SIZE));
+      self
+        .sender
+        .blocking_send(Ok(Chunk::More(chunk)))
+        .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "client disconnected"))?;
+    }
+
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+/// Serializes `value` as JSON on a blocking thread.
+pub(super) async fn json<T: Serialize + Send + 'static>(value: T) -> ServerResult<Response> {
+  let format = AmountFormat::current();
+  respond("application/json", move |writer| {
+    json::to_writer(writer, &value, format)?;
+    Ok(())
+  })
+  .await
+}
+
+/// Serializes `items` as a JSON array, serializing batches of elements in
+/// parallel.
+pub(super) async fn json_array<T: Serialize + Send + Sync + 'static>(
+  items: Vec<T>,
+) -> ServerResult<Response> {
+  let format = AmountFormat::current();
+  respond("application/json", move |writer| {
+    writer.write_all(b"[")?;
+
+    for (i, batch) in items.chunks(BATCH_SIZE).enumerate() {
+      let elements = batch
+        .par_iter()
+        .map(|item| json::to_vec(item, format))
+        .collect::<serde_json::Result<Vec<Vec<u8>>>>()?;
+
+      for (j, element) in elements.iter().enumerate() {
+        if i > 0 || j > 0 {
+          writer.write_all(b",")?;
+        }
+        writer.write_all(element)?;
+      }
+    }
+
+    writer.write_all(b"]")?;
+
+    Ok(())
+  })
+  .await
+}
+
+/// Renders `page` on a blocking thread.
+pub(super) async fn html<T: Display + Send + 'static>(page: T) -> ServerResult<Response> {
+  respond("text/html; charset=utf-8", move |writer| {
+    write!(writer, "{page}")?;
+    Ok(())
+  })
+  .await
+}
+
+async fn respond(
+  content_type: &'static str,
+  write: impl FnOnce(&mut ChunkWriter) -> Result + Send + 'static,
+) -> ServerResult<Response> {
+  let (sender, mut receiver) = mpsc::channel(CHUNKS_IN_FLIGHT);
+
+  task::spawn_blocking(move || {
+    let mut writer = ChunkWriter {
+      buffer: Vec::with_capacity(CHUNK_SIZE),
+      sender: sender.clone(),
+    };
+
+    let result = match write(&mut writer) {
+      Ok(()) => Ok(Chunk::Last(writer.buffer)),
+      Err(err) => Err(io::Error::new(io::ErrorKind::Other, err.to_string())),
+    };
+
+    // fails only if the client disconnected
+    sender.blocking_send(result).ok();
+  });
+
+  let first = match receiver.recv().await {
+    Some(Ok(Chunk::Last(body))) => {
+      return Ok(([(header::CONTENT_TYPE, content_type)], body).into_response());
+    }
+    Some(Ok(Chunk::More(chunk))) => chunk,
+    Some(Err(err)) => return Err(ServerError::Internal(err.into())),
+    None => {
+      return Err(ServerError::Internal(anyhow!(
+        "response serialization stopped"
+      )))
+    }
+  };
+
+  // an error after the first chunk aborts the response
+  let rest = futures::stream::unfold(Some(receiver), |receiver| async move {
+    let mut receiver = receiver?;
+    match receiver.recv().await? {
+      Ok(Chunk::More(chunk)) => Some((Ok(chunk), Some(receiver))),
+      Ok(Chunk::Last(chunk)) => Some((Ok(chunk), None)),
+      Err(err) => Some((Err(err), None)),
+    }
+  });
+
+  Ok(
+    (
+      [(header::CONTENT_TYPE, content_type)],
+      body::StreamBody::new(futures::stream::once(async { Ok(first) }).chain(rest)),
+    )
+      .into_response(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn body<T: Serialize + Send + Sync + 'static>(items: Vec<T>) -> (Option<u64>, Vec<u8>) {
+    Runtime::new().unwrap().block_on(async {
+      let response = json_array(items).await.unwrap();
+      let size = body::HttpBody::size_hint(response.body()).exact();
+      let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+      (size, body.to_vec())
+    })
+  }
+
+  #[test]
+  fn small_bodies_are_sent_whole() {
+    let (size, body) = body(vec![1, 2, 3]);
+    assert_eq!(body, b"[1,2,3]");
+    assert_eq!(size, Some(7));
+  }
+
+  #[test]
+  fn large_bodies_are_streamed() {
+    let items = (0..100_000)
+      .map(|i| format!("item {i}"))
+      .collect::<Vec<String>>();
+
+    let (size, body) = body(items.clone());
+
+    assert_eq!(size, None);
+    assert_eq!(serde_json::from_slice::<Vec<String>>(&body).unwrap(), items);
+  }
+}
@@ -0,0 +1,343 @@
+use {super::*, crate::relics::Relic, std::sync::mpsc};
+
+/// Events waiting for delivery to a single webhook. Further events for the
+/// webhook are dropped while its queue is full.
+const QUEUE_SIZE: usize = 1024;
+
+/// Delivery attempts per event, with exponential backoff in between.
+const ATTEMPTS: u32 = 5;
+
+/// Registered webhooks and their delivery queues.
+///
+/// Registrations are stored in a JSON file next to the index instead of in the
+/// index database, so restoring a reorg savepoint doesn't drop them and
+/// registering doesn't wait for the indexer to commit. Every webhook is served
+/// by its own thread from a bounded queue, so a slow or unreachable endpoint
+/// only delays its own events.
+pub(crate) struct Webhooks {
+  path: PathBuf,
+  state: Mutex<State>,
+}
+
+struct State {
+  registry: Registry,
+  queues: BTreeMap<u64, mpsc::SyncSender<Event>>,
+}
+
+/// Contents of the webhooks file. `next_id` only ever grows, so the id of a
+/// removed webhook is never handed out again.
+#[derive(Default, Serialize, Deserialize)]
+struct Registry {
+  next_id: u64,
+  webhooks: BTreeMap<u64, Webhook>,
+}
+
+impl Webhooks {
+  /// Loads the webhooks registered for the index at `index_path`.
+  pub(crate) fn load(index_path: &Path) -> Result<Self> {
+    let path = index_path.with_extension("webhooks.json");
+
+    let registry = match fs::read(&path) {
+      Ok(contents) => serde_json::from_slice::<Registry>(&contents)
+        .or_else(|_| {
+          // files written before ids were tracked only contain the webhooks
+          serde_json::from_slice::<BTreeMap<u64, Webhook>>(&contents).map(|webhooks| Registry {
+            next_id: webhooks
+              .last_key_value()
+              .map(|(id, _)| id + 1)
+              .unwrap_or_default(),
+            webhooks,
+          })
+        })
+        .with_context(|| format!("invalid webhooks file `{}`", path.display()))?,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => Registry::default(),
+      Err(err) => return Err(err.into()),
+    };
+
+    Ok(Self {
+      path,
+      state: Mutex::new(State {
+        registry,
+        queues: BTreeMap::new(),
+      }),
+    })
+  }
+
+  pub(crate) fn list(&self) -> Vec<(u64, Webhook)> {
+    self
+      .state
+      .lock()
+      .unwrap()
+      .registry
+      .webhooks
+      .iter()
+      .map(|(id, webhook)| (*id, webhook.clone()))
+      .collect()
+  }
+
+  /// Registers `webhook` and returns its id.
+  pub(crate) fn insert(&self, webhook: Webhook) -> Result<u64> {
+    let mut state = self.state.lock().unwrap();
+    let registry = &mut state.registry;
+
+    let id = registry.next_id;
+
+    registry.next_id += 1;
+    registry.webhooks.insert(id, webhook);
+
+    if let Err(err) = self.save(registry) {
+      registry.next_id -= 1;
+      registry.webhooks.remove(&id);
+      return Err(err);
+    }
+
+    Ok(id)
+  }
+
+  /// Returns false if there is no webhook with `id`. Events already queued
+  /// for the webhook are still delivered.
+  pub(crate) fn remove(&self, id: u64) -> Result<bool> {
+    let mut state = self.state.lock().unwrap();
+
+    let Some(webhook) = state.registry.webhooks.remove(&id) else {
+      return Ok(false);
+    };
+
+    if let Err(err) = self.save(&state.registry) {
+      state.registry.webhooks.insert(id, webhook);
+      return Err(err);
+    }
+
+    state.queues.remove(&id);
+
+    Ok(true)
+  }
+
+  fn save(&self, registry: &Registry) -> Result {
+    // write to a temporary file first, so a crash never leaves a truncated file
+    let tmp = self.path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(registry)?)?;
+    fs::rename(&tmp, &self.path)?;
+    Ok(())
+  }
+
+  /// Queues `event` for every webhook it matches and returns the number of
+  /// webhooks it was queued for.
+  pub(crate) fn dispatch(&self, event: &Event, relic: Option<Relic>) -> usize {
+    let mut state = self.state.lock().unwrap();
+    let State { registry, queues } = &mut *state;
+
+    let mut queued = 0;
+
+    for (id, webhook) in registry.webhooks.iter() {
+      if !webhook.matches(event, relic) {
+        continue;
+      }
+
+      let queue = queues
+        .entry(*id)
+        .or_insert_with(|| Self::spawn_worker(*id, webhook.url.clone()));
+
+      match queue.try_send(event.clone()) {
+        Ok(()) => queued += 1,
+        Err(mpsc::TrySendError::Full(_)) => {
+          log::warn!("webhook {id} delivery queue is full, dropping event");
+        }
+        Err(mpsc::TrySendError::Disconnected(_)) => {
+          log::warn!("webhook {id} delivery thread stopped, dropping event");
+          queues.remove(id);
+        }
+      }
+    }
+
+    queued
+  }
+
+  /// Queues committed events for delivery until `events` is closed.
+  pub(crate) fn send(&self, index: &Index, mut events: broadcast::Receiver<Event>) {
+    loop {
+      let event = match events.blocking_recv() {
+        Ok(event) => event,
+        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+          log::warn!("webhook delivery lagged behind by {skipped} events");
+          continue;
+        }
+        Err(broadcast::error::RecvError::Closed) => break,
+      };
+
+      if self.state.lock().unwrap().registry.webhooks.is_empty() {
+        continue;
+      }
+
+      let relic = event
+        .relic_id()
+        .and_then(|id| index.get_relic_by_id(id).ok().flatten());
+
+      self.dispatch(&event, relic);
+    }
+  }
+
+  fn spawn_worker(id: u64, url: String) -> mpsc::SyncSender<Event> {
+    let (sender, receiver) = mpsc::sync_channel::<Event>(QUEUE_SIZE);
+
+    thread::spawn(move || {
+      let client = reqwest::blocking::Client::new();
+
+      for event in receiver {
+        Self::deliver(&client, id, &url, &event);
+      }
+    });
+
+    sender
+  }
+
+  /// POST `event` to `url`, retrying with exponential backoff. Returns
+  /// whether the event was delivered.
+  fn deliver(client: &reqwest::blocking::Client, id: u64, url: &str, event: &Event) -> bool {
+    let mut backoff = Duration::from_millis(500);
+
+    for attempt in 1..=ATTEMPTS {
+      let result = client
+        .post(url)
+        .json(event)
+        .send()
+        .and_then(|response| response.error_for_status());
+
+      match result {
+        Ok(_) => return true,
+        Err(error) => {
+          log::warn!("webhook {id} delivery attempt {attempt}/{ATTEMPTS} failed: {error}");
+          if attempt < ATTEMPTS {
+            thread::sleep(backoff);
+            backoff *= 2;
+          }
+        }
+      }
+    }
+
+    false
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::webhook::Watch};
+
+  fn event() -> Event {
+    Event {
+      block_height: 1,
+      event_index: 0,
+      txid: Txid::all_zeros(),
+      info: EventInfo::RelicMinted {
+        relic_id: RelicId { block: 1, tx: 0 },
+        amount: 1,
+      },
+    }
+  }
+
+  fn webhook(url: String, relic: &str) -> Webhook {
+    Webhook {
+      url,
+      watch: Watch::Relic(relic.parse().unwrap()),
+    }
+  }
+
+  #[test]
+  fn webhooks_are_persisted() {
+    let tempdir = TempDir::new().unwrap();
+    let index_path = tempdir.path().join("index.redb");
+
+    let webhooks = Webhooks::load(&index_path).unwrap();
+    assert_eq!(webhooks.list(), Vec::new());
+
+    let foo = webhook("http://example.com/foo".into(), "FOO");
+    let bar = webhook("http://example.com/bar".into(), "BAR");
+
+    assert_eq!(webhooks.insert(foo).unwrap(), 0);
+    assert_eq!(webhooks.insert(bar.clone()).unwrap(), 1);
+    assert!(webhooks.remove(0).unwrap());
+    assert!(!webhooks.remove(0).unwrap());
+
+    assert!(tempdir.path().join("index.webhooks.json").is_file());
+
+    assert_eq!(Webhooks::load(&index_path).unwrap().list(), vec![(1, bar)]);
+  }
+
+  #[test]
+  fn ids_of_removed_webhooks_are_not_reused() {
+    let tempdir = TempDir::new().unwrap();
+    let index_path = tempdir.path().join("index.redb");
+
+    let webhooks = Webhooks::load(&index_path).unwrap();
+
+    let foo = webhook("http://example.com/foo".into(), "FOO");
+    let bar = webhook("http://example.com/bar".into(), "BAR");
+
+    assert_eq!(webhooks.insert(foo.clone()).unwrap(), 0);
+    assert_eq!(webhooks.insert(bar.clone()).unwrap(), 1);
+    assert!(webhooks.remove(1).unwrap());
+    assert_eq!(webhooks.insert(bar.clone()).unwrap(), 2);
+    assert!(webhooks.remove(2).unwrap());
+
+    let webhooks = Webhooks::load(&index_path).unwrap();
+    assert_eq!(webhooks.insert(bar.clone()).unwrap(), 3);
+    assert_eq!(webhooks.list(), vec![(0, foo), (3, bar)]);
+  }
+
+  #[test]
+  fn webhooks_files_without_next_id_are_loaded() {
+    let tempdir = TempDir::new().unwrap();
+    let index_path = tempdir.path().join("index.redb");
+
+    let foo = webhook("http://example.com/foo".into(), "FOO");
+
+    fs::write(
+      tempdir.path().join("index.webhooks.json"),
+      serde_json::to_vec(&BTreeMap::from([(4, foo.clone())])).unwrap(),
+    )
+    .unwrap();
+
+    let webhooks = Webhooks::load(&index_path).unwrap();
+    assert_eq!(webhooks.list(), vec![(4, foo.clone())]);
+    assert_eq!(webhooks.insert(foo).unwrap(), 5);
+  }
+
+  #[test]
+  fn events_are_only_queued_for_matching_webhooks() {
+    let tempdir = TempDir::new().unwrap();
+    let webhooks = Webhooks::load(&tempdir.path().join("index.redb")).unwrap();
+
+    // nothing listens, so the events stay queued
+    webhooks
+      .insert(webhook("http://127.0.0.1:1/".into(), "FOO"))
+      .unwrap();
+
+    assert_eq!(webhooks.dispatch(&event(), Some("FOO".parse().unwrap())), 1);
+    assert_eq!(webhooks.dispatch(&event(), Some("BAR".parse().unwrap())), 0);
+    assert_eq!(webhooks.dispatch(&event(), None), 0);
+  }
+
+  #[test]
+  fn unreachable_webhooks_do_not_delay_others() {
+    let tempdir = TempDir::new().unwrap();
+    let webhooks = Webhooks::load(&tempdir.path().join("index.redb")).unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+    webhooks
+      .insert(webhook("http://127.0.0.1:1/".into(), "FOO"))
+      .unwrap();
+    webhooks.insert(webhook(url, "FOO")).unwrap();
+
+    assert_eq!(webhooks.dispatch(&event(), Some("FOO".parse().unwrap())), 2);
+
+    // the unreachable webhook is still retrying when the other one receives
+    // the event
+    let start = Instant::now();
+    let body = receive_request(&listener);
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    assert_eq!(serde_json::from_slice::<Event>(&body).unwrap(), event());
+  }
+}
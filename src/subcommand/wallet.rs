@@ -20,6 +20,8 @@ pub mod balance;
 pub mod create;
 pub(crate) mod inscribe;
 pub mod inscriptions;
+pub mod lock_utxo;
+pub mod locked_utxos;
 pub mod outputs;
 pub mod receive;
 mod restore;
@@ -27,6 +29,7 @@ pub mod sats;
 pub mod send;
 pub(crate) mod transaction_builder;
 pub mod transactions;
+pub mod unlock_utxo;
 
 #[derive(Debug, Parser)]
 pub(crate) enum Wallet {
@@ -38,6 +41,10 @@ pub(crate) enum Wallet {
   Inscribe(inscribe::Inscribe),
   #[clap(about = "List wallet inscriptions")]
   Inscriptions,
+  #[clap(about = "Keep coin selection from spending an output")]
+  LockUtxo(lock_utxo::LockUtxo),
+  #[clap(about = "List outputs that coin selection does not spend")]
+  LockedUtxos,
   #[clap(about = "Generate receive address")]
   Receive,
   #[clap(about = "Restore wallet")]
@@ -48,6 +55,8 @@ pub(crate) enum Wallet {
   Send(send::Send),
   #[clap(about = "See wallet transactions")]
   Transactions(transactions::Transactions),
+  #[clap(about = "Unlock an output locked with `wallet lock-utxo`")]
+  UnlockUtxo(unlock_utxo::UnlockUtxo),
   #[clap(about = "List wallet outputs")]
   Outputs,
 }
@@ -59,11 +68,14 @@ impl Wallet {
       Self::Create(create) => create.run(options),
       Self::Inscribe(inscribe) => inscribe.run(options),
       Self::Inscriptions => inscriptions::run(options),
+      Self::LockUtxo(lock_utxo) => lock_utxo.run(options),
+      Self::LockedUtxos => locked_utxos::run(options),
       Self::Receive => receive::run(options),
       Self::Restore(restore) => restore.run(options),
       Self::Sats(sats) => sats.run(options),
       Self::Send(send) => send.run(options),
       Self::Transactions(transactions) => transactions.run(options),
+      Self::UnlockUtxo(unlock_utxo) => unlock_utxo.run(options),
       Self::Outputs => outputs::run(options),
     }
   }
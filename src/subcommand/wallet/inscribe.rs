@@ -65,7 +65,16 @@ impl Inscribe {
 
     let client = options.dogecoin_rpc_client_for_wallet_command(false)?;
 
-    let mut utxos = index.get_unspent_outputs(Wallet::load(&options)?)?;
+    let wallet = Wallet::load(&options)?;
+
+    let mut utxos = index.get_unspent_outputs(&wallet)?;
+
+    // only spend a locked output if it was explicitly chosen
+    let locked_outputs = wallet.locked_outputs(&index, &utxos)?;
+    utxos.retain(|outpoint, _| {
+      self.satpoint.map(|satpoint| satpoint.outpoint) == Some(*outpoint)
+        || !locked_outputs.contains_key(outpoint)
+    });
 
     let inscriptions = index.get_inscriptions(None)?;
 
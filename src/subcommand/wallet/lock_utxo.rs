@@ -0,0 +1,37 @@
+use {super::*, crate::wallet::Wallet};
+
+#[derive(Debug, Parser)]
+pub(crate) struct LockUtxo {
+  #[clap(help = "Lock <OUTPOINT>.")]
+  outpoint: OutPoint,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub output: OutPoint,
+  /// false if the output was already locked
+  pub locked: bool,
+}
+
+impl LockUtxo {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+    index.update()?;
+
+    let mut wallet = Wallet::load(&options)?;
+
+    if !index
+      .get_unspent_outputs(&wallet)?
+      .contains_key(&self.outpoint)
+    {
+      bail!("output {} not in wallet", self.outpoint);
+    }
+
+    print_json(Output {
+      output: self.outpoint,
+      locked: wallet.lock(self.outpoint)?,
+    })?;
+
+    Ok(Box::new(Empty {}))
+  }
+}
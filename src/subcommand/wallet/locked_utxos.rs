@@ -0,0 +1,29 @@
+use {
+  super::*,
+  crate::wallet::{LockReason, Wallet},
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub output: OutPoint,
+  pub reason: LockReason,
+}
+
+pub(crate) fn run(options: Options) -> SubcommandResult {
+  let index = Index::open(&options)?;
+  index.update()?;
+
+  let wallet = Wallet::load(&options)?;
+
+  let unspent_outputs = index.get_unspent_outputs(&wallet)?;
+
+  print_json(
+    wallet
+      .locked_outputs(&index, &unspent_outputs)?
+      .into_iter()
+      .map(|(output, reason)| Output { output, reason })
+      .collect::<Vec<Output>>(),
+  )?;
+
+  Ok(Box::new(Empty {}))
+}
@@ -1,5 +1,8 @@
 use bitcoin::PackedLockTime;
-use {super::*, crate::wallet::Wallet};
+use {
+  super::*,
+  crate::wallet::{LockReason, Wallet},
+};
 
 #[derive(Debug, Parser)]
 pub(crate) struct Send {
@@ -23,12 +26,17 @@ impl Send {
 
     let client = options.dogecoin_rpc_client_for_wallet_command(false)?;
 
-    let unspent_outputs = index.get_unspent_outputs(Wallet::load(&options)?)?;
+    let wallet = Wallet::load(&options)?;
+
+    let mut unspent_outputs = index.get_unspent_outputs(&wallet)?;
+
+    let locked_outputs = wallet.locked_outputs(&index, &unspent_outputs)?;
 
     let inscriptions = index.get_inscriptions(None)?;
 
     let satpoint = match self.outgoing {
       Outgoing::Amount(amount) => {
+        Self::lock_outputs(&client, &locked_outputs)?;
         let transaction = Self::send_amount(&client, amount, address, self.fee_rate)?;
         return Ok(Box::new(Output { transaction }));
       }
@@ -47,6 +55,11 @@ impl Send {
       Outgoing::Relic { .. } => unimplemented!(),
     };
 
+    // the outgoing output may be locked, but no other locked output is spent
+    unspent_outputs.retain(|outpoint, _| {
+      *outpoint == satpoint.outpoint || !locked_outputs.contains_key(outpoint)
+    });
+
     let change = [get_change_address(&client)?, get_change_address(&client)?];
 
     let unsigned_transaction = TransactionBuilder::build_transaction_with_postage(
@@ -69,6 +82,23 @@ impl Send {
     Ok(Box::new(Output { transaction: txid }))
   }
 
+  /// Locks the wallet's locked outputs in Dogecoin Core as well, so that its
+  /// coin selection doesn't spend them.
+  fn lock_outputs(client: &Client, locked_outputs: &BTreeMap<OutPoint, LockReason>) -> Result {
+    let unlocked = client
+      .list_unspent(None, None, None, None, None)?
+      .into_iter()
+      .map(|utxo| OutPoint::new(utxo.txid, utxo.vout))
+      .filter(|outpoint| locked_outputs.contains_key(outpoint))
+      .collect::<Vec<OutPoint>>();
+
+    if !client.lock_unspent(&unlocked)? {
+      bail!("failed to lock ordinal UTXOs");
+    }
+
+    Ok(())
+  }
+
   fn send_amount(
     client: &Client,
     amount: Amount,
@@ -0,0 +1,33 @@
+use {super::*, crate::wallet::Wallet};
+
+#[derive(Debug, Parser)]
+pub(crate) struct UnlockUtxo {
+  #[clap(help = "Unlock <OUTPOINT>.")]
+  outpoint: OutPoint,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub output: OutPoint,
+}
+
+impl UnlockUtxo {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let mut wallet = Wallet::load(&options)?;
+
+    if !wallet.unlock(self.outpoint)? {
+      bail!("output {} is not locked", self.outpoint);
+    }
+
+    // `wallet send` also locks outputs in Dogecoin Core, until it restarts
+    options
+      .dogecoin_rpc_client_for_wallet_command(false)?
+      .unlock_unspent(&[self.outpoint])?;
+
+    print_json(Output {
+      output: self.outpoint,
+    })?;
+
+    Ok(Box::new(Empty {}))
+  }
+}
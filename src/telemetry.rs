@@ -0,0 +1,153 @@
+use super::*;
+
+#[cfg(feature = "telemetry")]
+use {
+  axum::{extract::MatchedPath, middleware::Next, response::Response},
+  opentelemetry::{
+    global,
+    trace::{FutureExt, Span as _, SpanKind, Status, TraceContextExt, Tracer},
+    Context, ContextGuard, KeyValue,
+  },
+};
+
+/// Name of the tracer, and of the service in exported traces.
+#[cfg(feature = "telemetry")]
+const SERVICE_NAME: &str = "ord";
+
+/// Exports spans in batches to the OTLP collector at `endpoint`, over gRPC,
+/// until `shutdown`. Must be called from within the Tokio runtime that sends
+/// the batches.
+#[cfg(feature = "telemetry")]
+pub(crate) fn init(endpoint: &str) -> Result {
+  opentelemetry_otlp::new_pipeline()
+    .tracing()
+    .with_exporter(
+      opentelemetry_otlp::new_exporter()
+        .tonic()
+        .with_endpoint(endpoint),
+    )
+    .with_trace_config(opentelemetry_sdk::trace::config().with_resource(
+      opentelemetry_sdk::Resource::new([KeyValue::new("service.name", SERVICE_NAME)]),
+    ))
+    .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+
+  Ok(())
+}
+
+#[cfg(not(feature = "telemetry"))]
+pub(crate) fn init(_endpoint: &str) -> Result {
+  bail!("exporting traces requires ord to be built with the `telemetry` feature")
+}
+
+/// Exports the remaining spans.
+pub(crate) fn shutdown() {
+  #[cfg(feature = "telemetry")]
+  global::shutdown_tracer_provider();
+}
+
+/// Span of index work, ended when dropped. Spans entered on the same thread
+/// while it is alive are its children. Does nothing until `init` is called,
+/// or at all without the `telemetry` feature.
+pub(crate) struct Span {
+  #[cfg(feature = "telemetry")]
+  context: Context,
+  #[cfg(feature = "telemetry")]
+  _guard: ContextGuard,
+}
+
+#[cfg(feature = "telemetry")]
+impl Span {
+  pub(crate) fn enter(name: &'static str) -> Self {
+    let context = Context::current_with_span(global::tracer(SERVICE_NAME).start(name));
+
+    Self {
+      _guard: context.clone().attach(),
+      context,
+    }
+  }
+
+  pub(crate) fn with_height(self, height: u32) -> Self {
+    self.set(KeyValue::new("ord.block.height", i64::from(height)));
+    self
+  }
+
+  pub(crate) fn with_txid(self, txid: Txid) -> Self {
+    self.set(KeyValue::new("ord.tx.id", txid.to_string()));
+    self
+  }
+
+  /// Records the relic the work is about, replacing the one recorded before.
+  pub(crate) fn set_relic(&self, id: RelicId) {
+    self.set(KeyValue::new("ord.relic.id", id.to_string()));
+  }
+
+  fn set(&self, attribute: KeyValue) {
+    self.context.span().set_attribute(attribute);
+  }
+}
+
+#[cfg(feature = "telemetry")]
+impl Drop for Span {
+  fn drop(&mut self) {
+    self.context.span().end();
+  }
+}
+
+#[cfg(not(feature = "telemetry"))]
+impl Span {
+  pub(crate) fn enter(_: &'static str) -> Self {
+    Self {}
+  }
+
+  pub(crate) fn with_height(self, _: u32) -> Self {
+    self
+  }
+
+  pub(crate) fn with_txid(self, _: Txid) -> Self {
+    self
+  }
+
+  pub(crate) fn set_relic(&self, _: RelicId) {}
+}
+
+/// Traces every request in a server span named after its method and route,
+/// with the index work of its handler as children.
+#[cfg(feature = "telemetry")]
+pub(crate) async fn middleware<B>(request: http::Request<B>, next: Next<B>) -> Response {
+  let route = request
+    .extensions()
+    .get::<MatchedPath>()
+    .map(|path| path.as_str().to_string())
+    .unwrap_or_else(|| request.uri().path().to_string());
+
+  let tracer = global::tracer(SERVICE_NAME);
+
+  let span = tracer
+    .span_builder(format!("{} {route}", request.method()))
+    .with_kind(SpanKind::Server)
+    .with_attributes(vec![
+      KeyValue::new("http.request.method", request.method().to_string()),
+      KeyValue::new("http.route", route),
+    ])
+    .start(&tracer);
+
+  let context = Context::current_with_span(span);
+
+  let response = next.run(request).with_context(context.clone()).await;
+
+  let span = context.span();
+  let status = response.status();
+
+  span.set_attribute(KeyValue::new(
+    "http.response.status_code",
+    i64::from(status.as_u16()),
+  ));
+
+  if status.is_server_error() {
+    span.set_status(Status::error(status.to_string()));
+  }
+
+  span.end();
+
+  response
+}
@@ -61,6 +61,17 @@ where
     Self { content, config }
   }
 
+  fn title(&self) -> String {
+    match &self.config.branding.title {
+      Some(site) => format!("{} | {site}", self.content.title()),
+      None => self.content.title(),
+    }
+  }
+
+  fn site_title(&self) -> &str {
+    self.config.branding.title.as_deref().unwrap_or("Bones")
+  }
+
   fn og_image(&self) -> String {
     if let Some(domain) = &self.config.domain {
       format!("https://{domain}/static/favicon.png")
@@ -28,6 +28,8 @@ pub struct BlockJson {
   output_addresses_per_tx: HashMap<Txid, String>,
   output_scripts_per_tx: HashMap<Txid, String>,
   inscriptions_per_tx: HashMap<Txid, (InscriptionId, Option<String>, Option<Vec<u8>>)>,
+  /// input values and addresses were omitted because Dogecoin Core no longer has the spent outputs
+  pruned: bool,
 }
 
 impl BlockJson {
@@ -43,6 +45,7 @@ impl BlockJson {
     inscriptions_per_tx: HashMap<Txid, (InscriptionId, Option<String>, Option<Vec<u8>>)>,
     output_addresses_per_tx: HashMap<Txid, String>,
     output_scripts_per_tx: HashMap<Txid, String>,
+    pruned: bool,
   ) -> Self {
     let mut target = block.header.target().to_be_bytes();
     target.reverse();
@@ -58,6 +61,7 @@ impl BlockJson {
       inscriptions_per_tx,
       output_addresses_per_tx,
       output_scripts_per_tx,
+      pruned,
     }
   }
 }
@@ -1,14 +1,19 @@
-use super::*;
+use {super::*, crate::relics::SpacedRelic};
 
 #[derive(Boilerplate)]
 pub(crate) struct HomeHtml {
   last: u32,
   blocks: Vec<BlockHash>,
   inscriptions: Vec<InscriptionId>,
+  featured: Vec<SpacedRelic>,
 }
 
 impl HomeHtml {
-  pub(crate) fn new(blocks: Vec<(u32, BlockHash)>, inscriptions: Vec<InscriptionId>) -> Self {
+  pub(crate) fn new(
+    blocks: Vec<(u32, BlockHash)>,
+    inscriptions: Vec<InscriptionId>,
+    featured: Vec<SpacedRelic>,
+  ) -> Self {
     Self {
       last: blocks
         .get(0)
@@ -17,6 +22,7 @@ impl HomeHtml {
         .unwrap_or(0),
       blocks: blocks.into_iter().map(|(_, hash)| hash).collect(),
       inscriptions,
+      featured,
     }
   }
 }
@@ -50,6 +56,7 @@ mod tests {
           )
         ],
         vec![inscription_id(1), inscription_id(2)],
+        Vec::new(),
       )
       .to_string(),
       "<h2>Latest Shibescriptions</h2>
@@ -66,4 +73,17 @@ mod tests {
 ",
     );
   }
+
+  #[test]
+  fn featured() {
+    assert_regex_match!(
+      &HomeHtml::new(Vec::new(), Vec::new(), vec!["FOO•BAR".parse().unwrap()]).to_string(),
+      "<h2>Featured Bones</h2>
+<ul>
+  <li><a href=/bone/FOO•BAR>FOO•BAR</a></li>
+</ul>
+<h2>Latest Blocks</h2>
+.*",
+    );
+  }
 }
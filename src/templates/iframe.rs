@@ -19,6 +19,24 @@ impl Iframe {
       thumbnail: false,
     })
   }
+
+  /// Replace preview iframes in rendered `html` with plain links, for pages
+  /// that are served without scripts or frames.
+  pub(crate) fn strip(html: &str) -> String {
+    lazy_static! {
+      static ref THUMBNAIL: Regex =
+        Regex::new(r"<a href=/shibescription/([[:xdigit:]]{64}i\d+)><iframe [^>]*></iframe></a>")
+          .unwrap();
+      static ref MAIN: Regex =
+        Regex::new(r"<iframe [^>]*src=/preview/([[:xdigit:]]{64}i\d+)></iframe>").unwrap();
+    }
+
+    let html = THUMBNAIL.replace_all(html, "<a href=/shibescription/$1>$1</a>");
+
+    MAIN
+      .replace_all(&html, "<a href=/content/$1>$1</a>")
+      .into_owned()
+  }
 }
 
 impl Display for Iframe {
@@ -61,4 +79,22 @@ mod tests {
       "<iframe sandbox=allow-scripts scrolling=no loading=lazy src=/preview/1{64}i1></iframe>",
     );
   }
+
+  #[test]
+  fn strip() {
+    assert_eq!(
+      Iframe::strip(&format!(
+        "<div>{}</div>{}",
+        Iframe::thumbnail(inscription_id(1)).0,
+        Iframe::main(inscription_id(2)).0
+      )),
+      format!(
+        "<div><a href=/shibescription/{}>{}</a></div><a href=/content/{}>{}</a>",
+        inscription_id(1),
+        inscription_id(1),
+        inscription_id(2),
+        inscription_id(2)
+      ),
+    );
+  }
 }
@@ -29,7 +29,7 @@ pub(crate) struct InscriptionHtml {
   #[serde(rename = "bone_deployed")]
   pub(crate) relic_enshrined: bool,
   pub(crate) syndicate: Option<SyndicateId>,
-  pub(crate) charms: u16,
+  pub(crate) charms: u32,
   pub(crate) child_count: u64,
   pub(crate) children: Vec<InscriptionId>,
 }
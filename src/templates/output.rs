@@ -1,5 +1,5 @@
 use super::*;
-use crate::relics::spaced_relic::SpacedRelic;
+use crate::{index::address_label::AddressLabel, relics::spaced_relic::SpacedRelic};
 
 #[derive(Boilerplate)]
 pub(crate) struct OutputHtml {
@@ -14,11 +14,17 @@ pub(crate) struct OutputHtml {
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct AddressOutputJson {
   pub(crate) outpoint: Vec<OutPoint>,
+  /// label assigned to the address by the operator
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) label: Option<AddressLabel>,
 }
 
 impl AddressOutputJson {
   pub fn new(outputs: Vec<OutPoint>) -> Self {
-    Self { outpoint: outputs }
+    Self {
+      outpoint: outputs,
+      label: None,
+    }
   }
 }
 
@@ -1,8 +1,9 @@
 use super::*;
-use crate::index::relics_entry::{RelicEntry, RelicState};
+use crate::index::relics_entry::{RelicEntry, RelicEstimates, RelicState};
 use crate::relics::enshrining::Enshrining;
 use crate::relics::relic_id::RelicId;
 use crate::relics::{MintTerms, Pool, SpacedRelic};
+use ciborium::Value;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RelicEntryHtml {
@@ -17,6 +18,10 @@ pub struct RelicEntryHtml {
   pub pool: Option<Pool>,
   pub timestamp: u64,
   pub turbo: bool,
+  #[serde(default)]
+  pub featured: bool,
+  #[serde(default)]
+  pub verified: bool,
 }
 
 impl RelicEntryHtml {
@@ -59,6 +64,8 @@ impl From<RelicEntry> for RelicEntryHtml {
       pool: entry.pool,
       timestamp: entry.timestamp,
       turbo: entry.turbo,
+      featured: false,
+      verified: false,
     }
   }
 }
@@ -68,8 +75,30 @@ pub struct RelicHtml {
   pub entry: RelicEntryHtml,
   pub id: RelicId,
   pub mintable: bool,
+  pub swappable: bool,
   pub owner: Option<InscriptionId>,
   pub thumb: Option<InscriptionId>,
+  /// estimated mint-out and pool launch, while minting
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub estimates: Option<RelicEstimates>,
+  /// amounts rendered with divisibility and symbol, see `?formatted=true`
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub formatted: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelicMetaJson {
+  pub id: RelicId,
+  #[serde(rename = "spaced_bone")]
+  pub spaced_relic: SpacedRelic,
+  pub symbol: Option<char>,
+  pub spacers: u32,
+  pub divisibility: u8,
+  pub thumbnail: Option<InscriptionId>,
+  pub owner: Option<InscriptionId>,
+  pub owner_sequence_number: Option<u32>,
+  /// CBOR metadata of the owner inscription, which sealed the ticker
+  pub metadata: Option<Value>,
 }
 
 impl PageContent for RelicHtml {
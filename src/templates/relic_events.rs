@@ -1,12 +1,15 @@
 use super::*;
-use crate::index::event::Event;
+use crate::index::event::FormattedEvent;
 use crate::relics::spaced_relic::SpacedRelic;
 
 #[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RelicEventsHtml {
   #[serde(rename = "spaced_bone")]
   pub spaced_relic: SpacedRelic,
-  pub events: Vec<Event>,
+  pub events: Vec<FormattedEvent>,
+  pub total_events: u64,
+  pub next_page: Option<usize>,
+  pub prev_page: Option<usize>,
 }
 
 impl PageContent for RelicEventsHtml {
@@ -16,6 +16,8 @@ pub struct SyndicateEntryHtml {
   pub quota: u128,
   pub royalty: u128,
   pub gated: bool,
+  pub allowlist: bool,
+  pub holding: Option<(RelicId, u128)>,
   pub lock: Option<u64>,
   pub reward: Option<u128>,
   pub turbo: bool,
@@ -33,6 +35,8 @@ impl From<SyndicateEntry> for SyndicateEntryHtml {
       quota: entry.quota,
       royalty: entry.royalty,
       gated: entry.gated,
+      allowlist: entry.allowlist,
+      holding: entry.holding,
       lock: entry.lock,
       reward: entry.reward,
       turbo: entry.turbo,
@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, crate::relics::SpacedRelic};
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub(crate) struct Utxo {
@@ -7,4 +7,8 @@ pub(crate) struct Utxo {
   pub(crate) script: Script,
   pub(crate) shibes: u64,
   pub(crate) confirmations: Option<u32>,
+  pub(crate) has_inscriptions: bool,
+  pub(crate) inscription_count: usize,
+  #[serde(rename = "bones")]
+  pub(crate) relics: BTreeMap<SpacedRelic, Pile>,
 }
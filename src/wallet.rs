@@ -1,14 +1,92 @@
 use super::*;
 
-#[derive(Copy, Clone)]
+/// Why coin selection leaves an output alone.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LockReason {
+  /// locked with `wallet lock-utxo`
+  User,
+  /// carries an inscription, which includes chests
+  Inscription,
+  /// carries bones
+  Bone,
+}
+
+#[derive(Clone)]
 pub(crate) struct Wallet {
-  _private: (),
+  locked_path: PathBuf,
+  locked: BTreeSet<OutPoint>,
 }
 
 impl Wallet {
   pub(crate) fn load(options: &Options) -> Result<Self> {
     options.dogecoin_rpc_client_for_wallet_command(false)?;
 
-    Ok(Self { _private: () })
+    let locked_path = options
+      .data_dir()?
+      .join("locked-utxos")
+      .join(format!("{}.json", options.wallet));
+
+    let locked = if locked_path.exists() {
+      serde_json::from_str(&fs::read_to_string(&locked_path)?)
+        .with_context(|| format!("invalid locked outputs file `{}`", locked_path.display()))?
+    } else {
+      BTreeSet::new()
+    };
+
+    Ok(Self {
+      locked_path,
+      locked,
+    })
+  }
+
+  /// Locks `outpoint`, returning false if it was already locked.
+  pub(crate) fn lock(&mut self, outpoint: OutPoint) -> Result<bool> {
+    let inserted = self.locked.insert(outpoint);
+    self.save()?;
+    Ok(inserted)
+  }
+
+  /// Unlocks `outpoint`, returning false if it was not locked.
+  pub(crate) fn unlock(&mut self, outpoint: OutPoint) -> Result<bool> {
+    let removed = self.locked.remove(&outpoint);
+    self.save()?;
+    Ok(removed)
+  }
+
+  /// The outputs among `unspent_outputs` that coin selection must not spend.
+  pub(crate) fn locked_outputs(
+    &self,
+    index: &Index,
+    unspent_outputs: &BTreeMap<OutPoint, Amount>,
+  ) -> Result<BTreeMap<OutPoint, LockReason>> {
+    let mut locked = BTreeMap::new();
+
+    for outpoint in unspent_outputs.keys() {
+      let reason = if self.locked.contains(outpoint) {
+        LockReason::User
+      } else if !index.get_inscriptions_on_output(*outpoint)?.is_empty() {
+        LockReason::Inscription
+      } else if index.has_relic_index()
+        && !index.get_relic_balances_for_outpoint(*outpoint)?.is_empty()
+      {
+        LockReason::Bone
+      } else {
+        continue;
+      };
+
+      locked.insert(*outpoint, reason);
+    }
+
+    Ok(locked)
+  }
+
+  fn save(&self) -> Result {
+    if let Some(parent) = self.locked_path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+
+    fs::write(&self.locked_path, serde_json::to_string(&self.locked)?)
+      .with_context(|| format!("failed to write `{}`", self.locked_path.display()))
   }
 }
@@ -525,8 +525,6 @@ impl Api for Server {
     unlock: bool,
     outputs: Vec<JsonOutPoint>,
   ) -> Result<bool, jsonrpc_core::Error> {
-    assert!(!unlock);
-
     let mut state = self.state();
 
     if state.fail_lock_unspent {
@@ -538,8 +536,12 @@ impl Api for Server {
         vout: output.vout,
         txid: output.txid,
       };
-      assert!(state.utxos.contains_key(&output));
-      state.locked.insert(output);
+      if unlock {
+        state.locked.remove(&output);
+      } else {
+        assert!(state.utxos.contains_key(&output));
+        state.locked.insert(output);
+      }
     }
 
     Ok(true)
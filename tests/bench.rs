@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn swaps_requires_relic_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("bench swaps")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: benchmarking swaps requires index created with `--index-relics` flag\n",
+    )
+    .run();
+}
+
+#[test]
+fn swaps_requires_indexed_swaps() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--index-relics bench swaps --blocks 5")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: no swaps indexed\n")
+    .run();
+}
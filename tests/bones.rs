@@ -0,0 +1,140 @@
+use {super::*, ord::subcommand::bones::list::Output};
+
+#[test]
+fn list_includes_base_token() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new("--index-relics bones list --json")
+    .rpc_server(&rpc_server)
+    .output::<Vec<Output>>();
+
+  assert_eq!(output.len(), 1);
+  assert_eq!(output[0].id.to_string(), "1:0");
+}
+
+#[test]
+fn show_queries_server() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_args(&rpc_server, &["--index-relics"]);
+
+  CommandBuilder::new(format!("bones show BONE --server {}", server.url()))
+    .stdout_regex("bone +BONE\nid +1:0\n.*")
+    .run();
+}
+
+#[test]
+fn requires_relic_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("bones list")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: querying bones requires index created with `--index-relics` flag\n")
+    .run();
+}
+
+#[test]
+fn export_prints_snapshot() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("--index-relics bones export")
+    .rpc_server(&rpc_server)
+    .stdout_regex(
+      r#"\{
+  "height": 1,
+  "block_hash": "[[:xdigit:]]{64}",
+  "relics": \[
+.*
+\}
+"#,
+    )
+    .run();
+}
+
+#[test]
+fn bones_can_be_resolved_in_bulk() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_args(&rpc_server, &["--index-relics"]);
+
+  let response = reqwest::blocking::Client::new()
+    .post(server.url().join("/bones/resolve").unwrap())
+    .json(&["BONE", "1:0", "FOOBAR", "!"])
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let resolved = response.json::<serde_json::Value>().unwrap();
+
+  assert_eq!(resolved[0]["id"], "1:0");
+  assert_eq!(resolved[0]["entry"], resolved[1]["entry"]);
+  assert_eq!(resolved[1]["query"], "1:0");
+  assert_eq!(resolved[2]["error"], "bone FOOBAR not found");
+  assert!(resolved[2].get("entry").is_none());
+  assert!(resolved[3]["error"]
+    .as_str()
+    .unwrap()
+    .starts_with("invalid bone `!`"));
+
+  let response = reqwest::blocking::Client::new()
+    .post(server.url().join("/bones/resolve").unwrap())
+    .json(&vec!["BONE"; 1001])
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn amounts_can_be_served_as_strings() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_args(&rpc_server, &["--index-relics"]);
+
+  let request = |format: Option<&str>| {
+    let mut request =
+      reqwest::blocking::Client::new().get(server.url().join("/bone/BONE?json=true").unwrap());
+    if let Some(format) = format {
+      request = request.header("x-amount-format", format);
+    }
+    request.send().unwrap()
+  };
+
+  let numbers = request(None).json::<serde_json::Value>().unwrap();
+  assert!(numbers["state"]["burned"].is_number());
+
+  let strings = request(Some("string")).json::<serde_json::Value>().unwrap();
+  assert_eq!(
+    strings["state"]["burned"],
+    numbers["state"]["burned"].to_string()
+  );
+  assert_eq!(strings["block"], numbers["block"]);
+
+  assert_eq!(
+    request(Some("number")).json::<serde_json::Value>().unwrap(),
+    numbers
+  );
+
+  assert_eq!(request(Some("foo")).status(), StatusCode::BAD_REQUEST);
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &["--index-relics"],
+    &["--amount-format", "string"],
+  );
+
+  let strings = reqwest::blocking::Client::new()
+    .get(server.url().join("/bone/BONE?json=true").unwrap())
+    .send()
+    .unwrap()
+    .json::<serde_json::Value>()
+    .unwrap();
+  assert!(strings["state"]["burned"].is_string());
+}
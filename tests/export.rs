@@ -0,0 +1,20 @@
+use super::*;
+
+#[test]
+fn export_content_prints_summary() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  inscribe(&rpc_server);
+
+  CommandBuilder::new("export content --format dir --output out")
+    .rpc_server(&rpc_server)
+    .stdout_regex(
+      r#"\{
+  "resumed_after": null,
+  "exported": 1,
+  "last_sequence_number": 0
+\}
+"#,
+    )
+    .run();
+}
@@ -1,4 +1,7 @@
-use super::*;
+use {
+  super::*,
+  std::sync::{Arc, Mutex},
+};
 
 #[test]
 fn custom_index_path() {
@@ -35,3 +38,100 @@ fn re_opening_database_does_not_trigger_schema_check() {
     .rpc_server(&rpc_server)
     .run();
 }
+
+#[test]
+fn stats_reports_tables() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("index update")
+    .rpc_server(&rpc_server)
+    .run();
+
+  CommandBuilder::new("index stats")
+    .rpc_server(&rpc_server)
+    .stdout_regex(
+      r#"\{
+  "index_path": ".*\.redb",
+  "index_file_size": \d+,
+  "stored_bytes": \d+,
+  "fragmented_bytes": \d+,
+  "tables": \[
+.*
+      "name": "HEIGHT_TO_BLOCK_HASH",
+      "multimap": false,
+      "entries": 2,
+.*
+\}
+"#,
+    )
+    .run();
+}
+
+#[test]
+fn compact_keeps_index_usable() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run();
+
+  CommandBuilder::new(format!("--index {} index compact", index_path.display()))
+    .rpc_server(&rpc_server)
+    .stderr_regex("Deleting reorg savepoints…\nDeleted 0 savepoint\\(s\\)\nCompacting .*\n")
+    .stdout_regex(
+      r#"\{
+  "savepoints_deleted": 0,
+  "size_before": \d+,
+  "size_after": \d+
+\}
+"#,
+    )
+    .run();
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run();
+}
+
+#[test]
+fn hooks_are_called_for_committed_blocks() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(2);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let cookie_file = tempdir.path().join("cookie");
+  fs::write(&cookie_file, "username:password").unwrap();
+
+  let mut index = ord::Index::open_with_args([
+    "ord",
+    "--rpc-url",
+    &rpc_server.url(),
+    "--cookie-file",
+    cookie_file.to_str().unwrap(),
+    "--data-dir",
+    tempdir.path().to_str().unwrap(),
+  ])
+  .unwrap();
+
+  let blocks = Arc::new(Mutex::new(Vec::new()));
+
+  {
+    let blocks = blocks.clone();
+    index.on_block(Box::new(move |height, _| {
+      blocks.lock().unwrap().push(height)
+    }));
+  }
+
+  index.update().unwrap();
+
+  assert_eq!(*blocks.lock().unwrap(), [0, 1, 2]);
+}
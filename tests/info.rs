@@ -18,6 +18,10 @@ fn json_with_satoshi_index() {
   "page_size": \d+,
   "sat_ranges": 2,
   "stored_bytes": \d+,
+  "table_schema_versions": \{
+    "ADDRESS_TO_LABEL": 1,
+    "CHEST_UNLOCK_HEIGHT_TO_SEQUENCE_NUMBER": 1
+  \},
   "transactions": \[
     \{
       "starting_block_count": 0,
@@ -50,6 +54,10 @@ fn json_without_satoshi_index() {
   "page_size": \d+,
   "sat_ranges": 0,
   "stored_bytes": \d+,
+  "table_schema_versions": \{
+    "ADDRESS_TO_LABEL": 1,
+    "CHEST_UNLOCK_HEIGHT_TO_SEQUENCE_NUMBER": 1
+  \},
   "transactions": \[
     \{
       "starting_block_count": 0,
@@ -0,0 +1,13 @@
+//! End-to-end tests against a real Dogecoin Core regtest node, covering what
+//! the mock RPC server can't: consensus and policy checks of the transactions
+//! the protocol relies on, and `ord` indexing and serving them. They need
+//! `dogecoind`, see `Node`, so they are ignored by default and run with
+//! `just test-integration`.
+
+use {
+  super::*,
+  node::{Node, OrdServer},
+};
+
+mod bones;
+mod node;
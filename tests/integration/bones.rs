@@ -0,0 +1,401 @@
+use {
+  super::*,
+  bitcoin::{consensus, TxOut},
+  ord::{Enshrining, Keepsake, MintTerms, RelicId, SpacedRelic, Summoning, Swap},
+};
+
+const METADATA_TAG: i64 = 5;
+const DELEGATE_TAG: i64 = 11;
+
+/// Inscriptions delegating to the bonestone inscription mint the base token
+/// when burned.
+const BONESTONES: &str = "babc46e7095a90c814d4c161b1d9d47f921c566ea93ad483d78741cc27c07deb";
+
+/// Value of the output holding the base tokens minted by `mint_base_token`.
+const BASE_VALUE: u64 = Node::POSTAGE - 1 - Node::FEE;
+
+/// Mints the base token by burning a bonestone and returns the transaction,
+/// whose output 1 holds the minted base tokens.
+fn mint_base_token(node: &mut Node) -> Txid {
+  let bonestone = consensus::serialize(&Txid::from_str(BONESTONES).unwrap());
+
+  // reveal a bonestone delegate onto the OP_RETURN output, burning it
+  let bonestone = node.reveal(b"", b"", &[(DELEGATE_TAG, &bonestone)]);
+
+  let base_mint = node.broadcast(
+    vec![bonestone],
+    vec![
+      TxOut {
+        value: 1,
+        script_pubkey: Keepsake {
+          pointer: Some(1),
+          ..Default::default()
+        }
+        .encipher(),
+      },
+      TxOut {
+        value: BASE_VALUE,
+        script_pubkey: Node::script_pubkey(),
+      },
+    ],
+  );
+
+  node.mine();
+
+  base_mint
+}
+
+#[test]
+#[ignore]
+fn bones_can_be_enshrined_minted_and_swapped() {
+  let mut node = Node::spawn();
+
+  let base_mint = mint_base_token(&mut node);
+
+  let spaced_relic = "HAPPYPATHTOKEN".parse::<SpacedRelic>().unwrap();
+
+  let mut metadata = Vec::new();
+  ciborium::into_writer(&spaced_relic.to_metadata(), &mut metadata).unwrap();
+
+  // seal the ticker to the inscription and enshrine it, paying the sealing fee
+  let sealing = node.reveal(
+    b"text/plain;charset=utf-8",
+    b"happy path",
+    &[(METADATA_TAG, &metadata)],
+  );
+
+  let enshrining = node.broadcast(
+    vec![
+      sealing,
+      Node::input(OutPoint {
+        txid: base_mint,
+        vout: 1,
+      }),
+    ],
+    vec![
+      TxOut {
+        value: Node::POSTAGE - Node::FEE,
+        script_pubkey: Node::script_pubkey(),
+      },
+      TxOut {
+        value: BASE_VALUE,
+        script_pubkey: Node::script_pubkey(),
+      },
+      TxOut {
+        value: 0,
+        script_pubkey: Keepsake {
+          sealing: true,
+          enshrining: Some(Enshrining {
+            mint_terms: Some(MintTerms {
+              amount: Some(1000),
+              cap: Some(1),
+              price: Some(5000),
+              seed: Some(1000),
+              swap_height: None,
+              fee: None,
+            }),
+            ..Default::default()
+          }),
+          pointer: Some(1),
+          ..Default::default()
+        }
+        .encipher(),
+      },
+    ],
+  );
+
+  let id = RelicId {
+    block: node.mine(),
+    tx: 1,
+  };
+
+  // mint the whole supply, which opens the pool
+  let mint = node.broadcast(
+    vec![Node::input(OutPoint {
+      txid: enshrining,
+      vout: 1,
+    })],
+    vec![
+      TxOut {
+        value: BASE_VALUE - Node::FEE,
+        script_pubkey: Node::script_pubkey(),
+      },
+      TxOut {
+        value: 0,
+        script_pubkey: Keepsake {
+          mint: Some(id),
+          ..Default::default()
+        }
+        .encipher(),
+      },
+    ],
+  );
+
+  node.mine();
+
+  node.broadcast(
+    vec![Node::input(OutPoint {
+      txid: mint,
+      vout: 0,
+    })],
+    vec![
+      TxOut {
+        value: BASE_VALUE - 2 * Node::FEE,
+        script_pubkey: Node::script_pubkey(),
+      },
+      TxOut {
+        value: 0,
+        script_pubkey: Keepsake {
+          swap: Some(Swap {
+            input: None,
+            output: Some(id),
+            input_amount: Some(562),
+            output_amount: Some(100),
+            is_exact_input: false,
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      },
+    ],
+  );
+
+  node.mine();
+
+  let output = node.ord("index update").output().unwrap();
+  assert!(
+    output.status.success(),
+    "{}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+
+  let server = node.serve();
+
+  let bone = server.json("/bone/BONE?json=true");
+  assert_eq!(bone["state"]["mints"], 1);
+
+  let entry = server.json(&format!("/bone/{id}?json=true"));
+  assert_eq!(entry["spaced_bone"], "HAPPYPATHTOKEN");
+  assert_eq!(entry["state"]["mints"], 1);
+  assert_eq!(entry["pool"]["base_supply"], 5000 + 556);
+  assert_eq!(entry["pool"]["quote_supply"], 1000 - 100);
+
+  // amounts are rendered with the bone's divisibility and symbol on request
+  let events = server.json(&format!("/events?relic={id}&formatted=true"));
+  let mint = events["events"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .find(|event| event["info"].get("BoneMinted").is_some())
+    .unwrap();
+  assert_eq!(mint["info"]["BoneMinted"]["amount"], 1000);
+  assert_eq!(mint["formatted"]["amount"], "0.00001\u{A0}¤");
+
+  let events = server.json(&format!("/events?relic={id}"));
+  assert!(events["events"][0].get("formatted").is_none());
+}
+
+#[test]
+#[ignore]
+fn chest_unlocks_follow_encasing_relocking_and_releasing() {
+  let mut node = Node::spawn();
+
+  let base_mint = mint_base_token(&mut node);
+
+  // summon a syndicate for the base token, its inscription is revealed in the
+  // summoning transaction
+  let syndicate = node.reveal(b"text/plain;charset=utf-8", b"syndicate", &[]);
+
+  node.broadcast(
+    vec![syndicate],
+    vec![
+      TxOut {
+        value: Node::POSTAGE - Node::FEE,
+        script_pubkey: Node::script_pubkey(),
+      },
+      TxOut {
+        value: 0,
+        script_pubkey: Keepsake {
+          summoning: Some(Summoning {
+            quota: Some(1000),
+            lock: Some(5),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      },
+    ],
+  );
+
+  let syndicate = RelicId {
+    block: node.mine(),
+    tx: 1,
+  };
+
+  // encase a chest, its inscription ends up on output 0 and the remaining base
+  // tokens on output 1
+  let chest = node.reveal(b"text/plain;charset=utf-8", b"chest", &[]);
+
+  let encasing = node.broadcast(
+    vec![
+      chest,
+      Node::input(OutPoint {
+        txid: base_mint,
+        vout: 1,
+      }),
+    ],
+    vec![
+      TxOut {
+        value: Node::POSTAGE - Node::FEE,
+        script_pubkey: Node::script_pubkey(),
+      },
+      TxOut {
+        value: BASE_VALUE,
+        script_pubkey: Node::script_pubkey(),
+      },
+      TxOut {
+        value: 0,
+        script_pubkey: Keepsake {
+          encasing: Some(syndicate),
+          pointer: Some(1),
+          ..Default::default()
+        }
+        .encipher(),
+      },
+    ],
+  );
+
+  let encased = node.mine();
+
+  let chest = format!("{encasing}i0");
+
+  let unlocks = |server: &OrdServer, path: &str| {
+    let unlocks = server.json(path);
+    let chests = unlocks["chests"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .map(|chest| {
+        (
+          chest["inscription_id"].as_str().unwrap().to_string(),
+          chest["unlock_height"].as_u64().unwrap(),
+        )
+      })
+      .collect::<Vec<(String, u64)>>();
+    (chests, unlocks["bones"].clone())
+  };
+
+  {
+    let server = node.serve();
+
+    for path in [
+      "/chests/unlocking".to_string(),
+      format!("/syndicate/{syndicate}/unlocks"),
+    ] {
+      assert_eq!(
+        unlocks(&server, &path),
+        (
+          vec![(chest.clone(), encased + 5)],
+          serde_json::json!({ "BONE": 1000 }),
+        ),
+        "{path}"
+      );
+    }
+
+    assert_eq!(
+      unlocks(&server, &format!("/chests/unlocking?from={}", encased + 6)),
+      (Vec::new(), serde_json::json!({})),
+    );
+  }
+
+  // relock the chest, which keeps its inscription on output 0
+  let relocking = node.broadcast(
+    vec![Node::input(OutPoint {
+      txid: encasing,
+      vout: 0,
+    })],
+    vec![
+      TxOut {
+        value: Node::POSTAGE - 2 * Node::FEE,
+        script_pubkey: Node::script_pubkey(),
+      },
+      TxOut {
+        value: 0,
+        script_pubkey: Keepsake {
+          relock: Some(encased + 20),
+          ..Default::default()
+        }
+        .encipher(),
+      },
+    ],
+  );
+
+  node.mine();
+
+  {
+    let server = node.serve();
+
+    for path in [
+      format!("/chests/unlocking?to={}", encased + 20),
+      format!("/syndicate/{syndicate}/unlocks?from={}", encased + 20),
+    ] {
+      assert_eq!(
+        unlocks(&server, &path),
+        (
+          vec![(chest.clone(), encased + 20)],
+          serde_json::json!({ "BONE": 1000 }),
+        ),
+        "{path}"
+      );
+    }
+
+    // the chest no longer unlocks at its original height
+    assert_eq!(
+      unlocks(
+        &server,
+        &format!("/chests/unlocking?from={0}&to={0}", encased + 5)
+      ),
+      (Vec::new(), serde_json::json!({})),
+    );
+  }
+
+  node.mine_blocks(20);
+
+  node.broadcast(
+    vec![Node::input(OutPoint {
+      txid: relocking,
+      vout: 0,
+    })],
+    vec![
+      TxOut {
+        value: Node::POSTAGE - 3 * Node::FEE,
+        script_pubkey: Node::script_pubkey(),
+      },
+      TxOut {
+        value: 0,
+        script_pubkey: Keepsake {
+          release: true,
+          ..Default::default()
+        }
+        .encipher(),
+      },
+    ],
+  );
+
+  node.mine();
+
+  let server = node.serve();
+
+  // released chests no longer unlock
+  for path in [
+    "/chests/unlocking?from=0".to_string(),
+    format!("/syndicate/{syndicate}/unlocks?from=0"),
+  ] {
+    assert_eq!(
+      unlocks(&server, &path),
+      (Vec::new(), serde_json::json!({})),
+      "{path}"
+    );
+  }
+}
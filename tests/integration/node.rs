@@ -0,0 +1,345 @@
+use {
+  super::*,
+  bitcoin::{
+    blockdata::{opcodes, script},
+    Address, PackedLockTime, Script, Sequence, Transaction, TxIn, TxOut, Witness,
+  },
+  bitcoincore_rpc::{Auth, Client, RpcApi},
+  reqwest::blocking::Response,
+  std::{collections::VecDeque, env, ffi::OsString},
+};
+
+/// Environment variable with the path of the `dogecoind` binary to run,
+/// `dogecoind` on the `PATH` if unset.
+const DOGECOIND: &str = "ORD_INTEGRATION_DOGECOIND";
+
+/// Confirmations Dogecoin Core requires at most before coinbase outputs can be
+/// spent.
+const COINBASE_MATURITY: u64 = 240;
+
+/// Mature coinbase outputs available to fund transactions.
+const FUNDING_OUTPUTS: u64 = 10;
+
+/// A Dogecoin Core regtest node, stopped when dropped.
+///
+/// Blocks are mined to an anyone-can-spend P2SH script, so tests build and
+/// broadcast raw transactions without a wallet: every output paying to
+/// `Node::script_pubkey` is spent with `Node::input`.
+pub(crate) struct Node {
+  child: Child,
+  client: Client,
+  rpc_port: u16,
+  tempdir: TempDir,
+  /// spendable outputs paying to `Node::script_pubkey`, with their values
+  funding: VecDeque<(OutPoint, u64)>,
+}
+
+impl Node {
+  /// Value of inscription commit outputs.
+  pub(crate) const POSTAGE: u64 = 100_000_000;
+
+  /// Fee paid by every transaction, well above the minimum relay fee.
+  pub(crate) const FEE: u64 = 1_000_000;
+
+  pub(crate) fn spawn() -> Self {
+    let tempdir = TempDir::new().unwrap();
+
+    let data_dir = tempdir.path().join("dogecoin");
+    fs::create_dir(&data_dir).unwrap();
+
+    let rpc_port = TcpListener::bind("127.0.0.1:0")
+      .unwrap()
+      .local_addr()
+      .unwrap()
+      .port();
+
+    let child = Command::new(env::var_os(DOGECOIND).unwrap_or_else(|| "dogecoind".into()))
+      .arg({
+        let mut arg = OsString::from("-datadir=");
+        arg.push(&data_dir);
+        arg
+      })
+      .arg("-regtest")
+      .arg("-txindex")
+      .arg("-listen=0")
+      .arg("-disablewallet")
+      .arg(format!("-rpcport={rpc_port}"))
+      .stdout(Stdio::null())
+      .spawn()
+      .unwrap_or_else(|err| panic!("failed to spawn dogecoind, is ${DOGECOIND} set? {err}"));
+
+    let cookie = data_dir.join("regtest").join(".cookie");
+
+    let client = (0..)
+      .find_map(|attempt| {
+        let client = Client::new(
+          &format!("127.0.0.1:{rpc_port}"),
+          Auth::CookieFile(cookie.clone()),
+        )
+        .ok()
+        .filter(|client| client.get_block_count().is_ok());
+
+        if client.is_none() {
+          assert!(attempt < 200, "dogecoind RPC did not respond");
+          thread::sleep(Duration::from_millis(50));
+        }
+
+        client
+      })
+      .unwrap();
+
+    fs::write(
+      tempdir.path().join("bones.toml"),
+      "[chains.regtest]\nbonestones-start-height = 0\n",
+    )
+    .unwrap();
+
+    let mut node = Self {
+      child,
+      client,
+      rpc_port,
+      tempdir,
+      funding: VecDeque::new(),
+    };
+
+    let script_pubkey = Self::script_pubkey();
+
+    for block in node.mine_blocks(FUNDING_OUTPUTS) {
+      let coinbase = &block.txdata[0];
+      let (vout, output) = coinbase
+        .output
+        .iter()
+        .enumerate()
+        .find(|(_, output)| output.script_pubkey == script_pubkey)
+        .unwrap();
+
+      node.funding.push_back((
+        OutPoint {
+          txid: coinbase.txid(),
+          vout: vout.try_into().unwrap(),
+        },
+        output.value,
+      ));
+    }
+
+    node.mine_blocks(COINBASE_MATURITY);
+
+    node
+  }
+
+  /// Anyone-can-spend P2SH script all mined and funding outputs pay to.
+  pub(crate) fn script_pubkey() -> Script {
+    Self::redeem_script().to_p2sh()
+  }
+
+  fn redeem_script() -> Script {
+    script::Builder::new()
+      .push_opcode(opcodes::OP_TRUE)
+      .into_script()
+  }
+
+  /// Spends `outpoint`, which must pay to `Node::script_pubkey`.
+  pub(crate) fn input(outpoint: OutPoint) -> TxIn {
+    TxIn {
+      previous_output: outpoint,
+      script_sig: script::Builder::new()
+        .push_slice(Self::redeem_script().as_bytes())
+        .into_script(),
+      sequence: Sequence::MAX,
+      witness: Witness::new(),
+    }
+  }
+
+  /// Mines `n` blocks and returns them.
+  pub(crate) fn mine_blocks(&self, n: u64) -> Vec<bitcoin::Block> {
+    let address = Address::p2sh(&Self::redeem_script(), Network::Regtest).unwrap();
+
+    self
+      .client
+      .generate_to_address(n, &address)
+      .unwrap()
+      .iter()
+      .map(|hash| self.client.get_block(hash).unwrap())
+      .collect()
+  }
+
+  /// Mines a single block and returns its height.
+  pub(crate) fn mine(&self) -> u64 {
+    self.mine_blocks(1);
+    self.client.get_block_count().unwrap()
+  }
+
+  pub(crate) fn broadcast(&self, input: Vec<TxIn>, output: Vec<TxOut>) -> Txid {
+    self
+      .client
+      .send_raw_transaction(&Transaction {
+        version: 1,
+        lock_time: PackedLockTime::ZERO,
+        input,
+        output,
+      })
+      .unwrap()
+  }
+
+  /// Commits to a Dogecoin inscription in a block of its own and returns the
+  /// input revealing it, spending an output worth `Node::POSTAGE`.
+  ///
+  /// The inscription is pushed onto the script sig, followed by the P2SH
+  /// redeem script, which drops it.
+  pub(crate) fn reveal(
+    &mut self,
+    content_type: &[u8],
+    body: &[u8],
+    fields: &[(i64, &[u8])],
+  ) -> TxIn {
+    let mut builder = script::Builder::new()
+      .push_slice(b"ord")
+      .push_int(1)
+      .push_slice(content_type)
+      .push_int(0)
+      .push_slice(body);
+
+    for (tag, value) in fields {
+      builder = builder.push_int(*tag).push_slice(value);
+    }
+
+    let pushes = 5 + fields.len() * 2;
+
+    let redeem_script = (0..pushes)
+      .fold(script::Builder::new(), |builder, _| {
+        builder.push_opcode(opcodes::all::OP_DROP)
+      })
+      .push_opcode(opcodes::OP_TRUE)
+      .into_script();
+
+    let (outpoint, value) = self.funding.pop_front().expect("all funding outputs spent");
+
+    let change = value - Self::POSTAGE - Self::FEE;
+
+    let commit = self.broadcast(
+      vec![Self::input(outpoint)],
+      vec![
+        TxOut {
+          value: Self::POSTAGE,
+          script_pubkey: redeem_script.to_p2sh(),
+        },
+        TxOut {
+          value: change,
+          script_pubkey: Self::script_pubkey(),
+        },
+      ],
+    );
+
+    self.funding.push_back((
+      OutPoint {
+        txid: commit,
+        vout: 1,
+      },
+      change,
+    ));
+
+    self.mine();
+
+    TxIn {
+      previous_output: OutPoint {
+        txid: commit,
+        vout: 0,
+      },
+      script_sig: builder.push_slice(redeem_script.as_bytes()).into_script(),
+      sequence: Sequence::MAX,
+      witness: Witness::new(),
+    }
+  }
+
+  /// `ord` on regtest with the relic index, using this node and a data dir of
+  /// its own.
+  pub(crate) fn ord(&self, args: &str) -> Command {
+    let mut command = Command::new(executable_path("ord"));
+
+    command
+      .env("ORD_INTEGRATION_TEST", "1")
+      .arg("--chain")
+      .arg("regtest")
+      .arg("--rpc-url")
+      .arg(format!("127.0.0.1:{}", self.rpc_port))
+      .arg("--dogecoin-data-dir")
+      .arg(self.tempdir.path().join("dogecoin"))
+      .arg("--data-dir")
+      .arg(self.tempdir.path().join("ord"))
+      .arg("--bones-config")
+      .arg(self.tempdir.path().join("bones.toml"))
+      .arg("--index-relics")
+      .args(args.split_whitespace());
+
+    command
+  }
+
+  /// Runs `ord server` and waits until it indexed every block of the node.
+  pub(crate) fn serve(&self) -> OrdServer {
+    let port = TcpListener::bind("127.0.0.1:0")
+      .unwrap()
+      .local_addr()
+      .unwrap()
+      .port();
+
+    let child = self
+      .ord(&format!("server --http-port {port} --address 127.0.0.1"))
+      .spawn()
+      .unwrap();
+
+    let server = OrdServer { child, port };
+
+    let block_count = self.client.get_block_count().unwrap() + 1;
+
+    for attempt in 0.. {
+      if let Ok(response) = reqwest::blocking::get(server.url().join("/block-count").unwrap()) {
+        if response.status() == StatusCode::OK
+          && response.text().unwrap().parse::<u64>().unwrap() == block_count
+        {
+          break;
+        }
+      }
+
+      assert!(attempt < 400, "ord server did not index the chain");
+
+      thread::sleep(Duration::from_millis(50));
+    }
+
+    server
+  }
+}
+
+impl Drop for Node {
+  fn drop(&mut self) {
+    self.child.kill().unwrap();
+    self.child.wait().unwrap();
+  }
+}
+
+pub(crate) struct OrdServer {
+  child: Child,
+  port: u16,
+}
+
+impl OrdServer {
+  pub(crate) fn url(&self) -> Url {
+    format!("http://127.0.0.1:{}", self.port).parse().unwrap()
+  }
+
+  pub(crate) fn request(&self, path: &str) -> Response {
+    reqwest::blocking::get(self.url().join(path).unwrap()).unwrap()
+  }
+
+  pub(crate) fn json(&self, path: &str) -> serde_json::Value {
+    let response = self.request(path);
+    assert_eq!(response.status(), StatusCode::OK, "{path}");
+    response.json().unwrap()
+  }
+}
+
+impl Drop for OrdServer {
+  fn drop(&mut self) {
+    self.child.kill().unwrap();
+    self.child.wait().unwrap();
+  }
+}
@@ -11,6 +11,7 @@ use {
   serde::{de::DeserializeOwned, Deserialize},
   std::{
     fs,
+    io::{BufRead, BufReader},
     net::TcpListener,
     path::Path,
     process::{Child, Command, Stdio},
@@ -69,13 +70,17 @@ fn create_wallet(rpc_server: &test_bitcoincore_rpc::Handle) {
     .output::<Create>();
 }
 
+mod bench;
+mod bones;
 mod command_builder;
 mod core;
 mod epochs;
 mod expected;
+mod export;
 mod find;
 mod index;
 mod info;
+mod integration;
 mod list;
 mod parse;
 mod server;
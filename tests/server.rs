@@ -88,6 +88,48 @@ fn inscription_page() {
   );
 }
 
+#[test]
+fn plain_mode_pages_have_no_scripts_or_iframes_and_links_stay_plain() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let Inscribe {
+    inscription,
+    reveal,
+    ..
+  } = inscribe(&rpc_server);
+
+  let server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  server.assert_response_regex(
+    format!("/shibescription/{inscription}?plain=true"),
+    format!(
+      ".*<a href=/[?]plain=true>.*
+.*<input type=hidden name=plain value=true>.*
+<h1>Shibescription 0</h1>
+.*<a href=/content/{inscription}[?]plain=true>{inscription}</a>.*
+  <dd><a href=/block/2[?]plain=true>2</a></dd>.*
+  <dd><a class=monospace href=/output/{reveal}:0[?]plain=true>{reveal}:0</a></dd>.*",
+    ),
+  );
+
+  let html = server
+    .request(format!("/shibescription/{inscription}?plain=true"))
+    .text()
+    .unwrap();
+
+  assert!(!html.contains("<script"));
+  assert!(!html.contains("<iframe"));
+
+  let html = server
+    .request(format!("/shibescription/{inscription}"))
+    .text()
+    .unwrap();
+
+  assert!(html.contains("<script"));
+  assert!(html.contains("<a href=/block/2>2</a>"));
+}
+
 #[test]
 fn inscription_appears_on_reveal_transaction_page() {
   let rpc_server = test_bitcoincore_rpc::spawn();
@@ -189,6 +231,101 @@ fn inscription_content() {
   assert_eq!(response.bytes().unwrap(), "FOO");
 }
 
+#[test]
+fn raw_transactions_are_served_with_etag() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  let Inscribe { reveal, .. } = inscribe(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_args(&rpc_server, &["--index-raw-transactions"]);
+
+  let response = server.request(format!("/tx/{reveal}/raw"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let etag = response.headers().get("etag").unwrap().clone();
+  assert_eq!(etag, format!("\"{reveal}\"").as_str());
+
+  let tx = bitcoin::consensus::encode::deserialize::<bitcoin::Transaction>(
+    &hex::decode(response.text().unwrap()).unwrap(),
+  )
+  .unwrap();
+  assert_eq!(tx.txid(), reveal);
+
+  let response = reqwest::blocking::Client::new()
+    .get(server.url().join(&format!("/tx/{reveal}/raw")).unwrap())
+    .header("if-none-match", etag)
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+  // unknown transactions are not found, even if their etag matches
+  let txid = "0000000000000000000000000000000000000000000000000000000000000000";
+
+  let response = reqwest::blocking::Client::new()
+    .get(server.url().join(&format!("/tx/{txid}/raw")).unwrap())
+    .header("if-none-match", format!("\"{txid}\""))
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn inscriptions_can_be_resolved_in_bulk() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  let Inscribe { inscription, .. } = inscribe(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  // wait for the server to index the inscription
+  server.request("/");
+
+  let unknown = format!("{}i0", "0".repeat(64));
+
+  let response = reqwest::blocking::Client::new()
+    .post(server.url().join("/inscriptions/resolve").unwrap())
+    .json(&[&inscription, &unknown])
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  assert_eq!(
+    response.json::<serde_json::Value>().unwrap(),
+    serde_json::json!([
+      {
+        "id": inscription,
+        "delegate": null,
+        "content_type": "text/plain;charset=utf-8",
+        "content_length": 3,
+        "body": true,
+      },
+      null,
+    ])
+  );
+
+  let response = reqwest::blocking::Client::new()
+    .post(server.url().join("/inscriptions/resolve").unwrap())
+    .json(&vec![&inscription; 1001])
+    .send()
+    .unwrap();
+
+  assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
 #[test]
 fn home_page_includes_latest_inscriptions() {
   let rpc_server = test_bitcoincore_rpc::spawn();
@@ -298,3 +435,424 @@ fn expected_sat_time_is_rounded() {
     r".*<dt>timestamp</dt><dd><time>.* \d+:\d+:\d+ UTC</time> \(expected\)</dd>.*",
   );
 }
+
+#[test]
+fn unversioned_json_paths_are_deprecated() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  let response = server.request("/parse/relic-id/1:1");
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(response.headers().get("deprecation").unwrap(), "true");
+  assert_eq!(
+    response.headers().get("link").unwrap(),
+    "</v1/parse/relic-id/1:1>; rel=\"successor-version\""
+  );
+
+  let response = server.request("/v1/parse/relic-id/1:1");
+  assert_eq!(response.status(), StatusCode::OK);
+  assert!(response.headers().get("deprecation").is_none());
+  assert!(response.headers().get("sunset").is_none());
+
+  let response = server.request("/v1/status");
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(response.text().unwrap(), "OK");
+}
+
+#[test]
+fn keepsakes_can_be_decoded() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  let decode = |body: &str| {
+    let response = reqwest::blocking::Client::new()
+      .post(server.url().join("/decode/keepsake").unwrap())
+      .body(body.to_string())
+      .send()
+      .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    serde_json::from_str::<serde_json::Value>(&response.text().unwrap()).unwrap()
+  };
+
+  // OP_RETURN OP_PUSHNUM_14 with a pointer to output 1
+  let decoded = decode("6a5e020401");
+  assert_eq!(decoded["txid"], serde_json::Value::Null);
+  assert_eq!(decoded["keepsake"]["pointer"], 1);
+  assert_eq!(decoded["cenotaph"], serde_json::Value::Null);
+
+  // opcodes are not allowed after the protocol identifier
+  let decoded = decode("6a5e51");
+  assert_eq!(decoded["keepsake"], serde_json::Value::Null);
+  assert_eq!(decoded["cenotaph"]["flaw"], "Opcode");
+
+  // not a keepsake
+  let decoded = decode("6a");
+  assert_eq!(decoded["keepsake"], serde_json::Value::Null);
+  assert_eq!(decoded["cenotaph"], serde_json::Value::Null);
+
+  let response = reqwest::blocking::Client::new()
+    .post(server.url().join("/decode/keepsake").unwrap())
+    .body("foo")
+    .send()
+    .unwrap();
+  assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn keepsake_size_and_fee_can_be_estimated() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  let estimate = |body: serde_json::Value| {
+    reqwest::blocking::Client::new()
+      .post(server.url().join("/estimate/keepsake").unwrap())
+      .json(&body)
+      .send()
+      .unwrap()
+  };
+
+  let response = estimate(serde_json::json!({
+    "keepsake": { "pointer": 1 },
+    "outputs": 2,
+    "fee_rate": 2.0,
+  }));
+  assert_eq!(response.status(), StatusCode::OK);
+  let estimated = response.json::<serde_json::Value>().unwrap();
+  assert_eq!(estimated["script"], "6a5e020401");
+  assert_eq!(estimated["script_size"], 5);
+  assert_eq!(estimated["standard"], true);
+  // value, script length and script
+  assert_eq!(estimated["output_size"], 8 + 1 + 5);
+  assert_eq!(estimated["fee"], 2 * (8 + 1 + 5));
+  assert_eq!(estimated["round_trips"], true);
+  assert_eq!(estimated["keepsake"]["pointer"], 1);
+
+  // the transaction has no output 5, so the indexer won't read the keepsake
+  // back as requested
+  let response = estimate(serde_json::json!({
+    "keepsake": { "pointer": 5 },
+    "outputs": 2,
+    "fee_rate": 1.0,
+  }));
+  assert_eq!(response.status(), StatusCode::OK);
+  let estimated = response.json::<serde_json::Value>().unwrap();
+  assert_eq!(estimated["round_trips"], false);
+  assert_eq!(estimated["keepsake"], serde_json::Value::Null);
+
+  let response = estimate(serde_json::json!({
+    "keepsake": {},
+    "outputs": 1,
+    "fee_rate": -1.0,
+  }));
+  assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn responses_include_index_tip() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let block = rpc_server.mine_blocks(1).pop().unwrap();
+
+  let server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  let response = server.request("/status");
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(response.headers().get("x-index-height").unwrap(), "1");
+  assert_eq!(
+    response.headers().get("x-index-block-hash").unwrap(),
+    block.block_hash().to_string().as_str()
+  );
+}
+
+#[test]
+fn security_headers_are_configurable() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  rpc_server.mine_blocks(1);
+
+  let client = reqwest::blocking::Client::new();
+
+  let server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  let response = client
+    .get(server.url().join("/status").unwrap())
+    .header("origin", "https://wallet.example")
+    .send()
+    .unwrap();
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    response
+      .headers()
+      .get("access-control-allow-origin")
+      .unwrap(),
+    "*"
+  );
+  assert_eq!(
+    response.headers().get("content-security-policy").unwrap(),
+    "default-src 'self'"
+  );
+  assert!(response.headers().contains_key("strict-transport-security"));
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &[],
+    &[
+      "--cors-origin https://wallet.example",
+      "--allow-post-cors",
+      "--disable-hsts",
+      "--csp-extra upgrade-insecure-requests",
+    ],
+  );
+
+  let response = client
+    .get(server.url().join("/status").unwrap())
+    .header("origin", "https://wallet.example")
+    .send()
+    .unwrap();
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    response
+      .headers()
+      .get("access-control-allow-origin")
+      .unwrap(),
+    "https://wallet.example"
+  );
+  assert_eq!(
+    response.headers().get("content-security-policy").unwrap(),
+    "default-src 'self'; upgrade-insecure-requests"
+  );
+  assert!(!response.headers().contains_key("strict-transport-security"));
+
+  let response = client
+    .request(
+      reqwest::Method::OPTIONS,
+      server.url().join("/outputs").unwrap(),
+    )
+    .header("origin", "https://wallet.example")
+    .header("access-control-request-method", "POST")
+    .header("access-control-request-headers", "content-type")
+    .send()
+    .unwrap();
+  assert_eq!(response.status(), StatusCode::OK);
+  assert!(response
+    .headers()
+    .get("access-control-allow-methods")
+    .unwrap()
+    .to_str()
+    .unwrap()
+    .contains("POST"));
+
+  let response = client
+    .get(server.url().join("/status").unwrap())
+    .header("origin", "https://elsewhere.example")
+    .send()
+    .unwrap();
+  assert!(!response
+    .headers()
+    .contains_key("access-control-allow-origin"));
+}
+
+#[test]
+fn recursive_block_endpoints() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let block = rpc_server.mine_blocks(1).pop().unwrap();
+
+  let server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  let response = server.request("/r/blockheight");
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(response.text().unwrap(), "1");
+
+  let response = server.request("/r/blockhash/1");
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    response.text().unwrap(),
+    format!("\"{}\"", block.block_hash())
+  );
+
+  let response = server.request("/r/blocktime");
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(response.text().unwrap(), block.header.time.to_string());
+
+  assert_eq!(
+    server.request("/r/blockhash/2").status(),
+    StatusCode::NOT_FOUND
+  );
+}
+
+#[test]
+fn new_inscriptions_are_streamed() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let server = TestServer::spawn_with_args(&rpc_server, &[]);
+
+  let stream = reqwest::blocking::Client::builder()
+    .timeout(Duration::from_secs(30))
+    .build()
+    .unwrap()
+    .get(server.url().join("/sse/inscriptions").unwrap())
+    .send()
+    .unwrap();
+
+  assert_eq!(stream.status(), StatusCode::OK);
+
+  let Inscribe { inscription, .. } = inscribe(&rpc_server);
+
+  // wait for the server to index the reveal
+  server.request("/status");
+
+  let data = BufReader::new(stream)
+    .lines()
+    .map(Result::unwrap)
+    .find_map(|line| {
+      line
+        .strip_prefix("data:")
+        .map(|data| data.trim().to_string())
+    })
+    .unwrap();
+
+  let event = serde_json::from_str::<serde_json::Value>(&data).unwrap();
+
+  assert_eq!(event["inscription_id"], inscription);
+}
+
+#[test]
+fn webhooks_can_be_registered_and_removed() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--admin-token", "foo"]);
+
+  let client = reqwest::blocking::Client::new();
+
+  let register = |token: &str| {
+    client
+      .post(server.url().join("/webhooks").unwrap())
+      .bearer_auth(token)
+      .header(reqwest::header::CONTENT_TYPE, "application/json")
+      .body(r#"{"url": "https://example.com/hook", "bone": "FOO•BAR"}"#)
+      .send()
+      .unwrap()
+  };
+
+  assert_eq!(register("bar").status(), StatusCode::UNAUTHORIZED);
+
+  let response = register("foo");
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(
+    response.text().unwrap(),
+    r#"{"id":0,"url":"https://example.com/hook","bone":"FOO•BAR"}"#
+  );
+
+  let list = || {
+    client
+      .get(server.url().join("/webhooks").unwrap())
+      .bearer_auth("foo")
+      .send()
+      .unwrap()
+      .text()
+      .unwrap()
+  };
+
+  assert_eq!(
+    list(),
+    r#"[{"id":0,"url":"https://example.com/hook","bone":"FOO•BAR"}]"#
+  );
+
+  let remove = || {
+    client
+      .delete(server.url().join("/webhooks/0").unwrap())
+      .bearer_auth("foo")
+      .send()
+      .unwrap()
+      .status()
+  };
+
+  assert_eq!(remove(), StatusCode::NO_CONTENT);
+  assert_eq!(remove(), StatusCode::NOT_FOUND);
+  assert_eq!(list(), "[]");
+}
+
+#[test]
+fn addresses_can_be_labeled() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let server = TestServer::spawn_with_server_args(&rpc_server, &[], &["--admin-token", "foo"]);
+
+  let client = reqwest::blocking::Client::new();
+
+  let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+
+  let label = |token: &str| {
+    client
+      .put(
+        server
+          .url()
+          .join(&format!("/labels/address/{}", address.to_uppercase()))
+          .unwrap(),
+      )
+      .bearer_auth(token)
+      .header(reqwest::header::CONTENT_TYPE, "application/json")
+      .body(r#"{"label": "Burn", "tags": ["burn"]}"#)
+      .send()
+      .unwrap()
+  };
+
+  assert_eq!(label("bar").status(), StatusCode::UNAUTHORIZED);
+
+  let response = label("foo");
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let labeled = format!(r#"{{"address":"{address}","label":"Burn","tags":["burn"]}}"#);
+
+  assert_eq!(response.text().unwrap(), labeled);
+
+  assert_eq!(
+    reqwest::blocking::get(server.url().join("/labels").unwrap())
+      .unwrap()
+      .text()
+      .unwrap(),
+    format!("[{labeled}]")
+  );
+
+  assert_eq!(
+    reqwest::blocking::get(server.url().join(&format!("/address/{address}")).unwrap())
+      .unwrap()
+      .text()
+      .unwrap(),
+    r#"[{"outpoint":[],"label":{"label":"Burn","tags":["burn"]}}]"#
+  );
+
+  let remove = || {
+    client
+      .delete(
+        server
+          .url()
+          .join(&format!("/labels/address/{address}"))
+          .unwrap(),
+      )
+      .bearer_auth("foo")
+      .send()
+      .unwrap()
+      .status()
+  };
+
+  assert_eq!(remove(), StatusCode::NO_CONTENT);
+  assert_eq!(remove(), StatusCode::NOT_FOUND);
+
+  assert_eq!(
+    reqwest::blocking::get(
+      server
+        .url()
+        .join(&format!("/labels/address/{address}"))
+        .unwrap()
+    )
+    .unwrap()
+    .status(),
+    StatusCode::NOT_FOUND
+  );
+}
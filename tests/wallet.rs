@@ -4,6 +4,7 @@ mod balance;
 mod create;
 mod inscribe;
 mod inscriptions;
+mod lock_utxo;
 mod outputs;
 mod receive;
 mod restore;
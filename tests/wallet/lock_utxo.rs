@@ -0,0 +1,76 @@
+use {
+  super::*,
+  ord::subcommand::wallet::{lock_utxo, locked_utxos, unlock_utxo},
+};
+
+#[test]
+fn locked_outputs_are_listed_until_unlocked() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let coinbase_tx = &rpc_server.mine_blocks(1)[0].txdata[0];
+  let outpoint = OutPoint::new(coinbase_tx.txid(), 0);
+
+  assert!(CommandBuilder::new("wallet locked-utxos")
+    .rpc_server(&rpc_server)
+    .output::<Vec<locked_utxos::Output>>()
+    .is_empty());
+
+  let output = CommandBuilder::new(format!("wallet lock-utxo {outpoint}"))
+    .rpc_server(&rpc_server)
+    .output::<lock_utxo::Output>();
+
+  assert_eq!(output.output, outpoint);
+  assert!(output.locked);
+
+  let locked = CommandBuilder::new("wallet locked-utxos")
+    .rpc_server(&rpc_server)
+    .output::<Vec<locked_utxos::Output>>();
+
+  assert_eq!(locked.len(), 1);
+  assert_eq!(locked[0].output, outpoint);
+
+  assert_eq!(
+    CommandBuilder::new(format!("wallet unlock-utxo {outpoint}"))
+      .rpc_server(&rpc_server)
+      .output::<unlock_utxo::Output>()
+      .output,
+    outpoint
+  );
+
+  assert!(CommandBuilder::new("wallet locked-utxos")
+    .rpc_server(&rpc_server)
+    .output::<Vec<locked_utxos::Output>>()
+    .is_empty());
+}
+
+#[test]
+fn inscribed_outputs_are_locked() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let Inscribe { reveal, .. } = inscribe(&rpc_server);
+
+  let locked = CommandBuilder::new("wallet locked-utxos")
+    .rpc_server(&rpc_server)
+    .output::<Vec<locked_utxos::Output>>();
+
+  assert_eq!(locked.len(), 1);
+  assert_eq!(locked[0].output, OutPoint::new(reveal, 0));
+}
+
+#[test]
+fn outputs_not_in_wallet_cannot_be_locked() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  CommandBuilder::new(format!("wallet lock-utxo {}", OutPoint::null()))
+    .rpc_server(&rpc_server)
+    .expected_stderr(format!(
+      "error: output {} not in wallet\n",
+      OutPoint::null()
+    ))
+    .expected_exit_code(1)
+    .run();
+}